@@ -1,5 +1,6 @@
 fn main() {
     println!("cargo:rerun-if-changed=world-src/");
+    println!("cargo:rerun-if-changed=shim/");
 
     let mut build = cc::Build::new();
 
@@ -26,6 +27,7 @@ fn main() {
         "world-src/stonemask.cpp",
         "world-src/synthesis.cpp",
         "world-src/synthesisrealtime.cpp",
+        "shim/synthesizer.cpp",
     ];
 
     for source in &sources {
@@ -0,0 +1,15 @@
+// Allocation and buffer access for WorldSynthesizer, whose layout (nested
+// FFT plans, RNG state) is not mirrored on the Rust side.
+#include "world/synthesisrealtime.h"
+
+extern "C" {
+
+WorldSynthesizer *NewWorldSynthesizer() { return new WorldSynthesizer(); }
+
+void DeleteWorldSynthesizer(WorldSynthesizer *synth) { delete synth; }
+
+const double *GetSynthesizerBuffer(const WorldSynthesizer *synth) {
+  return synth->buffer;
+}
+
+}  // extern "C"
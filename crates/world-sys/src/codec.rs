@@ -0,0 +1,284 @@
+use crate::{
+    CodeAperiodicity, CodeSpectralEnvelope, DecodeAperiodicity, DecodeSpectralEnvelope,
+    F0Candidate, GetNumberOfAperiodicities, WorldError, WorldParams,
+};
+use std::os::raw::c_int;
+
+/// Floor applied before coding: the coders take the log of every bin, and
+/// digital silence analyzes to exact zeros (WORLD's `kMySafeGuardMinimum`).
+const CODING_FLOOR: f64 = 1e-12;
+
+/// [`WorldParams`] with the spectrogram stored as `dimensions` mel-cepstral
+/// coefficients per frame and the aperiodicity as a few band levels (dB),
+/// instead of `fft_size / 2 + 1` bins each.
+///
+/// Lossy: [`CodedWorldParams::decode`] gives back a smoothed envelope and
+/// band-interpolated aperiodicity. f0, the candidate lattice and the
+/// voicing confidence are kept as they are.
+#[derive(Debug, Clone)]
+pub struct CodedWorldParams {
+    pub f0: Vec<f64>,
+    pub temporal_positions: Vec<f64>,
+    /// Coded spectral envelope: frame_count rows of `dimensions` values.
+    pub coded_spectrogram: Vec<Vec<f64>>,
+    /// Coded aperiodicity: frame_count rows of
+    /// [`aperiodicity_bands`]`(sample_rate)` values.
+    pub coded_aperiodicity: Vec<Vec<f64>>,
+    pub fft_size: usize,
+    pub frame_period: f64,
+    /// Sample rate the analysis was made at; the coding depends on it.
+    pub sample_rate: i32,
+    pub dimensions: usize,
+    pub f0_candidates: Option<Vec<Vec<F0Candidate>>>,
+    pub voicing: Vec<f32>,
+}
+
+/// Bands WORLD codes aperiodicity into at `sample_rate`: one per 3 kHz up
+/// to 15 kHz, stopping at least 3 kHz below Nyquist (1 at 16 kHz, 5 at
+/// 44.1 kHz). 0 at 6 kHz and below, where nothing can be coded.
+pub fn aperiodicity_bands(sample_rate: i32) -> usize {
+    if sample_rate <= 0 {
+        return 0;
+    }
+    unsafe { GetNumberOfAperiodicities(sample_rate) }.max(0) as usize
+}
+
+/// Code full-resolution aperiodicity rows (`fft_size / 2 + 1` bins, linear
+/// `0..=1`) into [`aperiodicity_bands`] levels per frame.
+///
+/// # Errors
+///
+/// Returns an error if `sample_rate` has no bands or a row has the wrong
+/// width.
+pub fn code_aperiodicity(
+    aperiodicity: &[Vec<f64>],
+    sample_rate: i32,
+    fft_size: usize,
+) -> Result<Vec<Vec<f64>>, WorldError> {
+    let bands = checked_bands(sample_rate)?;
+    check_rows("aperiodicity", aperiodicity, fft_size / 2 + 1)?;
+    let floored = floored_rows(aperiodicity);
+    let ap_ptrs: Vec<*const f64> = floored.iter().map(|row| row.as_ptr()).collect();
+    let mut coded: Vec<Vec<f64>> = (0..aperiodicity.len()).map(|_| vec![0.0; bands]).collect();
+    let mut coded_ptrs: Vec<*mut f64> = coded.iter_mut().map(|row| row.as_mut_ptr()).collect();
+    unsafe {
+        CodeAperiodicity(
+            ap_ptrs.as_ptr(),
+            aperiodicity.len() as c_int,
+            sample_rate,
+            fft_size as c_int,
+            coded_ptrs.as_mut_ptr(),
+        );
+    }
+    Ok(coded)
+}
+
+/// Expand coded aperiodicity back to `fft_size / 2 + 1` bins per frame.
+///
+/// # Errors
+///
+/// Returns an error if `sample_rate` has no bands or a row has the wrong
+/// width.
+pub fn decode_aperiodicity(
+    coded: &[Vec<f64>],
+    sample_rate: i32,
+    fft_size: usize,
+) -> Result<Vec<Vec<f64>>, WorldError> {
+    let bands = checked_bands(sample_rate)?;
+    check_rows("coded aperiodicity", coded, bands)?;
+    let coded_ptrs: Vec<*const f64> = coded.iter().map(|row| row.as_ptr()).collect();
+    let mut aperiodicity: Vec<Vec<f64>> = (0..coded.len())
+        .map(|_| vec![0.0; fft_size / 2 + 1])
+        .collect();
+    let mut ap_ptrs: Vec<*mut f64> = aperiodicity
+        .iter_mut()
+        .map(|row| row.as_mut_ptr())
+        .collect();
+    unsafe {
+        DecodeAperiodicity(
+            coded_ptrs.as_ptr(),
+            coded.len() as c_int,
+            sample_rate,
+            fft_size as c_int,
+            ap_ptrs.as_mut_ptr(),
+        );
+    }
+    for value in aperiodicity.iter_mut().flatten() {
+        if !value.is_finite() {
+            *value = 1.0 - CODING_FLOOR; // fully aperiodic, as WORLD initializes it
+        }
+    }
+    Ok(aperiodicity)
+}
+
+impl WorldParams {
+    /// Compress the spectrogram to `dimensions` coefficients per frame
+    /// (WORLD's `CodeSpectralEnvelope`; 40–60 is typical for speech) and the
+    /// aperiodicity to [`aperiodicity_bands`] levels (`CodeAperiodicity`).
+    /// `sample_rate` must be the rate the params were analyzed at.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the params fail [`WorldParams::validate`],
+    /// `sample_rate` is too low to code, or `dimensions` is not in
+    /// `1..=fft_size / 2`.
+    pub fn encode(
+        &self,
+        sample_rate: i32,
+        dimensions: usize,
+    ) -> Result<CodedWorldParams, WorldError> {
+        self.validate()?;
+        checked_bands(sample_rate)?;
+        check_dimensions(dimensions, self.fft_size)?;
+
+        // Floored copy: the coder reads the rows through const pointers but
+        // takes their log.
+        let floored = floored_rows(&self.spectrogram);
+        let sp_ptrs: Vec<*const f64> = floored.iter().map(|row| row.as_ptr()).collect();
+        let mut coded: Vec<Vec<f64>> = (0..self.f0.len()).map(|_| vec![0.0; dimensions]).collect();
+        let mut coded_ptrs: Vec<*mut f64> = coded.iter_mut().map(|row| row.as_mut_ptr()).collect();
+        unsafe {
+            CodeSpectralEnvelope(
+                sp_ptrs.as_ptr(),
+                self.f0.len() as c_int,
+                sample_rate,
+                self.fft_size as c_int,
+                dimensions as c_int,
+                coded_ptrs.as_mut_ptr(),
+            );
+        }
+
+        Ok(CodedWorldParams {
+            f0: self.f0.clone(),
+            temporal_positions: self.temporal_positions.clone(),
+            coded_spectrogram: coded,
+            coded_aperiodicity: code_aperiodicity(&self.aperiodicity, sample_rate, self.fft_size)?,
+            fft_size: self.fft_size,
+            frame_period: self.frame_period,
+            sample_rate,
+            dimensions,
+            f0_candidates: self.f0_candidates.clone(),
+            voicing: self.voicing.clone(),
+        })
+    }
+}
+
+impl CodedWorldParams {
+    /// Expand the coded envelope and aperiodicity back into full
+    /// [`WorldParams`], ready for `synthesize` at
+    /// [`CodedWorldParams::sample_rate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fields are inconsistent (row counts or
+    /// widths, `dimensions`, sample rate), e.g. after editing them by hand.
+    pub fn decode(&self) -> Result<WorldParams, WorldError> {
+        let frame_count = self.f0.len();
+        checked_bands(self.sample_rate)?;
+        check_dimensions(self.dimensions, self.fft_size)?;
+        check_rows(
+            "coded spectrogram",
+            &self.coded_spectrogram,
+            self.dimensions,
+        )?;
+        if self.coded_spectrogram.len() != frame_count
+            || self.coded_aperiodicity.len() != frame_count
+        {
+            return Err(WorldError::InvalidParams(format!(
+                "coded rows ({} spectrogram, {} aperiodicity) != f0 length ({frame_count})",
+                self.coded_spectrogram.len(),
+                self.coded_aperiodicity.len(),
+            )));
+        }
+
+        let sp_width = self.fft_size / 2 + 1;
+        let coded_ptrs: Vec<*const f64> = self
+            .coded_spectrogram
+            .iter()
+            .map(|row| row.as_ptr())
+            .collect();
+        let mut spectrogram: Vec<Vec<f64>> =
+            (0..frame_count).map(|_| vec![0.0; sp_width]).collect();
+        let mut sp_ptrs: Vec<*mut f64> =
+            spectrogram.iter_mut().map(|row| row.as_mut_ptr()).collect();
+        unsafe {
+            DecodeSpectralEnvelope(
+                coded_ptrs.as_ptr(),
+                frame_count as c_int,
+                self.sample_rate,
+                self.fft_size as c_int,
+                self.dimensions as c_int,
+                sp_ptrs.as_mut_ptr(),
+            );
+        }
+        for value in spectrogram.iter_mut().flatten() {
+            if !value.is_finite() {
+                *value = CODING_FLOOR;
+            }
+        }
+
+        let params = WorldParams {
+            f0: self.f0.clone(),
+            temporal_positions: self.temporal_positions.clone(),
+            spectrogram,
+            aperiodicity: decode_aperiodicity(
+                &self.coded_aperiodicity,
+                self.sample_rate,
+                self.fft_size,
+            )?,
+            fft_size: self.fft_size,
+            frame_period: self.frame_period,
+            sample_rate: self.sample_rate,
+            f0_candidates: self.f0_candidates.clone(),
+            voicing: self.voicing.clone(),
+        };
+        params.validate()?;
+        Ok(params)
+    }
+}
+
+fn checked_bands(sample_rate: i32) -> Result<usize, WorldError> {
+    match aperiodicity_bands(sample_rate) {
+        0 => Err(WorldError::InvalidParams(format!(
+            "sample_rate {sample_rate} is too low for WORLD's codec (needs > 6000 Hz)",
+        ))),
+        bands => Ok(bands),
+    }
+}
+
+fn check_dimensions(dimensions: usize, fft_size: usize) -> Result<(), WorldError> {
+    if dimensions == 0 || dimensions > fft_size / 2 {
+        return Err(WorldError::InvalidParams(format!(
+            "dimensions must be in 1..={}, got {dimensions}",
+            fft_size / 2,
+        )));
+    }
+    Ok(())
+}
+
+fn check_rows(what: &str, rows: &[Vec<f64>], width: usize) -> Result<(), WorldError> {
+    match rows.iter().position(|row| row.len() != width) {
+        Some(i) => Err(WorldError::InvalidParams(format!(
+            "{what}[{i}] width ({}) != {width}",
+            rows[i].len(),
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Copy with every bin at least [`CODING_FLOOR`] (non-finite bins too).
+fn floored_rows(rows: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .map(|&v| {
+                    if v.is_finite() {
+                        v.max(CODING_FLOOR)
+                    } else {
+                        CODING_FLOOR
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
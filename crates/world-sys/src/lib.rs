@@ -1,4 +1,12 @@
+mod codec;
+mod morph;
+mod params_file;
+mod realtime;
 mod safe;
+pub use codec::*;
+pub use morph::*;
+pub use params_file::*;
+pub use realtime::*;
 pub use safe::*;
 
 use std::mem::MaybeUninit;
@@ -97,6 +105,46 @@ extern "C" {
     );
 }
 
+// --- Codec (spectral envelope and aperiodicity compression) ---
+
+extern "C" {
+    pub(crate) fn GetNumberOfAperiodicities(fs: c_int) -> c_int;
+
+    pub(crate) fn CodeAperiodicity(
+        aperiodicity: *const *const f64,
+        f0_length: c_int,
+        fs: c_int,
+        fft_size: c_int,
+        coded_aperiodicity: *mut *mut f64,
+    );
+
+    pub(crate) fn DecodeAperiodicity(
+        coded_aperiodicity: *const *const f64,
+        f0_length: c_int,
+        fs: c_int,
+        fft_size: c_int,
+        aperiodicity: *mut *mut f64,
+    );
+
+    pub(crate) fn CodeSpectralEnvelope(
+        spectrogram: *const *const f64,
+        f0_length: c_int,
+        fs: c_int,
+        fft_size: c_int,
+        number_of_dimensions: c_int,
+        coded_spectral_envelope: *mut *mut f64,
+    );
+
+    pub(crate) fn DecodeSpectralEnvelope(
+        coded_spectral_envelope: *const *const f64,
+        f0_length: c_int,
+        fs: c_int,
+        fft_size: c_int,
+        number_of_dimensions: c_int,
+        spectrogram: *mut *mut f64,
+    );
+}
+
 // --- Synthesis ---
 
 extern "C" {
@@ -113,6 +161,48 @@ extern "C" {
     );
 }
 
+// --- Realtime synthesis ---
+
+/// Opaque `WorldSynthesizer`; only handled through pointers from
+/// `NewWorldSynthesizer` (shim/synthesizer.cpp).
+#[repr(C)]
+pub(crate) struct WorldSynthesizer {
+    _private: [u8; 0],
+}
+
+extern "C" {
+    pub(crate) fn NewWorldSynthesizer() -> *mut WorldSynthesizer;
+
+    pub(crate) fn DeleteWorldSynthesizer(synth: *mut WorldSynthesizer);
+
+    pub(crate) fn GetSynthesizerBuffer(synth: *const WorldSynthesizer) -> *const f64;
+
+    pub(crate) fn InitializeSynthesizer(
+        fs: c_int,
+        frame_period: f64,
+        fft_size: c_int,
+        buffer_size: c_int,
+        number_of_pointers: c_int,
+        synth: *mut WorldSynthesizer,
+    );
+
+    pub(crate) fn AddParameters(
+        f0: *mut f64,
+        f0_length: c_int,
+        spectrogram: *mut *mut f64,
+        aperiodicity: *mut *mut f64,
+        synth: *mut WorldSynthesizer,
+    ) -> c_int;
+
+    pub(crate) fn RefreshSynthesizer(synth: *mut WorldSynthesizer);
+
+    pub(crate) fn DestroySynthesizer(synth: *mut WorldSynthesizer);
+
+    pub(crate) fn IsLocked(synth: *mut WorldSynthesizer) -> c_int;
+
+    pub(crate) fn Synthesis2(synth: *mut WorldSynthesizer) -> c_int;
+}
+
 // --- Helpers ---
 
 /// Initialize a WORLD option struct via its C initializer function.
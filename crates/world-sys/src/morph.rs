@@ -0,0 +1,161 @@
+use crate::{WorldError, WorldParams, SANITIZED_SPECTRUM_BIN};
+
+/// Blend two analyses of the same material frame by frame: `alpha` 0 is
+/// `a`, 1 is `b`. The shorter analysis is first stretched linearly to the
+/// longer one's frame count, so both takes line up start to end (no
+/// dynamic time warping). f0 is interpolated in log Hz, the spectrogram in
+/// log power and the aperiodicity linearly. A frame unvoiced on one side
+/// takes the other side's f0 while `alpha` leans towards that side, and is
+/// unvoiced otherwise. The result has voicing only when both sides have it
+/// and no candidate lattice.
+///
+/// # Errors
+///
+/// Returns [`WorldError::InvalidParams`] if either side fails
+/// [`WorldParams::validate`], their `fft_size`, `frame_period` or
+/// `sample_rate` differ (analyses made with other settings or at another
+/// rate), or `alpha` is not in `0.0..=1.0`.
+pub fn morph(a: &WorldParams, b: &WorldParams, alpha: f64) -> Result<WorldParams, WorldError> {
+    a.validate()?;
+    b.validate()?;
+    if !(0.0..=1.0).contains(&alpha) {
+        return Err(WorldError::InvalidParams(format!(
+            "morph alpha must be in 0..=1, got {alpha}"
+        )));
+    }
+    if a.fft_size != b.fft_size {
+        return Err(WorldError::InvalidParams(format!(
+            "can't morph fft_size {} into fft_size {}",
+            b.fft_size, a.fft_size,
+        )));
+    }
+    if a.frame_period != b.frame_period {
+        return Err(WorldError::InvalidParams(format!(
+            "can't morph frame_period {} ms into frame_period {} ms",
+            b.frame_period, a.frame_period,
+        )));
+    }
+    if a.sample_rate != b.sample_rate {
+        return Err(WorldError::InvalidParams(format!(
+            "can't morph sample_rate {} Hz into sample_rate {} Hz",
+            b.sample_rate, a.sample_rate,
+        )));
+    }
+    let frames = a.f0.len().max(b.f0.len());
+    let (a_f0, b_f0) = (resample_f0(&a.f0, frames), resample_f0(&b.f0, frames));
+    let (a_sp, b_sp) = (
+        resample_rows(&a.spectrogram, frames),
+        resample_rows(&b.spectrogram, frames),
+    );
+    let (a_ap, b_ap) = (
+        resample_rows(&a.aperiodicity, frames),
+        resample_rows(&b.aperiodicity, frames),
+    );
+    let lerp = |x: f64, y: f64| x + (y - x) * alpha;
+    let log_lerp = |x: f64, y: f64| {
+        let floor = SANITIZED_SPECTRUM_BIN;
+        lerp(x.max(floor).ln(), y.max(floor).ln()).exp()
+    };
+
+    let f0 = a_f0
+        .iter()
+        .zip(&b_f0)
+        .map(|(&x, &y)| match (x > 0.0, y > 0.0) {
+            (true, true) => log_lerp(x, y),
+            (true, false) if alpha <= 0.5 => x,
+            (false, true) if alpha >= 0.5 => y,
+            _ => 0.0,
+        })
+        .collect();
+    let blend_rows =
+        |a: &[Vec<f64>], b: &[Vec<f64>], blend: &dyn Fn(f64, f64) -> f64| -> Vec<Vec<f64>> {
+            a.iter()
+                .zip(b)
+                .map(|(x, y)| x.iter().zip(y).map(|(&x, &y)| blend(x, y)).collect())
+                .collect()
+        };
+    let voicing = if a.voicing.is_empty() || b.voicing.is_empty() {
+        Vec::new()
+    } else {
+        let widen = |v: &[f32]| v.iter().map(|&c| f64::from(c)).collect::<Vec<_>>();
+        let (a_v, b_v) = (
+            resample(&widen(&a.voicing), frames),
+            resample(&widen(&b.voicing), frames),
+        );
+        a_v.iter()
+            .zip(&b_v)
+            .map(|(&x, &y)| lerp(x, y) as f32)
+            .collect()
+    };
+    let morphed = WorldParams {
+        f0,
+        temporal_positions: (0..frames)
+            .map(|i| i as f64 * a.frame_period / 1000.0)
+            .collect(),
+        spectrogram: blend_rows(&a_sp, &b_sp, &log_lerp),
+        aperiodicity: blend_rows(&a_ap, &b_ap, &lerp),
+        fft_size: a.fft_size,
+        frame_period: a.frame_period,
+        sample_rate: a.sample_rate,
+        f0_candidates: None,
+        voicing,
+    };
+    morphed.validate()?;
+    Ok(morphed)
+}
+
+/// Where output frame `i` of `frames` falls in an input of `len` frames:
+/// the frame before it and the fraction of the way to the next.
+fn source_position(i: usize, len: usize, frames: usize) -> (usize, usize, f64) {
+    if len <= 1 || frames <= 1 {
+        return (0, 0, 0.0);
+    }
+    let t = i as f64 * (len - 1) as f64 / (frames - 1) as f64;
+    let lo = t.floor() as usize;
+    (lo, (lo + 1).min(len - 1), t - lo as f64)
+}
+
+/// Linear resampling of per-frame values to `frames` values.
+fn resample(values: &[f64], frames: usize) -> Vec<f64> {
+    (0..frames)
+        .map(|i| {
+            let (lo, hi, frac) = source_position(i, values.len(), frames);
+            values[lo] * (1.0 - frac) + values[hi] * frac
+        })
+        .collect()
+}
+
+/// [`resample`] for f0, except that across a voicing boundary the nearer
+/// frame is taken: a frame between 0 and 200 Hz is not voiced at 100 Hz.
+fn resample_f0(f0: &[f64], frames: usize) -> Vec<f64> {
+    (0..frames)
+        .map(|i| {
+            let (lo, hi, frac) = source_position(i, f0.len(), frames);
+            if f0[lo] > 0.0 && f0[hi] > 0.0 {
+                f0[lo] * (1.0 - frac) + f0[hi] * frac
+            } else if frac < 0.5 {
+                f0[lo]
+            } else {
+                f0[hi]
+            }
+        })
+        .collect()
+}
+
+/// Linear resampling of rows to `frames` rows, as the speed stage's
+/// `resample_2d` does.
+fn resample_rows(rows: &[Vec<f64>], frames: usize) -> Vec<Vec<f64>> {
+    if rows.len() == frames {
+        return rows.to_vec();
+    }
+    (0..frames)
+        .map(|i| {
+            let (lo, hi, frac) = source_position(i, rows.len(), frames);
+            rows[lo]
+                .iter()
+                .zip(&rows[hi])
+                .map(|(&x, &y)| x * (1.0 - frac) + y * frac)
+                .collect()
+        })
+        .collect()
+}
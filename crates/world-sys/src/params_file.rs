@@ -0,0 +1,231 @@
+use crate::{WorldError, WorldParams};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// First bytes of every params file.
+const MAGIC: &[u8; 4] = b"VFWP";
+/// Format written by [`WorldParams::save`]; files of other versions are
+/// rejected, not converted.
+const VERSION: u32 = 3;
+/// Magic, version, source hash, fft_size, frame_period, frame_count,
+/// voicing length and sample rate.
+const HEADER_LEN: u64 = 4 + 4 + 8 + 8 + 8 + 8 + 8 + 8;
+/// Largest `fft_size` a file may declare. CheapTrick needs 8192 at 96 kHz
+/// with a 40 Hz floor; anything far beyond that is a corrupt header.
+const MAX_FILE_FFT_SIZE: usize = 1 << 16;
+
+/// The fixed-size start of a params file.
+struct Header {
+    source_hash: u64,
+    fft_size: usize,
+    frame_period: f64,
+    frame_count: usize,
+    /// 0 (no voicing track) or `frame_count`.
+    voicing_len: usize,
+    sample_rate: i32,
+}
+
+impl WorldParams {
+    /// Write the params to `path` (see [`WorldParams::save_with_source`]),
+    /// with no source hash (0).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the params fail [`WorldParams::validate`] or the
+    /// file can't be written.
+    pub fn save(&self, path: &Path) -> Result<(), WorldError> {
+        self.save_with_source(path, 0)
+    }
+
+    /// Write the params to `path` in voiceforge's binary format: the magic
+    /// `VFWP`, a version, `source_hash`, `fft_size`, `frame_period`, the
+    /// frame count, the voicing length and the sample rate, then f0,
+    /// temporal positions, voicing (f32), spectrogram rows and aperiodicity
+    /// rows, all little-endian. `source_hash` identifies the audio the
+    /// params came from ([`params_source_hash`] reads it back).
+    /// The f0 candidate lattice is not stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the params fail [`WorldParams::validate`] or the
+    /// file can't be written.
+    pub fn save_with_source(&self, path: &Path, source_hash: u64) -> Result<(), WorldError> {
+        self.validate()?;
+        let io_error = |e: std::io::Error| file_error(path, &e.to_string());
+        let mut out = BufWriter::new(File::create(path).map_err(io_error)?);
+        out.write_all(MAGIC).map_err(io_error)?;
+        out.write_all(&VERSION.to_le_bytes()).map_err(io_error)?;
+        for field in [source_hash, self.fft_size as u64] {
+            out.write_all(&field.to_le_bytes()).map_err(io_error)?;
+        }
+        out.write_all(&self.frame_period.to_le_bytes())
+            .map_err(io_error)?;
+        for field in [
+            self.f0.len() as u64,
+            self.voicing.len() as u64,
+            self.sample_rate as u64,
+        ] {
+            out.write_all(&field.to_le_bytes()).map_err(io_error)?;
+        }
+        for value in self.f0.iter().chain(&self.temporal_positions) {
+            out.write_all(&value.to_le_bytes()).map_err(io_error)?;
+        }
+        for confidence in &self.voicing {
+            out.write_all(&confidence.to_le_bytes()).map_err(io_error)?;
+        }
+        for row in self.spectrogram.iter().chain(&self.aperiodicity) {
+            for value in row {
+                out.write_all(&value.to_le_bytes()).map_err(io_error)?;
+            }
+        }
+        out.flush().map_err(io_error)
+    }
+
+    /// Read params written by [`WorldParams::save`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorldError::File`] if the file can't be read, isn't a params
+    /// file, has another version or its length doesn't match its header,
+    /// and [`WorldError::InvalidParams`] if what it holds fails
+    /// [`WorldParams::validate`].
+    pub fn load(path: &Path) -> Result<WorldParams, WorldError> {
+        let io_error = |e: std::io::Error| file_error(path, &e.to_string());
+        let mut input = BufReader::new(File::open(path).map_err(io_error)?);
+        let header = read_header(path, &mut input)?;
+        let width = header.fft_size / 2 + 1;
+        let f0 = read_values(&mut input, header.frame_count).map_err(io_error)?;
+        let temporal_positions = read_values(&mut input, header.frame_count).map_err(io_error)?;
+        let mut voicing_bytes = vec![0u8; header.voicing_len * 4];
+        input.read_exact(&mut voicing_bytes).map_err(io_error)?;
+        let voicing = voicing_bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        let mut read_rows = || -> Result<Vec<Vec<f64>>, WorldError> {
+            (0..header.frame_count)
+                .map(|_| read_values(&mut input, width).map_err(io_error))
+                .collect()
+        };
+        let spectrogram = read_rows()?;
+        let aperiodicity = read_rows()?;
+        let params = WorldParams {
+            f0,
+            temporal_positions,
+            spectrogram,
+            aperiodicity,
+            fft_size: header.fft_size,
+            frame_period: header.frame_period,
+            sample_rate: header.sample_rate,
+            f0_candidates: None,
+            voicing,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+}
+
+/// The source hash stored in the params file at `path`, reading only its
+/// header: enough to tell whether the file belongs to some audio before
+/// loading it.
+///
+/// # Errors
+///
+/// Returns [`WorldError::File`] like [`WorldParams::load`] does for a bad
+/// header or length.
+pub fn params_source_hash(path: &Path) -> Result<u64, WorldError> {
+    let file = File::open(path).map_err(|e| file_error(path, &e.to_string()))?;
+    read_header(path, &mut BufReader::new(file)).map(|header| header.source_hash)
+}
+
+fn file_error(path: &Path, msg: &str) -> WorldError {
+    WorldError::File(format!("{}: {msg}", path.display()))
+}
+
+/// Read and check the header, including that the file is exactly as long
+/// as the header says, so a truncated or padded file fails before any
+/// frames are allocated.
+fn read_header(path: &Path, input: &mut BufReader<File>) -> Result<Header, WorldError> {
+    let file_len = input
+        .get_ref()
+        .metadata()
+        .map_err(|e| file_error(path, &e.to_string()))?
+        .len();
+    if file_len < HEADER_LEN {
+        return Err(file_error(
+            path,
+            &format!("{file_len} bytes is too short for a params file"),
+        ));
+    }
+    let mut bytes = [0u8; HEADER_LEN as usize];
+    input
+        .read_exact(&mut bytes)
+        .map_err(|e| file_error(path, &e.to_string()))?;
+    if &bytes[..4] != MAGIC {
+        return Err(file_error(path, "not a voiceforge params file"));
+    }
+    let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+    let u64_at = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+    let version = u32_at(4);
+    if version != VERSION {
+        return Err(file_error(
+            path,
+            &format!("unsupported version {version} (expected {VERSION})"),
+        ));
+    }
+    let header = Header {
+        source_hash: u64_at(8),
+        fft_size: usize::try_from(u64_at(16)).unwrap_or(usize::MAX),
+        frame_period: f64::from_bits(u64_at(24)),
+        frame_count: usize::try_from(u64_at(32)).unwrap_or(usize::MAX),
+        voicing_len: usize::try_from(u64_at(40)).unwrap_or(usize::MAX),
+        sample_rate: i32::try_from(u64_at(48)).unwrap_or(0),
+    };
+    if header.sample_rate <= 0 {
+        return Err(file_error(
+            path,
+            &format!("sample_rate {} is out of range", u64_at(48)),
+        ));
+    }
+    if header.fft_size == 0 || header.fft_size > MAX_FILE_FFT_SIZE {
+        return Err(file_error(
+            path,
+            &format!("fft_size {} is out of range", header.fft_size),
+        ));
+    }
+    if header.voicing_len != 0 && header.voicing_len != header.frame_count {
+        return Err(file_error(
+            path,
+            &format!(
+                "voicing length {} for {} frames",
+                header.voicing_len, header.frame_count
+            ),
+        ));
+    }
+    let width = (header.fft_size / 2 + 1) as u64;
+    let expected = (header.frame_count as u64)
+        .checked_mul(2 + 2 * width)
+        .and_then(|values| values.checked_mul(8))
+        .and_then(|bytes| bytes.checked_add(4 * header.voicing_len as u64))
+        .and_then(|bytes| bytes.checked_add(HEADER_LEN));
+    if expected != Some(file_len) {
+        return Err(file_error(
+            path,
+            &format!(
+                "{file_len} bytes, but the header describes {} frames of fft_size {}",
+                header.frame_count, header.fft_size,
+            ),
+        ));
+    }
+    Ok(header)
+}
+
+fn read_values(input: &mut impl Read, count: usize) -> std::io::Result<Vec<f64>> {
+    let mut bytes = vec![0u8; count * 8];
+    input.read_exact(&mut bytes)?;
+    Ok(bytes
+        .chunks_exact(8)
+        .map(|b| f64::from_le_bytes(b.try_into().unwrap()))
+        .collect())
+}
@@ -0,0 +1,282 @@
+use crate::safe::output_length;
+use crate::{
+    AddParameters, DeleteWorldSynthesizer, DestroySynthesizer, GetSynthesizerBuffer,
+    InitializeSynthesizer, IsLocked, NewWorldSynthesizer, RefreshSynthesizer, Synthesis2,
+    WorldError, WorldParams, WorldSynthesizer,
+};
+use std::os::raw::c_int;
+
+/// Frames per [`RealtimeSynthesizer::add_parameters`] call in
+/// [`synthesize_realtime`].
+const STREAM_CHUNK_FRAMES: usize = 8;
+/// Ring slots for [`synthesize_realtime`]: 512 frames queued, 2.5 s at the
+/// default 5 ms period, far more than one block plus a pitch period needs.
+const STREAM_CHUNKS: usize = 64;
+/// Block length for [`synthesize_realtime`], in samples.
+const STREAM_BUFFER_SIZE: usize = 256;
+
+/// Frames handed to the synthesizer by one `add_parameters` call. WORLD
+/// keeps pointers into them, so they stay alive until the slot is reused.
+struct Chunk {
+    _f0: Vec<f64>,
+    _spectrogram: Vec<Vec<f64>>,
+    _aperiodicity: Vec<Vec<f64>>,
+    _sp_ptrs: Vec<*mut f64>,
+    _ap_ptrs: Vec<*mut f64>,
+}
+
+/// WORLD's ring-buffer synthesizer (`synthesisrealtime.cpp`): frames go in
+/// with [`RealtimeSynthesizer::add_parameters`], audio comes out
+/// `buffer_size` samples at a time from
+/// [`RealtimeSynthesizer::synthesize_block`], from the first sample on.
+///
+/// The ring holds `chunks` `add_parameters` calls; a slot frees up once
+/// synthesis has moved past its frames. When the queued frames are too
+/// short to finish the next block and the ring is full, the synthesizer is
+/// locked ([`RealtimeSynthesizer::is_locked`]) and must be refreshed.
+pub struct RealtimeSynthesizer {
+    synth: *mut WorldSynthesizer,
+    buffer_size: usize,
+    fft_size: usize,
+    slots: Vec<Option<Chunk>>,
+    /// Successful `add_parameters` calls since the last refresh; WORLD's
+    /// `head_pointer`.
+    added: usize,
+}
+
+// The synthesizer and the chunks it points into are owned exclusively and
+// only touched through `&mut self`.
+unsafe impl Send for RealtimeSynthesizer {}
+
+impl RealtimeSynthesizer {
+    /// A synthesizer for frames `frame_period` ms apart with `fft_size`
+    /// spectra (rows of `fft_size / 2 + 1` bins), producing blocks of
+    /// `buffer_size` samples at `sample_rate`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any size is zero, `sample_rate` is not positive
+    /// or `frame_period` is not finite and positive.
+    pub fn new(
+        sample_rate: i32,
+        frame_period: f64,
+        fft_size: usize,
+        buffer_size: usize,
+        chunks: usize,
+    ) -> Result<Self, WorldError> {
+        if sample_rate <= 0 {
+            return Err(WorldError::InvalidParams(
+                "sample_rate must be positive".into(),
+            ));
+        }
+        if !(frame_period.is_finite() && frame_period > 0.0) {
+            return Err(WorldError::InvalidParams(format!(
+                "frame_period must be finite and positive, got {frame_period}",
+            )));
+        }
+        if fft_size == 0 || buffer_size == 0 || chunks == 0 {
+            return Err(WorldError::InvalidParams(format!(
+                "fft_size ({fft_size}), buffer_size ({buffer_size}) and chunks ({chunks}) must be positive",
+            )));
+        }
+
+        let synth = unsafe { NewWorldSynthesizer() };
+        unsafe {
+            InitializeSynthesizer(
+                sample_rate,
+                frame_period,
+                fft_size as c_int,
+                buffer_size as c_int,
+                chunks as c_int,
+                synth,
+            );
+        }
+        Ok(Self {
+            synth,
+            buffer_size,
+            fft_size,
+            slots: (0..chunks).map(|_| None).collect(),
+            added: 0,
+        })
+    }
+
+    /// Queue the next frames. Returns `Ok(false)` when the ring is full:
+    /// synthesize blocks to free a slot, then try again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slices are empty, differ in length, or a row
+    /// is not `fft_size / 2 + 1` bins wide.
+    pub fn add_parameters(
+        &mut self,
+        f0: &[f64],
+        spectrogram: &[Vec<f64>],
+        aperiodicity: &[Vec<f64>],
+    ) -> Result<bool, WorldError> {
+        if f0.is_empty() || spectrogram.len() != f0.len() || aperiodicity.len() != f0.len() {
+            return Err(WorldError::InvalidParams(format!(
+                "need matching non-empty frames: f0 {}, spectrogram {}, aperiodicity {}",
+                f0.len(),
+                spectrogram.len(),
+                aperiodicity.len(),
+            )));
+        }
+        let width = self.fft_size / 2 + 1;
+        if let Some(row) = spectrogram
+            .iter()
+            .chain(aperiodicity)
+            .find(|row| row.len() != width)
+        {
+            return Err(WorldError::InvalidParams(format!(
+                "row width ({}) != fft_size / 2 + 1 ({width})",
+                row.len(),
+            )));
+        }
+
+        let mut f0 = f0.to_vec();
+        let mut spectrogram = spectrogram.to_vec();
+        let mut aperiodicity = aperiodicity.to_vec();
+        let mut sp_ptrs: Vec<*mut f64> =
+            spectrogram.iter_mut().map(|row| row.as_mut_ptr()).collect();
+        let mut ap_ptrs: Vec<*mut f64> = aperiodicity
+            .iter_mut()
+            .map(|row| row.as_mut_ptr())
+            .collect();
+        let accepted = unsafe {
+            AddParameters(
+                f0.as_mut_ptr(),
+                f0.len() as c_int,
+                sp_ptrs.as_mut_ptr(),
+                ap_ptrs.as_mut_ptr(),
+                self.synth,
+            )
+        } == 1;
+        if accepted {
+            // WORLD only accepts when this slot's previous frames have been
+            // released, so the chunk it held can go. Moving the Vecs keeps
+            // their heap buffers (and the pointers WORLD holds) in place.
+            let slot = self.added % self.slots.len();
+            self.slots[slot] = Some(Chunk {
+                _f0: f0,
+                _spectrogram: spectrogram,
+                _aperiodicity: aperiodicity,
+                _sp_ptrs: sp_ptrs,
+                _ap_ptrs: ap_ptrs,
+            });
+            self.added += 1;
+        }
+        Ok(accepted)
+    }
+
+    /// Synthesize the next `buffer_size` samples, or `None` if the queued
+    /// frames don't reach past the end of that block yet.
+    pub fn synthesize_block(&mut self) -> Option<&[f64]> {
+        if unsafe { Synthesis2(self.synth) } == 0 {
+            return None;
+        }
+        let buffer = unsafe { GetSynthesizerBuffer(self.synth) };
+        Some(unsafe { std::slice::from_raw_parts(buffer, self.buffer_size) })
+    }
+
+    /// True when no frames can be added and no block synthesized; see
+    /// [`RealtimeSynthesizer::refresh`].
+    pub fn is_locked(&mut self) -> bool {
+        unsafe { IsLocked(self.synth) == 1 }
+    }
+
+    /// Drop every queued frame and start over from sample 0.
+    pub fn refresh(&mut self) {
+        unsafe { RefreshSynthesizer(self.synth) };
+        self.slots.iter_mut().for_each(|slot| *slot = None);
+        self.added = 0;
+    }
+
+    pub fn buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+}
+
+impl Drop for RealtimeSynthesizer {
+    fn drop(&mut self) {
+        // The chunks are dropped after this, once WORLD no longer uses them.
+        unsafe {
+            DestroySynthesizer(self.synth);
+            DeleteWorldSynthesizer(self.synth);
+        }
+    }
+}
+
+/// Synthesize all of `params` through a [`RealtimeSynthesizer`], block by
+/// block. Returns as many samples as [`crate::synthesize`] does; the tail is
+/// flushed by repeating the last frame past the end.
+///
+/// # Errors
+///
+/// Returns an error if the params fail [`WorldParams::validate`], the
+/// output would be too large, or the synthesizer locks.
+pub fn synthesize_realtime(params: &WorldParams, sample_rate: i32) -> Result<Vec<f64>, WorldError> {
+    if sample_rate <= 0 {
+        return Err(WorldError::InvalidParams(
+            "sample_rate must be positive".into(),
+        ));
+    }
+    params.validate()?;
+    let y_length = output_length(params, sample_rate)?;
+
+    let mut synth = RealtimeSynthesizer::new(
+        sample_rate,
+        params.frame_period,
+        params.fft_size,
+        STREAM_BUFFER_SIZE,
+        STREAM_CHUNKS,
+    )?;
+    let frames = params.f0.len();
+    let last = frames - 1;
+    let padding = (
+        vec![params.f0[last]; STREAM_CHUNK_FRAMES],
+        vec![params.spectrogram[last].clone(); STREAM_CHUNK_FRAMES],
+        vec![params.aperiodicity[last].clone(); STREAM_CHUNK_FRAMES],
+    );
+    // Enough padding to cover one block plus an impulse response, twice.
+    let frame_samples = (params.frame_period / 1000.0 * f64::from(sample_rate)).max(1.0);
+    let padding_limit = (2.0 * (STREAM_BUFFER_SIZE + params.fft_size) as f64
+        / frame_samples
+        / STREAM_CHUNK_FRAMES as f64)
+        .ceil() as usize
+        + 1;
+
+    let mut y = Vec::with_capacity(y_length + STREAM_BUFFER_SIZE);
+    let mut next = 0;
+    let mut padded = 0;
+    while y.len() < y_length {
+        if let Some(block) = synth.synthesize_block() {
+            y.extend_from_slice(block);
+            continue;
+        }
+        let accepted = if next < frames {
+            let end = (next + STREAM_CHUNK_FRAMES).min(frames);
+            let accepted = synth.add_parameters(
+                &params.f0[next..end],
+                &params.spectrogram[next..end],
+                &params.aperiodicity[next..end],
+            )?;
+            if accepted {
+                next = end;
+            }
+            accepted
+        } else if padded < padding_limit {
+            padded += 1;
+            synth.add_parameters(&padding.0, &padding.1, &padding.2)?
+        } else {
+            false
+        };
+        if !accepted {
+            return Err(WorldError::InvalidParams(format!(
+                "realtime synthesizer locked after {} of {y_length} samples",
+                y.len(),
+            )));
+        }
+    }
+    y.truncate(y_length);
+    Ok(y)
+}
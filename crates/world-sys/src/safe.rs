@@ -1,19 +1,51 @@
 use crate::{
-    init_option, init_option_with_fs, CheapTrick, D4C, Dio, GetFFTSizeForCheapTrick,
+    init_option, init_option_with_fs, CheapTrick, Dio, DioOption, GetFFTSizeForCheapTrick,
     GetSamplesForDIO, InitializeCheapTrickOption, InitializeD4COption, InitializeDioOption,
-    StoneMask, Synthesis,
+    StoneMask, Synthesis, D4C,
 };
 use std::fmt;
+use std::ops::Range;
 use std::os::raw::c_int;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-/// Maximum output samples to allocate (~10 minutes at 96kHz).
-const MAX_SYNTHESIS_SAMPLES: usize = 96_000 * 60 * 10;
+/// CheapTrick and D4C each run as this many calls over consecutive frame
+/// ranges, reporting progress after each (1% steps of their 25% share).
+/// Frames are analyzed independently, so splitting doesn't change them.
+const STAGE_CHUNKS: usize = 25;
+
+/// Aperiodicity below this frequency decides a frame's voicing confidence.
+/// D4C estimates its first band at 3 kHz and interpolates down from there.
+const VOICING_BAND_HZ: f64 = 3000.0;
+
+/// Voicing confidence from which a frame counts as voiced in
+/// [`WorldParams::voiced_ratio`] and is modified in full by pitch changes.
+pub const CONFIDENT_VOICING: f32 = 0.5;
+
+/// What [`WorldParams::sanitize`] puts in place of a non-finite spectrogram
+/// bin: WORLD's `kMySafeGuardMinimum`, the floor it keeps envelopes above.
+pub const SANITIZED_SPECTRUM_BIN: f64 = 1e-12;
+
+/// How far (s) a frame may lie outside the range [`WorldParams::slice`]
+/// was asked for and still be taken, so `0.2` catches a frame at
+/// `0.20000000000000004`.
+pub const SLICE_TOLERANCE_SECS: f64 = 1e-6;
+
+/// Maximum output samples to allocate (an hour at 96kHz, ~2.8 GB of f64),
+/// enough for the long files chunked analysis makes practical.
+const MAX_SYNTHESIS_SAMPLES: usize = 96_000 * 60 * 60;
 
 /// Errors from WORLD parameter validation or synthesis.
 #[derive(Debug, Clone)]
 pub enum WorldError {
     InvalidParams(String),
-    AllocationTooLarge { requested: usize, max: usize },
+    AllocationTooLarge {
+        requested: usize,
+        max: usize,
+    },
+    /// The caller's cancellation token was set during analysis.
+    Cancelled,
+    /// A params file couldn't be read or written, or isn't one.
+    File(String),
 }
 
 impl fmt::Display for WorldError {
@@ -21,16 +53,32 @@ impl fmt::Display for WorldError {
         match self {
             WorldError::InvalidParams(msg) => write!(f, "invalid WORLD params: {msg}"),
             WorldError::AllocationTooLarge { requested, max } => {
-                write!(f, "synthesis output too large: {requested} samples (max {max})")
+                write!(
+                    f,
+                    "synthesis output too large: {requested} samples (max {max})"
+                )
             }
+            WorldError::Cancelled => write!(f, "analysis cancelled"),
+            WorldError::File(msg) => write!(f, "params file {msg}"),
         }
     }
 }
 
 impl std::error::Error for WorldError {}
 
-/// Parameters extracted by WORLD analysis.
-#[derive(Debug, Clone)]
+/// One alternative f0 for a frame, with how strongly the analysis supports it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct F0Candidate {
+    /// Fundamental frequency in Hz, 0 for "unvoiced".
+    pub f0: f64,
+    /// Support in `0.0..=1.0` (fraction of f0 tracks that agree).
+    pub score: f64,
+}
+
+/// Parameters extracted by WORLD analysis. The default is empty (no frames,
+/// no sample rate), e.g. as a buffer to render into; it isn't valid input
+/// for synthesis.
+#[derive(Debug, Clone, Default)]
 pub struct WorldParams {
     pub f0: Vec<f64>,
     pub temporal_positions: Vec<f64>,
@@ -40,20 +88,143 @@ pub struct WorldParams {
     pub aperiodicity: Vec<Vec<f64>>,
     pub fft_size: usize,
     pub frame_period: f64,
+    /// Sample rate the params were analyzed at, in Hz: what a spectrogram
+    /// or aperiodicity bin `k` means (`k * sample_rate / fft_size` Hz).
+    pub sample_rate: i32,
+    /// Optional f0 candidate lattice: per frame, the best candidates first.
+    /// Only filled by extended analysis; not used by synthesis.
+    pub f0_candidates: Option<Vec<Vec<F0Candidate>>>,
+    /// Voicing confidence per frame in `0.0..=1.0` (see
+    /// [`voicing_confidence`]), or empty when unknown, e.g. for params built
+    /// by hand; every voiced frame is then taken as confident.
+    pub voicing: Vec<f32>,
+}
+
+/// DIO search settings for [`track_f0`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct F0Search {
+    /// Lowest f0 searched, in Hz.
+    pub f0_floor: f64,
+    /// Highest f0 searched, in Hz.
+    pub f0_ceil: f64,
+    /// Band-pass filter channels per octave (WORLD's default is 2).
+    pub channels_in_octave: f64,
+}
+
+/// Analysis settings for [`analyze_with_options`] and
+/// [`analyze_with_progress`]. The defaults are WORLD's own.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnalyzeOptions {
+    /// Lowest f0 searched, in Hz. Also sets CheapTrick's window, so a lower
+    /// floor means a larger `fft_size`.
+    pub f0_floor: f64,
+    /// Highest f0 searched, in Hz.
+    pub f0_ceil: f64,
+    /// Hop between analysis frames, in ms. Larger is faster and coarser.
+    pub frame_period: f64,
+    /// D4C voicing threshold in `0.0..=1.0`: frames whose aperiodicity
+    /// suggests noise above it are treated as unvoiced (0 disables).
+    pub d4c_threshold: f64,
+    /// Threads CheapTrick and D4C run their frame chunks on; 0 = one per
+    /// available core. The result doesn't depend on it.
+    pub threads: usize,
+    /// CheapTrick's spectral recovery coefficient `q1`, in `-1.0..=1.0`;
+    /// `None` keeps WORLD's -0.15. Closer to 0 smooths the envelope less,
+    /// which can help when resynthesized speech sounds muffled.
+    pub cheaptrick_q1: Option<f64>,
+    /// DIO's decimation factor in `1..=12` (WORLD's `speed`, default 1): DIO
+    /// tracks f0 on every `dio_speed`-th sample, much faster and slightly
+    /// less accurate.
+    pub dio_speed: i32,
+    /// Refine DIO's f0 with StoneMask (default). Off, DIO's raw f0 goes
+    /// straight to CheapTrick and D4C: the f0 stage takes about half the
+    /// time, at some accuracy on noisy or fast-moving pitch.
+    pub refine_f0: bool,
+}
+
+impl Default for AnalyzeOptions {
+    fn default() -> Self {
+        let dio = unsafe { init_option(InitializeDioOption) };
+        let d4c = unsafe { init_option(InitializeD4COption) };
+        Self {
+            f0_floor: dio.f0_floor,
+            f0_ceil: dio.f0_ceil,
+            frame_period: dio.frame_period,
+            d4c_threshold: d4c.threshold,
+            threads: 0,
+            cheaptrick_q1: None,
+            dio_speed: dio.speed,
+            refine_f0: true,
+        }
+    }
+}
+
+impl AnalyzeOptions {
+    /// Check the ranges: `0 < f0_floor < f0_ceil`, `frame_period > 0`,
+    /// `d4c_threshold` in `0.0..=1.0`, `cheaptrick_q1` in `-1.0..=1.0`, all
+    /// finite, and `dio_speed` in `1..=12`.
+    pub fn validate(&self) -> Result<(), WorldError> {
+        if !(self.f0_floor.is_finite() && self.f0_floor > 0.0) {
+            return Err(WorldError::InvalidParams(format!(
+                "f0_floor must be finite and positive, got {}",
+                self.f0_floor,
+            )));
+        }
+        if !(self.f0_ceil.is_finite() && self.f0_ceil > self.f0_floor) {
+            return Err(WorldError::InvalidParams(format!(
+                "f0_ceil ({}) must be finite and above f0_floor ({})",
+                self.f0_ceil, self.f0_floor,
+            )));
+        }
+        if !(self.frame_period.is_finite() && self.frame_period > 0.0) {
+            return Err(WorldError::InvalidParams(format!(
+                "frame_period must be finite and positive, got {}",
+                self.frame_period,
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.d4c_threshold) {
+            return Err(WorldError::InvalidParams(format!(
+                "d4c_threshold must be in 0..=1, got {}",
+                self.d4c_threshold,
+            )));
+        }
+        if let Some(q1) = self.cheaptrick_q1.filter(|q1| !(-1.0..=1.0).contains(q1)) {
+            return Err(WorldError::InvalidParams(format!(
+                "cheaptrick_q1 must be in -1..=1, got {q1}"
+            )));
+        }
+        if !(1..=12).contains(&self.dio_speed) {
+            return Err(WorldError::InvalidParams(format!(
+                "dio_speed must be in 1..=12, got {}",
+                self.dio_speed
+            )));
+        }
+        Ok(())
+    }
 }
 
 impl WorldParams {
     /// Validate internal consistency of parameters.
     ///
-    /// Returns `Err` if dimensions are inconsistent. Prefer this over panicking
+    /// Returns `Err` if dimensions are inconsistent or f0, a temporal
+    /// position or a spectrogram or aperiodicity bin is NaN or infinite
+    /// (see [`WorldParams::sanitize`]). Prefer this over panicking
     /// so callers (including a future REST API) can handle errors gracefully.
-    fn validate(&self) -> Result<(), WorldError> {
+    pub fn validate(&self) -> Result<(), WorldError> {
         let frame_count = self.f0.len();
         if frame_count == 0 {
             return Err(WorldError::InvalidParams("f0 must not be empty".into()));
         }
         if self.fft_size == 0 {
-            return Err(WorldError::InvalidParams("fft_size must be positive".into()));
+            return Err(WorldError::InvalidParams(
+                "fft_size must be positive".into(),
+            ));
+        }
+        if self.sample_rate <= 0 {
+            return Err(WorldError::InvalidParams(format!(
+                "sample_rate must be positive, got {}",
+                self.sample_rate,
+            )));
         }
         // H-8: Validate frame_period to prevent Inf→usize UB in y_length calculation.
         if !self.frame_period.is_finite() || self.frame_period <= 0.0 {
@@ -100,23 +271,242 @@ impl WorldParams {
                 )));
             }
         }
+        if !self.voicing.is_empty() && self.voicing.len() != frame_count {
+            return Err(WorldError::InvalidParams(format!(
+                "voicing length ({}) != f0 length ({frame_count})",
+                self.voicing.len(),
+            )));
+        }
+
+        for (what, values) in [
+            ("f0", &self.f0),
+            ("temporal_positions", &self.temporal_positions),
+        ] {
+            if let Some(i) = values.iter().position(|v| !v.is_finite()) {
+                return Err(WorldError::InvalidParams(format!(
+                    "{what}[{i}] is {}",
+                    values[i]
+                )));
+            }
+        }
+        for (what, rows) in [
+            ("spectrogram", &self.spectrogram),
+            ("aperiodicity", &self.aperiodicity),
+        ] {
+            if let Some(i) = rows
+                .iter()
+                .position(|row| row.iter().any(|v| !v.is_finite()))
+            {
+                return Err(WorldError::InvalidParams(format!(
+                    "{what}[{i}] has non-finite bins"
+                )));
+            }
+        }
 
         Ok(())
     }
+
+    /// Replace NaN and infinite values, which WORLD can produce on odd
+    /// input, with harmless ones: f0 with 0 (unvoiced), spectrogram bins
+    /// with [`SANITIZED_SPECTRUM_BIN`], aperiodicity bins with 1 (fully
+    /// aperiodic) and voicing with 0. Returns how many values were
+    /// replaced, for the caller to report or reject.
+    pub fn sanitize(&mut self) -> usize {
+        fn replace(values: &mut [f64], with: f64) -> usize {
+            let mut replaced = 0;
+            for v in values.iter_mut().filter(|v| !v.is_finite()) {
+                *v = with;
+                replaced += 1;
+            }
+            replaced
+        }
+        let mut replaced = replace(&mut self.f0, 0.0);
+        replaced += self
+            .spectrogram
+            .iter_mut()
+            .map(|row| replace(row, SANITIZED_SPECTRUM_BIN))
+            .sum::<usize>();
+        replaced += self
+            .aperiodicity
+            .iter_mut()
+            .map(|row| replace(row, 1.0))
+            .sum::<usize>();
+        for confidence in self.voicing.iter_mut().filter(|c| !c.is_finite()) {
+            *confidence = 0.0;
+            replaced += 1;
+        }
+        replaced
+    }
+
+    /// The frames whose temporal position lies in `start_secs..=end_secs`
+    /// (within [`SLICE_TOLERANCE_SECS`]), with positions rebased so the
+    /// first is 0. Voicing and the candidate lattice are sliced along.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorldError::InvalidParams`] if the params or the slice
+    /// fail [`WorldParams::validate`], the range is not finite or ends
+    /// before it starts, or no frame lies in it.
+    pub fn slice(&self, start_secs: f64, end_secs: f64) -> Result<WorldParams, WorldError> {
+        self.validate()?;
+        if !(start_secs.is_finite() && end_secs.is_finite()) || end_secs < start_secs {
+            return Err(WorldError::InvalidParams(format!(
+                "invalid slice {start_secs}..{end_secs} s"
+            )));
+        }
+        let first = self
+            .temporal_positions
+            .partition_point(|&t| t < start_secs - SLICE_TOLERANCE_SECS);
+        let end = self
+            .temporal_positions
+            .partition_point(|&t| t <= end_secs + SLICE_TOLERANCE_SECS);
+        if first >= end {
+            return Err(WorldError::InvalidParams(format!(
+                "no frames between {start_secs} and {end_secs} s"
+            )));
+        }
+        let frames = first..end;
+        let origin = self.temporal_positions[first];
+        let sliced = WorldParams {
+            f0: self.f0[frames.clone()].to_vec(),
+            temporal_positions: self.temporal_positions[frames.clone()]
+                .iter()
+                .map(|t| t - origin)
+                .collect(),
+            spectrogram: self.spectrogram[frames.clone()].to_vec(),
+            aperiodicity: self.aperiodicity[frames.clone()].to_vec(),
+            fft_size: self.fft_size,
+            frame_period: self.frame_period,
+            sample_rate: self.sample_rate,
+            f0_candidates: self
+                .f0_candidates
+                .as_ref()
+                .and_then(|lattice| lattice.get(frames.clone()).map(<[_]>::to_vec)),
+            voicing: self
+                .voicing
+                .get(frames)
+                .map(<[_]>::to_vec)
+                .unwrap_or_default(),
+        };
+        sliced.validate()?;
+        Ok(sliced)
+    }
+
+    /// `other`'s frames appended to these, as one analysis: temporal
+    /// positions run on one frame period apart across the join. Voicing and
+    /// the candidate lattice are kept only when both sides have them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorldError::InvalidParams`] if either side fails
+    /// [`WorldParams::validate`] or their `fft_size`, `frame_period` or
+    /// `sample_rate` differ (analyses made with other settings or at
+    /// another rate).
+    pub fn concat(&self, other: &WorldParams) -> Result<WorldParams, WorldError> {
+        self.validate()?;
+        other.validate()?;
+        if self.fft_size != other.fft_size {
+            return Err(WorldError::InvalidParams(format!(
+                "can't join fft_size {} to fft_size {}",
+                other.fft_size, self.fft_size,
+            )));
+        }
+        if self.frame_period != other.frame_period {
+            return Err(WorldError::InvalidParams(format!(
+                "can't join frame_period {} ms to frame_period {} ms",
+                other.frame_period, self.frame_period,
+            )));
+        }
+        if self.sample_rate != other.sample_rate {
+            return Err(WorldError::InvalidParams(format!(
+                "can't join sample_rate {} Hz to sample_rate {} Hz",
+                other.sample_rate, self.sample_rate,
+            )));
+        }
+        let frames = self.f0.len() + other.f0.len();
+        let both = |a: &[f32], b: &[f32]| {
+            if a.is_empty() || b.is_empty() {
+                Vec::new()
+            } else {
+                [a, b].concat()
+            }
+        };
+        let joined = WorldParams {
+            f0: [&self.f0[..], &other.f0].concat(),
+            // DIO's positions, continued through the appended frames.
+            temporal_positions: (0..frames)
+                .map(|i| i as f64 * self.frame_period / 1000.0)
+                .collect(),
+            spectrogram: [&self.spectrogram[..], &other.spectrogram].concat(),
+            aperiodicity: [&self.aperiodicity[..], &other.aperiodicity].concat(),
+            fft_size: self.fft_size,
+            frame_period: self.frame_period,
+            sample_rate: self.sample_rate,
+            f0_candidates: match (&self.f0_candidates, &other.f0_candidates) {
+                (Some(a), Some(b)) => Some([&a[..], b].concat()),
+                _ => None,
+            },
+            voicing: both(&self.voicing, &other.voicing),
+        };
+        joined.validate()?;
+        Ok(joined)
+    }
+
+    /// Share of frames that are confidently voiced (confidence at least
+    /// [`CONFIDENT_VOICING`]), in `0.0..=1.0`. Without a voicing track,
+    /// the share of frames with f0 > 0.
+    pub fn voiced_ratio(&self) -> f64 {
+        if self.f0.is_empty() {
+            return 0.0;
+        }
+        let voiced = if self.voicing.is_empty() {
+            self.f0.iter().filter(|&&f0| f0 > 0.0).count()
+        } else {
+            self.voicing
+                .iter()
+                .filter(|&&c| c >= CONFIDENT_VOICING)
+                .count()
+        };
+        voiced as f64 / self.f0.len() as f64
+    }
 }
 
-/// Analyze audio using WORLD vocoder (DIO -> StoneMask -> CheapTrick -> D4C).
-/// Accepts a callback to report progress (called at 25%, 50%, 75%, 100%).
+/// Analyze audio using WORLD vocoder (DIO -> StoneMask -> CheapTrick -> D4C)
+/// with `options`; StoneMask is skipped unless `options.refine_f0`.
+/// Accepts a callback to report progress: 25% after DIO,
+/// 50% after StoneMask, then rising in steps to 75% through CheapTrick and
+/// to 100% through D4C, which run in chunks of frames. Percentages strictly
+/// increase. `WorldParams::frame_period` is `options.frame_period`.
+///
+/// Non-finite f0 comes back as 0; spectrogram and aperiodicity bins are as
+/// WORLD computed them, so a NaN there fails [`WorldParams::validate`] until
+/// [`WorldParams::sanitize`] replaces it.
+///
+/// CheapTrick and D4C run their chunks on `options.threads` threads.
+/// `cancel` is checked before each stage and each chunk; a WORLD call
+/// already running finishes first.
+///
+/// # Errors
+///
+/// Returns an error, before any analysis runs, if `options` fails
+/// [`AnalyzeOptions::validate`], and [`WorldError::Cancelled`] once `cancel`
+/// is set.
 ///
 /// # Panics
 ///
 /// Panics if `audio` is empty, `sample_rate` is not positive, or `audio` length
 /// exceeds `i32::MAX`.
-#[must_use]
-pub fn analyze_with_progress<F>(audio: &[f64], sample_rate: i32, mut on_stage: F) -> WorldParams
+pub fn analyze_with_progress<F>(
+    audio: &[f64],
+    sample_rate: i32,
+    options: &AnalyzeOptions,
+    cancel: &AtomicBool,
+    mut on_stage: F,
+) -> Result<WorldParams, WorldError>
 where
     F: FnMut(u8),
 {
+    options.validate()?;
     assert!(!audio.is_empty(), "audio must not be empty");
     assert!(sample_rate > 0, "sample_rate must be positive");
     assert!(
@@ -129,8 +519,12 @@ where
     let fs = sample_rate;
 
     // Initialize DIO options
-    let dio_option = unsafe { init_option(InitializeDioOption) };
-    let frame_period = dio_option.frame_period;
+    let mut dio_option = unsafe { init_option(InitializeDioOption) };
+    dio_option.f0_floor = options.f0_floor;
+    dio_option.f0_ceil = options.f0_ceil;
+    dio_option.frame_period = options.frame_period;
+    dio_option.speed = options.dio_speed;
+    let frame_period = options.frame_period;
 
     // Get number of frames
     let f0_length_raw = unsafe { GetSamplesForDIO(fs, x_length, frame_period) };
@@ -140,7 +534,23 @@ where
     );
     let f0_length = f0_length_raw as usize;
 
+    let mut last_pct = 0;
+    let mut report = |pct: u8| {
+        if pct > last_pct {
+            last_pct = pct;
+            on_stage(pct);
+        }
+    };
+    let check_cancel = || {
+        if cancel.load(Ordering::Relaxed) {
+            Err(WorldError::Cancelled)
+        } else {
+            Ok(())
+        }
+    };
+
     // Run DIO for f0 estimation
+    check_cancel()?;
     let mut temporal_positions = vec![0.0f64; f0_length];
     let mut f0 = vec![0.0f64; f0_length];
     unsafe {
@@ -153,25 +563,23 @@ where
             f0.as_mut_ptr(),
         );
     }
-    on_stage(25);
+    report(25);
 
     // Refine f0 with StoneMask
-    let mut refined_f0 = vec![0.0f64; f0_length];
-    unsafe {
-        StoneMask(
-            audio.as_ptr(),
-            x_length,
-            fs,
-            temporal_positions.as_ptr(),
-            f0.as_ptr(),
-            f0_length_raw,
-            refined_f0.as_mut_ptr(),
-        );
-    }
-    on_stage(50);
+    check_cancel()?;
+    let mut refined_f0 = if options.refine_f0 {
+        stone_mask(audio, fs, &temporal_positions, &f0)
+    } else {
+        f0
+    };
+    report(50);
 
     // Initialize CheapTrick options and get FFT size
     let mut ct_option = unsafe { init_option_with_fs(InitializeCheapTrickOption, fs) };
+    ct_option.f0_floor = options.f0_floor;
+    if let Some(q1) = options.cheaptrick_q1 {
+        ct_option.q1 = q1;
+    }
     let fft_size = unsafe { GetFFTSizeForCheapTrick(fs, &ct_option) } as usize;
     ct_option.fft_size = fft_size as c_int;
 
@@ -181,41 +589,60 @@ where
     let mut sp_rows: Vec<Vec<f64>> = (0..f0_length).map(|_| vec![0.0f64; sp_width]).collect();
     let mut sp_ptrs: Vec<*mut f64> = sp_rows.iter_mut().map(|row| row.as_mut_ptr()).collect();
 
-    unsafe {
-        CheapTrick(
-            audio.as_ptr(),
-            x_length,
-            fs,
-            temporal_positions.as_ptr(),
-            refined_f0.as_ptr(),
-            f0_length_raw,
-            &ct_option,
-            sp_ptrs.as_mut_ptr(),
-        );
-    }
-    on_stage(75);
+    let chunks = frame_chunks(f0_length);
+    let threads = match options.threads {
+        0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    };
+    check_cancel()?;
+    run_chunks(
+        &chunks,
+        &mut sp_ptrs,
+        threads,
+        cancel,
+        |done| report(50 + (25 * done / chunks.len()) as u8),
+        |range, rows| unsafe {
+            CheapTrick(
+                audio.as_ptr(),
+                x_length,
+                fs,
+                temporal_positions[range.start..].as_ptr(),
+                refined_f0[range.start..].as_ptr(),
+                range.len() as c_int,
+                &ct_option,
+                rows,
+            );
+        },
+    )?;
 
     // Initialize D4C options
-    let d4c_option = unsafe { init_option(InitializeD4COption) };
+    let mut d4c_option = unsafe { init_option(InitializeD4COption) };
+    d4c_option.threshold = options.d4c_threshold;
 
     // Allocate aperiodicity (array of pointers to rows)
     let mut ap_rows: Vec<Vec<f64>> = (0..f0_length).map(|_| vec![0.0f64; sp_width]).collect();
     let mut ap_ptrs: Vec<*mut f64> = ap_rows.iter_mut().map(|row| row.as_mut_ptr()).collect();
 
-    unsafe {
-        D4C(
-            audio.as_ptr(),
-            x_length,
-            fs,
-            temporal_positions.as_ptr(),
-            refined_f0.as_ptr(),
-            f0_length_raw,
-            fft_size as c_int,
-            &d4c_option,
-            ap_ptrs.as_mut_ptr(),
-        );
-    }
-    on_stage(100);
+    run_chunks(
+        &chunks,
+        &mut ap_ptrs,
+        threads,
+        cancel,
+        |done| report(75 + (25 * done / chunks.len()) as u8),
+        |range, rows| unsafe {
+            D4C(
+                audio.as_ptr(),
+                x_length,
+                fs,
+                temporal_positions[range.start..].as_ptr(),
+                refined_f0[range.start..].as_ptr(),
+                range.len() as c_int,
+                fft_size as c_int,
+                &d4c_option,
+                rows,
+            );
+        },
+    )?;
 
     // M-4: Runtime check (not debug_assert) for non-finite f0 values.
     // WORLD can produce NaN on silent or very short audio.
@@ -225,14 +652,230 @@ where
         }
     }
 
-    WorldParams {
+    let voicing = voicing_confidence(&refined_f0, &ap_rows, fft_size, fs);
+    Ok(WorldParams {
         f0: refined_f0,
         temporal_positions,
         spectrogram: sp_rows,
         aperiodicity: ap_rows,
         fft_size,
         frame_period,
+        sample_rate: fs,
+        f0_candidates: None,
+        voicing,
+    })
+}
+
+/// Voicing confidence of each frame in `0.0..=1.0`: 0 where f0 is 0,
+/// otherwise one minus the mean aperiodicity below 3 kHz. A clean periodic
+/// frame scores around 0.85, one D4C judged unvoiced (all aperiodic) or a
+/// noisy f0 guess close to 0. `sample_rate` must be the analysis rate.
+pub fn voicing_confidence(
+    f0: &[f64],
+    aperiodicity: &[Vec<f64>],
+    fft_size: usize,
+    sample_rate: i32,
+) -> Vec<f32> {
+    let last_bin = (VOICING_BAND_HZ * fft_size as f64 / f64::from(sample_rate.max(1))) as usize;
+    f0.iter()
+        .zip(aperiodicity)
+        .map(|(&f0, row)| {
+            let band = &row[..(last_bin + 1).min(row.len())];
+            if f0 <= 0.0 || band.is_empty() {
+                return 0.0;
+            }
+            let mean = band.iter().sum::<f64>() / band.len() as f64;
+            (1.0 - mean).clamp(0.0, 1.0) as f32
+        })
+        .collect()
+}
+
+/// Split `frames` into at most [`STAGE_CHUNKS`] consecutive, non-empty
+/// ranges of near-equal length.
+fn frame_chunks(frames: usize) -> Vec<Range<usize>> {
+    let count = frames.clamp(1, STAGE_CHUNKS);
+    (0..count)
+        .map(|k| k * frames / count..(k + 1) * frames / count)
+        .collect()
+}
+
+/// Output row pointers of one chunk, moved to the thread that fills them.
+struct ChunkRows(*mut *mut f64);
+
+// Chunks cover disjoint frames, so no two threads touch the same pointer
+// or row, and `run_chunks` joins every thread before the rows are used.
+unsafe impl Send for ChunkRows {}
+
+impl ChunkRows {
+    fn get(&self) -> *mut *mut f64 {
+        self.0
+    }
+}
+
+/// Run `stage(range, rows)` for every chunk of frames, where `rows` points
+/// at `rows[range.start]`, spread over `threads` scoped threads (chunk `k`
+/// goes to thread `k % threads`). `on_done(n)` runs on the calling thread
+/// after each chunk, with `n` counting finished chunks. Threads stop taking
+/// chunks once `cancel` is set.
+fn run_chunks<S>(
+    chunks: &[Range<usize>],
+    rows: &mut [*mut f64],
+    threads: usize,
+    cancel: &AtomicBool,
+    mut on_done: impl FnMut(usize),
+    stage: S,
+) -> Result<(), WorldError>
+where
+    S: Fn(Range<usize>, *mut *mut f64) + Sync,
+{
+    let threads = threads.clamp(1, chunks.len().max(1));
+    let base = rows.as_mut_ptr();
+    std::thread::scope(|scope| {
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        for t in 0..threads {
+            let mine: Vec<(Range<usize>, ChunkRows)> = chunks
+                .iter()
+                .skip(t)
+                .step_by(threads)
+                .map(|range| (range.clone(), ChunkRows(unsafe { base.add(range.start) })))
+                .collect();
+            let done_tx = done_tx.clone();
+            let stage = &stage;
+            scope.spawn(move || {
+                for (range, rows) in mine {
+                    if cancel.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    stage(range, rows.get());
+                    let _ = done_tx.send(());
+                }
+            });
+        }
+        drop(done_tx);
+        for (done, ()) in done_rx.iter().enumerate() {
+            on_done(done + 1);
+        }
+    });
+    if cancel.load(Ordering::Relaxed) {
+        return Err(WorldError::Cancelled);
     }
+    Ok(())
+}
+
+/// Track f0 alone (DIO -> StoneMask) with custom search settings, on the
+/// frame grid of an analysis with `frame_period` (ms). Unvoiced and
+/// non-finite frames are 0.
+///
+/// # Panics
+///
+/// Panics if `audio` is empty, `sample_rate` is not positive, or `audio` length
+/// exceeds `i32::MAX`.
+#[must_use]
+pub fn track_f0(audio: &[f64], sample_rate: i32, search: &F0Search, frame_period: f64) -> Vec<f64> {
+    let mut dio_option = unsafe { init_option(InitializeDioOption) };
+    dio_option.f0_floor = search.f0_floor;
+    dio_option.f0_ceil = search.f0_ceil;
+    dio_option.channels_in_octave = search.channels_in_octave;
+    dio_option.frame_period = frame_period;
+    f0_contour(audio, sample_rate, &dio_option, true).1
+}
+
+/// The f0 contour [`analyze_with_options`] would find (DIO -> StoneMask,
+/// or DIO alone without `options.refine_f0`), without the spectral
+/// analysis: `(temporal_positions, f0)`, unvoiced and non-finite frames 0.
+/// A fraction of the full analysis' time, for showing pitch before it
+/// finishes.
+///
+/// # Errors
+///
+/// Returns an error if `options` fails [`AnalyzeOptions::validate`].
+///
+/// # Panics
+///
+/// Same as [`analyze`].
+pub fn analyze_f0_only(
+    audio: &[f64],
+    sample_rate: i32,
+    options: &AnalyzeOptions,
+) -> Result<(Vec<f64>, Vec<f64>), WorldError> {
+    options.validate()?;
+    let mut dio_option = unsafe { init_option(InitializeDioOption) };
+    dio_option.f0_floor = options.f0_floor;
+    dio_option.f0_ceil = options.f0_ceil;
+    dio_option.frame_period = options.frame_period;
+    dio_option.speed = options.dio_speed;
+    Ok(f0_contour(
+        audio,
+        sample_rate,
+        &dio_option,
+        options.refine_f0,
+    ))
+}
+
+/// DIO then (if `refine`) StoneMask with `dio_option`: temporal positions
+/// and f0, non-finite frames zeroed.
+fn f0_contour(
+    audio: &[f64],
+    sample_rate: i32,
+    dio_option: &DioOption,
+    refine: bool,
+) -> (Vec<f64>, Vec<f64>) {
+    assert!(!audio.is_empty(), "audio must not be empty");
+    assert!(sample_rate > 0, "sample_rate must be positive");
+    assert!(
+        audio.len() <= c_int::MAX as usize,
+        "audio length ({}) exceeds i32::MAX",
+        audio.len(),
+    );
+
+    let x_length = audio.len() as c_int;
+    let f0_length_raw = unsafe { GetSamplesForDIO(sample_rate, x_length, dio_option.frame_period) };
+    assert!(
+        f0_length_raw > 0,
+        "GetSamplesForDIO returned non-positive value: {f0_length_raw}",
+    );
+    let f0_length = f0_length_raw as usize;
+
+    let mut temporal_positions = vec![0.0f64; f0_length];
+    let mut f0 = vec![0.0f64; f0_length];
+    unsafe {
+        Dio(
+            audio.as_ptr(),
+            x_length,
+            sample_rate,
+            dio_option,
+            temporal_positions.as_mut_ptr(),
+            f0.as_mut_ptr(),
+        );
+    }
+    let mut refined_f0 = if refine {
+        stone_mask(audio, sample_rate, &temporal_positions, &f0)
+    } else {
+        f0
+    };
+    for val in &mut refined_f0 {
+        if !val.is_finite() {
+            *val = 0.0;
+        }
+    }
+    (temporal_positions, refined_f0)
+}
+
+/// StoneMask's refinement of DIO's `f0` at `temporal_positions`.
+fn stone_mask(audio: &[f64], sample_rate: i32, temporal_positions: &[f64], f0: &[f64]) -> Vec<f64> {
+    let mut refined_f0 = vec![0.0f64; f0.len()];
+    unsafe {
+        StoneMask(
+            audio.as_ptr(),
+            audio.len() as c_int,
+            sample_rate,
+            temporal_positions.as_ptr(),
+            f0.as_ptr(),
+            f0.len() as c_int,
+            refined_f0.as_mut_ptr(),
+        );
+    }
+    refined_f0
 }
 
 /// Analyze audio using WORLD vocoder (DIO -> StoneMask -> CheapTrick -> D4C).
@@ -243,7 +886,47 @@ where
 /// exceeds `i32::MAX`.
 #[must_use]
 pub fn analyze(audio: &[f64], sample_rate: i32) -> WorldParams {
-    analyze_with_progress(audio, sample_rate, |_| {})
+    analyze_with_progress(
+        audio,
+        sample_rate,
+        &AnalyzeOptions::default(),
+        &AtomicBool::new(false),
+        |_| {},
+    )
+    .expect("WORLD's default analysis options are valid")
+}
+
+/// [`analyze`] with custom settings.
+///
+/// # Errors
+///
+/// Returns an error if `options` fails [`AnalyzeOptions::validate`].
+///
+/// # Panics
+///
+/// Same as [`analyze`].
+pub fn analyze_with_options(
+    audio: &[f64],
+    sample_rate: i32,
+    options: &AnalyzeOptions,
+) -> Result<WorldParams, WorldError> {
+    analyze_with_progress(audio, sample_rate, options, &AtomicBool::new(false), |_| {})
+}
+
+/// Samples WORLD synthesizes from `params` at `sample_rate`: one frame
+/// period per frame after the first, plus one.
+pub(crate) fn output_length(params: &WorldParams, sample_rate: i32) -> Result<usize, WorldError> {
+    let y_length_f =
+        (params.f0.len() as f64 - 1.0) * params.frame_period / 1000.0 * sample_rate as f64 + 1.0;
+    // #19: Guard against unreasonable allocation sizes (compared as f64, so
+    // an over-long output is an error rather than clamped to the maximum).
+    if y_length_f > MAX_SYNTHESIS_SAMPLES as f64 {
+        return Err(WorldError::AllocationTooLarge {
+            requested: y_length_f as usize,
+            max: MAX_SYNTHESIS_SAMPLES,
+        });
+    }
+    Ok(y_length_f.max(1.0) as usize)
 }
 
 /// Synthesize audio from WORLD parameters.
@@ -251,29 +934,37 @@ pub fn analyze(audio: &[f64], sample_rate: i32) -> WorldParams {
 /// Returns the reconstructed audio waveform, or an error if parameters are
 /// invalid or the output would be too large.
 pub fn synthesize(params: &WorldParams, sample_rate: i32) -> Result<Vec<f64>, WorldError> {
+    let mut y = Vec::new();
+    synthesize_into(params, sample_rate, &mut y)?;
+    Ok(y)
+}
+
+/// [`synthesize`] into `out`, which is resized to the output length and
+/// keeps its allocation, so a caller synthesizing repeatedly allocates only
+/// when the output grows. Returns the output length.
+///
+/// # Errors
+///
+/// As [`synthesize`]; `out` is left untouched.
+pub fn synthesize_into(
+    params: &WorldParams,
+    sample_rate: i32,
+    out: &mut Vec<f64>,
+) -> Result<usize, WorldError> {
     if sample_rate <= 0 {
-        return Err(WorldError::InvalidParams("sample_rate must be positive".into()));
+        return Err(WorldError::InvalidParams(
+            "sample_rate must be positive".into(),
+        ));
     }
     params.validate()?;
 
     let fs = sample_rate;
     let f0_length = params.f0.len() as c_int;
+    let y_length = output_length(params, sample_rate)?;
 
-    // Calculate output length — clamp before cast to prevent UB from Inf/NaN.
-    let y_length_f = (params.f0.len() as f64 - 1.0) * params.frame_period / 1000.0
-        * sample_rate as f64
-        + 1.0;
-    let y_length = y_length_f.min(MAX_SYNTHESIS_SAMPLES as f64).max(1.0) as usize;
-
-    // #19: Guard against unreasonable allocation sizes.
-    if y_length > MAX_SYNTHESIS_SAMPLES {
-        return Err(WorldError::AllocationTooLarge {
-            requested: y_length,
-            max: MAX_SYNTHESIS_SAMPLES,
-        });
-    }
-
-    let mut y = vec![0.0f64; y_length];
+    out.clear();
+    out.resize(y_length, 0.0);
+    let y = out;
 
     // Build pointer arrays for spectrogram and aperiodicity
     let sp_ptrs: Vec<*const f64> = params.spectrogram.iter().map(|row| row.as_ptr()).collect();
@@ -293,5 +984,5 @@ pub fn synthesize(params: &WorldParams, sample_rate: i32) -> Result<Vec<f64>, Wo
         );
     }
 
-    Ok(y)
+    Ok(y_length)
 }
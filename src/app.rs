@@ -1,8 +1,63 @@
-use crate::audio::decoder::AudioData;
-use crate::audio::playback::PlaybackState;
-use crate::dsp::effects::EffectsParams;
-use crate::dsp::modifier::WorldSliderValues;
+use crate::audio::decoder::{AudioData, AudioFormat};
+use crate::audio::edit::{LengthMode, Splice};
+use crate::audio::playback::{PlaybackState, LIVE_GAIN_MAX_DB, LIVE_GAIN_MIN_DB};
+use crate::config::Config;
+use crate::dsp::classify::{self, Classification, ContentClass};
+use crate::dsp::effects::{
+    EffectStage, EffectsParams, EqLayout, EqParams, ParametricBand, ParametricKind, ReverbPreset,
+    EQ_BAND_RANGE_DB, EQ_Q_RANGE, LIMITER_MIN_CEILING_DB, PARAMETRIC_GAIN_RANGE_DB,
+    PARAMETRIC_MAX_BANDS, PARAMETRIC_Q_RANGE, REVERB_MAX_DAMPING, REVERB_SIZE_RANGE,
+};
+use crate::dsp::f0_candidates::{F0Choice, F0_CANDIDATE_COUNT};
+use crate::dsp::inspect::FrameInfo;
+use crate::dsp::loudness::AbLoudness;
+use crate::dsp::modifier::{
+    self, Automation, FormantWarp, GrowlRate, RangeCenter, RetuneScale, VoicePreset,
+    WorldSliderValues, NOTE_NAMES,
+};
+use crate::dsp::processing::{FrameSnapshot, SharedEffects};
+use crate::dsp::spectrum::{SpectralPeak, SpectrumAutoRange};
+use crate::session::SessionStats;
+use crate::terminal;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long the spectrum highlights an EQ band after its gain changes.
+pub const EQ_HIGHLIGHT_SECS: f32 = 1.0;
+
+/// The Comp Makeup slider's bottom stop: automatic makeup
+/// ([`EffectsParams::comp_makeup_db`] `None`) rather than a fixed gain.
+pub const COMP_MAKEUP_AUTO: f64 = -0.5;
+
+/// The EQ Width slider's bottom stop: each layout's own Q
+/// ([`EqParams::q`] `None`).
+pub const EQ_Q_AUTO: f64 = 0.6;
+
+/// The Loudness Target slider's bottom stop: normalization off
+/// ([`EffectsParams::target_lufs`] `None`).
+pub const LUFS_TARGET_OFF: f64 = -24.5;
+
+/// Editable parameters of each parametric EQ band, in the order the EQ
+/// panel's parametric mode steps through them with Left/Right.
+pub const PARAMETRIC_FIELDS: [&str; 4] = ["Freq", "Q", "Gain", "Type"];
+
+/// Parametric EQ bands before any edits: peaks spread over the voice range,
+/// all at 0 dB (off).
+pub const PARAMETRIC_DEFAULTS: [ParametricBand; PARAMETRIC_MAX_BANDS] = [
+    parametric_default(200.0),
+    parametric_default(800.0),
+    parametric_default(3000.0),
+    parametric_default(8000.0),
+];
+
+const fn parametric_default(freq_hz: f32) -> ParametricBand {
+    ParametricBand {
+        freq_hz,
+        q: 1.41,
+        gain_db: 0.0,
+        kind: ParametricKind::Peak,
+    }
+}
 
 /// Which mode the UI is in.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -10,9 +65,81 @@ pub enum AppMode {
     Normal,
     FilePicker,
     Saving,
+    /// "Go to" timestamp prompt (Transport focus, `g`).
+    Seeking,
     Help,
+    Stats,
+    /// First-run walkthrough; see [`TOUR_STEPS`].
+    Tour,
+    /// f0 candidate correction over a time range (`c`, extended analysis).
+    F0Correct,
+    /// Choosing the region of B a clip is punched in over (`e`).
+    PunchIn,
+    /// Rearranging the effects chain (`O`).
+    ChainOrder,
 }
 
+/// Panel a walkthrough step points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TourTarget {
+    WorldSliders,
+    EffectsSliders,
+    Master,
+    EqBands,
+    Spectrum,
+    Transport,
+}
+
+/// One step of the first-run walkthrough.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TourStep {
+    pub target: TourTarget,
+    pub title: &'static str,
+    /// Two sentences: what the panel is, and how to use it.
+    pub text: &'static str,
+}
+
+/// The walkthrough, one panel per step, in screen reading order.
+pub const TOUR_STEPS: [TourStep; 6] = [
+    TourStep {
+        target: TourTarget::WorldSliders,
+        title: "WORLD Vocoder",
+        text: "These sliders re-synthesize the voice: pitch, pitch range, speed, breathiness, \
+               formants and spectral tilt. Pick one with \u{2191}/\u{2193} and change it with \
+               \u{2190}/\u{2192}; Tab moves to the next panel.",
+    },
+    TourStep {
+        target: TourTarget::EffectsSliders,
+        title: "Effects",
+        text: "Filters, compressor, reverb and a pitch-shift effect run after the vocoder. \
+               They re-apply quickly because the voice is not re-synthesized.",
+    },
+    TourStep {
+        target: TourTarget::Master,
+        title: "Master",
+        text: "Output Gain sets the playback and export level. Press a to compare the \
+               original (A) with the processed result (B).",
+    },
+    TourStep {
+        target: TourTarget::EqBands,
+        title: "Equalizer",
+        text: "Twelve bands from 31 Hz to 16 kHz shape the final tone. Select a band with \
+               \u{2190}/\u{2192} and boost or cut it with \u{2191}/\u{2193}; d resets it.",
+    },
+    TourStep {
+        target: TourTarget::Spectrum,
+        title: "Spectrum",
+        text: "The live spectrum of what you are hearing, highlighting the EQ band you just \
+               changed. Press p while paused to read exact peak frequencies.",
+    },
+    TourStep {
+        target: TourTarget::Transport,
+        title: "Transport",
+        text: "Space plays and pauses, [ and ] seek by five seconds, and r loops. Open a \
+               file with o, export with s, and press ? for every key.",
+    },
+];
+
 /// Which panel has keyboard focus.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PanelFocus {
@@ -44,10 +171,83 @@ pub enum Action {
     PrecheckAudio(String),
     Resynthesize,
     ReapplyEffects,
-    /// Live gain update — carries pre-computed linear multiplier.
+    /// Live gain update — carries the Output Gain slider value in dB.
     LiveGain(f32),
     ToggleAB,
     ExportWav(String),
+    /// Ctrl+Z: restore the terminal and stop the process (job control).
+    Suspend,
+    /// The walkthrough was completed or skipped; record it so it does not
+    /// auto-start again.
+    TourFinished,
+    /// Put an f0 candidate into the cached analysis over a range.
+    ApplyF0Candidate(F0Choice),
+    /// Splice this file into B over [`AppState::punch_region`].
+    PunchIn(String),
+    /// Re-send `Analyze` for [`AppState::original_audio`] after the worker
+    /// lost its analysis.
+    Reanalyze,
+    /// Re-analyze [`AppState::original_audio`] with this D4C voicing
+    /// threshold (`t`/`T`).
+    SetVoicingThreshold(f64),
+}
+
+/// Shown while [`AppState::analysis_lost`] is set.
+pub const ANALYSIS_LOST_MSG: &str = "Analysis lost — press R to re-analyze";
+
+/// Longest a resynthesis due while on A waits for the previous one.
+pub const MAX_RESYNTH_DEFER: Duration = Duration::from_secs(10);
+
+/// Match rows the file picker shows when the terminal has room.
+pub const FILE_PICKER_ROWS: usize = 5;
+
+/// How far `←`/`→` move the end of an f0 correction range.
+pub const F0_CORRECTION_STEP_SECS: f64 = 0.05;
+
+/// An f0 correction in progress: the range runs from the source time it
+/// was started at to `end_secs`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct F0Correction {
+    pub anchor_secs: f64,
+    pub end_secs: f64,
+    /// Candidate applied last (0 = best); `None` until the first cycle.
+    pub rank: Option<usize>,
+}
+
+/// How far `←`/`→` move the end of a punch-in region.
+pub const PUNCH_STEP_SECS: f64 = 0.05;
+
+/// How far `t`/`T` move the D4C voicing threshold.
+pub const VOICING_THRESHOLD_STEP: f64 = 0.05;
+
+/// A punch-in being set up: the region of the processed buffer (B) from
+/// the time it was started at to `end_secs`, and how a clip of another
+/// length is fitted in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PunchRegion {
+    pub anchor_secs: f64,
+    pub end_secs: f64,
+    pub mode: LengthMode,
+}
+
+impl PunchRegion {
+    /// Region bounds in order, in seconds.
+    pub fn bounds(&self) -> (f64, f64) {
+        (
+            self.anchor_secs.min(self.end_secs),
+            self.anchor_secs.max(self.end_secs),
+        )
+    }
+}
+
+/// What the file picker's choice is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PickerPurpose {
+    /// Load the file (`o`).
+    #[default]
+    Open,
+    /// Punch the file in over [`AppState::punch_region`].
+    PunchIn,
 }
 
 /// Info about the currently loaded file.
@@ -73,6 +273,9 @@ pub struct SliderDef {
     pub default: f64,
     pub step: f64,
     pub unit: &'static str,
+    /// Names for a stepped selector: when non-empty, the value is an index
+    /// into this list and the slider shows the name instead of the number.
+    pub choices: &'static [&'static str],
 }
 
 impl SliderDef {
@@ -108,8 +311,55 @@ impl SliderDef {
         }
         (self.value - self.min) / (self.max - self.min)
     }
+
+    /// What a slider at its special bottom stop shows instead of the number:
+    /// "Auto" for the compressor makeup and EQ width, "Off" for the loudness
+    /// target.
+    pub fn stop_label(&self) -> Option<&'static str> {
+        match self.label {
+            "Comp Makeup" if self.value <= COMP_MAKEUP_AUTO => Some("Auto"),
+            "EQ Width" if self.value <= EQ_Q_AUTO => Some("Auto"),
+            "Loudness Target" if self.value <= LUFS_TARGET_OFF => Some("Off"),
+            _ => None,
+        }
+    }
+
+    /// Name of the current choice for stepped selector sliders.
+    pub fn choice_label(&self) -> Option<&'static str> {
+        if self.choices.is_empty() || !self.value.is_finite() {
+            return None;
+        }
+        let idx = (self.value.round().max(0.0) as usize).min(self.choices.len() - 1);
+        Some(self.choices[idx])
+    }
 }
 
+/// Indices into `world_sliders` of the voice preset selector and its amount;
+/// changing either sets the sliders the preset covers.
+const VOICE_PRESET_SLIDERS: [usize; 2] = [17, 18];
+/// Choices of the voice preset selector: none, then [`VoicePreset::NAMES`].
+const VOICE_PRESET_CHOICES: [&str; 5] = ["None", "Feminize", "Masculinize", "Child", "Elderly"];
+
+/// Indices into `world_sliders` of the sliders that only reshape the spectral
+/// envelope and can therefore be previewed on a single analysis frame.
+const SPECTRAL_WORLD_SLIDERS: [usize; 5] = [4, 5, 16, 20, 24];
+
+/// Index into `world_sliders` of the Pitch Range slider, whose centre
+/// `Enter` cycles.
+const PITCH_RANGE_SLIDER: usize = 1;
+
+/// Index into `world_sliders` of the Formant Shift slider, whose warp
+/// `Enter` cycles.
+const FORMANT_SLIDER: usize = 4;
+
+/// Index into `world_sliders` of the Humanize slider, whose seed `Enter`
+/// re-rolls.
+const HUMANIZE_SLIDER: usize = 25;
+
+/// How far either side of the playhead a `,`/`.` pitch nudge reaches, in
+/// source seconds: about a syllable.
+pub const PITCH_NUDGE_SECS: f64 = 0.15;
+
 /// All application state for the TUI.
 pub struct AppState {
     pub mode: AppMode,
@@ -122,9 +372,15 @@ pub struct AppState {
     pub playback: PlaybackState,
     pub audio_data: Option<Arc<AudioData>>,
     pub original_audio: Option<Arc<AudioData>>,
+    /// `(source s, f0 Hz)` of the loaded file, sent ahead of the analysis
+    /// (`PitchCurve`); read by the transport's pitch readout.
+    pub pitch_curve: Option<Vec<(f64, f64)>>,
     pub processing_status: Option<String>,
     pub loop_enabled: bool,
     pub ab_original: bool,
+    /// A slider changed while on A: the transport shows "Listening to A"
+    /// until the user toggles to B.
+    pub ab_hint: bool,
     pub should_quit: bool,
     pub file_picker_input: String,
     /// L-11: Cursor position within file_picker_input (byte offset).
@@ -135,18 +391,123 @@ pub struct AppState {
     pub file_picker_scroll: usize,
     /// File picker selection: index into file_picker_matches; None when user is typing or list empty.
     pub file_picker_selected: Option<usize>,
+    /// Match rows the picker shows at the current terminal size (at most
+    /// [`FILE_PICKER_ROWS`]); see [`AppState::clamp_file_picker`].
+    pub file_picker_rows: usize,
     pub status_message: Option<String>,
     /// L-12: When the status message was set. Used for auto-clear after timeout.
     pub status_message_time: Option<std::time::Instant>,
     pub spectrum_bins: Vec<f32>,
-    /// Gain values for 12-band EQ in dB.
-    pub eq_gains: [f64; 12],
-    /// Currently selected EQ band (0-11).
+    /// Graphic EQ band layout (`b` switches).
+    pub eq_layout: EqLayout,
+    /// Gain of each graphic EQ band in dB, one per band of `eq_layout`.
+    pub eq_gains: Vec<f64>,
+    /// Currently selected EQ band (an index into `eq_gains`).
     pub eq_selected_band: usize,
+    /// Parametric EQ bands, run after the graphic EQ (0 dB = off).
+    pub eq_parametric: [ParametricBand; PARAMETRIC_MAX_BANDS],
+    /// Whether the EQ panel edits the parametric bands instead of the
+    /// graphic ones (Enter toggles).
+    pub eq_parametric_mode: bool,
+    /// Selected parametric cell: `band * PARAMETRIC_FIELDS.len() + field`.
+    pub eq_parametric_cell: usize,
     /// WORLD bypass: when true, skip WORLD synthesis and route original mono → effects.
     pub world_bypass: bool,
+    /// Whisper mode: resynthesize every frame unvoiced (noise only).
+    pub world_whisper: bool,
+    /// Trim B to A's loudness after the effects (`l` toggles); see
+    /// [`EffectsParams::loudness_match`].
+    pub loudness_match: bool,
+    /// Effects chain order ([`EffectsParams::order`]), seeded from
+    /// `--fx-order` and rearranged in the chain order overlay.
+    pub effects_order: Vec<EffectStage>,
+    /// Stage selected in the chain order overlay.
+    pub chain_order_selected: usize,
+    /// Source seconds the WORLD sliders are confined to
+    /// ([`WorldSliderValues::region`]), set with `m`.
+    pub world_region: Option<(f64, f64)>,
+    /// Start of a WORLD region whose end hasn't been marked yet.
+    pub world_region_start: Option<f64>,
+    /// What the Pitch Range slider expands around
+    /// ([`WorldSliderValues::pitch_range_center`]); `Enter` on it cycles.
+    pub world_range_center: RangeCenter,
+    /// How the Formant Shift slider maps the envelope
+    /// ([`WorldSliderValues::formant_warp`]); `Enter` on it cycles.
+    pub world_formant_warp: FormantWarp,
+    /// Seed of the Humanize slider's drift
+    /// ([`WorldSliderValues::humanize_seed`]); `Enter` on it re-rolls.
+    pub world_humanize_seed: u64,
+    /// Pitch-edit curve ([`WorldSliderValues::f0_offsets`]), built up with
+    /// `,`/`.`.
+    pub world_f0_offsets: Vec<(f64, f64)>,
+    /// Slider ramps ([`WorldSliderValues::automation`]), set with `k`.
+    pub world_automation: Automation,
+    /// First point of a `k` ramp whose end hasn't been marked yet:
+    /// `(slider index, source seconds, value)`.
+    pub world_automation_start: Option<(usize, f64, f64)>,
     /// Stale-result guard for precheck: expecting AudioPrecheckDone/Failed for this path.
     pub awaiting_load_path: Option<String>,
+    /// Session-wide counters shown in the stats overlay.
+    pub session_stats: SessionStats,
+    /// Analysis frame near the playhead, used for the spectral slider preview.
+    pub frame_snapshot: Option<FrameSnapshot>,
+    /// A `SnapshotFrame` request is in flight; avoids re-requesting per keypress.
+    pub frame_snapshot_requested: bool,
+    /// Spectrum display: percentile auto-range (true) or fixed [-80, 0] dB.
+    pub spectrum_auto_range: bool,
+    /// Rolling level range, updated wherever `spectrum_bins` is computed.
+    pub spectrum_range: SpectrumAutoRange,
+    /// Inspect toggle: while paused, the spectrum uses the large FFT and shows peak readouts.
+    pub spectrum_inspect: bool,
+    /// Loudest peaks of the paused inspect window, loudest first.
+    pub spectrum_peaks: Vec<SpectralPeak>,
+    /// Startup configuration (CLI flags, safe mode).
+    pub config: Config,
+    /// Generation of the processed buffer in `audio_data`; bumped on every
+    /// `SynthesisDone` so exports can say which one they captured.
+    pub processed_revision: u64,
+    /// Label of the snapshot the worker is currently exporting (e.g. "B rev 14").
+    pub export_in_flight: Option<String>,
+    /// Latest progress line of that export (percentage, throughput, ETA).
+    pub export_progress: Option<String>,
+    /// A/B levels of the latest processed buffer, for the loudness readout.
+    pub ab_loudness: Option<AbLoudness>,
+    /// Parse error shown inside the seek prompt; cleared on the next edit.
+    pub seek_error: Option<String>,
+    /// When an EQ band gain last changed, for the spectrum's band highlight.
+    pub eq_adjusted_at: Option<Instant>,
+    /// Frame inspector overlay (`f`): raw WORLD values at the playhead.
+    pub inspector_open: bool,
+    /// Latest inspector values from the worker.
+    pub frame_info: Option<FrameInfo>,
+    /// Source playhead time `frame_info` was requested for.
+    pub frame_info_playhead: Option<f64>,
+    /// An `InspectFrame` request is in flight.
+    pub frame_info_requested: bool,
+    /// Index into [`TOUR_STEPS`] while the walkthrough is showing.
+    pub tour_step: Option<usize>,
+    /// Whether the terminal has focus. Unfocused, the spectrum stops
+    /// updating and the UI redraws at ~2 fps; audio and processing go on.
+    pub terminal_focused: bool,
+    /// The f0 correction being edited (`AppMode::F0Correct`).
+    pub f0_correction: Option<F0Correction>,
+    /// A candidate was applied to this file's analysis; see
+    /// [`WorldSliderValues::f0_edited`].
+    pub f0_edited: bool,
+    /// Content class of the analyzed file, shown in the status bar.
+    pub content_class: Option<Classification>,
+    /// The punch-in being set up (`AppMode::PunchIn`, then the picker).
+    pub punch_region: Option<PunchRegion>,
+    pub picker_purpose: PickerPurpose,
+    /// B holds a punch-in that has not been exported yet. The next
+    /// resynthesis replaces B and drops it.
+    pub b_edited: bool,
+    /// The worker caught a panic and dropped its analysis: sliders can't
+    /// resynthesize until `R` re-analyzes the file.
+    pub analysis_lost: bool,
+    /// The effects parameters as last published for the worker and
+    /// realtime readers; see [`AppState::publish_effects`].
+    pub effects: SharedEffects,
 }
 
 impl AppState {
@@ -162,22 +523,69 @@ impl AppState {
             playback: PlaybackState::new(),
             audio_data: None,
             original_audio: None,
+            pitch_curve: None,
             processing_status: None,
             loop_enabled: false,
             ab_original: false,
+            ab_hint: false,
             should_quit: false,
             file_picker_input: String::new(),
             input_cursor: 0,
             file_picker_matches: Vec::new(),
             file_picker_scroll: 0,
             file_picker_selected: None,
+            file_picker_rows: FILE_PICKER_ROWS,
             status_message: None,
             status_message_time: None,
             spectrum_bins: Vec::new(),
-            eq_gains: [0.0; 12],
+            eq_layout: EqLayout::default(),
+            eq_gains: vec![0.0; EqLayout::default().band_count()],
             eq_selected_band: 0,
+            eq_parametric: PARAMETRIC_DEFAULTS,
+            eq_parametric_mode: false,
+            eq_parametric_cell: 0,
             world_bypass: false,
+            world_whisper: false,
+            loudness_match: false,
+            effects_order: EffectStage::DEFAULT_ORDER.to_vec(),
+            chain_order_selected: 0,
+            world_region: None,
+            world_region_start: None,
+            world_range_center: RangeCenter::default(),
+            world_formant_warp: FormantWarp::default(),
+            world_humanize_seed: 0,
+            world_f0_offsets: Vec::new(),
+            world_automation: Automation::default(),
+            world_automation_start: None,
             awaiting_load_path: None,
+            session_stats: SessionStats::new(),
+            frame_snapshot: None,
+            frame_snapshot_requested: false,
+            spectrum_auto_range: false,
+            spectrum_range: SpectrumAutoRange::new(),
+            spectrum_inspect: false,
+            spectrum_peaks: Vec::new(),
+            config: Config::default(),
+            processed_revision: 0,
+            export_in_flight: None,
+            export_progress: None,
+            ab_loudness: None,
+            seek_error: None,
+            eq_adjusted_at: None,
+            inspector_open: false,
+            frame_info: None,
+            frame_info_playhead: None,
+            frame_info_requested: false,
+            terminal_focused: true,
+            tour_step: None,
+            f0_correction: None,
+            f0_edited: false,
+            content_class: None,
+            punch_region: None,
+            picker_purpose: PickerPurpose::Open,
+            b_edited: false,
+            analysis_lost: false,
+            effects: SharedEffects::default(),
         }
     }
 
@@ -191,6 +599,7 @@ impl AppState {
                 default: 0.0,
                 step: 0.5,
                 unit: "st",
+                choices: &[],
             },
             SliderDef {
                 label: "Pitch Range",
@@ -200,6 +609,7 @@ impl AppState {
                 default: 1.0,
                 step: 0.1,
                 unit: "×",
+                choices: &[],
             },
             SliderDef {
                 label: "Speed",
@@ -209,6 +619,7 @@ impl AppState {
                 default: 1.0,
                 step: 0.05,
                 unit: "×",
+                choices: &[],
             },
             SliderDef {
                 label: "Breathiness",
@@ -218,6 +629,7 @@ impl AppState {
                 default: 0.0,
                 step: 0.1,
                 unit: "×",
+                choices: &[],
             },
             SliderDef {
                 label: "Formant Shift",
@@ -227,6 +639,7 @@ impl AppState {
                 default: 0.0,
                 step: 0.5,
                 unit: "st",
+                choices: &[],
             },
             SliderDef {
                 label: "Spectral Tilt",
@@ -235,7 +648,208 @@ impl AppState {
                 value: 0.0,
                 default: 0.0,
                 step: 0.5,
-                unit: "dB/oct",
+                unit: "dB/oct @1k",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Flatten",
+                min: 0.0,
+                max: 1.0,
+                value: 0.0,
+                default: 0.0,
+                step: 0.1,
+                unit: "×",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Flatten To",
+                min: 0.0,
+                max: 500.0,
+                value: 0.0,
+                default: 0.0,
+                step: 10.0,
+                unit: "Hz (0 = median)",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Retune",
+                min: 0.0,
+                max: 1.0,
+                value: 0.0,
+                default: 0.0,
+                step: 0.1,
+                unit: "×",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Retune Speed",
+                min: 0.0,
+                max: 500.0,
+                value: 50.0,
+                default: 50.0,
+                step: 10.0,
+                unit: "ms",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Retune Scale",
+                min: 0.0,
+                max: (RetuneScale::NAMES.len() - 1) as f64,
+                value: 0.0,
+                default: 0.0,
+                step: 1.0,
+                unit: "",
+                choices: &RetuneScale::NAMES,
+            },
+            SliderDef {
+                label: "Retune Key",
+                min: 0.0,
+                max: (NOTE_NAMES.len() - 1) as f64,
+                value: 0.0,
+                default: 0.0,
+                step: 1.0,
+                unit: "",
+                choices: &NOTE_NAMES,
+            },
+            SliderDef {
+                label: "Vibrato Depth",
+                min: 0.0,
+                max: 200.0,
+                value: 0.0,
+                default: 0.0,
+                step: 10.0,
+                unit: "ct",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Vibrato Rate",
+                min: 0.5,
+                max: 10.0,
+                value: 5.0,
+                default: 5.0,
+                step: 0.5,
+                unit: "Hz",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Robot",
+                min: 0.0,
+                max: 400.0,
+                value: 0.0,
+                default: 0.0,
+                step: 10.0,
+                unit: "Hz (0 = off)",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Pitch Smoothing",
+                min: 0.0,
+                max: 9.0,
+                value: 0.0,
+                default: 0.0,
+                step: 1.0,
+                unit: "frames",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Envelope Smoothing",
+                min: 0.0,
+                max: 1.0,
+                value: 0.0,
+                default: 0.0,
+                step: 0.1,
+                unit: "×",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Voice Preset",
+                min: 0.0,
+                max: VOICE_PRESET_CHOICES.len() as f64 - 1.0,
+                value: 0.0,
+                default: 0.0,
+                step: 1.0,
+                unit: "",
+                choices: &VOICE_PRESET_CHOICES,
+            },
+            SliderDef {
+                label: "Preset Amount",
+                min: 0.0,
+                max: 1.0,
+                value: 1.0,
+                default: 1.0,
+                step: 0.1,
+                unit: "×",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Breath Crossover",
+                min: 0.0,
+                max: 8000.0,
+                value: 2000.0,
+                default: 2000.0,
+                step: 250.0,
+                unit: "Hz",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Presence",
+                min: -12.0,
+                max: 12.0,
+                value: 0.0,
+                default: 0.0,
+                step: 0.5,
+                unit: "dB",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Growl",
+                min: 0.0,
+                max: 1.0,
+                value: 0.0,
+                default: 0.0,
+                step: 0.1,
+                unit: "×",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Growl Rate",
+                min: 0.0,
+                max: 80.0,
+                value: 0.0,
+                default: 0.0,
+                step: 10.0,
+                unit: "Hz (0 = ½ f0)",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Denoise",
+                min: 0.0,
+                max: modifier::DENOISE_MAX_DB,
+                value: 0.0,
+                default: 0.0,
+                step: 1.0,
+                unit: "dB",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Nasality",
+                min: -modifier::NASALITY_MAX_DB,
+                max: modifier::NASALITY_MAX_DB,
+                value: 0.0,
+                default: 0.0,
+                step: 0.5,
+                unit: "dB",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Humanize",
+                min: 0.0,
+                max: modifier::HUMANIZE_MAX_CENTS,
+                value: 0.0,
+                default: 0.0,
+                step: 5.0,
+                unit: "ct",
+                choices: &[],
             },
         ]
     }
@@ -243,12 +857,13 @@ impl AppState {
     fn default_master_sliders() -> Vec<SliderDef> {
         vec![SliderDef {
             label: "Output Gain",
-            min: -12.0,
-            max: 12.0,
+            min: LIVE_GAIN_MIN_DB as f64,
+            max: LIVE_GAIN_MAX_DB as f64,
             value: 0.0,
             default: 0.0,
             step: 0.5,
             unit: "dB",
+            choices: &[],
         }]
     }
 
@@ -262,6 +877,7 @@ impl AppState {
                 default: 20.0,
                 step: 10.0,
                 unit: "Hz",
+                choices: &[],
             },
             SliderDef {
                 label: "High Cut",
@@ -271,6 +887,7 @@ impl AppState {
                 default: 20000.0,
                 step: 500.0,
                 unit: "Hz",
+                choices: &[],
             },
             SliderDef {
                 label: "Compressor",
@@ -280,6 +897,7 @@ impl AppState {
                 default: 0.0,
                 step: 1.0,
                 unit: "dB",
+                choices: &[],
             },
             SliderDef {
                 label: "Reverb Mix",
@@ -289,6 +907,17 @@ impl AppState {
                 default: 0.0,
                 step: 0.05,
                 unit: "",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Reverb Type",
+                min: 0.0,
+                max: (ReverbPreset::NAMES.len() - 1) as f64,
+                value: 2.0,
+                default: 2.0,
+                step: 1.0,
+                unit: "",
+                choices: &ReverbPreset::NAMES,
             },
             SliderDef {
                 label: "Pitch Shift FX",
@@ -298,16 +927,410 @@ impl AppState {
                 default: 0.0,
                 step: 0.5,
                 unit: "st",
+                choices: &[],
+            },
+            SliderDef {
+                label: "EQ Tilt",
+                min: -EQ_BAND_RANGE_DB as f64,
+                max: EQ_BAND_RANGE_DB as f64,
+                value: 0.0,
+                default: 0.0,
+                step: 0.5,
+                unit: "dB",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Limiter Ceiling",
+                min: LIMITER_MIN_CEILING_DB as f64,
+                max: 0.0,
+                value: 0.0,
+                default: 0.0,
+                step: 0.5,
+                unit: "dB (0 = off)",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Gate Threshold",
+                min: -80.0,
+                max: 0.0,
+                value: 0.0,
+                default: 0.0,
+                step: 2.0,
+                unit: "dB (0 = off)",
+                choices: &[],
+            },
+            SliderDef {
+                label: "De-esser",
+                min: -60.0,
+                max: 0.0,
+                value: 0.0,
+                default: 0.0,
+                step: 2.0,
+                unit: "dB (0 = off)",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Chorus Mix",
+                min: 0.0,
+                max: 1.0,
+                value: 0.0,
+                default: 0.0,
+                step: 0.05,
+                unit: "",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Chorus Rate",
+                min: 0.1,
+                max: 2.0,
+                value: 0.8,
+                default: 0.8,
+                step: 0.1,
+                unit: "Hz",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Delay Time",
+                min: 10.0,
+                max: 1000.0,
+                value: 250.0,
+                default: 250.0,
+                step: 10.0,
+                unit: "ms",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Delay Feedback",
+                min: 0.0,
+                max: 0.9,
+                value: 0.4,
+                default: 0.4,
+                step: 0.05,
+                unit: "",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Delay Mix",
+                min: 0.0,
+                max: 1.0,
+                value: 0.0,
+                default: 0.0,
+                step: 0.05,
+                unit: "",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Reverb Size",
+                min: REVERB_SIZE_RANGE.0 as f64,
+                max: REVERB_SIZE_RANGE.1 as f64,
+                value: 1.0,
+                default: 1.0,
+                step: 0.05,
+                unit: "×",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Reverb Damping",
+                min: 0.0,
+                max: REVERB_MAX_DAMPING as f64,
+                value: 0.0,
+                default: 0.0,
+                step: 0.05,
+                unit: "",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Reverb Pre-delay",
+                min: 0.0,
+                max: 200.0,
+                value: 0.0,
+                default: 0.0,
+                step: 5.0,
+                unit: "ms",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Comp Ratio",
+                min: 1.0,
+                max: 20.0,
+                value: 4.0,
+                default: 4.0,
+                step: 0.5,
+                unit: ":1",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Comp Attack",
+                min: 0.5,
+                max: 100.0,
+                value: 5.0,
+                default: 5.0,
+                step: 0.5,
+                unit: "ms",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Comp Release",
+                min: 10.0,
+                max: 1000.0,
+                value: 50.0,
+                default: 50.0,
+                step: 10.0,
+                unit: "ms",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Comp Makeup",
+                min: COMP_MAKEUP_AUTO,
+                max: 24.0,
+                value: COMP_MAKEUP_AUTO,
+                default: COMP_MAKEUP_AUTO,
+                step: 0.5,
+                unit: "dB",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Tremolo Depth",
+                min: 0.0,
+                max: 1.0,
+                value: 0.0,
+                default: 0.0,
+                step: 0.05,
+                unit: "",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Tremolo Rate",
+                min: 0.1,
+                max: 20.0,
+                value: 5.0,
+                default: 5.0,
+                step: 0.1,
+                unit: "Hz",
+                choices: &[],
+            },
+            SliderDef {
+                label: "Loudness Target",
+                min: LUFS_TARGET_OFF,
+                max: -10.0,
+                value: LUFS_TARGET_OFF,
+                default: LUFS_TARGET_OFF,
+                step: 0.5,
+                unit: "LUFS",
+                choices: &[],
+            },
+            SliderDef {
+                label: "FX Mix",
+                min: 0.0,
+                max: 1.0,
+                value: 1.0,
+                default: 1.0,
+                step: 0.05,
+                unit: "",
+                choices: &[],
+            },
+            SliderDef {
+                label: "EQ Width",
+                min: EQ_Q_AUTO,
+                max: EQ_Q_RANGE.1 as f64,
+                value: EQ_Q_AUTO,
+                default: EQ_Q_AUTO,
+                step: 0.1,
+                unit: "Q",
+                choices: &[],
             },
         ]
     }
 
     /// Set a status message with auto-clear timestamp (L-12).
+    /// Fit the picker's selection and scroll to the matches and to `rows`
+    /// visible rows (after a resize or a new listing): the selection stays
+    /// in range and inside the window, and the window never starts past the
+    /// last full page.
+    pub fn clamp_file_picker(&mut self, rows: usize) {
+        // With no room at all, keep the selection as the window's only row.
+        let rows = rows.max(1);
+        self.file_picker_rows = rows;
+        let total = self.file_picker_matches.len();
+        self.file_picker_selected = match self.file_picker_selected {
+            Some(_) if total == 0 => None,
+            selected => selected.map(|sel| sel.min(total - 1)),
+        };
+        self.file_picker_scroll = self.file_picker_scroll.min(total.saturating_sub(rows));
+        if let Some(sel) = self.file_picker_selected {
+            if sel < self.file_picker_scroll {
+                self.file_picker_scroll = sel;
+            } else if sel >= self.file_picker_scroll + rows {
+                self.file_picker_scroll = sel + 1 - rows;
+            }
+        }
+    }
+
     pub fn set_status(&mut self, msg: String) {
         self.status_message = Some(msg);
         self.status_message_time = Some(std::time::Instant::now());
     }
 
+    /// Record an EQ band gain change (starts the spectrum highlight).
+    pub fn mark_eq_adjusted(&mut self) {
+        self.eq_adjusted_at = Some(Instant::now());
+    }
+
+    /// The EQ band to highlight on the spectrum: the selected band while the
+    /// EQ panel is focused and its gain changed within [`EQ_HIGHLIGHT_SECS`].
+    pub fn eq_highlight_band(&self) -> Option<usize> {
+        let at = self.eq_adjusted_at?;
+        (self.focus == PanelFocus::EqBands && at.elapsed().as_secs_f32() < EQ_HIGHLIGHT_SECS)
+            .then_some(self.eq_selected_band)
+    }
+
+    /// `b`: switch the graphic EQ between 12 and 31 bands. Gains don't carry
+    /// over (the bands differ), so the new layout starts flat; returns true
+    /// when bands were reset and the effects need reapplying.
+    pub fn toggle_eq_layout(&mut self) -> bool {
+        let was_flat = self.eq_gains.iter().all(|&g| g == 0.0);
+        self.eq_layout = self.eq_layout.toggle();
+        self.eq_gains = vec![0.0; self.eq_layout.band_count()];
+        self.eq_selected_band = self.eq_selected_band.min(self.eq_gains.len() - 1);
+        self.set_status(format!(
+            "Graphic EQ: {} bands{}",
+            self.eq_gains.len(),
+            if was_flat { "" } else { " (reset to flat)" }
+        ));
+        !was_flat
+    }
+
+    /// `Enter` on the EQ panel: switch between editing the graphic and the
+    /// parametric bands.
+    pub fn toggle_parametric_eq(&mut self) {
+        self.eq_parametric_mode = !self.eq_parametric_mode;
+        self.set_status(if self.eq_parametric_mode {
+            "Parametric EQ: ←/→ select a parameter, ↑/↓ adjust".to_string()
+        } else {
+            "Graphic EQ".to_string()
+        });
+    }
+
+    /// Move the parametric cursor by `delta` cells (band parameters, band
+    /// after band), clamped to the table.
+    pub fn select_parametric_cell(&mut self, delta: isize) {
+        let last = PARAMETRIC_MAX_BANDS * PARAMETRIC_FIELDS.len() - 1;
+        self.eq_parametric_cell = self
+            .eq_parametric_cell
+            .saturating_add_signed(delta)
+            .min(last);
+    }
+
+    /// The selected parametric band and parameter (an index into
+    /// [`PARAMETRIC_FIELDS`]).
+    pub fn parametric_selection(&self) -> (usize, usize) {
+        (
+            self.eq_parametric_cell / PARAMETRIC_FIELDS.len(),
+            self.eq_parametric_cell % PARAMETRIC_FIELDS.len(),
+        )
+    }
+
+    /// Adjust the selected parametric parameter by `steps`: a semitone of
+    /// frequency, a quarter octave of Q, 0.5 dB of gain, or (by sign) the
+    /// next/previous filter type. Returns true when the band's audible
+    /// response changed and the effects need reapplying.
+    pub fn adjust_parametric(&mut self, steps: f64) -> bool {
+        let (band, field) = self.parametric_selection();
+        let Some(b) = self.eq_parametric.get_mut(band) else {
+            return false;
+        };
+        let old = *b;
+        let steps = steps as f32;
+        match field {
+            0 => b.freq_hz = (b.freq_hz * 2f32.powf(steps / 12.0)).clamp(20.0, 20000.0),
+            1 => {
+                b.q =
+                    (b.q * 2f32.powf(steps / 4.0)).clamp(PARAMETRIC_Q_RANGE.0, PARAMETRIC_Q_RANGE.1)
+            }
+            2 => {
+                b.gain_db = ((b.gain_db + 0.5 * steps) * 10.0).round() / 10.0;
+                b.gain_db = b
+                    .gain_db
+                    .clamp(-PARAMETRIC_GAIN_RANGE_DB, PARAMETRIC_GAIN_RANGE_DB);
+            }
+            _ => {
+                b.kind = if steps > 0.0 {
+                    b.kind.next()
+                } else {
+                    b.kind.prev()
+                }
+            }
+        }
+        *b != old && (b.gain_db != 0.0 || old.gain_db != 0.0)
+    }
+
+    /// `d` on the parametric EQ: the selected parameter back to its default.
+    /// Returns true when the effects need reapplying.
+    pub fn reset_parametric_cell(&mut self) -> bool {
+        let (band, field) = self.parametric_selection();
+        let (Some(b), Some(default)) = (
+            self.eq_parametric.get_mut(band),
+            PARAMETRIC_DEFAULTS.get(band),
+        ) else {
+            return false;
+        };
+        let old = *b;
+        match field {
+            0 => b.freq_hz = default.freq_hz,
+            1 => b.q = default.q,
+            2 => b.gain_db = default.gain_db,
+            _ => b.kind = default.kind,
+        }
+        *b != old && (b.gain_db != 0.0 || old.gain_db != 0.0)
+    }
+
+    /// Select the stage `delta` rows away in the chain order overlay.
+    pub fn select_chain_stage(&mut self, delta: isize) {
+        let last = self.effects_order.len().saturating_sub(1);
+        self.chain_order_selected = self
+            .chain_order_selected
+            .saturating_add_signed(delta)
+            .min(last);
+    }
+
+    /// Shift+↑/↓ in the chain order overlay: swap the selected stage with
+    /// its neighbour, keeping it selected. Returns true when the order
+    /// changed and the effects need reapplying.
+    pub fn move_chain_stage(&mut self, delta: isize) -> bool {
+        let from = self.chain_order_selected;
+        let Some(to) = from
+            .checked_add_signed(delta)
+            .filter(|&to| to < self.effects_order.len())
+        else {
+            return false;
+        };
+        self.effects_order.swap(from, to);
+        self.chain_order_selected = to;
+        true
+    }
+
+    /// `d` in the chain order overlay: back to the standard order. Returns
+    /// true when the order changed.
+    pub fn reset_chain_order(&mut self) -> bool {
+        let changed = self.effects_order != EffectStage::DEFAULT_ORDER;
+        self.effects_order = EffectStage::DEFAULT_ORDER.to_vec();
+        changed
+    }
+
+    /// Esc/Enter/`O` in the chain order overlay: close it. The order lives
+    /// only in this session, so a rearranged one is reported as the
+    /// `--fx-order` flag that starts with it next time.
+    pub fn close_chain_order(&mut self) {
+        self.mode = AppMode::Normal;
+        if self.effects_order != EffectStage::DEFAULT_ORDER {
+            let ids: Vec<&str> = self.effects_order.iter().map(|stage| stage.id()).collect();
+            self.set_status(format!(
+                "Chain order (not saved): --fx-order={}",
+                ids.join(",")
+            ));
+        }
+    }
+
     /// Reset all transient state for loading a new file.
     /// Called from main.rs on AudioPrecheckDone and CLI args.
     pub fn prepare_for_load(&mut self) {
@@ -316,8 +1339,561 @@ impl AppState {
         self.status_message_time = None;
         self.spectrum_bins.clear();
         self.ab_original = false;
+        self.ab_hint = false;
         self.original_audio = None;
+        self.pitch_curve = None;
         self.awaiting_load_path = None;
+        self.frame_snapshot = None;
+        self.frame_snapshot_requested = false;
+        self.spectrum_range.reset();
+        self.spectrum_peaks.clear();
+        self.processed_revision = 0;
+        self.ab_loudness = None;
+        self.clear_frame_info();
+        self.f0_correction = None;
+        self.f0_edited = false;
+        self.content_class = None;
+        self.punch_region = None;
+        self.world_region = None;
+        self.world_region_start = None;
+        self.world_f0_offsets.clear();
+        self.world_automation = Automation::default();
+        self.world_automation_start = None;
+        self.picker_purpose = PickerPurpose::Open;
+        self.b_edited = false;
+        self.analysis_lost = false;
+    }
+
+    /// Format of the analyzed original, which every processed buffer must
+    /// match; `None` until analysis of the current file has finished.
+    pub fn analyzed_format(&self) -> Option<AudioFormat> {
+        self.original_audio.as_ref().map(|a| a.format())
+    }
+
+    /// Format to resynthesize for, like [`AppState::analyzed_format`], but
+    /// `None` with an explicit status while the analysis is lost.
+    pub fn resynthesis_format(&mut self) -> Option<AudioFormat> {
+        if self.analysis_lost {
+            self.set_status(ANALYSIS_LOST_MSG.to_string());
+            return None;
+        }
+        self.analyzed_format()
+    }
+
+    /// The worker dropped its analysis (`AnalysisInvalidated`). Only
+    /// matters once a file was analyzed; f0 corrections and frame previews
+    /// went with the analysis.
+    pub fn invalidate_analysis(&mut self) {
+        if self.original_audio.is_none() {
+            return;
+        }
+        self.analysis_lost = true;
+        // The panic's "Internal error" would otherwise spin forever.
+        if let Some(msg) = self.processing_status.take() {
+            self.set_status(msg);
+        }
+        self.f0_correction = None;
+        self.f0_edited = false;
+        if self.mode == AppMode::F0Correct {
+            self.mode = AppMode::Normal;
+        }
+        self.frame_snapshot = None;
+        self.frame_snapshot_requested = false;
+        self.clear_frame_info();
+    }
+
+    /// The D4C voicing threshold `steps` × [`VOICING_THRESHOLD_STEP`] from
+    /// the current one (on the step grid, clamped to `0..=1`), to re-analyze
+    /// with. `None`, with a status, when nothing is analyzed or the
+    /// threshold is already at that end. f0 corrections don't survive the
+    /// re-analysis, so they are dropped here.
+    pub fn stepped_voicing_threshold(&mut self, steps: i32) -> Option<f64> {
+        if !self.config.analyze_on_load {
+            self.set_status("Analysis is off — no voicing threshold to change".to_string());
+            return None;
+        }
+        if self.original_audio.is_none() {
+            self.set_status("Load a file to change the voicing threshold".to_string());
+            return None;
+        }
+        let current = self.config.analysis.d4c_threshold;
+        let grid = (current / VOICING_THRESHOLD_STEP).round() + f64::from(steps);
+        let stepped = (grid * VOICING_THRESHOLD_STEP * 100.0).round() / 100.0;
+        let threshold = stepped.clamp(0.0, 1.0);
+        if (threshold - current).abs() < 1e-9 {
+            self.set_status(format!("Voicing threshold already at {current:.2}"));
+            return None;
+        }
+        self.f0_correction = None;
+        self.f0_edited = false;
+        if self.mode == AppMode::F0Correct {
+            self.mode = AppMode::Normal;
+        }
+        Some(threshold)
+    }
+
+    /// A slider change just queued processing. On A nothing audible would
+    /// change, so either switch to B (`--switch-to-b`) or raise the hint.
+    /// Returns `true` when it switched; the caller swaps the playing buffer.
+    pub fn note_processing_change(&mut self) -> bool {
+        if !self.ab_original {
+            return false;
+        }
+        if self.config.switch_to_b_on_change
+            && self.audio_data.is_some()
+            && self.playback.audio_lock.is_some()
+        {
+            self.ab_original = false;
+            self.ab_hint = false;
+            return true;
+        }
+        self.ab_hint = true;
+        false
+    }
+
+    /// Whether a due resynthesis should wait for the one dispatched at
+    /// `in_flight`: on A its result is not heard, so only the latest sliders
+    /// matter. Gives up after [`MAX_RESYNTH_DEFER`] in case the worker never
+    /// answers the first one.
+    pub fn defer_resynthesis(&self, in_flight: Option<Instant>) -> bool {
+        self.ab_original && in_flight.is_some_and(|t| t.elapsed() < MAX_RESYNTH_DEFER)
+    }
+
+    /// The buffer the user is hearing, with a label naming that exact
+    /// generation: the original ("A") or the processed buffer ("B rev N").
+    /// The returned Arc is a snapshot — later resyntheses don't change it.
+    pub fn export_snapshot(&self) -> Option<(Arc<AudioData>, String)> {
+        if self.ab_original {
+            self.original_audio
+                .as_ref()
+                .map(|a| (Arc::clone(a), "A".to_string()))
+        } else {
+            self.audio_data
+                .as_ref()
+                .map(|a| (Arc::clone(a), format!("B rev {}", self.processed_revision)))
+        }
+    }
+
+    /// Drop the inspector values (and any in-flight request) so the next
+    /// refresh fetches fresh ones.
+    pub fn clear_frame_info(&mut self) {
+        self.frame_info = None;
+        self.frame_info_playhead = None;
+        self.frame_info_requested = false;
+    }
+
+    /// Whether the open inspector should fetch the frame at `playhead_secs`
+    /// (source time): nothing shown yet, or the playhead has moved more than
+    /// one frame since the shown values were requested.
+    pub fn inspector_wants_frame(&self, playhead_secs: f64) -> bool {
+        if !self.inspector_open || self.frame_info_requested {
+            return false;
+        }
+        match (&self.frame_info, self.frame_info_playhead) {
+            (Some(info), Some(at)) => (playhead_secs - at).abs() > info.frame_period_ms / 1000.0,
+            _ => true,
+        }
+    }
+
+    /// Start the walkthrough at its first step (first run, or `?` twice).
+    pub fn start_tour(&mut self) {
+        self.tour_step = Some(0);
+        self.mode = AppMode::Tour;
+    }
+
+    /// Go to the next step. Returns `true` when that ends the walkthrough.
+    pub fn advance_tour(&mut self) -> bool {
+        match self.tour_step {
+            Some(step) if step + 1 < TOUR_STEPS.len() => {
+                self.tour_step = Some(step + 1);
+                false
+            }
+            _ => {
+                self.end_tour();
+                true
+            }
+        }
+    }
+
+    /// Close the walkthrough (finished or skipped).
+    pub fn end_tour(&mut self) {
+        self.tour_step = None;
+        if self.mode == AppMode::Tour {
+            self.mode = AppMode::Normal;
+        }
+    }
+
+    /// The step being shown, if the walkthrough is open.
+    pub fn current_tour_step(&self) -> Option<&'static TourStep> {
+        TOUR_STEPS.get(self.tour_step?)
+    }
+
+    /// Start an f0 correction at the playhead and open the inspector, which
+    /// shows the f0 each candidate puts there.
+    pub fn start_f0_correction(&mut self) {
+        let at = self.source_playhead_secs();
+        self.f0_correction = Some(F0Correction {
+            anchor_secs: at,
+            end_secs: at,
+            rank: None,
+        });
+        self.inspector_open = true;
+        self.mode = AppMode::F0Correct;
+    }
+
+    /// Move the end of the correction range, within the analyzed audio.
+    pub fn extend_f0_correction(&mut self, delta_secs: f64) {
+        let duration = self
+            .original_audio
+            .as_ref()
+            .map_or(0.0, |a| a.duration_secs());
+        if let Some(ref mut c) = self.f0_correction {
+            c.end_secs = (c.end_secs + delta_secs).clamp(0.0, duration);
+        }
+    }
+
+    /// Step to the next candidate (the first step skips the best one, which
+    /// is normally what the analysis already has) and return the choice to
+    /// send to the worker.
+    pub fn cycle_f0_candidate(&mut self) -> Option<F0Choice> {
+        let c = self.f0_correction.as_mut()?;
+        let rank = c.rank.map_or(1, |r| (r + 1) % F0_CANDIDATE_COUNT);
+        c.rank = Some(rank);
+        Some(F0Choice {
+            start_secs: c.anchor_secs,
+            end_secs: c.end_secs,
+            rank,
+        })
+    }
+
+    /// Leave correction mode; applied candidates stay.
+    pub fn end_f0_correction(&mut self) {
+        self.f0_correction = None;
+        if self.mode == AppMode::F0Correct {
+            self.mode = AppMode::Normal;
+        }
+    }
+
+    /// Playhead position mapped back onto the source (analysis) timeline.
+    ///
+    /// The processed buffer is time-scaled by the Speed slider; the original
+    /// (A side) is already on the source timeline.
+    pub fn source_playhead_secs(&self) -> f64 {
+        let Some(ref info) = self.file_info else {
+            return 0.0;
+        };
+        let secs = self
+            .playback
+            .current_time_secs(info.sample_rate, info.channels);
+        if self.ab_original {
+            secs
+        } else {
+            secs * self.world_slider_values().speed
+        }
+    }
+
+    /// f0 of the original at `source_secs` from the nearest point of
+    /// [`AppState::pitch_curve`]; `None` without a curve or where unvoiced.
+    pub fn pitch_at(&self, source_secs: f64) -> Option<f64> {
+        let curve = self.pitch_curve.as_deref()?;
+        let after = curve.partition_point(|&(t, _)| t < source_secs);
+        let nearest = match (after.checked_sub(1).map(|i| curve[i]), curve.get(after)) {
+            (Some(before), Some(&next)) if next.0 - source_secs < source_secs - before.0 => next,
+            (Some(before), _) => before,
+            (None, next) => *next?,
+        };
+        (nearest.1 > 0.0).then_some(nearest.1)
+    }
+
+    /// Playhead in the processed buffer (B), in seconds.
+    pub fn processed_playhead_secs(&self) -> f64 {
+        match self.audio_data {
+            Some(ref audio) => self
+                .playback
+                .current_time_secs(audio.sample_rate, audio.channels),
+            None => 0.0,
+        }
+    }
+
+    /// A WORLD slider was moved. Moving the voice preset selector or its
+    /// amount sets the pitch shift, breathiness, formant shift and tilt
+    /// sliders to the preset, which can then be fine-tuned one by one;
+    /// "None" leaves them as they are.
+    pub fn world_slider_changed(&mut self, idx: usize) {
+        if !VOICE_PRESET_SLIDERS.contains(&idx) {
+            return;
+        }
+        let [preset_idx, amount_idx] = VOICE_PRESET_SLIDERS;
+        let Some(preset) = (self.world_sliders[preset_idx].value.round() as usize)
+            .checked_sub(1)
+            .and_then(|i| VoicePreset::ALL.get(i).copied())
+        else {
+            return;
+        };
+        let values = self
+            .world_slider_values()
+            .with_preset(preset, self.world_sliders[amount_idx].value);
+        let s = &mut self.world_sliders;
+        s[0].value = values.pitch_shift;
+        s[3].value = values.breathiness;
+        s[4].value = values.formant_shift;
+        s[5].value = values.spectral_tilt;
+    }
+
+    /// `m`: mark the start of a WORLD region at the playhead, then its end
+    /// (either order), then clear it. Returns true when the region
+    /// changed, so B needs resynthesizing.
+    pub fn mark_world_region(&mut self) -> bool {
+        if self.file_info.is_none() {
+            self.set_status("Open a file before marking a region".to_string());
+            return false;
+        }
+        if self.world_region.take().is_some() {
+            self.set_status("WORLD region cleared — sliders apply everywhere".to_string());
+            return true;
+        }
+        let at = self.source_playhead_secs();
+        let Some(start) = self.world_region_start.take() else {
+            self.world_region_start = Some(at);
+            self.set_status(format!(
+                "Region start at {at:.2}s — press m again at its end"
+            ));
+            return false;
+        };
+        let region = (start.min(at), start.max(at));
+        if region.1 - region.0 < 2.0 * modifier::REGION_RAMP_SECS {
+            self.set_status("Region too short — mark its start again".to_string());
+            return false;
+        }
+        self.world_region = Some(region);
+        self.set_status(format!(
+            "WORLD sliders apply to {:.2}–{:.2}s only",
+            region.0, region.1
+        ));
+        true
+    }
+
+    /// `Enter` on a WORLD slider with a second mode: the Pitch Range's
+    /// centre, the Formant Shift's warp or the Humanize seed. Returns true
+    /// when B needs resynthesizing.
+    pub fn cycle_slider_mode(&mut self) -> bool {
+        if self.focus != PanelFocus::WorldSliders {
+            return false;
+        }
+        match self.selected_slider {
+            PITCH_RANGE_SLIDER => self.cycle_range_center(),
+            FORMANT_SLIDER => self.cycle_formant_warp(),
+            HUMANIZE_SLIDER => self.reroll_humanize_seed(),
+            _ => false,
+        }
+    }
+
+    /// `Enter` with the Humanize slider selected: a new drift from the next
+    /// seed. Returns true when B needs resynthesizing — only if the slider
+    /// is up.
+    pub fn reroll_humanize_seed(&mut self) -> bool {
+        if self.focus != PanelFocus::WorldSliders || self.selected_slider != HUMANIZE_SLIDER {
+            return false;
+        }
+        self.world_humanize_seed = self.world_humanize_seed.wrapping_add(1);
+        self.set_status(format!(
+            "Humanize drift re-rolled (seed {})",
+            self.world_humanize_seed
+        ));
+        self.world_sliders[HUMANIZE_SLIDER].value != 0.0
+    }
+
+    /// `Enter` with the Formant Shift slider selected: switch between the
+    /// linear and log-frequency warps. Returns true when B needs
+    /// resynthesizing — only if the formants are shifted.
+    pub fn cycle_formant_warp(&mut self) -> bool {
+        if self.focus != PanelFocus::WorldSliders || self.selected_slider != FORMANT_SLIDER {
+            return false;
+        }
+        self.world_formant_warp = self.world_formant_warp.next();
+        self.set_status(format!(
+            "Formant shift warps in {} frequency",
+            self.world_formant_warp.name()
+        ));
+        self.world_sliders[FORMANT_SLIDER].value != 0.0
+    }
+
+    /// `Enter` with the Pitch Range slider selected: cycle what it expands
+    /// around. Returns true when B needs resynthesizing — only if the range
+    /// is away from 1×, since the centre changes nothing otherwise.
+    pub fn cycle_range_center(&mut self) -> bool {
+        if self.focus != PanelFocus::WorldSliders || self.selected_slider != PITCH_RANGE_SLIDER {
+            return false;
+        }
+        self.world_range_center = self.world_range_center.next();
+        self.set_status(format!(
+            "Pitch range around the {}",
+            self.world_range_center.name()
+        ));
+        self.world_sliders[PITCH_RANGE_SLIDER].value != 1.0
+    }
+
+    /// `,`/`.`: nudge the pitch of the source within [`PITCH_NUDGE_SECS`] of
+    /// the playhead by `semitones`, adding to any earlier nudges there.
+    /// Returns true when the edit curve changed, so B needs resynthesizing.
+    pub fn nudge_pitch(&mut self, semitones: f64) -> bool {
+        if self.file_info.is_none() {
+            self.set_status("Open a file before editing the pitch".to_string());
+            return false;
+        }
+        let at = self.source_playhead_secs();
+        modifier::nudge_f0_offsets(
+            &mut self.world_f0_offsets,
+            (at - PITCH_NUDGE_SECS).max(0.0),
+            at + PITCH_NUDGE_SECS,
+            semitones,
+        );
+        let total = modifier::f0_offset_at(&self.world_f0_offsets, at);
+        self.set_status(format!("Pitch at {at:.2}s: {total:+.0} st"));
+        true
+    }
+
+    /// `k` with the Pitch, Breathiness or Spectral Tilt slider selected:
+    /// record the slider's value at the playhead as the start of a ramp,
+    /// then its value at the end (either order), then clear the ramp. While
+    /// the ramp is set it replaces the slider. Returns true when the
+    /// automation changed, so B needs resynthesizing.
+    pub fn mark_automation_point(&mut self) -> bool {
+        if self.file_info.is_none() {
+            self.set_status("Open a file before automating a slider".to_string());
+            return false;
+        }
+        let idx = self.selected_slider;
+        let lane_of: fn(&mut Automation) -> &mut Vec<(f64, f64)> = match idx {
+            0 if self.focus == PanelFocus::WorldSliders => |a: &mut Automation| &mut a.pitch_shift,
+            3 if self.focus == PanelFocus::WorldSliders => |a: &mut Automation| &mut a.breathiness,
+            5 if self.focus == PanelFocus::WorldSliders => {
+                |a: &mut Automation| &mut a.spectral_tilt
+            }
+            _ => {
+                self.set_status(
+                    "Only Pitch Shift, Breathiness and Spectral Tilt can be automated".to_string(),
+                );
+                return false;
+            }
+        };
+        let label = self.world_sliders[idx].label;
+        let lane = lane_of(&mut self.world_automation);
+        if !lane.is_empty() {
+            lane.clear();
+            self.world_automation_start = None;
+            self.set_status(format!(
+                "{label} ramp cleared — the slider applies everywhere"
+            ));
+            return true;
+        }
+        let at = self.source_playhead_secs();
+        let value = self.world_sliders[idx].value;
+        let Some((start_at, start_value)) = self
+            .world_automation_start
+            .take()
+            .filter(|&(start_idx, start_at, _)| start_idx == idx && start_at != at)
+            .map(|(_, start_at, start_value)| (start_at, start_value))
+        else {
+            self.world_automation_start = Some((idx, at, value));
+            self.set_status(format!(
+                "{label} ramp from {value:.2} at {at:.2}s — press k again at its end"
+            ));
+            return false;
+        };
+        *lane_of(&mut self.world_automation) = if start_at < at {
+            vec![(start_at, start_value), (at, value)]
+        } else {
+            vec![(at, value), (start_at, start_value)]
+        };
+        self.set_status(format!(
+            "{label} ramps {start_value:.2} at {start_at:.2}s \u{2192} {value:.2} at {at:.2}s"
+        ));
+        true
+    }
+
+    /// Start choosing a punch-in region at the playhead. Punch-ins edit B,
+    /// so this needs processed audio and B selected; otherwise it only
+    /// explains why.
+    pub fn start_punch_in(&mut self) {
+        if self.audio_data.is_none() {
+            self.set_status("Nothing to punch into — open a file first".to_string());
+            return;
+        }
+        if self.ab_original {
+            self.set_status("Punch-in edits B — press 'a' to switch".to_string());
+            return;
+        }
+        let at = self.processed_playhead_secs();
+        self.punch_region = Some(PunchRegion {
+            anchor_secs: at,
+            end_secs: at,
+            mode: LengthMode::default(),
+        });
+        self.mode = AppMode::PunchIn;
+    }
+
+    /// Move the end of the punch-in region, within B.
+    pub fn extend_punch_in(&mut self, delta_secs: f64) {
+        let duration = self.audio_data.as_ref().map_or(0.0, |a| a.duration_secs());
+        if let Some(ref mut r) = self.punch_region {
+            r.end_secs = (r.end_secs + delta_secs).clamp(0.0, duration);
+        }
+    }
+
+    pub fn toggle_punch_mode(&mut self) {
+        if let Some(ref mut r) = self.punch_region {
+            r.mode = r.mode.toggled();
+        }
+    }
+
+    /// The splice for the current region in frames of B, with the
+    /// configured crossfade; `None` without a non-empty region.
+    pub fn punch_splice(&self) -> Option<Splice> {
+        let region = self.punch_region?;
+        let rate = f64::from(self.audio_data.as_ref()?.sample_rate);
+        let (start, end) = region.bounds();
+        let (start, end) = (
+            (start * rate).round() as usize,
+            (end * rate).round() as usize,
+        );
+        (start < end).then(|| Splice {
+            start,
+            end,
+            fade: (self.config.punch_fade.as_secs_f64() * rate).round() as usize,
+            mode: region.mode,
+        })
+    }
+
+    /// Leave punch-in setup without splicing anything.
+    pub fn end_punch_in(&mut self) {
+        self.punch_region = None;
+        self.picker_purpose = PickerPurpose::Open;
+        if self.mode == AppMode::PunchIn {
+            self.mode = AppMode::Normal;
+        }
+    }
+
+    /// How long the main loop waits for input between redraws.
+    pub fn frame_interval(&self) -> Duration {
+        terminal::frame_interval(self.terminal_focused)
+    }
+
+    /// Whether the inspect view is showing: toggled on and playback paused.
+    pub fn inspect_active(&self) -> bool {
+        self.spectrum_inspect
+            && !self
+                .playback
+                .playing
+                .load(std::sync::atomic::Ordering::Acquire)
+    }
+
+    /// Whether a WORLD slider that reshapes the spectral envelope (Formant
+    /// Shift, Spectral Tilt, Envelope Smoothing, Presence) is selected — the
+    /// spectrum panel previews these.
+    pub fn spectral_slider_selected(&self) -> bool {
+        self.focus == PanelFocus::WorldSliders
+            && SPECTRAL_WORLD_SLIDERS.contains(&self.selected_slider)
     }
 
     /// Get the sliders for the currently focused panel.
@@ -348,21 +1924,95 @@ impl AppState {
         self.focused_sliders().len()
     }
 
+    /// Whether every processing control (WORLD, effects, EQ, f0 edits) is at
+    /// its default. The master gain is a listening level and doesn't count.
+    pub fn sliders_neutral(&self) -> bool {
+        self.world_sliders
+            .iter()
+            .chain(&self.effects_sliders)
+            .all(|s| s.value == s.default)
+            && self.eq_gains.iter().all(|&g| g == 0.0)
+            && self.eq_parametric.iter().all(|b| b.gain_db == 0.0)
+            && !self.world_bypass
+            && !self.f0_edited
+    }
+
+    /// With `--auto-preset`, load the effects preset for the classified
+    /// content, but only over untouched (neutral) controls. Returns the class
+    /// whose preset was applied; the caller reapplies effects.
+    pub fn apply_content_preset(&mut self) -> Option<ContentClass> {
+        if !self.config.auto_preset || !self.sliders_neutral() {
+            return None;
+        }
+        let class = self.content_class?.class;
+        let preset = classify::preset(class)?;
+        self.effects_sliders[0].value = preset.low_cut_hz;
+        self.effects_sliders[2].value = preset.compressor_thresh_db;
+        self.effects_sliders[3].value = preset.reverb_mix;
+        Some(class)
+    }
+
+    /// Publish the current effects parameters if they differ from the last
+    /// published snapshot. Returns the revision that holds them, for
+    /// commands that should run with (at least) these parameters.
+    pub fn publish_effects(&mut self) -> u64 {
+        let params = self.effects_params();
+        let (revision, published) = self.effects.load();
+        if *published == params {
+            revision
+        } else {
+            self.effects.publish(params)
+        }
+    }
+
     /// Extract current effects slider values.
     pub fn effects_params(&self) -> EffectsParams {
-        use crate::dsp::effects::EqParams;
         let s = &self.effects_sliders;
-        let eq_gains_f32: [f32; 12] = self.eq_gains.map(|g| g as f32);
         EffectsParams {
             gain_db: self.master_sliders[0].value as f32,
             low_cut_hz: s[0].value as f32,
             high_cut_hz: s[1].value as f32,
             compressor_thresh_db: s[2].value as f32,
             reverb_mix: s[3].value as f32,
-            pitch_shift_semitones: s[4].value as f32,
-            eq: EqParams {
-                gains: eq_gains_f32,
-            },
+            reverb_preset: ReverbPreset::from_index(s[4].value.round() as usize),
+            pitch_shift_semitones: s[5].value as f32,
+            pitch_shift_resample: self.config.resample_pitch_fx,
+            eq_tilt_db: s[6].value as f32,
+            limiter_ceiling_db: s[7].value as f32,
+            gate_thresh_db: s[8].value as f32,
+            deess_thresh_db: s[9].value as f32,
+            chorus_mix: s[10].value as f32,
+            chorus_rate_hz: s[11].value as f32,
+            delay_time_ms: s[12].value as f32,
+            delay_feedback: s[13].value as f32,
+            delay_mix: s[14].value as f32,
+            reverb_size: s[15].value as f32,
+            reverb_damping: s[16].value as f32,
+            reverb_predelay_ms: s[17].value as f32,
+            comp_ratio: s[18].value as f32,
+            comp_attack_ms: s[19].value as f32,
+            comp_release_ms: s[20].value as f32,
+            comp_makeup_db: (s[21].value > COMP_MAKEUP_AUTO).then_some(s[21].value as f32),
+            tremolo_depth: s[22].value as f32,
+            tremolo_rate_hz: s[23].value as f32,
+            target_lufs: (s[24].value > LUFS_TARGET_OFF).then_some(s[24].value as f32),
+            fx_mix: s[25].value as f32,
+            eq: self.eq_params(),
+            parametric: self.eq_parametric.to_vec(),
+            precision: self.config.effects_precision,
+            loudness_match: self.loudness_match,
+            order: self.effects_order.clone(),
+        }
+    }
+
+    /// The graphic EQ's parameters: band layout and gains, and the EQ Width
+    /// slider's Q.
+    pub fn eq_params(&self) -> EqParams {
+        let width = self.effects_sliders[26].value;
+        EqParams {
+            layout: self.eq_layout,
+            gains: self.eq_gains.iter().map(|&g| g as f32).collect(),
+            q: (width > EQ_Q_AUTO).then_some(width as f32),
         }
     }
 
@@ -371,12 +2021,47 @@ impl AppState {
         let s = &self.world_sliders;
         WorldSliderValues {
             pitch_shift: s[0].value,
+            automation: self.world_automation.clone(),
             pitch_range: s[1].value,
+            pitch_range_center: self.world_range_center,
             speed: s[2].value,
+            speed_unvoiced: 0.0,
             breathiness: s[3].value,
+            breathiness_crossover_hz: s[19].value,
             formant_shift: s[4].value,
+            formant_keep_energy: true,
+            formant_warp: self.world_formant_warp,
             spectral_tilt: s[5].value,
+            tilt_pivot_hz: modifier::TILT_PIVOT_HZ,
+            presence_db: s[20].value,
+            presence_center_hz: modifier::PRESENCE_CENTER_HZ,
+            presence_width_oct: modifier::PRESENCE_WIDTH_OCT,
+            nasality_db: s[24].value,
+            flatten_amount: s[6].value,
+            flatten_target_hz: s[7].value,
+            retune_strength: s[8].value,
+            retune_speed_ms: s[9].value,
+            retune_scale: RetuneScale::from_index(s[10].value.round() as usize),
+            retune_root: s[11].value.round() as usize,
+            humanize_cents: s[HUMANIZE_SLIDER].value,
+            humanize_seed: self.world_humanize_seed,
+            f0_offsets: self.world_f0_offsets.clone(),
+            vibrato_depth_cents: s[12].value,
+            vibrato_rate_hz: s[13].value,
+            growl_depth: s[21].value,
+            growl_rate: if s[22].value > 0.0 {
+                GrowlRate::Fixed(s[22].value)
+            } else {
+                GrowlRate::Subharmonic
+            },
+            robot_f0: (s[14].value > 0.0).then_some(s[14].value),
+            whisper: self.world_whisper,
+            region: self.world_region,
+            f0_smoothing_frames: s[15].value,
+            spectral_smoothing: s[16].value,
+            denoise_db: s[23].value,
             bypass: self.world_bypass,
+            f0_edited: self.f0_edited,
         }
     }
 }
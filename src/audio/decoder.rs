@@ -37,6 +37,73 @@ impl AudioData {
         }
         self.samples.len() / self.channels as usize
     }
+
+    /// Sample rate and channel count of this buffer.
+    #[must_use]
+    pub fn format(&self) -> AudioFormat {
+        AudioFormat {
+            sample_rate: self.sample_rate,
+            channels: self.channels,
+        }
+    }
+
+    /// `other` appended to this buffer, e.g. two takes to analyze and
+    /// process as one.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message like "concat: expected 48000 Hz mono, got 44100 Hz
+    /// mono" if `other` is in another format.
+    pub fn concat(&self, other: &AudioData) -> Result<AudioData, String> {
+        self.format().check(other.format(), "concat")?;
+        Ok(AudioData {
+            samples: [&self.samples[..], &other.samples].concat(),
+            ..*self
+        })
+    }
+}
+
+/// Sample rate and channel layout of a buffer. Passed explicitly between
+/// the UI and the worker and checked at each stage boundary, so a buffer
+/// from one file can never be processed as if it had another file's rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AudioFormat {
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+impl AudioFormat {
+    /// Mono at `sample_rate` — the format of WORLD analysis and synthesis.
+    pub fn mono(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            channels: 1,
+        }
+    }
+
+    /// `Ok` if `actual` is this format, otherwise an error naming `stage`
+    /// and both formats.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message like "effects: expected 48000 Hz mono, got 44100 Hz mono".
+    pub fn check(self, actual: AudioFormat, stage: &str) -> Result<(), String> {
+        if actual == self {
+            Ok(())
+        } else {
+            Err(format!("{stage}: expected {self}, got {actual}"))
+        }
+    }
+}
+
+impl fmt::Display for AudioFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.channels {
+            1 => write!(f, "{} Hz mono", self.sample_rate),
+            2 => write!(f, "{} Hz stereo", self.sample_rate),
+            n => write!(f, "{} Hz {n} ch", self.sample_rate),
+        }
+    }
 }
 
 /// Errors that can occur during audio decoding.
@@ -81,14 +148,15 @@ impl From<std::io::Error> for DecoderError {
 /// Decode an audio file into interleaved f32 PCM, with optional progress reporting.
 ///
 /// Supports WAV, MP3, and FLAC (depending on symphonia features).
-/// The `on_progress` closure is called with percentage (0–100) only when `n_frames` is known
-/// (e.g., WAV/FLAC files). For formats without frame count metadata, no progress is reported.
+/// The `on_progress` closure is called with the percentage (0–100) and the file's total
+/// duration in seconds, only when `n_frames` is known (e.g., WAV/FLAC files). For formats
+/// without frame count metadata, no progress is reported.
 pub fn decode_file_with_progress<F>(
     path: &Path,
     mut on_progress: F,
 ) -> Result<AudioData, DecoderError>
 where
-    F: FnMut(u8),
+    F: FnMut(u8, f64),
 {
     let file = File::open(path)?;
     let mss = MediaSourceStream::new(Box::new(file), Default::default());
@@ -199,7 +267,7 @@ where
                 // Use u128 to avoid overflow on extremely long audio files (18+ hours).
                 let pct = ((frames_decoded as u128 * 100) / total as u128).min(100) as u8;
                 if pct != last_pct {
-                    on_progress(pct);
+                    on_progress(pct, total as f64 / sample_rate as f64);
                     last_pct = pct;
                 }
             }
@@ -230,5 +298,5 @@ where
 /// Supports WAV, MP3, and FLAC (depending on symphonia features).
 /// This is a convenience wrapper around `decode_file_with_progress` that discards progress.
 pub fn decode_file(path: &Path) -> Result<AudioData, DecoderError> {
-    decode_file_with_progress(path, |_| {})
+    decode_file_with_progress(path, |_, _| {})
 }
@@ -0,0 +1,188 @@
+use std::fmt;
+
+use crate::audio::decoder::AudioData;
+
+/// How a clip whose length differs from the region is fitted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LengthMode {
+    /// Keep the region's length: a longer clip is truncated, a shorter one
+    /// padded with silence. Everything after the region stays in place.
+    #[default]
+    Fit,
+    /// Insert the whole clip: audio after the region moves by the length
+    /// difference, so the buffer grows or shrinks.
+    Shift,
+}
+
+impl LengthMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            LengthMode::Fit => "fit to region",
+            LengthMode::Shift => "shift remainder",
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            LengthMode::Fit => LengthMode::Shift,
+            LengthMode::Shift => LengthMode::Fit,
+        }
+    }
+}
+
+/// Where and how to splice a clip into a buffer. Positions are in frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Splice {
+    /// First frame of the region replaced.
+    pub start: usize,
+    /// One past the last frame replaced (`start..end`).
+    pub end: usize,
+    /// Crossfade length at each end, shortened to half of the shorter of
+    /// region and inserted audio so the two fades never overlap.
+    pub fade: usize,
+    pub mode: LengthMode,
+}
+
+/// Errors splicing a clip into a buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditError {
+    /// The clip has a different channel count from the buffer.
+    ChannelMismatch { expected: u16, got: u16 },
+    /// The clip is at another sample rate; resample it first.
+    RateMismatch { expected: u32, got: u32 },
+    /// `start..end` is empty, reversed or past the end of the buffer.
+    InvalidRegion {
+        start: usize,
+        end: usize,
+        frames: usize,
+    },
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EditError::ChannelMismatch { expected, got } => {
+                write!(f, "clip has {got} channel(s), the buffer {expected}")
+            }
+            EditError::RateMismatch { expected, got } => {
+                write!(f, "clip is {got} Hz, the buffer {expected} Hz")
+            }
+            EditError::InvalidRegion { start, end, frames } => {
+                write!(f, "region {start}..{end} is not inside 0..{frames}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+/// Linear-interpolation resample of interleaved audio to `sample_rate`.
+/// Good enough for short punch-in clips; returns a copy when the rate
+/// already matches.
+pub fn resample(audio: &AudioData, sample_rate: u32) -> AudioData {
+    let channels = audio.channels as usize;
+    let frames = audio.frame_count();
+    if audio.sample_rate == sample_rate || audio.sample_rate == 0 || frames == 0 {
+        return AudioData {
+            samples: audio.samples.clone(),
+            sample_rate,
+            channels: audio.channels,
+        };
+    }
+    let ratio = f64::from(audio.sample_rate) / f64::from(sample_rate);
+    let new_frames = (frames as f64 / ratio).round().max(1.0) as usize;
+    let mut samples = Vec::with_capacity(new_frames * channels);
+    for i in 0..new_frames {
+        let pos = i as f64 * ratio;
+        let i0 = (pos.floor() as usize).min(frames - 1);
+        let i1 = (i0 + 1).min(frames - 1);
+        let frac = (pos - i0 as f64) as f32;
+        for ch in 0..channels {
+            let a = audio.samples[i0 * channels + ch];
+            let b = audio.samples[i1 * channels + ch];
+            samples.push(a + (b - a) * frac);
+        }
+    }
+    AudioData {
+        samples,
+        sample_rate,
+        channels: audio.channels,
+    }
+}
+
+/// Replace `splice.start..splice.end` of `base` with `clip`.
+///
+/// The inserted audio (the clip, fitted to the region in [`LengthMode::Fit`])
+/// fades in over the base audio it replaces and fades out into the base
+/// audio just before the region end, which then continues unchanged, so
+/// both boundaries are continuous. The result has `base`'s length in `Fit`
+/// mode and `base − region + clip` frames in `Shift` mode.
+///
+/// # Errors
+///
+/// Rejects clips in another channel layout or sample rate, and regions that
+/// are empty or outside `base`.
+pub fn splice(base: &AudioData, clip: &AudioData, splice: &Splice) -> Result<AudioData, EditError> {
+    if clip.channels != base.channels {
+        return Err(EditError::ChannelMismatch {
+            expected: base.channels,
+            got: clip.channels,
+        });
+    }
+    if clip.sample_rate != base.sample_rate {
+        return Err(EditError::RateMismatch {
+            expected: base.sample_rate,
+            got: clip.sample_rate,
+        });
+    }
+    let frames = base.frame_count();
+    let Splice { start, end, .. } = *splice;
+    if start >= end || end > frames {
+        return Err(EditError::InvalidRegion { start, end, frames });
+    }
+
+    let channels = base.channels as usize;
+    let region = end - start;
+    let inserted = match splice.mode {
+        LengthMode::Fit => region,
+        LengthMode::Shift => clip.frame_count(),
+    };
+    let fade = splice.fade.min(region / 2).min(inserted / 2);
+    // Frame `i` of the inserted audio: the clip, silent past its end.
+    let clip_frame =
+        |i: usize, ch: usize| clip.samples.get(i * channels + ch).copied().unwrap_or(0.0);
+
+    let mut samples = Vec::with_capacity((frames - region + inserted) * channels);
+    samples.extend_from_slice(&base.samples[..start * channels]);
+    for i in 0..inserted {
+        for ch in 0..channels {
+            let value = clip_frame(i, ch);
+            let value = if i < fade {
+                // Fade in over the base audio the region starts with.
+                let w = fade_weight(i, fade);
+                base.samples[(start + i) * channels + ch] * (1.0 - w) + value * w
+            } else if i + fade >= inserted {
+                // Fade out into the base audio that leads up to `end`.
+                let j = i + fade - inserted;
+                let w = fade_weight(j, fade);
+                value * (1.0 - w) + base.samples[(end - fade + j) * channels + ch] * w
+            } else {
+                value
+            };
+            samples.push(value);
+        }
+    }
+    samples.extend_from_slice(&base.samples[end * channels..]);
+
+    Ok(AudioData {
+        samples,
+        sample_rate: base.sample_rate,
+        channels: base.channels,
+    })
+}
+
+/// Linear ramp weight of step `i` of `len`, centred in each step so neither
+/// end of the fade lands exactly on 0 or 1.
+fn fade_weight(i: usize, len: usize) -> f32 {
+    (i as f32 + 0.5) / len as f32
+}
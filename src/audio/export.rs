@@ -14,6 +14,9 @@ impl std::fmt::Display for ExportError {
 
 impl std::error::Error for ExportError {}
 
+/// Samples written between progress checks.
+const PROGRESS_CHUNK: usize = 1 << 16;
+
 /// Write interleaved f32 samples to a 16-bit PCM WAV file.
 pub fn export_wav(
     samples: &[f32],
@@ -21,6 +24,45 @@ pub fn export_wav(
     channels: u16,
     path: &Path,
 ) -> Result<(), ExportError> {
+    export_wav_with_progress(samples, sample_rate, channels, path, |_| {})
+}
+
+/// [`export_wav`], calling `on_progress` with the percentage (0–100)
+/// written each time it advances.
+pub fn export_wav_with_progress<F>(
+    samples: &[f32],
+    sample_rate: u32,
+    channels: u16,
+    path: &Path,
+    on_progress: F,
+) -> Result<(), ExportError>
+where
+    F: FnMut(u8),
+{
+    export_wav_streaming(
+        samples.iter().copied(),
+        sample_rate,
+        channels,
+        path,
+        on_progress,
+    )
+}
+
+/// Write interleaved samples from `source` to a 16-bit PCM WAV file,
+/// converting [`PROGRESS_CHUNK`] samples at a time. Memory stays bounded by
+/// one chunk however long the source is; the output is byte-identical to
+/// [`export_wav`] on the collected samples.
+pub fn export_wav_streaming<I, F>(
+    source: I,
+    sample_rate: u32,
+    channels: u16,
+    path: &Path,
+    mut on_progress: F,
+) -> Result<(), ExportError>
+where
+    I: Iterator<Item = f32> + ExactSizeIterator,
+    F: FnMut(u8),
+{
     let spec = WavSpec {
         channels,
         sample_rate,
@@ -30,11 +72,27 @@ pub fn export_wav(
     let mut writer = WavWriter::create(path, spec)
         .map_err(|e| ExportError(format!("cannot create file: {e}")))?;
 
-    for &s in samples {
-        let val = (s.clamp(-1.0, 1.0) * 32767.0) as i16;
-        writer
-            .write_sample(val)
+    let total = source.len();
+    let mut source = source.map(|s| (s.clamp(-1.0, 1.0) * 32767.0) as i16);
+    let mut written = 0;
+    let mut last_pct = 0;
+    while written < total {
+        let len = PROGRESS_CHUNK.min(total - written);
+        let mut chunk = writer.get_i16_writer(len as u32);
+        for _ in 0..len {
+            // A source shorter than its reported length pads with silence
+            // rather than leaving the header count wrong.
+            chunk.write_sample(source.next().unwrap_or(0));
+        }
+        chunk
+            .flush()
             .map_err(|e| ExportError(format!("write error: {e}")))?;
+        written += len;
+        let pct = (written as u128 * 100 / total as u128) as u8;
+        if pct != last_pct {
+            on_progress(pct);
+            last_pct = pct;
+        }
     }
 
     writer
@@ -49,10 +107,7 @@ pub fn export_wav(
 pub fn default_export_path(source_path: &str) -> String {
     let p = Path::new(source_path);
     let dir = p.parent().unwrap_or(Path::new("."));
-    let stem = p
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("output");
+    let stem = p.file_stem().and_then(|s| s.to_str()).unwrap_or("output");
 
     // M-14: Use a bounded range to avoid u32 overflow in release builds.
     for n in 1_u32..=9999 {
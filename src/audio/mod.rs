@@ -1,3 +1,4 @@
 pub mod decoder;
+pub mod edit;
 pub mod export;
 pub mod playback;
@@ -5,6 +5,97 @@ use std::sync::{Arc, RwLock};
 
 use super::decoder::AudioData;
 
+/// Lower bound of the live output gain, in dB (matches the Master slider).
+pub const LIVE_GAIN_MIN_DB: f32 = -12.0;
+/// Upper bound of the live output gain, in dB (matches the Master slider).
+pub const LIVE_GAIN_MAX_DB: f32 = 12.0;
+/// Time constant for ramping the applied gain toward its target.
+const GAIN_SMOOTHING_SECS: f32 = 0.005;
+/// Default ear-protection ceiling on the output, in dBFS.
+pub const DEFAULT_OUTPUT_CEILING_DB: f32 = -3.0;
+/// How long the ceiling clamp must stay engaged before the output is ducked.
+pub const CLAMP_DUCK_AFTER_SECS: f32 = 0.1;
+/// Gaps between clamped samples up to this long (e.g. zero crossings of a
+/// loud low tone) still count as one continuous clamp run.
+const CLAMP_HOLD_SECS: f32 = 0.02;
+/// Attenuation applied while the output is ducked, in dB.
+pub const DUCK_DB: f32 = -20.0;
+/// Code a U16 device reads as 0.0 (unsigned PCM is offset binary).
+pub const U16_MIDPOINT: u16 = 32768;
+/// Nonzero xorshift seed; the dither sequence need not differ between streams.
+const DITHER_SEED: u32 = 0x9E37_79B9;
+
+/// A device sample format the callback can write, with an explicit
+/// conversion from f32 full scale instead of cpal's `FromSample`.
+pub trait OutputSample: cpal::SizedSample {
+    /// One quantization step in f32 full scale; 0 for float formats,
+    /// which get no dither.
+    const LSB: f32;
+    fn from_f32(value: f32) -> Self;
+}
+
+impl OutputSample for f32 {
+    const LSB: f32 = 0.0;
+    fn from_f32(value: f32) -> Self {
+        value
+    }
+}
+
+impl OutputSample for i16 {
+    const LSB: f32 = 1.0 / 32768.0;
+    fn from_f32(value: f32) -> Self {
+        f32_to_i16(value)
+    }
+}
+
+impl OutputSample for u16 {
+    const LSB: f32 = 1.0 / 32768.0;
+    fn from_f32(value: f32) -> Self {
+        f32_to_u16(value)
+    }
+}
+
+/// Round f32 full scale to the nearest i16 code: 1.0 maps to 32767 (clamped),
+/// -1.0 to -32768. NaN maps to 0.
+#[must_use]
+pub fn f32_to_i16(value: f32) -> i16 {
+    // `as` saturates and sends NaN to 0.
+    (value * 32768.0).round().clamp(-32768.0, 32767.0) as i16
+}
+
+/// The i16 code shifted up by [`U16_MIDPOINT`], so 0.0 is the midpoint,
+/// -1.0 is 0 and 1.0 is 65535.
+#[must_use]
+pub fn f32_to_u16(value: f32) -> u16 {
+    (i32::from(f32_to_i16(value)) + i32::from(U16_MIDPOINT)) as u16
+}
+
+/// Next TPDF dither value in LSBs: the sum of two uniform values in
+/// [-0.5, 0.5), so in (-1, 1) with a triangular distribution. Advances the
+/// xorshift32 `state`, which must be nonzero.
+pub fn tpdf_dither(state: &mut u32) -> f32 {
+    let mut uniform = || {
+        *state ^= *state << 13;
+        *state ^= *state >> 17;
+        *state ^= *state << 5;
+        // Top 24 bits: exact in f32.
+        (*state >> 8) as f32 / (1 << 24) as f32 - 0.5
+    };
+    uniform() + uniform()
+}
+
+/// Convert decibels to a linear amplitude multiplier.
+#[must_use]
+pub fn db_to_linear(db: f32) -> f32 {
+    10.0_f32.powf(db / 20.0)
+}
+
+/// Convert a linear amplitude multiplier to decibels.
+#[must_use]
+pub fn linear_to_db(linear: f32) -> f32 {
+    20.0 * linear.log10()
+}
+
 /// Shared playback state between the main thread and the audio callback.
 #[derive(Debug)]
 pub struct PlaybackState {
@@ -15,11 +106,22 @@ pub struct PlaybackState {
     /// Handle to the audio data in the running stream's callback.
     /// Allows swapping audio without rebuilding the cpal stream.
     pub audio_lock: Option<Arc<RwLock<Arc<AudioData>>>>,
-    /// Live gain multiplier (f32 stored as bits). Applied in the audio callback
-    /// for instant (~5ms) feedback without buffer swap.
-    pub live_gain: Arc<AtomicU32>,
+    /// Target live gain multiplier (f32 stored as bits). The audio callback
+    /// ramps toward it for instant (~5ms) feedback without buffer swap.
+    /// Only written through [`PlaybackState::set_live_gain_db`].
+    live_gain: Arc<AtomicU32>,
     /// Whether playback should loop back to the start when it reaches the end.
     pub loop_enabled: Arc<AtomicBool>,
+    /// Ear-protection ceiling as a linear amplitude (f32 bits). The callback
+    /// clamps every sample to it after the live gain.
+    output_ceiling: Arc<AtomicU32>,
+    /// Set by the callback when the ceiling clamp stayed engaged for
+    /// [`CLAMP_DUCK_AFTER_SECS`]; output is ducked by [`DUCK_DB`] until the
+    /// user acknowledges via [`PlaybackState::restore_output`].
+    output_limited: Arc<AtomicBool>,
+    /// TPDF dither before converting to integer device formats. Off gives
+    /// plain rounding (for measurements).
+    pub dither: Arc<AtomicBool>,
 }
 
 impl Default for PlaybackState {
@@ -30,6 +132,11 @@ impl Default for PlaybackState {
             audio_lock: None,
             live_gain: Arc::new(AtomicU32::new(1.0_f32.to_bits())),
             loop_enabled: Arc::new(AtomicBool::new(false)),
+            output_ceiling: Arc::new(AtomicU32::new(
+                db_to_linear(DEFAULT_OUTPUT_CEILING_DB).to_bits(),
+            )),
+            output_limited: Arc::new(AtomicBool::new(false)),
+            dither: Arc::new(AtomicBool::new(true)),
         }
     }
 }
@@ -40,6 +147,63 @@ impl PlaybackState {
         Self::default()
     }
 
+    /// Set the live output gain in dB — the single writer of the gain target.
+    ///
+    /// The value is clamped to [`LIVE_GAIN_MIN_DB`, `LIVE_GAIN_MAX_DB`]. Non-finite
+    /// input is rejected and the previous gain kept, so a bad source can never
+    /// push NaN/inf into the audio callback. Returns whether the value was accepted.
+    pub fn set_live_gain_db(&self, db: f32) -> bool {
+        if !db.is_finite() {
+            log::warn!("rejected non-finite live gain: {db} dB");
+            return false;
+        }
+        let linear = db_to_linear(db.clamp(LIVE_GAIN_MIN_DB, LIVE_GAIN_MAX_DB));
+        self.live_gain.store(linear.to_bits(), Ordering::Relaxed);
+        true
+    }
+
+    /// Current live gain target as a linear multiplier.
+    #[must_use]
+    pub fn live_gain(&self) -> f32 {
+        f32::from_bits(self.live_gain.load(Ordering::Relaxed))
+    }
+
+    /// Current live gain target in dB.
+    #[must_use]
+    pub fn live_gain_db(&self) -> f32 {
+        linear_to_db(self.live_gain())
+    }
+
+    /// Set the ear-protection output ceiling in dBFS (at most 0). Non-finite
+    /// input is rejected. Returns whether the value was accepted.
+    pub fn set_output_ceiling_db(&self, db: f32) -> bool {
+        if !db.is_finite() {
+            log::warn!("rejected non-finite output ceiling: {db} dBFS");
+            return false;
+        }
+        let linear = db_to_linear(db.min(0.0));
+        self.output_ceiling
+            .store(linear.to_bits(), Ordering::Relaxed);
+        true
+    }
+
+    /// Current output ceiling in dBFS.
+    #[must_use]
+    pub fn output_ceiling_db(&self) -> f32 {
+        linear_to_db(f32::from_bits(self.output_ceiling.load(Ordering::Relaxed)))
+    }
+
+    /// Whether the output is ducked after a sustained clamp.
+    #[must_use]
+    pub fn is_output_limited(&self) -> bool {
+        self.output_limited.load(Ordering::Acquire)
+    }
+
+    /// Acknowledge the limiter: lift the duck. Returns whether it was ducked.
+    pub fn restore_output(&self) -> bool {
+        self.output_limited.swap(false, Ordering::AcqRel)
+    }
+
     /// Toggle play/pause. Returns the new playing state.
     pub fn toggle_playing(&self) -> bool {
         // fetch_xor is atomic — no TOCTOU race with the audio callback.
@@ -51,7 +215,9 @@ impl PlaybackState {
     pub fn seek_by_samples(&self, offset: isize, max_samples: usize) {
         // Use Acquire/Release to synchronize with the audio callback thread.
         let current = self.position.load(Ordering::Acquire);
-        let new_pos = (current as isize).saturating_add(offset).clamp(0, max_samples as isize) as usize;
+        let new_pos = (current as isize)
+            .saturating_add(offset)
+            .clamp(0, max_samples as isize) as usize;
         self.position.store(new_pos, Ordering::Release);
     }
 
@@ -63,6 +229,17 @@ impl PlaybackState {
         self.seek_by_samples(offset, max_samples);
     }
 
+    /// Seek to an absolute time, landing on a frame start and clamped to the
+    /// last frame of a `max_samples`-long interleaved buffer.
+    pub fn seek_to_secs(&self, secs: f64, sample_rate: u32, channels: u16, max_samples: usize) {
+        let ch = channels.max(1) as usize;
+        let frame = (secs.max(0.0) * sample_rate as f64).floor();
+        let frame = frame.min((usize::MAX / ch) as f64) as usize;
+        let last_frame = max_samples.saturating_sub(ch) / ch;
+        self.position
+            .store(frame.min(last_frame) * ch, Ordering::Release);
+    }
+
     /// Current playback time in seconds.
     #[must_use]
     pub fn current_time_secs(&self, sample_rate: u32, channels: u16) -> f64 {
@@ -90,22 +267,67 @@ impl std::error::Error for PlaybackError {}
 ///
 /// `audio` is behind an `RwLock` so the main thread can atomically swap in new
 /// `AudioData` (e.g. on file reload) without invalidating the callback reference.
-struct CallbackContext {
+/// Public so tests can drive [`write_audio_data`] without an audio device.
+pub struct CallbackContext {
     audio: Arc<RwLock<Arc<AudioData>>>,
     playing: Arc<AtomicBool>,
     position: Arc<AtomicUsize>,
     device_channels: u16,
     live_gain: Arc<AtomicU32>,
+    /// Gain actually applied, ramped toward `live_gain` once per frame.
+    applied_gain: f32,
+    /// One-pole smoothing coefficient per frame (from `GAIN_SMOOTHING_SECS`).
+    gain_smoothing: f32,
     loop_enabled: Arc<AtomicBool>,
+    output_ceiling: Arc<AtomicU32>,
+    output_limited: Arc<AtomicBool>,
+    /// Frames since a sample last hit the ceiling.
+    frames_since_clamp: usize,
+    /// Length of the current clamp run, in frames.
+    clamp_run: usize,
+    /// `CLAMP_HOLD_SECS` / `CLAMP_DUCK_AFTER_SECS` in frames.
+    clamp_hold_frames: usize,
+    duck_after_frames: usize,
+    dither: Arc<AtomicBool>,
+    /// xorshift32 state for [`tpdf_dither`]; lives here so the callback
+    /// never allocates.
+    dither_rng: u32,
+}
+
+impl CallbackContext {
+    pub fn new(
+        state: &PlaybackState,
+        config: &StreamConfig,
+        audio: &Arc<RwLock<Arc<AudioData>>>,
+    ) -> Self {
+        let frames = (GAIN_SMOOTHING_SECS * config.sample_rate as f32).max(1.0);
+        Self {
+            playing: Arc::clone(&state.playing),
+            position: Arc::clone(&state.position),
+            device_channels: config.channels,
+            audio: Arc::clone(audio),
+            live_gain: Arc::clone(&state.live_gain),
+            applied_gain: state.live_gain(),
+            gain_smoothing: 1.0 - (-1.0 / frames).exp(),
+            loop_enabled: Arc::clone(&state.loop_enabled),
+            output_ceiling: Arc::clone(&state.output_ceiling),
+            output_limited: Arc::clone(&state.output_limited),
+            frames_since_clamp: usize::MAX,
+            clamp_run: 0,
+            clamp_hold_frames: (CLAMP_HOLD_SECS * config.sample_rate as f32) as usize,
+            duck_after_frames: (CLAMP_DUCK_AFTER_SECS * config.sample_rate as f32).max(1.0)
+                as usize,
+            dither: Arc::clone(&state.dither),
+            dither_rng: DITHER_SEED,
+        }
+    }
 }
 
 /// Start audio playback on the default output device.
 ///
 /// Returns the cpal `Stream` (must be kept alive for playback to continue)
 /// and the shared `PlaybackState` for controlling playback.
-pub fn start_playback(
-    audio: Arc<AudioData>,
-) -> Result<(Stream, PlaybackState), PlaybackError> {
+pub fn start_playback(audio: Arc<AudioData>) -> Result<(Stream, PlaybackState), PlaybackError> {
     let host = cpal::default_host();
     let device = host
         .default_output_device()
@@ -121,14 +343,7 @@ pub fn start_playback(
     let mut state = PlaybackState::new();
     let audio_lock = Arc::new(RwLock::new(audio));
 
-    let ctx = CallbackContext {
-        playing: Arc::clone(&state.playing),
-        position: Arc::clone(&state.position),
-        device_channels: config.channels,
-        audio: Arc::clone(&audio_lock),
-        live_gain: Arc::clone(&state.live_gain),
-        loop_enabled: Arc::clone(&state.loop_enabled),
-    };
+    let ctx = CallbackContext::new(&state, &config, &audio_lock);
 
     let stream = match sample_format {
         SampleFormat::F32 => build_stream::<f32>(&device, &config, ctx)?,
@@ -151,16 +366,16 @@ pub fn start_playback(
     Ok((stream, state))
 }
 
-fn build_stream<T: cpal::SizedSample + cpal::FromSample<f32>>(
+fn build_stream<T: OutputSample>(
     device: &cpal::Device,
     config: &StreamConfig,
-    ctx: CallbackContext,
+    mut ctx: CallbackContext,
 ) -> Result<Stream, PlaybackError> {
     let stream = device
         .build_output_stream(
             config,
             move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
-                write_audio_data(data, &ctx);
+                write_audio_data(data, &mut ctx);
             },
             |err| log::error!("audio stream error: {err}"),
             None,
@@ -190,14 +405,7 @@ pub fn rebuild_stream(
 
     let audio_lock = Arc::new(RwLock::new(audio));
 
-    let ctx = CallbackContext {
-        playing: Arc::clone(&state.playing),
-        position: Arc::clone(&state.position),
-        device_channels: config.channels,
-        audio: Arc::clone(&audio_lock),
-        live_gain: Arc::clone(&state.live_gain),
-        loop_enabled: Arc::clone(&state.loop_enabled),
-    };
+    let ctx = CallbackContext::new(state, &config, &audio_lock);
 
     let stream = match sample_format {
         SampleFormat::F32 => build_stream::<f32>(&device, &config, ctx)?,
@@ -239,14 +447,18 @@ pub fn swap_audio(
     *guard = new_audio;
 }
 
-fn write_audio_data<T: cpal::SizedSample + cpal::FromSample<f32>>(
-    output: &mut [T],
-    ctx: &CallbackContext,
-) {
-    let silence = T::from_sample(0.0f32);
+/// Fill one device buffer: read from the shared audio at the playhead, apply
+/// the live gain, then the ear-protection ceiling and duck. Integer formats
+/// get one LSB of TPDF dither before conversion unless dither is off.
+pub fn write_audio_data<T: OutputSample>(output: &mut [T], ctx: &mut CallbackContext) {
+    let silence = T::from_f32(0.0);
+
+    let target_gain = f32::from_bits(ctx.live_gain.load(Ordering::Relaxed));
 
     // Use Acquire to synchronize with main-thread Release stores.
     if !ctx.playing.load(Ordering::Acquire) {
+        // Nothing audible to ramp — jump straight to the target.
+        ctx.applied_gain = target_gain;
         for sample in output.iter_mut() {
             *sample = silence;
         }
@@ -278,10 +490,16 @@ fn write_audio_data<T: cpal::SizedSample + cpal::FromSample<f32>>(
     }
 
     let mut pos = ctx.position.load(Ordering::Acquire);
-    let gain = f32::from_bits(ctx.live_gain.load(Ordering::Relaxed));
+    let mut gain = ctx.applied_gain;
     let looping = ctx.loop_enabled.load(Ordering::Relaxed);
+    let ceiling = f32::from_bits(ctx.output_ceiling.load(Ordering::Relaxed));
+    let mut ducked = ctx.output_limited.load(Ordering::Acquire);
+    let duck = db_to_linear(DUCK_DB);
+    let dither = T::LSB > 0.0 && ctx.dither.load(Ordering::Relaxed);
 
     for frame in output.chunks_mut(dc) {
+        gain += (target_gain - gain) * ctx.gain_smoothing;
+        let frame_gain = if ducked { gain * duck } else { gain };
         if pos >= total_samples {
             if looping && total_samples > 0 {
                 pos = 0;
@@ -293,6 +511,7 @@ fn write_audio_data<T: cpal::SizedSample + cpal::FromSample<f32>>(
             }
         }
 
+        let mut clamped = false;
         for (dev_ch, sample) in frame.iter_mut().enumerate() {
             let src_ch = dev_ch % ac;
             let idx = pos + src_ch;
@@ -301,11 +520,38 @@ fn write_audio_data<T: cpal::SizedSample + cpal::FromSample<f32>>(
             } else {
                 0.0f32
             };
-            // M-10: Clamp after gain to prevent DAC clipping.
-            *sample = T::from_sample((val * gain).clamp(-1.0, 1.0));
+            // M-10: Clamp after gain to prevent DAC clipping; the ceiling
+            // is at most 0 dBFS, so this also keeps within [-1, 1].
+            let out = val * frame_gain;
+            clamped |= out.abs() > ceiling;
+            let out = out.clamp(-ceiling, ceiling);
+            *sample = if dither {
+                T::from_f32(out + tpdf_dither(&mut ctx.dither_rng) * T::LSB)
+            } else {
+                T::from_f32(out)
+            };
         }
         pos += ac;
+
+        // Sustained clamping (a screech, not a stray peak) ducks the output.
+        if clamped {
+            ctx.frames_since_clamp = 0;
+        } else {
+            ctx.frames_since_clamp = ctx.frames_since_clamp.saturating_add(1);
+        }
+        if ctx.frames_since_clamp > ctx.clamp_hold_frames {
+            ctx.clamp_run = 0;
+        } else {
+            ctx.clamp_run += 1;
+            if !ducked && ctx.clamp_run >= ctx.duck_after_frames {
+                log::warn!("output clamped for {CLAMP_DUCK_AFTER_SECS}s — ducking by {DUCK_DB} dB");
+                ctx.output_limited.store(true, Ordering::Release);
+                ducked = true;
+                ctx.clamp_run = 0;
+            }
+        }
     }
 
+    ctx.applied_gain = gain;
     ctx.position.store(pos, Ordering::Release);
 }
@@ -0,0 +1,251 @@
+use std::time::Duration;
+
+use world_sys::AnalyzeOptions;
+
+use crate::audio::playback::DEFAULT_OUTPUT_CEILING_DB;
+use crate::dsp::effects::{EffectStage, Precision};
+use crate::dsp::processing::ProcessingCommand;
+use crate::dsp::spectrum::{FFT_SIZE, INSPECT_FFT_SIZE};
+
+/// Debounce delay for resynthesize commands.
+pub const RESYNTH_DEBOUNCE: Duration = Duration::from_millis(150);
+/// Debounce delay for effects-only commands.
+pub const EFFECTS_DEBOUNCE: Duration = Duration::from_millis(80);
+
+/// Files at least this long (seconds) get a preview analysis first.
+pub const PREVIEW_ANALYSIS_SECS: f64 = 60.0;
+
+/// Default crossfade at each end of a punch-in.
+pub const PUNCH_FADE: Duration = Duration::from_millis(10);
+
+/// Terminal colour palette used for rendering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Palette {
+    /// 24-bit colour (gradients, muted greys).
+    #[default]
+    TrueColor,
+    /// The 16 basic ANSI colours only; RGB colours are mapped to the nearest one.
+    Ansi16,
+}
+
+/// Startup configuration parsed from the command line.
+///
+/// Every feature gate reads from here, so safe mode is one code path: the
+/// flag only chooses different values for these fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// `--safe-mode`: minimum moving parts for debugging crashes.
+    pub safe_mode: bool,
+    /// Run WORLD analysis after decoding. Off = files load for playback only,
+    /// and resynthesis / effects are unavailable.
+    pub analyze_on_load: bool,
+    pub palette: Palette,
+    /// Terminal mouse capture. Not used by any view yet; safe mode pins it off.
+    pub mouse_capture: bool,
+    /// Terminal graphics protocols (sixel/kitty) for the spectrum. Not
+    /// functional yet (Unicode bars are always used); safe mode pins it off.
+    pub graphics_protocols: bool,
+    pub spectrum_fft_size: usize,
+    pub inspect_fft_size: usize,
+    pub resynth_debounce: Duration,
+    pub effects_debounce: Duration,
+    /// Effects chain precision (`--f64-effects` selects double precision).
+    pub effects_precision: Precision,
+    /// `--chipmunk-pitch-fx`: the Pitch Shift FX slider resamples, changing
+    /// the length with the pitch, instead of keeping the duration.
+    pub resample_pitch_fx: bool,
+    /// Starting effects chain order (`--fx-order=eq,comp,...`; stages left
+    /// out follow in their default order). Rearranged at runtime with `O`,
+    /// which isn't saved; closing the overlay shows the flag for the new order.
+    pub effects_order: Vec<EffectStage>,
+    /// Ear-protection output ceiling in dBFS (`--output-ceiling=<dB>`).
+    pub output_ceiling_db: f32,
+    /// `--extended-analysis`: keep f0 candidates for manual correction.
+    pub extended_analysis: bool,
+    /// `--cache-analysis`: save each fresh analysis next to the audio file
+    /// (`song.vfw`) so the next load of the same file skips it.
+    pub cache_analysis: bool,
+    /// Analyze files at least this long (seconds) in two passes: a quick
+    /// preview to start with, then the full analysis in the background
+    /// (`--preview-analysis=<secs>`, `--no-preview-analysis`).
+    pub preview_analysis_secs: Option<f64>,
+    /// Scale WORLD renders that would clip down to just under full scale
+    /// (`--no-normalize` leaves them as synthesized).
+    pub normalize_synthesis: bool,
+    /// TPDF dither on 16-bit output devices (`--no-dither` turns it off
+    /// for measurements).
+    pub dither: bool,
+    /// `--auto-preset`: apply the speech / singing effects preset after
+    /// analysis when every control is still neutral.
+    pub auto_preset: bool,
+    /// `--switch-to-b`: a slider change while listening to A (original)
+    /// switches to B instead of only showing the hint.
+    pub switch_to_b_on_change: bool,
+    /// Crossfade at each end of a punch-in (`--punch-fade=<ms>`).
+    pub punch_fade: Duration,
+    /// WORLD analysis settings (`--f0-floor=<Hz>`, `--f0-ceil=<Hz>`,
+    /// `--frame-period=<ms>`, `--d4c-threshold=<0..1>`,
+    /// `--cheaptrick-q1=<-1..1>`, `--no-refine-f0`); always valid after
+    /// [`Config::from_args`]. The voicing threshold also changes at runtime
+    /// (`t`/`T`).
+    pub analysis: AnalyzeOptions,
+    /// File to open on startup (first non-flag argument).
+    pub file: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            safe_mode: false,
+            analyze_on_load: true,
+            palette: Palette::TrueColor,
+            mouse_capture: false,
+            graphics_protocols: false,
+            spectrum_fft_size: FFT_SIZE,
+            inspect_fft_size: INSPECT_FFT_SIZE,
+            resynth_debounce: RESYNTH_DEBOUNCE,
+            effects_debounce: EFFECTS_DEBOUNCE,
+            effects_precision: Precision::F32,
+            resample_pitch_fx: false,
+            effects_order: EffectStage::DEFAULT_ORDER.to_vec(),
+            output_ceiling_db: DEFAULT_OUTPUT_CEILING_DB,
+            extended_analysis: false,
+            cache_analysis: false,
+            preview_analysis_secs: Some(PREVIEW_ANALYSIS_SECS),
+            normalize_synthesis: true,
+            dither: true,
+            auto_preset: false,
+            switch_to_b_on_change: false,
+            punch_fade: PUNCH_FADE,
+            analysis: AnalyzeOptions::default(),
+            file: None,
+        }
+    }
+}
+
+impl Config {
+    /// Conservative settings for `--safe-mode`.
+    pub fn safe() -> Self {
+        Self {
+            safe_mode: true,
+            analyze_on_load: false,
+            palette: Palette::Ansi16,
+            mouse_capture: false,
+            graphics_protocols: false,
+            spectrum_fft_size: FFT_SIZE / 2,
+            inspect_fft_size: FFT_SIZE * 2,
+            resynth_debounce: Duration::from_millis(400),
+            effects_debounce: Duration::from_millis(250),
+            preview_analysis_secs: None,
+            ..Self::default()
+        }
+    }
+
+    /// Parse command-line arguments (without the program name). Unknown
+    /// `--` flags are logged and ignored.
+    pub fn from_args<I, S>(args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let args: Vec<String> = args.into_iter().map(Into::into).collect();
+        let mut config = if args.iter().any(|a| a == "--safe-mode") {
+            Self::safe()
+        } else {
+            Self::default()
+        };
+        for arg in &args {
+            match arg.as_str() {
+                "--safe-mode" => {}
+                "--f64-effects" => config.effects_precision = Precision::F64,
+                "--chipmunk-pitch-fx" => config.resample_pitch_fx = true,
+                "--extended-analysis" => config.extended_analysis = true,
+                "--cache-analysis" => config.cache_analysis = true,
+                "--no-preview-analysis" => config.preview_analysis_secs = None,
+                "--no-refine-f0" => config.analysis.refine_f0 = false,
+                "--no-normalize" => config.normalize_synthesis = false,
+                "--no-dither" => config.dither = false,
+                "--auto-preset" => config.auto_preset = true,
+                "--switch-to-b" => config.switch_to_b_on_change = true,
+                flag if flag.starts_with("--output-ceiling=") => {
+                    match flag["--output-ceiling=".len()..].parse::<f32>() {
+                        Ok(db) if db.is_finite() => config.output_ceiling_db = db.min(0.0),
+                        _ => log::warn!("ignoring invalid {flag}"),
+                    }
+                }
+                flag if flag.starts_with("--fx-order=") => {
+                    match EffectStage::parse_order(&flag["--fx-order=".len()..]) {
+                        Ok(order) => config.effects_order = order,
+                        Err(e) => log::warn!("ignoring {flag} — {e}"),
+                    }
+                }
+                flag if flag.starts_with("--punch-fade=") => {
+                    match flag["--punch-fade=".len()..].parse::<u64>() {
+                        Ok(ms) => config.punch_fade = Duration::from_millis(ms),
+                        _ => log::warn!("ignoring invalid {flag}"),
+                    }
+                }
+                flag if flag.starts_with("--preview-analysis=") => {
+                    match flag["--preview-analysis=".len()..].parse::<f64>() {
+                        Ok(secs) if secs.is_finite() && secs >= 0.0 => {
+                            config.preview_analysis_secs = Some(secs)
+                        }
+                        _ => log::warn!("ignoring invalid {flag}"),
+                    }
+                }
+                flag if flag.starts_with("--f0-floor=") => {
+                    match flag["--f0-floor=".len()..].parse::<f64>() {
+                        Ok(hz) => config.analysis.f0_floor = hz,
+                        _ => log::warn!("ignoring invalid {flag}"),
+                    }
+                }
+                flag if flag.starts_with("--f0-ceil=") => {
+                    match flag["--f0-ceil=".len()..].parse::<f64>() {
+                        Ok(hz) => config.analysis.f0_ceil = hz,
+                        _ => log::warn!("ignoring invalid {flag}"),
+                    }
+                }
+                flag if flag.starts_with("--frame-period=") => {
+                    match flag["--frame-period=".len()..].parse::<f64>() {
+                        Ok(ms) => config.analysis.frame_period = ms,
+                        _ => log::warn!("ignoring invalid {flag}"),
+                    }
+                }
+                flag if flag.starts_with("--d4c-threshold=") => {
+                    match flag["--d4c-threshold=".len()..].parse::<f64>() {
+                        Ok(threshold) => config.analysis.d4c_threshold = threshold,
+                        _ => log::warn!("ignoring invalid {flag}"),
+                    }
+                }
+                flag if flag.starts_with("--cheaptrick-q1=") => {
+                    match flag["--cheaptrick-q1=".len()..].parse::<f64>() {
+                        Ok(q1) => config.analysis.cheaptrick_q1 = Some(q1),
+                        _ => log::warn!("ignoring invalid {flag}"),
+                    }
+                }
+                flag if flag.starts_with("--") => log::warn!("ignoring unknown flag {flag}"),
+                path => {
+                    if config.file.is_none() {
+                        config.file = Some(path.to_string());
+                    }
+                }
+            }
+        }
+        // Checked together: the floor and ceiling only make sense as a pair.
+        if let Err(e) = config.analysis.validate() {
+            log::warn!("ignoring analysis flags — {e}");
+            config.analysis = AnalyzeOptions::default();
+        }
+        config
+    }
+
+    /// The worker command that loads `path`: decode + analyze normally, or
+    /// decode only when analysis is disabled.
+    pub fn load_command(&self, path: String) -> ProcessingCommand {
+        if self.analyze_on_load {
+            ProcessingCommand::Load(path)
+        } else {
+            ProcessingCommand::Decode(path)
+        }
+    }
+}
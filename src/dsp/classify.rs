@@ -0,0 +1,262 @@
+use world_sys::WorldParams;
+
+use crate::dsp::world;
+
+/// Envelope levels more than this far below the loudest frame count as the
+/// floor, so long pauses don't dominate the rhythm measure.
+const ENVELOPE_RANGE_DB: f64 = 60.0;
+
+/// Envelope modulation depth (standard deviation, dB) at which rhythm counts
+/// fully; shallower modulation (e.g. beating partials of a held chord)
+/// is scaled down proportionally.
+const FULL_RHYTHM_DEPTH_DB: f64 = 10.0;
+
+/// Moving-average length applied to the energy envelope first, so pitch-rate
+/// ripple (beating partials) can't pose as rhythm.
+const ENVELOPE_SMOOTHING_SECS: f64 = 0.05;
+
+/// Syllable-rate periods searched by the rhythm measure (2–8 Hz).
+const RHYTHM_MIN_SECS: f64 = 0.125;
+const RHYTHM_MAX_SECS: f64 = 0.5;
+
+/// What a file most likely holds, for picking defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentClass {
+    Speech,
+    Singing,
+    Other,
+}
+
+impl ContentClass {
+    pub fn label(self) -> &'static str {
+        match self {
+            ContentClass::Speech => "Speech",
+            ContentClass::Singing => "Singing",
+            ContentClass::Other => "Other",
+        }
+    }
+}
+
+/// Summary of one analysis that the classifier works from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContentFeatures {
+    /// Share of non-silent frames with an f0 (0..=1).
+    pub voiced_ratio: f64,
+    /// Standard deviation of voiced f0 around its median, in semitones.
+    pub f0_spread_st: f64,
+    /// Energy-weighted mean frequency of the spectral envelope.
+    pub centroid_hz: f64,
+    /// Mean spectral flatness of the envelope (geometric over arithmetic
+    /// mean): near 0 for tones and chords, towards 1 for noise.
+    pub flatness: f64,
+    /// Peak autocorrelation (0..=1) of the dB energy envelope at syllable-rate
+    /// lags, scaled by modulation depth; steady material scores near 0.
+    pub rhythmicity: f64,
+}
+
+impl ContentFeatures {
+    /// Features of a WORLD analysis of audio at `sample_rate`. Silent frames
+    /// are left out of every measure except the rhythm envelope.
+    pub fn from_params(params: &WorldParams, sample_rate: u32) -> Self {
+        let silent = world::silent_frames(&params.spectrogram);
+        let sounding = silent.iter().filter(|&&s| !s).count();
+        let voiced: Vec<f64> = params
+            .f0
+            .iter()
+            .zip(&silent)
+            .filter(|&(&f0, &s)| !s && f0 > 0.0)
+            .map(|(&f0, _)| f0)
+            .collect();
+        let voiced_ratio = if sounding == 0 {
+            0.0
+        } else {
+            voiced.len() as f64 / sounding as f64
+        };
+
+        let flatness = if sounding == 0 {
+            1.0
+        } else {
+            params
+                .spectrogram
+                .iter()
+                .zip(&silent)
+                .filter(|&(_, &s)| !s)
+                .map(|(row, _)| spectral_flatness(row))
+                .sum::<f64>()
+                / sounding as f64
+        };
+
+        Self {
+            voiced_ratio,
+            f0_spread_st: f0_spread_semitones(&voiced),
+            centroid_hz: spectral_centroid(&params.spectrogram, params.fft_size, sample_rate),
+            flatness,
+            rhythmicity: rhythmicity(&params.spectrogram, params.frame_period),
+        }
+    }
+}
+
+/// The classifier's verdict: the most likely class and its probability.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Classification {
+    pub class: ContentClass,
+    pub confidence: f64,
+    pub features: ContentFeatures,
+}
+
+impl Classification {
+    /// Status bar text, e.g. "Speech 87%".
+    pub fn describe(&self) -> String {
+        format!("{} {:.0}%", self.class.label(), self.confidence * 100.0)
+    }
+}
+
+/// Two logistic stages: is there voice or music at all (voiced frames or a
+/// tonal, not noise-bright spectrum), and if so, is it speech (syllable
+/// rhythm, gliding pitch) or singing / music (sustained, steady level).
+pub fn classify(features: &ContentFeatures) -> Classification {
+    let f = features;
+    let brightness = (f.centroid_hz - 3000.0).max(0.0) / 1000.0;
+    let musical = logistic(8.0 * (f.voiced_ratio - 0.3) + 12.0 * (0.4 - f.flatness) - brightness);
+    let speech = logistic(10.0 * (f.rhythmicity - 0.35) + (f.f0_spread_st - 1.0));
+    let scores = [
+        (ContentClass::Speech, musical * speech),
+        (ContentClass::Singing, musical * (1.0 - speech)),
+        (ContentClass::Other, 1.0 - musical),
+    ];
+    // Ties keep the earlier class.
+    let (class, confidence) = scores
+        .into_iter()
+        .reduce(|best, s| if s.1 > best.1 { s } else { best })
+        .unwrap_or((ContentClass::Other, 1.0));
+    Classification {
+        class,
+        confidence,
+        features: *features,
+    }
+}
+
+/// Effects defaults that suit a class. The analysis f0 range is not part of
+/// it: by the time the class is known the analysis has already run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContentPreset {
+    pub low_cut_hz: f64,
+    pub compressor_thresh_db: f64,
+    pub reverb_mix: f64,
+}
+
+/// The preset for `class`; `Other` keeps the neutral defaults.
+pub fn preset(class: ContentClass) -> Option<ContentPreset> {
+    match class {
+        // Rumble off, firm levelling for an even spoken level.
+        ContentClass::Speech => Some(ContentPreset {
+            low_cut_hz: 80.0,
+            compressor_thresh_db: -18.0,
+            reverb_mix: 0.0,
+        }),
+        // Keep the low end of the voice, gentler compression, a little room.
+        ContentClass::Singing => Some(ContentPreset {
+            low_cut_hz: 60.0,
+            compressor_thresh_db: -12.0,
+            reverb_mix: 0.15,
+        }),
+        ContentClass::Other => None,
+    }
+}
+
+fn logistic(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+fn f0_spread_semitones(voiced: &[f64]) -> f64 {
+    if voiced.len() < 2 {
+        return 0.0;
+    }
+    let mut sorted = voiced.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let median = sorted[sorted.len() / 2];
+    let st: Vec<f64> = voiced.iter().map(|&f| 12.0 * (f / median).log2()).collect();
+    let mean = st.iter().sum::<f64>() / st.len() as f64;
+    (st.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / st.len() as f64).sqrt()
+}
+
+fn spectral_flatness(row: &[f64]) -> f64 {
+    if row.is_empty() {
+        return 1.0;
+    }
+    let n = row.len() as f64;
+    let log_mean = row
+        .iter()
+        .map(|&p| world::frame_energy_floor(p).ln())
+        .sum::<f64>()
+        / n;
+    let mean = world::frame_energy_floor(row.iter().sum::<f64>() / n);
+    (log_mean.exp() / mean).min(1.0)
+}
+
+fn spectral_centroid(spectrogram: &[Vec<f64>], fft_size: usize, sample_rate: u32) -> f64 {
+    if fft_size == 0 {
+        return 0.0;
+    }
+    let bin_hz = f64::from(sample_rate) / fft_size as f64;
+    let (mut weighted, mut total) = (0.0, 0.0);
+    for row in spectrogram {
+        for (k, &p) in row.iter().enumerate() {
+            if p.is_finite() {
+                weighted += k as f64 * bin_hz * p;
+                total += p;
+            }
+        }
+    }
+    if total > world::FRAME_ENERGY_EPS {
+        weighted / total
+    } else {
+        0.0
+    }
+}
+
+fn rhythmicity(spectrogram: &[Vec<f64>], frame_period_ms: f64) -> f64 {
+    if frame_period_ms.is_nan() || frame_period_ms <= 0.0 {
+        return 0.0;
+    }
+    let levels: Vec<f64> = spectrogram
+        .iter()
+        .map(|row| 10.0 * world::frame_energy_floor(world::frame_energy(row)).log10())
+        .collect();
+    let Some(loudest) = levels.iter().copied().reduce(f64::max) else {
+        return 0.0;
+    };
+    let floor = loudest - ENVELOPE_RANGE_DB;
+    let floored: Vec<f64> = levels.iter().map(|&l| l.max(floor)).collect();
+    let half = ((ENVELOPE_SMOOTHING_SECS * 1000.0 / frame_period_ms / 2.0).round() as usize).max(1);
+    let envelope: Vec<f64> = (0..floored.len())
+        .map(|i| {
+            let window = &floored[i.saturating_sub(half)..(i + half + 1).min(floored.len())];
+            window.iter().sum::<f64>() / window.len() as f64
+        })
+        .collect();
+    let mean = envelope.iter().sum::<f64>() / envelope.len() as f64;
+    let centred: Vec<f64> = envelope.iter().map(|l| l - mean).collect();
+    let power: f64 = centred.iter().map(|x| x * x).sum();
+    let depth = ((power / centred.len() as f64).sqrt() / FULL_RHYTHM_DEPTH_DB).min(1.0);
+    if depth <= 0.0 {
+        return 0.0;
+    }
+
+    let frame_secs = frame_period_ms / 1000.0;
+    let min_lag = (RHYTHM_MIN_SECS / frame_secs).round() as usize;
+    let max_lag = ((RHYTHM_MAX_SECS / frame_secs).round() as usize).min(centred.len() / 2);
+    (min_lag.max(1)..=max_lag)
+        .map(|lag| {
+            let r: f64 = centred
+                .iter()
+                .zip(&centred[lag..])
+                .map(|(a, b)| a * b)
+                .sum();
+            // Unbiased: shorter overlaps are scaled up to the full length.
+            r / power * centred.len() as f64 / (centred.len() - lag) as f64
+        })
+        .fold(0.0, f64::max)
+        .min(1.0)
+        * depth
+}
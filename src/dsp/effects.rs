@@ -1,34 +1,361 @@
-use std::f32::consts::PI;
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub};
 
-/// Parameters for the 12-band graphic EQ.
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+
+/// Band layout of the graphic EQ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EqLayout {
+    /// Octave-spaced bands, shelves at both ends.
+    #[default]
+    Bands12,
+    /// The ISO 1/3-octave bands, 20 Hz to 20 kHz, all peaking.
+    Bands31,
+}
+
+impl EqLayout {
+    /// Centre frequencies and filter types of the bands, low to high.
+    pub fn bands(self) -> &'static [(f32, &'static str)] {
+        match self {
+            EqLayout::Bands12 => &EQ_BANDS,
+            EqLayout::Bands31 => &EQ_BANDS_31,
+        }
+    }
+
+    pub fn band_count(self) -> usize {
+        self.bands().len()
+    }
+
+    /// Q of the layout's peaking bands.
+    pub fn peak_q(self) -> f32 {
+        match self {
+            EqLayout::Bands12 => EQ_PEAK_Q,
+            EqLayout::Bands31 => EQ_THIRD_OCTAVE_Q,
+        }
+    }
+
+    /// The other layout.
+    pub fn toggle(self) -> Self {
+        match self {
+            EqLayout::Bands12 => EqLayout::Bands31,
+            EqLayout::Bands31 => EqLayout::Bands12,
+        }
+    }
+}
+
+/// Parameters for the graphic EQ: one gain per band of `layout` (missing
+/// bands count as 0 dB, extra gains are ignored).
 #[derive(Debug, Clone, PartialEq)]
 pub struct EqParams {
-    pub gains: [f32; 12],
+    pub layout: EqLayout,
+    pub gains: Vec<f32>,
+    /// Q of every peaking band, clamped to [`EQ_Q_RANGE`]; `None` = the
+    /// layout's own ([`EqLayout::peak_q`]). Narrower bands overlap less, so
+    /// boosting neighbours doesn't pile up in between.
+    pub q: Option<f32>,
 }
 
 impl Default for EqParams {
     fn default() -> Self {
-        Self { gains: [0.0; 12] }
+        Self::flat(EqLayout::default())
     }
 }
 
 impl EqParams {
+    /// Every band of `layout` at 0 dB.
+    pub fn flat(layout: EqLayout) -> Self {
+        Self {
+            layout,
+            gains: vec![0.0; layout.band_count()],
+            q: None,
+        }
+    }
+
+    /// Q the peaking bands run at.
+    pub fn peak_q(&self) -> f32 {
+        self.q.map_or(self.layout.peak_q(), |q| {
+            q.clamp(EQ_Q_RANGE.0, EQ_Q_RANGE.1)
+        })
+    }
+
     /// True when all EQ bands are at 0 dB (neutral).
     pub fn is_neutral(&self) -> bool {
         self.gains.iter().all(|&g| g.abs() < 1e-6)
     }
 }
 
+/// Filter shape of a [`ParametricBand`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParametricKind {
+    #[default]
+    Peak,
+    LowShelf,
+    HighShelf,
+}
+
+impl ParametricKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            ParametricKind::Peak => "Peak",
+            ParametricKind::LowShelf => "Low shelf",
+            ParametricKind::HighShelf => "High shelf",
+        }
+    }
+
+    /// The next shape, wrapping (Peak → Low shelf → High shelf → Peak).
+    pub fn next(self) -> Self {
+        match self {
+            ParametricKind::Peak => ParametricKind::LowShelf,
+            ParametricKind::LowShelf => ParametricKind::HighShelf,
+            ParametricKind::HighShelf => ParametricKind::Peak,
+        }
+    }
+
+    /// The previous shape, wrapping.
+    pub fn prev(self) -> Self {
+        self.next().next()
+    }
+}
+
+/// One band of the parametric EQ (see [`apply_parametric_eq`]). `q` only
+/// shapes peaking bands; the shelves keep the graphic EQ's slope.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParametricBand {
+    pub freq_hz: f32,
+    pub q: f32,
+    pub gain_db: f32,
+    pub kind: ParametricKind,
+}
+
+/// Reverb algorithm preset. Each preset is a tuned table of comb/allpass
+/// delays plus an early-reflection tap pattern (see [`ReverbPreset::tables`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReverbPreset {
+    Room,
+    Hall,
+    /// The original single Schroeder topology, without early reflections.
+    #[default]
+    Plate,
+}
+
+impl ReverbPreset {
+    /// All presets in selector order (matches the Effects panel slider).
+    pub const ALL: [ReverbPreset; 3] =
+        [ReverbPreset::Room, ReverbPreset::Hall, ReverbPreset::Plate];
+    /// Display names in selector order.
+    pub const NAMES: [&'static str; 3] = ["Room", "Hall", "Plate"];
+
+    /// Preset for a selector index, clamped to the last preset.
+    pub fn from_index(index: usize) -> Self {
+        Self::ALL[index.min(Self::ALL.len() - 1)]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ReverbPreset::Room => "Room",
+            ReverbPreset::Hall => "Hall",
+            ReverbPreset::Plate => "Plate",
+        }
+    }
+
+    fn tables(self) -> &'static ReverbTables {
+        match self {
+            ReverbPreset::Room => &ROOM_TABLES,
+            ReverbPreset::Hall => &HALL_TABLES,
+            ReverbPreset::Plate => &PLATE_TABLES,
+        }
+    }
+}
+
+/// One stage of the effects chain; [`EffectsParams::order`] lists them in
+/// the order they run. (The output gain is applied live and the loudness
+/// normalization always comes last, so neither is a stage.)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffectStage {
+    HighPass,
+    LowPass,
+    Gate,
+    Compressor,
+    Tremolo,
+    DeEsser,
+    PitchShift,
+    Chorus,
+    Delay,
+    Reverb,
+    Eq,
+    Limiter,
+}
+
+impl EffectStage {
+    /// The standard order: cleanup filters, dynamics, modulation and pitch,
+    /// time effects, tone, then the limiter.
+    pub const DEFAULT_ORDER: [EffectStage; 12] = [
+        EffectStage::HighPass,
+        EffectStage::LowPass,
+        EffectStage::Gate,
+        EffectStage::Compressor,
+        EffectStage::Tremolo,
+        EffectStage::DeEsser,
+        EffectStage::PitchShift,
+        EffectStage::Chorus,
+        EffectStage::Delay,
+        EffectStage::Reverb,
+        EffectStage::Eq,
+        EffectStage::Limiter,
+    ];
+
+    /// Display name.
+    pub fn name(self) -> &'static str {
+        match self {
+            EffectStage::HighPass => "Low Cut",
+            EffectStage::LowPass => "High Cut",
+            EffectStage::Gate => "Gate",
+            EffectStage::Compressor => "Compressor",
+            EffectStage::Tremolo => "Tremolo",
+            EffectStage::DeEsser => "De-esser",
+            EffectStage::PitchShift => "Pitch Shift",
+            EffectStage::Chorus => "Chorus",
+            EffectStage::Delay => "Delay",
+            EffectStage::Reverb => "Reverb",
+            EffectStage::Eq => "EQ",
+            EffectStage::Limiter => "Limiter",
+        }
+    }
+
+    /// Identifier used on the command line (`--fx-order=`).
+    pub fn id(self) -> &'static str {
+        match self {
+            EffectStage::HighPass => "highpass",
+            EffectStage::LowPass => "lowpass",
+            EffectStage::Gate => "gate",
+            EffectStage::Compressor => "comp",
+            EffectStage::Tremolo => "tremolo",
+            EffectStage::DeEsser => "deesser",
+            EffectStage::PitchShift => "pitch",
+            EffectStage::Chorus => "chorus",
+            EffectStage::Delay => "delay",
+            EffectStage::Reverb => "reverb",
+            EffectStage::Eq => "eq",
+            EffectStage::Limiter => "limiter",
+        }
+    }
+
+    /// Stage for an [`EffectStage::id`].
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::DEFAULT_ORDER
+            .into_iter()
+            .find(|stage| stage.id() == id)
+    }
+
+    /// Parse a comma-separated list of ids into a full order: stages in the
+    /// order given, then any left out in their default order.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message naming an unknown or repeated id.
+    pub fn parse_order(list: &str) -> Result<Vec<EffectStage>, String> {
+        let mut order = Vec::with_capacity(Self::DEFAULT_ORDER.len());
+        for id in list.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+            let stage =
+                Self::from_id(id).ok_or_else(|| format!("unknown effects stage \"{id}\""))?;
+            if order.contains(&stage) {
+                return Err(format!("effects stage \"{id}\" listed twice"));
+            }
+            order.push(stage);
+        }
+        for stage in Self::DEFAULT_ORDER {
+            if !order.contains(&stage) {
+                order.push(stage);
+            }
+        }
+        Ok(order)
+    }
+}
+
+/// Arithmetic precision of the effects chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Precision {
+    /// Single precision — the interactive default, fastest.
+    #[default]
+    F32,
+    /// Double precision, dithered back to `f32` at the end — for mastering exports.
+    F64,
+}
+
 /// Parameters for the post-WORLD effects chain.
 #[derive(Debug, Clone, PartialEq)]
 pub struct EffectsParams {
     pub gain_db: f32,
     pub low_cut_hz: f32,
     pub high_cut_hz: f32,
+    /// Noise gate threshold in dBFS (see [`apply_gate`]); 0 = off.
+    pub gate_thresh_db: f32,
     pub compressor_thresh_db: f32,
+    /// Compression ratio above the threshold (n:1).
+    pub comp_ratio: f32,
+    pub comp_attack_ms: f32,
+    pub comp_release_ms: f32,
+    /// Makeup gain in dB; `None` = automatic, half the threshold's depth.
+    pub comp_makeup_db: Option<f32>,
+    /// Tremolo depth, 0 (off) to 1 (see [`apply_tremolo`]).
+    pub tremolo_depth: f32,
+    /// Tremolo rate in Hz.
+    pub tremolo_rate_hz: f32,
+    /// De-esser threshold in dBFS (see [`apply_deesser`]); 0 = off.
+    pub deess_thresh_db: f32,
+    /// Chorus wet share, 0 (off) to 1 (see [`apply_chorus`]).
+    pub chorus_mix: f32,
+    /// Rate of the chorus delay modulation in Hz.
+    pub chorus_rate_hz: f32,
+    /// Echo delay in ms (see [`apply_delay`]).
+    pub delay_time_ms: f32,
+    /// Share of each echo fed back into the next, 0 to 0.9.
+    pub delay_feedback: f32,
+    /// Echo wet share, 0 (off) to 1.
+    pub delay_mix: f32,
     pub reverb_mix: f32,
+    pub reverb_preset: ReverbPreset,
+    /// Scale of the reverb's delay lengths (1 = the preset as tuned;
+    /// larger = bigger room, longer decay).
+    pub reverb_size: f32,
+    /// High-frequency loss per pass through the reverb's combs, 0 (bright)
+    /// to [`REVERB_MAX_DAMPING`].
+    pub reverb_damping: f32,
+    /// Delay of the wet signal behind the dry, in ms.
+    pub reverb_predelay_ms: f32,
     pub pitch_shift_semitones: f32,
+    /// Pitch shift by plain resampling (the chipmunk effect: the buffer
+    /// shortens or lengthens with the pitch) instead of the
+    /// duration-preserving phase vocoder; does not affect
+    /// [`EffectsParams::is_neutral`].
+    pub pitch_shift_resample: bool,
     pub eq: EqParams,
+    /// EQ tilt macro in dB: the offset at the 16 kHz band, mirrored at 31 Hz
+    /// (negative = warmer, positive = brighter). Layered on top of `eq`.
+    pub eq_tilt_db: f32,
+    /// Parametric EQ bands run after the graphic EQ; only the first
+    /// [`PARAMETRIC_MAX_BANDS`] are used and 0 dB bands are skipped.
+    pub parametric: Vec<ParametricBand>,
+    /// Brickwall limiter ceiling in dBFS (see [`apply_limiter`]); 0 = off.
+    pub limiter_ceiling_db: f32,
+    /// Order the stages run in ([`EffectStage::DEFAULT_ORDER`] by default);
+    /// a stage left out doesn't run.
+    pub order: Vec<EffectStage>,
+    /// Wet/dry balance of the whole chain (see [`blend_dry`]): 1 = fully
+    /// processed, 0 = the input unchanged (the chain is skipped, so no
+    /// delay tail or pitch resampling either).
+    pub fx_mix: f32,
+    /// Integrated loudness the finished buffer is gained to, in LUFS (see
+    /// [`normalize_lufs`]); `None` = off.
+    pub target_lufs: Option<f32>,
+    /// Processing precision; does not affect [`EffectsParams::is_neutral`].
+    pub precision: Precision,
+    /// Trim the processed buffer to the original's loudness at the end of
+    /// the worker's effects pass, for a fair A/B; does not affect
+    /// [`EffectsParams::is_neutral`] (WORLD edits change the level too).
+    pub loudness_match: bool,
 }
 
 impl Default for EffectsParams {
@@ -37,10 +364,36 @@ impl Default for EffectsParams {
             gain_db: 0.0,
             low_cut_hz: 20.0,
             high_cut_hz: 20000.0,
+            gate_thresh_db: 0.0,
             compressor_thresh_db: 0.0,
+            comp_ratio: 4.0,
+            comp_attack_ms: 5.0,
+            comp_release_ms: 50.0,
+            comp_makeup_db: None,
+            tremolo_depth: 0.0,
+            tremolo_rate_hz: 5.0,
+            deess_thresh_db: 0.0,
+            chorus_mix: 0.0,
+            chorus_rate_hz: 0.8,
+            delay_time_ms: 250.0,
+            delay_feedback: 0.4,
+            delay_mix: 0.0,
             reverb_mix: 0.0,
+            reverb_preset: ReverbPreset::default(),
+            reverb_size: 1.0,
+            reverb_damping: 0.0,
+            reverb_predelay_ms: 0.0,
             pitch_shift_semitones: 0.0,
+            pitch_shift_resample: false,
             eq: EqParams::default(),
+            eq_tilt_db: 0.0,
+            parametric: Vec::new(),
+            limiter_ceiling_db: 0.0,
+            target_lufs: None,
+            order: EffectStage::DEFAULT_ORDER.to_vec(),
+            fx_mix: 1.0,
+            precision: Precision::default(),
+            loudness_match: false,
         }
     }
 }
@@ -52,53 +405,357 @@ impl EffectsParams {
     pub fn is_neutral(&self) -> bool {
         self.low_cut_hz <= 20.0
             && self.high_cut_hz >= 20000.0
+            && self.gate_thresh_db >= 0.0
             && self.compressor_thresh_db >= 0.0
+            && self.deess_thresh_db >= 0.0
+            && self.tremolo_depth.abs() < 1e-6
+            && self.chorus_mix.abs() < 1e-6
+            && self.delay_mix.abs() < 1e-6
             && self.reverb_mix.abs() < 1e-6
             && self.pitch_shift_semitones.abs() < 1e-6
             && self.eq.is_neutral()
+            && self.eq_tilt_db.abs() < 1e-6
+            && self
+                .parametric
+                .iter()
+                .take(PARAMETRIC_MAX_BANDS)
+                .all(|b| b.gain_db.abs() < 1e-6)
+            && self.limiter_ceiling_db >= 0.0
+            && self.target_lufs.is_none()
+            && self.fx_mix >= 1.0
     }
+
+    /// Frames [`apply_effects`] adds to each channel for the delay's echoes
+    /// to ring out: [`DELAY_TAIL_REPEATS`] delay times when the delay is on,
+    /// otherwise (or when [`EffectsParams::fx_mix`] skips the chain) none.
+    pub fn tail_frames(&self, sample_rate: u32) -> usize {
+        if self.delay_mix > 0.0 && self.fx_mix > 0.0 {
+            DELAY_TAIL_REPEATS * delay_frames(self.delay_time_ms, sample_rate)
+        } else {
+            0
+        }
+    }
+}
+
+/// Apply the full effects chain: the stages in [`EffectsParams::order`]
+/// (by default highpass → lowpass → gate → compressor → tremolo → de-esser
+/// → pitch shift → chorus → delay → reverb → EQ → limiter), blended with
+/// the input by [`EffectsParams::fx_mix`], then the loudness normalization.
+/// Returns a new buffer of the input's length, except that the delay, when
+/// on, adds [`EffectsParams::tail_frames`] frames of echoes and the
+/// resampling pitch shift ([`EffectsParams::pitch_shift_resample`]) scales
+/// it with the pitch.
+///
+/// `samples` are interleaved with `channels` channels (0 is taken as mono).
+/// Each channel is deinterleaved and runs through the chain on its own, with
+/// its own filter, compressor and reverb state, so one channel never leaks
+/// into another; a trailing partial frame is dropped.
+///
+/// With [`Precision::F64`] the chain runs in double precision and the result
+/// is dithered back down to `f32` at the end.
+pub fn apply_effects(
+    samples: &[f32],
+    sample_rate: u32,
+    channels: u16,
+    params: &EffectsParams,
+) -> Vec<f32> {
+    apply_effects_normalized(samples, sample_rate, channels, params).0
 }
 
-/// Apply the full effects chain in order: gain → highpass → lowpass →
-/// compressor → pitch shift → reverb → EQ.  Returns a new buffer.
-pub fn apply_effects(samples: &[f32], sample_rate: u32, params: &EffectsParams) -> Vec<f32> {
+/// [`apply_effects`], also returning what the loudness normalization stage
+/// measured and applied (`None` when it is off or the buffer is silent).
+pub fn apply_effects_normalized(
+    samples: &[f32],
+    sample_rate: u32,
+    channels: u16,
+    params: &EffectsParams,
+) -> (Vec<f32>, Option<LufsNormalization>) {
     if params.is_neutral() || samples.is_empty() || sample_rate == 0 {
-        return samples.to_vec();
+        return (samples.to_vec(), None);
     }
+    let mut processed = run_channels(samples, sample_rate, channels, params);
+    // Last: a static gain over all channels together, so it can't run per channel.
+    let normalization = params
+        .target_lufs
+        .and_then(|target| normalize_lufs(&mut processed, sample_rate, channels, target));
+    (processed, normalization)
+}
 
-    let mut buf = samples.to_vec();
-
-    // 1. Gain — applied live in audio callback, skipped here.
+/// Deinterleave, run each channel through the chain and interleave again.
+fn run_channels(
+    samples: &[f32],
+    sample_rate: u32,
+    channels: u16,
+    params: &EffectsParams,
+) -> Vec<f32> {
+    let channels = usize::from(channels.max(1));
+    if channels == 1 {
+        return run_channel(samples.to_vec(), sample_rate, params);
+    }
+    let processed: Vec<Vec<f32>> = (0..channels)
+        .map(|c| {
+            let channel = samples.iter().skip(c).step_by(channels).copied().collect();
+            run_channel(channel, sample_rate, params)
+        })
+        .collect();
+    // The resampling pitch shift and the delay change the length; every
+    // channel by the same amount for whole frames.
+    let frames = processed.iter().map(Vec::len).min().unwrap_or(0);
+    (0..frames)
+        .flat_map(|i| processed.iter().map(move |channel| channel[i]))
+        .collect()
+}
 
-    // 2. High-pass (low cut)
-    if params.low_cut_hz > 20.0 {
-        apply_biquad(&mut buf, sample_rate, BiquadType::Highpass, params.low_cut_hz);
+/// [`run_chain`] on one channel at the requested precision, then the
+/// wet/dry blend. The blend is in `f32`, after the dither, so the dry
+/// signal comes through untouched; at mix 0 the chain doesn't run at all.
+fn run_channel(samples: Vec<f32>, sample_rate: u32, params: &EffectsParams) -> Vec<f32> {
+    if params.fx_mix <= 0.0 {
+        return samples;
+    }
+    let dry = (params.fx_mix < 1.0).then(|| samples.clone());
+    let mut wet = match params.precision {
+        Precision::F32 => run_chain(samples, sample_rate, params),
+        Precision::F64 => {
+            let wide: Vec<f64> = samples.iter().map(|&s| s as f64).collect();
+            dither_to_f32(&run_chain(wide, sample_rate, params))
+        }
+    };
+    if let Some(dry) = dry {
+        blend_dry(&mut wet, &dry, params.fx_mix);
     }
+    wet
+}
 
-    // 3. Low-pass (high cut)
-    if params.high_cut_hz < 20000.0 {
-        apply_biquad(&mut buf, sample_rate, BiquadType::Lowpass, params.high_cut_hz);
+/// The effects chain proper on one channel, generic over the processing
+/// sample type.
+fn run_chain<T: Sample>(mut buf: Vec<T>, sample_rate: u32, params: &EffectsParams) -> Vec<T> {
+    // Gain — applied live in audio callback, skipped here.
+    for &stage in &params.order {
+        buf = run_stage(stage, buf, sample_rate, params);
     }
+    buf
+}
 
-    // 4. Compressor
-    if params.compressor_thresh_db < 0.0 {
-        apply_compressor(&mut buf, params.compressor_thresh_db, sample_rate);
+/// Mix `dry` (the chain's input) back under `wet` (its output):
+/// `wet · mix + dry · (1 − mix)`, `mix` clamped to 0..1. The output keeps
+/// the wet length, which the delay's tail and the resampling pitch shift
+/// change: a shorter dry signal is padded with silence, a longer one is cut
+/// off. At mix 0 the overlap is the input bit for bit.
+pub fn blend_dry(wet: &mut [f32], dry: &[f32], mix: f32) {
+    let mix = mix.clamp(0.0, 1.0);
+    for (i, w) in wet.iter_mut().enumerate() {
+        let d = dry.get(i).copied().unwrap_or_default();
+        *w = d * (1.0 - mix) + *w * mix;
     }
+}
 
-    // 5. Pitch shift (FX) — may change buffer length
-    if params.pitch_shift_semitones != 0.0 {
-        buf = apply_pitch_shift(&buf, params.pitch_shift_semitones);
+/// One stage of [`run_chain`]; stages that are off pass `buf` through.
+/// Each works on whatever length it is handed, so the pitch shift and the
+/// delay, which change the length, can sit anywhere in the order.
+fn run_stage<T: Sample>(
+    stage: EffectStage,
+    mut buf: Vec<T>,
+    sample_rate: u32,
+    params: &EffectsParams,
+) -> Vec<T> {
+    match stage {
+        EffectStage::HighPass => {
+            if params.low_cut_hz > 20.0 {
+                apply_biquad(
+                    &mut buf,
+                    sample_rate,
+                    BiquadType::Highpass,
+                    params.low_cut_hz,
+                );
+            }
+        }
+        EffectStage::LowPass => {
+            if params.high_cut_hz < 20000.0 {
+                apply_biquad(
+                    &mut buf,
+                    sample_rate,
+                    BiquadType::Lowpass,
+                    params.high_cut_hz,
+                );
+            }
+        }
+        // By default before the compressor's makeup gain lifts the floor.
+        EffectStage::Gate => {
+            if params.gate_thresh_db < 0.0 {
+                apply_gate(
+                    &mut buf,
+                    sample_rate,
+                    params.gate_thresh_db,
+                    GateTiming::default(),
+                );
+            }
+        }
+        EffectStage::Compressor => {
+            if params.compressor_thresh_db < 0.0 {
+                apply_compressor(
+                    &mut buf,
+                    params.compressor_thresh_db,
+                    sample_rate,
+                    params.comp_ratio,
+                    (params.comp_attack_ms, params.comp_release_ms),
+                    params.comp_makeup_db,
+                );
+            }
+        }
+        EffectStage::Tremolo => {
+            if params.tremolo_depth > 0.0 {
+                apply_tremolo(
+                    &mut buf,
+                    sample_rate,
+                    params.tremolo_depth,
+                    params.tremolo_rate_hz,
+                );
+            }
+        }
+        // By default after the compressor's makeup gain has lifted the esses.
+        EffectStage::DeEsser => {
+            if params.deess_thresh_db < 0.0 {
+                apply_deesser(&mut buf, sample_rate, params.deess_thresh_db);
+            }
+        }
+        // Changes the buffer length only when resampling.
+        EffectStage::PitchShift => {
+            if params.pitch_shift_semitones != 0.0 {
+                buf = if params.pitch_shift_resample {
+                    resample_pitch(&buf, params.pitch_shift_semitones)
+                } else {
+                    apply_pitch_shift(&buf, params.pitch_shift_semitones)
+                };
+            }
+        }
+        EffectStage::Chorus => {
+            if params.chorus_mix > 0.0 {
+                apply_chorus(
+                    &mut buf,
+                    sample_rate,
+                    params.chorus_mix,
+                    params.chorus_rate_hz,
+                );
+            }
+        }
+        // Extends the buffer by its tail.
+        EffectStage::Delay => {
+            if params.delay_mix > 0.0 {
+                buf.resize(buf.len() + params.tail_frames(sample_rate), T::default());
+                apply_delay(
+                    &mut buf,
+                    sample_rate,
+                    params.delay_time_ms,
+                    params.delay_feedback,
+                    params.delay_mix,
+                );
+            }
+        }
+        EffectStage::Reverb => {
+            if params.reverb_mix > 0.0 {
+                buf = apply_reverb(
+                    &buf,
+                    sample_rate,
+                    params.reverb_mix,
+                    params.reverb_preset,
+                    params.reverb_size,
+                    params.reverb_damping,
+                    params.reverb_predelay_ms,
+                );
+            }
+        }
+        // Graphic, then parametric.
+        EffectStage::Eq => {
+            apply_eq(&mut buf, sample_rate, &params.eq, params.eq_tilt_db);
+            apply_parametric_eq(&mut buf, sample_rate, &params.parametric);
+        }
+        EffectStage::Limiter => {
+            if params.limiter_ceiling_db < 0.0 {
+                apply_limiter(
+                    &mut buf,
+                    sample_rate,
+                    params.limiter_ceiling_db,
+                    LIMITER_RELEASE_MS,
+                );
+            }
+        }
     }
+    buf
+}
 
-    // 6. Reverb
-    if params.reverb_mix > 0.0 {
-        buf = apply_reverb(&buf, sample_rate, params.reverb_mix);
+// ── Precision ───────────────────────────────────────────────────────────
+
+/// Floating-point type the effects cores run in.
+///
+/// Implemented for `f32` (interactive path) and `f64` ([`Precision::F64`]).
+/// Filter coefficients are designed in `f64` and converted, so only the
+/// per-sample arithmetic differs between the two.
+pub trait Sample:
+    Copy
+    + Default
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + AddAssign
+    + MulAssign
+{
+    fn from_f64(v: f64) -> Self;
+    fn to_f64(self) -> f64;
+    fn abs(self) -> Self;
+    fn powf(self, exp: Self) -> Self;
+}
+
+impl Sample for f32 {
+    fn from_f64(v: f64) -> Self {
+        v as f32
+    }
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+    fn abs(self) -> Self {
+        f32::abs(self)
     }
+    fn powf(self, exp: Self) -> Self {
+        f32::powf(self, exp)
+    }
+}
 
-    // 7. EQ (final stage)
-    apply_eq(&mut buf, sample_rate, &params.eq);
+impl Sample for f64 {
+    fn from_f64(v: f64) -> Self {
+        v
+    }
+    fn to_f64(self) -> f64 {
+        self
+    }
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+    fn powf(self, exp: Self) -> Self {
+        f64::powf(self, exp)
+    }
+}
 
-    buf
+/// Convert a double-precision render back to `f32` with TPDF dither of one
+/// `f32` ULP at each sample's magnitude, so the final rounding error is
+/// decorrelated from the signal. Fixed-seed xorshift keeps renders reproducible.
+fn dither_to_f32(samples: &[f64]) -> Vec<f32> {
+    let mut state: u32 = 0x9E37_79B9;
+    let mut uniform = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state as f64 / u32::MAX as f64
+    };
+    samples
+        .iter()
+        .map(|&s| {
+            let ulp = (s.abs() as f32).max(f32::MIN_POSITIVE) as f64 * f32::EPSILON as f64;
+            let tpdf = uniform() - uniform();
+            (s + tpdf * ulp) as f32
+        })
+        .collect()
 }
 
 // ── Gain ────────────────────────────────────────────────────────────────
@@ -113,9 +770,19 @@ pub fn apply_gain(samples: &mut [f32], gain_db: f32) {
     }
 }
 
+/// [`apply_gain`] without a copy: yields each sample with the gain applied,
+/// bit-identical to the in-place version. For streaming export.
+pub fn gained(samples: &[f32], gain_db: f32) -> impl ExactSizeIterator<Item = f32> + '_ {
+    let linear = 10.0_f32.powf(gain_db / 20.0);
+    samples.iter().map(move |&s| s * linear)
+}
+
 // ── Biquad filter (cookbook) ─────────────────────────────────────────────
 
-enum BiquadType {
+/// Response of a [`Biquad`] (RBJ cookbook designs). The high- and low-pass
+/// are Butterworth (Q 0.707); the shelves have slope 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BiquadType {
     Highpass,
     Lowpass,
     Peaking { gain_db: f32, q: f32 },
@@ -123,25 +790,37 @@ enum BiquadType {
     HighShelf { gain_db: f32 },
 }
 
-struct Biquad {
-    b0: f32,
-    b1: f32,
-    b2: f32,
-    a1: f32,
-    a2: f32,
+/// One biquad section, direct form I, with its filter state: successive
+/// [`Biquad::process`] calls continue where the last block ended, so a
+/// signal can be filtered in blocks of any size with the same result as in
+/// one pass.
+#[derive(Debug, Clone)]
+pub struct Biquad<T = f32> {
+    b0: T,
+    b1: T,
+    b2: T,
+    a1: T,
+    a2: T,
+    /// Last two inputs and outputs.
+    x1: T,
+    x2: T,
+    y1: T,
+    y2: T,
 }
 
-impl Biquad {
-    fn new(btype: BiquadType, freq: f32, sample_rate: u32) -> Self {
-        let nyquist = sample_rate as f32 / 2.0;
-        let clamped = freq.min(nyquist * 0.95).max(1.0);
-        let w0 = 2.0 * PI * clamped / sample_rate as f32;
+impl<T: Sample> Biquad<T> {
+    /// Design the filter in `f64` and convert the coefficients to `T`, with
+    /// silent state. `freq` is clamped to 1 Hz .. 95% of Nyquist.
+    pub fn new(btype: BiquadType, freq: f32, sample_rate: u32) -> Self {
+        let nyquist = sample_rate as f64 / 2.0;
+        let clamped = (freq as f64).min(nyquist * 0.95).max(1.0);
+        let w0 = 2.0 * PI * clamped / sample_rate as f64;
         let cos_w0 = w0.cos();
         let sin_w0 = w0.sin();
 
         let (_a0, a1, a2, b0, b1, b2) = match btype {
             BiquadType::Highpass => {
-                let q = std::f32::consts::FRAC_1_SQRT_2; // 0.707 Butterworth
+                let q = std::f64::consts::FRAC_1_SQRT_2; // 0.707 Butterworth
                 let alpha = sin_w0 / (2.0 * q);
                 let a0_val = 1.0 + alpha;
                 (
@@ -154,7 +833,7 @@ impl Biquad {
                 )
             }
             BiquadType::Lowpass => {
-                let q = std::f32::consts::FRAC_1_SQRT_2; // 0.707 Butterworth
+                let q = std::f64::consts::FRAC_1_SQRT_2; // 0.707 Butterworth
                 let alpha = sin_w0 / (2.0 * q);
                 let a0_val = 1.0 + alpha;
                 (
@@ -167,8 +846,8 @@ impl Biquad {
                 )
             }
             BiquadType::Peaking { gain_db, q } => {
-                let alpha = sin_w0 / (2.0 * q);
-                let a = 10.0_f32.powf(gain_db / 40.0);
+                let alpha = sin_w0 / (2.0 * q as f64);
+                let a = 10.0_f64.powf(gain_db as f64 / 40.0);
                 let a0_val = 1.0 + alpha / a;
                 (
                     a0_val,
@@ -180,7 +859,7 @@ impl Biquad {
                 )
             }
             BiquadType::LowShelf { gain_db } => {
-                let a = 10.0_f32.powf(gain_db / 40.0);
+                let a = 10.0_f64.powf(gain_db as f64 / 40.0);
                 let s = 1.0; // Shelf slope
                 let alpha = sin_w0 / 2.0 * ((a + 1.0 / a) * (1.0 / s - 1.0) + 2.0).sqrt();
                 let cos_w0_a = 2.0 * a.sqrt() * alpha;
@@ -195,7 +874,7 @@ impl Biquad {
                 )
             }
             BiquadType::HighShelf { gain_db } => {
-                let a = 10.0_f32.powf(gain_db / 40.0);
+                let a = 10.0_f64.powf(gain_db as f64 / 40.0);
                 let s = 1.0; // Shelf slope
                 let alpha = sin_w0 / 2.0 * ((a + 1.0 / a) * (1.0 / s - 1.0) + 2.0).sqrt();
                 let cos_w0_a = 2.0 * a.sqrt() * alpha;
@@ -212,148 +891,818 @@ impl Biquad {
         };
 
         Self {
-            b0,
-            b1,
-            b2,
-            a1,
-            a2,
+            b0: T::from_f64(b0),
+            b1: T::from_f64(b1),
+            b2: T::from_f64(b2),
+            a1: T::from_f64(a1),
+            a2: T::from_f64(a2),
+            x1: T::default(),
+            x2: T::default(),
+            y1: T::default(),
+            y2: T::default(),
         }
     }
 
-    fn process_sample(&self, x: f32, x1: &mut f32, x2: &mut f32, y1: &mut f32, y2: &mut f32) -> f32 {
-        let y = self.b0 * x + self.b1 * *x1 + self.b2 * *x2
-            - self.a1 * *y1 - self.a2 * *y2;
-        *x2 = *x1;
-        *x1 = x;
-        *y2 = *y1;
-        *y1 = y;
+    /// Magnitude response in dB at `freq` Hz.
+    pub fn magnitude_db(&self, freq: f64, sample_rate: u32) -> f64 {
+        let w = 2.0 * PI * freq / sample_rate as f64;
+        // Evaluate numerator and denominator polynomials at z = e^{jw}.
+        let eval = |c0: f64, c1: f64, c2: f64| {
+            let re = c0 + c1 * w.cos() + c2 * (2.0 * w).cos();
+            let im = -(c1 * w.sin() + c2 * (2.0 * w).sin());
+            (re * re + im * im).sqrt()
+        };
+        let num = eval(self.b0.to_f64(), self.b1.to_f64(), self.b2.to_f64());
+        let den = eval(1.0, self.a1.to_f64(), self.a2.to_f64());
+        20.0 * (num / den.max(1e-12)).max(1e-12).log10()
+    }
+
+    /// Filter one sample.
+    pub fn process_sample(&mut self, x: T) -> T {
+        let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2
+            - self.a1 * self.y1
+            - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x;
+        self.y2 = self.y1;
+        self.y1 = y;
         y
     }
+
+    /// Filter `block` in place, carrying the state on to the next call.
+    pub fn process(&mut self, block: &mut [T]) {
+        for s in block.iter_mut() {
+            *s = self.process_sample(*s);
+        }
+    }
+
+    /// Back to silent state, as if new; the coefficients stay.
+    pub fn reset(&mut self) {
+        self.x1 = T::default();
+        self.x2 = T::default();
+        self.y1 = T::default();
+        self.y2 = T::default();
+    }
+}
+
+/// Biquads in series: each block passes every section in turn.
+#[derive(Debug, Clone, Default)]
+pub struct BiquadChain<T = f32> {
+    sections: Vec<Biquad<T>>,
 }
 
-fn apply_biquad(samples: &mut [f32], sample_rate: u32, btype: BiquadType, freq: f32) {
-    let filt = Biquad::new(btype, freq, sample_rate);
-    let (mut x1, mut x2, mut y1, mut y2) = (0.0_f32, 0.0, 0.0, 0.0);
+impl<T: Sample> BiquadChain<T> {
+    pub fn new() -> Self {
+        Self {
+            sections: Vec::new(),
+        }
+    }
+
+    /// Append a section after the existing ones.
+    pub fn push(&mut self, section: Biquad<T>) {
+        self.sections.push(section);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// Filter `block` in place through every section, carrying each
+    /// section's state on to the next call.
+    pub fn process(&mut self, block: &mut [T]) {
+        for section in &mut self.sections {
+            section.process(block);
+        }
+    }
+
+    /// Every section back to silent state.
+    pub fn reset(&mut self) {
+        for section in &mut self.sections {
+            section.reset();
+        }
+    }
+
+    /// Combined magnitude response in dB at `freq` Hz.
+    pub fn magnitude_db(&self, freq: f64, sample_rate: u32) -> f64 {
+        self.sections
+            .iter()
+            .map(|s| s.magnitude_db(freq, sample_rate))
+            .sum()
+    }
+}
+
+impl<T: Sample> FromIterator<Biquad<T>> for BiquadChain<T> {
+    fn from_iter<I: IntoIterator<Item = Biquad<T>>>(iter: I) -> Self {
+        Self {
+            sections: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// One pass of a fresh [`Biquad`] over a whole buffer.
+fn apply_biquad<T: Sample>(samples: &mut [T], sample_rate: u32, btype: BiquadType, freq: f32) {
+    Biquad::new(btype, freq, sample_rate).process(samples);
+}
+
+// ── Noise Gate ──────────────────────────────────────────────────────────
+
+/// Timing and depth of [`apply_gate`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GateTiming {
+    /// Time to open fully from the floor, in ms.
+    pub attack_ms: f64,
+    /// How long the gate stays open after the signal drops below the
+    /// threshold, in ms, so it doesn't chatter between syllables.
+    pub hold_ms: f64,
+    /// Time to close fully down to the floor, in ms.
+    pub release_ms: f64,
+    /// Attenuation when closed, in dB (negative).
+    pub floor_db: f64,
+}
+
+impl Default for GateTiming {
+    fn default() -> Self {
+        Self {
+            attack_ms: 1.0,
+            hold_ms: 50.0,
+            release_ms: 150.0,
+            floor_db: -80.0,
+        }
+    }
+}
+
+/// Release of the gate's level detector, in ms: long enough to ride over
+/// the zero crossings of a low voice.
+const GATE_DETECTOR_RELEASE_MS: f64 = 10.0;
+
+/// Noise gate: a peak envelope follower (instant attack,
+/// [`GATE_DETECTOR_RELEASE_MS`] release) opens the gate whenever it is over
+/// `threshold_db` dBFS; once it has stayed under for the hold time the gate
+/// closes. The gain moves in dB, linearly, between 0 and the floor, over
+/// the attack time when opening and the release time when closing.
+pub fn apply_gate<T: Sample>(
+    samples: &mut [T],
+    sample_rate: u32,
+    threshold_db: f32,
+    timing: GateTiming,
+) {
+    if samples.is_empty() || sample_rate == 0 {
+        return;
+    }
+    let per_ms = sample_rate as f64 * 0.001;
+    let threshold = 10.0_f64.powf(threshold_db as f64 / 20.0);
+    let detector_release = (-1.0 / (GATE_DETECTOR_RELEASE_MS * per_ms)).exp();
+    let hold = (timing.hold_ms * per_ms).round() as usize;
+    let floor_db = timing.floor_db.min(0.0);
+    // dB per sample, opening and closing.
+    let open_step = -floor_db / (timing.attack_ms * per_ms).max(1.0);
+    let close_step = -floor_db / (timing.release_ms * per_ms).max(1.0);
+
+    let mut env = 0.0_f64;
+    let mut held = 0;
+    let mut gain_db = floor_db;
     for s in samples.iter_mut() {
-        *s = filt.process_sample(*s, &mut x1, &mut x2, &mut y1, &mut y2);
+        let level = s.to_f64().abs();
+        env = if level > env {
+            level
+        } else {
+            detector_release * env + (1.0 - detector_release) * level
+        };
+        if env > threshold {
+            held = hold;
+        } else {
+            held = held.saturating_sub(1);
+        }
+        gain_db = if env > threshold || held > 0 {
+            (gain_db + open_step).min(0.0)
+        } else {
+            (gain_db - close_step).max(floor_db)
+        };
+        if gain_db < 0.0 {
+            *s *= T::from_f64(10.0_f64.powf(gain_db / 20.0));
+        }
     }
 }
 
 // ── Compressor ──────────────────────────────────────────────────────────
 
+/// Width of the compressor's soft knee in dB, centred on the threshold.
+pub const COMP_KNEE_DB: f64 = 6.0;
+
+/// Gain reduction in dB (≤ 0) for an envelope `over_db` above the threshold:
+/// none below the knee, `(1 − 1/ratio)·over` above it, and the quadratic
+/// blend between the two inside it.
+fn compressor_gain_db(over_db: f64, ratio: f64) -> f64 {
+    let slope = 1.0 - 1.0 / ratio;
+    let half = COMP_KNEE_DB / 2.0;
+    if over_db <= -half {
+        0.0
+    } else if over_db >= half {
+        -slope * over_db
+    } else {
+        -slope * (over_db + half).powi(2) / (2.0 * COMP_KNEE_DB)
+    }
+}
+
+/// Feed-forward compressor: an attack/release envelope follower (`timing_ms`)
+/// drives [`compressor_gain_db`] at `ratio`.
+///
 /// L-8: The compressor applies makeup gain unconditionally (above and below threshold).
 /// This means signals below threshold are amplified, which raises the noise floor.
 /// This is standard compressor behavior — "upward compression" of quiet signals.
-fn apply_compressor(samples: &mut [f32], threshold_db: f32, sample_rate: u32) {
-    let threshold = 10.0_f32.powf(threshold_db / 20.0);
-    let ratio = 4.0_f32;
-    // Attack 5ms, release 50ms
-    let attack = (-1.0 / (0.005 * sample_rate as f32)).exp();
-    let release = (-1.0 / (0.050 * sample_rate as f32)).exp();
-    // Makeup gain: compensate roughly half the threshold reduction
-    let makeup = 10.0_f32.powf(-threshold_db / 40.0);
-
-    let mut env = 0.0_f32;
+/// `makeup_db` of `None` compensates roughly half the threshold reduction.
+fn apply_compressor<T: Sample>(
+    samples: &mut [T],
+    threshold_db: f32,
+    sample_rate: u32,
+    ratio: f32,
+    timing_ms: (f32, f32),
+    makeup_db: Option<f32>,
+) {
+    let threshold_db = threshold_db as f64;
+    let ratio = (ratio as f64).max(1.0);
+    let time_constant =
+        |ms: f32| T::from_f64((-1.0 / ((ms as f64).max(0.01) * 0.001 * sample_rate as f64)).exp());
+    let (attack, release) = (time_constant(timing_ms.0), time_constant(timing_ms.1));
+    let makeup_db = makeup_db.map_or(-threshold_db / 2.0, f64::from);
+    let makeup = 10.0_f64.powf(makeup_db / 20.0);
+    // Below this envelope the knee hasn't started: only the makeup applies.
+    let knee_start = T::from_f64(10.0_f64.powf((threshold_db - COMP_KNEE_DB / 2.0) / 20.0));
+    let one = T::from_f64(1.0);
+
+    let mut env = T::default();
     for s in samples.iter_mut() {
         let level = s.abs();
         let coeff = if level > env { attack } else { release };
-        env = coeff * env + (1.0 - coeff) * level;
+        env = coeff * env + (one - coeff) * level;
 
-        if env > threshold {
-            let gain = (threshold / env).powf(1.0 - 1.0 / ratio);
-            *s *= gain * makeup;
+        let gain_db = if env > knee_start {
+            compressor_gain_db(20.0 * env.to_f64().log10() - threshold_db, ratio)
+        } else {
+            0.0
+        };
+        *s *= T::from_f64(makeup * 10.0_f64.powf(gain_db / 20.0));
+    }
+}
+
+// ── Tremolo ─────────────────────────────────────────────────────────────
+
+/// Tremolo: each sample scaled by `1 − depth·(0.5 − 0.5·cos(2π·rate·t))`,
+/// so the level dips from full to `1 − depth` and back once per cycle,
+/// starting at full; depth 0 passes the signal through.
+pub fn apply_tremolo<T: Sample>(samples: &mut [T], sample_rate: u32, depth: f32, rate_hz: f32) {
+    if sample_rate == 0 {
+        return;
+    }
+    let depth = depth.clamp(0.0, 1.0) as f64;
+    let step = 2.0 * PI * rate_hz as f64 / sample_rate as f64;
+    for (i, s) in samples.iter_mut().enumerate() {
+        let dip = 0.5 - 0.5 * (step * i as f64).cos();
+        *s *= T::from_f64(1.0 - depth * dip);
+    }
+}
+
+// ── Limiter ─────────────────────────────────────────────────────────────
+
+/// How far ahead the limiter looks, in ms: the gain has reached its floor by
+/// the time the peak arrives, without an audible click.
+pub const LIMITER_LOOKAHEAD_MS: f64 = 1.5;
+
+/// Release time of the limiter in the effects chain, in ms.
+pub const LIMITER_RELEASE_MS: f64 = 50.0;
+
+/// Lowest limiter ceiling the Effects panel offers, in dBFS.
+pub const LIMITER_MIN_CEILING_DB: f32 = -6.0;
+
+/// Lookahead brickwall peak limiter: no sample comes out above
+/// `ceiling_db` dBFS. Each sample's gain is the smallest gain any sample in
+/// the next [`LIMITER_LOOKAHEAD_MS`] needs, averaged over the lookahead
+/// (so it ramps down over that time rather than stepping), then released
+/// back towards 1 with a `release_ms` time constant. A delay line of the
+/// lookahead holds the samples until their gain is known, and the output
+/// is read back in step with the input, so the length and timing don't
+/// change. Below the ceiling every gain is exactly 1: the signal passes
+/// through untouched.
+pub fn apply_limiter<T: Sample>(
+    samples: &mut [T],
+    sample_rate: u32,
+    ceiling_db: f32,
+    release_ms: f64,
+) {
+    if samples.is_empty() || sample_rate == 0 {
+        return;
+    }
+    let ceiling = 10.0_f64.powf(ceiling_db as f64 / 20.0);
+    let lookahead = ((LIMITER_LOOKAHEAD_MS * 0.001 * sample_rate as f64).round() as usize).max(1);
+    let release = (-1.0 / (release_ms.max(0.0) * 0.001 * sample_rate as f64)).exp();
+    let required = |x: T| {
+        let peak = x.to_f64().abs();
+        if peak > ceiling {
+            ceiling / peak
+        } else {
+            1.0
+        }
+    };
+
+    // Monotonic deque of (index, required gain) for the window minimum over
+    // the lookahead, and the running sum for its average.
+    let mut minima: VecDeque<(usize, f64)> = VecDeque::new();
+    let mut averaged: VecDeque<f64> = VecDeque::with_capacity(lookahead + 1);
+    let mut sum = 0.0;
+    let mut gain = 1.0_f64;
+    let mut delay: VecDeque<T> = VecDeque::with_capacity(lookahead + 1);
+    let n = samples.len();
+    // Sample `i` enters at step `i` and leaves the delay line at step
+    // `i + lookahead`, once every sample that can lower its gain is in.
+    for step in 0..n + lookahead {
+        if step < n {
+            let g = required(samples[step]);
+            while minima.back().is_some_and(|&(_, m)| m >= g) {
+                minima.pop_back();
+            }
+            minima.push_back((step, g));
+            delay.push_back(samples[step]);
+        }
+        let Some(out) = step.checked_sub(lookahead) else {
+            continue;
+        };
+        while minima.front().is_some_and(|&(i, _)| i < out) {
+            minima.pop_front();
+        }
+        let window_min = minima.front().map_or(1.0, |&(_, m)| m);
+        if out == 0 {
+            // The windows before the start would only reach into the first
+            // one, so its minimum stands in for them.
+            averaged.extend(std::iter::repeat_n(window_min, lookahead));
+            sum = window_min * lookahead as f64;
+        }
+        averaged.push_back(window_min);
+        sum += window_min;
+        if averaged.len() > lookahead + 1 {
+            sum -= averaged.pop_front().unwrap_or(0.0);
+        }
+        // Every averaged minimum covers the sample itself, so the average
+        // never lets it through above the ceiling.
+        let target = (sum / (lookahead + 1) as f64).min(1.0);
+        gain = if target < gain {
+            target
+        } else {
+            target + release * (gain - target)
+        };
+        let x = delay.pop_front().unwrap_or_default();
+        let y = (x.to_f64() * gain).clamp(-ceiling, ceiling);
+        samples[out] = if gain < 1.0 { T::from_f64(y) } else { x };
+    }
+}
+
+// ── De-esser ────────────────────────────────────────────────────────────
+
+/// Sibilance band the de-esser listens to and ducks, in Hz.
+pub const DEESS_BAND_HZ: (f32, f32) = (5000.0, 9000.0);
+
+/// Compression ratio of the de-esser above its threshold.
+const DEESS_RATIO: f64 = 8.0;
+
+/// Split-band de-esser: the 5–9 kHz band ([`DEESS_BAND_HZ`], a high-pass
+/// then a low-pass, scaled to unity gain at its geometric centre, where its
+/// phase is zero) is split off, with the rest of the signal its exact
+/// complement (the input minus the band), so at unity gain the sum is the
+/// input. The band's peak envelope (60 ms release) drives a
+/// [`DEESS_RATIO`] compressor above `threshold_db` dBFS that ducks only
+/// the band before the two are summed back.
+pub fn apply_deesser<T: Sample>(samples: &mut [T], sample_rate: u32, threshold_db: f32) {
+    if samples.is_empty() || sample_rate == 0 {
+        return;
+    }
+    let threshold = 10.0_f64.powf(threshold_db as f64 / 20.0);
+    let exponent = 1.0 - 1.0 / DEESS_RATIO;
+    let release = (-1.0 / (0.060 * sample_rate as f64)).exp();
+    let mut highpass = Biquad::<T>::new(BiquadType::Highpass, DEESS_BAND_HZ.0, sample_rate);
+    let mut lowpass = Biquad::<T>::new(BiquadType::Lowpass, DEESS_BAND_HZ.1, sample_rate);
+    let centre = (DEESS_BAND_HZ.0 as f64 * DEESS_BAND_HZ.1 as f64).sqrt();
+    let centre_db =
+        highpass.magnitude_db(centre, sample_rate) + lowpass.magnitude_db(centre, sample_rate);
+    let makeup = T::from_f64(10.0_f64.powf(-centre_db / 20.0));
+
+    let mut env = 0.0_f64;
+    for s in samples.iter_mut() {
+        let band = lowpass.process_sample(highpass.process_sample(*s)) * makeup;
+
+        let level = band.abs().to_f64();
+        env = if level > env {
+            level
         } else {
-            *s *= makeup;
+            release * env + (1.0 - release) * level
+        };
+        if env > threshold {
+            let gain = (threshold / env).powf(exponent);
+            *s = *s - band * T::from_f64(1.0 - gain);
         }
     }
 }
 
-// ── Pitch Shift (FX — resampling, changes buffer length) ───────────────
+// ── Pitch Shift (FX) ────────────────────────────────────────────────────
+
+/// FFT size of the pitch-shift phase vocoder: ~46 ms at 44.1 kHz, enough
+/// bins to resolve the harmonics of a low voice.
+const VOCODER_FFT_SIZE: usize = 2048;
+
+/// Synthesis hop of the phase vocoder: 75 % overlap of Hann windows.
+const VOCODER_HOP: usize = VOCODER_FFT_SIZE / 4;
 
-fn apply_pitch_shift(samples: &[f32], semitones: f32) -> Vec<f32> {
-    let ratio = 2.0_f32.powf(semitones / 12.0);
-    let new_len = ((samples.len() as f64) / ratio as f64).round().max(1.0) as usize;
+/// Duration-preserving pitch shift: [`resample_pitch`] moves the pitch
+/// (and the length by the same ratio), then a phase vocoder stretches the
+/// result back to exactly the input length. The vocoder reads frames every
+/// `hop / ratio` samples of the resampled signal and writes them every
+/// `hop`, advancing each bin's phase by its measured frequency, so the
+/// partials stay continuous across frames.
+fn apply_pitch_shift<T: Sample>(samples: &[T], semitones: f32) -> Vec<T> {
+    let n = samples.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let ratio = 2.0_f64.powf(semitones as f64 / 12.0);
+    let shifted: Vec<f64> = resample_pitch(samples, semitones)
+        .iter()
+        .map(|s| s.to_f64())
+        .collect();
+
+    let size = VOCODER_FFT_SIZE;
+    let half = size / 2;
+    let window: Vec<f64> = (0..size)
+        .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f64 / size as f64).cos())
+        .collect();
+    let mut planner = FftPlanner::<f64>::new();
+    let forward = planner.plan_fft_forward(size);
+    let inverse = planner.plan_fft_inverse(size);
+
+    let mut out = vec![0.0; n];
+    let mut norm = vec![0.0; n];
+    let mut frame = vec![Complex::new(0.0, 0.0); size];
+    let mut last_phase = vec![0.0; half + 1];
+    let mut phase = vec![0.0; half + 1];
+    // Enough frames either side that every output sample is under a full
+    // set of overlapping windows.
+    let first = -((size / VOCODER_HOP) as i64);
+    let last = (n / VOCODER_HOP) as i64 + (size / VOCODER_HOP) as i64;
+    let mut last_centre = None;
+    for k in first..=last {
+        let centre = (k as f64 * VOCODER_HOP as f64 / ratio).round() as i64;
+        for (i, bin) in frame.iter_mut().enumerate() {
+            let at = centre + i as i64 - half as i64;
+            let x = if (0..shifted.len() as i64).contains(&at) {
+                shifted[at as usize]
+            } else {
+                0.0
+            };
+            *bin = Complex::new(x * window[i], 0.0);
+        }
+        forward.process(&mut frame);
+        let magnitude: Vec<f64> = frame[..=half].iter().map(|c| c.norm()).collect();
+        let measured: Vec<f64> = frame[..=half].iter().map(|c| c.arg()).collect();
+        let advanced: Vec<f64> = (0..=half)
+            .map(|b| match last_centre {
+                Some(previous) if centre > previous => {
+                    let advance = (centre - previous) as f64;
+                    let expected = 2.0 * PI * b as f64 / size as f64;
+                    let deviation = measured[b] - last_phase[b] - expected * advance;
+                    let wrapped = deviation - 2.0 * PI * (deviation / (2.0 * PI)).round();
+                    phase[b] + (expected + wrapped / advance) * VOCODER_HOP as f64
+                }
+                Some(_) => phase[b] + 2.0 * PI * b as f64 / size as f64 * VOCODER_HOP as f64,
+                None => measured[b],
+            })
+            .collect();
+        // Identity phase locking: each bin keeps its measured phase
+        // relative to the nearest spectral peak, so the bins of one
+        // partial stay a coherent windowed sinusoid instead of drifting
+        // apart (phasiness, and a dip in level between frames).
+        let peaks: Vec<usize> = (0..=half)
+            .filter(|&b| {
+                (b.saturating_sub(2)..=(b + 2).min(half)).all(|j| magnitude[j] <= magnitude[b])
+            })
+            .collect();
+        let mut nearest = 0;
+        for b in 0..=half {
+            while nearest + 1 < peaks.len()
+                && peaks[nearest + 1].abs_diff(b) < peaks[nearest].abs_diff(b)
+            {
+                nearest += 1;
+            }
+            // No peak at all only for a non-finite frame.
+            let peak = peaks.get(nearest).copied().unwrap_or(b);
+            phase[b] = advanced[peak] + measured[b] - measured[peak];
+            frame[b] = Complex::from_polar(magnitude[b], phase[b]);
+            if b > 0 && b < half {
+                frame[size - b] = frame[b].conj();
+            }
+        }
+        last_phase.copy_from_slice(&measured);
+        last_centre = Some(centre);
+        inverse.process(&mut frame);
+
+        let start = k * VOCODER_HOP as i64 - half as i64;
+        for (i, bin) in frame.iter().enumerate() {
+            let at = start + i as i64;
+            if (0..n as i64).contains(&at) {
+                out[at as usize] += bin.re / size as f64 * window[i];
+                norm[at as usize] += window[i] * window[i];
+            }
+        }
+    }
+    out.iter()
+        .zip(&norm)
+        .map(|(&y, &w)| T::from_f64(if w > 1e-9 { y / w } else { 0.0 }))
+        .collect()
+}
+
+/// Pitch shift by linear-interpolation resampling: the pitch and the
+/// duration change together, by `2^(semitones/12)` and its inverse.
+fn resample_pitch<T: Sample>(samples: &[T], semitones: f32) -> Vec<T> {
+    let ratio = 2.0_f64.powf(semitones as f64 / 12.0);
+    let new_len = ((samples.len() as f64) / ratio).round().max(1.0) as usize;
+    let ratio = T::from_f64(ratio);
+    let one = T::from_f64(1.0);
 
     (0..new_len)
         .map(|i| {
-            let src = i as f32 * ratio;
-            let idx = src as usize;
-            let frac = src - idx as f32;
+            let src = T::from_f64(i as f64) * ratio;
+            let idx = src.to_f64() as usize;
+            let frac = src - T::from_f64(idx as f64);
             if idx + 1 < samples.len() {
-                samples[idx] * (1.0 - frac) + samples[idx + 1] * frac
+                samples[idx] * (one - frac) + samples[idx + 1] * frac
             } else if idx < samples.len() {
                 samples[idx]
             } else {
-                0.0
+                T::default()
             }
         })
         .collect()
 }
 
-// ── Reverb (Schroeder: 4 comb ∥ → 2 allpass series) ────────────────────
+// ── Chorus ──────────────────────────────────────────────────────────────
 
-fn apply_reverb(samples: &[f32], sample_rate: u32, mix: f32) -> Vec<f32> {
-    let scale = sample_rate as f32 / 44100.0;
+/// Centre delay of each chorus voice, in ms.
+const CHORUS_DELAYS_MS: [f64; 3] = [10.0, 15.0, 20.0];
 
-    // Comb filter delays and feedback gains
-    let comb_params: [(usize, f32); 4] = [
-        (((1557.0 * scale) as usize).max(1), 0.84),
-        (((1617.0 * scale) as usize).max(1), 0.82),
-        (((1491.0 * scale) as usize).max(1), 0.80),
-        (((1422.0 * scale) as usize).max(1), 0.78),
-    ];
-    // Allpass delays
-    let allpass_params: [(usize, f32); 2] = [
-        (((225.0 * scale) as usize).max(1), 0.5),
-        (((556.0 * scale) as usize).max(1), 0.5),
-    ];
+/// How far each chorus voice's delay swings either side of its centre, in
+/// ms: the voices span 5–25 ms between them.
+const CHORUS_DEPTH_MS: f64 = 5.0;
+
+/// Chorus: three voices, each the input through a delay line swept
+/// sinusoidally by ±[`CHORUS_DEPTH_MS`] around its centre
+/// ([`CHORUS_DELAYS_MS`]) at `rate_hz`, a third of a cycle apart, read with
+/// linear interpolation between samples. Their average is mixed with the
+/// dry signal by `mix`. One ring buffer, allocated up front, holds the
+/// longest delay, so the per-sample loop doesn't allocate.
+pub fn apply_chorus<T: Sample>(samples: &mut [T], sample_rate: u32, mix: f32, rate_hz: f32) {
+    if samples.is_empty() || sample_rate == 0 {
+        return;
+    }
+    let per_ms = sample_rate as f64 * 0.001;
+    let longest = CHORUS_DELAYS_MS.iter().copied().fold(0.0, f64::max) + CHORUS_DEPTH_MS;
+    let len = (longest * per_ms).ceil() as usize + 2;
+    let mut ring = vec![T::default(); len];
+    let mut write = 0;
+    let step = 2.0 * PI * rate_hz as f64 / sample_rate as f64;
+    let mix = mix.clamp(0.0, 1.0) as f64;
+    let wet_gain = T::from_f64(mix / CHORUS_DELAYS_MS.len() as f64);
+    let dry_gain = T::from_f64(1.0 - mix);
+    let one = T::from_f64(1.0);
+
+    for (i, s) in samples.iter_mut().enumerate() {
+        ring[write] = *s;
+        let mut wet = T::default();
+        for (voice, &centre_ms) in CHORUS_DELAYS_MS.iter().enumerate() {
+            let phase = step * i as f64 + 2.0 * PI * voice as f64 / CHORUS_DELAYS_MS.len() as f64;
+            let delay = (centre_ms + CHORUS_DEPTH_MS * phase.sin()) * per_ms;
+            let whole = delay.floor() as usize;
+            let frac = T::from_f64(delay - whole as f64);
+            let newer = ring[(write + len - whole) % len];
+            let older = ring[(write + len - whole - 1) % len];
+            wet += newer * (one - frac) + older * frac;
+        }
+        *s = dry_gain * *s + wet_gain * wet;
+        write = (write + 1) % len;
+    }
+}
+
+// ── Delay ───────────────────────────────────────────────────────────────
+
+/// Echoes the delay's tail leaves room for after the input ends.
+pub const DELAY_TAIL_REPEATS: usize = 3;
+
+/// A delay time in whole frames, at least one.
+fn delay_frames(time_ms: f32, sample_rate: u32) -> usize {
+    ((time_ms as f64 * 0.001 * sample_rate as f64).round() as usize).max(1)
+}
+
+/// Feedback delay: a ring buffer one delay time long holds the input plus
+/// `feedback` (clamped to 0.9) times what comes out of it, so the echoes
+/// repeat every `time_ms`, each `feedback` times the last. The echoes are
+/// mixed with the dry signal by `mix`. Works in place: the caller appends
+/// the room for the tail.
+pub fn apply_delay<T: Sample>(
+    samples: &mut [T],
+    sample_rate: u32,
+    time_ms: f32,
+    feedback: f32,
+    mix: f32,
+) {
+    if samples.is_empty() || sample_rate == 0 {
+        return;
+    }
+    let mut ring = vec![T::default(); delay_frames(time_ms, sample_rate)];
+    let feedback = T::from_f64(feedback.clamp(0.0, 0.9) as f64);
+    let mix = mix.clamp(0.0, 1.0) as f64;
+    let (wet_gain, dry_gain) = (T::from_f64(mix), T::from_f64(1.0 - mix));
+    let mut idx = 0;
+    for s in samples.iter_mut() {
+        let delayed = ring[idx];
+        ring[idx] = *s + feedback * delayed;
+        *s = dry_gain * *s + wet_gain * delayed;
+        idx = (idx + 1) % ring.len();
+    }
+}
+
+// ── Reverb (early reflections + Schroeder: N comb ∥ → allpass series) ───
+
+/// Delay/gain tables for one reverb preset. Delays are in samples at 44.1 kHz
+/// and scaled to the actual sample rate; early-reflection taps are in ms.
+struct ReverbTables {
+    combs: &'static [(f32, f32)],
+    allpasses: &'static [(f32, f32)],
+    early: &'static [(f32, f32)],
+}
+
+/// Small room: short, fast-decaying combs and a dense cluster of early taps.
+const ROOM_TABLES: ReverbTables = ReverbTables {
+    combs: &[
+        (1116.0, 0.70),
+        (1188.0, 0.69),
+        (1277.0, 0.68),
+        (1356.0, 0.66),
+    ],
+    allpasses: &[(225.0, 0.5), (341.0, 0.5)],
+    early: &[
+        (7.0, 0.55),
+        (11.0, 0.45),
+        (17.0, 0.38),
+        (23.0, 0.30),
+        (29.0, 0.22),
+    ],
+};
+
+/// Concert hall: long combs with high feedback and sparse, late early taps.
+const HALL_TABLES: ReverbTables = ReverbTables {
+    combs: &[
+        (2473.0, 0.89),
+        (2767.0, 0.88),
+        (3217.0, 0.87),
+        (3557.0, 0.86),
+    ],
+    allpasses: &[(556.0, 0.6), (441.0, 0.6)],
+    early: &[
+        (15.0, 0.40),
+        (27.0, 0.35),
+        (41.0, 0.30),
+        (58.0, 0.25),
+        (73.0, 0.20),
+        (90.0, 0.15),
+    ],
+};
+
+/// Plate: the original tuning; plates have no discrete early reflections.
+const PLATE_TABLES: ReverbTables = ReverbTables {
+    combs: &[
+        (1557.0, 0.84),
+        (1617.0, 0.82),
+        (1491.0, 0.80),
+        (1422.0, 0.78),
+    ],
+    allpasses: &[(225.0, 0.5), (556.0, 0.5)],
+    early: &[],
+};
 
+/// Range of [`EffectsParams::reverb_size`].
+pub const REVERB_SIZE_RANGE: (f32, f32) = (0.5, 2.0);
+
+/// Highest [`EffectsParams::reverb_damping`]; at 1 the combs would stop
+/// feeding back at all.
+pub const REVERB_MAX_DAMPING: f32 = 0.9;
+
+/// The preset's reverb reshaped by `size` (scales every comb, allpass and
+/// early-reflection delay, so the decay time grows with it at the preset's
+/// feedback), `damping` (a one-pole low-pass in each comb's feedback path)
+/// and `predelay_ms` (the whole wet signal starts that much later), mixed
+/// with the dry signal by `mix`. Keeps the input length.
+fn apply_reverb<T: Sample>(
+    samples: &[T],
+    sample_rate: u32,
+    mix: f32,
+    preset: ReverbPreset,
+    size: f32,
+    damping: f32,
+    predelay_ms: f32,
+) -> Vec<T> {
+    let tables = preset.tables();
+    let size = size.clamp(REVERB_SIZE_RANGE.0, REVERB_SIZE_RANGE.1);
+    let scale = sample_rate as f32 / 44100.0 * size;
+    let damping = T::from_f64(damping.clamp(0.0, REVERB_MAX_DAMPING) as f64);
     let n = samples.len();
 
-    // Sum of 4 parallel comb filters
-    let mut wet = vec![0.0_f32; n];
-    for &(delay, feedback) in &comb_params {
-        let out = comb_filter(samples, delay, feedback);
-        for (w, o) in wet.iter_mut().zip(out.iter()) {
+    // Sum of parallel comb filters
+    let mut wet = vec![T::default(); n];
+    for &(delay, feedback) in tables.combs {
+        let delay = ((delay * scale) as usize).max(1);
+        let out = comb_filter(samples, delay, T::from_f64(feedback as f64), damping);
+        for (w, &o) in wet.iter_mut().zip(out.iter()) {
             *w += o;
         }
     }
+    let comb_norm = T::from_f64(1.0 / tables.combs.len() as f64);
     for s in wet.iter_mut() {
-        *s *= 0.25;
+        *s *= comb_norm;
+    }
+
+    // Allpass filters in series
+    for &(delay, gain) in tables.allpasses {
+        let delay = ((delay * scale) as usize).max(1);
+        wet = allpass_filter(&wet, delay, T::from_f64(gain as f64));
     }
 
-    // Two allpass filters in series
-    for &(delay, gain) in &allpass_params {
-        wet = allpass_filter(&wet, delay, gain);
+    // Early reflections sit in front of the diffuse tail.
+    if !tables.early.is_empty() {
+        let early = early_reflections(samples, sample_rate, tables.early, size);
+        for (w, &e) in wet.iter_mut().zip(early.iter()) {
+            *w += e;
+        }
+    }
+
+    // Pre-delay
+    let predelay = (predelay_ms.max(0.0) * 0.001 * sample_rate as f32).round() as usize;
+    if predelay > 0 {
+        wet.truncate(n.saturating_sub(predelay));
+        wet.splice(0..0, std::iter::repeat_n(T::default(), predelay.min(n)));
     }
 
     // Wet/dry mix
+    let mix = T::from_f64(mix as f64);
+    let dry = T::from_f64(1.0) - mix;
     samples
         .iter()
         .zip(wet.iter())
-        .map(|(&d, &w)| (1.0 - mix) * d + mix * w)
+        .map(|(&d, &w)| dry * d + mix * w)
         .collect()
 }
 
-fn comb_filter(input: &[f32], delay: usize, feedback: f32) -> Vec<f32> {
+/// Discrete early-reflection taps: `(delay_ms, gain)` pairs, the delays
+/// scaled by `size`, summed onto a silent buffer of the input length.
+fn early_reflections<T: Sample>(
+    input: &[T],
+    sample_rate: u32,
+    taps: &[(f32, f32)],
+    size: f32,
+) -> Vec<T> {
     let n = input.len();
-    let mut output = vec![0.0_f32; n];
-    let mut buf = vec![0.0_f32; delay];
+    let mut output = vec![T::default(); n];
+    for &(delay_ms, gain) in taps {
+        let delay = (delay_ms * size * 0.001 * sample_rate as f32).round() as usize;
+        if delay >= n {
+            continue;
+        }
+        let gain = T::from_f64(gain as f64);
+        for (o, &x) in output[delay..].iter_mut().zip(input.iter()) {
+            *o += gain * x;
+        }
+    }
+    output
+}
+
+/// Feedback comb; the fed-back signal passes a one-pole low-pass with
+/// coefficient `damping` (0 = no filtering).
+fn comb_filter<T: Sample>(input: &[T], delay: usize, feedback: T, damping: T) -> Vec<T> {
+    let n = input.len();
+    let mut output = vec![T::default(); n];
+    let mut buf = vec![T::default(); delay];
     let mut idx = 0;
+    let mut lowpass = T::default();
+    let undamped = T::from_f64(1.0) - damping;
 
     for i in 0..n {
         let delayed = buf[idx];
-        buf[idx] = input[i] + feedback * delayed;
+        lowpass = undamped * delayed + damping * lowpass;
+        buf[idx] = input[i] + feedback * lowpass;
         output[i] = delayed;
         idx = (idx + 1) % delay;
     }
     output
 }
 
-fn allpass_filter(input: &[f32], delay: usize, gain: f32) -> Vec<f32> {
+fn allpass_filter<T: Sample>(input: &[T], delay: usize, gain: T) -> Vec<T> {
     let n = input.len();
-    let mut output = vec![0.0_f32; n];
-    let mut buf = vec![0.0_f32; delay];
+    let mut output = vec![T::default(); n];
+    let mut buf = vec![T::default(); delay];
     let mut idx = 0;
 
     for i in 0..n {
@@ -366,7 +1715,7 @@ fn allpass_filter(input: &[f32], delay: usize, gain: f32) -> Vec<f32> {
     output
 }
 
-// ── 12-Band Graphic EQ ───────────────────────────────────────────────────────
+// ── Graphic EQ ──────────────────────────────────────────────────────────────
 
 /// 12-band graphic EQ: frequencies and filter types.
 const EQ_BANDS: [(f32, &str); 12] = [
@@ -384,27 +1733,302 @@ const EQ_BANDS: [(f32, &str); 12] = [
     (16000.0, "shelf_high"),
 ];
 
-/// Apply 12-band graphic EQ to samples.
-pub fn apply_eq(samples: &mut [f32], sample_rate: u32, params: &EqParams) {
-    if params.is_neutral() || samples.is_empty() || sample_rate == 0 {
+/// 31-band graphic EQ: the ISO 266 1/3-octave centre frequencies.
+const EQ_BANDS_31: [(f32, &str); 31] = [
+    (20.0, "peak"),
+    (25.0, "peak"),
+    (31.5, "peak"),
+    (40.0, "peak"),
+    (50.0, "peak"),
+    (63.0, "peak"),
+    (80.0, "peak"),
+    (100.0, "peak"),
+    (125.0, "peak"),
+    (160.0, "peak"),
+    (200.0, "peak"),
+    (250.0, "peak"),
+    (315.0, "peak"),
+    (400.0, "peak"),
+    (500.0, "peak"),
+    (630.0, "peak"),
+    (800.0, "peak"),
+    (1000.0, "peak"),
+    (1250.0, "peak"),
+    (1600.0, "peak"),
+    (2000.0, "peak"),
+    (2500.0, "peak"),
+    (3150.0, "peak"),
+    (4000.0, "peak"),
+    (5000.0, "peak"),
+    (6300.0, "peak"),
+    (8000.0, "peak"),
+    (10000.0, "peak"),
+    (12500.0, "peak"),
+    (16000.0, "peak"),
+    (20000.0, "peak"),
+];
+
+/// Q of the 12-band EQ's peaking bands (~1 octave bandwidth).
+pub const EQ_PEAK_Q: f32 = 1.41;
+
+/// Q of the 31-band EQ's bands (1/3 octave bandwidth).
+pub const EQ_THIRD_OCTAVE_Q: f32 = 4.32;
+
+/// Range of [`EqParams::q`].
+pub const EQ_Q_RANGE: (f32, f32) = (0.7, 4.0);
+
+/// Frequency span a graphic EQ band mainly affects, in Hz: the -3 dB
+/// bandwidth `fc·(√(1 + 1/4Q²) ∓ 1/2Q)` for peaking bands of Q `q`; for the
+/// shelves, everything beyond half an octave inside the corner. `None` for
+/// an out-of-range band.
+pub fn eq_band_span_hz(layout: EqLayout, q: f32, band: usize) -> Option<(f32, f32)> {
+    let &(freq, kind) = layout.bands().get(band)?;
+    Some(match kind {
+        "shelf_low" => (0.0, freq * std::f32::consts::SQRT_2),
+        "shelf_high" => (freq / std::f32::consts::SQRT_2, f32::INFINITY),
+        _ => {
+            let half = 1.0 / (2.0 * q);
+            let root = (1.0 + half * half).sqrt();
+            (freq * (root - half), freq * (root + half))
+        }
+    })
+}
+
+/// Gain range of each graphic EQ band in dB (manual gain plus tilt is clamped to ±this).
+pub const EQ_BAND_RANGE_DB: f32 = 6.0;
+
+/// Per-band offsets of the EQ tilt macro: a straight line in log-frequency
+/// through 0 dB at the geometric centre, reaching `tilt_db` at the 16 kHz band
+/// and `-tilt_db` at the 31 Hz band.
+pub fn eq_tilt_offsets(tilt_db: f32) -> [f32; 12] {
+    let mut offsets = [0.0; 12];
+    offsets.copy_from_slice(&tilt_offsets(EqLayout::Bands12, tilt_db));
+    offsets
+}
+
+/// [`eq_tilt_offsets`] for any layout: `±tilt_db` at its outermost bands.
+fn tilt_offsets(layout: EqLayout, tilt_db: f32) -> Vec<f32> {
+    let bands = layout.bands();
+    let lo = bands[0].0.ln();
+    let hi = bands[bands.len() - 1].0.ln();
+    bands
+        .iter()
+        .map(|&(freq, _)| {
+            let pos = (freq.ln() - lo) / (hi - lo);
+            tilt_db * (2.0 * pos - 1.0)
+        })
+        .collect()
+}
+
+/// Band gains that realise the tilt line as the EQ's actual response.
+///
+/// Neighbouring bands overlap, so setting each band to its nominal offset
+/// over-steepens the slope (and the shelves only reach half gain at their
+/// corner). A few rounds of correction measured at the band centres, using
+/// the filters' analytic magnitude response, bring the response onto the line.
+fn tilt_band_gains(layout: EqLayout, q: f32, tilt_db: f32, sample_rate: u32) -> Vec<f32> {
+    let target = tilt_offsets(layout, tilt_db);
+    let mut gains = target.clone();
+    for _ in 0..12 {
+        let filters: BiquadChain<f64> = layout
+            .bands()
+            .iter()
+            .zip(gains.iter())
+            .map(|(&(freq, kind), &gain_db)| {
+                Biquad::new(eq_band_type(kind, gain_db, q), freq, sample_rate)
+            })
+            .collect();
+        for (j, &(freq, _)) in layout.bands().iter().enumerate() {
+            let response = filters.magnitude_db(freq as f64, sample_rate);
+            let err = target[j] as f64 - response;
+            gains[j] = (gains[j] + 0.6 * err as f32).clamp(-EQ_BAND_RANGE_DB, EQ_BAND_RANGE_DB);
+        }
+    }
+    gains
+}
+
+fn eq_band_type(kind: &str, gain_db: f32, q: f32) -> BiquadType {
+    match kind {
+        "shelf_low" => BiquadType::LowShelf { gain_db },
+        "shelf_high" => BiquadType::HighShelf { gain_db },
+        _ => BiquadType::Peaking { gain_db, q },
+    }
+}
+
+/// Apply the graphic EQ to one channel's samples, band by band of
+/// `params.layout` (peaking bands at [`EqParams::peak_q`]), with the tilt macro layered additively on top of each
+/// band's gain (clamped to [`EQ_BAND_RANGE_DB`]).
+pub fn apply_eq<T: Sample>(samples: &mut [T], sample_rate: u32, params: &EqParams, tilt_db: f32) {
+    let tilt_neutral = tilt_db.abs() < 1e-6;
+    if (params.is_neutral() && tilt_neutral) || samples.is_empty() || sample_rate == 0 {
         return;
     }
+    let (layout, q) = (params.layout, params.peak_q());
+    let tilt = if tilt_neutral {
+        vec![0.0; layout.band_count()]
+    } else {
+        tilt_band_gains(layout, q, tilt_db, sample_rate)
+    };
 
-    for (i, &(freq, kind)) in EQ_BANDS.iter().enumerate() {
-        let gain_db = params.gains[i];
+    let mut chain: BiquadChain<T> = layout
+        .bands()
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &(freq, kind))| {
+            let gain = params.gains.get(i).copied().unwrap_or(0.0);
+            let gain_db = (gain + tilt[i]).clamp(-EQ_BAND_RANGE_DB, EQ_BAND_RANGE_DB);
+            // Skip neutral bands
+            (gain_db.abs() >= 1e-6)
+                .then(|| Biquad::new(eq_band_type(kind, gain_db, q), freq, sample_rate))
+        })
+        .collect();
+    chain.process(samples);
+}
+
+/// Most bands the parametric EQ runs.
+pub const PARAMETRIC_MAX_BANDS: usize = 4;
+
+/// Gain range of a parametric EQ band in dB (±this).
+pub const PARAMETRIC_GAIN_RANGE_DB: f32 = 18.0;
+
+/// Q range of a parametric EQ band.
+pub const PARAMETRIC_Q_RANGE: (f32, f32) = (0.3, 16.0);
+
+/// Apply the parametric EQ bands in order to one channel's samples, each a
+/// cookbook biquad of its kind at its own frequency, Q and gain (clamped to
+/// [`PARAMETRIC_GAIN_RANGE_DB`] and [`PARAMETRIC_Q_RANGE`]). Bands past
+/// [`PARAMETRIC_MAX_BANDS`] and 0 dB bands are skipped.
+pub fn apply_parametric_eq<T: Sample>(
+    samples: &mut [T],
+    sample_rate: u32,
+    bands: &[ParametricBand],
+) {
+    if samples.is_empty() || sample_rate == 0 {
+        return;
+    }
+    for band in bands.iter().take(PARAMETRIC_MAX_BANDS) {
+        let gain_db = band
+            .gain_db
+            .clamp(-PARAMETRIC_GAIN_RANGE_DB, PARAMETRIC_GAIN_RANGE_DB);
         if gain_db.abs() < 1e-6 {
-            continue; // Skip neutral bands
+            continue;
         }
-
-        let btype = match kind {
-            "shelf_low" => BiquadType::LowShelf { gain_db },
-            "shelf_high" => BiquadType::HighShelf { gain_db },
-            _ => BiquadType::Peaking {
+        let btype = match band.kind {
+            ParametricKind::Peak => BiquadType::Peaking {
                 gain_db,
-                q: 1.41, // ~1 octave bandwidth
+                q: band.q.clamp(PARAMETRIC_Q_RANGE.0, PARAMETRIC_Q_RANGE.1),
             },
+            ParametricKind::LowShelf => BiquadType::LowShelf { gain_db },
+            ParametricKind::HighShelf => BiquadType::HighShelf { gain_db },
         };
+        apply_biquad(samples, sample_rate, btype, band.freq_hz);
+    }
+}
+
+// ── Loudness ────────────────────────────────────────────────────────────
+
+/// Block length and hop of [`measure_lufs_approx`], in seconds (BS.1770's
+/// 400 ms blocks, 75% overlap).
+const LOUDNESS_BLOCK_SECS: f64 = 0.4;
+const LOUDNESS_HOP_SECS: f64 = 0.1;
+
+/// Plain RMS of `samples` (interleaved channels pooled), linear.
+pub fn measure_rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let mean_square = samples
+        .iter()
+        .map(|&s| f64::from(s) * f64::from(s))
+        .sum::<f64>()
+        / samples.len() as f64;
+    mean_square.sqrt() as f32
+}
+
+/// Integrated loudness in LUFS, after BS.1770: K-weighted (the cookbook
+/// high shelf and high-pass standing in for the standard's pre-filters),
+/// gated absolutely at -70 LUFS and relatively 10 LU under the ungated
+/// mean. Channels are averaged rather than summed, so a mono buffer and
+/// the same signal on two channels read the same. Silence (nothing above
+/// the gates) reads [`SILENCE_DB`](crate::dsp::loudness::SILENCE_DB).
+pub fn measure_lufs_approx(samples: &[f32], sample_rate: u32, channels: u16) -> f32 {
+    use crate::dsp::loudness::SILENCE_DB;
 
-        apply_biquad(samples, sample_rate, btype, freq);
+    let channels = usize::from(channels.max(1));
+    let frames = samples.len() / channels;
+    if frames == 0 || sample_rate == 0 {
+        return SILENCE_DB;
+    }
+    let mut power = vec![0.0f64; frames];
+    for c in 0..channels {
+        let mut weighted: Vec<f64> = samples
+            .iter()
+            .skip(c)
+            .step_by(channels)
+            .map(|&s| f64::from(s))
+            .collect();
+        apply_biquad(
+            &mut weighted,
+            sample_rate,
+            BiquadType::HighShelf { gain_db: 4.0 },
+            1500.0,
+        );
+        apply_biquad(&mut weighted, sample_rate, BiquadType::Highpass, 38.0);
+        for (p, &w) in power.iter_mut().zip(&weighted) {
+            *p += w * w / channels as f64;
+        }
+    }
+
+    let block = ((LOUDNESS_BLOCK_SECS * f64::from(sample_rate)) as usize).clamp(1, frames);
+    let hop = ((LOUDNESS_HOP_SECS * f64::from(sample_rate)) as usize).max(1);
+    let blocks: Vec<f64> = (0..=(frames - block) / hop)
+        .map(|i| power[i * hop..i * hop + block].iter().sum::<f64>() / block as f64)
+        .collect();
+    let lufs = |mean_square: f64| -0.691 + 10.0 * mean_square.log10();
+    let gated_mean = |floor: f64| {
+        let kept: Vec<f64> = blocks
+            .iter()
+            .copied()
+            .filter(|&ms| ms > 0.0 && lufs(ms) > floor)
+            .collect();
+        (!kept.is_empty()).then(|| kept.iter().sum::<f64>() / kept.len() as f64)
+    };
+    let Some(absolute) = gated_mean(-70.0) else {
+        return SILENCE_DB;
+    };
+    let relative = gated_mean(lufs(absolute) - 10.0).unwrap_or(absolute);
+    (lufs(relative) as f32).max(SILENCE_DB)
+}
+
+/// What [`normalize_lufs`] measured and applied.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LufsNormalization {
+    /// Integrated loudness before the gain, in LUFS.
+    pub measured_lufs: f32,
+    pub gain_db: f32,
+}
+
+/// Loudness normalization: one static gain over the whole interleaved
+/// buffer that brings its [`measure_lufs_approx`] reading to `target_lufs`.
+/// Runs after the limiter, so a boost can take peaks back over its ceiling.
+/// Leaves a silent buffer alone and returns `None`.
+pub fn normalize_lufs(
+    samples: &mut [f32],
+    sample_rate: u32,
+    channels: u16,
+    target_lufs: f32,
+) -> Option<LufsNormalization> {
+    let measured_lufs = measure_lufs_approx(samples, sample_rate, channels);
+    if measured_lufs <= crate::dsp::loudness::SILENCE_DB {
+        return None;
+    }
+    let gain_db = target_lufs - measured_lufs;
+    let gain = 10.0_f32.powf(gain_db / 20.0);
+    for s in samples.iter_mut() {
+        *s *= gain;
     }
+    Some(LufsNormalization {
+        measured_lufs,
+        gain_db,
+    })
 }
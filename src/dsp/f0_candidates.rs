@@ -0,0 +1,173 @@
+use std::ops::Range;
+
+use world_sys::{F0Candidate, F0Search, WorldParams};
+
+use crate::audio::decoder::AudioData;
+use crate::dsp::inspect;
+use crate::dsp::world;
+
+/// Candidates kept per frame.
+pub const F0_CANDIDATE_COUNT: usize = 3;
+
+/// Two f0 values closer than this ratio (a semitone) are the same candidate.
+const SAME_PITCH_RATIO: f64 = 1.059_463_094_359_295_2;
+
+/// DIO passes run next to the main analysis. Each one errs differently
+/// (a low range halves high voices, a high range doubles low ones), so
+/// where they disagree the lattice shows the alternatives.
+pub const CANDIDATE_SEARCHES: [F0Search; 3] = [
+    // Low voices, below WORLD's default 71 Hz floor.
+    F0Search {
+        f0_floor: 40.0,
+        f0_ceil: 400.0,
+        channels_in_octave: 4.0,
+    },
+    // High voices and falsetto.
+    F0Search {
+        f0_floor: 140.0,
+        f0_ceil: 1600.0,
+        channels_in_octave: 4.0,
+    },
+    // Default range with a denser filter bank.
+    F0Search {
+        f0_floor: 71.0,
+        f0_ceil: 800.0,
+        channels_in_octave: 8.0,
+    },
+];
+
+/// A correction request: put candidate `rank` (0 = best) into f0 for every
+/// frame between the two times (either order, both ends included).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct F0Choice {
+    pub start_secs: f64,
+    pub end_secs: f64,
+    pub rank: usize,
+}
+
+/// Run the extra f0 tracks on `audio` and store the merged lattice in
+/// `params.f0_candidates`, with the analysis' own f0 as the first track.
+///
+/// # Errors
+///
+/// Returns an error if the audio is empty or has no sample rate.
+pub fn attach(audio: &AudioData, params: &mut WorldParams) -> Result<(), world_sys::WorldError> {
+    let mut tracks = vec![params.f0.clone()];
+    tracks.extend(world::track_f0(
+        audio,
+        &CANDIDATE_SEARCHES,
+        params.frame_period,
+    )?);
+    params.f0_candidates = Some(merge_candidates(&tracks, F0_CANDIDATE_COUNT));
+    Ok(())
+}
+
+/// Merge per-frame f0 tracks (0 = unvoiced) into at most `count` candidates
+/// per frame, best first. The first track sets the frame count.
+///
+/// Values within a semitone vote for the same candidate, which keeps the
+/// value of the earliest track that proposed it; the score is the share of
+/// tracks voting for it ("unvoiced" is a candidate too). Ties go to the
+/// earlier track. Remaining slots are filled with the octaves below and
+/// above the best voiced candidate (score 0), the usual tracker errors.
+pub fn merge_candidates(tracks: &[Vec<f64>], count: usize) -> Vec<Vec<F0Candidate>> {
+    let Some(primary) = tracks.first() else {
+        return Vec::new();
+    };
+    let voters = tracks.len() as f64;
+    (0..primary.len())
+        .map(|frame| {
+            let mut voiced: Vec<(f64, usize)> = Vec::new();
+            let mut unvoiced = 0;
+            for value in tracks.iter().map(|t| t.get(frame).copied().unwrap_or(0.0)) {
+                if !(value.is_finite() && value > 0.0) {
+                    unvoiced += 1;
+                } else if let Some(c) = voiced.iter_mut().find(|(f0, _)| same_pitch(*f0, value)) {
+                    c.1 += 1;
+                } else {
+                    voiced.push((value, 1));
+                }
+            }
+
+            let mut row: Vec<F0Candidate> = voiced
+                .iter()
+                .map(|&(f0, votes)| F0Candidate {
+                    f0,
+                    score: votes as f64 / voters,
+                })
+                .collect();
+            if unvoiced > 0 {
+                row.push(F0Candidate {
+                    f0: 0.0,
+                    score: unvoiced as f64 / voters,
+                });
+            }
+            // Stable: equal scores keep track order.
+            row.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+            if let Some(best) = row.iter().find(|c| c.f0 > 0.0).map(|c| c.f0) {
+                for octave in [best / 2.0, best * 2.0] {
+                    if row.len() < count && !row.iter().any(|c| same_pitch(c.f0, octave)) {
+                        row.push(F0Candidate {
+                            f0: octave,
+                            score: 0.0,
+                        });
+                    }
+                }
+            }
+            row.truncate(count);
+            row
+        })
+        .collect()
+}
+
+/// Frames from the one nearest `start_secs` to the one nearest `end_secs`
+/// (either order), as an index range. Empty without frames.
+pub fn frame_range(temporal_positions: &[f64], start_secs: f64, end_secs: f64) -> Range<usize> {
+    let (lo, hi) = if start_secs <= end_secs {
+        (start_secs, end_secs)
+    } else {
+        (end_secs, start_secs)
+    };
+    match (
+        inspect::nearest_frame(temporal_positions, lo),
+        inspect::nearest_frame(temporal_positions, hi),
+    ) {
+        (Some(first), Some(last)) => first..last + 1,
+        _ => 0..0,
+    }
+}
+
+/// Overwrite f0 in `frames` with each frame's candidate `rank`. Frames with
+/// fewer candidates keep their f0. Written frames count as fully voiced
+/// (a hand-picked f0 is trusted). Returns how many frames were written.
+///
+/// # Errors
+///
+/// Returns an error if `params` has no candidate lattice.
+pub fn apply_candidate(
+    params: &mut WorldParams,
+    frames: Range<usize>,
+    rank: usize,
+) -> Result<usize, String> {
+    let lattice = params
+        .f0_candidates
+        .as_ref()
+        .ok_or_else(|| "no f0 candidates (start with --extended-analysis)".to_string())?;
+    let end = frames.end.min(params.f0.len());
+    let mut written = 0;
+    for frame in frames.start.min(end)..end {
+        if let Some(candidate) = lattice.get(frame).and_then(|row| row.get(rank)) {
+            params.f0[frame] = candidate.f0;
+            if let Some(confidence) = params.voicing.get_mut(frame) {
+                *confidence = 1.0;
+            }
+            written += 1;
+        }
+    }
+    Ok(written)
+}
+
+fn same_pitch(a: f64, b: f64) -> bool {
+    a > 0.0 && b > 0.0 && (a / b).max(b / a) < SAME_PITCH_RATIO
+}
@@ -0,0 +1,137 @@
+use std::f64::consts::PI;
+
+use crate::audio::decoder::AudioData;
+
+/// Number of frames in `duration_secs` at `sample_rate`.
+fn frame_count(sample_rate: u32, duration_secs: f64) -> usize {
+    (sample_rate as f64 * duration_secs.max(0.0)).round() as usize
+}
+
+fn mono(samples: Vec<f32>, sample_rate: u32) -> AudioData {
+    AudioData {
+        samples,
+        sample_rate,
+        channels: 1,
+    }
+}
+
+/// Mono sine tone.
+pub fn sine(freq_hz: f64, amplitude: f32, sample_rate: u32, duration_secs: f64) -> AudioData {
+    chord(&[freq_hz], amplitude, sample_rate, duration_secs)
+}
+
+/// Mono sum of equal-level sines, scaled so the sum never exceeds `amplitude`.
+pub fn chord(freqs_hz: &[f64], amplitude: f32, sample_rate: u32, duration_secs: f64) -> AudioData {
+    let n = frame_count(sample_rate, duration_secs);
+    let per_voice = f64::from(amplitude) / freqs_hz.len().max(1) as f64;
+    let samples = (0..n)
+        .map(|i| {
+            let t = i as f64 / sample_rate as f64;
+            let sum: f64 = freqs_hz.iter().map(|f| (2.0 * PI * f * t).sin()).sum();
+            (sum * per_voice) as f32
+        })
+        .collect();
+    mono(samples, sample_rate)
+}
+
+/// Mono exponential sine sweep from `start_hz` to `end_hz` (equal time per octave).
+pub fn sweep(
+    start_hz: f64,
+    end_hz: f64,
+    amplitude: f32,
+    sample_rate: u32,
+    duration_secs: f64,
+) -> AudioData {
+    let n = frame_count(sample_rate, duration_secs);
+    let ratio = (end_hz / start_hz).ln();
+    let samples = (0..n)
+        .map(|i| {
+            let t = i as f64 / sample_rate as f64;
+            // Phase is the integral of f(t) = start · (end/start)^(t/T).
+            let phase = if ratio.abs() < 1e-12 {
+                2.0 * PI * start_hz * t
+            } else {
+                2.0 * PI * start_hz * duration_secs / ratio
+                    * ((t / duration_secs * ratio).exp() - 1.0)
+            };
+            f64::from(amplitude) as f32 * phase.sin() as f32
+        })
+        .collect();
+    mono(samples, sample_rate)
+}
+
+/// Mono speech-like test signal: `rate_hz` syllables per second, each a
+/// harmonic tone (five harmonics, falling 1/n) whose pitch glides down from
+/// 1.2 × `f0_hz` to `f0_hz`, with a raised-cosine level envelope over the
+/// first 60% of the syllable and silence between syllables.
+pub fn syllables(
+    f0_hz: f64,
+    rate_hz: f64,
+    amplitude: f32,
+    sample_rate: u32,
+    duration_secs: f64,
+) -> AudioData {
+    let n = frame_count(sample_rate, duration_secs);
+    let period = 1.0 / rate_hz;
+    let norm: f64 = (1..=5).map(|h| 1.0 / h as f64).sum();
+    let mut phase = 0.0;
+    let samples = (0..n)
+        .map(|i| {
+            let t = i as f64 / sample_rate as f64;
+            let within = (t % period) / (0.6 * period);
+            if within >= 1.0 {
+                return 0.0;
+            }
+            phase += 2.0 * PI * f0_hz * (1.2 - 0.2 * within) / sample_rate as f64;
+            let level = 0.5 - 0.5 * (2.0 * PI * within).cos();
+            let tone: f64 = (1..=5).map(|h| (h as f64 * phase).sin() / h as f64).sum();
+            (f64::from(amplitude) * level * tone / norm) as f32
+        })
+        .collect();
+    mono(samples, sample_rate)
+}
+
+/// Mono uniform white noise in `[-amplitude, amplitude)`. The same `seed`
+/// always gives the same samples.
+pub fn white_noise(amplitude: f32, sample_rate: u32, duration_secs: f64, seed: u64) -> AudioData {
+    // xorshift64*; zero is its only fixed point, so remap it.
+    let mut state = if seed == 0 {
+        0x9E37_79B9_7F4A_7C15
+    } else {
+        seed
+    };
+    let samples = (0..frame_count(sample_rate, duration_secs))
+        .map(|_| {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            let bits = state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 40;
+            let unit = bits as f32 / (1u64 << 24) as f32; // [0, 1)
+            amplitude * (2.0 * unit - 1.0)
+        })
+        .collect();
+    mono(samples, sample_rate)
+}
+
+/// Mono digital silence.
+pub fn silence(sample_rate: u32, duration_secs: f64) -> AudioData {
+    mono(
+        vec![0.0; frame_count(sample_rate, duration_secs)],
+        sample_rate,
+    )
+}
+
+/// Interleave mono signals into one multichannel buffer, truncated to the
+/// shortest input. Sample rates are taken from the first channel.
+pub fn interleave(channels: &[AudioData]) -> AudioData {
+    let sample_rate = channels.first().map_or(0, |c| c.sample_rate);
+    let frames = channels.iter().map(|c| c.samples.len()).min().unwrap_or(0);
+    let samples = (0..frames)
+        .flat_map(|i| channels.iter().map(move |c| c.samples[i]))
+        .collect();
+    AudioData {
+        samples,
+        sample_rate,
+        channels: channels.len() as u16,
+    }
+}
@@ -0,0 +1,109 @@
+use world_sys::WorldParams;
+
+use crate::dsp::world;
+
+/// Spectral peaks listed per frame by the inspector.
+pub const INSPECT_PEAK_COUNT: usize = 5;
+
+/// A local maximum of a WORLD spectral envelope, reported at its raw bin
+/// (no interpolation: the inspector shows the numbers as analyzed).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnvelopePeak {
+    pub bin: usize,
+    pub freq_hz: f64,
+    /// Power in dB (`10·log10`), floored with [`world::frame_energy_floor`].
+    pub level_db: f64,
+}
+
+/// Raw analysis values of one WORLD frame, for the inspector overlay.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameInfo {
+    pub frame: usize,
+    pub frame_count: usize,
+    /// `temporal_positions[frame]` in seconds (source time).
+    pub time_secs: f64,
+    /// Frame spacing in milliseconds; the overlay refreshes after the
+    /// playhead moves further than this.
+    pub frame_period_ms: f64,
+    /// Fundamental frequency in Hz, 0 for unvoiced frames.
+    pub f0_hz: f64,
+    /// Loudest envelope peaks, loudest first.
+    pub peaks: Vec<EnvelopePeak>,
+    /// Mean aperiodicity over all bins (0 = periodic, 1 = noise).
+    pub mean_aperiodicity: f64,
+}
+
+impl FrameInfo {
+    /// Values of the frame nearest `time_secs`, or `None` without frames.
+    pub fn from_params(params: &WorldParams, sample_rate: u32, time_secs: f64) -> Option<Self> {
+        let frame = nearest_frame(&params.temporal_positions, time_secs)?;
+        let envelope = params.spectrogram.get(frame).map_or(&[][..], Vec::as_slice);
+        let aperiodicity = params
+            .aperiodicity
+            .get(frame)
+            .map_or(&[][..], Vec::as_slice);
+        let mean_aperiodicity = if aperiodicity.is_empty() {
+            0.0
+        } else {
+            aperiodicity.iter().sum::<f64>() / aperiodicity.len() as f64
+        };
+        Some(Self {
+            frame,
+            frame_count: params.temporal_positions.len(),
+            time_secs: params.temporal_positions[frame],
+            frame_period_ms: params.frame_period,
+            f0_hz: params.f0.get(frame).copied().unwrap_or(0.0),
+            peaks: envelope_peaks(envelope, params.fft_size, sample_rate, INSPECT_PEAK_COUNT),
+            mean_aperiodicity,
+        })
+    }
+}
+
+/// Index of the frame whose temporal position is nearest `time_secs`
+/// (positions must be ascending). Ties go to the earlier frame; times
+/// outside the analysis clamp to the first or last frame.
+pub fn nearest_frame(temporal_positions: &[f64], time_secs: f64) -> Option<usize> {
+    let last = temporal_positions.len().checked_sub(1)?;
+    if !time_secs.is_finite() {
+        return Some(0);
+    }
+    let after = temporal_positions.partition_point(|&t| t < time_secs);
+    if after == 0 {
+        return Some(0);
+    }
+    if after > last {
+        return Some(last);
+    }
+    let before = after - 1;
+    if time_secs - temporal_positions[before] <= temporal_positions[after] - time_secs {
+        Some(before)
+    } else {
+        Some(after)
+    }
+}
+
+/// The `count` loudest local maxima of a power envelope (`fft_size / 2 + 1`
+/// bins), loudest first. Uses the same peak rule as
+/// [`crate::dsp::spectrum::find_peaks`]; a flat or silent row has none.
+pub fn envelope_peaks(
+    envelope: &[f64],
+    fft_size: usize,
+    sample_rate: u32,
+    count: usize,
+) -> Vec<EnvelopePeak> {
+    if envelope.len() < 3 || fft_size == 0 {
+        return Vec::new();
+    }
+    let bin_hz = f64::from(sample_rate) / fft_size as f64;
+    let mut peaks: Vec<EnvelopePeak> = (1..envelope.len() - 1)
+        .filter(|&k| envelope[k] > envelope[k - 1] && envelope[k] >= envelope[k + 1])
+        .map(|k| EnvelopePeak {
+            bin: k,
+            freq_hz: k as f64 * bin_hz,
+            level_db: 10.0 * world::frame_energy_floor(envelope[k]).log10(),
+        })
+        .collect();
+    peaks.sort_by(|x, y| y.level_db.total_cmp(&x.level_db));
+    peaks.truncate(count);
+    peaks
+}
@@ -0,0 +1,57 @@
+/// Level reported for an empty or all-zero buffer, in dBFS.
+pub const SILENCE_DB: f32 = -120.0;
+
+/// A/B level difference above which the readout is highlighted: louder
+/// usually sounds "better", so differences this large bias the comparison.
+pub const LOUDNESS_MATCH_TOLERANCE_DB: f32 = 1.0;
+
+/// RMS level of `samples` (interleaved channels are pooled) in dBFS.
+/// A full-scale sine reads about -3 dB.
+pub fn rms_dbfs(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return SILENCE_DB;
+    }
+    let mean_square = samples
+        .iter()
+        .map(|&s| f64::from(s) * f64::from(s))
+        .sum::<f64>()
+        / samples.len() as f64;
+    if mean_square <= 0.0 {
+        return SILENCE_DB;
+    }
+    (10.0 * mean_square.log10()).max(f64::from(SILENCE_DB)) as f32
+}
+
+/// Loudness of the original (A) and processed (B) buffers, measured on the
+/// worker after each synthesis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AbLoudness {
+    /// RMS of the analyzed original; `None` until analysis has run.
+    pub original_db: Option<f32>,
+    /// RMS of the processed buffer.
+    pub processed_db: f32,
+}
+
+impl AbLoudness {
+    /// B minus A in dB; `None` without an analyzed original.
+    pub fn delta_db(&self) -> Option<f32> {
+        self.original_db.map(|a| self.processed_db - a)
+    }
+
+    /// Whether A and B differ by more than [`LOUDNESS_MATCH_TOLERANCE_DB`].
+    pub fn is_mismatched(&self) -> bool {
+        self.delta_db()
+            .is_some_and(|d| d.abs() > LOUDNESS_MATCH_TOLERANCE_DB)
+    }
+
+    /// Status-bar text, e.g. "B is +2.3 dB louder than A".
+    pub fn describe(&self) -> String {
+        match self.delta_db() {
+            None => format!("B {:.1} dBFS RMS (A not analyzed)", self.processed_db),
+            // Rounds to ±0.0 at one decimal.
+            Some(d) if d.abs() < 0.05 => "B matches A in level".to_string(),
+            Some(d) if d > 0.0 => format!("B is +{d:.1} dB louder than A"),
+            Some(d) => format!("B is {:.1} dB quieter than A", -d),
+        }
+    }
+}
@@ -1,5 +1,11 @@
+pub mod classify;
 pub mod effects;
+pub mod f0_candidates;
+pub mod generate;
+pub mod inspect;
+pub mod loudness;
 pub mod modifier;
+pub mod parallel;
 pub mod processing;
 pub mod spectrum;
 pub mod world;
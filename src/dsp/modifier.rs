@@ -1,195 +1,2566 @@
-use world_sys::WorldParams;
+use std::ops::Range;
+use std::sync::Arc;
+
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+use world_sys::{WorldParams, CONFIDENT_VOICING, SANITIZED_SPECTRUM_BIN};
+
+use crate::dsp::{parallel, world};
 
 /// Slider values for WORLD parameter modification.
 #[derive(Debug, Clone, PartialEq)]
 pub struct WorldSliderValues {
+    /// Median filter window over voiced runs of f0, in frames (see
+    /// [`smooth_f0`]); below 2 = off.
+    pub f0_smoothing_frames: f64,
     /// Pitch shift in semitones.
     pub pitch_shift: f64,
+    /// Envelopes that take over some of the sliders across the take; see
+    /// [`Automation`].
+    pub automation: Automation,
     /// Pitch range scale factor (1.0 = unchanged).
     pub pitch_range: f64,
-    /// Speed factor (1.0 = unchanged, 2.0 = double speed).
+    /// What the pitch range expands around.
+    pub pitch_range_center: RangeCenter,
+    /// Speed factor (1.0 = unchanged, 2.0 = double speed); of the voiced
+    /// frames only when `speed_unvoiced` is set.
     pub speed: f64,
+    /// Speed factor of the unvoiced runs (pauses and most consonants); 0 =
+    /// the same as `speed`. See [`WorldSliderValues::unvoiced_speed`].
+    pub speed_unvoiced: f64,
     /// Breathiness multiplier (0.0 = unchanged).
     pub breathiness: f64,
+    /// Frequency in Hz the breathiness fades in around (see
+    /// [`breathiness_weight`]); 0 = the same amount on every bin.
+    pub breathiness_crossover_hz: f64,
     /// Formant shift in semitones.
     pub formant_shift: f64,
+    /// Rescale each formant-shifted frame back to its original power (see
+    /// [`formant_shift_row`]), so the shift doesn't change the loudness.
+    pub formant_keep_energy: bool,
+    /// How the formant shift maps the envelope's bins.
+    pub formant_warp: FormantWarp,
     /// Spectral tilt in dB/octave.
     pub spectral_tilt: f64,
+    /// Frequency the tilt pivots on, left at its level (see
+    /// [`spectral_tilt_row_at`]).
+    pub tilt_pivot_hz: f64,
+    /// Presence boost (or cut) in dB at `presence_center_hz`; see
+    /// [`apply_presence`].
+    pub presence_db: f64,
+    pub presence_center_hz: f64,
+    /// Width of the presence bell in octaves, at half its gain in dB.
+    pub presence_width_oct: f64,
+    /// Nasality in dB (0 = unchanged, negative = less nasal); see
+    /// [`apply_nasality`].
+    pub nasality_db: f64,
+    /// Denoise strength in dB (0 = off); see [`apply_denoise`].
+    pub denoise_db: f64,
+    /// Cepstral smoothing of the envelope, 0 (off) to 1 (only the coarsest
+    /// shape kept); see [`smooth_envelope_row`].
+    pub spectral_smoothing: f64,
+    /// Pull of voiced f0 towards `flatten_target_hz`, 0 (unchanged) to 1
+    /// (a monotone at the target).
+    pub flatten_amount: f64,
+    /// Pitch the contour flattens to, in Hz; 0 = the median of the voiced
+    /// frames.
+    pub flatten_target_hz: f64,
+    /// Pull of voiced f0 towards the nearest note of the retune scale, 0
+    /// (off) to 1 (fully on the note).
+    pub retune_strength: f64,
+    /// Time constant of the retune correction in ms; 0 snaps to each note
+    /// instantly.
+    pub retune_speed_ms: f64,
+    pub retune_scale: RetuneScale,
+    /// Root of the retune scale as a pitch class (0 = C … 11 = B).
+    pub retune_root: usize,
+    /// Slow random drift of voiced f0, in cents either way (0 = off, up
+    /// to [`HUMANIZE_MAX_CENTS`]); see [`humanize_drift`].
+    pub humanize_cents: f64,
+    /// Seed of the humanize drift: the same seed drifts the same way on
+    /// every render.
+    pub humanize_seed: u64,
+    /// Pitch-edit curve: `(source seconds, semitones)` points sorted by
+    /// time (see [`f0_offset_at`]); empty = no edits.
+    pub f0_offsets: Vec<(f64, f64)>,
+    /// Vibrato depth in cents (peak deviation; 0 = no vibrato).
+    pub vibrato_depth_cents: f64,
+    /// Vibrato rate in Hz.
+    pub vibrato_rate_hz: f64,
+    /// Growl (vocal fry) amount, 0 (off) to 1; see [`apply_growl`].
+    pub growl_depth: f64,
+    pub growl_rate: GrowlRate,
+    /// Robot voice at this pitch in Hz (see [`apply_robotize`]); `None` =
+    /// off.
+    pub robot_f0: Option<f64>,
+    /// Whisper: every frame unvoiced and fully aperiodic (see
+    /// [`apply_whisper`]). Overrides the pitch sliders and the robot.
+    pub whisper: bool,
+    /// Source seconds `(start, end)` the modifications are confined to
+    /// (see [`apply`]); `None` = the whole take.
+    pub region: Option<(f64, f64)>,
     /// When true, skip WORLD synthesis and use original mono directly.
     pub bypass: bool,
+    /// The cached f0 has manual corrections, so WORLD must run even with
+    /// every slider at neutral.
+    pub f0_edited: bool,
+}
+
+impl WorldSliderValues {
+    /// Check if all sliders are at their neutral (default) positions.
+    /// M-5: Use epsilon comparison to be robust against floating-point drift.
+    pub fn is_neutral(&self) -> bool {
+        const EPS: f64 = 1e-9;
+        self.f0_smoothing_frames < 2.0
+            && automated_value_is_zero(self.pitch_shift, &self.automation.pitch_shift, EPS)
+            && (self.pitch_range - 1.0).abs() < EPS
+            && !self.is_stretched()
+            && automated_value_is_zero(self.breathiness, &self.automation.breathiness, EPS)
+            && self.formant_shift.abs() < EPS
+            && automated_value_is_zero(self.spectral_tilt, &self.automation.spectral_tilt, EPS)
+            && self.presence_db.abs() < EPS
+            && self.nasality_db.abs() < EPS
+            && self.spectral_smoothing.abs() < EPS
+            && self.denoise_db.abs() < EPS
+            && self.flatten_amount.abs() < EPS
+            && self.retune_strength.abs() < EPS
+            && self.humanize_cents.abs() < EPS
+            && self
+                .f0_offsets
+                .iter()
+                .all(|&(_, semitones)| semitones.abs() < EPS)
+            && (self.vibrato_depth_cents.abs() < EPS || self.vibrato_rate_hz.abs() < EPS)
+            && self.growl_depth.abs() < EPS
+            && self.robot_f0.is_none()
+            && !self.whisper
+            && !self.f0_edited
+    }
+}
+
+impl WorldSliderValues {
+    /// The speed factor of unvoiced runs: `speed_unvoiced`, or `speed` when
+    /// that is 0. A voiced run and an unvoiced one are each stretched by
+    /// their own factor, so slowing the vowels down needn't drag the pauses
+    /// and consonants out with them.
+    pub fn unvoiced_speed(&self) -> f64 {
+        if self.speed_unvoiced > 0.0 {
+            self.speed_unvoiced
+        } else {
+            self.speed
+        }
+    }
+
+    /// True when the render's length differs from the source's: either
+    /// speed is away from 1.
+    pub fn is_stretched(&self) -> bool {
+        const EPS: f64 = 1e-9;
+        (self.speed - 1.0).abs() > EPS || (self.unvoiced_speed() - 1.0).abs() > EPS
+    }
+
+    /// Neutral values with `preset` at `intensity` (see
+    /// [`WorldSliderValues::with_preset`]).
+    pub fn preset(preset: VoicePreset, intensity: f64) -> Self {
+        Self::default().with_preset(preset, intensity)
+    }
+
+    pub fn preset_feminize(intensity: f64) -> Self {
+        Self::preset(VoicePreset::Feminize, intensity)
+    }
+
+    pub fn preset_masculinize(intensity: f64) -> Self {
+        Self::preset(VoicePreset::Masculinize, intensity)
+    }
+
+    pub fn preset_child(intensity: f64) -> Self {
+        Self::preset(VoicePreset::Child, intensity)
+    }
+
+    pub fn preset_elderly(intensity: f64) -> Self {
+        Self::preset(VoicePreset::Elderly, intensity)
+    }
+
+    /// These values with the pitch shift, formant shift, breathiness and
+    /// spectral tilt set to `preset`'s, scaled towards neutral by
+    /// `intensity` (0 = neutral, 1 = the full preset). The four are set,
+    /// not added to: presets don't stack, the last one applied wins.
+    pub fn with_preset(self, preset: VoicePreset, intensity: f64) -> Self {
+        let amount = intensity.clamp(0.0, 1.0);
+        let target = preset.targets();
+        Self {
+            pitch_shift: target.pitch_shift * amount,
+            formant_shift: target.formant_shift * amount,
+            breathiness: target.breathiness * amount,
+            spectral_tilt: target.spectral_tilt * amount,
+            ..self
+        }
+    }
+}
+
+/// Coordinated pitch, formant, breathiness and tilt settings for a voice
+/// character ([`WorldSliderValues::with_preset`]). The presets aren't
+/// mirror images: feminize adds a little breath that masculinize doesn't
+/// take away, and the formants move less than the pitch, as between real
+/// speakers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoicePreset {
+    Feminize,
+    Masculinize,
+    Child,
+    Elderly,
+}
+
+impl VoicePreset {
+    /// All presets in selector order (matches the WORLD panel slider).
+    pub const ALL: [VoicePreset; 4] = [
+        VoicePreset::Feminize,
+        VoicePreset::Masculinize,
+        VoicePreset::Child,
+        VoicePreset::Elderly,
+    ];
+    /// Display names in selector order.
+    pub const NAMES: [&'static str; 4] = ["Feminize", "Masculinize", "Child", "Elderly"];
+
+    /// The preset's four sliders at full intensity; everything else
+    /// neutral.
+    fn targets(self) -> WorldSliderValues {
+        let (pitch_shift, formant_shift, breathiness, spectral_tilt) = match self {
+            VoicePreset::Feminize => (5.0, 2.0, 0.2, 1.0),
+            VoicePreset::Masculinize => (-5.0, -2.0, 0.0, -1.5),
+            // Smaller vocal tract: formants up almost as far as the pitch.
+            VoicePreset::Child => (8.0, 4.0, 0.1, 2.0),
+            // Lower, breathier and duller, with the formants barely moved.
+            VoicePreset::Elderly => (-2.0, -0.5, 0.6, -2.5),
+        };
+        WorldSliderValues {
+            pitch_shift,
+            formant_shift,
+            breathiness,
+            spectral_tilt,
+            ..WorldSliderValues::default()
+        }
+    }
+}
+
+impl Default for WorldSliderValues {
+    fn default() -> Self {
+        Self {
+            f0_smoothing_frames: 0.0,
+            pitch_shift: 0.0,
+            automation: Automation::default(),
+            pitch_range: 1.0,
+            pitch_range_center: RangeCenter::default(),
+            speed: 1.0,
+            speed_unvoiced: 0.0,
+            breathiness: 0.0,
+            breathiness_crossover_hz: 2000.0,
+            formant_shift: 0.0,
+            formant_keep_energy: true,
+            formant_warp: FormantWarp::default(),
+            spectral_tilt: 0.0,
+            tilt_pivot_hz: TILT_PIVOT_HZ,
+            presence_db: 0.0,
+            presence_center_hz: PRESENCE_CENTER_HZ,
+            presence_width_oct: PRESENCE_WIDTH_OCT,
+            nasality_db: 0.0,
+            denoise_db: 0.0,
+            spectral_smoothing: 0.0,
+            flatten_amount: 0.0,
+            flatten_target_hz: 0.0,
+            retune_strength: 0.0,
+            retune_speed_ms: 50.0,
+            retune_scale: RetuneScale::default(),
+            retune_root: 0,
+            humanize_cents: 0.0,
+            humanize_seed: 0,
+            f0_offsets: Vec::new(),
+            vibrato_depth_cents: 0.0,
+            vibrato_rate_hz: 5.0,
+            growl_depth: 0.0,
+            growl_rate: GrowlRate::default(),
+            robot_f0: None,
+            whisper: false,
+            region: None,
+            bypass: false,
+            f0_edited: false,
+        }
+    }
+}
+
+/// Breakpoint envelopes over source time for the sliders that can change
+/// across the take. Each lane holds `(source seconds, value)` points sorted
+/// by time, read like [`f0_offset_at`]: linear between points, held before
+/// the first and after the last. A lane with points replaces its slider's
+/// value; an empty lane leaves the slider in force.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Automation {
+    /// Pitch shift in semitones.
+    pub pitch_shift: Vec<(f64, f64)>,
+    pub breathiness: Vec<(f64, f64)>,
+    /// Spectral tilt in dB/octave.
+    pub spectral_tilt: Vec<(f64, f64)>,
+}
+
+impl Automation {
+    /// True when no lane has points.
+    pub fn is_empty(&self) -> bool {
+        self.pitch_shift.is_empty() && self.breathiness.is_empty() && self.spectral_tilt.is_empty()
+    }
+
+    /// These lanes for a slice of the take starting `secs` seconds in, whose
+    /// positions start at 0.
+    pub fn shifted(&self, secs: f64) -> Self {
+        let shift = |lane: &[(f64, f64)]| lane.iter().map(|&(t, v)| (t - secs, v)).collect();
+        Self {
+            pitch_shift: shift(&self.pitch_shift),
+            breathiness: shift(&self.breathiness),
+            spectral_tilt: shift(&self.spectral_tilt),
+        }
+    }
+}
+
+/// A slider's value at `t` source seconds: `lane` there when it has points
+/// (see [`Automation`]), otherwise the slider's `value`.
+pub fn automated_value(value: f64, lane: &[(f64, f64)], t: f64) -> f64 {
+    if lane.is_empty() {
+        value
+    } else {
+        f0_offset_at(lane, t)
+    }
+}
+
+/// True when a slider with `lane` is within `eps` of 0 all through the take.
+fn automated_value_is_zero(value: f64, lane: &[(f64, f64)], eps: f64) -> bool {
+    if lane.is_empty() {
+        value.abs() < eps
+    } else {
+        lane.iter().all(|&(_, v)| v.abs() < eps)
+    }
+}
+
+/// The pitch the range stage expands and compresses around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RangeCenter {
+    /// Confidence-weighted mean of the voiced frames.
+    #[default]
+    Mean,
+    /// Confidence-weighted median of the voiced frames, so a long low (or
+    /// high) stretch doesn't drag the centre off the other one.
+    Median,
+    /// The median of each voiced run on its own.
+    PerSegment,
+}
+
+impl RangeCenter {
+    /// All centres in cycling order.
+    pub const ALL: [RangeCenter; 3] = [
+        RangeCenter::Mean,
+        RangeCenter::Median,
+        RangeCenter::PerSegment,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RangeCenter::Mean => "mean",
+            RangeCenter::Median => "median",
+            RangeCenter::PerSegment => "per segment",
+        }
+    }
+
+    /// The next centre, wrapping around.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&c| c == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// How the formant shift maps the envelope's bins. Both move an envelope
+/// feature at `f` Hz to `f · 2^(semitones / 12)`; they differ in how the
+/// envelope is read between bins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FormantWarp {
+    /// Interpolate linearly in power between the two nearest bins
+    /// ([`formant_warp_row`]).
+    #[default]
+    Linear,
+    /// Interpolate in log frequency and log power, and average the bins a
+    /// downward shift squeezes together ([`formant_log_warp_row`]).
+    Log,
+}
+
+impl FormantWarp {
+    /// All warps in cycling order.
+    pub const ALL: [FormantWarp; 2] = [FormantWarp::Linear, FormantWarp::Log];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FormantWarp::Linear => "linear",
+            FormantWarp::Log => "log",
+        }
+    }
+
+    /// The next warp, wrapping around.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&w| w == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Pitch-class names, C first; the retune root's selector order.
+pub const NOTE_NAMES: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+/// Notes the retune stage snaps to, relative to the root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RetuneScale {
+    /// Every semitone; the root doesn't matter.
+    #[default]
+    Chromatic,
+    Major,
+    /// Natural minor.
+    Minor,
+}
+
+impl RetuneScale {
+    /// All scales in selector order (matches the WORLD panel slider).
+    pub const ALL: [RetuneScale; 3] = [
+        RetuneScale::Chromatic,
+        RetuneScale::Major,
+        RetuneScale::Minor,
+    ];
+    /// Display names in selector order.
+    pub const NAMES: [&'static str; 3] = ["Chromatic", "Major", "Minor"];
+
+    /// Scale for a selector index, clamped to the last scale.
+    pub fn from_index(index: usize) -> Self {
+        Self::ALL[index.min(Self::ALL.len() - 1)]
+    }
+
+    /// Semitones above the root of each note in the scale.
+    fn intervals(self) -> &'static [i64] {
+        match self {
+            RetuneScale::Chromatic => &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+            RetuneScale::Major => &[0, 2, 4, 5, 7, 9, 11],
+            RetuneScale::Minor => &[0, 2, 3, 5, 7, 8, 10],
+        }
+    }
+}
+
+/// Which stages of [`apply`] differ between two sets of slider values: the
+/// stages a resynthesis has to redo. A render whose stages are all clean
+/// can be reused as it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DirtyStages {
+    /// Smoothing, pitch shift (or its automation), pitch range, flatten,
+    /// retune, humanize, vibrato, robot, whisper, the region or the f0 edit
+    /// flag changed.
+    pub f0: bool,
+    /// Either speed changed (every frame moves).
+    pub speed: bool,
+    /// Smoothing, denoise, breathiness, formant shift, spectral tilt (or
+    /// their automation), presence, nasality, growl, robot, whisper or the
+    /// region changed.
+    pub spectral: bool,
+}
+
+impl DirtyStages {
+    /// Every stage, e.g. when bypass was toggled.
+    pub const ALL: Self = Self {
+        f0: true,
+        speed: true,
+        spectral: true,
+    };
+
+    /// The stages that change going from `previous` to `next`.
+    pub fn between(previous: &WorldSliderValues, next: &WorldSliderValues) -> Self {
+        if previous.bypass != next.bypass {
+            return Self::ALL;
+        }
+        Self {
+            f0: previous.f0_smoothing_frames != next.f0_smoothing_frames
+                || previous.pitch_shift != next.pitch_shift
+                || previous.automation.pitch_shift != next.automation.pitch_shift
+                || previous.pitch_range != next.pitch_range
+                || previous.pitch_range_center != next.pitch_range_center
+                || previous.flatten_amount != next.flatten_amount
+                || previous.flatten_target_hz != next.flatten_target_hz
+                || previous.retune_strength != next.retune_strength
+                || previous.retune_speed_ms != next.retune_speed_ms
+                || previous.retune_scale != next.retune_scale
+                || previous.retune_root != next.retune_root
+                || previous.humanize_cents != next.humanize_cents
+                || previous.humanize_seed != next.humanize_seed
+                || previous.f0_offsets != next.f0_offsets
+                || previous.vibrato_depth_cents != next.vibrato_depth_cents
+                || previous.vibrato_rate_hz != next.vibrato_rate_hz
+                || previous.growl_depth != next.growl_depth
+                || previous.growl_rate != next.growl_rate
+                || previous.robot_f0 != next.robot_f0
+                || previous.whisper != next.whisper
+                || previous.region != next.region
+                || previous.f0_edited != next.f0_edited,
+            speed: previous.speed != next.speed || previous.speed_unvoiced != next.speed_unvoiced,
+            spectral: previous.spectral_smoothing != next.spectral_smoothing
+                || previous.denoise_db != next.denoise_db
+                || previous.breathiness != next.breathiness
+                || previous.automation.breathiness != next.automation.breathiness
+                || previous.breathiness_crossover_hz != next.breathiness_crossover_hz
+                || previous.formant_shift != next.formant_shift
+                || previous.formant_keep_energy != next.formant_keep_energy
+                || previous.formant_warp != next.formant_warp
+                || previous.spectral_tilt != next.spectral_tilt
+                || previous.automation.spectral_tilt != next.automation.spectral_tilt
+                || previous.tilt_pivot_hz != next.tilt_pivot_hz
+                || previous.presence_db != next.presence_db
+                || previous.presence_center_hz != next.presence_center_hz
+                || previous.presence_width_oct != next.presence_width_oct
+                || previous.nasality_db != next.nasality_db
+                || previous.growl_depth != next.growl_depth
+                || previous.growl_rate != next.growl_rate
+                || previous.robot_f0 != next.robot_f0
+                || previous.whisper != next.whisper
+                || previous.region != next.region,
+        }
+    }
+
+    pub fn any(&self) -> bool {
+        self.f0 || self.speed || self.spectral
+    }
+}
+
+/// The frames whose values differ between two rows of per-frame values
+/// (e.g. the f0 of the last render and the cached f0 after a correction),
+/// as one span from the first to the last difference; `None` when they are
+/// equal. Rows of different lengths differ everywhere.
+pub fn changed_frames(previous: &[f64], next: &[f64]) -> Option<Range<usize>> {
+    if previous.len() != next.len() {
+        return Some(0..previous.len().max(next.len()));
+    }
+    let differs = |(a, b): (&f64, &f64)| a.to_bits() != b.to_bits();
+    let first = previous.iter().zip(next).position(differs)?;
+    let last = previous.iter().zip(next).rposition(differs)?;
+    Some(first..last + 1)
+}
+
+/// Apply slider-driven modifications to WORLD parameters.
+///
+/// Returns a new `WorldParams` with modifications applied.
+/// The original `params` is not mutated.
+///
+/// With [`WorldSliderValues::whisper`] the f0 stages are skipped (there is
+/// no pitch left for them to move) and the result is whispered after the
+/// speed and spectral stages, so the envelope still follows the formant
+/// and tilt sliders.
+///
+/// With a [`WorldSliderValues::region`] every stage still runs over the
+/// whole take (the pitch range keeps its whole-take mean), but only the
+/// frames inside the region keep the result: the rest are the original
+/// frames, and the first and last [`REGION_RAMP_SECS`] of the region
+/// crossfade between the two (see [`region_weight`]). The speed can't be
+/// confined and stretches everything, after the crossfade.
+pub fn apply(params: &WorldParams, values: &WorldSliderValues) -> WorldParams {
+    let mut result = WorldParams::default();
+    apply_into(params, values, &mut result);
+    result
+}
+
+/// [`apply`] into `scratch`, reusing its buffers rather than allocating a
+/// copy of `src` per call, so a processing thread that keeps one scratch
+/// across renders allocates nothing for a render the size of the last.
+/// Rows of the envelope and aperiodicity are only written where they
+/// differ from `src`'s: with every spectral slider at neutral (e.g. a
+/// render after a pitch change), a scratch that already holds the source
+/// rows is only read. On a 2-minute take at 48 kHz (24 000 frames of 1025
+/// bins, ~390 MB of rows) a pitch-only modification takes ~105 ms in a
+/// release build against ~270 ms for [`apply`], and allocates nothing
+/// after the first.
+pub fn apply_into(src: &WorldParams, values: &WorldSliderValues, scratch: &mut WorldParams) {
+    let Some(region) = values.region else {
+        apply_everywhere_into(src, values, scratch);
+        return;
+    };
+    let unstretched = WorldSliderValues {
+        speed: 1.0,
+        speed_unvoiced: 0.0,
+        region: None,
+        ..values.clone()
+    };
+    apply_everywhere_into(src, &unstretched, scratch);
+    confine_to_region(scratch, src, 0, region);
+    apply_speed(scratch, values.speed, values.unvoiced_speed());
+}
+
+/// [`apply_into`] without the region.
+fn apply_everywhere_into(
+    params: &WorldParams,
+    values: &WorldSliderValues,
+    result: &mut WorldParams,
+) {
+    copy_params_into(params, result);
+    // The denoise judges frames by the source's voicing and levels, so it
+    // runs before anything else changes them.
+    if values.denoise_db > 0.0 {
+        apply_denoise(result, values.denoise_db);
+    }
+    let f0 = if values.whisper {
+        F0Pipeline { stages: Vec::new() }
+    } else {
+        F0Pipeline::from_values(values)
+    };
+    run_pipelines(
+        result,
+        &f0,
+        (values.speed, values.unvoiced_speed()),
+        &SpectralPipeline::from_values(values),
+    );
+    if values.whisper {
+        apply_whisper(result);
+    }
+}
+
+/// Make `dst` a copy of `src` without the candidate lattice (it is
+/// analysis-only), reusing `dst`'s buffers. Rows already equal to `src`'s,
+/// bit for bit, aren't written.
+fn copy_params_into(src: &WorldParams, dst: &mut WorldParams) {
+    let sync_rows = |dst: &mut Vec<Vec<f64>>, src: &[Vec<f64>]| {
+        dst.truncate(src.len());
+        for (d, s) in dst.iter_mut().zip(src) {
+            if d.len() != s.len() || d.iter().zip(s).any(|(a, b)| a.to_bits() != b.to_bits()) {
+                d.clone_from(s);
+            }
+        }
+        let kept = dst.len();
+        dst.extend_from_slice(&src[kept..]);
+    };
+    dst.f0.clone_from(&src.f0);
+    dst.temporal_positions.clone_from(&src.temporal_positions);
+    sync_rows(&mut dst.spectrogram, &src.spectrogram);
+    sync_rows(&mut dst.aperiodicity, &src.aperiodicity);
+    dst.fft_size = src.fft_size;
+    dst.frame_period = src.frame_period;
+    dst.sample_rate = src.sample_rate;
+    dst.f0_candidates = None;
+    dst.voicing.clone_from(&src.voicing);
+}
+
+/// Median-filter each voiced run of `params.f0` over `window_frames` frames
+/// (an even window uses the next odd size; below 2 is a no-op), removing
+/// single-frame octave jumps without touching the voicing. The window
+/// never reaches across an unvoiced gap and shrinks symmetrically at the
+/// ends of a run, so a run's first and last frames are kept; see
+/// [`reject_f0_outliers`] for those.
+pub fn smooth_f0(params: &mut WorldParams, window_frames: usize) {
+    median_filter_voiced(&mut params.f0, window_frames);
+}
+
+/// Unvoice (f0 = 0) every voiced frame more than `max_semitones` away from
+/// all its voiced neighbours, e.g. a doubled frame at the start of a run
+/// that [`smooth_f0`] can't reach. Frames without a voiced neighbour are
+/// kept.
+pub fn reject_f0_outliers(params: &mut WorldParams, max_semitones: f64) {
+    let f0 = &params.f0;
+    let semitones = |a: f64, b: f64| 12.0 * (a / b).log2().abs();
+    let outliers: Vec<usize> = (0..f0.len())
+        .filter(|&i| f0[i] > 0.0)
+        .filter(|&i| {
+            let neighbours = [i.checked_sub(1), Some(i + 1)]
+                .into_iter()
+                .flatten()
+                .filter_map(|j| f0.get(j).copied())
+                .filter(|&f| f > 0.0);
+            let mut distances = neighbours.map(|f| semitones(f0[i], f)).peekable();
+            distances.peek().is_some() && distances.all(|d| d > max_semitones)
+        })
+        .collect();
+    for i in outliers {
+        params.f0[i] = 0.0;
+    }
+}
+
+fn median_filter_voiced(f0: &mut [f64], window_frames: usize) {
+    let half = window_frames / 2;
+    if half == 0 {
+        return;
+    }
+    let mut start = 0;
+    while start < f0.len() {
+        if f0[start] <= 0.0 {
+            start += 1;
+            continue;
+        }
+        let end = start
+            + f0[start..]
+                .iter()
+                .position(|&f| f <= 0.0)
+                .unwrap_or(f0.len() - start);
+        let run = f0[start..end].to_vec();
+        let mut window = Vec::with_capacity(2 * half + 1);
+        for (i, out) in f0[start..end].iter_mut().enumerate() {
+            let reach = half.min(i).min(run.len() - 1 - i);
+            window.clear();
+            window.extend_from_slice(&run[i - reach..=i + reach]);
+            window.sort_by(f64::total_cmp);
+            *out = window[reach];
+        }
+        start = end;
+    }
+}
+
+/// Robot voice: every frame's f0 set to `f0_hz`, unvoiced frames included,
+/// and the aperiodicity of every frame pushed down to
+/// [`ROBOT_APERIODICITY`], so the whole take is a steady pulse train
+/// through the original envelope. [`apply`] does this last when
+/// [`WorldSliderValues::robot_f0`] is set (skipping silent frames'
+/// aperiodicity, like every spectral stage).
+pub fn apply_robotize(params: &mut WorldParams, f0_hz: f64) {
+    let robot = Robotize(Some(f0_hz));
+    F0Stage::apply(&robot, &mut params.f0, &params.temporal_positions);
+    SpectralStage::apply(
+        &robot,
+        &mut params.spectrogram,
+        &mut params.aperiodicity,
+        params.fft_size,
+    );
+}
+
+/// Scale each voiced frame's f0 by `2^(offset / 12)`, the offset in
+/// semitones read off the `offsets` curve at the frame's time (see
+/// [`f0_offset_at`]). [`apply`] does this after the retune when
+/// [`WorldSliderValues::f0_offsets`] is set.
+pub fn apply_f0_offsets(params: &mut WorldParams, offsets: &[(f64, f64)]) {
+    F0Offsets(offsets.to_vec()).apply(&mut params.f0, &params.temporal_positions);
+}
+
+/// The semitones of a pitch-edit curve at `t` seconds: linear between its
+/// `(seconds, semitones)` points (sorted by time), held at the first and
+/// last point's value before and after them; 0 for an empty curve.
+pub fn f0_offset_at(offsets: &[(f64, f64)], t: f64) -> f64 {
+    let after = offsets.partition_point(|&(time, _)| time <= t);
+    match (after.checked_sub(1).map(|i| offsets[i]), offsets.get(after)) {
+        (Some((t0, v0)), Some(&(t1, v1))) => v0 + (v1 - v0) * (t - t0) / (t1 - t0),
+        (Some((_, v)), None) | (None, Some(&(_, v))) => v,
+        (None, None) => 0.0,
+    }
+}
+
+/// Add `semitones` to a pitch-edit curve over `start..=end` seconds, ramping
+/// in and out over [`REGION_RAMP_SECS`] either side, on top of whatever the
+/// curve already holds there. The curve stays sorted.
+pub fn nudge_f0_offsets(offsets: &mut Vec<(f64, f64)>, start: f64, end: f64, semitones: f64) {
+    let bump = [
+        (start - REGION_RAMP_SECS, 0.0),
+        (start, semitones),
+        (end, semitones),
+        (end + REGION_RAMP_SECS, 0.0),
+    ];
+    let mut times: Vec<f64> = offsets.iter().chain(&bump).map(|&(time, _)| time).collect();
+    times.sort_by(f64::total_cmp);
+    times.dedup();
+    // Both curves are piecewise linear, so their sum is too, with a point
+    // wherever either has one.
+    *offsets = times
+        .into_iter()
+        .map(|t| (t, f0_offset_at(offsets, t) + f0_offset_at(&bump, t)))
+        .collect();
+}
+
+/// Denoise for steady background hiss, which D4C hears as aperiodicity:
+/// in voiced frames, aperiodicity is soft-thresholded, values below a gate
+/// that grows to [`DENOISE_APERIODICITY_THRESHOLD`] at [`DENOISE_MAX_DB`]
+/// going to 0 and the rest shrinking by it; unvoiced frames
+/// are gated down by up to `amount_db`, fully when they are more than
+/// 2 × [`DENOISE_KEEP_DB`] below the loudest frame and not at all within
+/// [`DENOISE_KEEP_DB`] of it, so consonants survive while the hiss between
+/// phrases goes.
+pub fn apply_denoise(params: &mut WorldParams, amount_db: f64) {
+    let peak_db = peak_frame_db(&params.spectrogram);
+    denoise_frames(
+        &mut params.spectrogram,
+        &mut params.aperiodicity,
+        &params.f0,
+        amount_db,
+        peak_db,
+    );
+}
+
+/// Denoise strength at which the voiced aperiodicity gate is fully open.
+pub const DENOISE_MAX_DB: f64 = 30.0;
+
+/// Voiced aperiodicity gate at full strength; breath well above it survives.
+pub const DENOISE_APERIODICITY_THRESHOLD: f64 = 0.5;
+
+/// Unvoiced frames within this many dB of the loudest frame aren't gated.
+pub const DENOISE_KEEP_DB: f64 = 20.0;
+
+/// Level in dB of the loudest frame's power.
+fn peak_frame_db(spectrogram: &[Vec<f64>]) -> f64 {
+    let peak = spectrogram
+        .iter()
+        .map(|row| world::frame_energy(row))
+        .fold(0.0, f64::max);
+    10.0 * world::frame_energy_floor(peak).log10()
+}
+
+fn denoise_frames(
+    spectrogram: &mut [Vec<f64>],
+    aperiodicity: &mut [Vec<f64>],
+    f0: &[f64],
+    amount_db: f64,
+    peak_db: f64,
+) {
+    if amount_db <= 0.0 {
+        return;
+    }
+    let threshold = DENOISE_APERIODICITY_THRESHOLD * (amount_db / DENOISE_MAX_DB).clamp(0.0, 1.0);
+    for (i, &f) in f0.iter().enumerate() {
+        if f > 0.0 {
+            if let Some(row) = aperiodicity.get_mut(i) {
+                for val in row.iter_mut() {
+                    *val = (*val - threshold).max(0.0);
+                }
+            }
+        } else if let Some(row) = spectrogram.get_mut(i) {
+            let frame_db = 10.0 * world::frame_energy_floor(world::frame_energy(row)).log10();
+            let depth = ((peak_db - frame_db - DENOISE_KEEP_DB) / DENOISE_KEEP_DB).clamp(0.0, 1.0);
+            let gain = 10.0_f64.powf(-amount_db * depth / 10.0);
+            for bin in row.iter_mut() {
+                *bin *= gain;
+            }
+        }
+    }
+}
+
+/// Growl: each voiced frame's f0 moved by up to `depth` (0 to 1) ×
+/// [`GROWL_MAX_CENTS`], partly a cosine at `rate` and partly seeded noise,
+/// so the pitch jumps from period to period like vocal fry — and the same
+/// way on every render. The aperiodicity of the 1–3 kHz bands
+/// ([`GROWL_BAND_HZ`]) rises by `depth` × [`GROWL_APERIODICITY`], which
+/// needs [`WorldParams::sample_rate`].
+pub fn apply_growl(params: &mut WorldParams, depth: f64, rate: GrowlRate) {
+    let growl = Growl { depth, rate };
+    F0Stage::apply(&growl, &mut params.f0, &params.temporal_positions);
+    growl.apply_at_rate(
+        &mut params.spectrogram,
+        &mut params.aperiodicity,
+        params.fft_size,
+        params.sample_rate,
+    );
+}
+
+/// How fast the growl modulates f0.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GrowlRate {
+    /// Half the frame's own f0: every other glottal period high, the rest low.
+    #[default]
+    Subharmonic,
+    /// A fixed rate in Hz (40–80 Hz reads as a rattle).
+    Fixed(f64),
+}
+
+/// Largest growl swing of f0, in cents either way, at depth 1.
+pub const GROWL_MAX_CENTS: f64 = 100.0;
+
+/// Share of the growl swing that is the periodic modulation; the rest is
+/// the seeded noise.
+const GROWL_PERIODIC_SHARE: f64 = 0.7;
+
+/// Bands, in Hz, whose aperiodicity the growl raises.
+pub const GROWL_BAND_HZ: (f64, f64) = (1000.0, 3000.0);
+
+/// Aperiodicity the growl adds in [`GROWL_BAND_HZ`] at depth 1.
+pub const GROWL_APERIODICITY: f64 = 0.1;
+
+/// Whisper: every frame's f0 set to 0 and its aperiodicity to 1, so WORLD
+/// synthesizes only the noise excitation, shaped by the unchanged envelope.
+pub fn apply_whisper(params: &mut WorldParams) {
+    params.f0.fill(0.0);
+    for val in params.aperiodicity.iter_mut().flatten() {
+        *val = 1.0;
+    }
+}
+
+/// Aperiodicity of robotized frames: -60 dB, as good as fully periodic
+/// without the exact zero WORLD's log-domain code would choke on.
+pub const ROBOT_APERIODICITY: f64 = 0.001;
+
+/// [`apply`] for `frames` of `params` only: the f0 stages still see the
+/// whole contour (the pitch range works around the voiced mean), the
+/// spectral stages only the window. Ignores `values.speed`, which moves
+/// every frame; callers preview at speed 1 only.
+pub fn apply_window(
+    params: &WorldParams,
+    values: &WorldSliderValues,
+    frames: Range<usize>,
+) -> WorldParams {
+    let mut f0 = params.f0.clone();
+    if !values.whisper {
+        F0Pipeline::from_values(values).run_with_voicing(
+            &mut f0,
+            &params.temporal_positions,
+            &params.voicing,
+        );
+    }
+    let voicing = if params.voicing.is_empty() {
+        Vec::new()
+    } else {
+        params.voicing[frames.clone()].to_vec()
+    };
+    let mut result = WorldParams {
+        f0: f0[frames.clone()].to_vec(),
+        temporal_positions: params.temporal_positions[frames.clone()].to_vec(),
+        spectrogram: params.spectrogram[frames.clone()].to_vec(),
+        aperiodicity: params.aperiodicity[frames.clone()].to_vec(),
+        fft_size: params.fft_size,
+        frame_period: params.frame_period,
+        sample_rate: params.sample_rate,
+        f0_candidates: None,
+        voicing,
+    };
+    if values.denoise_db > 0.0 {
+        denoise_frames(
+            &mut result.spectrogram,
+            &mut result.aperiodicity,
+            &params.f0[frames.clone()],
+            values.denoise_db,
+            peak_frame_db(&params.spectrogram),
+        );
+    }
+    SpectralPipeline::from_values(values).run_timed(
+        &mut result.spectrogram,
+        &mut result.aperiodicity,
+        result.fft_size,
+        result.sample_rate,
+        &result.temporal_positions,
+    );
+    if values.whisper {
+        apply_whisper(&mut result);
+    }
+    if let Some(region) = values.region {
+        confine_to_region(&mut result, params, frames.start, region);
+    }
+    result
+}
+
+/// [`apply`] gliding from preset `a` to preset `b` across `crossfade`
+/// (source seconds `(start, end)`): frames before the start are exactly
+/// `a`'s render, frames after the end `b`'s, and each frame in between
+/// blends the two by how far into the window it is
+/// ([`crossfade_weight`]). As in [`world_sys::morph`], f0 blends in log Hz
+/// (halfway, the pitch shift is the average of the two), the envelope in
+/// log power and the aperiodicity linearly, and a frame voiced on one side
+/// only takes whichever side it leans towards. The speed can't glide, so
+/// both renders run at `a`'s, uniformly: a separate unvoiced speed would
+/// stretch the two renders' runs apart whenever their voicing differs.
+pub fn apply_crossfade(
+    params: &WorldParams,
+    a: &WorldSliderValues,
+    b: &WorldSliderValues,
+    crossfade: (f64, f64),
+) -> WorldParams {
+    let a = &WorldSliderValues {
+        speed_unvoiced: 0.0,
+        ..a.clone()
+    };
+    let mut result = apply(params, a);
+    let to = apply(
+        params,
+        &WorldSliderValues {
+            speed: a.speed,
+            speed_unvoiced: 0.0,
+            ..b.clone()
+        },
+    );
+    // After the speed change the positions are output time; the window is
+    // in source time.
+    let origin = result.temporal_positions.first().copied().unwrap_or(0.0);
+    let lerp = |x: f64, y: f64, w: f64| x + (y - x) * w;
+    let log_lerp = |x: f64, y: f64, w: f64| {
+        let floor = SANITIZED_SPECTRUM_BIN;
+        lerp(x.max(floor).ln(), y.max(floor).ln(), w).exp()
+    };
+    let frames = result.f0.len().min(to.f0.len());
+    for i in 0..frames {
+        let t = origin + (result.temporal_positions[i] - origin) * a.speed;
+        let w = crossfade_weight(t, crossfade);
+        if w == 0.0 {
+            continue;
+        }
+        if w == 1.0 {
+            result.f0[i] = to.f0[i];
+            result.spectrogram[i].clone_from(&to.spectrogram[i]);
+            result.aperiodicity[i].clone_from(&to.aperiodicity[i]);
+        } else {
+            let (x, y) = (result.f0[i], to.f0[i]);
+            result.f0[i] = match (x > 0.0, y > 0.0) {
+                (true, true) => log_lerp(x, y, w),
+                (true, false) if w <= 0.5 => x,
+                (false, true) if w >= 0.5 => y,
+                _ => 0.0,
+            };
+            for (v, &y) in result.spectrogram[i].iter_mut().zip(&to.spectrogram[i]) {
+                *v = log_lerp(*v, y, w);
+            }
+            for (v, &y) in result.aperiodicity[i].iter_mut().zip(&to.aperiodicity[i]) {
+                *v = lerp(*v, y, w);
+            }
+        }
+        if let (Some(v), Some(&y)) = (result.voicing.get_mut(i), to.voicing.get(i)) {
+            *v = lerp(f64::from(*v), f64::from(y), w) as f32;
+        }
+    }
+    result
+}
+
+/// How much of preset B [`apply_crossfade`] takes at `t` source seconds: 0
+/// before `crossfade`, rising linearly to 1 at its end and held after. A
+/// window that doesn't open switches at its start.
+pub fn crossfade_weight(t: f64, crossfade: (f64, f64)) -> f64 {
+    let (start, end) = crossfade;
+    if end <= start {
+        return if t >= start { 1.0 } else { 0.0 };
+    }
+    ((t - start) / (end - start)).clamp(0.0, 1.0)
+}
+
+/// Length of the crossfade at each end of a [`WorldSliderValues::region`],
+/// in seconds: 4 frames at the default 5 ms period.
+pub const REGION_RAMP_SECS: f64 = 0.02;
+
+/// How much of the modified frame at `t` seconds [`apply`] keeps for
+/// `region`: 0 outside it, rising linearly to 1 over the first
+/// [`REGION_RAMP_SECS`] inside and falling back over the last. A region
+/// shorter than two ramps peaks below 1.
+pub fn region_weight(t: f64, region: (f64, f64)) -> f64 {
+    let (start, end) = region;
+    ((t - start).min(end - t) / REGION_RAMP_SECS).clamp(0.0, 1.0)
+}
+
+/// Blend `modified` (frames `offset..` of `original`, unstretched) back
+/// towards the original outside `region`. f0 and both row sets crossfade
+/// linearly; a frame voiced on one side only takes whichever side has
+/// the larger weight.
+fn confine_to_region(
+    modified: &mut WorldParams,
+    original: &WorldParams,
+    offset: usize,
+    region: (f64, f64),
+) {
+    let blend_row = |out: &mut [f64], from: &[f64], w: f64| {
+        for (o, &f) in out.iter_mut().zip(from) {
+            *o = f + (*o - f) * w;
+        }
+    };
+    for i in 0..modified.f0.len() {
+        let w = region_weight(modified.temporal_positions[i], region);
+        if w == 1.0 {
+            continue;
+        }
+        let j = offset + i;
+        let (f, from) = (&mut modified.f0[i], original.f0[j]);
+        *f = match (*f > 0.0, from > 0.0) {
+            (true, true) => from + (*f - from) * w,
+            _ if w < 0.5 => from,
+            _ => *f,
+        };
+        blend_row(&mut modified.spectrogram[i], &original.spectrogram[j], w);
+        blend_row(&mut modified.aperiodicity[i], &original.aperiodicity[j], w);
+    }
+}
+
+/// Run explicit pipelines: the f0 stages, then the speed resampling, then the
+/// spectral stages. [`apply`] is this with the pipelines built from the
+/// sliders; callers can insert extra stages (region gating, automation, test
+/// probes) into either pipeline first.
+pub fn apply_pipelines(
+    params: &WorldParams,
+    f0: &F0Pipeline,
+    speed: f64,
+    spectral: &SpectralPipeline,
+) -> WorldParams {
+    let mut result = WorldParams::default();
+    copy_params_into(params, &mut result);
+    run_pipelines(&mut result, f0, (speed, speed), spectral);
+    result
+}
+
+/// [`apply_pipelines`] on `result` in place, with the voiced and unvoiced
+/// speeds.
+fn run_pipelines(
+    result: &mut WorldParams,
+    f0: &F0Pipeline,
+    (speed, unvoiced_speed): (f64, f64),
+    spectral: &SpectralPipeline,
+) {
+    f0.run_with_voicing(&mut result.f0, &result.temporal_positions, &result.voicing);
+    // After the speed change the positions are output time; automation
+    // lanes are in source time.
+    let source_tpos = apply_speed(result, speed, unvoiced_speed);
+    spectral.run_timed(
+        &mut result.spectrogram,
+        &mut result.aperiodicity,
+        result.fft_size,
+        result.sample_rate,
+        &source_tpos,
+    );
+}
+
+/// One transformation of the f0 contour (Hz per frame, 0 = unvoiced).
+/// `tpos` holds each frame's time in seconds.
+pub trait F0Stage {
+    /// Stable identifier, used for ordering checks and logs.
+    fn name(&self) -> &'static str;
+    fn apply(&self, f0: &mut [f64], tpos: &[f64]);
+
+    /// [`F0Stage::apply`] given each frame's voicing confidence
+    /// ([`WorldParams::voicing`], empty when unknown). Stages that should
+    /// leave unreliable f0 alone override this; by default it is ignored.
+    fn apply_with_voicing(&self, f0: &mut [f64], tpos: &[f64], _voicing: &[f32]) {
+        self.apply(f0, tpos);
+    }
+}
+
+/// One transformation of the spectral envelope and/or aperiodicity
+/// (rows of `fft_size / 2 + 1` bins, one per frame). Stages only see
+/// non-silent frames; see [`SpectralPipeline::run`].
+pub trait SpectralStage {
+    /// Stable identifier, used for ordering checks and logs.
+    fn name(&self) -> &'static str;
+    fn apply(&self, spectrogram: &mut [Vec<f64>], aperiodicity: &mut [Vec<f64>], fft_size: usize);
+
+    /// [`SpectralStage::apply`] given the sample rate the bins belong to
+    /// ([`WorldParams::sample_rate`], 0 when unknown). Stages that work in
+    /// Hz override this; by default it is ignored.
+    fn apply_at_rate(
+        &self,
+        spectrogram: &mut [Vec<f64>],
+        aperiodicity: &mut [Vec<f64>],
+        fft_size: usize,
+        _sample_rate: i32,
+    ) {
+        self.apply(spectrogram, aperiodicity, fft_size);
+    }
+
+    /// [`SpectralStage::apply_at_rate`] given each frame's source time in
+    /// seconds (empty when unknown). Automated stages override this; by
+    /// default it is ignored.
+    fn apply_timed(
+        &self,
+        spectrogram: &mut [Vec<f64>],
+        aperiodicity: &mut [Vec<f64>],
+        fft_size: usize,
+        sample_rate: i32,
+        _tpos: &[f64],
+    ) {
+        self.apply_at_rate(spectrogram, aperiodicity, fft_size, sample_rate);
+    }
+}
+
+/// Ordered f0 stages. Each stage sees the contour produced by the ones
+/// before it.
+pub struct F0Pipeline {
+    stages: Vec<Box<dyn F0Stage>>,
+}
+
+impl F0Pipeline {
+    /// The slider-driven f0 pipeline, in this fixed order:
+    ///
+    /// 1. `pitch_smooth` — median filter, so later stages never see the
+    ///    analysis glitches it removes
+    /// 2. `pitch_shift` — scale voiced frames by the semitone ratio
+    /// 3. `pitch_range` — expand/compress around the (shifted) voiced mean
+    /// 4. `pitch_flatten` — pull towards a fixed pitch (or the median)
+    /// 5. `retune` — pull towards the nearest note of a scale
+    /// 6. `humanize` — slow seeded drift, after the retune so it doesn't
+    ///    pull the drift back onto the note
+    /// 7. `pitch_offsets` — the hand-drawn edit curve, after the retune so
+    ///    it doesn't snap an edit back onto the note
+    /// 8. `vibrato` — sine LFO on the result, so neither the range nor the
+    ///    flattening scales it, and the retune doesn't snap it away
+    /// 9. `growl` — fast, partly random modulation on top of everything
+    ///    above, so it stays rough whatever the contour does
+    /// 10. `robot` — replace the contour with a constant (voicing every frame)
+    ///
+    /// Every stage is present even at its neutral value (where it is a
+    /// no-op), so stage indices don't depend on the slider positions.
+    pub fn from_values(values: &WorldSliderValues) -> Self {
+        Self {
+            stages: vec![
+                Box::new(Smooth(values.f0_smoothing_frames.round().max(0.0) as usize)),
+                Box::new(PitchShift {
+                    semitones: values.pitch_shift,
+                    lane: values.automation.pitch_shift.clone(),
+                }),
+                Box::new(PitchRange {
+                    range: values.pitch_range,
+                    center: values.pitch_range_center,
+                }),
+                Box::new(Flatten {
+                    amount: values.flatten_amount,
+                    target_hz: values.flatten_target_hz,
+                }),
+                Box::new(Retune {
+                    strength: values.retune_strength,
+                    speed_ms: values.retune_speed_ms,
+                    scale: values.retune_scale,
+                    root: values.retune_root,
+                }),
+                Box::new(Humanize {
+                    cents: values.humanize_cents,
+                    seed: values.humanize_seed,
+                }),
+                Box::new(F0Offsets(values.f0_offsets.clone())),
+                Box::new(Vibrato {
+                    depth_cents: values.vibrato_depth_cents,
+                    rate_hz: values.vibrato_rate_hz,
+                }),
+                Box::new(Growl {
+                    depth: values.growl_depth,
+                    rate: values.growl_rate,
+                }),
+                Box::new(Robotize(values.robot_f0)),
+            ],
+        }
+    }
+
+    /// Insert a stage at `index` (0 = before everything, `len()` = last).
+    pub fn insert(&mut self, index: usize, stage: Box<dyn F0Stage>) {
+        self.stages.insert(index.min(self.stages.len()), stage);
+    }
+
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Stage names in execution order.
+    pub fn stage_names(&self) -> Vec<&'static str> {
+        self.stages.iter().map(|s| s.name()).collect()
+    }
+
+    pub fn run(&self, f0: &mut [f64], tpos: &[f64]) {
+        self.run_with_voicing(f0, tpos, &[]);
+    }
+
+    /// [`F0Pipeline::run`] with per-frame voicing confidence (empty when
+    /// unknown), passed to every stage's [`F0Stage::apply_with_voicing`].
+    pub fn run_with_voicing(&self, f0: &mut [f64], tpos: &[f64], voicing: &[f32]) {
+        for stage in &self.stages {
+            stage.apply_with_voicing(f0, tpos, voicing);
+        }
+    }
+}
+
+/// Ordered spectral stages, run after the speed resampling.
+pub struct SpectralPipeline {
+    stages: Vec<Box<dyn SpectralStage>>,
+}
+
+impl SpectralPipeline {
+    /// The slider-driven spectral pipeline, in this fixed order:
+    ///
+    /// 1. `spectral_smooth` — cepstral lifter, before the shift can stretch
+    ///    the envelope's ripples
+    /// 2. `breathiness` — raise aperiodicity
+    /// 3. `formant_shift` — warp the envelope's frequency axis
+    /// 4. `spectral_tilt` — dB/octave slope on the (shifted) envelope
+    /// 5. `presence` — bell-shaped boost around a centre frequency
+    /// 6. `nasality` — nasal peak and dip at fixed frequencies, after the
+    ///    formant shift so they stay put
+    /// 7. `growl` — a little aperiodicity in the 1–3 kHz bands
+    /// 8. `robot` — clamp the aperiodicity, overriding the breathiness and
+    ///    the growl
+    pub fn from_values(values: &WorldSliderValues) -> Self {
+        Self {
+            stages: vec![
+                Box::new(SpectralSmooth(values.spectral_smoothing)),
+                Box::new(Breathiness {
+                    amount: values.breathiness,
+                    lane: values.automation.breathiness.clone(),
+                    crossover_hz: values.breathiness_crossover_hz,
+                }),
+                Box::new(FormantShift {
+                    semitones: values.formant_shift,
+                    keep_energy: values.formant_keep_energy,
+                    warp: values.formant_warp,
+                }),
+                Box::new(SpectralTilt {
+                    db_per_oct: values.spectral_tilt,
+                    lane: values.automation.spectral_tilt.clone(),
+                    pivot_hz: values.tilt_pivot_hz,
+                }),
+                Box::new(Presence {
+                    gain_db: values.presence_db,
+                    center_hz: values.presence_center_hz,
+                    width_oct: values.presence_width_oct,
+                }),
+                Box::new(Nasality(values.nasality_db)),
+                Box::new(Growl {
+                    depth: values.growl_depth,
+                    rate: values.growl_rate,
+                }),
+                Box::new(Robotize(values.robot_f0.filter(|_| !values.whisper))),
+            ],
+        }
+    }
+
+    /// Insert a stage at `index` (0 = before everything, `len()` = last).
+    pub fn insert(&mut self, index: usize, stage: Box<dyn SpectralStage>) {
+        self.stages.insert(index.min(self.stages.len()), stage);
+    }
+
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Stage names in execution order.
+    pub fn stage_names(&self) -> Vec<&'static str> {
+        self.stages.iter().map(|s| s.name()).collect()
+    }
+
+    /// Run every stage over the frames. Silent frames (see
+    /// [`world::silent_frames`]) are skipped: each stage is called once per
+    /// run of consecutive non-silent frames, so stages never see a zero row.
+    /// `aperiodicity` may be shorter than `spectrogram` (the envelope preview
+    /// passes none); stages get the matching part of it.
+    pub fn run(
+        &self,
+        spectrogram: &mut [Vec<f64>],
+        aperiodicity: &mut [Vec<f64>],
+        fft_size: usize,
+    ) {
+        self.run_at_rate(spectrogram, aperiodicity, fft_size, 0);
+    }
+
+    /// [`SpectralPipeline::run`] with the sample rate of the bins (0 when
+    /// unknown), passed to every stage's [`SpectralStage::apply_at_rate`].
+    pub fn run_at_rate(
+        &self,
+        spectrogram: &mut [Vec<f64>],
+        aperiodicity: &mut [Vec<f64>],
+        fft_size: usize,
+        sample_rate: i32,
+    ) {
+        self.run_timed(spectrogram, aperiodicity, fft_size, sample_rate, &[]);
+    }
+
+    /// [`SpectralPipeline::run_at_rate`] with each frame's source time in
+    /// seconds (empty when unknown, or shorter than `spectrogram` like
+    /// `aperiodicity`), passed to every stage's [`SpectralStage::apply_timed`].
+    pub fn run_timed(
+        &self,
+        spectrogram: &mut [Vec<f64>],
+        aperiodicity: &mut [Vec<f64>],
+        fft_size: usize,
+        sample_rate: i32,
+        tpos: &[f64],
+    ) {
+        let silent = world::silent_frames(spectrogram);
+        let mut runs = Vec::new();
+        let mut start = None;
+        for (i, &is_silent) in silent.iter().chain([&true]).enumerate() {
+            match (start, is_silent) {
+                (None, false) => start = Some(i),
+                (Some(s), true) => {
+                    runs.push(s..i);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        for stage in &self.stages {
+            for run in &runs {
+                let ap_end = run.end.min(aperiodicity.len());
+                let ap_start = run.start.min(ap_end);
+                let t_end = run.end.min(tpos.len());
+                let t_start = run.start.min(t_end);
+                stage.apply_timed(
+                    &mut spectrogram[run.clone()],
+                    &mut aperiodicity[ap_start..ap_end],
+                    fft_size,
+                    sample_rate,
+                    &tpos[t_start..t_end],
+                );
+            }
+        }
+    }
+}
+
+/// How much of a pitch edit frame `i` gets: 1 at or above
+/// [`CONFIDENT_VOICING`], fading linearly to 0 at zero confidence, so a
+/// doubtful f0 estimate isn't dragged along. 1 when `voicing` is empty.
+fn voicing_weight(voicing: &[f32], i: usize) -> f64 {
+    voicing.get(i).map_or(1.0, |&confidence| {
+        f64::from((confidence / CONFIDENT_VOICING).clamp(0.0, 1.0))
+    })
+}
+
+/// [`smooth_f0`] as a stage: the median window in frames.
+struct Smooth(usize);
+
+impl F0Stage for Smooth {
+    fn name(&self) -> &'static str {
+        "pitch_smooth"
+    }
+
+    fn apply(&self, f0: &mut [f64], _tpos: &[f64]) {
+        median_filter_voiced(f0, self.0);
+    }
+}
+
+/// Shift f0 by semitones, from the automation lane at each frame's time
+/// when it has points. f0=0 (unvoiced) frames are left unchanged, and
+/// frames with low voicing confidence are shifted less.
+struct PitchShift {
+    semitones: f64,
+    lane: Vec<(f64, f64)>,
+}
+
+impl F0Stage for PitchShift {
+    fn name(&self) -> &'static str {
+        "pitch_shift"
+    }
+
+    fn apply(&self, f0: &mut [f64], tpos: &[f64]) {
+        self.apply_with_voicing(f0, tpos, &[]);
+    }
+
+    fn apply_with_voicing(&self, f0: &mut [f64], tpos: &[f64], voicing: &[f32]) {
+        if self.semitones == 0.0 && self.lane.is_empty() {
+            return;
+        }
+        for (i, f) in f0.iter_mut().enumerate() {
+            if *f > 0.0 {
+                let semitones = tpos.get(i).map_or(self.semitones, |&t| {
+                    automated_value(self.semitones, &self.lane, t)
+                });
+                *f *= 2.0_f64.powf(semitones * voicing_weight(voicing, i) / 12.0);
+            }
+        }
+    }
+}
+
+/// Expand/compress f0 around its [`RangeCenter`]. Only affects voiced
+/// frames; frames with low voicing confidence count less towards the centre
+/// and are moved less.
+struct PitchRange {
+    range: f64,
+    center: RangeCenter,
+}
+
+impl F0Stage for PitchRange {
+    fn name(&self) -> &'static str {
+        "pitch_range"
+    }
+
+    fn apply(&self, f0: &mut [f64], tpos: &[f64]) {
+        self.apply_with_voicing(f0, tpos, &[]);
+    }
+
+    fn apply_with_voicing(&self, f0: &mut [f64], _tpos: &[f64], voicing: &[f32]) {
+        let range = self.range;
+        if range == 1.0 {
+            return;
+        }
+        let spans = match self.center {
+            RangeCenter::Mean | RangeCenter::Median => std::iter::once(0..f0.len()).collect(),
+            RangeCenter::PerSegment => voiced_runs(f0),
+        };
+        for span in spans {
+            // Voiced frames with their confidence weights.
+            let weighted: Vec<(f64, f64)> = span
+                .clone()
+                .filter(|&i| f0[i] > 0.0)
+                .map(|i| (f0[i], voicing_weight(voicing, i)))
+                .collect();
+            let center = match self.center {
+                RangeCenter::Mean => weighted_mean(&weighted),
+                RangeCenter::Median | RangeCenter::PerSegment => weighted_median(weighted),
+            };
+            let Some(center) = center else {
+                continue;
+            };
+            for i in span {
+                let f = &mut f0[i];
+                if *f > 0.0 {
+                    let scale = 1.0 + (range - 1.0) * voicing_weight(voicing, i);
+                    *f = center + (*f - center) * scale;
+                    if *f < 0.0 {
+                        *f = 0.0;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The frame ranges of consecutive voiced (f0 > 0) frames.
+fn voiced_runs(f0: &[f64]) -> Vec<Range<usize>> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (i, &f) in f0.iter().chain([&0.0]).enumerate() {
+        match (start, f > 0.0) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                runs.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    runs
+}
+
+/// Mean of `(value, weight)` pairs; `None` when the weights sum to 0.
+fn weighted_mean(weighted: &[(f64, f64)]) -> Option<f64> {
+    let (sum, weight) = weighted.iter().fold((0.0, 0.0), |(sum, weight), &(f, w)| {
+        (sum + f * w, weight + w)
+    });
+    (weight > 0.0).then(|| sum / weight)
+}
+
+/// The value at which the weights of `(value, weight)` pairs reach half
+/// their total, in value order; `None` when the weights sum to 0.
+fn weighted_median(mut weighted: Vec<(f64, f64)>) -> Option<f64> {
+    let total: f64 = weighted.iter().map(|&(_, w)| w).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    weighted.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut reached = 0.0;
+    weighted.into_iter().find_map(|(f, w)| {
+        reached += w;
+        (reached >= total / 2.0).then_some(f)
+    })
+}
+
+/// Pull voiced f0 towards one pitch: `target + (f0 - target) · (1 - amount)`,
+/// so amount 1 is a monotone at the target. A target of 0 uses the median
+/// of the voiced frames.
+struct Flatten {
+    amount: f64,
+    target_hz: f64,
+}
+
+impl F0Stage for Flatten {
+    fn name(&self) -> &'static str {
+        "pitch_flatten"
+    }
+
+    fn apply(&self, f0: &mut [f64], _tpos: &[f64]) {
+        if self.amount == 0.0 {
+            return;
+        }
+        let target = if self.target_hz > 0.0 {
+            self.target_hz
+        } else {
+            let mut voiced: Vec<f64> = f0.iter().copied().filter(|&f| f > 0.0).collect();
+            if voiced.is_empty() {
+                return;
+            }
+            voiced.sort_by(f64::total_cmp);
+            voiced[voiced.len() / 2]
+        };
+        let keep = 1.0 - self.amount.clamp(0.0, 1.0);
+        for f in f0.iter_mut().filter(|f| **f > 0.0) {
+            *f = target + (*f - target) * keep;
+        }
+    }
 }
 
-impl WorldSliderValues {
-    /// Check if all sliders are at their neutral (default) positions.
-    /// M-5: Use epsilon comparison to be robust against floating-point drift.
-    pub fn is_neutral(&self) -> bool {
-        const EPS: f64 = 1e-9;
-        self.pitch_shift.abs() < EPS
-            && (self.pitch_range - 1.0).abs() < EPS
-            && (self.speed - 1.0).abs() < EPS
-            && self.breathiness.abs() < EPS
-            && self.formant_shift.abs() < EPS
-            && self.spectral_tilt.abs() < EPS
+/// Auto-tune: each voiced frame is moved by `strength` times the
+/// (smoothed) distance in semitones to the nearest note of `scale` on
+/// `root`, A4 = 440 Hz. The correction follows its target through a
+/// one-pole low-pass with a `speed_ms` time constant, restarting at the
+/// first frame of every voiced run, so 0 ms is the hard snap and longer
+/// times let note changes glide.
+struct Retune {
+    strength: f64,
+    speed_ms: f64,
+    scale: RetuneScale,
+    root: usize,
+}
+
+impl Retune {
+    /// Semitones from `midi` (fractional MIDI note number) to the nearest
+    /// note in the scale.
+    fn correction(&self, midi: f64) -> f64 {
+        let nearest = midi.round() as i64;
+        let root = (self.root % 12) as i64;
+        (nearest - 6..=nearest + 6)
+            .filter(|note| {
+                self.scale
+                    .intervals()
+                    .contains(&(note - root).rem_euclid(12))
+            })
+            .map(|note| note as f64 - midi)
+            .min_by(|a, b| a.abs().total_cmp(&b.abs()))
+            .unwrap_or(0.0)
     }
 }
 
-impl Default for WorldSliderValues {
-    fn default() -> Self {
-        Self {
-            pitch_shift: 0.0,
-            pitch_range: 1.0,
-            speed: 1.0,
-            breathiness: 0.0,
-            formant_shift: 0.0,
-            spectral_tilt: 0.0,
-            bypass: false,
+impl F0Stage for Retune {
+    fn name(&self) -> &'static str {
+        "retune"
+    }
+
+    fn apply(&self, f0: &mut [f64], tpos: &[f64]) {
+        if self.strength == 0.0 {
+            return;
+        }
+        let frame_secs = match tpos {
+            [first, second, ..] => second - first,
+            _ => 0.0,
+        };
+        let follow = if self.speed_ms > 0.0 {
+            1.0 - (-frame_secs * 1000.0 / self.speed_ms).exp()
+        } else {
+            1.0
+        };
+        let mut smoothed: Option<f64> = None;
+        for f in f0.iter_mut() {
+            if *f <= 0.0 {
+                smoothed = None;
+                continue;
+            }
+            let target = self.correction(69.0 + 12.0 * (*f / 440.0).log2());
+            let correction = match smoothed {
+                Some(previous) => previous + follow * (target - previous),
+                None => target,
+            };
+            smoothed = Some(correction);
+            *f *= 2.0_f64.powf(self.strength.clamp(0.0, 1.0) * correction / 12.0);
         }
     }
 }
 
-/// Apply slider-driven modifications to WORLD parameters.
-///
-/// Returns a new `WorldParams` with modifications applied.
-/// The original `params` is not mutated.
-pub fn apply(params: &WorldParams, values: &WorldSliderValues) -> WorldParams {
-    let mut result = params.clone();
+/// Largest humanize drift of f0, in cents either way.
+pub const HUMANIZE_MAX_CENTS: f64 = 50.0;
 
-    apply_pitch_shift(&mut result, values.pitch_shift);
-    apply_pitch_range(&mut result, values.pitch_range);
-    apply_speed(&mut result, values.speed);
-    apply_breathiness(&mut result, values.breathiness);
-    apply_formant_shift(&mut result, values.formant_shift);
-    apply_spectral_tilt(&mut result, values.spectral_tilt);
+/// Rate of the humanize drift's random knots, in Hz: the drift wanders on
+/// the time scale of a syllable, well below a vibrato.
+pub const HUMANIZE_RATE_HZ: f64 = 2.0;
 
-    result
+/// The humanize drift at source time `t`, in `-1.0..=1.0`: a seeded random
+/// value every 1 / [`HUMANIZE_RATE_HZ`] seconds, eased from one to the next
+/// with a smoothstep, so the drift is band-limited to a few Hz. A knot's
+/// value depends only on `seed` and its index, so any slice of the take
+/// drifts exactly as it does in a whole-take render.
+pub fn humanize_drift(seed: u64, t: f64) -> f64 {
+    // The (k + 1)th splitmix64 output from `seed`.
+    let knot = |k: i64| {
+        let mut z = seed.wrapping_add(
+            (k as u64)
+                .wrapping_add(1)
+                .wrapping_mul(0x9E37_79B9_7F4A_7C15),
+        );
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        2.0 * ((z >> 11) as f64 / (1u64 << 53) as f64) - 1.0
+    };
+    let pos = t * HUMANIZE_RATE_HZ;
+    let k = pos.floor();
+    let frac = pos - k;
+    let ease = frac * frac * (3.0 - 2.0 * frac);
+    let (a, b) = (knot(k as i64), knot(k as i64 + 1));
+    a + (b - a) * ease
 }
 
-/// Shift f0 by semitones. f0=0 (unvoiced) frames are left unchanged.
-fn apply_pitch_shift(params: &mut WorldParams, semitones: f64) {
-    if semitones == 0.0 {
-        return;
+/// Humanize: each voiced frame scaled by `cents` × [`humanize_drift`] at
+/// its time; unvoiced frames stay 0.
+struct Humanize {
+    cents: f64,
+    seed: u64,
+}
+
+impl F0Stage for Humanize {
+    fn name(&self) -> &'static str {
+        "humanize"
     }
-    let ratio = 2.0_f64.powf(semitones / 12.0);
-    for f0 in &mut params.f0 {
-        if *f0 > 0.0 {
-            *f0 *= ratio;
+
+    fn apply(&self, f0: &mut [f64], tpos: &[f64]) {
+        let cents = self.cents.clamp(0.0, HUMANIZE_MAX_CENTS);
+        if cents == 0.0 {
+            return;
+        }
+        for (f, &t) in f0.iter_mut().zip(tpos) {
+            if *f > 0.0 {
+                *f *= 2.0_f64.powf(cents * humanize_drift(self.seed, t) / 1200.0);
+            }
         }
     }
 }
 
-/// Expand/compress f0 around its mean. Only affects voiced frames.
-fn apply_pitch_range(params: &mut WorldParams, range: f64) {
-    if range == 1.0 {
-        return;
+/// [`apply_f0_offsets`] as a stage.
+struct F0Offsets(Vec<(f64, f64)>);
+
+impl F0Stage for F0Offsets {
+    fn name(&self) -> &'static str {
+        "pitch_offsets"
     }
-    // Compute mean of voiced frames.
-    let voiced: Vec<f64> = params.f0.iter().copied().filter(|&f| f > 0.0).collect();
-    if voiced.is_empty() {
-        return;
+
+    fn apply(&self, f0: &mut [f64], tpos: &[f64]) {
+        if self.0.is_empty() {
+            return;
+        }
+        for (f, &t) in f0.iter_mut().zip(tpos) {
+            if *f > 0.0 {
+                *f *= 2.0_f64.powf(f0_offset_at(&self.0, t) / 12.0);
+            }
+        }
+    }
+}
+
+/// Sine LFO on f0: each voiced frame is scaled by
+/// `2^(depth_cents / 1200 · sin(2π · rate_hz · t))` at its time `t`, so the
+/// phase runs on through unvoiced frames (left at 0) and the vibrato picks
+/// up where the clock is after each gap.
+struct Vibrato {
+    depth_cents: f64,
+    rate_hz: f64,
+}
+
+impl F0Stage for Vibrato {
+    fn name(&self) -> &'static str {
+        "vibrato"
+    }
+
+    fn apply(&self, f0: &mut [f64], tpos: &[f64]) {
+        if self.depth_cents == 0.0 || self.rate_hz == 0.0 {
+            return;
+        }
+        for (f, &t) in f0.iter_mut().zip(tpos) {
+            if *f > 0.0 {
+                let phase = 2.0 * std::f64::consts::PI * self.rate_hz * t;
+                *f *= 2.0_f64.powf(self.depth_cents / 1200.0 * phase.sin());
+            }
+        }
+    }
+}
+
+/// The growl of [`apply_growl`], as both an f0 and a spectral stage.
+struct Growl {
+    depth: f64,
+    rate: GrowlRate,
+}
+
+impl F0Stage for Growl {
+    fn name(&self) -> &'static str {
+        "growl"
+    }
+
+    fn apply(&self, f0: &mut [f64], tpos: &[f64]) {
+        let depth = self.depth.clamp(0.0, 1.0);
+        if depth == 0.0 {
+            return;
+        }
+        // xorshift64* from a fixed seed, advanced on every frame, so each
+        // frame's jitter is the same on every render.
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut phase = 0.0;
+        let mut last_t = tpos.first().copied().unwrap_or(0.0);
+        for (f, &t) in f0.iter_mut().zip(tpos) {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            let unit =
+                (state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11) as f64 / (1u64 << 53) as f64;
+            let rate_hz = match self.rate {
+                GrowlRate::Subharmonic => 0.5 * *f,
+                GrowlRate::Fixed(hz) => hz,
+            };
+            phase += 2.0 * std::f64::consts::PI * rate_hz * (t - last_t);
+            last_t = t;
+            if *f > 0.0 {
+                let swing = GROWL_PERIODIC_SHARE * phase.cos()
+                    + (1.0 - GROWL_PERIODIC_SHARE) * (2.0 * unit - 1.0);
+                *f *= 2.0_f64.powf(depth * GROWL_MAX_CENTS * swing / 1200.0);
+            }
+        }
+    }
+}
+
+impl SpectralStage for Growl {
+    fn name(&self) -> &'static str {
+        "growl"
     }
-    let mean = voiced.iter().sum::<f64>() / voiced.len() as f64;
 
-    for f0 in &mut params.f0 {
-        if *f0 > 0.0 {
-            *f0 = mean + (*f0 - mean) * range;
-            if *f0 < 0.0 {
-                *f0 = 0.0;
+    fn apply(&self, spectrogram: &mut [Vec<f64>], aperiodicity: &mut [Vec<f64>], fft_size: usize) {
+        self.apply_at_rate(spectrogram, aperiodicity, fft_size, 0);
+    }
+
+    fn apply_at_rate(
+        &self,
+        _spectrogram: &mut [Vec<f64>],
+        aperiodicity: &mut [Vec<f64>],
+        fft_size: usize,
+        sample_rate: i32,
+    ) {
+        let depth = self.depth.clamp(0.0, 1.0);
+        if depth == 0.0 || sample_rate <= 0 {
+            return;
+        }
+        let bin_hz = f64::from(sample_rate) / fft_size as f64;
+        let band = (GROWL_BAND_HZ.0 / bin_hz).ceil() as usize
+            ..=(GROWL_BAND_HZ.1 / bin_hz).floor() as usize;
+        for row in aperiodicity.iter_mut() {
+            for val in row.iter_mut().take(band.end() + 1).skip(*band.start()) {
+                *val = (*val + depth * GROWL_APERIODICITY).clamp(0.0, 1.0);
             }
         }
     }
 }
 
+/// The robot voice of [`apply_robotize`], as both an f0 and a spectral
+/// stage; `None` is a no-op.
+struct Robotize(Option<f64>);
+
+impl F0Stage for Robotize {
+    fn name(&self) -> &'static str {
+        "robot"
+    }
+
+    fn apply(&self, f0: &mut [f64], _tpos: &[f64]) {
+        if let Some(f0_hz) = self.0 {
+            f0.fill(f0_hz);
+        }
+    }
+}
+
+impl SpectralStage for Robotize {
+    fn name(&self) -> &'static str {
+        "robot"
+    }
+
+    fn apply(
+        &self,
+        _spectrogram: &mut [Vec<f64>],
+        aperiodicity: &mut [Vec<f64>],
+        _fft_size: usize,
+    ) {
+        if self.0.is_none() {
+            return;
+        }
+        for val in aperiodicity.iter_mut().flatten() {
+            *val = val.min(ROBOT_APERIODICITY);
+        }
+    }
+}
+
 /// Resample frames via linear interpolation to change speed.
 /// speed > 1.0 = fewer frames (faster), speed < 1.0 = more frames (slower).
-fn apply_speed(params: &mut WorldParams, speed: f64) {
+/// f0 only interpolates within voiced runs (see [`resample_f0`]). With an
+/// `unvoiced_speed` other than `speed`, see [`stretch_runs`]. Returns the
+/// source time of each output frame.
+fn apply_speed(params: &mut WorldParams, speed: f64, unvoiced_speed: f64) -> Vec<f64> {
+    let origin = params.temporal_positions.first().copied().unwrap_or(0.0);
+    if speed != unvoiced_speed {
+        return stretch_runs(params, speed, unvoiced_speed);
+    }
     if speed == 1.0 {
-        return;
+        return params.temporal_positions.clone();
     }
 
     let old_len = params.f0.len();
     if old_len == 0 {
-        return;
+        return Vec::new();
     }
     let new_len = ((old_len as f64) / speed).round().max(1.0) as usize;
 
-    params.f0 = resample_1d(&params.f0, new_len);
-    // M-6: Recompute temporal_positions for the new frame count.
+    params.f0 = resample_f0(&params.f0, new_len);
+    // M-6: Recompute temporal_positions for the new frame count, one
+    // frame_period apart from the same first position.
     // WORLD synthesis uses f0.len() × frame_period for output length,
     // not temporal_positions directly, so audio output is correct.
     params.temporal_positions = (0..new_len)
-        .map(|i| i as f64 * params.frame_period / 1000.0)
+        .map(|i| origin + i as f64 * params.frame_period / 1000.0)
         .collect();
     params.spectrogram = resample_2d(&params.spectrogram, new_len);
     params.aperiodicity = resample_2d(&params.aperiodicity, new_len);
+    if !params.voicing.is_empty() {
+        let voicing: Vec<f64> = params.voicing.iter().map(|&c| f64::from(c)).collect();
+        params.voicing = resample_1d(&voicing, new_len)
+            .into_iter()
+            .map(|c| c as f32)
+            .collect();
+    }
+    params
+        .temporal_positions
+        .iter()
+        .map(|&t| origin + (t - origin) * speed)
+        .collect()
 }
 
-/// Increase aperiodicity to add breathiness.
-fn apply_breathiness(params: &mut WorldParams, amount: f64) {
-    if amount == 0.0 {
-        return;
+/// Stretch each voiced run (f0 > 0) by `speed` and each unvoiced run by
+/// `unvoiced_speed`, resampling a run on its own so its edges stay put.
+/// Output lengths round cumulatively, so the total is within a frame of
+/// `voiced / speed + unvoiced / unvoiced_speed` however many runs there
+/// are. Returns the source time of each output frame.
+fn stretch_runs(params: &mut WorldParams, speed: f64, unvoiced_speed: f64) -> Vec<f64> {
+    let old_len = params.f0.len();
+    if old_len == 0 {
+        return Vec::new();
+    }
+    let origin = params.temporal_positions.first().copied().unwrap_or(0.0);
+    let period = params.frame_period / 1000.0;
+    let voicing: Vec<f64> = params.voicing.iter().map(|&c| f64::from(c)).collect();
+
+    let mut f0 = Vec::new();
+    let mut spectrogram = Vec::new();
+    let mut aperiodicity = Vec::new();
+    let mut new_voicing = Vec::new();
+    let mut source_tpos = Vec::new();
+    let mut ideal = 0.0;
+    let mut start = 0;
+    while start < old_len {
+        let voiced = params.f0[start] > 0.0;
+        let end = (start..old_len)
+            .find(|&i| (params.f0[i] > 0.0) != voiced)
+            .unwrap_or(old_len);
+        let len = end - start;
+        ideal += len as f64 / if voiced { speed } else { unvoiced_speed };
+        let out_len = (ideal.round() as usize).saturating_sub(f0.len());
+
+        f0.extend(resample_f0(&params.f0[start..end], out_len));
+        spectrogram.extend(resample_2d(&params.spectrogram[start..end], out_len));
+        aperiodicity.extend(resample_2d(&params.aperiodicity[start..end], out_len));
+        if !voicing.is_empty() {
+            new_voicing.extend(resample_1d(&voicing[start..end], out_len));
+        }
+        // The same index mapping the resamplers use.
+        source_tpos.extend((0..out_len).map(|j| {
+            let pos = if out_len > 1 {
+                j as f64 * (len - 1) as f64 / (out_len - 1) as f64
+            } else {
+                0.0
+            };
+            origin + (start as f64 + pos) * period
+        }));
+        start = end;
+    }
+    if f0.is_empty() {
+        // Everything rounded away: keep the first frame, like a uniform
+        // stretch's one-frame minimum.
+        f0.push(params.f0[0]);
+        spectrogram.extend(params.spectrogram.first().cloned());
+        aperiodicity.extend(params.aperiodicity.first().cloned());
+        new_voicing.extend(voicing.first());
+        source_tpos.push(origin);
+    }
+
+    params.temporal_positions = (0..f0.len()).map(|i| origin + i as f64 * period).collect();
+    params.f0 = f0;
+    params.spectrogram = spectrogram;
+    params.aperiodicity = aperiodicity;
+    params.voicing = new_voicing.into_iter().map(|c| c as f32).collect();
+    source_tpos
+}
+
+/// Increase aperiodicity to add breathiness, weighted per bin by
+/// [`breathiness_weight`], by the automation lane at each frame's time when
+/// it has points. Without a sample rate the crossover can't be placed, so
+/// every bin gets the full amount.
+struct Breathiness {
+    amount: f64,
+    lane: Vec<(f64, f64)>,
+    crossover_hz: f64,
+}
+
+impl SpectralStage for Breathiness {
+    fn name(&self) -> &'static str {
+        "breathiness"
+    }
+
+    fn apply(&self, spectrogram: &mut [Vec<f64>], aperiodicity: &mut [Vec<f64>], fft_size: usize) {
+        self.apply_at_rate(spectrogram, aperiodicity, fft_size, 0);
+    }
+
+    fn apply_at_rate(
+        &self,
+        spectrogram: &mut [Vec<f64>],
+        aperiodicity: &mut [Vec<f64>],
+        fft_size: usize,
+        sample_rate: i32,
+    ) {
+        self.apply_timed(spectrogram, aperiodicity, fft_size, sample_rate, &[]);
+    }
+
+    fn apply_timed(
+        &self,
+        _spectrogram: &mut [Vec<f64>],
+        aperiodicity: &mut [Vec<f64>],
+        fft_size: usize,
+        sample_rate: i32,
+        tpos: &[f64],
+    ) {
+        if self.amount == 0.0 && self.lane.is_empty() {
+            return;
+        }
+        let crossover_hz = if sample_rate > 0 {
+            self.crossover_hz
+        } else {
+            0.0
+        };
+        let bin_hz = f64::from(sample_rate) / fft_size as f64;
+        for (frame, row) in aperiodicity.iter_mut().enumerate() {
+            let amount = tpos.get(frame).map_or(self.amount, |&t| {
+                automated_value(self.amount, &self.lane, t)
+            });
+            if amount == 0.0 {
+                continue;
+            }
+            for (i, val) in row.iter_mut().enumerate() {
+                let weight = breathiness_weight(i as f64 * bin_hz, crossover_hz);
+                // Aperiodicity is in [0, 1] range (or close). Increase towards 1.
+                *val = (*val + amount * weight).clamp(0.0, 1.0);
+            }
+        }
+    }
+}
+
+/// Share of the breathiness a bin at `freq_hz` gets: a one-octave ramp
+/// centred on `crossover_hz`, from 0 half an octave below it to 1 half an
+/// octave above, so low harmonics keep their periodicity while the top end
+/// turns to air. 1 everywhere when `crossover_hz` is 0.
+pub fn breathiness_weight(freq_hz: f64, crossover_hz: f64) -> f64 {
+    if crossover_hz <= 0.0 {
+        return 1.0;
+    }
+    (0.5 + (freq_hz / crossover_hz).log2()).clamp(0.0, 1.0)
+}
+
+/// Cepstral coefficients [`smooth_envelope_row`] keeps at full smoothing.
+const MIN_LIFTER_COEFFS: usize = 16;
+
+/// Smooth the envelope by liftering its cepstrum.
+struct SpectralSmooth(f64);
+
+impl SpectralStage for SpectralSmooth {
+    fn name(&self) -> &'static str {
+        "spectral_smooth"
+    }
+
+    fn apply(&self, spectrogram: &mut [Vec<f64>], _aperiodicity: &mut [Vec<f64>], fft_size: usize) {
+        if let Some(lifter) = Lifter::new(fft_size, self.0) {
+            parallel::for_each_row(spectrogram, |_, row| lifter.apply(row));
+        }
     }
-    for row in &mut params.aperiodicity {
-        for val in row.iter_mut() {
-            // Aperiodicity is in [0, 1] range (or close). Increase towards 1.
-            *val = (*val + amount).clamp(0.0, 1.0);
+}
+
+/// Cepstral smoothing of one spectrogram row (one frame's power envelope):
+/// log power → cepstrum → keep the lowest quefrencies → back. `amount` 0
+/// keeps all `fft_size / 2` coefficients (no change), 1 keeps
+/// [`MIN_LIFTER_COEFFS`], geometrically in between, so narrow spikes and
+/// ripples go while the broad formants stay.
+///
+/// Same math as the full-matrix modifier; exposed for cheap previews.
+pub fn smooth_envelope_row(row: &mut [f64], fft_size: usize, amount: f64) {
+    if let Some(lifter) = Lifter::new(fft_size, amount) {
+        lifter.apply(row);
+    }
+}
+
+/// FFT plans and cutoff for cepstral smoothing, shared by every row.
+struct Lifter {
+    forward: Arc<dyn Fft<f64>>,
+    inverse: Arc<dyn Fft<f64>>,
+    fft_size: usize,
+    /// Coefficients kept on each side of quefrency 0 (0 included).
+    keep: usize,
+}
+
+impl Lifter {
+    /// `None` when `amount` keeps everything or `fft_size` is too small.
+    fn new(fft_size: usize, amount: f64) -> Option<Self> {
+        let half = fft_size / 2;
+        if amount <= 0.0 || half <= MIN_LIFTER_COEFFS {
+            return None;
+        }
+        let ratio = MIN_LIFTER_COEFFS as f64 / half as f64;
+        let keep = (half as f64 * ratio.powf(amount.min(1.0))).round() as usize;
+        let mut planner = FftPlanner::new();
+        Some(Self {
+            forward: planner.plan_fft_forward(fft_size),
+            inverse: planner.plan_fft_inverse(fft_size),
+            fft_size,
+            keep: keep.clamp(MIN_LIFTER_COEFFS, half),
+        })
+    }
+
+    fn apply(&self, row: &mut [f64]) {
+        let n = self.fft_size;
+        let width = (n / 2 + 1).min(row.len());
+        if width < n / 2 + 1 {
+            return;
+        }
+        // The log spectrum mirrored into a full real sequence.
+        let mut buffer: Vec<Complex<f64>> = (0..n)
+            .map(|k| {
+                let bin = if k <= n / 2 { k } else { n - k };
+                Complex::new(row[bin].max(SANITIZED_SPECTRUM_BIN).ln(), 0.0)
+            })
+            .collect();
+        self.forward.process(&mut buffer);
+        for c in &mut buffer[self.keep..=n - self.keep] {
+            *c = Complex::new(0.0, 0.0);
+        }
+        self.inverse.process(&mut buffer);
+        for (bin, c) in row[..width].iter_mut().zip(&buffer) {
+            *bin = (c.re / n as f64).exp();
         }
     }
 }
 
 /// Warp the spectrogram frequency axis to shift formants.
-fn apply_formant_shift(params: &mut WorldParams, semitones: f64) {
-    if semitones == 0.0 {
+struct FormantShift {
+    semitones: f64,
+    keep_energy: bool,
+    warp: FormantWarp,
+}
+
+impl SpectralStage for FormantShift {
+    fn name(&self) -> &'static str {
+        "formant_shift"
+    }
+
+    fn apply(&self, spectrogram: &mut [Vec<f64>], _aperiodicity: &mut [Vec<f64>], fft_size: usize) {
+        let semitones = self.semitones;
+        if semitones == 0.0 {
+            return;
+        }
+        let warp = self.warp;
+        if self.keep_energy {
+            parallel::for_each_row(spectrogram, |_, row| {
+                formant_shift_row_with(row, fft_size, semitones, warp)
+            });
+        } else {
+            parallel::for_each_row(spectrogram, |_, row| {
+                warp_row(row, fft_size, semitones, warp)
+            });
+        }
+    }
+}
+
+/// Largest loudness correction, either way, [`formant_shift_row`] applies
+/// after the warp, so a frame whose energy the shift pushed out of (or
+/// pulled in from) beyond Nyquist isn't blown up to match.
+pub const FORMANT_ENERGY_CORRECTION_DB: f64 = 3.0;
+
+/// Formant shift applied to a single spectrogram row (one frame's envelope):
+/// [`formant_warp_row`], then the row rescaled to its original total power
+/// (within [`FORMANT_ENERGY_CORRECTION_DB`]).
+///
+/// Same math as the full-matrix modifier; exposed for cheap previews.
+pub fn formant_shift_row(row: &mut [f64], fft_size: usize, semitones: f64) {
+    formant_shift_row_with(row, fft_size, semitones, FormantWarp::Linear);
+}
+
+/// [`formant_shift_row`] with the given bin mapping.
+pub fn formant_shift_row_with(row: &mut [f64], fft_size: usize, semitones: f64, warp: FormantWarp) {
+    let sp_width = (fft_size / 2 + 1).min(row.len());
+    let before: f64 = row[..sp_width].iter().sum();
+    warp_row(row, fft_size, semitones, warp);
+    let after: f64 = row[..sp_width].iter().sum();
+    if before > 0.0 && after > 0.0 {
+        let limit = 10.0_f64.powf(FORMANT_ENERGY_CORRECTION_DB / 10.0);
+        let gain = (before / after).clamp(1.0 / limit, limit);
+        for bin in &mut row[..sp_width] {
+            *bin *= gain;
+        }
+    }
+}
+
+/// The formant shift's frequency warp alone: bin `i` takes the envelope at
+/// `i / 2^(semitones / 12)`, linearly interpolated, with no correction of
+/// the power it gains or loses.
+pub fn formant_warp_row(row: &mut [f64], fft_size: usize, semitones: f64) {
+    let sp_width = (fft_size / 2 + 1).min(row.len());
+    if semitones == 0.0 || sp_width == 0 {
         return;
     }
     let ratio = 2.0_f64.powf(semitones / 12.0);
-    let sp_width = params.fft_size / 2 + 1;
-
-    for row in &mut params.spectrogram {
-        let original = row.clone();
-        for (i, bin) in row.iter_mut().enumerate().take(sp_width) {
-            // Map destination bin i to source bin.
-            let src = i as f64 / ratio;
-            let src_floor = src.floor() as usize;
-            let frac = src - src_floor as f64;
-
-            if src_floor + 1 < sp_width {
-                *bin = original[src_floor] * (1.0 - frac) + original[src_floor + 1] * frac;
-            } else if src_floor < sp_width {
-                *bin = original[src_floor];
-            } else {
-                // Beyond the original spectrum — use the last bin value.
-                *bin = original[sp_width - 1];
-            }
+    let original = row[..sp_width].to_vec();
+    for (i, bin) in row.iter_mut().enumerate().take(sp_width) {
+        // Map destination bin i to source bin.
+        let src = i as f64 / ratio;
+        let src_floor = src.floor() as usize;
+        let frac = src - src_floor as f64;
+
+        if src_floor + 1 < sp_width {
+            *bin = original[src_floor] * (1.0 - frac) + original[src_floor + 1] * frac;
+        } else if src_floor < sp_width {
+            *bin = original[src_floor];
+        } else {
+            // Beyond the original spectrum — use the last bin value.
+            *bin = original[sp_width - 1];
         }
     }
 }
 
-/// Apply a spectral tilt (dB per octave slope) across frequency bins.
-fn apply_spectral_tilt(params: &mut WorldParams, tilt_db_per_oct: f64) {
-    if tilt_db_per_oct == 0.0 {
+/// The formant shift's frequency warp alone, in either mapping.
+fn warp_row(row: &mut [f64], fft_size: usize, semitones: f64, warp: FormantWarp) {
+    match warp {
+        FormantWarp::Linear => formant_warp_row(row, fft_size, semitones),
+        FormantWarp::Log => formant_log_warp_row(row, fft_size, semitones),
+    }
+}
+
+/// The formant shift's frequency warp read in log frequency: bin `i` takes
+/// the envelope at `i / 2^(semitones / 12)`, interpolated between the two
+/// nearest bins by their distance in octaves and geometrically in power, so
+/// a peak keeps its shape in semitones wherever it lands. Where a downward
+/// shift squeezes more than one source bin into a bin, it takes their mean
+/// power instead of sampling one point, so the envelope's fine ripple
+/// doesn't alias into the result. DC is kept as it is (it has no place in
+/// log frequency), and bins below the first non-DC one interpolate from it
+/// linearly. No correction of the power it gains or loses.
+pub fn formant_log_warp_row(row: &mut [f64], fft_size: usize, semitones: f64) {
+    let sp_width = (fft_size / 2 + 1).min(row.len());
+    if semitones == 0.0 || sp_width < 2 {
         return;
     }
-    let sp_width = params.fft_size / 2 + 1;
-    if sp_width < 2 {
+    let ratio = 2.0_f64.powf(semitones / 12.0);
+    let original = row[..sp_width].to_vec();
+    let last = sp_width - 1;
+    let log_lerp = |a: f64, b: f64, frac: f64| {
+        let floor = SANITIZED_SPECTRUM_BIN;
+        let (la, lb) = (a.max(floor).ln(), b.max(floor).ln());
+        (la + (lb - la) * frac).exp()
+    };
+    for (i, bin) in row.iter_mut().enumerate().take(sp_width).skip(1) {
+        let src = i as f64 / ratio;
+        // The span of source bins this bin covers.
+        let (lo, hi) = ((i as f64 - 0.5) / ratio, (i as f64 + 0.5) / ratio);
+        let (first, end) = (lo.ceil().max(1.0) as usize, (hi.floor() as usize).min(last));
+        *bin = if hi - lo > 1.0 && first <= end {
+            original[first..=end].iter().sum::<f64>() / (end - first + 1) as f64
+        } else if src >= last as f64 {
+            original[last]
+        } else if src < 1.0 {
+            original[0] + (original[1] - original[0]) * src
+        } else {
+            let k = src.floor() as usize;
+            let frac = (src / k as f64).log2() / ((k + 1) as f64 / k as f64).log2();
+            log_lerp(original[k], original[k + 1], frac)
+        };
+    }
+}
+
+/// Apply a spectral tilt (dB per octave slope) across frequency bins, from
+/// the automation lane at each frame's time when it has points. Without a
+/// sample rate the pivot can't be placed, so it falls back to
+/// [`spectral_tilt_row`]'s bin 1.
+struct SpectralTilt {
+    db_per_oct: f64,
+    lane: Vec<(f64, f64)>,
+    pivot_hz: f64,
+}
+
+impl SpectralStage for SpectralTilt {
+    fn name(&self) -> &'static str {
+        "spectral_tilt"
+    }
+
+    fn apply(&self, spectrogram: &mut [Vec<f64>], aperiodicity: &mut [Vec<f64>], fft_size: usize) {
+        self.apply_at_rate(spectrogram, aperiodicity, fft_size, 0);
+    }
+
+    fn apply_at_rate(
+        &self,
+        spectrogram: &mut [Vec<f64>],
+        aperiodicity: &mut [Vec<f64>],
+        fft_size: usize,
+        sample_rate: i32,
+    ) {
+        self.apply_timed(spectrogram, aperiodicity, fft_size, sample_rate, &[]);
+    }
+
+    fn apply_timed(
+        &self,
+        spectrogram: &mut [Vec<f64>],
+        _aperiodicity: &mut [Vec<f64>],
+        fft_size: usize,
+        sample_rate: i32,
+        tpos: &[f64],
+    ) {
+        if self.db_per_oct == 0.0 && self.lane.is_empty() {
+            return;
+        }
+        for (frame, row) in spectrogram.iter_mut().enumerate() {
+            let tilt = tpos.get(frame).map_or(self.db_per_oct, |&t| {
+                automated_value(self.db_per_oct, &self.lane, t)
+            });
+            if tilt == 0.0 {
+                continue;
+            }
+            if sample_rate > 0 && self.pivot_hz > 0.0 {
+                spectral_tilt_row_at(row, fft_size, sample_rate, tilt, self.pivot_hz);
+            } else {
+                spectral_tilt_row(row, fft_size, tilt);
+            }
+        }
+    }
+}
+
+/// Default tilt pivot: near the voice's loudest harmonics, so a tilt
+/// reshapes the timbre without swinging the loudness.
+pub const TILT_PIVOT_HZ: f64 = 1000.0;
+
+/// Spectral tilt applied to a single spectrogram row (one frame's envelope),
+/// pivoting on bin 1 (the lowest non-DC bin) when the sample rate is
+/// unknown; see [`spectral_tilt_row_at`].
+///
+/// Same math as the full-matrix modifier; exposed for cheap previews.
+pub fn spectral_tilt_row(row: &mut [f64], fft_size: usize, tilt_db_per_oct: f64) {
+    tilt_row(row, fft_size, tilt_db_per_oct, 1.0);
+}
+
+/// Spectral tilt on a row analysed at `sample_rate`, pivoting on `pivot_hz`:
+/// each bin gains `tilt · log2(f / pivot_hz)` dB, so the level near the
+/// pivot is unchanged.
+pub fn spectral_tilt_row_at(
+    row: &mut [f64],
+    fft_size: usize,
+    sample_rate: i32,
+    tilt_db_per_oct: f64,
+    pivot_hz: f64,
+) {
+    tilt_row(
+        row,
+        fft_size,
+        tilt_db_per_oct,
+        pivot_hz * fft_size as f64 / f64::from(sample_rate),
+    );
+}
+
+fn tilt_row(row: &mut [f64], fft_size: usize, tilt_db_per_oct: f64, ref_bin: f64) {
+    let sp_width = fft_size / 2 + 1;
+    if tilt_db_per_oct == 0.0 || sp_width < 2 {
         return;
     }
 
-    // Tilt is applied relative to bin 1 (lowest non-DC bin) to avoid log2(0).
+    // DC is skipped to avoid log2(0).
     // Each bin represents frequency bin i ~ i * (sr/fft_size).
     // gain_db = tilt * log2(bin_index / ref_bin) for each bin.
-    let ref_bin = 1.0_f64;
+    for (i, bin) in row.iter_mut().enumerate().take(sp_width).skip(1) {
+        let octaves = (i as f64 / ref_bin).log2();
+        let gain_db = tilt_db_per_oct * octaves;
+        let gain_linear = 10.0_f64.powf(gain_db / 20.0);
+        // Spectrogram values are power spectra, so apply gain squared.
+        *bin *= gain_linear * gain_linear;
+    }
+}
+
+/// Boost (or cut) the envelope around `center_hz` by `gain_db`, with a
+/// Gaussian bell in log frequency `width_oct` octaves wide at half gain: a
+/// "presence" control that, unlike the tilt, leaves the lows and the top
+/// octave alone. Bins are placed in Hz with [`WorldParams::sample_rate`].
+pub fn apply_presence(params: &mut WorldParams, gain_db: f64, center_hz: f64, width_oct: f64) {
+    for row in params.spectrogram.iter_mut() {
+        presence_row(
+            row,
+            params.fft_size,
+            params.sample_rate,
+            gain_db,
+            center_hz,
+            width_oct,
+        );
+    }
+}
+
+/// Default presence centre: the geometric middle of 2–6 kHz.
+pub const PRESENCE_CENTER_HZ: f64 = 3500.0;
+
+/// Default presence width: 2–6 kHz at half gain.
+pub const PRESENCE_WIDTH_OCT: f64 = 1.6;
+
+/// [`apply_presence`] as a stage. Without a sample rate the bell can't be
+/// placed, so it does nothing.
+struct Presence {
+    gain_db: f64,
+    center_hz: f64,
+    width_oct: f64,
+}
+
+impl SpectralStage for Presence {
+    fn name(&self) -> &'static str {
+        "presence"
+    }
+
+    fn apply(&self, spectrogram: &mut [Vec<f64>], aperiodicity: &mut [Vec<f64>], fft_size: usize) {
+        self.apply_at_rate(spectrogram, aperiodicity, fft_size, 0);
+    }
+
+    fn apply_at_rate(
+        &self,
+        spectrogram: &mut [Vec<f64>],
+        _aperiodicity: &mut [Vec<f64>],
+        fft_size: usize,
+        sample_rate: i32,
+    ) {
+        for row in spectrogram.iter_mut() {
+            presence_row(
+                row,
+                fft_size,
+                sample_rate,
+                self.gain_db,
+                self.center_hz,
+                self.width_oct,
+            );
+        }
+    }
+}
+
+fn presence_row(
+    row: &mut [f64],
+    fft_size: usize,
+    sample_rate: i32,
+    gain_db: f64,
+    center_hz: f64,
+    width_oct: f64,
+) {
+    if gain_db == 0.0 || sample_rate <= 0 || center_hz <= 0.0 || width_oct <= 0.0 {
+        return;
+    }
+    // Half gain at ±width/2 octaves: sigma = FWHM / (2·sqrt(2·ln 2)).
+    let sigma = width_oct / (2.0 * (2.0 * std::f64::consts::LN_2).sqrt());
+    let bin_hz = f64::from(sample_rate) / fft_size as f64;
+    for (i, bin) in row.iter_mut().enumerate().skip(1) {
+        let octaves = (i as f64 * bin_hz / center_hz).log2();
+        let bin_gain_db = gain_db * (-0.5 * (octaves / sigma).powi(2)).exp();
+        // Spectrogram values are power spectra: 10·log10 rather than 20.
+        *bin *= 10.0_f64.powf(bin_gain_db / 10.0);
+    }
+}
+
+/// Make the voice more (positive `amount_db`) or less (negative) nasal: a
+/// peak of `amount_db` at [`NASAL_PEAK_HZ`], where the nasal resonance
+/// sits, and a dip of as much at [`NASAL_DIP_HZ`], where the nasal
+/// antiresonance takes energy out. Both are Gaussian bells in log
+/// frequency [`NASAL_WIDTH_OCT`] wide at half gain, as in
+/// [`apply_presence`]; bins are placed in Hz with
+/// [`WorldParams::sample_rate`].
+pub fn apply_nasality(params: &mut WorldParams, amount_db: f64) {
+    for row in params.spectrogram.iter_mut() {
+        nasality_row(row, params.fft_size, params.sample_rate, amount_db);
+    }
+}
+
+/// Centre of the nasal peak: the middle of 250–450 Hz.
+pub const NASAL_PEAK_HZ: f64 = 350.0;
+
+/// Centre of the nasal dip: the middle of 800 Hz–1.2 kHz.
+pub const NASAL_DIP_HZ: f64 = 1000.0;
+
+/// Width of the nasal peak and dip in octaves, at half gain; narrow enough
+/// that neither reaches the other's centre.
+pub const NASAL_WIDTH_OCT: f64 = 0.8;
+
+/// Slider range of the nasality, ± this many dB.
+pub const NASALITY_MAX_DB: f64 = 6.0;
+
+/// [`apply_nasality`] as a stage.
+struct Nasality(f64);
+
+impl SpectralStage for Nasality {
+    fn name(&self) -> &'static str {
+        "nasality"
+    }
+
+    fn apply(&self, spectrogram: &mut [Vec<f64>], aperiodicity: &mut [Vec<f64>], fft_size: usize) {
+        self.apply_at_rate(spectrogram, aperiodicity, fft_size, 0);
+    }
 
-    for row in &mut params.spectrogram {
-        for (i, bin) in row.iter_mut().enumerate().take(sp_width).skip(1) {
-            let octaves = (i as f64 / ref_bin).log2();
-            let gain_db = tilt_db_per_oct * octaves;
-            let gain_linear = 10.0_f64.powf(gain_db / 20.0);
-            // Spectrogram values are power spectra, so apply gain squared.
-            *bin *= gain_linear * gain_linear;
+    fn apply_at_rate(
+        &self,
+        spectrogram: &mut [Vec<f64>],
+        _aperiodicity: &mut [Vec<f64>],
+        fft_size: usize,
+        sample_rate: i32,
+    ) {
+        for row in spectrogram.iter_mut() {
+            nasality_row(row, fft_size, sample_rate, self.0);
         }
     }
 }
 
+fn nasality_row(row: &mut [f64], fft_size: usize, sample_rate: i32, amount_db: f64) {
+    presence_row(
+        row,
+        fft_size,
+        sample_rate,
+        amount_db,
+        NASAL_PEAK_HZ,
+        NASAL_WIDTH_OCT,
+    );
+    presence_row(
+        row,
+        fft_size,
+        sample_rate,
+        -amount_db,
+        NASAL_DIP_HZ,
+        NASAL_WIDTH_OCT,
+    );
+}
+
+/// Preview the spectral-envelope sliders on a single spectrogram row, via the
+/// same [`SpectralPipeline`] (and so the same stage order) as [`apply`].
+/// Stages that need the sample rate (see [`preview_envelope_at_rate`]) are
+/// left out.
+pub fn preview_envelope(row: &[f64], fft_size: usize, values: &WorldSliderValues) -> Vec<f64> {
+    preview_envelope_at_rate(row, fft_size, 0, values)
+}
+
+/// [`preview_envelope`] for a row analysed at `sample_rate`.
+pub fn preview_envelope_at_rate(
+    row: &[f64],
+    fft_size: usize,
+    sample_rate: i32,
+    values: &WorldSliderValues,
+) -> Vec<f64> {
+    let mut rows = [row.to_vec()];
+    SpectralPipeline::from_values(values).run_at_rate(&mut rows, &mut [], fft_size, sample_rate);
+    let [out] = rows;
+    out
+}
+
+/// [`resample_1d`] for an f0 contour: linear between two voiced frames,
+/// but the nearest frame's value wherever either neighbour is unvoiced, so
+/// a voicing boundary never produces an f0 between 0 and the voiced pitch
+/// (which would synthesize as a pitch dive).
+fn resample_f0(f0: &[f64], new_len: usize) -> Vec<f64> {
+    if new_len == 0 {
+        return Vec::new();
+    }
+    if f0.len() <= 1 || new_len == 1 {
+        return vec![f0.first().copied().unwrap_or(0.0); new_len];
+    }
+
+    let old_len = f0.len();
+    (0..new_len)
+        .map(|i| {
+            let t = i as f64 * (old_len - 1) as f64 / (new_len - 1) as f64;
+            let lo = t.floor() as usize;
+            let hi = (lo + 1).min(old_len - 1);
+            let frac = t - lo as f64;
+            if f0[lo] > 0.0 && f0[hi] > 0.0 {
+                f0[lo] * (1.0 - frac) + f0[hi] * frac
+            } else if frac < 0.5 {
+                f0[lo]
+            } else {
+                f0[hi]
+            }
+        })
+        .collect()
+}
+
 /// Linearly resample a 1D vector to a new length.
 fn resample_1d(data: &[f64], new_len: usize) -> Vec<f64> {
     if new_len == 0 {
@@ -223,15 +2594,13 @@ fn resample_2d(data: &[Vec<f64>], new_len: usize) -> Vec<Vec<f64>> {
         return vec![data[0].clone(); new_len];
     }
 
-    (0..new_len)
-        .map(|i| {
-            let t = i as f64 * (old_len - 1) as f64 / (new_len - 1) as f64;
-            let lo = t.floor() as usize;
-            let hi = (lo + 1).min(old_len - 1);
-            let frac = t - lo as f64;
-            (0..width)
-                .map(|j| data[lo][j] * (1.0 - frac) + data[hi][j] * frac)
-                .collect()
-        })
-        .collect()
+    parallel::map_rows(new_len, |i| {
+        let t = i as f64 * (old_len - 1) as f64 / (new_len - 1) as f64;
+        let lo = t.floor() as usize;
+        let hi = (lo + 1).min(old_len - 1);
+        let frac = t - lo as f64;
+        (0..width)
+            .map(|j| data[lo][j] * (1.0 - frac) + data[hi][j] * frac)
+            .collect()
+    })
 }
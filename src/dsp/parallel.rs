@@ -0,0 +1,76 @@
+//! Row-parallel loops for the frame-wise stages (spectrogram rows are
+//! independent), on scoped std threads.
+
+use std::sync::OnceLock;
+
+/// Environment variable overriding the thread count (a positive integer).
+pub const THREADS_ENV: &str = "VOICEFORGE_THREADS";
+
+/// Below this many rows a loop runs on the calling thread: spawning would
+/// cost more than it saves.
+const MIN_PARALLEL_ROWS: usize = 256;
+
+/// Threads for row-parallel work: [`THREADS_ENV`] if set to a positive
+/// integer, else one per available core. Read once.
+pub fn threads() -> usize {
+    static THREADS: OnceLock<usize> = OnceLock::new();
+    *THREADS.get_or_init(|| {
+        let value = std::env::var(THREADS_ENV).ok();
+        parse_threads(value.as_deref())
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()))
+    })
+}
+
+/// A positive thread count from [`THREADS_ENV`]'s value; `None` when unset
+/// or invalid (logged).
+pub fn parse_threads(value: Option<&str>) -> Option<usize> {
+    let value = value?;
+    match value.trim().parse::<usize>() {
+        Ok(n) if n > 0 => Some(n),
+        _ => {
+            log::warn!("ignoring invalid {THREADS_ENV}={value}");
+            None
+        }
+    }
+}
+
+/// `f(i, &mut rows[i])` for every row, on [`threads`] threads.
+pub fn for_each_row<T: Send>(rows: &mut [T], f: impl Fn(usize, &mut T) + Sync) {
+    for_each_row_on(threads(), rows, f);
+}
+
+/// [`for_each_row`] on `threads` threads; each gets one contiguous block.
+pub fn for_each_row_on<T: Send>(threads: usize, rows: &mut [T], f: impl Fn(usize, &mut T) + Sync) {
+    let threads = threads.clamp(1, rows.len().max(1));
+    if threads == 1 || rows.len() < MIN_PARALLEL_ROWS {
+        for (i, row) in rows.iter_mut().enumerate() {
+            f(i, row);
+        }
+        return;
+    }
+    let block = rows.len().div_ceil(threads);
+    let f = &f;
+    std::thread::scope(|scope| {
+        for (b, chunk) in rows.chunks_mut(block).enumerate() {
+            scope.spawn(move || {
+                for (i, row) in chunk.iter_mut().enumerate() {
+                    f(b * block + i, row);
+                }
+            });
+        }
+    });
+}
+
+/// `(0..len).map(f).collect()`, on [`threads`] threads.
+pub fn map_rows<T: Send>(len: usize, f: impl Fn(usize) -> T + Sync) -> Vec<T> {
+    map_rows_on(threads(), len, f)
+}
+
+/// [`map_rows`] on `threads` threads.
+pub fn map_rows_on<T: Send>(threads: usize, len: usize, f: impl Fn(usize) -> T + Sync) -> Vec<T> {
+    let mut rows: Vec<Option<T>> = (0..len).map(|_| None).collect();
+    for_each_row_on(threads, &mut rows, |i, row| *row = Some(f(i)));
+    rows.into_iter()
+        .map(|row| row.expect("every row is filled"))
+        .collect()
+}
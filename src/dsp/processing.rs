@@ -1,35 +1,509 @@
+use std::ops::Range;
 use std::panic;
-use std::path::Path;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 
 use crossbeam_channel::{Receiver, Sender};
 
-use crate::audio::decoder::{self, AudioData};
-use crate::dsp::effects::{self, EffectsParams};
-use crate::dsp::modifier::{self, WorldSliderValues};
+use crate::audio::decoder::{self, AudioData, AudioFormat};
+use crate::audio::edit::{self, Splice};
+use crate::audio::export;
+use crate::dsp::classify::{self, Classification, ContentFeatures};
+use crate::dsp::effects::{self, EffectsParams, LufsNormalization};
+use crate::dsp::f0_candidates::{self, F0Choice};
+use crate::dsp::inspect::FrameInfo;
+use crate::dsp::loudness::{self, AbLoudness};
+use crate::dsp::modifier::{self, DirtyStages, WorldSliderValues};
 use crate::dsp::world;
-use world_sys::WorldParams;
+use crate::paths;
+use crate::published::Published;
+use world_sys::{AnalyzeOptions, WorldError, WorldParams};
 
 /// Commands sent from the main thread to the processing thread.
+// Resynthesize carries every WORLD slider; commands are sent a handful of
+// times per second at most, so the size isn't worth a box.
+#[allow(clippy::large_enum_variant)]
 pub enum ProcessingCommand {
-    Load(String),                                      // path to decode
-    ScanDirectory(String),                             // path prefix as typed
-    PrecheckAudio(String),                             // path to validate
-    Resynthesize(WorldSliderValues, EffectsParams),
-    ReapplyEffects(EffectsParams),
+    /// Decode and analyze this path.
+    Load(String),
+    /// Decode for playback only, no analysis.
+    Decode(String),
+    /// Path prefix as typed.
+    ScanDirectory(String),
+    /// Path to validate.
+    PrecheckAudio(String),
+    /// New WORLD sliders, the effects revision, the format the UI expects back
+    /// and the source seconds to re-render (`None` for all).
+    Resynthesize(WorldSliderValues, u64, AudioFormat, Option<Range<f64>>),
+    /// Effects revision and the format the UI expects back.
+    ReapplyEffects(u64, AudioFormat),
+    /// Source time (s) of the frame to fetch.
+    SnapshotFrame(f64),
+    /// Source time (s) of the frame to inspect.
+    InspectFrame(f64),
+    /// Overwrite f0 in a range with one candidate.
+    ApplyF0Candidate(F0Choice),
+    /// Re-analyze decoded audio (only the changed span if possible).
+    Analyze(AudioData),
+    /// Write a captured buffer to WAV.
+    Export(ExportJob),
+    /// Splice a clip into a captured buffer.
+    PunchIn(PunchJob),
+    /// Write the current analysis to a params file.
+    SaveParams(String),
+    /// Replace the analysis with a params file made from the same audio.
+    LoadParams(String),
+    /// Analyze with these from now on; drops the analysis.
+    SetAnalysisOptions(AnalyzeOptions),
+    /// Another take of the same material and alpha (0 this one, 1 that one).
+    Morph(String, f64),
+    /// Presets A and B, and the source seconds B fades in over.
+    ResynthesizeMorph(WorldSliderValues, WorldSliderValues, (f64, f64)),
     Shutdown,
+    /// Kill the worker with a panic that escapes `catch_unwind` (a panic
+    /// payload whose `Drop` panics again). Only for exercising the watchdog.
+    #[cfg(feature = "test-hooks")]
+    #[doc(hidden)]
+    SimulateCrash,
+    /// Panic inside `catch_unwind`, as a bug in a command would. Only for
+    /// exercising `AnalysisInvalidated`.
+    #[cfg(feature = "test-hooks")]
+    #[doc(hidden)]
+    SimulatePanic,
 }
 
 /// Results sent from the processing thread back to the main thread.
 pub enum ProcessingResult {
-    AudioReady(AudioData, String),                    // decoded audio + path that was decoded
+    /// Decoded audio + path that was decoded.
+    AudioReady(AudioData, String),
     AnalysisDone(AudioData),
     SynthesisDone(AudioData),
     Status(String),
-    DirectoryListing(String, Vec<String>),            // (input_prefix_echo, sorted entries)
-    AudioPrecheckDone(String),                        // path is valid audio
-    AudioPrecheckFailed(String, String),              // (path, error message)
+    /// The prefix as typed, echoed back, and its sorted entries.
+    DirectoryListing(String, Vec<String>),
+    /// Path is valid audio.
+    AudioPrecheckDone(String),
+    /// Path and error message.
+    AudioPrecheckFailed(String, String),
+    /// One analysis frame for UI previews.
+    FrameSnapshot(FrameSnapshot),
+    /// Raw values of one frame for the inspector.
+    FrameInfo(FrameInfo),
+    /// The applied choice and the frames written.
+    F0Corrected(F0Choice, usize),
+    /// Content class, follows every `AnalysisDone`.
+    Classified(Classification),
+    /// Destination path and outcome.
+    ExportDone(String, Result<(), String>),
+    /// A/B levels, follows every `SynthesisDone`.
+    Loudness(AbLoudness),
+    /// Progress line for the running export.
+    ExportProgress(String),
+    /// The punch-in's base with the clip spliced in.
+    PunchedIn(AudioData),
+    /// B around the playhead, ahead of `SynthesisDone`.
+    PreviewReady(PreviewClip),
+    /// (source s, f0 Hz) points of the audio being analyzed, ahead of
+    /// `AnalysisDone`.
+    PitchCurve(Vec<(f64, f64)>),
+    /// A caught panic dropped the analysis; re-send `Analyze`.
+    AnalysisInvalidated,
+    /// The full analysis replaced a preview; resynthesize.
+    AnalysisRefined,
+    /// Worker time of the resynthesis whose `SynthesisDone` was just sent;
+    /// effects-only passes send none.
+    SynthesisTimed(Duration),
+}
+
+/// A WAV export handed to the worker.
+///
+/// `audio` is the buffer captured when the export was dispatched. Later
+/// resyntheses replace `AppState::audio_data` with new Arcs but never touch
+/// this one, so the file always holds the generation that was current at
+/// dispatch, no matter how the job interleaves with `SynthesisDone`.
+pub struct ExportJob {
+    pub audio: Arc<AudioData>,
+    /// Live gain in dB, baked in because it is not part of the buffer.
+    pub gain_db: f32,
+    pub path: String,
+    /// Which snapshot is being written, for the status line (e.g. "B rev 14").
+    pub label: String,
+}
+
+/// A punch-in handed to the worker: decode the clip at `path`, resample it
+/// to `base`'s rate and splice it in. Like [`ExportJob`], `base` is the
+/// processed buffer captured when the job was dispatched.
+pub struct PunchJob {
+    pub base: Arc<AudioData>,
+    pub path: String,
+    pub splice: Splice,
+}
+
+/// Seconds of a resynthesis rendered first, around the playhead.
+const PREVIEW_SECS: f64 = 2.0;
+/// How much of the preview window lies before the playhead.
+const PREVIEW_LEAD_SECS: f64 = 0.25;
+/// Crossfade at each end of a spliced preview.
+const PREVIEW_FADE_SECS: f64 = 0.02;
+/// Crossfade at each end of a region resynthesized on its own.
+const REGION_FADE_SECS: f64 = 0.02;
+
+/// The ~2 s of a resynthesis around the playhead, rendered with the
+/// realtime synthesizer before the full buffer. Only sent for files more
+/// than twice that long, at speed 1 (a speed change moves every sample);
+/// the full `SynthesisDone` always follows.
+#[derive(Debug, Clone)]
+pub struct PreviewClip {
+    /// Processed audio (WORLD and effects) for `start..start + len`.
+    pub audio: AudioData,
+    /// First frame the clip covers in the full buffer.
+    pub start: usize,
+    /// Frames in the full buffer the clip belongs to.
+    pub total_frames: usize,
+}
+
+impl PreviewClip {
+    /// `base` with the clip crossfaded in, or `None` if `base` is not the
+    /// same length and format as the buffer the clip belongs to (e.g. it
+    /// was rendered at another speed).
+    pub fn splice_into(&self, base: &AudioData) -> Option<AudioData> {
+        if base.frame_count() != self.total_frames || base.format() != self.audio.format() {
+            return None;
+        }
+        let end = (self.start + self.audio.frame_count()).min(self.total_frames);
+        let fade = (PREVIEW_FADE_SECS * f64::from(base.sample_rate)) as usize;
+        edit::splice(
+            base,
+            &self.audio,
+            &Splice {
+                start: self.start,
+                end,
+                fade,
+                mode: edit::LengthMode::Fit,
+            },
+        )
+        .ok()
+    }
+}
+
+/// A single WORLD spectrogram frame, copied out of the cached analysis so the
+/// UI can preview spectral-envelope sliders without a synthesis round trip.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameSnapshot {
+    /// Power spectral envelope, `fft_size / 2 + 1` bins.
+    pub envelope: Vec<f64>,
+    pub fft_size: usize,
+    pub sample_rate: u32,
+    /// Source time of the frame in seconds.
+    pub time_secs: f64,
+}
+
+/// Turns percentage callbacks into status lines with throughput and ETA,
+/// e.g. "Decoding... 37% (12.3× realtime, ~8 s left)".
+///
+/// The ETA extrapolates linearly from the elapsed time. Updates are
+/// throttled to [`ProgressTracker::EMIT_INTERVAL`] (100% always gets
+/// through), and percentages never go backwards.
+#[derive(Debug, Clone)]
+pub struct ProgressTracker {
+    label: String,
+    /// Duration of the audio being processed, for the realtime factor (0 = unknown).
+    media_secs: f64,
+    started: Instant,
+    last_emit: Option<Instant>,
+    pct: u8,
+}
+
+impl ProgressTracker {
+    /// Minimum time between emitted statuses.
+    pub const EMIT_INTERVAL: Duration = Duration::from_millis(500);
+    /// ETAs beyond this are shown as "> 1 h" (a stalled job extrapolates to forever).
+    pub const MAX_ETA_SECS: f64 = 3600.0;
+
+    pub fn new(label: &str, media_secs: f64) -> Self {
+        Self::started_at(label, media_secs, Instant::now())
+    }
+
+    /// Tracker whose clock started at `started` (for tests with known timestamps).
+    pub fn started_at(label: &str, media_secs: f64, started: Instant) -> Self {
+        Self {
+            label: label.to_string(),
+            media_secs,
+            started,
+            last_emit: None,
+            pct: 0,
+        }
+    }
+
+    /// Set the media duration once it is known (e.g. after the decoder probes the file).
+    pub fn set_media_secs(&mut self, secs: f64) {
+        self.media_secs = secs;
+    }
+
+    /// Record `pct` now; see [`ProgressTracker::update_at`].
+    pub fn update(&mut self, pct: u8) -> Option<String> {
+        self.update_at(pct, Instant::now())
+    }
+
+    /// Record `pct` observed at `now`. Returns the status line to show, or
+    /// `None` if the last one went out less than `EMIT_INTERVAL` ago.
+    pub fn update_at(&mut self, pct: u8, now: Instant) -> Option<String> {
+        self.pct = self.pct.max(pct.min(100));
+        let due = self
+            .last_emit
+            .is_none_or(|t| now.saturating_duration_since(t) >= Self::EMIT_INTERVAL);
+        if !due && self.pct < 100 {
+            return None;
+        }
+        self.last_emit = Some(now);
+        Some(self.status_at(now))
+    }
+
+    /// Estimated seconds remaining at `now`, capped at `MAX_ETA_SECS`;
+    /// `None` before any progress or once complete.
+    pub fn eta_secs_at(&self, now: Instant) -> Option<f64> {
+        if self.pct == 0 || self.pct >= 100 {
+            return None;
+        }
+        let elapsed = now.saturating_duration_since(self.started).as_secs_f64();
+        let pct = f64::from(self.pct);
+        Some((elapsed * (100.0 - pct) / pct).min(Self::MAX_ETA_SECS))
+    }
+
+    /// Seconds of audio processed per wall-clock second, if measurable.
+    pub fn realtime_factor_at(&self, now: Instant) -> Option<f64> {
+        let elapsed = now.saturating_duration_since(self.started).as_secs_f64();
+        if self.media_secs <= 0.0 || self.pct == 0 || elapsed <= 0.0 {
+            return None;
+        }
+        Some(self.media_secs * f64::from(self.pct) / 100.0 / elapsed)
+    }
+
+    /// Status line for the current progress at `now`.
+    pub fn status_at(&self, now: Instant) -> String {
+        let mut details = Vec::new();
+        if let Some(factor) = self.realtime_factor_at(now) {
+            details.push(format!("{factor:.1}× realtime"));
+        }
+        if let Some(eta) = self.eta_secs_at(now) {
+            details.push(if eta >= Self::MAX_ETA_SECS {
+                "> 1 h left".to_string()
+            } else if eta >= 60.0 {
+                format!("~{} min left", (eta / 60.0).ceil() as u64)
+            } else {
+                format!("~{} s left", eta.ceil() as u64)
+            });
+        }
+        if details.is_empty() {
+            format!("{} {}%", self.label, self.pct)
+        } else {
+            format!("{} {}% ({})", self.label, self.pct, details.join(", "))
+        }
+    }
+}
+
+/// Effects parameters shared between the UI (sole publisher) and the worker.
+/// Commands only carry the revision that prompted them; the worker always
+/// processes with the latest snapshot.
+pub type SharedEffects = Arc<Published<EffectsParams>>;
+
+/// Analysis settings a worker is spawned with. All but `world` stay fixed;
+/// [`ProcessingHandle::set_analysis`] replaces `world` at runtime and drops
+/// the current analysis, so the next `Load` or `Analyze` and every analysis
+/// after it (re-analyses and preview refinements included) use the new
+/// settings. A respawned worker starts with the latest ones.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AnalysisOptions {
+    /// Extended analysis: also keep the f0 candidate lattice for manual
+    /// correction (three extra f0 tracks per analysis, and a few candidates
+    /// per frame in memory).
+    pub f0_candidates: bool,
+    /// f0 range, frame period and voicing threshold for WORLD.
+    pub world: AnalyzeOptions,
+    /// Save each fresh analysis of a loaded file to its sidecar
+    /// ([`params_sidecar`]). Sidecars that match are read either way.
+    pub cache_params: bool,
+    /// Audio at least this long (seconds) gets a quick preview analysis
+    /// ([`preview_analysis_options`]) first, installed right away while the full
+    /// analysis runs in the background and replaces it when done. `None`
+    /// analyzes in one pass.
+    pub preview_from_secs: Option<f64>,
+    /// Scale WORLD renders whose peak would clip down to
+    /// [`world::SYNTHESIS_PEAK_CEILING`] ([`world::synthesize_normalized`]),
+    /// reporting the attenuation in a status after `SynthesisDone`.
+    pub normalize_synthesis: bool,
+}
+
+/// DIO decimation of the preview analysis.
+const PREVIEW_DIO_SPEED: i32 = 4;
+/// The preview analysis hops this many times further between frames.
+const PREVIEW_FRAME_PERIOD_SCALE: f64 = 2.0;
+
+/// Settings of the preview analysis for `world`: DIO at
+/// [`PREVIEW_DIO_SPEED`] and a [`PREVIEW_FRAME_PERIOD_SCALE`] times coarser
+/// frame period.
+pub fn preview_analysis_options(world: &AnalyzeOptions) -> AnalyzeOptions {
+    AnalyzeOptions {
+        dio_speed: PREVIEW_DIO_SPEED,
+        frame_period: world.frame_period * PREVIEW_FRAME_PERIOD_SCALE,
+        ..*world
+    }
+}
+
+/// The full analysis running on its own thread after a preview was
+/// installed. Its result replaces the preview only if the preview is still
+/// the cached analysis by then.
+struct Refinement {
+    /// The analyzed audio and the outcome, sent once.
+    result_rx: Receiver<(AudioData, Result<WorldParams, WorldError>)>,
+    /// The token of the `Load`/`Analyze` that started it.
+    cancel: Arc<AtomicBool>,
+    /// f0 of the installed preview, to recognise it when the result arrives.
+    preview_f0: Vec<f64>,
+    /// Sidecar to cache the full analysis in (a load with `cache_params`).
+    save_to: Option<PathBuf>,
+}
+
+impl Refinement {
+    fn spawn(
+        audio: &AudioData,
+        options: AnalysisOptions,
+        cancel: &Arc<AtomicBool>,
+        preview_f0: Vec<f64>,
+        result_tx: &Sender<ProcessingResult>,
+    ) -> Self {
+        let (tx, result_rx) = crossbeam_channel::bounded(1);
+        let audio = audio.clone();
+        let thread_cancel = Arc::clone(cancel);
+        let status_tx = result_tx.clone();
+        let mut progress = ProgressTracker::new("Refining analysis...", audio.duration_secs());
+        thread::spawn(move || {
+            let result =
+                world::analyze_with_progress(&audio, &options.world, &thread_cancel, |pct| {
+                    if let Some(status) = progress.update(pct) {
+                        let _ = status_tx.send(ProcessingResult::Status(status));
+                    }
+                });
+            let _ = tx.send((audio, result));
+        });
+        Self {
+            result_rx,
+            cancel: Arc::clone(cancel),
+            preview_f0,
+            save_to: None,
+        }
+    }
+}
+
+/// Extension of the params file cached next to an audio file.
+pub const PARAMS_SIDECAR_EXTENSION: &str = "vfw";
+
+/// Where the analysis of the audio file at `audio_path` is cached:
+/// `song.wav` → `song.vfw`. A file that already has the sidecar extension
+/// gets it appended, so the audio is never overwritten.
+pub fn params_sidecar(audio_path: &str) -> PathBuf {
+    let path = Path::new(audio_path);
+    let sidecar = path.with_extension(PARAMS_SIDECAR_EXTENSION);
+    if sidecar == path {
+        PathBuf::from(format!("{audio_path}.{PARAMS_SIDECAR_EXTENSION}"))
+    } else {
+        sidecar
+    }
+}
+
+/// Identifies what a params file was analyzed from: the mono samples,
+/// their rate and the WORLD settings (threads aside, which don't change the
+/// result). FNV-1a, so the value is stable across builds and platforms.
+fn analysis_key(mono: &AudioData, options: &AnalyzeOptions) -> u64 {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+    let settings = [
+        options.f0_floor,
+        options.f0_ceil,
+        options.frame_period,
+        options.d4c_threshold,
+    ];
+    let words = [u64::from(mono.sample_rate), u64::from(mono.channels)]
+        .into_iter()
+        .chain(settings.iter().map(|v| v.to_bits()))
+        .chain(options.cheaptrick_q1.map(f64::to_bits))
+        .chain((options.dio_speed != 1).then_some(options.dio_speed as u64))
+        .chain((!options.refine_f0).then_some(u64::MAX))
+        .chain(mono.samples.iter().map(|s| u64::from(s.to_bits())));
+    let mut hash = FNV_OFFSET;
+    for word in words {
+        for byte in word.to_le_bytes() {
+            hash = (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+        }
+    }
+    hash
+}
+
+/// Cancellation tokens for analyses, shared by the handle and its worker.
+///
+/// Each `Load`/`Analyze` sent is generation `n` (counting from 1); the
+/// worker takes the token for its `n`-th one when it starts it. Sending the
+/// next one cancels the current token, so an analysis that is running or
+/// still queued stops (or never starts) once a newer file is on its way.
+#[derive(Debug)]
+struct AnalysisCancel {
+    /// (latest generation sent, its token)
+    current: Mutex<(u64, Arc<AtomicBool>)>,
+}
+
+impl AnalysisCancel {
+    fn new() -> Self {
+        Self {
+            current: Mutex::new((0, Arc::new(AtomicBool::new(false)))),
+        }
+    }
+
+    /// A `Load`/`Analyze` was sent: cancel the previous one.
+    fn supersede(&self) {
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        current.1.store(true, Ordering::Relaxed);
+        *current = (current.0 + 1, Arc::new(AtomicBool::new(false)));
+    }
+
+    /// A playback-only `Decode` was sent: cancel without a new analysis.
+    fn cancel(&self) {
+        let current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        current.1.store(true, Ordering::Relaxed);
+    }
+
+    /// The token for generation `generation`: already set if a newer one
+    /// has been sent.
+    fn token(&self, generation: u64) -> Arc<AtomicBool> {
+        let current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        if current.0 == generation {
+            Arc::clone(&current.1)
+        } else {
+            Arc::new(AtomicBool::new(true))
+        }
+    }
+}
+
+/// The worker's side of [`AnalysisCancel`]: counts the `Load`/`Analyze`
+/// commands it has taken off the queue, and holds the full analysis a
+/// preview is waiting for.
+struct AnalysisTokens {
+    shared: Arc<AnalysisCancel>,
+    taken: u64,
+    refining: Option<Refinement>,
+}
+
+impl AnalysisTokens {
+    /// Token for the `Load`/`Analyze` just dequeued. Call exactly once per
+    /// such command. Drops the pending refinement, which that command
+    /// cancelled.
+    fn next(&mut self) -> Arc<AtomicBool> {
+        self.taken += 1;
+        self.refining = None;
+        self.shared.token(self.taken)
+    }
 }
 
 /// Handle for communicating with the processing thread.
@@ -37,35 +511,132 @@ pub struct ProcessingHandle {
     cmd_tx: Sender<ProcessingCommand>,
     result_rx: Receiver<ProcessingResult>,
     thread: Option<thread::JoinHandle<()>>,
+    options: AnalysisOptions,
+    effects: SharedEffects,
+    /// Source-time playhead (f64 bits) that resynthesis previews start at.
+    playhead: Arc<AtomicU64>,
+    analysis_cancel: Arc<AnalysisCancel>,
 }
 
 impl ProcessingHandle {
     /// Spawn the processing thread and return a handle.
     pub fn spawn() -> Self {
+        Self::spawn_with(AnalysisOptions::default(), SharedEffects::default())
+    }
+
+    /// Spawn the processing thread with non-default analysis settings,
+    /// reading effects parameters from `effects` (the UI publishes there).
+    pub fn spawn_with(options: AnalysisOptions, effects: SharedEffects) -> Self {
+        Self::spawn_sharing(
+            options,
+            effects,
+            Arc::new(AtomicU64::new(0.0_f64.to_bits())),
+        )
+    }
+
+    fn spawn_sharing(
+        options: AnalysisOptions,
+        effects: SharedEffects,
+        playhead: Arc<AtomicU64>,
+    ) -> Self {
         let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
         let (result_tx, result_rx) = crossbeam_channel::unbounded();
 
+        let analysis_cancel = Arc::new(AnalysisCancel::new());
+        let worker_effects = Arc::clone(&effects);
+        let worker_playhead = Arc::clone(&playhead);
+        let tokens = AnalysisTokens {
+            shared: Arc::clone(&analysis_cancel),
+            taken: 0,
+            refining: None,
+        };
         let thread = thread::spawn(move || {
-            processing_loop(cmd_rx, result_tx);
+            processing_loop(
+                cmd_rx,
+                result_tx,
+                options,
+                worker_effects,
+                worker_playhead,
+                tokens,
+            );
         });
 
         Self {
             cmd_tx,
             result_rx,
             thread: Some(thread),
+            options,
+            effects,
+            playhead,
+            analysis_cancel,
         }
     }
 
+    /// The effects parameters the worker processes with. Publish here before
+    /// sending `Resynthesize` / `ReapplyEffects`.
+    pub fn effects(&self) -> &SharedEffects {
+        &self.effects
+    }
+
+    /// Where the listener is, in source seconds. A resynthesis renders a
+    /// [`PreviewClip`] around the latest value first; the UI updates it
+    /// every tick.
+    pub fn set_playhead(&self, source_secs: f64) {
+        self.playhead
+            .store(source_secs.to_bits(), Ordering::Relaxed);
+    }
+
     /// Send a command to the processing thread.
+    ///
+    /// A `Load` or `Analyze` cancels the analysis in flight (and any still
+    /// queued), which then ends without a result; so does a `Decode`.
     pub fn send(&self, cmd: ProcessingCommand) {
+        match cmd {
+            ProcessingCommand::Load(_) | ProcessingCommand::Analyze(_) => {
+                self.analysis_cancel.supersede();
+            }
+            ProcessingCommand::Decode(_) | ProcessingCommand::SetAnalysisOptions(_) => {
+                self.analysis_cancel.cancel();
+            }
+            _ => {}
+        }
         let _ = self.cmd_tx.send(cmd);
     }
 
+    /// Analyze with `world` from now on, in this worker and any respawned
+    /// one. The worker drops its analysis (and stops one in flight); send
+    /// `Analyze` to redo it.
+    pub fn set_analysis(&mut self, world: AnalyzeOptions) {
+        self.options.world = world;
+        self.send(ProcessingCommand::SetAnalysisOptions(world));
+    }
+
     /// Try to receive a result without blocking.
     pub fn try_recv(&self) -> Option<ProcessingResult> {
         self.result_rx.try_recv().ok()
     }
 
+    /// Whether the processing thread is still running.
+    ///
+    /// `false` after a clean `Shutdown` or when the thread died — e.g. a panic
+    /// that escaped `catch_unwind` in `processing_loop`.
+    pub fn is_alive(&self) -> bool {
+        self.thread.as_ref().is_some_and(|t| !t.is_finished())
+    }
+
+    /// Replace a dead worker with a freshly spawned one.
+    ///
+    /// The new thread starts with empty caches (no WORLD params, no post-WORLD
+    /// audio), so the caller must re-send `Analyze` (or `Load`) for the current
+    /// file before resynthesis can work again. Results still queued from the old
+    /// thread are dropped along with its channels. The analysis options carry over.
+    pub fn respawn(&mut self) {
+        *self = Self::spawn_sharing(
+            self.options,
+            Arc::clone(&self.effects),
+            Arc::clone(&self.playhead),
+        );
+    }
 }
 
 impl Drop for ProcessingHandle {
@@ -80,91 +651,1079 @@ impl Drop for ProcessingHandle {
 }
 
 /// Run WORLD analysis and update cached state. Returns `true` on success.
+/// A cancelled analysis drops the caches and ends quietly: the command that
+/// cancelled it reports next.
+///
+/// Audio of at least [`AnalysisOptions::preview_from_secs`] gets the
+/// preview analysis installed instead, and the full one is left running in
+/// `refining`.
+#[allow(clippy::too_many_arguments)]
 fn run_analyze(
     audio: &AudioData,
     result_tx: &Sender<ProcessingResult>,
-    sample_rate: &mut u32,
+    options: AnalysisOptions,
+    cancel: &Arc<AtomicBool>,
+    refining: &mut Option<Refinement>,
+    format: &mut Option<AudioFormat>,
     cached_params: &mut Option<WorldParams>,
     original_mono: &mut Option<AudioData>,
-    post_world_audio: &mut Option<AudioData>,
+    post_world_audio: &mut Option<WorldRender>,
 ) -> bool {
-    *sample_rate = audio.sample_rate;
-    log::info!("analyze: {} samples @ {}Hz", audio.samples.len(), audio.sample_rate);
+    log::info!(
+        "analyze: {} samples @ {}Hz",
+        audio.samples.len(),
+        audio.sample_rate
+    );
+    *refining = None;
+    send_pitch_curve(audio, &options.world, cancel, result_tx);
+    let preview = options
+        .preview_from_secs
+        .is_some_and(|secs| audio.duration_secs() >= secs);
+    let (world_options, label) = if preview {
+        (
+            preview_analysis_options(&options.world),
+            "Previewing analysis...",
+        )
+    } else {
+        (options.world, "Analyzing...")
+    };
     let result_tx_clone = result_tx.clone();
-    match world::analyze_with_progress(audio, move |pct| {
-        let _ = result_tx_clone.send(ProcessingResult::Status(format!("Analyzing... {pct}%")));
+    let mut progress = ProgressTracker::new(label, audio.duration_secs());
+    match world::analyze_with_progress(audio, &world_options, cancel, move |pct| {
+        if let Some(status) = progress.update(pct) {
+            let _ = result_tx_clone.send(ProcessingResult::Status(status));
+        }
     }) {
+        Ok(params) if preview => {
+            log::info!(
+                "analyze: preview done — {} f0 frames, full analysis next",
+                params.f0.len()
+            );
+            let preview_f0 = params.f0.clone();
+            // The candidate lattice waits for the full analysis.
+            install_analysis(
+                audio,
+                params,
+                result_tx,
+                AnalysisOptions {
+                    f0_candidates: false,
+                    ..options
+                },
+                format,
+                cached_params,
+                original_mono,
+                post_world_audio,
+            );
+            *refining = Some(Refinement::spawn(
+                audio, options, cancel, preview_f0, result_tx,
+            ));
+            let _ = result_tx.send(ProcessingResult::Status(
+                "Preview ready — full analysis running...".into(),
+            ));
+            true
+        }
         Ok(params) => {
             log::info!("analyze: done — {} f0 frames", params.f0.len());
-            *cached_params = Some(params);
-            let mono = world::to_mono(audio);
-            *original_mono = Some(mono.clone());
-            *post_world_audio = Some(mono.clone());
-            let _ = result_tx.send(ProcessingResult::AnalysisDone(mono));
+            install_analysis(
+                audio,
+                params,
+                result_tx,
+                options,
+                format,
+                cached_params,
+                original_mono,
+                post_world_audio,
+            );
             true
         }
+        Err(WorldError::Cancelled) => {
+            log::info!("analyze: cancelled by a newer load");
+            clear_caches(format, cached_params, original_mono, post_world_audio);
+            false
+        }
         Err(e) => {
             log::error!("analyze: failed — {e}");
+            // Nothing cached may outlive a failed analysis: the UI has no
+            // analyzed audio now, so later effects must not run on the old file.
+            clear_caches(format, cached_params, original_mono, post_world_audio);
             let _ = result_tx.send(ProcessingResult::Status(format!("Analysis error: {e}")));
             false
         }
     }
 }
 
+/// Make `params` the analysis of `audio`: attach f0 candidates, reset the
+/// caches to the mono original and send `AnalysisDone` + `Classified`.
+#[allow(clippy::too_many_arguments)]
+fn install_analysis(
+    audio: &AudioData,
+    mut params: WorldParams,
+    result_tx: &Sender<ProcessingResult>,
+    options: AnalysisOptions,
+    format: &mut Option<AudioFormat>,
+    cached_params: &mut Option<WorldParams>,
+    original_mono: &mut Option<AudioData>,
+    post_world_audio: &mut Option<WorldRender>,
+) {
+    attach_f0_candidates(audio, &mut params, options);
+    let classified = classify_content(&params, audio.sample_rate);
+    *cached_params = Some(params);
+    let mono = world::to_mono(audio);
+    *format = Some(mono.format());
+    *original_mono = Some(mono.clone());
+    *post_world_audio = Some(WorldRender::original(mono.clone()));
+    let _ = result_tx.send(ProcessingResult::AnalysisDone(mono));
+    let _ = result_tx.send(ProcessingResult::Classified(classified));
+}
+
+/// Track the pitch of `audio` alone ([`world::pitch_curve`], at
+/// [`PREVIEW_DIO_SPEED`]) and send it, so the UI shows pitch while the
+/// analysis runs. Failures are left to the analysis to report, and a
+/// cancelled analysis sends nothing.
+fn send_pitch_curve(
+    audio: &AudioData,
+    world: &AnalyzeOptions,
+    cancel: &AtomicBool,
+    result_tx: &Sender<ProcessingResult>,
+) {
+    let options = AnalyzeOptions {
+        dio_speed: PREVIEW_DIO_SPEED,
+        ..*world
+    };
+    match world::pitch_curve(audio, &options) {
+        Ok(_) if cancel.load(Ordering::Relaxed) => {}
+        Ok(curve) => {
+            log::debug!("analyze: pitch curve of {} frames", curve.len());
+            let _ = result_tx.send(ProcessingResult::PitchCurve(curve));
+        }
+        Err(e) => log::debug!("analyze: no pitch curve — {e}"),
+    }
+}
+
+/// Take the full analysis a preview was waiting for. It replaces the
+/// preview only if nothing has cancelled or replaced that since; the UI
+/// then gets `AnalysisRefined` to resynthesize from it.
+#[allow(clippy::too_many_arguments)]
+fn finish_refinement(
+    refinement: Refinement,
+    refined: Result<(AudioData, Result<WorldParams, WorldError>), crossbeam_channel::RecvError>,
+    result_tx: &Sender<ProcessingResult>,
+    options: AnalysisOptions,
+    cached_params: &mut Option<WorldParams>,
+    original_mono: &Option<AudioData>,
+) {
+    let still_preview = cached_params
+        .as_ref()
+        .is_some_and(|p| p.f0 == refinement.preview_f0);
+    if refinement.cancel.load(Ordering::Relaxed) || !still_preview {
+        log::info!("analyze: full analysis no longer needed");
+        return;
+    }
+    match refined {
+        Ok((audio, Ok(mut params))) => {
+            log::info!(
+                "analyze: full analysis done — {} f0 frames replace the preview",
+                params.f0.len()
+            );
+            attach_f0_candidates(&audio, &mut params, options);
+            *cached_params = Some(params);
+            let _ = result_tx.send(ProcessingResult::Status("Full analysis ready".into()));
+            let _ = result_tx.send(ProcessingResult::AnalysisRefined);
+            if let Some(sidecar) = refinement.save_to {
+                run_save_params(
+                    &sidecar.to_string_lossy(),
+                    options,
+                    cached_params,
+                    original_mono,
+                    result_tx,
+                );
+            }
+        }
+        Ok((_, Err(e))) => {
+            log::error!("analyze: full analysis failed — {e}");
+            let _ = result_tx.send(ProcessingResult::Status(format!(
+                "Full analysis error: {e} (keeping the preview)"
+            )));
+        }
+        Err(_) => {
+            log::error!("analyze: full analysis thread died");
+            let _ = result_tx.send(ProcessingResult::Status(
+                "Full analysis failed (keeping the preview)".into(),
+            ));
+        }
+    }
+}
+
+/// Read the params file at `path` if it was analyzed from `mono` with the
+/// current settings.
+fn load_matching_params(
+    path: &Path,
+    mono: &AudioData,
+    options: AnalysisOptions,
+) -> Result<WorldParams, WorldError> {
+    if world_sys::params_source_hash(path)? != analysis_key(mono, &options.world) {
+        return Err(WorldError::File(format!(
+            "{}: analyzed from other audio or settings",
+            path.display(),
+        )));
+    }
+    WorldParams::load(path)
+}
+
+/// The analysis cached in `sidecar` for `audio`, if there is a matching one.
+/// A missing, stale or unreadable sidecar just means analyzing again.
+fn cached_analysis(
+    sidecar: &Path,
+    audio: &AudioData,
+    options: AnalysisOptions,
+) -> Option<WorldParams> {
+    if !sidecar.exists() {
+        return None;
+    }
+    match load_matching_params(sidecar, &world::to_mono(audio), options) {
+        Ok(params) => {
+            log::info!(
+                "load: analysis read from {} — {} f0 frames",
+                sidecar.display(),
+                params.f0.len()
+            );
+            Some(params)
+        }
+        Err(e) => {
+            log::info!("load: not using cached analysis — {e}");
+            None
+        }
+    }
+}
+
+/// Write the current analysis to `path`, tagged with the audio it came from.
+fn run_save_params(
+    path: &str,
+    options: AnalysisOptions,
+    cached_params: &Option<WorldParams>,
+    original_mono: &Option<AudioData>,
+    result_tx: &Sender<ProcessingResult>,
+) {
+    let (Some(params), Some(mono)) = (cached_params, original_mono) else {
+        let _ = result_tx.send(ProcessingResult::Status("No analysis to save".into()));
+        return;
+    };
+    match params.save_with_source(Path::new(path), analysis_key(mono, &options.world)) {
+        Ok(()) => {
+            log::info!("params: saved {} frames to {path}", params.f0.len());
+            let _ = result_tx.send(ProcessingResult::Status(format!(
+                "Analysis saved to {path}"
+            )));
+        }
+        Err(e) => {
+            log::error!("params: save failed — {e}");
+            let _ = result_tx.send(ProcessingResult::Status(format!("Save error: {e}")));
+        }
+    }
+}
+
+/// Replace the analysis with the params file at `path`, which must have
+/// been saved from the audio currently loaded. On any error the current
+/// analysis stays.
+fn run_load_params(
+    path: &str,
+    result_tx: &Sender<ProcessingResult>,
+    options: AnalysisOptions,
+    format: &mut Option<AudioFormat>,
+    cached_params: &mut Option<WorldParams>,
+    original_mono: &mut Option<AudioData>,
+    post_world_audio: &mut Option<WorldRender>,
+) {
+    let Some(mono) = original_mono.clone() else {
+        let _ = result_tx.send(ProcessingResult::Status(
+            "Load audio before its analysis".into(),
+        ));
+        return;
+    };
+    match load_matching_params(Path::new(path), &mono, options) {
+        Ok(params) => {
+            log::info!("params: loaded {} frames from {path}", params.f0.len());
+            install_analysis(
+                &mono,
+                params,
+                result_tx,
+                options,
+                format,
+                cached_params,
+                original_mono,
+                post_world_audio,
+            );
+        }
+        Err(e) => {
+            log::error!("params: load failed — {e}");
+            let _ = result_tx.send(ProcessingResult::Status(format!(
+                "Analysis load error: {e}"
+            )));
+        }
+    }
+}
+
+/// Blend the cached analysis with one of the file at `path` by `alpha`
+/// ([`world_sys::morph`]) and synthesize the blend with the latest
+/// effects. The file is brought to the session rate and analyzed with the
+/// same settings on every call. The blend becomes the post-WORLD render,
+/// so `ReapplyEffects` keeps it until the next `Resynthesize`.
+#[allow(clippy::too_many_arguments)]
+fn run_morph(
+    path: &str,
+    alpha: f64,
+    options: AnalysisOptions,
+    effects: &Published<EffectsParams>,
+    scratch: &mut Vec<f64>,
+    result_tx: &Sender<ProcessingResult>,
+    cached_params: &Option<WorldParams>,
+    original_mono: &Option<AudioData>,
+    format: Option<AudioFormat>,
+    post_world_audio: &mut Option<WorldRender>,
+) {
+    let (Some(params), Some(format)) = (cached_params.as_ref(), format) else {
+        let _ = result_tx.send(ProcessingResult::Status(
+            "Load audio before morphing".into(),
+        ));
+        return;
+    };
+    log::info!("morph: {alpha:.2} towards {path}");
+    let _ = result_tx.send(ProcessingResult::Status("Analyzing morph target...".into()));
+    let outcome = decoder::decode_file(Path::new(path))
+        .map_err(|e| e.to_string())
+        .and_then(|audio| {
+            let mono = world::to_mono(&edit::resample(&audio, format.sample_rate));
+            world::analyze_with_progress(&mono, &options.world, &AtomicBool::new(false), |_| {})
+                .map_err(|e| e.to_string())
+        })
+        .and_then(|target| world_sys::morph(params, &target, alpha).map_err(|e| e.to_string()))
+        .and_then(|blend| {
+            let _ = result_tx.send(ProcessingResult::Status("Synthesizing morph...".into()));
+            world::synthesize(&blend, format.sample_rate, Some(scratch)).map_err(|e| e.to_string())
+        });
+    match outcome {
+        Ok(audio) => {
+            let processed = apply_fx_chain(
+                &audio,
+                &load_effects(effects, 0),
+                format,
+                original_mono.as_ref(),
+            );
+            *post_world_audio = Some(WorldRender::original(audio));
+            match processed {
+                Ok(final_audio) => send_synthesis_done(final_audio, original_mono, result_tx),
+                Err(e) => send_format_error(&e, result_tx),
+            }
+        }
+        Err(e) => {
+            log::error!("morph: failed — {e}");
+            let _ = result_tx.send(ProcessingResult::Status(format!("Morph failed: {e}")));
+        }
+    }
+}
+
+/// Render the cached analysis gliding from preset `a` to preset `b` over
+/// `crossfade` source seconds ([`modifier::apply_crossfade`]) with the
+/// latest effects. Like a morph, the render becomes the post-WORLD audio,
+/// so `ReapplyEffects` keeps it until the next `Resynthesize`.
+#[allow(clippy::too_many_arguments)]
+fn run_resynthesize_morph(
+    a: &WorldSliderValues,
+    b: &WorldSliderValues,
+    crossfade: (f64, f64),
+    effects: &Published<EffectsParams>,
+    scratch: &mut Vec<f64>,
+    result_tx: &Sender<ProcessingResult>,
+    cached_params: &Option<WorldParams>,
+    original_mono: &Option<AudioData>,
+    format: Option<AudioFormat>,
+    post_world_audio: &mut Option<WorldRender>,
+) {
+    let (Some(params), Some(format)) = (cached_params.as_ref(), format) else {
+        let _ = result_tx.send(ProcessingResult::Status(
+            "Load audio before crossfading presets".into(),
+        ));
+        return;
+    };
+    log::info!(
+        "crossfade: presets over {:.2}..{:.2}s",
+        crossfade.0,
+        crossfade.1
+    );
+    let _ = result_tx.send(ProcessingResult::Status(
+        "Synthesizing preset crossfade...".into(),
+    ));
+    let modified = modifier::apply_crossfade(params, a, b, crossfade);
+    match world::synthesize(&modified, format.sample_rate, Some(scratch)) {
+        Ok(audio) => {
+            let processed = apply_fx_chain(
+                &audio,
+                &load_effects(effects, 0),
+                format,
+                original_mono.as_ref(),
+            );
+            *post_world_audio = Some(WorldRender::original(audio));
+            match processed {
+                Ok(final_audio) => send_synthesis_done(final_audio, original_mono, result_tx),
+                Err(e) => send_format_error(&e, result_tx),
+            }
+        }
+        Err(e) => {
+            log::error!("crossfade: failed — {e}");
+            let _ = result_tx.send(ProcessingResult::Status(format!("Crossfade failed: {e}")));
+        }
+    }
+}
+
+/// Classify a fresh analysis as speech / singing / other.
+fn classify_content(params: &WorldParams, sample_rate: u32) -> Classification {
+    let result = classify::classify(&ContentFeatures::from_params(params, sample_rate));
+    log::info!(
+        "analyze: content {} ({:?})",
+        result.describe(),
+        result.features
+    );
+    result
+}
+
+/// Fill the f0 candidate lattice when extended analysis is on. A failure
+/// only costs the correction feature, so it is logged, not reported.
+fn attach_f0_candidates(audio: &AudioData, params: &mut WorldParams, options: AnalysisOptions) {
+    if !options.f0_candidates {
+        return;
+    }
+    if let Err(e) = f0_candidates::attach(audio, params) {
+        log::warn!("analyze: f0 candidates unavailable — {e}");
+    }
+}
+
+/// Samples per block when diffing two versions of the audio.
+const DIFF_BLOCK: usize = 1024;
+
+/// Re-analyze only what changed when the span is at most this fraction of the file.
+const INCREMENTAL_MAX_FRACTION: f64 = 0.5;
+
+/// Span (seconds) where `new` differs from `old`, found by comparing
+/// per-block hashes of the samples. `None` when identical; the whole of
+/// `new` when the length, channel count or sample rate changed.
+pub fn changed_region(old: &AudioData, new: &AudioData) -> Option<(f64, f64)> {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    if old.sample_rate != new.sample_rate
+        || old.channels != new.channels
+        || old.samples.len() != new.samples.len()
+    {
+        return Some((0.0, new.duration_secs()));
+    }
+    let block_hash = |block: &[f32]| {
+        let mut hasher = DefaultHasher::new();
+        for s in block {
+            s.to_bits().hash(&mut hasher);
+        }
+        hasher.finish()
+    };
+    let mut changed = old
+        .samples
+        .chunks(DIFF_BLOCK)
+        .zip(new.samples.chunks(DIFF_BLOCK))
+        .enumerate()
+        .filter(|(_, (a, b))| block_hash(a) != block_hash(b))
+        .map(|(i, _)| i);
+    let first = changed.next()?;
+    let last = changed.next_back().unwrap_or(first);
+
+    let per_sec = new.sample_rate as f64 * new.channels.max(1) as f64;
+    let start = (first * DIFF_BLOCK) as f64 / per_sec;
+    let end = (((last + 1) * DIFF_BLOCK).min(new.samples.len())) as f64 / per_sec;
+    Some((start, end))
+}
+
+/// Analyze a new version of the current audio, re-running WORLD only on the
+/// changed span when the cached analysis is for a same-length version.
+/// Falls back to a full analysis otherwise.
+#[allow(clippy::too_many_arguments)]
+fn run_reanalyze(
+    audio: &AudioData,
+    result_tx: &Sender<ProcessingResult>,
+    options: AnalysisOptions,
+    tokens: &mut AnalysisTokens,
+    format: &mut Option<AudioFormat>,
+    cached_params: &mut Option<WorldParams>,
+    original_mono: &mut Option<AudioData>,
+    post_world_audio: &mut Option<WorldRender>,
+) -> bool {
+    // A preview can't be kept or patched: this command cancelled the full
+    // analysis it was waiting for.
+    let previewing = tokens.refining.is_some();
+    let cancel = tokens.next();
+    let mono = world::to_mono(audio);
+    if let (Some(params), Some(old), false) =
+        (cached_params.as_mut(), original_mono.as_ref(), previewing)
+    {
+        match changed_region(old, &mono) {
+            None => {
+                log::info!("reanalyze: audio unchanged — keeping analysis");
+                let _ = result_tx.send(ProcessingResult::AnalysisDone(mono));
+                return true;
+            }
+            Some((start, end))
+                if end - start <= INCREMENTAL_MAX_FRACTION * mono.duration_secs() =>
+            {
+                log::info!("reanalyze: region {start:.2}..{end:.2}s");
+                match world::reanalyze_region(&mono, params, start, end, &options.world) {
+                    Ok(()) => {
+                        attach_f0_candidates(&mono, params, options);
+                        let classified = classify_content(params, mono.sample_rate);
+                        *original_mono = Some(mono.clone());
+                        *post_world_audio = Some(WorldRender::original(mono.clone()));
+                        let _ = result_tx.send(ProcessingResult::AnalysisDone(mono));
+                        let _ = result_tx.send(ProcessingResult::Classified(classified));
+                        return true;
+                    }
+                    Err(e) => log::warn!("reanalyze: incremental path failed ({e}), running full"),
+                }
+            }
+            Some(_) => {}
+        }
+    }
+    run_analyze(
+        audio,
+        result_tx,
+        options,
+        &cancel,
+        &mut tokens.refining,
+        format,
+        cached_params,
+        original_mono,
+        post_world_audio,
+    )
+}
+
+/// The WORLD output the effects run on, and what it was synthesized from;
+/// `from` is `None` when it isn't a render of slider values: the
+/// unprocessed original (a fresh analysis, or a neutral or bypassed
+/// render) or a morph.
+struct WorldRender {
+    audio: AudioData,
+    from: Option<RenderedFrom>,
+    /// Peak normalization applied to `audio`; segments spliced into it get
+    /// the same.
+    gain: f32,
+}
+
+/// The slider values and the cached f0 and frame period of a render.
+struct RenderedFrom {
+    values: WorldSliderValues,
+    f0: Vec<f64>,
+    frame_period: f64,
+}
+
+impl WorldRender {
+    fn original(audio: AudioData) -> Self {
+        Self {
+            audio,
+            from: None,
+            gain: 1.0,
+        }
+    }
+}
+
+/// How much of the last render a resynthesis can keep.
+enum RenderChange {
+    /// All of it: the sliders' stages and the cached analysis are as they
+    /// were, so only the effects changed.
+    Nothing,
+    /// All but these frames, whose f0 changed (an f0 correction).
+    Frames(Range<usize>),
+    /// None of it.
+    All,
+}
+
+/// Compare the last render with what `values` on `params` would render:
+/// any dirty slider stage ([`DirtyStages`]) or another analysis means all
+/// of it, changed f0 rows only those frames (at speed 1, with a render of
+/// the whole contour to splice into).
+fn render_change(
+    params: &WorldParams,
+    values: &WorldSliderValues,
+    previous: Option<&WorldRender>,
+    expected: AudioFormat,
+) -> RenderChange {
+    let Some(WorldRender {
+        audio,
+        from: Some(from),
+        ..
+    }) = previous
+    else {
+        return RenderChange::All;
+    };
+    if DirtyStages::between(&from.values, values).any()
+        || from.frame_period != params.frame_period
+        || from.f0.len() != params.f0.len()
+        || audio.format() != expected
+    {
+        return RenderChange::All;
+    }
+    match modifier::changed_frames(&from.f0, &params.f0) {
+        None => RenderChange::Nothing,
+        Some(frames)
+            if !values.is_stretched()
+                && audio.frame_count() == rendered_frames(params, expected.sample_rate) =>
+        {
+            RenderChange::Frames(frames)
+        }
+        Some(_) => RenderChange::All,
+    }
+}
+
 /// Run resynthesis with given WORLD and effects params. Returns the result audio or None.
+/// Long files get a [`PreviewClip`] around `playhead_secs` first. With a
+/// `range`, only that span is resynthesized into the last render where it
+/// can be ([`synthesize_region`]); effects always run over the whole buffer.
+/// Otherwise the last render is kept as far as [`render_change`] allows:
+/// an effects-only change never reaches WORLD synthesis. A full render is
+/// modified into `params_scratch` ([`modifier::apply_into`]), kept by the
+/// worker across renders, and peak-normalized when `normalize` is set.
+#[allow(clippy::too_many_arguments)]
 fn run_resynthesize(
     latest_world: &WorldSliderValues,
     latest_fx: &EffectsParams,
+    range: Option<Range<f64>>,
+    normalize: bool,
     cached_params: &Option<WorldParams>,
     original_mono: &Option<AudioData>,
-    post_world_audio: &mut Option<AudioData>,
-    sample_rate: u32,
+    post_world_audio: &mut Option<WorldRender>,
+    expected: AudioFormat,
+    playhead_secs: f64,
+    scratch: &mut Vec<f64>,
+    params_scratch: &mut WorldParams,
     result_tx: &Sender<ProcessingResult>,
 ) -> bool {
     log::debug!("resynthesize: starting");
+    // The last render, taken out while this one is made. Every early return
+    // after that puts it back, so a failed render never costs the next
+    // ReapplyEffects its input.
+    let mut previous = None;
     let world_audio = if latest_world.bypass || latest_world.is_neutral() {
         if let Some(ref mono) = original_mono {
-            mono.clone()
+            WorldRender::original(mono.clone())
         } else {
             return false;
         }
     } else {
         // Stage 1: Modify parameters
-        let _ = result_tx.send(ProcessingResult::Status("Modifying parameters... (1/3)".into()));
+        let _ = result_tx.send(ProcessingResult::Status(
+            "Modifying parameters... (1/3)".into(),
+        ));
         // M-11: Use if-let instead of unwrap() for structural safety.
         let params = match cached_params.as_ref() {
             Some(p) => p,
             None => return false,
         };
-        let modified = modifier::apply(params, latest_world);
+        previous = post_world_audio.take();
+        let previous_gain = previous.as_ref().map_or(1.0, |r| r.gain);
+        let change = render_change(params, latest_world, previous.as_ref(), expected);
+        let kept = match (range, change) {
+            (Some(range), _) => {
+                let _ = result_tx.send(ProcessingResult::Status(
+                    "Synthesizing region... (2/3)".into(),
+                ));
+                synthesize_region(
+                    params,
+                    latest_world,
+                    range,
+                    previous.as_ref(),
+                    expected,
+                    scratch,
+                )
+            }
+            (None, RenderChange::Nothing) => {
+                log::debug!("resynthesize: WORLD stages unchanged — reusing the last render");
+                previous.take().map(|r| r.audio)
+            }
+            (None, RenderChange::Frames(frames)) => {
+                let _ = result_tx.send(ProcessingResult::Status(
+                    "Synthesizing changed frames... (2/3)".into(),
+                ));
+                previous.as_ref().and_then(|base| {
+                    synthesize_frames(params, latest_world, frames, base, expected, scratch)
+                })
+            }
+            (None, RenderChange::All) => None,
+        };
+        let from = Some(RenderedFrom {
+            values: latest_world.clone(),
+            f0: params.f0.clone(),
+            frame_period: params.frame_period,
+        });
+        if let Some(audio) = kept {
+            WorldRender {
+                audio,
+                from,
+                gain: previous_gain,
+            }
+        } else {
+            if let Some(clip) =
+                render_preview(params, latest_world, latest_fx, playhead_secs, expected)
+            {
+                let _ = result_tx.send(ProcessingResult::PreviewReady(clip));
+            }
+            modifier::apply_into(params, latest_world, params_scratch);
 
-        // Stage 2: Synthesize voice
-        let _ = result_tx.send(ProcessingResult::Status("Synthesizing voice... (2/3)".into()));
-        match world::synthesize(&modified, sample_rate) {
-            Ok(audio) => audio,
-            Err(e) => {
-                log::error!("resynthesize: failed — {e}");
-                let _ = result_tx.send(ProcessingResult::Status(format!("Synthesis error: {e}")));
-                return false;
+            // Stage 2: Synthesize voice
+            let _ = result_tx.send(ProcessingResult::Status(
+                "Synthesizing voice... (2/3)".into(),
+            ));
+            let synthesized = if normalize {
+                world::synthesize_normalized(params_scratch, expected.sample_rate, Some(scratch))
+            } else {
+                world::synthesize(params_scratch, expected.sample_rate, Some(scratch))
+                    .map(|audio| (audio, 1.0))
+            };
+            match synthesized {
+                Ok((audio, gain)) => WorldRender {
+                    audio,
+                    from,
+                    gain: gain as f32,
+                },
+                Err(e) => {
+                    log::error!("resynthesize: failed — {e}");
+                    let _ =
+                        result_tx.send(ProcessingResult::Status(format!("Synthesis error: {e}")));
+                    *post_world_audio = previous;
+                    return false;
+                }
             }
         }
     };
+    if let Err(e) = expected.check(world_audio.audio.format(), "synthesis") {
+        send_format_error(&e, result_tx);
+        if previous.is_some() {
+            *post_world_audio = previous;
+        }
+        return false;
+    }
 
     // Stage 3: Apply effects
     let _ = result_tx.send(ProcessingResult::Status("Applying effects... (3/3)".into()));
-    *post_world_audio = Some(world_audio.clone());
-    let final_audio = apply_fx_chain(&world_audio, latest_fx);
+    let processed = apply_fx_chain(
+        &world_audio.audio,
+        latest_fx,
+        expected,
+        original_mono.as_ref(),
+    );
+    let normalized = (world_audio.gain < 1.0).then_some(world_audio.gain);
+    *post_world_audio = Some(world_audio);
+    match processed {
+        Ok(final_audio) => {
+            send_synthesis_done(final_audio, original_mono, result_tx);
+            if let Some(gain) = normalized {
+                let db = 20.0 * gain.log10();
+                log::info!("resynthesize: normalized {db:.1} dB");
+                let _ = result_tx.send(ProcessingResult::Status(format!(
+                    "Normalized {db:.1} dB to avoid clipping"
+                )));
+            }
+            true
+        }
+        Err(e) => {
+            send_format_error(&e, result_tx);
+            false
+        }
+    }
+}
+
+/// WORLD and effects for the [`PREVIEW_SECS`] around `playhead_secs`, or
+/// `None` when the file is too short to need a preview, the speed is not 1,
+/// or rendering fails (the full render reports that).
+fn render_preview(
+    params: &WorldParams,
+    values: &WorldSliderValues,
+    fx: &EffectsParams,
+    playhead_secs: f64,
+    expected: AudioFormat,
+) -> Option<PreviewClip> {
+    let frames = params.f0.len();
+    let frame_secs = params.frame_period / 1000.0;
+    let window = (PREVIEW_SECS / frame_secs).round() as usize;
+    if values.is_stretched() || window < 2 || frames < 2 * window || !playhead_secs.is_finite() {
+        return None;
+    }
+    let first =
+        (((playhead_secs - PREVIEW_LEAD_SECS) / frame_secs).max(0.0) as usize).min(frames - window);
+    let modified = modifier::apply_window(params, values, first..first + window);
+    let audio = match world::synthesize_streaming(&modified, expected.sample_rate) {
+        Ok(audio) => audio,
+        Err(e) => {
+            log::debug!("preview: skipped — {e}");
+            return None;
+        }
+    };
+    // A window's loudness isn't the whole file's, so the clip is never
+    // loudness-matched; the full render that follows it is.
+    let audio = apply_fx_chain(&audio, fx, expected, None).ok()?.audio;
+    Some(PreviewClip {
+        audio,
+        start: (first as f64 * frame_secs * f64::from(expected.sample_rate)).round() as usize,
+        // The full render carries the delay's tail too.
+        total_frames: rendered_frames(params, expected.sample_rate)
+            + fx.tail_frames(expected.sample_rate),
+    })
+}
+
+/// WORLD's output length for the whole contour (see world_sys::synthesize).
+fn rendered_frames(params: &WorldParams, sample_rate: u32) -> usize {
+    ((params.f0.len() as f64 - 1.0) * params.frame_period / 1000.0 * f64::from(sample_rate) + 1.0)
+        as usize
+}
+
+/// `post_world` with `range` (source seconds) resynthesized on its own
+/// ([`WorldParams::slice`]) and crossfaded in, or `None` when that can't
+/// stand in for a full render: no previous render of the whole contour in
+/// `expected`, a speed other than 1, or a range without frames. The f0
+/// stages only see the region's contour.
+fn synthesize_region(
+    params: &WorldParams,
+    values: &WorldSliderValues,
+    range: Range<f64>,
+    post_world: Option<&WorldRender>,
+    expected: AudioFormat,
+    scratch: &mut Vec<f64>,
+) -> Option<AudioData> {
+    let base = post_world?;
+    let total = rendered_frames(params, expected.sample_rate);
+    if values.is_stretched() || base.audio.format() != expected || base.audio.frame_count() != total
+    {
+        return None;
+    }
+    let segment = match params.slice(range.start, range.end) {
+        Ok(segment) => segment,
+        Err(e) => {
+            log::debug!("region: full render instead — {e}");
+            return None;
+        }
+    };
+    // Where the segment's first frame sits in the whole contour.
+    let origin = params
+        .temporal_positions
+        .iter()
+        .copied()
+        .find(|&t| t >= range.start - world_sys::SLICE_TOLERANCE_SECS)?;
+    log::debug!(
+        "region: {:.2}..{:.2}s, {} frames",
+        range.start,
+        range.end,
+        segment.f0.len()
+    );
+    // The slice's positions start at 0; move a slider region and the
+    // automation along.
+    let values = WorldSliderValues {
+        region: values
+            .region
+            .map(|(start, end)| (start - origin, end - origin)),
+        automation: values.automation.shifted(origin),
+        ..values.clone()
+    };
+    splice_segment(
+        &modifier::apply(&segment, &values),
+        origin,
+        base,
+        expected,
+        scratch,
+    )
+}
+
+/// `base` (a render of the whole contour at speed 1) with `frames`
+/// resynthesized: the f0 stages see the whole contour as in a full render,
+/// and the window reaches far enough past the changed frames for the
+/// crossfades to join frames that didn't change.
+fn synthesize_frames(
+    params: &WorldParams,
+    values: &WorldSliderValues,
+    frames: Range<usize>,
+    base: &WorldRender,
+    expected: AudioFormat,
+    scratch: &mut Vec<f64>,
+) -> Option<AudioData> {
+    let margin = (2.0 * REGION_FADE_SECS / (params.frame_period / 1000.0)).ceil() as usize;
+    let window = frames.start.saturating_sub(margin)..(frames.end + margin).min(params.f0.len());
+    log::debug!("region: frames {window:?} changed since the last render");
+    let origin = params.temporal_positions[window.start];
+    splice_segment(
+        &modifier::apply_window(params, values, window),
+        origin,
+        base,
+        expected,
+        scratch,
+    )
+}
+
+/// Synthesize `segment` at `base`'s gain and crossfade it into `base` from
+/// `origin_secs` on; `None` (logged) if synthesis fails.
+fn splice_segment(
+    segment: &WorldParams,
+    origin_secs: f64,
+    base: &WorldRender,
+    expected: AudioFormat,
+    scratch: &mut Vec<f64>,
+) -> Option<AudioData> {
+    let mut audio = match world::synthesize(segment, expected.sample_rate, Some(scratch)) {
+        Ok(audio) => audio,
+        Err(e) => {
+            log::debug!("region: full render instead — {e}");
+            return None;
+        }
+    };
+    audio.samples.iter_mut().for_each(|s| *s *= base.gain);
+    let (base, total) = (&base.audio, base.audio.frame_count());
+    let sample_rate = f64::from(expected.sample_rate);
+    let start = ((origin_secs * sample_rate).round() as usize).min(total.saturating_sub(1));
+    edit::splice(
+        base,
+        &audio,
+        &Splice {
+            start,
+            end: (start + audio.frame_count()).min(total),
+            fade: (REGION_FADE_SECS * sample_rate) as usize,
+            mode: edit::LengthMode::Fit,
+        },
+    )
+    .ok()
+}
+
+/// The span a coalesced resynthesis has to cover: everything if either
+/// command asked for everything, else both ranges.
+fn merge_ranges(a: Option<Range<f64>>, b: Option<Range<f64>>) -> Option<Range<f64>> {
+    let (a, b) = (a?, b?);
+    Some(a.start.min(b.start)..a.end.max(b.end))
+}
+
+/// The latest published effects. The UI publishes before it sends, so this
+/// is never older than `requested`, the newest revision named by a command.
+fn load_effects(effects: &Published<EffectsParams>, requested: u64) -> Arc<EffectsParams> {
+    let (revision, params) = effects.load();
+    if revision < requested {
+        log::warn!("effects: revision {requested} requested, only {revision} published");
+    }
+    log::debug!("effects: processing with revision {revision} (requested {requested})");
+    params
+}
+
+/// Check the format the UI expects against the cached analysis (`None`
+/// when there is none). Reports a "Format error" status on mismatch.
+fn expect_format(
+    expected: AudioFormat,
+    cached: Option<AudioFormat>,
+    result_tx: &Sender<ProcessingResult>,
+) -> bool {
+    let checked = match cached {
+        Some(actual) => expected.check(actual, "analysis"),
+        None => Err(format!("no analyzed audio (expected {expected})")),
+    };
+    match checked {
+        Ok(()) => true,
+        Err(e) => {
+            send_format_error(&e, result_tx);
+            false
+        }
+    }
+}
+
+fn send_format_error(error: &str, result_tx: &Sender<ProcessingResult>) {
+    log::error!("format check failed — {error}");
+    let _ = result_tx.send(ProcessingResult::Status(format!("Format error: {error}")));
+}
+
+/// Drop everything derived from the current file.
+fn clear_caches(
+    format: &mut Option<AudioFormat>,
+    cached_params: &mut Option<WorldParams>,
+    original_mono: &mut Option<AudioData>,
+    post_world_audio: &mut Option<WorldRender>,
+) {
+    *format = None;
+    *cached_params = None;
+    *original_mono = None;
+    *post_world_audio = None;
+}
+
+/// Send a processed buffer, followed by its loudness next to the original's
+/// (and the loudness-match trim it got, if any).
+/// Measured here because both buffers already live on the worker.
+fn send_synthesis_done(
+    processed: FxOutput,
+    original_mono: &Option<AudioData>,
+    result_tx: &Sender<ProcessingResult>,
+) {
+    let FxOutput {
+        audio: final_audio,
+        trim_db,
+        normalization,
+    } = processed;
+    if let Some(LufsNormalization {
+        measured_lufs,
+        gain_db,
+    }) = normalization
+    {
+        log::info!("effects: measured {measured_lufs:.1} LUFS, normalized {gain_db:+.1} dB");
+        let _ = result_tx.send(ProcessingResult::Status(format!(
+            "Loudness: measured {measured_lufs:.1} LUFS, applied {gain_db:+.1} dB"
+        )));
+    }
+    if let Some(trim_db) = trim_db {
+        log::info!("effects: loudness matched to A, trim {trim_db:+.1} dB");
+        let _ = result_tx.send(ProcessingResult::Status(format!(
+            "Loudness matched to A: trim {trim_db:+.1} dB"
+        )));
+    }
+    let measured = AbLoudness {
+        original_db: original_mono
+            .as_ref()
+            .map(|a| loudness::rms_dbfs(&a.samples)),
+        processed_db: loudness::rms_dbfs(&final_audio.samples),
+    };
     let _ = result_tx.send(ProcessingResult::SynthesisDone(final_audio));
-    true
+    let _ = result_tx.send(ProcessingResult::Loudness(measured));
 }
 
-fn processing_loop(cmd_rx: Receiver<ProcessingCommand>, result_tx: Sender<ProcessingResult>) {
+fn processing_loop(
+    cmd_rx: Receiver<ProcessingCommand>,
+    result_tx: Sender<ProcessingResult>,
+    mut options: AnalysisOptions,
+    effects: SharedEffects,
+    playhead: Arc<AtomicU64>,
+    mut tokens: AnalysisTokens,
+) {
     let mut cached_params: Option<WorldParams> = None;
     let mut original_mono: Option<AudioData> = None;
-    let mut post_world_audio: Option<AudioData> = None;
-    let mut sample_rate: u32 = 0;
+    let mut post_world_audio: Option<WorldRender> = None;
+    let mut format: Option<AudioFormat> = None;
+    // WORLD's output buffer, reused by every resynthesis.
+    let mut scratch: Vec<f64> = Vec::new();
+    // The modified parameters, reused by every full render
+    // (`modifier::apply_into`).
+    let mut params_scratch = WorldParams::default();
 
-    while let Ok(cmd) = cmd_rx.recv() {
+    loop {
+        // While a preview is installed, its full analysis may finish between
+        // commands.
+        let refined_rx = tokens.refining.as_ref().map(|r| r.result_rx.clone());
+        let cmd = match refined_rx {
+            Some(refined_rx) => crossbeam_channel::select! {
+                recv(cmd_rx) -> cmd => cmd,
+                recv(refined_rx) -> refined => {
+                    if let Some(refinement) = tokens.refining.take() {
+                        finish_refinement(refinement, refined, &result_tx, options, &mut cached_params, &original_mono);
+                    }
+                    continue;
+                }
+            },
+            None => cmd_rx.recv(),
+        };
+        let Ok(cmd) = cmd else {
+            return;
+        };
         // CR-1: Wrap each command in catch_unwind so a panic sends an error
         // status instead of silently killing the processing thread.
         let result_tx_panic = result_tx.clone();
@@ -173,10 +1732,16 @@ fn processing_loop(cmd_rx: Receiver<ProcessingCommand>, result_tx: Sender<Proces
                 cmd,
                 &cmd_rx,
                 &result_tx,
+                &mut options,
+                &effects,
+                &playhead,
+                &mut tokens,
+                &mut scratch,
+                &mut params_scratch,
                 &mut cached_params,
                 &mut original_mono,
                 &mut post_world_audio,
-                &mut sample_rate,
+                &mut format,
             )
         }));
 
@@ -196,30 +1761,57 @@ fn processing_loop(cmd_rx: Receiver<ProcessingCommand>, result_tx: Sender<Proces
                 };
                 log::error!("processing thread caught panic: {msg}");
                 let _ = result_tx_panic.send(ProcessingResult::Status(msg));
-                cached_params = None;
-                original_mono = None;
-                post_world_audio = None;
+                clear_caches(
+                    &mut format,
+                    &mut cached_params,
+                    &mut original_mono,
+                    &mut post_world_audio,
+                );
+                // Resynthesis has nothing to work from until the UI re-sends
+                // `Analyze`; tell it rather than failing every slider move.
+                let _ = result_tx_panic.send(ProcessingResult::AnalysisInvalidated);
             }
         }
     }
 }
 
 /// Handle a single processing command. Returns `true` if the thread should exit.
+// The worker's caches are separate locals so each helper borrows only what it uses.
+#[allow(clippy::too_many_arguments)]
 fn handle_command(
     cmd: ProcessingCommand,
     cmd_rx: &Receiver<ProcessingCommand>,
     result_tx: &Sender<ProcessingResult>,
+    options: &mut AnalysisOptions,
+    effects: &Published<EffectsParams>,
+    playhead: &AtomicU64,
+    tokens: &mut AnalysisTokens,
+    scratch: &mut Vec<f64>,
+    params_scratch: &mut WorldParams,
     cached_params: &mut Option<WorldParams>,
     original_mono: &mut Option<AudioData>,
-    post_world_audio: &mut Option<AudioData>,
-    sample_rate: &mut u32,
+    post_world_audio: &mut Option<WorldRender>,
+    format: &mut Option<AudioFormat>,
 ) -> bool {
+    let mut batch = None;
     match cmd {
         ProcessingCommand::Load(path) => {
             run_load_file(
                 path,
                 result_tx,
-                sample_rate,
+                *options,
+                tokens,
+                format,
+                cached_params,
+                original_mono,
+                post_world_audio,
+            );
+        }
+        ProcessingCommand::Decode(path) => {
+            run_decode_file(
+                path,
+                result_tx,
+                format,
                 cached_params,
                 original_mono,
                 post_world_audio,
@@ -229,186 +1821,432 @@ fn handle_command(
             let entries = scan_directory_entries(&prefix);
             let _ = result_tx.send(ProcessingResult::DirectoryListing(prefix, entries));
         }
-        ProcessingCommand::PrecheckAudio(path) => {
-            match precheck_audio_file(&path) {
-                Ok(()) => {
-                    let _ = result_tx.send(ProcessingResult::AudioPrecheckDone(path));
-                }
-                Err(e) => {
-                    let _ = result_tx.send(ProcessingResult::AudioPrecheckFailed(path, e));
-                }
+        ProcessingCommand::PrecheckAudio(path) => match precheck_audio_file(&path) {
+            Ok(()) => {
+                let _ = result_tx.send(ProcessingResult::AudioPrecheckDone(path));
             }
-        }
-        ProcessingCommand::Resynthesize(values, fx_params) => {
+            Err(e) => {
+                let _ = result_tx.send(ProcessingResult::AudioPrecheckFailed(path, e));
+            }
+        },
+        ProcessingCommand::Resynthesize(values, fx_revision, expected, range) => {
             if cached_params.is_none() {
+                expect_format(expected, *format, result_tx);
                 return false;
             }
+            batch = Some(Batch {
+                world: Some((values, range)),
+                fx_revision,
+                expected,
+            });
+        }
+        ProcessingCommand::ReapplyEffects(fx_revision, expected) => {
+            batch = Some(Batch {
+                world: None,
+                fx_revision,
+                expected,
+            });
+        }
+        ProcessingCommand::SnapshotFrame(time_secs) => {
+            send_frame_snapshot(time_secs, cached_params, *format, result_tx);
+        }
+        ProcessingCommand::InspectFrame(time_secs) => {
+            send_frame_info(time_secs, cached_params, *format, result_tx);
+        }
+        ProcessingCommand::ApplyF0Candidate(choice) => {
+            apply_f0_choice(choice, cached_params, result_tx);
+        }
+        ProcessingCommand::Analyze(audio) => {
+            run_reanalyze(
+                &audio,
+                result_tx,
+                *options,
+                tokens,
+                format,
+                cached_params,
+                original_mono,
+                post_world_audio,
+            );
+        }
+        ProcessingCommand::Export(job) => run_export(job, result_tx),
+        ProcessingCommand::PunchIn(job) => run_punch_in(job, result_tx),
+        ProcessingCommand::SaveParams(path) => {
+            run_save_params(&path, *options, cached_params, original_mono, result_tx);
+        }
+        ProcessingCommand::LoadParams(path) => {
+            run_load_params(
+                &path,
+                result_tx,
+                *options,
+                format,
+                cached_params,
+                original_mono,
+                post_world_audio,
+            );
+        }
+        ProcessingCommand::SetAnalysisOptions(world) => {
+            set_analysis_options(
+                world,
+                options,
+                format,
+                cached_params,
+                original_mono,
+                post_world_audio,
+            );
+        }
+        ProcessingCommand::Morph(path, alpha) => {
+            run_morph(
+                &path,
+                alpha,
+                *options,
+                effects,
+                scratch,
+                result_tx,
+                cached_params,
+                original_mono,
+                *format,
+                post_world_audio,
+            );
+        }
+        ProcessingCommand::ResynthesizeMorph(a, b, crossfade) => {
+            run_resynthesize_morph(
+                &a,
+                &b,
+                crossfade,
+                effects,
+                scratch,
+                result_tx,
+                cached_params,
+                original_mono,
+                *format,
+                post_world_audio,
+            );
+        }
+        ProcessingCommand::Shutdown => return true,
+        #[cfg(feature = "test-hooks")]
+        ProcessingCommand::SimulateCrash => simulate_crash(),
+        #[cfg(feature = "test-hooks")]
+        ProcessingCommand::SimulatePanic => simulate_panic(),
+    }
+    let Some(batch) = batch else {
+        return false;
+    };
 
-            // Drain any queued commands — only process the latest.
-            let mut latest_world = values;
-            let mut latest_fx = fx_params;
-            loop {
-                match cmd_rx.try_recv() {
-                    Ok(ProcessingCommand::Resynthesize(newer_w, newer_fx)) => {
-                        latest_world = newer_w;
-                        latest_fx = newer_fx;
-                    }
-                    Ok(ProcessingCommand::ReapplyEffects(newer_fx)) => {
-                        latest_fx = newer_fx;
-                    }
-                    Ok(ProcessingCommand::Shutdown) => return true,
-                    Ok(ProcessingCommand::Load(path)) => {
-                        run_load_file(
-                            path,
-                            result_tx,
-                            sample_rate,
-                            cached_params,
-                            original_mono,
-                            post_world_audio,
-                        );
-                        // Don't continue draining — new file loaded, abort pending resynth
-                        return false;
-                    }
-                    Ok(ProcessingCommand::ScanDirectory(prefix)) => {
-                        let entries = scan_directory_entries(&prefix);
-                        let _ = result_tx.send(ProcessingResult::DirectoryListing(prefix, entries));
-                        // Continue draining — fast I/O
-                    }
-                    Ok(ProcessingCommand::PrecheckAudio(path)) => {
-                        match precheck_audio_file(&path) {
-                            Ok(()) => {
-                                let _ = result_tx.send(ProcessingResult::AudioPrecheckDone(path));
-                            }
-                            Err(e) => {
-                                let _ = result_tx.send(ProcessingResult::AudioPrecheckFailed(path, e));
-                            }
-                        }
-                        // Continue draining — fast I/O
-                    }
-                    Err(_) => break,
-                }
-            }
+    // Only the latest queued state is rendered; the commands drained on the
+    // way run first, through this same match.
+    let drained = drain_queue(cmd_rx, batch);
+    let ended = drained.barrier.is_some();
+    for cmd in drained.passed.into_iter().chain(drained.barrier) {
+        if handle_command(
+            cmd,
+            cmd_rx,
+            result_tx,
+            options,
+            effects,
+            playhead,
+            tokens,
+            scratch,
+            params_scratch,
+            cached_params,
+            original_mono,
+            post_world_audio,
+            format,
+        ) {
+            return true;
+        }
+    }
+    if ended {
+        return false;
+    }
 
-            run_resynthesize(
-                &latest_world,
-                &latest_fx,
+    let Batch {
+        world,
+        fx_revision,
+        expected,
+    } = drained.batch;
+    if !expect_format(expected, *format, result_tx) {
+        return false;
+    }
+    let fx = load_effects(effects, fx_revision);
+    match world {
+        Some((values, range)) => {
+            let started = Instant::now();
+            let rendered = run_resynthesize(
+                &values,
+                &fx,
+                range,
+                options.normalize_synthesis,
                 cached_params,
                 original_mono,
                 post_world_audio,
-                *sample_rate,
+                expected,
+                f64::from_bits(playhead.load(Ordering::Relaxed)),
+                scratch,
+                params_scratch,
                 result_tx,
             );
+            if rendered {
+                let _ = result_tx.send(ProcessingResult::SynthesisTimed(started.elapsed()));
+            }
         }
-        ProcessingCommand::ReapplyEffects(fx_params) => {
-            let mut latest_fx = fx_params;
-            loop {
-                match cmd_rx.try_recv() {
-                    Ok(ProcessingCommand::ReapplyEffects(newer)) => {
-                        latest_fx = newer;
-                    }
-                    Ok(ProcessingCommand::Load(path)) => {
-                        run_load_file(
-                            path,
-                            result_tx,
-                            sample_rate,
-                            cached_params,
-                            original_mono,
-                            post_world_audio,
-                        );
-                        return false;
-                    }
-                    Ok(ProcessingCommand::ScanDirectory(prefix)) => {
-                        let entries = scan_directory_entries(&prefix);
-                        let _ = result_tx.send(ProcessingResult::DirectoryListing(prefix, entries));
-                        // Continue draining — fast I/O
-                    }
-                    Ok(ProcessingCommand::PrecheckAudio(path)) => {
-                        match precheck_audio_file(&path) {
-                            Ok(()) => {
-                                let _ = result_tx.send(ProcessingResult::AudioPrecheckDone(path));
-                            }
-                            Err(e) => {
-                                let _ = result_tx.send(ProcessingResult::AudioPrecheckFailed(path, e));
-                            }
-                        }
-                        // Continue draining — fast I/O
-                    }
-                    Ok(ProcessingCommand::Resynthesize(world_vals, fx_vals)) => {
-                        // Full resynthesis supersedes effects-only.
-                        // Drain further and run resynthesize.
-                        let mut lw = world_vals;
-                        let mut lf = fx_vals;
-                        loop {
-                            match cmd_rx.try_recv() {
-                                Ok(ProcessingCommand::Resynthesize(w, fx)) => {
-                                    lw = w;
-                                    lf = fx;
-                                }
-                                Ok(ProcessingCommand::ReapplyEffects(fx)) => {
-                                    lf = fx;
-                                }
-                                Ok(ProcessingCommand::Shutdown) => return true,
-                                Ok(ProcessingCommand::Load(path)) => {
-                                    run_load_file(
-                                        path,
-                                        result_tx,
-                                        sample_rate,
-                                        cached_params,
-                                        original_mono,
-                                        post_world_audio,
-                                    );
-                                    return false;
-                                }
-                                Ok(ProcessingCommand::ScanDirectory(prefix)) => {
-                                    let entries = scan_directory_entries(&prefix);
-                                    let _ = result_tx.send(ProcessingResult::DirectoryListing(prefix, entries));
-                                    // Continue draining — fast I/O
-                                }
-                                Ok(ProcessingCommand::PrecheckAudio(path)) => {
-                                    match precheck_audio_file(&path) {
-                                        Ok(()) => {
-                                            let _ = result_tx.send(ProcessingResult::AudioPrecheckDone(path));
-                                        }
-                                        Err(e) => {
-                                            let _ = result_tx.send(ProcessingResult::AudioPrecheckFailed(path, e));
-                                        }
-                                    }
-                                    // Continue draining — fast I/O
-                                }
-                                Err(_) => break,
-                            }
-                        }
-                        if cached_params.is_some() {
-                            run_resynthesize(
-                                &lw,
-                                &lf,
-                                cached_params,
-                                original_mono,
-                                post_world_audio,
-                                *sample_rate,
-                                result_tx,
-                            );
-                        }
-                        return false;
-                    }
-                    Ok(ProcessingCommand::Shutdown) => return true,
-                    Err(_) => break,
+        None => {
+            if let Some(ref cached) = post_world_audio {
+                match apply_fx_chain(&cached.audio, &fx, expected, original_mono.as_ref()) {
+                    Ok(final_audio) => send_synthesis_done(final_audio, original_mono, result_tx),
+                    Err(e) => send_format_error(&e, result_tx),
                 }
             }
+        }
+    }
+    false
+}
 
-            if let Some(ref cached) = post_world_audio {
-                let final_audio = apply_fx_chain(cached, &latest_fx);
-                let _ = result_tx.send(ProcessingResult::SynthesisDone(final_audio));
+/// A `Resynthesize` or `ReapplyEffects` with the ones queued behind it
+/// folded in.
+struct Batch {
+    /// Sliders of the latest `Resynthesize` and the source range all of
+    /// them cover; `None` when only effects changed.
+    world: Option<(WorldSliderValues, Option<Range<f64>>)>,
+    /// Newest effects revision named by any of them.
+    fx_revision: u64,
+    /// Format the latest of them expects back.
+    expected: AudioFormat,
+}
+
+/// What [`drain_queue`] found behind a [`Batch`].
+struct Drained {
+    batch: Batch,
+    /// Commands that leave the batch alone, in arrival order.
+    passed: Vec<ProcessingCommand>,
+    /// The command that ended the drain, if one did (see [`ends_drain`]);
+    /// the batch is stale then and isn't rendered.
+    barrier: Option<ProcessingCommand>,
+}
+
+/// Take every queued command without blocking, folding further
+/// `Resynthesize`/`ReapplyEffects` into `batch`, until the queue is empty or
+/// a command [ends the drain](ends_drain).
+fn drain_queue(cmd_rx: &Receiver<ProcessingCommand>, mut batch: Batch) -> Drained {
+    let mut passed = Vec::new();
+    let barrier = loop {
+        match cmd_rx.try_recv() {
+            Ok(ProcessingCommand::Resynthesize(values, fx_revision, expected, range)) => {
+                let range = match batch.world.take() {
+                    Some((_, earlier)) => merge_ranges(earlier, range),
+                    None => range,
+                };
+                batch.world = Some((values, range));
+                batch.fx_revision = batch.fx_revision.max(fx_revision);
+                batch.expected = expected;
+            }
+            Ok(ProcessingCommand::ReapplyEffects(fx_revision, expected)) => {
+                batch.fx_revision = batch.fx_revision.max(fx_revision);
+                batch.expected = expected;
             }
+            Ok(cmd) if ends_drain(&cmd) => break Some(cmd),
+            Ok(cmd) => passed.push(cmd),
+            Err(_) => break None,
+        }
+    };
+    Drained {
+        batch,
+        passed,
+        barrier,
+    }
+}
+
+/// Whether `cmd`, queued behind a pending render, makes that render moot:
+/// it replaces the analysis or the render the batch would start from, or
+/// stops the worker. Every variant is listed so a new one has to choose.
+fn ends_drain(cmd: &ProcessingCommand) -> bool {
+    match cmd {
+        ProcessingCommand::Load(_)
+        | ProcessingCommand::Decode(_)
+        | ProcessingCommand::Analyze(_)
+        | ProcessingCommand::LoadParams(_)
+        | ProcessingCommand::SetAnalysisOptions(_)
+        | ProcessingCommand::Morph(..)
+        | ProcessingCommand::ResynthesizeMorph(..)
+        | ProcessingCommand::Shutdown => true,
+        #[cfg(feature = "test-hooks")]
+        ProcessingCommand::SimulateCrash | ProcessingCommand::SimulatePanic => true,
+        // Coalesced into the batch by `drain_queue`.
+        ProcessingCommand::Resynthesize(..) | ProcessingCommand::ReapplyEffects(..) => false,
+        // Fast, or working on data the batch doesn't change. An f0
+        // correction runs before the resynthesis it asks for; a params file
+        // is written from the analysis, not the render.
+        ProcessingCommand::ScanDirectory(_)
+        | ProcessingCommand::PrecheckAudio(_)
+        | ProcessingCommand::SnapshotFrame(_)
+        | ProcessingCommand::InspectFrame(_)
+        | ProcessingCommand::ApplyF0Candidate(_)
+        | ProcessingCommand::Export(_)
+        | ProcessingCommand::PunchIn(_)
+        | ProcessingCommand::SaveParams(_) => false,
+    }
+}
+
+/// Switch the worker to new WORLD settings. The cached analysis was made
+/// with the old ones, so every cache goes with it.
+fn set_analysis_options(
+    world: AnalyzeOptions,
+    options: &mut AnalysisOptions,
+    format: &mut Option<AudioFormat>,
+    cached_params: &mut Option<WorldParams>,
+    original_mono: &mut Option<AudioData>,
+    post_world_audio: &mut Option<WorldRender>,
+) {
+    log::info!("analysis options: {world:?}");
+    options.world = world;
+    clear_caches(format, cached_params, original_mono, post_world_audio);
+}
+
+/// Panic with a payload that panics again when dropped. `catch_unwind` catches
+/// the first panic, but dropping the payload in `processing_loop` unwinds out
+/// of the thread — the "panic while handling a panic" case the watchdog covers.
+#[cfg(feature = "test-hooks")]
+fn simulate_crash() -> ! {
+    struct PanicOnDrop;
+    impl Drop for PanicOnDrop {
+        fn drop(&mut self) {
+            panic!("simulated worker crash (payload drop)");
+        }
+    }
+    panic::panic_any(PanicOnDrop)
+}
+
+/// Panic the way a bug in a command handler would: `processing_loop`
+/// catches it and drops the caches.
+#[cfg(feature = "test-hooks")]
+fn simulate_panic() -> ! {
+    panic!("simulated processing panic")
+}
+
+/// Write an export job's snapshot (with live gain baked in) and report the outcome.
+fn run_export(job: ExportJob, result_tx: &Sender<ProcessingResult>) {
+    log::info!("export: writing {} to {}", job.label, job.path);
+    let mut progress = ProgressTracker::new(
+        &format!("Exporting {}...", job.label),
+        job.audio.duration_secs(),
+    );
+    // Gain is applied per chunk as it is written: no full-length copy.
+    let outcome = export::export_wav_streaming(
+        effects::gained(&job.audio.samples, job.gain_db),
+        job.audio.sample_rate,
+        job.audio.channels,
+        Path::new(&job.path),
+        |pct| {
+            if let Some(status) = progress.update(pct) {
+                let _ = result_tx.send(ProcessingResult::ExportProgress(status));
+            }
+        },
+    )
+    .map_err(|e| {
+        log::error!("export: failed — {e}");
+        e.to_string()
+    });
+    let _ = result_tx.send(ProcessingResult::ExportDone(job.path, outcome));
+}
+
+/// Decode, resample and splice a punch-in clip; failures become a status.
+fn run_punch_in(job: PunchJob, result_tx: &Sender<ProcessingResult>) {
+    log::info!(
+        "punch-in: {} over frames {}..{}",
+        job.path,
+        job.splice.start,
+        job.splice.end
+    );
+    let outcome = decoder::decode_file(Path::new(&job.path))
+        .map_err(|e| e.to_string())
+        .and_then(|clip| {
+            let clip = edit::resample(&clip, job.base.sample_rate);
+            edit::splice(&job.base, &clip, &job.splice).map_err(|e| e.to_string())
+        });
+    match outcome {
+        Ok(audio) => {
+            let _ = result_tx.send(ProcessingResult::PunchedIn(audio));
+        }
+        Err(e) => {
+            log::error!("punch-in: failed — {e}");
+            let _ = result_tx.send(ProcessingResult::Status(format!("Punch-in failed: {e}")));
+        }
+    }
+}
+
+/// Send the cached spectrogram frame nearest `time_secs`, if analysis exists.
+fn send_frame_snapshot(
+    time_secs: f64,
+    cached_params: &Option<WorldParams>,
+    format: Option<AudioFormat>,
+    result_tx: &Sender<ProcessingResult>,
+) {
+    let (Some(params), Some(format)) = (cached_params.as_ref(), format) else {
+        return;
+    };
+    if params.spectrogram.is_empty() || params.frame_period <= 0.0 {
+        return;
+    }
+    let last = params.spectrogram.len() - 1;
+    let frame = if time_secs.is_finite() {
+        ((time_secs.max(0.0) * 1000.0 / params.frame_period).round() as usize).min(last)
+    } else {
+        0
+    };
+    let _ = result_tx.send(ProcessingResult::FrameSnapshot(FrameSnapshot {
+        envelope: params.spectrogram[frame].clone(),
+        fft_size: params.fft_size,
+        sample_rate: format.sample_rate,
+        time_secs: frame as f64 * params.frame_period / 1000.0,
+    }));
+}
+
+/// Send the inspector values of the cached frame nearest `time_secs`, if
+/// analysis exists.
+fn send_frame_info(
+    time_secs: f64,
+    cached_params: &Option<WorldParams>,
+    format: Option<AudioFormat>,
+    result_tx: &Sender<ProcessingResult>,
+) {
+    let Some(info) = cached_params
+        .as_ref()
+        .zip(format)
+        .and_then(|(params, format)| FrameInfo::from_params(params, format.sample_rate, time_secs))
+    else {
+        return;
+    };
+    let _ = result_tx.send(ProcessingResult::FrameInfo(info));
+}
+
+/// Overwrite the cached f0 in the chosen range with one candidate.
+fn apply_f0_choice(
+    choice: F0Choice,
+    cached_params: &mut Option<WorldParams>,
+    result_tx: &Sender<ProcessingResult>,
+) {
+    let outcome = match cached_params.as_mut() {
+        Some(params) => {
+            let frames = f0_candidates::frame_range(
+                &params.temporal_positions,
+                choice.start_secs,
+                choice.end_secs,
+            );
+            f0_candidates::apply_candidate(params, frames, choice.rank)
+        }
+        None => Err("no analyzed audio".to_string()),
+    };
+    match outcome {
+        Ok(written) => {
+            let _ = result_tx.send(ProcessingResult::F0Corrected(choice, written));
+        }
+        Err(e) => {
+            let _ = result_tx.send(ProcessingResult::Status(format!("F0 correction: {e}")));
         }
-        ProcessingCommand::Shutdown => return true,
     }
-    false
 }
 
 /// Scan directory entries matching a given input prefix.
 fn scan_directory_entries(input: &str) -> Vec<String> {
-    use std::env;
     use std::fs;
 
     // Parse input into (dir_part, prefix) at the last '/'
@@ -418,13 +2256,9 @@ fn scan_directory_entries(input: &str) -> Vec<String> {
         (".".to_string(), input.to_string())
     };
 
-    // Expand ~ to home directory
-    if dir_part.starts_with("~/") {
-        if let Ok(home) = env::var("HOME") {
-            dir_part = format!("{}{}", home, &dir_part[1..]);
-        } else {
-            dir_part = ".".to_string();
-        }
+    // Expand ~ to home directory ($HOME, or %USERPROFILE% on Windows)
+    if dir_part.starts_with('~') {
+        dir_part = paths::expand_tilde(&dir_part).unwrap_or_else(|| ".".to_string());
     }
 
     // Fallback: empty dir means current directory
@@ -450,11 +2284,7 @@ fn scan_directory_entries(input: &str) -> Vec<String> {
             }
 
             // Determine if directory (follow symlinks)
-            let is_dir = entry
-                .path()
-                .metadata()
-                .map(|m| m.is_dir())
-                .unwrap_or(false);
+            let is_dir = entry.path().metadata().map(|m| m.is_dir()).unwrap_or(false);
 
             // Build stored path: strip "./" prefix if dir_part was "."
             let display_path = if dir_part == "." {
@@ -482,11 +2312,12 @@ fn precheck_audio_file(path: &str) -> Result<(), String> {
     use std::fs::File;
     use std::io::Read;
 
-    let mut file =
-        File::open(path).map_err(|e| format!("Cannot open file: {}", e))?;
+    let mut file = File::open(path).map_err(|e| format!("Cannot open file: {}", e))?;
 
     let mut buf = [0u8; 12];
-    let n = file.read(&mut buf).map_err(|e| format!("Cannot read file: {}", e))?;
+    let n = file
+        .read(&mut buf)
+        .map_err(|e| format!("Cannot read file: {}", e))?;
 
     if n == 0 {
         return Err("File is empty".to_string());
@@ -529,30 +2360,73 @@ fn precheck_audio_file(path: &str) -> Result<(), String> {
 }
 
 /// Run load (decode + analyze) for a file.
+#[allow(clippy::too_many_arguments)]
 fn run_load_file(
     path: String,
     result_tx: &Sender<ProcessingResult>,
-    sample_rate: &mut u32,
+    options: AnalysisOptions,
+    tokens: &mut AnalysisTokens,
+    format: &mut Option<AudioFormat>,
     cached_params: &mut Option<WorldParams>,
     original_mono: &mut Option<AudioData>,
-    post_world_audio: &mut Option<AudioData>,
+    post_world_audio: &mut Option<WorldRender>,
 ) {
+    // Drop the previous file's analysis up front, so a load that fails
+    // part-way never leaves it behind for later effects to run on.
+    clear_caches(format, cached_params, original_mono, post_world_audio);
+    let cancel = tokens.next();
+    if cancel.load(Ordering::Relaxed) {
+        log::info!("load: {path} superseded before it started");
+        return;
+    }
     let _ = result_tx.send(ProcessingResult::Status("Decoding...".into()));
     let tx = result_tx.clone();
-    match decoder::decode_file_with_progress(Path::new(&path), move |pct| {
-        let _ = tx.send(ProcessingResult::Status(format!("Decoding... {pct}%")));
+    let mut progress = ProgressTracker::new("Decoding...", 0.0);
+    match decoder::decode_file_with_progress(Path::new(&path), move |pct, total_secs| {
+        progress.set_media_secs(total_secs);
+        if let Some(status) = progress.update(pct) {
+            let _ = tx.send(ProcessingResult::Status(status));
+        }
     }) {
         Ok(audio_data) => {
             let audio = Arc::new(audio_data.clone());
+            let sidecar = params_sidecar(&path);
             let _ = result_tx.send(ProcessingResult::AudioReady(audio_data, path));
-            run_analyze(
+            if let Some(params) = cached_analysis(&sidecar, &audio, options) {
+                install_analysis(
+                    &audio,
+                    params,
+                    result_tx,
+                    options,
+                    format,
+                    cached_params,
+                    original_mono,
+                    post_world_audio,
+                );
+            } else if run_analyze(
                 &audio,
                 result_tx,
-                sample_rate,
+                options,
+                &cancel,
+                &mut tokens.refining,
+                format,
                 cached_params,
                 original_mono,
                 post_world_audio,
-            );
+            ) && options.cache_params
+            {
+                // A preview isn't worth caching; the full analysis is, once done.
+                match tokens.refining.as_mut() {
+                    Some(refinement) => refinement.save_to = Some(sidecar),
+                    None => run_save_params(
+                        &sidecar.to_string_lossy(),
+                        options,
+                        cached_params,
+                        original_mono,
+                        result_tx,
+                    ),
+                }
+            }
         }
         Err(e) => {
             log::error!("load: failed — {e}");
@@ -561,22 +2435,109 @@ fn run_load_file(
     }
 }
 
+/// Decode a file for playback only (safe mode). Drops the previous file's
+/// analysis so later Resynthesize commands are no-ops, not stale results.
+fn run_decode_file(
+    path: String,
+    result_tx: &Sender<ProcessingResult>,
+    format: &mut Option<AudioFormat>,
+    cached_params: &mut Option<WorldParams>,
+    original_mono: &mut Option<AudioData>,
+    post_world_audio: &mut Option<WorldRender>,
+) {
+    clear_caches(format, cached_params, original_mono, post_world_audio);
+    let _ = result_tx.send(ProcessingResult::Status("Decoding...".into()));
+    let tx = result_tx.clone();
+    let mut progress = ProgressTracker::new("Decoding...", 0.0);
+    match decoder::decode_file_with_progress(Path::new(&path), move |pct, total_secs| {
+        progress.set_media_secs(total_secs);
+        if let Some(status) = progress.update(pct) {
+            let _ = tx.send(ProcessingResult::Status(status));
+        }
+    }) {
+        Ok(audio_data) => {
+            log::info!("decode: {path} loaded for playback only (analysis disabled)");
+            let _ = result_tx.send(ProcessingResult::AudioReady(audio_data, path));
+        }
+        Err(e) => {
+            log::error!("decode: failed — {e}");
+            let _ = result_tx.send(ProcessingResult::Status(format!("Load error: {e}")));
+        }
+    }
+}
+
+/// A buffer out of [`apply_fx_chain`].
+#[derive(Debug, Clone)]
+pub struct FxOutput {
+    pub audio: AudioData,
+    /// Loudness-match trim applied to `audio` in dB, when the match ran.
+    pub trim_db: Option<f32>,
+    /// What the effects' loudness normalization stage measured and applied.
+    pub normalization: Option<LufsNormalization>,
+}
+
 /// Apply the effects chain, returning the original unchanged if effects are neutral.
-fn apply_fx_chain(audio: &AudioData, params: &EffectsParams) -> AudioData {
-    if params.is_neutral() {
-        return audio.clone();
-    }
-    // H-7: Effects processing assumes mono input (single filter state).
-    // WORLD always outputs mono, so this is safe. Document the precondition.
-    debug_assert!(
-        audio.channels == 1,
-        "apply_fx_chain expects mono audio, got {} channels",
-        audio.channels
+///
+/// With [`EffectsParams::loudness_match`] and a `reference` (the analyzed
+/// original), the result is then trimmed to the reference's integrated
+/// loudness ([`effects::measure_lufs_approx`]) — neutral effects included,
+/// since WORLD edits change the level too. Only the buffer is trimmed; the
+/// live output gain is applied after this, in the audio callback.
+///
+/// The filters are designed for `expected`, the format of the file the UI
+/// is showing; a buffer in any other format is rejected instead of being
+/// filtered with the wrong cutoffs.
+///
+/// # Errors
+///
+/// Returns a message naming both formats if `audio` is not `expected`.
+/// Multi-channel audio is fine: each channel keeps its own filter state
+/// (see [`effects::apply_effects`]).
+pub fn apply_fx_chain(
+    audio: &AudioData,
+    params: &EffectsParams,
+    expected: AudioFormat,
+    reference: Option<&AudioData>,
+) -> Result<FxOutput, String> {
+    expected.check(audio.format(), "effects")?;
+    let (samples, normalization) = effects::apply_effects_normalized(
+        &audio.samples,
+        audio.sample_rate,
+        audio.channels,
+        params,
     );
-    let processed = effects::apply_effects(&audio.samples, audio.sample_rate, params);
-    AudioData {
-        samples: processed,
+    let mut processed = AudioData {
+        samples,
         sample_rate: audio.sample_rate,
         channels: audio.channels,
+    };
+    let trim_db = match reference {
+        Some(reference) if params.loudness_match => match_loudness(&mut processed, reference),
+        _ => None,
+    };
+    Ok(FxOutput {
+        audio: processed,
+        trim_db,
+        normalization,
+    })
+}
+
+/// Scale `audio` to the integrated loudness of `reference`, returning the
+/// trim in dB; `None` (and untouched) when either is silent.
+fn match_loudness(audio: &mut AudioData, reference: &AudioData) -> Option<f32> {
+    let target = effects::measure_lufs_approx(
+        &reference.samples,
+        reference.sample_rate,
+        reference.channels,
+    );
+    let current = effects::measure_lufs_approx(&audio.samples, audio.sample_rate, audio.channels);
+    if target <= loudness::SILENCE_DB || current <= loudness::SILENCE_DB {
+        return None;
+    }
+    let trim_db = target - current;
+    let gain = 10.0_f32.powf(trim_db / 20.0);
+    for s in &mut audio.samples {
+        *s *= gain;
     }
+    Some(trim_db)
 }
@@ -1,6 +1,7 @@
 use crate::audio::decoder::AudioData;
 use rustfft::num_complex::Complex;
 use rustfft::FftPlanner;
+use std::collections::VecDeque;
 use std::sync::Arc;
 
 /// FFT window size used for spectrum analysis.
@@ -34,18 +35,20 @@ fn get_fft(size: usize) -> Arc<dyn rustfft::Fft<f32>> {
     })
 }
 
-pub fn compute_spectrum(samples: &[f32], fft_size: usize) -> Vec<f32> {
-    if fft_size < 2 {
-        return Vec::new();
-    }
+/// FFT window for the paused "inspect" view — 4× the live window for finer bins.
+pub const INSPECT_FFT_SIZE: usize = FFT_SIZE * 4;
 
+/// Frames the inspect view moves per Left/Right step (a quarter of the live window).
+pub const INSPECT_HOP: usize = FFT_SIZE / 4;
+
+/// Hann-windowed FFT magnitudes for bins `0..fft_size / 2`.
+fn hann_magnitudes(samples: &[f32], fft_size: usize) -> Vec<f32> {
     let mut buffer: Vec<Complex<f32>> = (0..fft_size)
         .map(|i| {
             let s = if i < samples.len() { samples[i] } else { 0.0 };
             // Hann window
-            let w = 0.5
-                * (1.0
-                    - (2.0 * std::f32::consts::PI * i as f32 / (fft_size - 1) as f32).cos());
+            let w =
+                0.5 * (1.0 - (2.0 * std::f32::consts::PI * i as f32 / (fft_size - 1) as f32).cos());
             Complex::new(s * w, 0.0)
         })
         .collect();
@@ -54,14 +57,75 @@ pub fn compute_spectrum(samples: &[f32], fft_size: usize) -> Vec<f32> {
     fft.process(&mut buffer);
 
     let bin_count = fft_size / 2;
-    let result: Vec<f32> = buffer[..bin_count]
-        .iter()
-        .map(|c| {
-            (20.0 * (c.norm() / (fft_size as f32).sqrt()).max(1e-10).log10()).clamp(-80.0, 0.0)
+    buffer[..bin_count].iter().map(|c| c.norm()).collect()
+}
+
+pub fn compute_spectrum(samples: &[f32], fft_size: usize) -> Vec<f32> {
+    if fft_size < 2 {
+        return Vec::new();
+    }
+
+    hann_magnitudes(samples, fft_size)
+        .into_iter()
+        .map(|m| (20.0 * (m / (fft_size as f32).sqrt()).max(1e-10).log10()).clamp(-80.0, 0.0))
+        .collect()
+}
+
+/// Spectrum in dBFS, unclamped: a sine of amplitude `a` centred on a bin
+/// reads `20·log10(a)`. Used for peak readouts, where the display clamp of
+/// [`compute_spectrum`] would flatten the peaks.
+pub fn compute_spectrum_dbfs(samples: &[f32], fft_size: usize) -> Vec<f32> {
+    if fft_size < 2 {
+        return Vec::new();
+    }
+    // Hann coherent gain is 1/2, and a real sine splits its energy over ±f.
+    let scale = 4.0 / fft_size as f32;
+    hann_magnitudes(samples, fft_size)
+        .into_iter()
+        .map(|m| 20.0 * (m * scale).max(1e-10).log10())
+        .collect()
+}
+
+/// A spectral peak located to sub-bin accuracy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectralPeak {
+    pub freq_hz: f32,
+    pub level_db: f32,
+}
+
+/// The `count` loudest local maxima of a dB spectrum (`fft_size / 2` bins),
+/// loudest first. Each peak is refined by fitting a parabola through the
+/// peak bin and its two neighbours, which puts a Hann-windowed tone within a
+/// small fraction of a bin.
+pub fn find_peaks(
+    bins_db: &[f32],
+    fft_size: usize,
+    sample_rate: u32,
+    count: usize,
+) -> Vec<SpectralPeak> {
+    if bins_db.len() < 3 || fft_size == 0 {
+        return Vec::new();
+    }
+    let bin_hz = sample_rate as f32 / fft_size as f32;
+    let mut peaks: Vec<SpectralPeak> = (1..bins_db.len() - 1)
+        .filter(|&k| bins_db[k] > bins_db[k - 1] && bins_db[k] >= bins_db[k + 1])
+        .map(|k| {
+            let (a, b, c) = (bins_db[k - 1], bins_db[k], bins_db[k + 1]);
+            let denom = a - 2.0 * b + c;
+            let offset = if denom.abs() > f32::EPSILON {
+                (0.5 * (a - c) / denom).clamp(-0.5, 0.5)
+            } else {
+                0.0
+            };
+            SpectralPeak {
+                freq_hz: (k as f32 + offset) * bin_hz,
+                level_db: b - 0.25 * (a - c) * offset,
+            }
         })
         .collect();
-
-    result
+    peaks.sort_by(|x, y| y.level_db.total_cmp(&x.level_db));
+    peaks.truncate(count);
+    peaks
 }
 
 /// Extract `size` mono samples from `audio` at interleaved position `pos`.
@@ -85,3 +149,112 @@ pub fn extract_window(audio: &AudioData, pos: usize, size: usize) -> Vec<f32> {
         })
         .collect()
 }
+
+/// Lowest level the spectrum display can show, in dB.
+pub const SPECTRUM_FLOOR_DB: f32 = -80.0;
+/// Highest level the spectrum display can show, in dB.
+pub const SPECTRUM_CEILING_DB: f32 = 0.0;
+
+/// Percentile of `values` (nearest rank, `p` in [0, 1]). Empty input gives the floor.
+pub fn percentile(values: &[f32], p: f32) -> f32 {
+    if values.is_empty() {
+        return SPECTRUM_FLOOR_DB;
+    }
+    let mut sorted: Vec<f32> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if sorted.is_empty() {
+        return SPECTRUM_FLOOR_DB;
+    }
+    sorted.sort_by(f32::total_cmp);
+    let rank = (p.clamp(0.0, 1.0) * (sorted.len() - 1) as f32).round() as usize;
+    sorted[rank]
+}
+
+/// Rolling auto-range for the spectrum display.
+///
+/// Each frame contributes its 5th/95th percentile bin levels; the target
+/// range is their average over the last few seconds plus a margin, and the
+/// displayed range eases toward it slowly so the bars don't pump.
+#[derive(Debug, Clone)]
+pub struct SpectrumAutoRange {
+    history: VecDeque<(f32, f32)>,
+    floor_db: f32,
+    ceiling_db: f32,
+}
+
+impl SpectrumAutoRange {
+    /// Frames kept in the rolling window (~3 s at the UI's ~30 fps).
+    pub const HISTORY_FRAMES: usize = 90;
+    /// Headroom added below the low and above the high percentile, in dB.
+    pub const MARGIN_DB: f32 = 3.0;
+    /// Narrowest range shown, so near-silence isn't blown up to full height.
+    pub const MIN_SPAN_DB: f32 = 24.0;
+    /// Fraction of the remaining distance to the target covered per frame.
+    pub const ADAPT_RATE: f32 = 0.05;
+
+    pub fn new() -> Self {
+        Self {
+            history: VecDeque::with_capacity(Self::HISTORY_FRAMES),
+            floor_db: SPECTRUM_FLOOR_DB,
+            ceiling_db: SPECTRUM_CEILING_DB,
+        }
+    }
+
+    /// Feed one frame of spectrum bins (dB) and ease the range toward the new target.
+    pub fn update(&mut self, bins: &[f32]) {
+        if bins.is_empty() {
+            return;
+        }
+        if self.history.len() == Self::HISTORY_FRAMES {
+            self.history.pop_front();
+        }
+        self.history
+            .push_back((percentile(bins, 0.05), percentile(bins, 0.95)));
+
+        let (target_floor, target_ceiling) = self.target();
+        self.floor_db += (target_floor - self.floor_db) * Self::ADAPT_RATE;
+        self.ceiling_db += (target_ceiling - self.ceiling_db) * Self::ADAPT_RATE;
+    }
+
+    /// Range the display would settle on given the current history.
+    pub fn target(&self) -> (f32, f32) {
+        if self.history.is_empty() {
+            return (SPECTRUM_FLOOR_DB, SPECTRUM_CEILING_DB);
+        }
+        let n = self.history.len() as f32;
+        let low = self.history.iter().map(|&(lo, _)| lo).sum::<f32>() / n;
+        let high = self.history.iter().map(|&(_, hi)| hi).sum::<f32>() / n;
+        let mut floor = (low - Self::MARGIN_DB).max(SPECTRUM_FLOOR_DB);
+        let mut ceiling = (high + Self::MARGIN_DB).min(SPECTRUM_CEILING_DB);
+        if ceiling - floor < Self::MIN_SPAN_DB {
+            floor = (ceiling - Self::MIN_SPAN_DB).max(SPECTRUM_FLOOR_DB);
+            ceiling = (floor + Self::MIN_SPAN_DB).min(SPECTRUM_CEILING_DB);
+        }
+        (floor, ceiling)
+    }
+
+    /// Current displayed range as `(floor_db, ceiling_db)`.
+    pub fn range(&self) -> (f32, f32) {
+        (self.floor_db, self.ceiling_db)
+    }
+
+    /// Forget the history and return to the full fixed range (e.g. on file load).
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Default for SpectrumAutoRange {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Map a level in dB onto a bar of `bar_height` rows for the range
+/// `[floor_db, ceiling_db]`, clamping outside it.
+pub fn level_to_height(db: f32, floor_db: f32, ceiling_db: f32, bar_height: f32) -> f32 {
+    let span = ceiling_db - floor_db;
+    if span <= f32::EPSILON || !db.is_finite() {
+        return 0.0;
+    }
+    ((db - floor_db) / span * bar_height).clamp(0.0, bar_height)
+}
@@ -1,5 +1,8 @@
 use crate::audio::decoder::AudioData;
-use world_sys::WorldParams;
+use crate::dsp::parallel;
+use std::borrow::Cow;
+use std::sync::atomic::AtomicBool;
+use world_sys::{AnalyzeOptions, WorldParams};
 
 /// Convert interleaved f32 PCM to mono f64 suitable for WORLD.
 fn to_mono_f64(audio: &AudioData) -> Vec<f64> {
@@ -61,19 +64,243 @@ pub fn to_mono(audio: &AudioData) -> AudioData {
     }
 }
 
+/// Audio longer than this (in seconds) is analyzed in chunks by
+/// [`analyze_with_progress`]; see [`analyze_chunked`].
+pub const CHUNKED_ANALYSIS_SECS: f64 = 120.0;
+
+/// Audio per chunk when [`analyze_with_progress`] analyzes in chunks.
+pub const ANALYSIS_CHUNK_SECS: f64 = 30.0;
+
+/// Extra audio analyzed on each side of a chunk, so the frames near its
+/// edges see the same surroundings as in a single pass. Frames from it are
+/// discarded.
+pub const ANALYSIS_CHUNK_OVERLAP_SECS: f64 = 1.0;
+
 /// Analyze audio using WORLD vocoder with progress callback. Converts to mono f64 internally.
-/// The callback is called at 25%, 50%, 75%, and 100% completion.
+/// The callback gets strictly increasing percentages: 25 and 50 after DIO and StoneMask,
+/// then steps up to 75 and 100 through CheapTrick and D4C.
+///
+/// Audio longer than [`CHUNKED_ANALYSIS_SECS`] goes through
+/// [`analyze_chunked`] with [`ANALYSIS_CHUNK_SECS`] chunks, and the
+/// percentages rise across the chunks instead.
 ///
 /// # Errors
 ///
-/// Returns an error if audio is empty or has zero channels.
+/// Returns an error if audio is empty or has zero channels, or `options`
+/// are out of range; `WorldError::Cancelled` if `cancel` is set before the
+/// last stage.
 pub fn analyze_with_progress<F>(
     audio: &AudioData,
+    options: &AnalyzeOptions,
+    cancel: &AtomicBool,
     on_stage: F,
 ) -> Result<WorldParams, world_sys::WorldError>
 where
     F: FnMut(u8),
 {
+    let mono = analysis_input(audio)?;
+    if mono.len() as f64 / f64::from(audio.sample_rate) > CHUNKED_ANALYSIS_SECS {
+        return analyze_mono_chunked(
+            &mono,
+            audio.sample_rate,
+            options,
+            ANALYSIS_CHUNK_SECS,
+            ANALYSIS_CHUNK_OVERLAP_SECS,
+            cancel,
+            on_stage,
+        );
+    }
+    let options = with_threads(options);
+    let mut params = world_sys::analyze_with_progress(
+        &mono,
+        audio.sample_rate as i32,
+        &options,
+        cancel,
+        on_stage,
+    )?;
+    sanitize_analysis(&mut params)?;
+    Ok(params)
+}
+
+/// Largest share of an analysis's values (f0 plus both rows per frame)
+/// that may be non-finite; more means the analysis itself went wrong.
+pub const MAX_NON_FINITE_SHARE: f64 = 0.01;
+
+/// Replace non-finite values in a fresh analysis (see
+/// [`WorldParams::sanitize`]), logging a warning with the count. Every
+/// analysis in this module goes through it, in release builds too.
+///
+/// # Errors
+///
+/// Returns `WorldError::InvalidParams` if more than
+/// [`MAX_NON_FINITE_SHARE`] of the values were non-finite.
+pub fn sanitize_analysis(params: &mut WorldParams) -> Result<usize, world_sys::WorldError> {
+    let replaced = params.sanitize();
+    if replaced == 0 {
+        return Ok(0);
+    }
+    let values = params.f0.len() * (1 + 2 * (params.fft_size / 2 + 1));
+    if replaced as f64 > MAX_NON_FINITE_SHARE * values as f64 {
+        return Err(world_sys::WorldError::InvalidParams(format!(
+            "analysis produced {replaced} non-finite values out of {values}",
+        )));
+    }
+    log::warn!("replaced {replaced} non-finite values in the WORLD analysis");
+    Ok(replaced)
+}
+
+/// Analyze `audio` `chunk_secs` at a time, each chunk with `overlap_secs`
+/// more audio on both sides, and stitch the frames into one [`WorldParams`]
+/// on the same frame grid as a single pass.
+///
+/// Each chunk's analysis is dropped as soon as its frames are copied out, so
+/// memory beyond the result stays that of one chunk instead of growing with
+/// the file (DIO alone transforms the whole signal at once). Frames near
+/// chunk edges match a single pass closely but not bit for bit. There is no
+/// candidate lattice. Progress and cancellation work as in
+/// [`analyze_with_progress`], with the percentages spread over the chunks.
+///
+/// # Errors
+///
+/// As [`analyze_with_progress`], and for a `chunk_secs` that isn't positive
+/// or an `overlap_secs` that is negative.
+pub fn analyze_chunked<F>(
+    audio: &AudioData,
+    options: &AnalyzeOptions,
+    chunk_secs: f64,
+    overlap_secs: f64,
+    cancel: &AtomicBool,
+    on_stage: F,
+) -> Result<WorldParams, world_sys::WorldError>
+where
+    F: FnMut(u8),
+{
+    let mono = analysis_input(audio)?;
+    analyze_mono_chunked(
+        &mono,
+        audio.sample_rate,
+        options,
+        chunk_secs,
+        overlap_secs,
+        cancel,
+        on_stage,
+    )
+}
+
+fn analyze_mono_chunked<F>(
+    mono: &[f64],
+    sample_rate: u32,
+    options: &AnalyzeOptions,
+    chunk_secs: f64,
+    overlap_secs: f64,
+    cancel: &AtomicBool,
+    mut on_stage: F,
+) -> Result<WorldParams, world_sys::WorldError>
+where
+    F: FnMut(u8),
+{
+    use world_sys::WorldError;
+
+    if !(chunk_secs.is_finite()
+        && chunk_secs > 0.0
+        && overlap_secs.is_finite()
+        && overlap_secs >= 0.0)
+    {
+        return Err(WorldError::InvalidParams(format!(
+            "chunk of {chunk_secs} s with {overlap_secs} s overlap",
+        )));
+    }
+    options.validate()?;
+    let options = with_threads(options);
+    let period_ms = options.frame_period;
+    let frame_secs = period_ms / 1000.0;
+    // Same frame count GetSamplesForDIO gives for this length.
+    let frame_count =
+        (1000.0 * mono.len() as f64 / f64::from(sample_rate) / period_ms) as usize + 1;
+    let chunk_frames = ((chunk_secs / frame_secs).round() as usize).max(1);
+    let chunks = frame_count.div_ceil(chunk_frames);
+
+    let mut params = WorldParams {
+        f0: Vec::with_capacity(frame_count),
+        temporal_positions: Vec::with_capacity(frame_count),
+        spectrogram: Vec::with_capacity(frame_count),
+        aperiodicity: Vec::with_capacity(frame_count),
+        fft_size: 0,
+        frame_period: period_ms,
+        sample_rate: sample_rate as i32,
+        f0_candidates: None,
+        voicing: Vec::with_capacity(frame_count),
+    };
+    let mut reported = 0;
+    for chunk in 0..chunks {
+        let first = chunk * chunk_frames;
+        let last = ((chunk + 1) * chunk_frames).min(frame_count) - 1;
+        let (seg_frame, sub) = analyze_segment(
+            mono,
+            sample_rate,
+            &options,
+            first as f64 * frame_secs - overlap_secs,
+            last as f64 * frame_secs + overlap_secs,
+            cancel,
+            |pct| {
+                let overall = ((chunk * 100 + usize::from(pct)) / chunks) as u8;
+                if overall > reported {
+                    reported = overall;
+                    on_stage(overall);
+                }
+            },
+        )?;
+        if chunk == 0 {
+            params.fft_size = sub.fft_size;
+        }
+        if sub.fft_size != params.fft_size || last - seg_frame >= sub.f0.len() {
+            return Err(WorldError::InvalidParams(format!(
+                "chunk {chunk} produced {} frames of fft_size {}, need {} of {}",
+                sub.f0.len(),
+                sub.fft_size,
+                last - seg_frame + 1,
+                params.fft_size,
+            )));
+        }
+        let (skip, take) = (first - seg_frame, last - first + 1);
+        params.f0.extend(sub.f0.iter().skip(skip).take(take));
+        // Positions depend only on the frame index (same formula as DIO).
+        params
+            .temporal_positions
+            .extend((first..=last).map(|frame| frame as f64 * period_ms / 1000.0));
+        params
+            .voicing
+            .extend(sub.voicing.iter().skip(skip).take(take));
+        params
+            .spectrogram
+            .extend(sub.spectrogram.into_iter().skip(skip).take(take));
+        params
+            .aperiodicity
+            .extend(sub.aperiodicity.into_iter().skip(skip).take(take));
+    }
+    params.validate()?;
+    Ok(params)
+}
+
+/// The f0 contour of `audio` as `(seconds, Hz)` points on the analysis
+/// frame grid, 0 Hz where unvoiced ([`world_sys::analyze_f0_only`]): the
+/// pitch, long before a full analysis is done.
+///
+/// # Errors
+///
+/// Returns an error if audio is empty or has zero channels, or `options`
+/// are out of range.
+pub fn pitch_curve(
+    audio: &AudioData,
+    options: &AnalyzeOptions,
+) -> Result<Vec<(f64, f64)>, world_sys::WorldError> {
+    let mono = analysis_input(audio)?;
+    let (positions, f0) = world_sys::analyze_f0_only(&mono, audio.sample_rate as i32, options)?;
+    Ok(positions.into_iter().zip(f0).collect())
+}
+
+/// `audio` as mono f64, or an error if there is nothing WORLD can analyze.
+fn analysis_input(audio: &AudioData) -> Result<Vec<f64>, world_sys::WorldError> {
     let mono = to_mono_f64(audio);
     // H-3: Guard against empty audio before the FFI call, which would panic.
     if mono.is_empty() {
@@ -86,11 +313,60 @@ where
             "sample_rate must be positive".into(),
         ));
     }
-    Ok(world_sys::analyze_with_progress(
-        &mono,
-        audio.sample_rate as i32,
+    Ok(mono)
+}
+
+/// Analyze `mono` from the last frame boundary at or before `from_secs`
+/// that falls on a whole sample, up to `to_secs` (both clamped to the
+/// audio). The segment's frame `j` is then frame `seg_frame + j` of a
+/// whole-file analysis; returns `seg_frame` and the segment's params.
+fn analyze_segment<F>(
+    mono: &[f64],
+    sample_rate: u32,
+    options: &AnalyzeOptions,
+    from_secs: f64,
+    to_secs: f64,
+    cancel: &AtomicBool,
+    on_stage: F,
+) -> Result<(usize, WorldParams), world_sys::WorldError>
+where
+    F: FnMut(u8),
+{
+    let sr = f64::from(sample_rate);
+    let period_ms = options.frame_period;
+    let frame_offset = |frame: usize| frame as f64 * period_ms * sr / 1000.0;
+    let mut seg_frame = (from_secs / (period_ms / 1000.0)).floor().max(0.0) as usize;
+    while seg_frame > 0 && frame_offset(seg_frame).fract() != 0.0 {
+        seg_frame -= 1;
+    }
+    let seg_start = frame_offset(seg_frame) as usize;
+    let seg_end = ((to_secs * sr).ceil() as usize).min(mono.len());
+    if seg_start >= seg_end {
+        return Err(world_sys::WorldError::InvalidParams(
+            "region is outside the audio".into(),
+        ));
+    }
+    let mut sub = world_sys::analyze_with_progress(
+        &mono[seg_start..seg_end],
+        sample_rate as i32,
+        &with_threads(options),
+        cancel,
         on_stage,
-    ))
+    )?;
+    sanitize_analysis(&mut sub)?;
+    Ok((seg_frame, sub))
+}
+
+/// `options` with an automatic (0) thread count resolved through
+/// [`parallel::threads`], so `VOICEFORGE_THREADS` applies to analysis too.
+fn with_threads(options: &AnalyzeOptions) -> AnalyzeOptions {
+    AnalyzeOptions {
+        threads: match options.threads {
+            0 => parallel::threads(),
+            n => n,
+        },
+        ..*options
+    }
 }
 
 /// Analyze audio using WORLD vocoder. Converts to mono f64 internally.
@@ -99,15 +375,250 @@ where
 ///
 /// Returns an error if audio is empty or has zero channels.
 pub fn analyze(audio: &AudioData) -> Result<WorldParams, world_sys::WorldError> {
-    analyze_with_progress(audio, |_| {})
+    analyze_with_progress(
+        audio,
+        &AnalyzeOptions::default(),
+        &AtomicBool::new(false),
+        |_| {},
+    )
+}
+
+/// Track f0 once per search setting (DIO -> StoneMask), each on the frame
+/// grid of an analysis with `frame_period` (ms). Converts to mono f64
+/// internally.
+///
+/// # Errors
+///
+/// Returns an error if audio is empty or has zero channels or sample rate.
+pub fn track_f0(
+    audio: &AudioData,
+    searches: &[world_sys::F0Search],
+    frame_period: f64,
+) -> Result<Vec<Vec<f64>>, world_sys::WorldError> {
+    let mono = to_mono_f64(audio);
+    if mono.is_empty() || audio.sample_rate == 0 {
+        return Err(world_sys::WorldError::InvalidParams(
+            "audio is empty or sample_rate is zero".into(),
+        ));
+    }
+    Ok(searches
+        .iter()
+        .map(|search| world_sys::track_f0(&mono, audio.sample_rate as i32, search, frame_period))
+        .collect())
+}
+
+/// Smallest per-bin / per-frame energy treated as sound. Digital silence
+/// analyzes to spectrogram rows of exact zeros; anything at or below this is
+/// silent.
+pub const FRAME_ENERGY_EPS: f64 = 1e-20;
+
+/// `energy` clamped to at least [`FRAME_ENERGY_EPS`]. Every computation that
+/// divides by (or takes the log of) a frame or bin energy goes through this,
+/// so silent frames give finite results. NaN maps to the floor as well.
+pub fn frame_energy_floor(energy: f64) -> f64 {
+    energy.max(FRAME_ENERGY_EPS)
+}
+
+/// Total power of one spectrogram row.
+pub fn frame_energy(row: &[f64]) -> f64 {
+    row.iter().sum()
+}
+
+/// Per-frame silence mask: `true` where the row's energy is at or below
+/// [`FRAME_ENERGY_EPS`]. Cheap enough to compute once per analysis or run.
+pub fn silent_frames(spectrogram: &[Vec<f64>]) -> Vec<bool> {
+    spectrogram
+        .iter()
+        .map(|row| frame_energy(row) <= FRAME_ENERGY_EPS)
+        .collect()
 }
 
 /// Synthesize audio from WORLD parameters. Returns mono AudioData.
 ///
+/// WORLD takes the log of every spectrogram bin, so zero bins (silent
+/// frames) are floored with [`frame_energy_floor`] first; they still
+/// synthesize as silence.
+///
+/// WORLD's f64 output goes to `scratch` when given (see
+/// [`world_sys::synthesize_into`]), so a caller that keeps it between calls
+/// doesn't allocate it again.
+///
 /// # Errors
 ///
 /// Returns an error if WORLD parameters are invalid or the output would be too large.
-pub fn synthesize(params: &WorldParams, sample_rate: u32) -> Result<AudioData, world_sys::WorldError> {
-    let samples = world_sys::synthesize(params, sample_rate as i32)?;
+pub fn synthesize(
+    params: &WorldParams,
+    sample_rate: u32,
+    scratch: Option<&mut Vec<f64>>,
+) -> Result<AudioData, world_sys::WorldError> {
+    let mut owned = Vec::new();
+    let samples = scratch.unwrap_or(&mut owned);
+    world_sys::synthesize_into(&floored(params), sample_rate as i32, samples)?;
+    Ok(from_mono_f64(samples, sample_rate))
+}
+
+/// Peak a render from [`synthesize_normalized`] is held to.
+pub const SYNTHESIS_PEAK_CEILING: f64 = 0.99;
+
+/// [`synthesize`], scaled down as a whole when its peak exceeds
+/// [`SYNTHESIS_PEAK_CEILING`]: WORLD's output isn't bounded, and a wider
+/// pitch range or a spectral tilt boost can take it well past ±1. Returns
+/// the gain applied as well (1.0 when the render was left alone).
+///
+/// # Errors
+///
+/// As [`synthesize`].
+pub fn synthesize_normalized(
+    params: &WorldParams,
+    sample_rate: u32,
+    scratch: Option<&mut Vec<f64>>,
+) -> Result<(AudioData, f64), world_sys::WorldError> {
+    let mut owned = Vec::new();
+    let samples = scratch.unwrap_or(&mut owned);
+    world_sys::synthesize_into(&floored(params), sample_rate as i32, samples)?;
+    let peak = samples.iter().fold(0.0f64, |peak, s| peak.max(s.abs()));
+    let gain = if peak > SYNTHESIS_PEAK_CEILING {
+        SYNTHESIS_PEAK_CEILING / peak
+    } else {
+        1.0
+    };
+    if gain < 1.0 {
+        samples.iter_mut().for_each(|s| *s *= gain);
+    }
+    Ok((from_mono_f64(samples, sample_rate), gain))
+}
+
+/// [`synthesize`] through WORLD's realtime (ring-buffer) synthesizer, which
+/// starts producing audio before it has seen every frame. Same length and
+/// near-identical output; used for short previews.
+///
+/// # Errors
+///
+/// As [`synthesize`], plus a locked synthesizer.
+pub fn synthesize_streaming(
+    params: &WorldParams,
+    sample_rate: u32,
+) -> Result<AudioData, world_sys::WorldError> {
+    let samples = world_sys::synthesize_realtime(&floored(params), sample_rate as i32)?;
     Ok(from_mono_f64(&samples, sample_rate))
 }
+
+/// `params` with every spectrogram bin floored by [`frame_energy_floor`],
+/// copied only if some bin needs it.
+fn floored(params: &WorldParams) -> Cow<'_, WorldParams> {
+    let needs_floor = params
+        .spectrogram
+        .iter()
+        .flatten()
+        .any(|&v| frame_energy_floor(v) != v);
+    if !needs_floor {
+        return Cow::Borrowed(params);
+    }
+    let mut floored = params.clone();
+    for v in floored.spectrogram.iter_mut().flatten() {
+        *v = frame_energy_floor(*v);
+    }
+    Cow::Owned(floored)
+}
+
+/// Frames within this distance of an edited region are recomputed: WORLD's
+/// analysis windows (CheapTrick spans ~3 pitch periods at the 71 Hz floor,
+/// D4C and DIO's contour smoothing reach similarly far) see the edit there.
+const REGION_CONTEXT_SECS: f64 = 0.1;
+
+/// Extra audio analyzed around the recomputed frames so they get the same
+/// surroundings as in a full analysis. Frames from the padding are discarded.
+const REGION_PAD_SECS: f64 = 0.3;
+
+/// Re-run WORLD analysis on `[start_secs, end_secs]` of `audio` only and
+/// splice the resulting frames into `params`, which must be the analysis of
+/// a previous version of the same-length audio, made with `options`.
+///
+/// Frames outside the region (plus [`REGION_CONTEXT_SECS`]) are left
+/// untouched. The analyzed span starts on a frame boundary that falls on a
+/// whole sample, so its frames line up exactly with the existing ones.
+/// Drops `params.f0_candidates`: the lattice no longer matches the new f0.
+///
+/// # Errors
+///
+/// Returns an error if the audio is empty, its length no longer matches
+/// `params`, the region is invalid, or the spliced params fail validation.
+pub fn reanalyze_region(
+    audio: &AudioData,
+    params: &mut WorldParams,
+    start_secs: f64,
+    end_secs: f64,
+    options: &AnalyzeOptions,
+) -> Result<(), world_sys::WorldError> {
+    use world_sys::WorldError;
+
+    let mono = to_mono_f64(audio);
+    if mono.is_empty() || audio.sample_rate == 0 {
+        return Err(WorldError::InvalidParams(
+            "audio is empty or sample_rate is zero".into(),
+        ));
+    }
+    if !(start_secs.is_finite() && end_secs.is_finite() && start_secs < end_secs) {
+        return Err(WorldError::InvalidParams(format!(
+            "invalid region {start_secs}..{end_secs} s"
+        )));
+    }
+    let sr = audio.sample_rate as f64;
+    let period_ms = params.frame_period;
+    let frame_secs = period_ms / 1000.0;
+    let frame_count = params.f0.len();
+    // Same frame count GetSamplesForDIO gives for this length.
+    let expected_frames = (1000.0 * mono.len() as f64 / sr / period_ms) as usize + 1;
+    if frame_count == 0 || expected_frames != frame_count {
+        return Err(WorldError::InvalidParams(format!(
+            "audio length changed ({expected_frames} frames, params have {frame_count})"
+        )));
+    }
+
+    // Frames to replace.
+    let first = ((start_secs - REGION_CONTEXT_SECS) / frame_secs)
+        .floor()
+        .max(0.0) as usize;
+    let last =
+        (((end_secs + REGION_CONTEXT_SECS) / frame_secs).ceil() as usize).min(frame_count - 1);
+    if first > last {
+        return Ok(());
+    }
+
+    // Analyzed span: padded, starting on a sample-aligned frame boundary.
+    let (seg_frame, sub) = analyze_segment(
+        &mono,
+        audio.sample_rate,
+        options,
+        start_secs - REGION_CONTEXT_SECS - REGION_PAD_SECS,
+        end_secs + REGION_CONTEXT_SECS + REGION_PAD_SECS,
+        &AtomicBool::new(false),
+        |_| {},
+    )?;
+    if sub.fft_size != params.fft_size || sub.frame_period != params.frame_period {
+        return Err(WorldError::InvalidParams(
+            "segment analysis used different fft_size / frame_period".into(),
+        ));
+    }
+    if last - seg_frame >= sub.f0.len() {
+        return Err(WorldError::InvalidParams(format!(
+            "segment produced {} frames, need {}",
+            sub.f0.len(),
+            last - seg_frame + 1
+        )));
+    }
+
+    for frame in first..=last {
+        let j = frame - seg_frame;
+        params.f0[frame] = sub.f0[j];
+        // Positions depend only on the frame index (same formula as DIO).
+        params.temporal_positions[frame] = frame as f64 * period_ms / 1000.0;
+        params.spectrogram[frame].clone_from(&sub.spectrogram[j]);
+        params.aperiodicity[frame].clone_from(&sub.aperiodicity[j]);
+        if !params.voicing.is_empty() {
+            params.voicing[frame] = sub.voicing[j];
+        }
+    }
+    params.f0_candidates = None;
+    params.validate()
+}
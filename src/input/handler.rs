@@ -3,19 +3,46 @@ use std::sync::atomic::Ordering;
 
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-use crate::app::{Action, AppMode, AppState, PanelFocus};
+use crate::app::{
+    Action, AppMode, AppState, PanelFocus, PickerPurpose, F0_CORRECTION_STEP_SECS, PUNCH_STEP_SECS,
+};
 use crate::audio::export;
+use crate::dsp::spectrum::INSPECT_HOP;
+use crate::util;
 
 /// Handle a key press event, mutating app state and optionally returning an action.
 pub fn handle_key_event(key: KeyEvent, app: &mut AppState) -> Option<Action> {
+    // Raw mode turns Ctrl+Z into a key press; job control works in every mode.
+    if key.code == KeyCode::Char('z') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        return Some(Action::Suspend);
+    }
     match app.mode {
         AppMode::FilePicker => handle_file_picker(key, app),
         AppMode::Saving => handle_save_dialog(key, app),
-        AppMode::Help => {
-            // Any key dismisses the help overlay.
+        AppMode::Seeking => handle_seek_prompt(key, app),
+        AppMode::Help if key.code == KeyCode::Char('?') => {
+            // `?` twice: replay the walkthrough.
+            app.start_tour();
+            None
+        }
+        AppMode::Help | AppMode::Stats => {
+            // Any key dismisses the help/stats overlay.
             app.mode = AppMode::Normal;
             None
         }
+        AppMode::Tour => {
+            // Esc skips the rest; any other key goes to the next step.
+            let finished = if key.code == KeyCode::Esc {
+                app.end_tour();
+                true
+            } else {
+                app.advance_tour()
+            };
+            finished.then_some(Action::TourFinished)
+        }
+        AppMode::F0Correct => handle_f0_correction(key, app),
+        AppMode::PunchIn => handle_punch_in(key, app),
+        AppMode::ChainOrder => handle_chain_order(key, app),
         AppMode::Normal => handle_normal(key, app),
     }
 }
@@ -87,7 +114,6 @@ fn handle_text_input(key: &KeyEvent, app: &mut AppState) -> bool {
     }
 }
 
-
 fn handle_file_picker(key: KeyEvent, app: &mut AppState) -> Option<Action> {
     match key.code {
         KeyCode::Esc => {
@@ -97,6 +123,7 @@ fn handle_file_picker(key: KeyEvent, app: &mut AppState) -> Option<Action> {
             app.file_picker_matches.clear();
             app.file_picker_scroll = 0;
             app.file_picker_selected = None;
+            app.end_punch_in();
             None
         }
         KeyCode::Down => {
@@ -108,11 +135,11 @@ fn handle_file_picker(key: KeyEvent, app: &mut AppState) -> Option<Action> {
                     }
                     _ => {}
                 }
-                // Adjust scroll to keep selection visible in 5-row window
-                const VISIBLE: usize = 5;
+                // Adjust scroll to keep selection visible in the window
+                let visible = app.file_picker_rows.max(1);
                 if let Some(sel) = app.file_picker_selected {
-                    if sel >= app.file_picker_scroll + VISIBLE {
-                        app.file_picker_scroll = sel + 1 - VISIBLE;
+                    if sel >= app.file_picker_scroll + visible {
+                        app.file_picker_scroll = sel + 1 - visible;
                     }
                 }
             }
@@ -124,7 +151,7 @@ fn handle_file_picker(key: KeyEvent, app: &mut AppState) -> Option<Action> {
                 Some(i) => app.file_picker_selected = Some(i - 1),
                 None => {}
             }
-            // Adjust scroll to keep selection visible in 5-row window
+            // Adjust scroll to keep selection visible in the window
             if let Some(sel) = app.file_picker_selected {
                 if sel < app.file_picker_scroll {
                     app.file_picker_scroll = sel;
@@ -136,7 +163,11 @@ fn handle_file_picker(key: KeyEvent, app: &mut AppState) -> Option<Action> {
             // Determine which match to use: selected index, or first match if no selection
             let match_idx = app
                 .file_picker_selected
-                .or(if app.file_picker_matches.is_empty() { None } else { Some(0) });
+                .or(if app.file_picker_matches.is_empty() {
+                    None
+                } else {
+                    Some(0)
+                });
 
             if let Some(idx) = match_idx {
                 if idx < app.file_picker_matches.len() {
@@ -175,7 +206,7 @@ fn handle_file_picker(key: KeyEvent, app: &mut AppState) -> Option<Action> {
                         app.file_picker_matches.clear();
                         app.file_picker_selected = None;
                         app.mode = AppMode::Normal;
-                        return Some(Action::PrecheckAudio(path));
+                        return Some(picked_file(app, path));
                     }
                 }
             }
@@ -189,10 +220,11 @@ fn handle_file_picker(key: KeyEvent, app: &mut AppState) -> Option<Action> {
             app.mode = AppMode::Normal;
 
             if path.is_empty() {
+                app.end_punch_in();
                 return None;
             }
 
-            Some(Action::PrecheckAudio(path))
+            Some(picked_file(app, path))
         }
         _ => {
             // All other keys: handle text input, then dispatch ScanDirectory if input changed
@@ -208,6 +240,39 @@ fn handle_file_picker(key: KeyEvent, app: &mut AppState) -> Option<Action> {
     }
 }
 
+/// The chain order overlay: ↑/↓ select a stage, Shift+↑/↓ move it, `d`
+/// restores the standard order.
+fn handle_chain_order(key: KeyEvent, app: &mut AppState) -> Option<Action> {
+    let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+    let changed = match key.code {
+        KeyCode::Up if shift => app.move_chain_stage(-1),
+        KeyCode::Down if shift => app.move_chain_stage(1),
+        KeyCode::Up => {
+            app.select_chain_stage(-1);
+            false
+        }
+        KeyCode::Down => {
+            app.select_chain_stage(1);
+            false
+        }
+        KeyCode::Char('d') => app.reset_chain_order(),
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('O') => {
+            app.close_chain_order();
+            false
+        }
+        _ => false,
+    };
+    changed.then_some(Action::ReapplyEffects)
+}
+
+/// The action for a file chosen in the picker: load it, or punch it in.
+fn picked_file(app: &mut AppState, path: String) -> Action {
+    match std::mem::take(&mut app.picker_purpose) {
+        PickerPurpose::Open => Action::PrecheckAudio(path),
+        PickerPurpose::PunchIn => Action::PunchIn(path),
+    }
+}
+
 fn handle_save_dialog(key: KeyEvent, app: &mut AppState) -> Option<Action> {
     match key.code {
         KeyCode::Esc => {
@@ -241,8 +306,117 @@ fn handle_save_dialog(key: KeyEvent, app: &mut AppState) -> Option<Action> {
     }
 }
 
+fn handle_seek_prompt(key: KeyEvent, app: &mut AppState) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => {
+            app.mode = AppMode::Normal;
+            app.file_picker_input.clear();
+            app.input_cursor = 0;
+            app.seek_error = None;
+        }
+        KeyCode::Enter => {
+            let Some(ref info) = app.file_info else {
+                app.mode = AppMode::Normal;
+                return None;
+            };
+            match util::parse_timestamp(&app.file_picker_input) {
+                Ok(target) => {
+                    let secs = target.to_secs(info.duration_secs);
+                    app.playback.seek_to_secs(
+                        secs,
+                        info.sample_rate,
+                        info.channels,
+                        info.total_samples,
+                    );
+                    app.mode = AppMode::Normal;
+                    app.file_picker_input.clear();
+                    app.input_cursor = 0;
+                    app.seek_error = None;
+                }
+                // Stay in the prompt so the input can be corrected.
+                Err(e) => app.seek_error = Some(e),
+            }
+        }
+        _ => {
+            if handle_text_input(&key, app) {
+                app.seek_error = None;
+            }
+        }
+    }
+    None
+}
+
+/// f0 correction: ←/→ move the end of the range, Tab (or `c`) puts the next
+/// candidate into it, Enter/Esc keep the result and leave.
+fn handle_f0_correction(key: KeyEvent, app: &mut AppState) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Enter => {
+            app.end_f0_correction();
+            None
+        }
+        KeyCode::Left => {
+            app.extend_f0_correction(-F0_CORRECTION_STEP_SECS);
+            None
+        }
+        KeyCode::Right => {
+            app.extend_f0_correction(F0_CORRECTION_STEP_SECS);
+            None
+        }
+        KeyCode::Tab | KeyCode::Char('c') => app.cycle_f0_candidate().map(Action::ApplyF0Candidate),
+        KeyCode::Char(' ') => {
+            app.playback.toggle_playing();
+            None
+        }
+        _ => None,
+    }
+}
+
+fn handle_punch_in(key: KeyEvent, app: &mut AppState) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => {
+            app.end_punch_in();
+            None
+        }
+        KeyCode::Left => {
+            app.extend_punch_in(-PUNCH_STEP_SECS);
+            None
+        }
+        KeyCode::Right => {
+            app.extend_punch_in(PUNCH_STEP_SECS);
+            None
+        }
+        KeyCode::Tab => {
+            app.toggle_punch_mode();
+            None
+        }
+        KeyCode::Enter if app.punch_splice().is_none() => {
+            app.set_status("Punch-in: extend the region with ←/→ first".to_string());
+            None
+        }
+        KeyCode::Enter => {
+            // Pick the clip; the region stays set until it is spliced.
+            app.picker_purpose = PickerPurpose::PunchIn;
+            app.mode = AppMode::FilePicker;
+            app.file_picker_input.clear();
+            app.input_cursor = 0;
+            app.file_picker_selected = None;
+            Some(Action::ScanDirectory)
+        }
+        KeyCode::Char(' ') => {
+            app.playback.toggle_playing();
+            None
+        }
+        _ => None,
+    }
+}
+
 fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
     match key.code {
+        KeyCode::Esc if app.inspector_open => {
+            app.inspector_open = false;
+            app.clear_frame_info();
+            None
+        }
         KeyCode::Char('q') | KeyCode::Esc => {
             app.should_quit = true;
             Some(Action::Quit)
@@ -259,10 +433,20 @@ fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
         }
         KeyCode::Up => {
             match app.focus {
+                PanelFocus::EqBands if app.eq_parametric_mode => {
+                    let steps = if key.modifiers.contains(KeyModifiers::SHIFT) {
+                        0.2
+                    } else {
+                        1.0
+                    };
+                    app.adjust_parametric(steps)
+                        .then_some(Action::ReapplyEffects)
+                }
                 PanelFocus::EqBands => {
                     // Adjust EQ band gain upward by 0.5 dB
                     app.eq_gains[app.eq_selected_band] =
                         (app.eq_gains[app.eq_selected_band] + 0.5).clamp(-6.0, 6.0);
+                    app.mark_eq_adjusted();
                     Some(Action::ReapplyEffects)
                 }
                 _ => {
@@ -275,10 +459,20 @@ fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
         }
         KeyCode::Down => {
             match app.focus {
+                PanelFocus::EqBands if app.eq_parametric_mode => {
+                    let steps = if key.modifiers.contains(KeyModifiers::SHIFT) {
+                        -0.2
+                    } else {
+                        -1.0
+                    };
+                    app.adjust_parametric(steps)
+                        .then_some(Action::ReapplyEffects)
+                }
                 PanelFocus::EqBands => {
                     // Adjust EQ band gain downward by 0.5 dB
                     app.eq_gains[app.eq_selected_band] =
                         (app.eq_gains[app.eq_selected_band] - 0.5).clamp(-6.0, 6.0);
+                    app.mark_eq_adjusted();
                     Some(Action::ReapplyEffects)
                 }
                 _ => {
@@ -291,7 +485,10 @@ fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
             }
         }
         KeyCode::Left => {
-            if app.focus == PanelFocus::Transport {
+            if app.inspect_active() {
+                step_inspect_window(-1, app);
+                None
+            } else if app.focus == PanelFocus::Transport {
                 if let Some(ref info) = app.file_info {
                     app.playback.seek_by_secs(
                         -5.0,
@@ -301,12 +498,16 @@ fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
                     );
                 }
                 None
+            } else if app.focus == PanelFocus::EqBands && app.eq_parametric_mode {
+                app.select_parametric_cell(-1);
+                None
             } else if app.focus == PanelFocus::EqBands {
                 // Navigate to previous band or adjust gain with Shift
                 if key.modifiers.contains(KeyModifiers::SHIFT) {
                     // Fine adjust: -0.1 dB
                     app.eq_gains[app.eq_selected_band] =
                         (app.eq_gains[app.eq_selected_band] - 0.1).clamp(-6.0, 6.0);
+                    app.mark_eq_adjusted();
                     Some(Action::ReapplyEffects)
                 } else {
                     // Navigate to previous band
@@ -332,7 +533,10 @@ fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
             }
         }
         KeyCode::Right => {
-            if app.focus == PanelFocus::Transport {
+            if app.inspect_active() {
+                step_inspect_window(1, app);
+                None
+            } else if app.focus == PanelFocus::Transport {
                 if let Some(ref info) = app.file_info {
                     app.playback.seek_by_secs(
                         5.0,
@@ -342,16 +546,20 @@ fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
                     );
                 }
                 None
+            } else if app.focus == PanelFocus::EqBands && app.eq_parametric_mode {
+                app.select_parametric_cell(1);
+                None
             } else if app.focus == PanelFocus::EqBands {
                 // Navigate to next band or adjust gain with Shift
                 if key.modifiers.contains(KeyModifiers::SHIFT) {
                     // Fine adjust: +0.1 dB
                     app.eq_gains[app.eq_selected_band] =
                         (app.eq_gains[app.eq_selected_band] + 0.1).clamp(-6.0, 6.0);
+                    app.mark_eq_adjusted();
                     Some(Action::ReapplyEffects)
                 } else {
                     // Navigate to next band
-                    if app.eq_selected_band + 1 < 12 {
+                    if app.eq_selected_band + 1 < app.eq_gains.len() {
                         app.eq_selected_band += 1;
                     }
                     None
@@ -379,6 +587,14 @@ fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
                 .store(app.loop_enabled, Ordering::Relaxed);
             None
         }
+        KeyCode::Char('R') => {
+            if app.analysis_lost {
+                Some(Action::Reanalyze)
+            } else {
+                app.set_status("Analysis is intact — nothing to re-analyze".to_string());
+                None
+            }
+        }
         KeyCode::Char('[') => {
             if let Some(ref info) = app.file_info {
                 app.playback.seek_by_secs(
@@ -392,12 +608,8 @@ fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
         }
         KeyCode::Char(']') => {
             if let Some(ref info) = app.file_info {
-                app.playback.seek_by_secs(
-                    5.0,
-                    info.sample_rate,
-                    info.channels,
-                    info.total_samples,
-                );
+                app.playback
+                    .seek_by_secs(5.0, info.sample_rate, info.channels, info.total_samples);
             }
             None
         }
@@ -408,12 +620,8 @@ fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
         KeyCode::End => {
             // M-2: Land on the last frame start, not one-past-end.
             if let Some(ref info) = app.file_info {
-                let last_frame = info
-                    .total_samples
-                    .saturating_sub(info.channels as usize);
-                app.playback
-                    .position
-                    .store(last_frame, Ordering::Release);
+                let last_frame = info.total_samples.saturating_sub(info.channels as usize);
+                app.playback.position.store(last_frame, Ordering::Release);
             }
             None
         }
@@ -425,6 +633,9 @@ fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
                 && app.playback.audio_lock.is_some()
             {
                 app.ab_original = !app.ab_original;
+                if !app.ab_original {
+                    app.ab_hint = false;
+                }
                 Some(Action::ToggleAB)
             } else {
                 None
@@ -450,6 +661,7 @@ fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
             None
         }
         KeyCode::Char('o') => {
+            app.picker_purpose = PickerPurpose::Open;
             app.mode = AppMode::FilePicker;
             app.file_picker_input.clear();
             app.input_cursor = 0;
@@ -461,13 +673,82 @@ fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
             app.mode = AppMode::Help;
             None
         }
+        KeyCode::Char('i') => {
+            app.mode = AppMode::Stats;
+            None
+        }
+        KeyCode::Char('O') => {
+            app.mode = AppMode::ChainOrder;
+            None
+        }
+        KeyCode::Char('f') => {
+            app.inspector_open = !app.inspector_open;
+            if !app.inspector_open {
+                app.clear_frame_info();
+            } else if app.original_audio.is_none() {
+                app.set_status("Frame inspector: waiting for analysis".to_string());
+            }
+            None
+        }
+        KeyCode::Char('c') => {
+            if !app.config.extended_analysis {
+                app.set_status("F0 correction needs --extended-analysis".to_string());
+            } else if app.original_audio.is_none() {
+                app.set_status("F0 correction: waiting for analysis".to_string());
+            } else {
+                app.start_f0_correction();
+            }
+            None
+        }
+        KeyCode::Char('e') => {
+            app.start_punch_in();
+            None
+        }
+        KeyCode::Char('v') => {
+            app.spectrum_auto_range = !app.spectrum_auto_range;
+            if app.spectrum_auto_range {
+                app.set_status("Spectrum range: auto".to_string());
+            } else {
+                app.set_status("Spectrum range: fixed -80..0 dB".to_string());
+            }
+            None
+        }
+        KeyCode::Char('g') if app.playback.is_output_limited() => {
+            app.playback.restore_output();
+            app.set_status("Output restored".to_string());
+            None
+        }
+        KeyCode::Char('g') if app.focus == PanelFocus::Transport => {
+            if app.file_info.is_some() {
+                app.file_picker_input.clear();
+                app.input_cursor = 0;
+                app.seek_error = None;
+                app.mode = AppMode::Seeking;
+            } else {
+                app.set_status("No file loaded".to_string());
+            }
+            None
+        }
+        KeyCode::Char('p') => {
+            app.spectrum_inspect = !app.spectrum_inspect;
+            if app.spectrum_inspect {
+                app.set_status("Spectrum inspect: on (while paused, ←/→ step)".to_string());
+            } else {
+                app.set_status("Spectrum inspect: off".to_string());
+            }
+            None
+        }
         KeyCode::Char('d') => {
             // Reset the selected slider to its default value, or EQ band to 0 dB.
             match app.focus {
+                PanelFocus::EqBands if app.eq_parametric_mode => app
+                    .reset_parametric_cell()
+                    .then_some(Action::ReapplyEffects),
                 PanelFocus::EqBands => {
                     let old_val = app.eq_gains[app.eq_selected_band];
                     app.eq_gains[app.eq_selected_band] = 0.0;
                     if (old_val - 0.0).abs() > 1e-6 {
+                        app.mark_eq_adjusted();
                         Some(Action::ReapplyEffects)
                     } else {
                         None
@@ -493,6 +774,12 @@ fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
                 }
             }
         }
+        KeyCode::Char('t') => app
+            .stepped_voicing_threshold(-1)
+            .map(Action::SetVoicingThreshold),
+        KeyCode::Char('T') => app
+            .stepped_voicing_threshold(1)
+            .map(Action::SetVoicingThreshold),
         KeyCode::Char('w') => {
             app.world_bypass = !app.world_bypass;
             if app.world_bypass {
@@ -502,6 +789,34 @@ fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
             }
             Some(Action::Resynthesize)
         }
+        KeyCode::Char('m') => app.mark_world_region().then_some(Action::Resynthesize),
+        KeyCode::Enter if app.focus == PanelFocus::EqBands => {
+            app.toggle_parametric_eq();
+            None
+        }
+        KeyCode::Enter => app.cycle_slider_mode().then_some(Action::Resynthesize),
+        KeyCode::Char(',') => app.nudge_pitch(-1.0).then_some(Action::Resynthesize),
+        KeyCode::Char('.') => app.nudge_pitch(1.0).then_some(Action::Resynthesize),
+        KeyCode::Char('k') => app.mark_automation_point().then_some(Action::Resynthesize),
+        KeyCode::Char('l') => {
+            app.loudness_match = !app.loudness_match;
+            if app.loudness_match {
+                app.set_status("Loudness match ON — B trimmed to A's loudness".to_string());
+            } else {
+                app.set_status("Loudness match OFF".to_string());
+            }
+            Some(Action::ReapplyEffects)
+        }
+        KeyCode::Char('b') => app.toggle_eq_layout().then_some(Action::ReapplyEffects),
+        KeyCode::Char('W') => {
+            app.world_whisper = !app.world_whisper;
+            if app.world_whisper {
+                app.set_status("Whisper ON — noise excitation only".to_string());
+            } else {
+                app.set_status("Whisper OFF".to_string());
+            }
+            Some(Action::Resynthesize)
+        }
         _ => None,
     }
 }
@@ -509,15 +824,24 @@ fn handle_normal(key: KeyEvent, app: &mut AppState) -> Option<Action> {
 /// Determine the action after adjusting an effects or WORLD slider.
 /// Gain (effects index 0) is applied live in the audio callback; all other
 /// effects go through the processing thread.
-fn effects_slider_action(focus: PanelFocus, _idx: usize, app: &AppState) -> Option<Action> {
+fn effects_slider_action(focus: PanelFocus, idx: usize, app: &mut AppState) -> Option<Action> {
     match focus {
-        PanelFocus::WorldSliders => Some(Action::Resynthesize),
-        PanelFocus::EffectsSliders => Some(Action::ReapplyEffects),
-        PanelFocus::Master => {
-            let linear = 10.0_f32.powf(app.master_sliders[0].value as f32 / 20.0);
-            Some(Action::LiveGain(linear))
+        PanelFocus::WorldSliders => {
+            app.world_slider_changed(idx);
+            Some(Action::Resynthesize)
         }
+        PanelFocus::EffectsSliders => Some(Action::ReapplyEffects),
+        PanelFocus::Master => Some(Action::LiveGain(app.master_sliders[0].value as f32)),
         PanelFocus::EqBands => Some(Action::ReapplyEffects),
         PanelFocus::Transport => None,
     }
 }
+
+/// Move the paused analysis window by `hops` inspect hops (negative = back).
+/// Only the position moves; playback stays paused.
+fn step_inspect_window(hops: isize, app: &mut AppState) {
+    if let Some(ref info) = app.file_info {
+        let offset = hops * (INSPECT_HOP * info.channels as usize) as isize;
+        app.playback.seek_by_samples(offset, info.total_samples);
+    }
+}
@@ -1,5 +1,11 @@
 pub mod app;
 pub mod audio;
+pub mod config;
 pub mod dsp;
 pub mod input;
+pub mod paths;
+pub mod published;
+pub mod session;
+pub mod terminal;
 pub mod ui;
+pub mod util;
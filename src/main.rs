@@ -1,27 +1,40 @@
-use std::io::{self, stdout};
-use std::path::Path;
+use std::io::{self, stdout, Stdout};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use ratatui::crossterm::event::{self, Event, KeyEventKind};
-use ratatui::crossterm::terminal::{
-    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
-};
-use ratatui::crossterm::ExecutableCommand;
+use ratatui::layout::Rect;
 use ratatui::prelude::CrosstermBackend;
 use ratatui::Terminal;
 
 use voiceforge::app::{Action, AppState, FileInfo};
 use voiceforge::audio;
-use voiceforge::dsp::processing::{ProcessingCommand, ProcessingHandle, ProcessingResult};
-use voiceforge::dsp::spectrum::{compute_spectrum, extract_window, FFT_SIZE};
+use voiceforge::config::Config;
+use voiceforge::dsp::processing::{
+    AnalysisOptions, ExportJob, ProcessingCommand, ProcessingHandle, ProcessingResult, PunchJob,
+};
+use voiceforge::dsp::spectrum::{
+    compute_spectrum, compute_spectrum_dbfs, extract_window, find_peaks,
+};
 use voiceforge::input::handler::handle_key_event;
-use voiceforge::ui::layout;
+use voiceforge::paths::{self, DirKind};
+use voiceforge::session;
+use voiceforge::terminal as tty;
+use voiceforge::ui::{file_picker, layout};
+
+/// Log file name inside the state directory.
+const LOG_FILE: &str = "voiceforge.log";
 
-/// Initialize file-based logging. All output goes to `voiceforge.log` — never to
+/// Initialize file-based logging. All output goes to `voiceforge.log` in the
+/// per-user state directory (the CWD if that can't be created) — never to
 /// stderr/stdout — so the ratatui TUI is never corrupted.
 fn setup_logger() -> Result<(), fern::InitError> {
+    let (log_path, dir_error) = match paths::ensure_dir(DirKind::State) {
+        Ok(dir) => (dir.join(LOG_FILE), None),
+        Err(e) => (PathBuf::from(LOG_FILE), Some(e)),
+    };
     fern::Dispatch::new()
         .format(|out, message, record| {
             let secs = std::time::SystemTime::now()
@@ -39,8 +52,11 @@ fn setup_logger() -> Result<(), fern::InitError> {
         } else {
             log::LevelFilter::Info
         })
-        .chain(fern::log_file("voiceforge.log")?)
+        .chain(fern::log_file(&log_path)?)
         .apply()?;
+    if let Some(e) = dir_error {
+        log::warn!("{e}; logging to the current directory");
+    }
     Ok(())
 }
 
@@ -49,18 +65,21 @@ struct TerminalGuard;
 
 impl Drop for TerminalGuard {
     fn drop(&mut self) {
-        if let Err(e) = disable_raw_mode() {
-            log::warn!("failed to disable raw mode: {e}");
-        }
-        if let Err(e) = stdout().execute(LeaveAlternateScreen) {
-            log::warn!("failed to leave alternate screen: {e}");
-        }
+        tty::leave();
     }
 }
 
-/// Debounce delay for resynthesize / effects commands.
-const RESYNTH_DEBOUNCE: Duration = Duration::from_millis(150);
-const EFFECTS_DEBOUNCE: Duration = Duration::from_millis(80);
+/// Ctrl+Z / SIGTSTP: hand the terminal back to the shell, stop, and on
+/// SIGCONT clear the screen so the next frame repaints everything.
+fn suspend(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> io::Result<()> {
+    log::info!("suspending");
+    tty::suspend()?;
+    log::info!("resumed");
+    terminal.clear()
+}
+
+/// Peaks read out under the spectrum in inspect mode.
+const INSPECT_PEAKS: usize = 3;
 
 fn main() -> io::Result<()> {
     // Initialize logging before anything else. If it fails (e.g., can't create
@@ -71,18 +90,50 @@ fn main() -> io::Result<()> {
     let sigint = Arc::new(std::sync::atomic::AtomicBool::new(false));
     signal_hook::flag::register(signal_hook::consts::SIGINT, Arc::clone(&sigint))
         .expect("failed to register SIGINT handler");
+    // Job control: a SIGTSTP from outside the terminal (raw mode turns Ctrl+Z
+    // into a key) must restore the terminal before stopping, and a SIGCONT
+    // after any stop means the shell may have reset the terminal. Neither
+    // signal exists off unix, where `sigcont` stays false.
+    #[cfg(unix)]
+    let sigtstp = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let sigcont = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    #[cfg(unix)]
+    {
+        signal_hook::flag::register(signal_hook::consts::SIGTSTP, Arc::clone(&sigtstp))
+            .expect("failed to register SIGTSTP handler");
+        signal_hook::flag::register(signal_hook::consts::SIGCONT, Arc::clone(&sigcont))
+            .expect("failed to register SIGCONT handler");
+    }
 
     // Set up terminal
-    enable_raw_mode()?;
-    stdout().execute(EnterAlternateScreen)?;
+    tty::enter()?;
     let _guard = TerminalGuard;
 
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
 
     let mut app = AppState::new();
+    app.config = Config::from_args(std::env::args().skip(1));
+    app.effects_order = app.config.effects_order.clone();
+    let size = terminal.size()?;
+    app.clamp_file_picker(file_picker::visible_rows(Rect::new(
+        0,
+        0,
+        size.width,
+        size.height,
+    )));
 
     // Spawn processing thread
-    let processing = ProcessingHandle::spawn();
+    app.publish_effects();
+    let mut processing = ProcessingHandle::spawn_with(
+        AnalysisOptions {
+            f0_candidates: app.config.extended_analysis,
+            world: app.config.analysis,
+            cache_params: app.config.cache_analysis,
+            preview_from_secs: app.config.preview_analysis_secs,
+            normalize_synthesis: app.config.normalize_synthesis,
+        },
+        Arc::clone(&app.effects),
+    );
 
     // Keep stream alive in main — it's not Send so can't go into AppState.
     let mut _stream: Option<cpal::Stream> = None;
@@ -97,25 +148,50 @@ fn main() -> io::Result<()> {
     // Deferred stream initialization (moved out of result drain to avoid blocking)
     let mut pending_stream_init: Option<Arc<audio::decoder::AudioData>> = None;
 
-    let args: Vec<String> = std::env::args().collect();
-    if args.len() >= 2 {
-        let path = args[1].clone();
+    // When the in-flight resynthesis / effects pass was dispatched, for
+    // deferring the next one while listening to A.
+    let mut synth_dispatched: Option<Instant> = None;
+
+    // Set while re-analyzing after a worker restart, so the recovery pass is
+    // not counted as a new analysis in the session stats.
+    let mut recovering_worker = false;
+
+    // Set while a user-requested re-analysis (Reanalyze, voicing threshold)
+    // runs; like recovery, it is not a new analysis for the session stats.
+    let mut reanalyzing = false;
+
+    // (position, inspect, A/B side, revision) the paused spectrum was computed
+    // for; `None` while playing.
+    let mut paused_spectrum_key: Option<(usize, bool, bool, u64)> = None;
+
+    // Session record for the export the worker is writing, filed on success.
+    let mut pending_export: Option<session::ExportRecord> = None;
+
+    if app.config.safe_mode {
+        log::warn!("safe mode: analysis, true colour and heavy spectrum settings disabled");
+    }
+    if let Some(path) = app.config.file.clone() {
         let p = Path::new(&path);
         if p.exists() && p.is_file() {
             current_file_path = Some(path.clone());
             app.prepare_for_load();
             resynth_pending = None;
             effects_pending = None;
-            processing.send(ProcessingCommand::Load(path));
+            processing.send(app.config.load_command(path));
         } else {
             app.set_status(format!("Error: file not found: {}", path));
         }
     }
 
+    // First run (no tour marker, no history yet): walk through the panels.
+    if paths::data_dir().is_some_and(|dir| session::is_first_run(&dir)) {
+        app.start_tour();
+    }
+
     // L-12: Status message auto-clear timeout.
     const STATUS_TIMEOUT: Duration = Duration::from_secs(5);
 
-    // Main event loop — ~30 fps
+    // Main event loop — ~30 fps, ~2 fps while the terminal is unfocused
     loop {
         // L-12: Auto-clear status message after timeout.
         if let Some(t) = app.status_message_time {
@@ -144,15 +220,16 @@ fn main() -> io::Result<()> {
                     Ok((stream, state)) => {
                         _stream = Some(stream);
                         app.playback = state;
-                        let gain_db = app.master_sliders[0].value as f32;
-                        app.playback.live_gain.store(
-                            10.0_f32.powf(gain_db / 20.0).to_bits(),
-                            std::sync::atomic::Ordering::Relaxed,
-                        );
-                        app.playback.loop_enabled.store(
-                            app.loop_enabled,
-                            std::sync::atomic::Ordering::Relaxed,
-                        );
+                        app.playback
+                            .set_live_gain_db(app.master_sliders[0].value as f32);
+                        app.playback
+                            .set_output_ceiling_db(app.config.output_ceiling_db);
+                        app.playback
+                            .dither
+                            .store(app.config.dither, Ordering::Relaxed);
+                        app.playback
+                            .loop_enabled
+                            .store(app.loop_enabled, std::sync::atomic::Ordering::Relaxed);
                     }
                     Err(e) => {
                         app.set_status(format!("Playback error: {e}"));
@@ -167,20 +244,57 @@ fn main() -> io::Result<()> {
             .store(app.loop_enabled, Ordering::Relaxed);
 
         // Update spectrum bins from current playback position
-        if app.playback.playing.load(Ordering::Acquire) {
+        if !app.terminal_focused {
+            // Background pane: keep the last bins; updates resume on FocusGained.
+        } else if app.playback.playing.load(Ordering::Acquire) {
+            paused_spectrum_key = None;
             if let Some(ref lock) = app.playback.audio_lock {
                 match lock.try_read() {
                     Ok(guard) => {
                         let pos = app.playback.position.load(Ordering::Acquire);
-                        let window = extract_window(&guard, pos, FFT_SIZE);
+                        let fft_size = app.config.spectrum_fft_size;
+                        let window = extract_window(&guard, pos, fft_size);
 
-                        app.spectrum_bins = compute_spectrum(&window, FFT_SIZE);
+                        app.spectrum_bins = compute_spectrum(&window, fft_size);
+                        app.spectrum_range.update(&app.spectrum_bins);
                     }
                     Err(_) => {
                         // try_read failed — spectrum will not update until lock is available
                     }
                 }
             }
+        } else if let Some(ref lock) = app.playback.audio_lock {
+            // Paused: recompute once from the exact paused position (the last
+            // live frame is usually a callback or two behind), and again after
+            // a step/seek, an inspect toggle, or a buffer swap.
+            let pos = app.playback.position.load(Ordering::Acquire);
+            let key = (
+                pos,
+                app.spectrum_inspect,
+                app.ab_original,
+                app.processed_revision,
+            );
+            if paused_spectrum_key != Some(key) {
+                if let Ok(guard) = lock.try_read() {
+                    paused_spectrum_key = Some(key);
+                    if app.spectrum_inspect {
+                        let fft_size = app.config.inspect_fft_size;
+                        let window = extract_window(&guard, pos, fft_size);
+                        app.spectrum_bins = compute_spectrum(&window, fft_size);
+                        app.spectrum_peaks = find_peaks(
+                            &compute_spectrum_dbfs(&window, fft_size),
+                            fft_size,
+                            guard.sample_rate,
+                            INSPECT_PEAKS,
+                        );
+                    } else {
+                        let fft_size = app.config.spectrum_fft_size;
+                        let window = extract_window(&guard, pos, fft_size);
+                        app.spectrum_bins = compute_spectrum(&window, fft_size);
+                        app.spectrum_peaks.clear();
+                    }
+                }
+            }
         }
 
         terminal.draw(|frame| {
@@ -191,6 +305,51 @@ fn main() -> io::Result<()> {
             break;
         }
 
+        #[cfg(unix)]
+        if sigtstp.swap(false, Ordering::Relaxed) {
+            suspend(&mut terminal)?;
+            sigcont.store(false, Ordering::Relaxed);
+            continue;
+        }
+        #[cfg(unix)]
+        if sigcont.swap(false, Ordering::Relaxed) {
+            // Stopped by something other than SIGTSTP (e.g. SIGSTOP).
+            tty::enter()?;
+            terminal.clear()?;
+            continue;
+        }
+
+        // Watchdog: a panic that escapes catch_unwind (e.g. while handling a
+        // panic) ends the worker silently. Respawn it and rebuild its caches.
+        if !processing.is_alive() {
+            log::error!("processing thread died — respawning");
+            processing.respawn();
+            resynth_pending = None;
+            effects_pending = None;
+            synth_dispatched = None;
+            app.frame_snapshot_requested = false;
+            app.frame_info_requested = false;
+            let export_lost = app.export_in_flight.take().is_some();
+            app.export_progress = None;
+            pending_export = None;
+            if let Some(ref original) = app.original_audio {
+                recovering_worker = true;
+                app.processing_status = Some("Re-analyzing after restart...".to_string());
+                processing.send(ProcessingCommand::Analyze(original.as_ref().clone()));
+            } else if let Some(ref path) = current_file_path {
+                // Died before analysis finished — start the load over.
+                processing.send(app.config.load_command(path.clone()));
+            }
+            if export_lost {
+                app.set_status("⚠ Processing thread crashed during export — restarted".to_string());
+            } else {
+                app.set_status("⚠ Processing thread crashed — restarted".to_string());
+            }
+        }
+
+        // Resynthesis previews start where the listener is.
+        processing.set_playhead(app.source_playhead_secs());
+
         // Poll for processing results (non-blocking)
         while let Some(result) = processing.try_recv() {
             match result {
@@ -201,6 +360,10 @@ fn main() -> io::Result<()> {
                         // Continue draining results
                     } else {
                         let audio = Arc::new(audio_data);
+                        if !app.config.analyze_on_load {
+                            // Playback-only load: no analysis will clear the status.
+                            app.processing_status = None;
+                        }
                         // M-2: Don't clear processing_status here; analysis is still pending.
                         // Status messages from the processing thread will update it with
                         // "Analyzing... 25/50/75/100%" as analysis progresses.
@@ -213,6 +376,7 @@ fn main() -> io::Result<()> {
                             if let Some(file_info) = build_file_info(path, &audio) {
                                 app.file_info = Some(file_info);
                                 app.audio_data = Some(Arc::clone(&audio));
+                                app.session_stats.record_file_loaded();
                                 // Defer playback start to avoid blocking the result drain
                                 pending_stream_init = Some(audio);
                             } else {
@@ -225,82 +389,117 @@ fn main() -> io::Result<()> {
                 }
                 ProcessingResult::AnalysisDone(mono_original) => {
                     app.processing_status = None;
+                    app.analysis_lost = false;
+                    let recovered = std::mem::take(&mut recovering_worker);
+                    let reanalyzed = std::mem::take(&mut reanalyzing);
+                    if !recovered && !reanalyzed {
+                        app.session_stats
+                            .record_analysis(mono_original.duration_secs());
+                    }
+                    let format = mono_original.format();
                     app.original_audio = Some(Arc::new(mono_original));
+                    // New analysis: the inspector re-reads its frame.
+                    app.clear_frame_info();
                     // Auto-resynthesize with current slider values
                     let values = app.world_slider_values();
-                    let fx = app.effects_params();
-                    processing.send(ProcessingCommand::Resynthesize(values, fx));
+                    let fx_revision = app.publish_effects();
+                    synth_dispatched = Some(Instant::now());
+                    processing.send(ProcessingCommand::Resynthesize(
+                        values,
+                        fx_revision,
+                        format,
+                        None,
+                    ));
                 }
                 ProcessingResult::SynthesisDone(audio_data) => {
+                    if let Some(Err(e)) = app
+                        .analyzed_format()
+                        .map(|expected| expected.check(audio_data.format(), "playback"))
+                    {
+                        // Never play a buffer built for another file's format.
+                        log::error!("SynthesisDone: discarding — {e}");
+                        synth_dispatched = None;
+                        app.processing_status = Some(format!("Format error: {e}"));
+                        continue;
+                    }
                     app.processing_status = None;
                     app.status_message = None;
-                    let new_audio = Arc::new(audio_data);
-
-                    if app.ab_original {
-                        // User is listening to original — just store the new
-                        // processed audio without touching the stream.
-                        app.audio_data = Some(new_audio);
-                    } else {
-                        // User is on B (processed) — swap or rebuild.
-
-                        // Adjust playback position for channel count changes
-                        // (e.g. stereo original → mono after WORLD synthesis).
-                        if let Some(ref mut info) = app.file_info {
-                            let old_channels = info.channels as usize;
-                            let new_channels = new_audio.channels as usize;
-
-                            if old_channels != new_channels
-                                && old_channels > 0
-                                && new_channels > 0
-                            {
-                                let current_pos =
-                                    app.playback.position.load(Ordering::Acquire);
-                                let frame = current_pos / old_channels;
-                                let new_pos =
-                                    (frame * new_channels).min(new_audio.samples.len());
-                                app.playback.position.store(new_pos, Ordering::Release);
-                            }
-
-                            info.channels = new_audio.channels;
-                            info.total_samples = new_audio.samples.len();
-                            info.duration_secs = new_audio.duration_secs();
-                        }
-
-                        // H-4: Clamp position inside swap_audio's write-lock to avoid TOCTOU.
-                        let max_samples = new_audio.samples.len();
-                        let current_pos = app.playback.position.load(Ordering::Acquire);
-                        let clamped_pos = current_pos.min(max_samples);
-
-                        // Swap audio in running stream if we have a lock, else defer rebuild
-                        if let Some(ref lock) = app.playback.audio_lock {
-                            audio::playback::swap_audio(
-                                lock,
-                                Arc::clone(&new_audio),
-                                Some((&app.playback.position, clamped_pos)),
-                            );
-                            app.audio_data = Some(new_audio);
-                        } else {
-                            // Defer stream rebuild to avoid blocking the result drain
-                            pending_stream_init = Some(Arc::clone(&new_audio));
-                            app.audio_data = Some(new_audio);
-                        }
+                    synth_dispatched = None;
+                    app.processed_revision += 1;
+                    // A resynthesis rebuilds B from the analysis: any
+                    // punch-in is gone.
+                    app.b_edited = false;
+                    install_processed(&mut app, Arc::new(audio_data), &mut pending_stream_init);
+                }
+                ProcessingResult::SynthesisTimed(elapsed) => {
+                    app.session_stats.record_synthesis(elapsed);
+                }
+                ProcessingResult::PitchCurve(curve) => {
+                    app.pitch_curve = Some(curve);
+                }
+                ProcessingResult::PreviewReady(clip) => {
+                    // Only over a rendered B of the same length; the full
+                    // SynthesisDone follows either way.
+                    let spliced = app
+                        .audio_data
+                        .as_deref()
+                        .filter(|_| app.processed_revision > 0 && !app.analysis_lost)
+                        .and_then(|b| clip.splice_into(b));
+                    if let Some(audio) = spliced {
+                        install_processed(&mut app, Arc::new(audio), &mut pending_stream_init);
                     }
                 }
                 ProcessingResult::Status(msg) => {
                     app.processing_status = Some(msg);
                 }
+                ProcessingResult::AnalysisInvalidated => {
+                    // Queued slider changes would only hit the missing analysis.
+                    resynth_pending = None;
+                    effects_pending = None;
+                    synth_dispatched = None;
+                    app.invalidate_analysis();
+                }
+                ProcessingResult::AnalysisRefined => {
+                    // B was rendered from the preview; render it again.
+                    app.clear_frame_info();
+                    resynth_pending = Some(Instant::now());
+                }
+                ProcessingResult::PunchedIn(audio_data) => {
+                    app.processing_status = None;
+                    let added = audio_data.duration_secs()
+                        - app.audio_data.as_ref().map_or(0.0, |a| a.duration_secs());
+                    app.processed_revision += 1;
+                    app.b_edited = true;
+                    install_processed(&mut app, Arc::new(audio_data), &mut pending_stream_init);
+                    app.set_status(format!(
+                        "Punched in ({added:+.2} s) — export to keep; slider changes re-render B"
+                    ));
+                }
+                ProcessingResult::F0Corrected(choice, written) => {
+                    app.f0_edited = true;
+                    // The inspector re-reads the corrected frame.
+                    app.clear_frame_info();
+                    app.set_status(format!(
+                        "f0 candidate #{} applied to {written} frames",
+                        choice.rank + 1
+                    ));
+                    resynth_pending = Some(Instant::now());
+                }
+                ProcessingResult::Classified(classification) => {
+                    app.content_class = Some(classification);
+                    if let Some(class) = app.apply_content_preset() {
+                        app.set_status(format!("{} preset applied", class.label()));
+                        app.publish_effects();
+                        effects_pending = Some(Instant::now());
+                    }
+                }
                 ProcessingResult::DirectoryListing(prefix, entries) => {
                     // Discard stale: input may have changed since scan was dispatched
                     if prefix == app.file_picker_input {
                         app.file_picker_matches = entries;
                         app.file_picker_scroll = 0;
-                        if let Some(sel) = app.file_picker_selected {
-                            if app.file_picker_matches.is_empty() {
-                                app.file_picker_selected = None;
-                            } else if sel >= app.file_picker_matches.len() {
-                                app.file_picker_selected = Some(app.file_picker_matches.len() - 1);
-                            }
-                        }
+                        let rows = app.file_picker_rows;
+                        app.clamp_file_picker(rows);
                     }
                 }
                 ProcessingResult::AudioPrecheckDone(path) => {
@@ -309,7 +508,7 @@ fn main() -> io::Result<()> {
                         current_file_path = Some(path.clone());
                         resynth_pending = None;
                         effects_pending = None;
-                        processing.send(ProcessingCommand::Load(path));
+                        processing.send(app.config.load_command(path));
                     }
                 }
                 ProcessingResult::AudioPrecheckFailed(path, msg) => {
@@ -319,134 +518,247 @@ fn main() -> io::Result<()> {
                         app.set_status(format!("Error: {msg}"));
                     }
                 }
+                ProcessingResult::Loudness(measured) => {
+                    app.ab_loudness = Some(measured);
+                }
+                ProcessingResult::FrameSnapshot(snapshot) => {
+                    // Discard if the preview was dismissed while the request was in flight.
+                    if app.frame_snapshot_requested {
+                        app.frame_snapshot_requested = false;
+                        app.frame_snapshot = Some(snapshot);
+                    }
+                }
+                ProcessingResult::FrameInfo(info) => {
+                    // Discard if the inspector was closed while the request was in flight.
+                    if app.frame_info_requested {
+                        app.frame_info_requested = false;
+                        app.frame_info = Some(info);
+                    }
+                }
+                ProcessingResult::ExportProgress(status) => {
+                    if app.export_in_flight.is_some() {
+                        app.export_progress = Some(status);
+                    }
+                }
+                ProcessingResult::ExportDone(dest_path, outcome) => {
+                    let label = app.export_in_flight.take();
+                    if outcome.is_ok() && label.is_some_and(|l| l.starts_with('B')) {
+                        app.b_edited = false;
+                    }
+                    app.export_progress = None;
+                    let record = pending_export.take();
+                    match outcome {
+                        Ok(()) => {
+                            if let Some(record) = record {
+                                append_history(&session::format_export_line(
+                                    session::unix_timestamp(),
+                                    &record,
+                                ));
+                                app.session_stats.record_export(record);
+                            }
+                            app.set_status(format!("Saved: {dest_path}"));
+                        }
+                        Err(e) => {
+                            app.set_status(format!("Export error: {e}"));
+                        }
+                    }
+                }
+            }
+        }
+
+        // The spectral preview lives only while a spectral slider is selected;
+        // the next adjustment fetches a fresh frame at the then-current playhead.
+        if !app.spectral_slider_selected() {
+            app.frame_snapshot = None;
+            app.frame_snapshot_requested = false;
+        }
+
+        // The inspector follows the playhead, one request at a time.
+        if app.original_audio.is_some() && !app.analysis_lost {
+            let playhead = app.source_playhead_secs();
+            if app.inspector_wants_frame(playhead) {
+                app.frame_info_requested = true;
+                app.frame_info_playhead = Some(playhead);
+                processing.send(ProcessingCommand::InspectFrame(playhead));
             }
         }
 
         // Check debounce timers
         if let Some(deadline) = resynth_pending {
-            if Instant::now() >= deadline {
+            if Instant::now() >= deadline && app.defer_resynthesis(synth_dispatched) {
+                // On A: wait for the pass in flight, then send only the latest.
+                resynth_pending = Some(Instant::now() + app.config.resynth_debounce);
+            } else if Instant::now() >= deadline {
                 resynth_pending = None;
-                effects_pending = None; // Resynthesize includes effects
-                let values = app.world_slider_values();
-                let fx = app.effects_params();
-                processing.send(ProcessingCommand::Resynthesize(values, fx));
+                // Resynthesize includes effects.
+                effects_pending = None;
+                // Without analysis there is nothing to resynthesize yet;
+                // AnalysisDone resynthesizes with the current sliders.
+                if let Some(format) = app.resynthesis_format() {
+                    let values = app.world_slider_values();
+                    let fx_revision = app.publish_effects();
+                    synth_dispatched = Some(Instant::now());
+                    processing.send(ProcessingCommand::Resynthesize(
+                        values,
+                        fx_revision,
+                        format,
+                        None,
+                    ));
+                }
             }
         }
         if let Some(deadline) = effects_pending {
-            if Instant::now() >= deadline {
+            if Instant::now() >= deadline && app.defer_resynthesis(synth_dispatched) {
+                effects_pending = Some(Instant::now() + app.config.effects_debounce);
+            } else if Instant::now() >= deadline {
                 effects_pending = None;
-                let fx = app.effects_params();
-                processing.send(ProcessingCommand::ReapplyEffects(fx));
+                if let Some(format) = app.resynthesis_format() {
+                    let fx_revision = app.publish_effects();
+                    synth_dispatched = Some(Instant::now());
+                    processing.send(ProcessingCommand::ReapplyEffects(fx_revision, format));
+                }
             }
         }
 
-        if event::poll(Duration::from_millis(33))? {
-            if let Event::Key(key) = event::read()? {
+        if event::poll(app.frame_interval())? {
+            let event = event::read()?;
+            if let Event::FocusGained | Event::FocusLost = event {
+                app.terminal_focused = matches!(event, Event::FocusGained);
+            }
+            if let Event::Resize(width, height) = event {
+                // The picker may have lost rows: keep scroll and selection
+                // inside what is still visible.
+                app.clamp_file_picker(file_picker::visible_rows(Rect::new(0, 0, width, height)));
+            }
+            if let Event::Key(key) = event {
                 if key.kind != KeyEventKind::Press {
                     continue;
                 }
-                if let Some(action) = handle_key_event(key, &mut app) {
+                let action = handle_key_event(key, &mut app);
+                // Realtime readers follow slider moves without waiting for
+                // the debounce; unchanged parameters publish nothing.
+                app.publish_effects();
+                if let Some(action) = action {
                     match action {
                         Action::Quit => break,
                         Action::ScanDirectory => {
-                            processing.send(ProcessingCommand::ScanDirectory(app.file_picker_input.clone()));
+                            processing.send(ProcessingCommand::ScanDirectory(
+                                app.file_picker_input.clone(),
+                            ));
                         }
                         Action::PrecheckAudio(path) => {
                             app.processing_status = Some("Checking...".to_string());
                             app.awaiting_load_path = Some(path.clone());
                             processing.send(ProcessingCommand::PrecheckAudio(path));
                         }
+                        Action::Resynthesize | Action::ReapplyEffects
+                            if !app.config.analyze_on_load =>
+                        {
+                            // Effects run on the post-WORLD buffer, which needs analysis too.
+                            app.set_status("Analysis disabled in safe mode".to_string());
+                        }
                         Action::Resynthesize => {
+                            if app.note_processing_change() {
+                                swap_to_selected_side(&mut app);
+                            }
                             // Debounce: reset timer on each slider change
-                            resynth_pending = Some(Instant::now() + RESYNTH_DEBOUNCE);
+                            resynth_pending = Some(Instant::now() + app.config.resynth_debounce);
+                            if app.spectral_slider_selected()
+                                && app.frame_snapshot.is_none()
+                                && !app.frame_snapshot_requested
+                                && app.original_audio.is_some()
+                                && !app.analysis_lost
+                            {
+                                app.frame_snapshot_requested = true;
+                                processing.send(ProcessingCommand::SnapshotFrame(
+                                    app.source_playhead_secs(),
+                                ));
+                            }
                         }
                         Action::ReapplyEffects => {
-                            effects_pending = Some(Instant::now() + EFFECTS_DEBOUNCE);
+                            if app.note_processing_change() {
+                                swap_to_selected_side(&mut app);
+                            }
+                            effects_pending = Some(Instant::now() + app.config.effects_debounce);
+                        }
+                        Action::Reanalyze => {
+                            if let Some(ref original) = app.original_audio {
+                                reanalyzing = true;
+                                app.processing_status = Some("Re-analyzing...".to_string());
+                                processing
+                                    .send(ProcessingCommand::Analyze(original.as_ref().clone()));
+                            }
+                        }
+                        Action::SetVoicingThreshold(threshold) => {
+                            app.config.analysis.d4c_threshold = threshold;
+                            processing.set_analysis(app.config.analysis);
+                            if let Some(ref original) = app.original_audio {
+                                reanalyzing = true;
+                                app.processing_status = Some(format!(
+                                    "Re-analyzing (voicing threshold {threshold:.2})..."
+                                ));
+                                processing
+                                    .send(ProcessingCommand::Analyze(original.as_ref().clone()));
+                            }
                         }
-                        Action::LiveGain(linear) => {
-                            app.playback
-                                .live_gain
-                                .store(linear.to_bits(), std::sync::atomic::Ordering::Relaxed);
+                        Action::ApplyF0Candidate(choice) => {
+                            processing.send(ProcessingCommand::ApplyF0Candidate(choice));
+                        }
+                        Action::PunchIn(path) => {
+                            if let (Some(splice), Some(base)) =
+                                (app.punch_splice(), app.audio_data.clone())
+                            {
+                                app.processing_status = Some("Punching in...".to_string());
+                                processing.send(ProcessingCommand::PunchIn(PunchJob {
+                                    base,
+                                    path,
+                                    splice,
+                                }));
+                            }
+                            app.end_punch_in();
+                        }
+                        Action::LiveGain(gain_db) => {
+                            app.playback.set_live_gain_db(gain_db);
                         }
                         Action::ExportWav(dest_path) => {
-                            // Export what the user is hearing: original when
-                            // A/B is on original, processed otherwise.
-                            let source = if app.ab_original {
-                                app.original_audio.as_ref()
-                            } else {
-                                app.audio_data.as_ref()
-                            };
-                            if let Some(audio) = source {
-                                let mut samples = audio.samples.clone();
-                                // Bake live gain (not stored in audio buffer).
-                                let gain_db = app.master_sliders[0].value as f32;
-                                if gain_db != 0.0 {
-                                    voiceforge::dsp::effects::apply_gain(
-                                        &mut samples,
-                                        gain_db,
-                                    );
-                                }
-                                match audio::export::export_wav(
-                                    &samples,
-                                    audio.sample_rate,
-                                    audio.channels,
-                                    Path::new(&dest_path),
-                                ) {
-                                    Ok(()) => {
-                                        app.set_status(format!("Saved: {dest_path}"));
-                                    }
-                                    Err(e) => {
-                                        app.set_status(format!("Export error: {e}"));
-                                    }
-                                }
+                            if let Some(ref running) = app.export_in_flight {
+                                app.set_status(format!(
+                                    "Export of {running} still running — try again when it finishes"
+                                ));
+                            } else if let Some((audio, label)) = app.export_snapshot() {
+                                // Export what the user is hearing, captured now:
+                                // later resyntheses can't change this buffer.
+                                pending_export = Some(session::ExportRecord {
+                                    source: current_file_path.clone().unwrap_or_default(),
+                                    output: dest_path.clone(),
+                                    world: app.world_slider_values(),
+                                    effects: app.effects_params(),
+                                });
+                                app.export_in_flight = Some(label.clone());
+                                app.export_progress = None;
+                                processing.send(ProcessingCommand::Export(ExportJob {
+                                    audio,
+                                    // Bake live gain (not stored in audio buffer).
+                                    gain_db: app.master_sliders[0].value as f32,
+                                    path: dest_path,
+                                    label,
+                                }));
                             }
                         }
+                        Action::TourFinished => {
+                            let written = paths::ensure_dir(DirKind::Data)
+                                .and_then(|dir| session::write_tour_marker(&dir));
+                            if let Err(e) = written {
+                                log::warn!("could not record the finished tour: {e}");
+                            }
+                        }
+                        Action::Suspend => {
+                            suspend(&mut terminal)?;
+                            sigcont.store(false, Ordering::Relaxed);
+                        }
                         Action::ToggleAB => {
                             // ab_original was already flipped by the handler
-                            if let Some(ref lock) = app.playback.audio_lock {
-                                let target = if app.ab_original {
-                                    app.original_audio.as_ref()
-                                } else {
-                                    app.audio_data.as_ref()
-                                };
-                                if let Some(audio) = target {
-                                    // Scale position proportionally if buffer lengths differ
-                                    // CR-2: Recover from poisoned lock.
-                                    let old_len = {
-                                        let guard =
-                                            lock.read().unwrap_or_else(|e| e.into_inner());
-                                        guard.samples.len()
-                                    };
-                                    let new_len = audio.samples.len();
-                                    let new_pos = if old_len != new_len {
-                                        let pos =
-                                            app.playback.position.load(Ordering::Acquire);
-                                        let fraction = if old_len > 0 {
-                                            pos as f64 / old_len as f64
-                                        } else {
-                                            0.0
-                                        };
-                                        (fraction * new_len as f64).round().min(new_len as f64) as usize
-                                    } else {
-                                        let pos = app.playback.position.load(Ordering::Acquire);
-                                        pos.min(new_len)
-                                    };
-
-                                    // Update file_info for the active buffer
-                                    if let Some(ref mut info) = app.file_info {
-                                        info.total_samples = audio.samples.len();
-                                        info.duration_secs = audio.duration_secs();
-                                        info.channels = audio.channels;
-                                    }
-
-                                    // H-4: Position clamp inside swap_audio's write-lock
-                                    audio::playback::swap_audio(
-                                        lock,
-                                        Arc::clone(audio),
-                                        Some((&app.playback.position, new_pos)),
-                                    );
-                                }
-                            }
+                            swap_to_selected_side(&mut app);
                         }
                     }
                 }
@@ -458,12 +770,129 @@ fn main() -> io::Result<()> {
     // This ensures the main thread is never blocked waiting for thread completion,
     // allowing the terminal guard to drop immediately and restore terminal state.
     processing.send(ProcessingCommand::Shutdown);
+    append_history(&session::format_session_line(
+        session::unix_timestamp(),
+        &app.session_stats,
+    ));
     // processing drops here → Drop impl detaches the JoinHandle (does NOT join)
 
     Ok(())
     // _guard Drop restores terminal
 }
 
+/// Make `new_audio` the processed buffer (B). On B it replaces the playing
+/// buffer in place (or once the stream is rebuilt), keeping the position;
+/// on A it is only stored.
+fn install_processed(
+    app: &mut AppState,
+    new_audio: Arc<audio::decoder::AudioData>,
+    pending_stream_init: &mut Option<Arc<audio::decoder::AudioData>>,
+) {
+    if app.ab_original {
+        // User is listening to original — just store the new
+        // processed audio without touching the stream.
+        app.audio_data = Some(new_audio);
+    } else {
+        // User is on B (processed) — swap or rebuild.
+
+        // Adjust playback position for channel count changes
+        // (e.g. stereo original → mono after WORLD synthesis).
+        if let Some(ref mut info) = app.file_info {
+            let old_channels = info.channels as usize;
+            let new_channels = new_audio.channels as usize;
+
+            if old_channels != new_channels && old_channels > 0 && new_channels > 0 {
+                let current_pos = app.playback.position.load(Ordering::Acquire);
+                let frame = current_pos / old_channels;
+                let new_pos = (frame * new_channels).min(new_audio.samples.len());
+                app.playback.position.store(new_pos, Ordering::Release);
+            }
+
+            info.channels = new_audio.channels;
+            info.total_samples = new_audio.samples.len();
+            info.duration_secs = new_audio.duration_secs();
+        }
+
+        // H-4: Clamp position inside swap_audio's write-lock to avoid TOCTOU.
+        let max_samples = new_audio.samples.len();
+        let current_pos = app.playback.position.load(Ordering::Acquire);
+        let clamped_pos = current_pos.min(max_samples);
+
+        // Swap audio in running stream if we have a lock, else defer rebuild
+        if let Some(ref lock) = app.playback.audio_lock {
+            audio::playback::swap_audio(
+                lock,
+                Arc::clone(&new_audio),
+                Some((&app.playback.position, clamped_pos)),
+            );
+            app.audio_data = Some(new_audio);
+        } else {
+            // Defer stream rebuild to avoid blocking the result drain
+            *pending_stream_init = Some(Arc::clone(&new_audio));
+            app.audio_data = Some(new_audio);
+        }
+    }
+}
+
+/// Point the stream at the side `app.ab_original` selects (A = original,
+/// B = processed), keeping the relative playback position.
+fn swap_to_selected_side(app: &mut AppState) {
+    if let Some(ref lock) = app.playback.audio_lock {
+        let target = if app.ab_original {
+            app.original_audio.as_ref()
+        } else {
+            app.audio_data.as_ref()
+        };
+        if let Some(audio) = target {
+            // Scale position proportionally if buffer lengths differ
+            // CR-2: Recover from poisoned lock.
+            let old_len = {
+                let guard = lock.read().unwrap_or_else(|e| e.into_inner());
+                guard.samples.len()
+            };
+            let new_len = audio.samples.len();
+            let new_pos = if old_len != new_len {
+                let pos = app.playback.position.load(Ordering::Acquire);
+                let fraction = if old_len > 0 {
+                    pos as f64 / old_len as f64
+                } else {
+                    0.0
+                };
+                (fraction * new_len as f64).round().min(new_len as f64) as usize
+            } else {
+                let pos = app.playback.position.load(Ordering::Acquire);
+                pos.min(new_len)
+            };
+
+            // Update file_info for the active buffer
+            if let Some(ref mut info) = app.file_info {
+                info.total_samples = audio.samples.len();
+                info.duration_secs = audio.duration_secs();
+                info.channels = audio.channels;
+            }
+
+            // H-4: Position clamp inside swap_audio's write-lock
+            audio::playback::swap_audio(
+                lock,
+                Arc::clone(audio),
+                Some((&app.playback.position, new_pos)),
+            );
+        }
+    }
+}
+
+/// Append a line to `history.log` in the data dir. Failures are logged, never fatal.
+fn append_history(line: &str) {
+    match session::history_path() {
+        Ok(path) => {
+            if let Err(e) = session::append_history_line(&path, line) {
+                log::warn!("failed to append to {}: {e}", path.display());
+            }
+        }
+        Err(e) => log::warn!("history log unavailable: {e}"),
+    }
+}
+
 /// Build FileInfo from a file path and decoded audio data.
 fn build_file_info(path: &str, audio: &Arc<audio::decoder::AudioData>) -> Option<FileInfo> {
     let p = Path::new(path);
@@ -481,4 +910,3 @@ fn build_file_info(path: &str, audio: &Arc<audio::decoder::AudioData>) -> Option
         total_samples: audio.samples.len(),
     })
 }
-
@@ -0,0 +1,212 @@
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Directory name used under each per-user base directory.
+const APP_DIR: &str = "voiceforge";
+
+/// Operating-system conventions for per-user directories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    /// XDG base directories (Linux, BSDs).
+    Unix,
+    /// `~/Library/...`.
+    MacOs,
+    /// `%APPDATA%` / `%LOCALAPPDATA%`.
+    Windows,
+}
+
+impl Platform {
+    /// The platform this binary was built for.
+    pub fn current() -> Self {
+        if cfg!(windows) {
+            Self::Windows
+        } else if cfg!(target_os = "macos") {
+            Self::MacOs
+        } else {
+            Self::Unix
+        }
+    }
+}
+
+/// The kinds of per-user directory the app writes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirKind {
+    /// Settings the user may edit or sync.
+    Config,
+    /// Persistent user data (presets, history).
+    Data,
+    /// Regenerable caches.
+    Cache,
+    /// Machine-local state (logs).
+    State,
+}
+
+impl DirKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Config => "config",
+            Self::Data => "data",
+            Self::Cache => "cache",
+            Self::State => "state",
+        }
+    }
+}
+
+/// Home directory per `platform`, reading variables through `env`.
+///
+/// Windows uses `USERPROFILE`, then `HOMEDRIVE` + `HOMEPATH`, then `HOME`
+/// (set by MSYS/Cygwin shells); everything else uses `HOME`.
+pub fn home_dir_in(platform: Platform, env: &dyn Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+    let var = |name: &str| env(name).filter(|v| !v.is_empty());
+    match platform {
+        Platform::Windows => var("USERPROFILE")
+            .map(PathBuf::from)
+            .or_else(|| {
+                let mut drive = var("HOMEDRIVE")?;
+                drive.push(var("HOMEPATH")?);
+                Some(PathBuf::from(drive))
+            })
+            .or_else(|| var("HOME").map(PathBuf::from)),
+        Platform::Unix | Platform::MacOs => var("HOME").map(PathBuf::from),
+    }
+}
+
+/// App directory of `kind` per `platform`, reading variables through `env`.
+///
+/// | kind   | Unix                                   | macOS                              | Windows                    |
+/// |--------|----------------------------------------|------------------------------------|----------------------------|
+/// | Config | `$XDG_CONFIG_HOME` or `~/.config`      | `~/Library/Application Support`    | `%APPDATA%`                |
+/// | Data   | `$XDG_DATA_HOME` or `~/.local/share`   | `~/Library/Application Support`    | `%APPDATA%`                |
+/// | Cache  | `$XDG_CACHE_HOME` or `~/.cache`        | `~/Library/Caches`                 | `%LOCALAPPDATA%\…\cache`   |
+/// | State  | `$XDG_STATE_HOME` or `~/.local/state`  | `~/Library/Logs`                   | `%LOCALAPPDATA%`           |
+///
+/// each followed by `voiceforge`. Relative XDG values are ignored, as the
+/// spec requires; unset Windows variables fall back to `~\AppData\...`.
+pub fn dir_in(
+    kind: DirKind,
+    platform: Platform,
+    env: &dyn Fn(&str) -> Option<OsString>,
+) -> Option<PathBuf> {
+    let home = || home_dir_in(platform, env);
+    let var = |name: &str| env(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+    let base = match platform {
+        Platform::Unix => {
+            let (xdg, fallback) = match kind {
+                DirKind::Config => ("XDG_CONFIG_HOME", ".config"),
+                DirKind::Data => ("XDG_DATA_HOME", ".local/share"),
+                DirKind::Cache => ("XDG_CACHE_HOME", ".cache"),
+                DirKind::State => ("XDG_STATE_HOME", ".local/state"),
+            };
+            var(xdg)
+                .filter(|p| p.is_absolute())
+                .or_else(|| Some(home()?.join(fallback)))?
+        }
+        Platform::MacOs => {
+            let library = home()?.join("Library");
+            match kind {
+                DirKind::Config | DirKind::Data => library.join("Application Support"),
+                DirKind::Cache => library.join("Caches"),
+                DirKind::State => library.join("Logs"),
+            }
+        }
+        Platform::Windows => {
+            let roaming =
+                || var("APPDATA").or_else(|| Some(home()?.join("AppData").join("Roaming")));
+            let local =
+                || var("LOCALAPPDATA").or_else(|| Some(home()?.join("AppData").join("Local")));
+            match kind {
+                DirKind::Config | DirKind::Data => roaming()?,
+                // Cache and logs share the local app dir; keep caches apart.
+                DirKind::Cache => return Some(local()?.join(APP_DIR).join("cache")),
+                DirKind::State => local()?,
+            }
+        }
+    };
+    Some(base.join(APP_DIR))
+}
+
+fn process_env(name: &str) -> Option<OsString> {
+    std::env::var_os(name)
+}
+
+/// The user's home directory on this platform.
+pub fn home_dir() -> Option<PathBuf> {
+    home_dir_in(Platform::current(), &process_env)
+}
+
+/// Per-user settings directory. See [`dir_in`] for the locations.
+pub fn config_dir() -> Option<PathBuf> {
+    dir_in(DirKind::Config, Platform::current(), &process_env)
+}
+
+/// Per-user data directory (history, presets).
+pub fn data_dir() -> Option<PathBuf> {
+    dir_in(DirKind::Data, Platform::current(), &process_env)
+}
+
+/// Per-user cache directory.
+pub fn cache_dir() -> Option<PathBuf> {
+    dir_in(DirKind::Cache, Platform::current(), &process_env)
+}
+
+/// Per-user state directory (logs).
+pub fn state_dir() -> Option<PathBuf> {
+    dir_in(DirKind::State, Platform::current(), &process_env)
+}
+
+/// The directory of `kind`, created if missing. Every persistence feature
+/// goes through here so failures read the same everywhere.
+pub fn ensure_dir(kind: DirKind) -> io::Result<PathBuf> {
+    create_dir(kind, dir_in(kind, Platform::current(), &process_env))
+}
+
+/// Create `dir` (the resolved location of `kind`, `None` if it could not be
+/// resolved), with an error naming the directory kind and path.
+pub fn create_dir(kind: DirKind, dir: Option<PathBuf>) -> io::Result<PathBuf> {
+    let dir = dir.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "no {} directory: home directory unknown (set {})",
+                kind.label(),
+                if cfg!(windows) { "USERPROFILE" } else { "HOME" }
+            ),
+        )
+    })?;
+    std::fs::create_dir_all(&dir).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!(
+                "cannot create {} directory {}: {e}",
+                kind.label(),
+                dir.display()
+            ),
+        )
+    })?;
+    Ok(dir)
+}
+
+/// Expand a leading `~` (alone, or followed by a separator) to `home`.
+/// Windows also accepts `~\`. Returns `None` if the input starts with `~`
+/// but `home` is unknown; other inputs are returned unchanged.
+pub fn expand_tilde_with(input: &str, platform: Platform, home: Option<&Path>) -> Option<String> {
+    let Some(rest) = input.strip_prefix('~') else {
+        return Some(input.to_string());
+    };
+    let is_sep = |c: char| c == '/' || (platform == Platform::Windows && c == '\\');
+    if !(rest.is_empty() || rest.starts_with(is_sep)) {
+        // `~user` is not supported; leave it for the filesystem to reject.
+        return Some(input.to_string());
+    }
+    let home = home?.to_string_lossy();
+    if rest.is_empty() {
+        return Some(home.into_owned());
+    }
+    Some(format!("{}{rest}", home.trim_end_matches(is_sep)))
+}
+
+/// [`expand_tilde_with`] for this platform and process environment.
+pub fn expand_tilde(input: &str) -> Option<String> {
+    expand_tilde_with(input, Platform::current(), home_dir().as_deref())
+}
@@ -0,0 +1,64 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock, TryLockError};
+
+/// A value published by one owner and read as whole snapshots by other
+/// threads (the processing worker, realtime consumers).
+///
+/// Readers clone an `Arc` out under a read lock that is held only for that
+/// clone, so a snapshot is never torn and its revision always matches it.
+/// Realtime code uses [`Published::try_load`], which never blocks (same
+/// pattern as the playback `audio_lock`). Revisions start at 0 and increase
+/// by one per [`Published::publish`].
+#[derive(Debug)]
+pub struct Published<T> {
+    current: RwLock<(u64, Arc<T>)>,
+    revision: AtomicU64,
+}
+
+impl<T> Published<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            current: RwLock::new((0, Arc::new(value))),
+            revision: AtomicU64::new(0),
+        }
+    }
+
+    /// Replace the value. Returns the new revision.
+    pub fn publish(&self, value: T) -> u64 {
+        let value = Arc::new(value);
+        // Recover from a poisoned lock: the tuple is always replaced whole.
+        let mut guard = self.current.write().unwrap_or_else(|e| e.into_inner());
+        let revision = guard.0 + 1;
+        *guard = (revision, value);
+        self.revision.store(revision, Ordering::Release);
+        revision
+    }
+
+    /// The current revision and value.
+    pub fn load(&self) -> (u64, Arc<T>) {
+        let guard = self.current.read().unwrap_or_else(|e| e.into_inner());
+        (guard.0, Arc::clone(&guard.1))
+    }
+
+    /// Like [`Published::load`], but `None` instead of waiting while a
+    /// publish is in progress.
+    pub fn try_load(&self) -> Option<(u64, Arc<T>)> {
+        let guard = match self.current.try_read() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+            Err(TryLockError::WouldBlock) => return None,
+        };
+        Some((guard.0, Arc::clone(&guard.1)))
+    }
+
+    /// Revision of the latest publish, without touching the lock.
+    pub fn revision(&self) -> u64 {
+        self.revision.load(Ordering::Acquire)
+    }
+}
+
+impl<T: Default> Default for Published<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
@@ -0,0 +1,153 @@
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::dsp::effects::EffectsParams;
+use crate::dsp::modifier::WorldSliderValues;
+use crate::paths::{self, DirKind};
+
+/// File name of the append-only processing history inside the data dir.
+pub const HISTORY_FILE: &str = "history.log";
+
+/// One WAV export written during the session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportRecord {
+    pub source: String,
+    pub output: String,
+    pub world: WorldSliderValues,
+    pub effects: EffectsParams,
+}
+
+/// Session-wide counters, updated from the main loop's result handling and
+/// the export path. Rendered by the stats overlay and summarized to
+/// `history.log` on quit.
+#[derive(Debug, Clone, Default)]
+pub struct SessionStats {
+    pub files_processed: usize,
+    /// Total duration of audio that went through WORLD analysis.
+    pub analyzed_secs: f64,
+    pub resyntheses: usize,
+    /// Worker time spent on resyntheses (not effects-only passes), as
+    /// reported by `ProcessingResult::SynthesisTimed`.
+    pub synthesis_time: Duration,
+    pub exports: Vec<ExportRecord>,
+}
+
+impl SessionStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A file finished decoding and is now the active file.
+    pub fn record_file_loaded(&mut self) {
+        self.files_processed += 1;
+    }
+
+    /// WORLD analysis completed for `duration_secs` of audio.
+    pub fn record_analysis(&mut self, duration_secs: f64) {
+        if duration_secs.is_finite() && duration_secs > 0.0 {
+            self.analyzed_secs += duration_secs;
+        }
+    }
+
+    /// A resynthesis finished; `elapsed` is the worker's time for it.
+    pub fn record_synthesis(&mut self, elapsed: Duration) {
+        self.resyntheses += 1;
+        self.synthesis_time += elapsed;
+    }
+
+    /// A WAV export was written successfully.
+    pub fn record_export(&mut self, record: ExportRecord) {
+        self.exports.push(record);
+    }
+
+    /// Mean synthesis time, or zero when nothing was synthesized yet.
+    pub fn mean_synthesis_time(&self) -> Duration {
+        if self.resyntheses == 0 {
+            Duration::ZERO
+        } else {
+            self.synthesis_time / self.resyntheses as u32
+        }
+    }
+}
+
+/// Format one history line for an export.
+///
+/// Tab-separated so the log stays greppable: timestamp, kind, source, output,
+/// then the key slider values.
+pub fn format_export_line(timestamp: u64, record: &ExportRecord) -> String {
+    let w = &record.world;
+    let fx = &record.effects;
+    format!(
+        "{timestamp}\texport\tsrc={}\tout={}\tpitch={:+.1}st range={:.2} speed={:.2} \
+         breath={:.2} formant={:+.1}st tilt={:+.1}dB/oct bypass={}\t\
+         lowcut={:.0}Hz highcut={:.0}Hz comp={:.0}dB reverb={:.2}/{} fxpitch={:+.1}st gain={:+.1}dB",
+        record.source,
+        record.output,
+        w.pitch_shift,
+        w.pitch_range,
+        w.speed,
+        w.breathiness,
+        w.formant_shift,
+        w.spectral_tilt,
+        w.bypass,
+        fx.low_cut_hz,
+        fx.high_cut_hz,
+        fx.compressor_thresh_db,
+        fx.reverb_mix,
+        fx.reverb_preset.name(),
+        fx.pitch_shift_semitones,
+        fx.gain_db,
+    )
+}
+
+/// Format the end-of-session summary line.
+pub fn format_session_line(timestamp: u64, stats: &SessionStats) -> String {
+    format!(
+        "{timestamp}\tsession\tfiles={}\tanalyzed={:.1}s\tresyntheses={}\tsynth_time={:.1}s\texports={}",
+        stats.files_processed,
+        stats.analyzed_secs,
+        stats.resyntheses,
+        stats.synthesis_time.as_secs_f64(),
+        stats.exports.len(),
+    )
+}
+
+/// Append a single line to the history file, creating it if needed.
+pub fn append_history_line(path: &Path, line: &str) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Path of `history.log`, creating the data dir if it does not exist yet.
+pub fn history_path() -> io::Result<PathBuf> {
+    Ok(paths::ensure_dir(DirKind::Data)?.join(HISTORY_FILE))
+}
+
+/// Marker file in the data dir recording that the first-run walkthrough
+/// was completed or skipped.
+pub const TOUR_MARKER_FILE: &str = "tour-done";
+
+/// Whether the walkthrough should start on its own: neither the tour
+/// marker nor a processing history exists in `data_dir` (returning users
+/// from before the tour have a history and are not interrupted).
+pub fn is_first_run(data_dir: &Path) -> bool {
+    !data_dir.join(TOUR_MARKER_FILE).exists() && !data_dir.join(HISTORY_FILE).exists()
+}
+
+/// Record in `data_dir` that the walkthrough has been seen.
+pub fn write_tour_marker(data_dir: &Path) -> io::Result<()> {
+    std::fs::write(
+        data_dir.join(TOUR_MARKER_FILE),
+        format!("{}\n", unix_timestamp()),
+    )
+}
+
+/// Current UNIX time in seconds (same clock as the log file timestamps).
+pub fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
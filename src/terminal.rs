@@ -0,0 +1,82 @@
+use std::io::{self, stdout};
+use std::time::Duration;
+
+use ratatui::crossterm::event::{DisableFocusChange, EnableFocusChange};
+use ratatui::crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::crossterm::ExecutableCommand;
+
+/// Main loop frame interval while the terminal has focus (~30 fps).
+pub const FOCUSED_FRAME_INTERVAL: Duration = Duration::from_millis(33);
+/// Frame interval while unfocused (~2 fps): enough to keep the transport
+/// clock moving in a background tmux pane without burning CPU.
+pub const UNFOCUSED_FRAME_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long the main loop waits for input before redrawing.
+pub fn frame_interval(focused: bool) -> Duration {
+    if focused {
+        FOCUSED_FRAME_INTERVAL
+    } else {
+        UNFOCUSED_FRAME_INTERVAL
+    }
+}
+
+/// Put the terminal in TUI mode: raw mode, alternate screen, and focus
+/// change reporting (terminals that don't support it ignore the request).
+pub fn enter() -> io::Result<()> {
+    enable_raw_mode()?;
+    stdout().execute(EnterAlternateScreen)?;
+    stdout().execute(EnableFocusChange)?;
+    Ok(())
+}
+
+/// Undo [`enter`]. Best effort: each step is attempted and failures are
+/// only logged, because this also runs while unwinding.
+pub fn leave() {
+    if let Err(e) = stdout().execute(DisableFocusChange) {
+        log::warn!("failed to disable focus reporting: {e}");
+    }
+    if let Err(e) = disable_raw_mode() {
+        log::warn!("failed to disable raw mode: {e}");
+    }
+    if let Err(e) = stdout().execute(LeaveAlternateScreen) {
+        log::warn!("failed to leave alternate screen: {e}");
+    }
+}
+
+/// Job-control suspend (Ctrl+Z / SIGTSTP): restore the terminal, stop the
+/// process, and re-enter TUI mode once SIGCONT resumes it. The caller
+/// must clear and redraw the screen afterwards.
+///
+/// # Errors
+///
+/// Returns an error if the process could not be stopped or the terminal
+/// could not be set up again.
+pub fn suspend() -> io::Result<()> {
+    suspend_with(
+        || {
+            leave();
+            Ok(())
+        },
+        enter,
+    )
+}
+
+/// [`suspend`] with the terminal steps supplied by the caller (tests run
+/// without a terminal). Returns after the process is continued.
+///
+/// # Errors
+///
+/// Returns the first error from `leave`, stopping, or `reenter`.
+pub fn suspend_with(
+    leave: impl FnOnce() -> io::Result<()>,
+    reenter: impl FnOnce() -> io::Result<()>,
+) -> io::Result<()> {
+    leave()?;
+    // SIGTSTP has a flag handler installed, so stop the way its default
+    // action would (SIGSTOP). Execution continues here on SIGCONT.
+    #[cfg(unix)]
+    signal_hook::low_level::emulate_default_handler(signal_hook::consts::SIGTSTP)?;
+    reenter()
+}
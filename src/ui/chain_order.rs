@@ -0,0 +1,61 @@
+use ratatui::layout::Constraint;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::AppState;
+use crate::ui::layout::centered_popup;
+
+const WIDTH: u16 = 40;
+
+/// Render the effects chain order overlay: the stages top to bottom in the
+/// order they run, the selected one highlighted.
+pub fn render(frame: &mut Frame, app: &AppState) {
+    // Borders, one row per stage, a blank row and the key hint.
+    let height = app.effects_order.len() as u16 + 4;
+    let Some(area) = centered_popup(Constraint::Length(WIDTH), height, frame.area()) else {
+        return;
+    };
+
+    frame.render_widget(Clear, area);
+    let block = Block::default()
+        .title(" Effects Chain ")
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let selected_style = Style::default()
+        .fg(Color::Black)
+        .bg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let mut lines: Vec<Line> = app
+        .effects_order
+        .iter()
+        .enumerate()
+        .map(|(i, stage)| {
+            let style = if i == app.chain_order_selected {
+                selected_style
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Line::from(vec![
+                Span::styled(format!("  {:>2}", i + 1), dim),
+                Span::styled(" \u{2502} ", dim),
+                Span::styled(stage.name(), style),
+            ])
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        " \u{2191}/\u{2193} select \u{00b7} Shift move \u{00b7} d reset \u{00b7} Esc",
+        dim,
+    )));
+    frame.render_widget(Paragraph::new(lines), inner);
+}
@@ -1,19 +1,32 @@
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
-use ratatui::text::Span;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
+use crate::app::PARAMETRIC_FIELDS;
+use crate::dsp::effects::{EqLayout, ParametricBand};
+
 /// 12 EQ band frequencies for display.
 const EQ_FREQS: [&str; 12] = [
     "31", "63", "125", "250", "500", "1k", "2k", "3.1k", "4k", "6.3k", "10k", "16k",
 ];
 
-/// Render the 12-band graphic EQ panel as vertical bars.
+/// 31 EQ band frequencies for display.
+const EQ_FREQS_31: [&str; 31] = [
+    "20", "25", "31", "40", "50", "63", "80", "100", "125", "160", "200", "250", "315", "400",
+    "500", "630", "800", "1k", "1.2k", "1.6k", "2k", "2.5k", "3.1k", "4k", "5k", "6.3k", "8k",
+    "10k", "12k", "16k", "20k",
+];
+
+/// Render the graphic EQ panel as vertical bars, one column per band of
+/// `layout`. When the panel is narrower than the bands, neighbouring bands
+/// share a column that shows the largest gain among them.
 pub fn render(
     frame: &mut Frame,
     area: Rect,
-    eq_gains: &[f64; 12],
+    layout: EqLayout,
+    eq_gains: &[f64],
     selected_band: usize,
     focused: bool,
 ) {
@@ -24,8 +37,12 @@ pub fn render(
 
     let title_color = if focused { Color::Cyan } else { Color::White };
     let border_style = Style::default().fg(title_color);
+    let title = match layout {
+        EqLayout::Bands12 => " Graphic EQ ",
+        EqLayout::Bands31 => " Graphic EQ (31) ",
+    };
     let block = Block::default()
-        .title(" Graphic EQ ")
+        .title(title)
         .title_alignment(ratatui::layout::Alignment::Center)
         .borders(Borders::ALL)
         .style(border_style);
@@ -38,8 +55,17 @@ pub fn render(
         return;
     }
 
-    // Column width for each band
-    let col_width = inner.width / 12;
+    let labels: &[&str] = match layout {
+        EqLayout::Bands12 => &EQ_FREQS,
+        EqLayout::Bands31 => &EQ_FREQS_31,
+    };
+    let band_count = labels.len();
+    // Bands per column: 1 unless the panel is too narrow for all of them.
+    let per_col = band_count.div_ceil(band_count.min(inner.width as usize));
+    let columns = band_count.div_ceil(per_col);
+
+    // Column width for each band (or group of bands)
+    let col_width = inner.width / columns as u16;
     if col_width < 1 {
         return;
     }
@@ -57,15 +83,24 @@ pub fn render(
     let bar_area_y = inner.y + 1; // Start bars after value label row
 
     // For each column
-    for band_idx in 0..12 {
-        let col_x = inner.x + (band_idx as u16 * col_width);
+    for col in 0..columns {
+        let col_x = inner.x + (col as u16 * col_width);
         let col_width = col_width.min((inner.x + inner.width).saturating_sub(col_x));
 
         if col_width < 1 {
             continue;
         }
 
-        let gain = eq_gains[band_idx];
+        let group = col * per_col..((col + 1) * per_col).min(band_count);
+        let is_selected = group.contains(&selected_band);
+        let gain = if is_selected {
+            eq_gains.get(selected_band).copied().unwrap_or(0.0)
+        } else {
+            group
+                .clone()
+                .map(|band| eq_gains.get(band).copied().unwrap_or(0.0))
+                .fold(0.0, |a: f64, g| if g.abs() > a.abs() { g } else { a })
+        };
 
         // Determine bar height and direction (scale: -6 to +6 dB per 'total_rows' pixels)
         // 0 dB is at center_row, positive gain extends upward, negative gain extends downward
@@ -84,23 +119,26 @@ pub fn render(
 
             if is_zero_line {
                 // 0 dB line
-                let marker = if focused && band_idx == selected_band { "▸" } else { "─" };
-                let style = if focused && band_idx == selected_band {
+                let marker = if focused && is_selected { "▸" } else { "─" };
+                let style = if focused && is_selected {
                     Style::default().fg(Color::Cyan)
                 } else {
                     Style::default().fg(Color::Gray)
                 };
                 let span = Span::styled(marker, style);
                 let para = Paragraph::new(span);
-                frame.render_widget(para, Rect {
-                    x: col_x,
-                    y,
-                    width: col_width,
-                    height: 1,
-                });
+                frame.render_widget(
+                    para,
+                    Rect {
+                        x: col_x,
+                        y,
+                        width: col_width,
+                        height: 1,
+                    },
+                );
             } else if is_boost && row_idx < center_row && center_row - row_idx <= bar_height {
                 // Boost region (above center): bars extend upward from center line
-                let style = if band_idx == selected_band {
+                let style = if is_selected {
                     Style::default()
                         .fg(Color::Black)
                         .bg(Color::Cyan)
@@ -110,15 +148,18 @@ pub fn render(
                 };
                 let span = Span::styled("█", style);
                 let para = Paragraph::new(span);
-                frame.render_widget(para, Rect {
-                    x: col_x,
-                    y,
-                    width: col_width,
-                    height: 1,
-                });
+                frame.render_widget(
+                    para,
+                    Rect {
+                        x: col_x,
+                        y,
+                        width: col_width,
+                        height: 1,
+                    },
+                );
             } else if is_cut && row_idx > center_row && row_idx - center_row <= bar_height {
                 // Cut region (below center): bars extend downward from center line
-                let style = if band_idx == selected_band {
+                let style = if is_selected {
                     Style::default()
                         .fg(Color::Black)
                         .bg(Color::Red)
@@ -128,19 +169,24 @@ pub fn render(
                 };
                 let span = Span::styled("█", style);
                 let para = Paragraph::new(span);
-                frame.render_widget(para, Rect {
-                    x: col_x,
-                    y,
-                    width: col_width,
-                    height: 1,
-                });
+                frame.render_widget(
+                    para,
+                    Rect {
+                        x: col_x,
+                        y,
+                        width: col_width,
+                        height: 1,
+                    },
+                );
             }
         }
 
         // Render frequency label at bottom
-        let freq_label = EQ_FREQS[band_idx];
-        let freq_style = if focused && band_idx == selected_band {
-            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        let freq_label = labels[group.start];
+        let freq_style = if focused && is_selected {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
         } else {
             Style::default().fg(Color::Gray)
         };
@@ -158,12 +204,17 @@ pub fn render(
 
         // Render value label at top
         let gain_str = format!("{:+.1}", gain);
-        let val_style = if focused && band_idx == selected_band {
-            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        let val_style = if focused && is_selected {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
         } else {
             Style::default().fg(Color::Gray)
         };
-        let val_span = Span::styled(&gain_str[..gain_str.len().min(col_width as usize)], val_style);
+        let val_span = Span::styled(
+            &gain_str[..gain_str.len().min(col_width as usize)],
+            val_style,
+        );
         let val_para = Paragraph::new(val_span);
         frame.render_widget(
             val_para,
@@ -176,3 +227,64 @@ pub fn render(
         );
     }
 }
+
+/// Render the parametric EQ bands as a table, one band per row, with the
+/// selected parameter highlighted while the panel is focused.
+pub fn render_parametric(
+    frame: &mut Frame,
+    area: Rect,
+    bands: &[ParametricBand],
+    selected_cell: usize,
+    focused: bool,
+) {
+    let title_color = if focused { Color::Cyan } else { Color::White };
+    let block = Block::default()
+        .title(" Parametric EQ ")
+        .title_alignment(ratatui::layout::Alignment::Center)
+        .borders(Borders::ALL)
+        .style(Style::default().fg(title_color));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.height < 1 || inner.width < 12 {
+        return;
+    }
+
+    let fields = PARAMETRIC_FIELDS.len();
+    let lines: Vec<Line> = bands
+        .iter()
+        .enumerate()
+        .map(|(band_idx, band)| {
+            let freq = if band.freq_hz >= 1000.0 {
+                format!("{:.2}k", band.freq_hz / 1000.0)
+            } else {
+                format!("{:.0}", band.freq_hz)
+            };
+            let cells = [
+                format!("{freq:>6}Hz"),
+                format!("Q {:>5.2}", band.q),
+                format!("{:+6.1}dB", band.gain_db),
+                band.kind.name().to_string(),
+            ];
+            let mut spans = vec![Span::styled(
+                format!("{} ", band_idx + 1),
+                Style::default().fg(Color::Gray),
+            )];
+            for (field, text) in cells.into_iter().enumerate() {
+                let style = if focused && band_idx * fields + field == selected_cell {
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else if band.gain_db.abs() < 1e-6 {
+                    Style::default().fg(Color::DarkGray)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                spans.push(Span::styled(text, style));
+                spans.push(Span::raw(" "));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), inner);
+}
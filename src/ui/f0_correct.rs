@@ -0,0 +1,72 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::AppState;
+use crate::dsp::f0_candidates::F0_CANDIDATE_COUNT;
+use crate::ui::layout::has_interior;
+
+const WIDTH: u16 = 50;
+/// Borders, range, candidate and key hint rows.
+const HEIGHT: u16 = 5;
+
+/// Render the f0 correction panel in the bottom-left corner of `area`
+/// (the inspector sits top-right and shows the resulting f0).
+pub fn render(frame: &mut Frame, area: Rect, app: &AppState) {
+    let Some(correction) = app.f0_correction else {
+        return;
+    };
+    let width = WIDTH.min(area.width);
+    let height = HEIGHT.min(area.height);
+    let rect = Rect::new(area.x, area.bottom() - height, width, height);
+    if !has_interior(rect) {
+        return;
+    }
+
+    frame.render_widget(Clear, rect);
+    let block = Block::default()
+        .title(" F0 Correction ")
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+    let inner = block.inner(rect);
+    frame.render_widget(block, rect);
+
+    let key_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let val_style = Style::default().fg(Color::White);
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let (start, end) = if correction.anchor_secs <= correction.end_secs {
+        (correction.anchor_secs, correction.end_secs)
+    } else {
+        (correction.end_secs, correction.anchor_secs)
+    };
+    let candidate = match correction.rank {
+        Some(rank) => format!("#{} of {F0_CANDIDATE_COUNT}", rank + 1),
+        None => "analysis".to_string(),
+    };
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("      Range", key_style),
+            Span::styled(" \u{2502} ", dim),
+            Span::styled(format!("{start:.3} \u{2013} {end:.3} s"), val_style),
+        ]),
+        Line::from(vec![
+            Span::styled("  Candidate", key_style),
+            Span::styled(" \u{2502} ", dim),
+            Span::styled(candidate, val_style),
+        ]),
+        Line::from(Span::styled(
+            " \u{2190}/\u{2192} range end \u{00b7} Tab next \u{00b7} Enter done",
+            dim,
+        )),
+    ];
+    frame.render_widget(Paragraph::new(lines), inner);
+}
@@ -1,17 +1,50 @@
-use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 
-use crate::app::AppState;
+use crate::app::{AppState, FILE_PICKER_ROWS};
+use crate::ui::layout::centered_popup;
+use crate::ui::text::{display_width, take_head, take_tail, truncate_middle};
+
+/// Popup rows besides the matches: borders (2), hint, input and divider.
+const CHROME_ROWS: u16 = 5;
+
+/// Match rows the picker can show in a frame of `frame_area` (0 when the
+/// popup would be skipped). The handler scrolls by this many.
+pub fn visible_rows(frame_area: Rect) -> usize {
+    let popup_h = CHROME_ROWS + FILE_PICKER_ROWS as u16;
+    match centered_popup(Constraint::Fill(1), popup_h, frame_area) {
+        Some(area) => area.height.saturating_sub(CHROME_ROWS) as usize,
+        None => 0,
+    }
+}
 
 pub fn render(frame: &mut Frame, app: &AppState) {
     let total = app.file_picker_matches.len();
-    let n_visible = total.min(5);
-    let popup_h: u16 = if n_visible == 0 { 4 } else { (5 + n_visible) as u16 };
+    let wanted_rows = total.min(FILE_PICKER_ROWS);
+    let popup_h: u16 = if wanted_rows == 0 {
+        4
+    } else {
+        CHROME_ROWS + wanted_rows as u16
+    };
 
-    let area = centered_rect(60, popup_h, frame.area());
+    // Default to 60% of the terminal, widening (up to the full width) when the
+    // longest match needs more room: borders (2) + selection prefix (2) + path.
+    let frame_area = frame.area();
+    let longest = app
+        .file_picker_matches
+        .iter()
+        .map(|m| display_width(m))
+        .max()
+        .unwrap_or(0);
+    let base_w = frame_area.width.saturating_mul(60) / 100;
+    let wanted_w = (longest + 4).min(u16::MAX as usize) as u16;
+    let popup_w = base_w.max(wanted_w).min(frame_area.width);
+    let Some(area) = centered_popup(Constraint::Length(popup_w), popup_h, frame_area) else {
+        return;
+    };
 
     // Clear background behind popup
     frame.render_widget(Clear, area);
@@ -28,25 +61,17 @@ pub fn render(frame: &mut Frame, app: &AppState) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Split inner area vertically: hint (1) + input (1) + match area (remaining)
-    let hint_area = Rect {
-        x: inner.x,
-        y: inner.y,
-        width: inner.width,
-        height: 1,
-    };
-    let input_area = Rect {
-        x: inner.x,
-        y: inner.y + 1,
-        width: inner.width,
-        height: 1,
-    };
-    let match_area = Rect {
-        x: inner.x,
-        y: inner.y + 2,
-        width: inner.width,
-        height: inner.height.saturating_sub(2),
-    };
+    // Split inner area vertically: hint (1) + input (1) + match area (remaining).
+    // Layout clips rows that don't fit instead of placing them past the popup.
+    let [hint_area, input_area, match_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Fill(1),
+    ])
+    .areas(inner);
+    // A shrunken popup shows fewer rows (one goes to the divider); the window
+    // never starts past the last full page.
+    let n_visible = wanted_rows.min(match_area.height.saturating_sub(1) as usize);
 
     // Render hint line
     let hint_line = Line::from(Span::styled(
@@ -70,7 +95,7 @@ pub fn render(frame: &mut Frame, app: &AppState) {
         let mut match_lines = Vec::new();
 
         // Divider with scroll indicator
-        let scroll = app.file_picker_scroll;
+        let scroll = app.file_picker_scroll.min(total - n_visible);
         let above = scroll;
         let below = total.saturating_sub(scroll + n_visible);
         let width = match_area.width as usize;
@@ -108,7 +133,9 @@ pub fn render(frame: &mut Frame, app: &AppState) {
 
             let prefix = if is_selected { "▶ " } else { "  " };
             let prefix_style = if is_selected {
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
             };
@@ -123,13 +150,8 @@ pub fn render(frame: &mut Frame, app: &AppState) {
                 Style::default().fg(Color::White)
             };
 
-            // Truncate path if it's too long
-            let max_path_len = width.saturating_sub(3);
-            let display_path = if match_path.len() > max_path_len {
-                format!("{}…", &match_path[..max_path_len - 1])
-            } else {
-                match_path.to_string()
-            };
+            // Middle-truncate long paths so the file name stays visible.
+            let display_path = truncate_middle(match_path, width.saturating_sub(2));
 
             let line = Line::from(vec![
                 Span::styled(prefix, prefix_style),
@@ -150,15 +172,6 @@ pub fn render(frame: &mut Frame, app: &AppState) {
     }
 }
 
-/// Create a centered rect of `percent_x`% width and `height` rows.
-fn centered_rect(percent_x: u16, height: u16, area: Rect) -> Rect {
-    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
-    let horizontal = Layout::horizontal([Constraint::Percentage(percent_x)]).flex(Flex::Center);
-    let [v] = vertical.areas(area);
-    let [h] = horizontal.areas(v);
-    h
-}
-
 /// L-10/L-11: Split input text at cursor and apply horizontal scrolling.
 /// Returns (before_cursor, after_cursor) strings truncated to fit the width.
 pub fn render_input_line(app: &AppState, width: usize) -> (String, String) {
@@ -173,15 +186,11 @@ pub fn render_input_line(app: &AppState, width: usize) -> (String, String) {
         return (String::new(), String::new());
     }
 
-    let before_chars: Vec<char> = before.chars().collect();
-    let after_chars: Vec<char> = after.chars().collect();
-
     // Give most of the space to text before cursor, but keep some for after.
-    let before_budget = avail.min(before_chars.len());
-    let after_budget = avail.saturating_sub(before_budget).min(after_chars.len());
-
-    let before_str: String = before_chars[before_chars.len() - before_budget..].iter().collect();
-    let after_str: String = after_chars[..after_budget].iter().collect();
+    // Measured in display columns over whole graphemes (wide CJK/emoji safe).
+    let before_str = take_tail(before, avail);
+    let after_budget = avail.saturating_sub(display_width(before_str));
+    let after_str = take_head(after, after_budget);
 
-    (before_str, after_str)
+    (before_str.to_string(), after_str.to_string())
 }
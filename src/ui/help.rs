@@ -1,11 +1,15 @@
-use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::layout::Constraint;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 
+use crate::ui::layout::centered_popup;
+
 pub fn render(frame: &mut Frame) {
-    let area = centered_rect(70, 20, frame.area());
+    let Some(area) = centered_popup(Constraint::Percentage(70), 32, frame.area()) else {
+        return;
+    };
 
     frame.render_widget(Clear, area);
 
@@ -21,7 +25,9 @@ pub fn render(frame: &mut Frame) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let key_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
     let desc_style = Style::default().fg(Color::White);
     let sep_style = Style::default().fg(Color::DarkGray);
 
@@ -30,15 +36,50 @@ pub fn render(frame: &mut Frame) {
         ("Tab", "Cycle panel focus"),
         ("\u{2191}/\u{2193}", "Select slider / Boost-cut band"),
         ("\u{2190}/\u{2192}", "Adjust slider / Navigate bands"),
-        ("Shift+\u{2190}/\u{2192}", "Fine-adjust slider / Fine-adjust band"),
+        (
+            "Shift+\u{2190}/\u{2192}",
+            "Fine-adjust slider / Fine-adjust band",
+        ),
         ("d", "Reset slider / Reset band to 0dB"),
+        (
+            "Enter",
+            "Pitch Range: centre / Formant Shift: warp / Humanize: re-roll / EQ: parametric bands",
+        ),
         ("[ / ]", "Seek \u{00b1}5s"),
         ("Home / End", "Jump to start / end"),
         ("r", "Toggle loop"),
+        ("R", "Re-analyze after the analysis was lost"),
+        (
+            "t / T",
+            "Lower / raise the D4C voicing threshold (re-analyzes)",
+        ),
         ("w", "Toggle WORLD bypass (ON/OFF)"),
+        ("W", "Toggle whisper (every frame unvoiced)"),
+        ("m", "Mark WORLD region start / end / clear"),
+        (
+            ", / .",
+            "Nudge the pitch around the playhead \u{2212}/+1 st",
+        ),
+        ("k", "Pitch / Breathiness / Tilt: ramp start / end / clear"),
         ("a", "A/B toggle (original vs processed)"),
+        ("l", "Loudness match B to A (ON/OFF)"),
+        ("b", "Graphic EQ: 12 / 31 bands (resets the bands)"),
         ("s", "Export WAV"),
         ("o", "Open file"),
+        ("i", "Session statistics"),
+        (
+            "O",
+            "Effects chain order (Shift+\u{2191}/\u{2193} move a stage)",
+        ),
+        ("v", "Spectrum range: auto / fixed"),
+        (
+            "p",
+            "Inspect spectrum peaks (paused, \u{2190}/\u{2192} step)",
+        ),
+        ("f", "Frame inspector (raw WORLD values at playhead)"),
+        ("c", "Correct f0 over a range (--extended-analysis)"),
+        ("e", "Punch a clip into B over a region"),
+        ("g", "Go to time (Transport) / Restore output after duck"),
         ("?", "This help"),
         ("q / Esc", "Quit"),
     ];
@@ -54,18 +95,10 @@ pub fn render(frame: &mut Frame) {
     }
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        "  Press any key to close",
+        "  Press ? for the guided tour, any other key to close",
         Style::default().fg(Color::DarkGray),
     )));
 
     let paragraph = Paragraph::new(lines);
     frame.render_widget(paragraph, inner);
 }
-
-fn centered_rect(percent_x: u16, height: u16, area: Rect) -> Rect {
-    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
-    let horizontal = Layout::horizontal([Constraint::Percentage(percent_x)]).flex(Flex::Center);
-    let [v] = vertical.areas(area);
-    let [h] = horizontal.areas(v);
-    h
-}
@@ -0,0 +1,108 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::AppState;
+use crate::dsp::inspect::{FrameInfo, INSPECT_PEAK_COUNT};
+use crate::ui::layout::has_interior;
+
+const WIDTH: u16 = 42;
+/// Borders, four value rows, a blank, the peak header and the peaks.
+const HEIGHT: u16 = 2 + 4 + 1 + 1 + INSPECT_PEAK_COUNT as u16;
+
+/// Render the frame inspector in the top-right corner of `area`.
+pub fn render(frame: &mut Frame, area: Rect, app: &AppState) {
+    let width = WIDTH.min(area.width);
+    let height = HEIGHT.min(area.height);
+    let rect = Rect::new(area.right() - width, area.y, width, height);
+    if !has_interior(rect) {
+        return;
+    }
+
+    frame.render_widget(Clear, rect);
+
+    let block = Block::default()
+        .title(" Frame Inspector ")
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+    let inner = block.inner(rect);
+    frame.render_widget(block, rect);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let lines = match app.frame_info {
+        Some(ref info) => info_lines(info),
+        None if app.original_audio.is_none() => {
+            vec![Line::from(Span::styled(" Waiting for analysis...", dim))]
+        }
+        None => vec![Line::from(Span::styled(" Reading frame...", dim))],
+    };
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn info_lines(info: &FrameInfo) -> Vec<Line<'static>> {
+    let key_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let val_style = Style::default().fg(Color::White);
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let f0 = if info.f0_hz > 0.0 {
+        format!("{:.2} Hz", info.f0_hz)
+    } else {
+        "unvoiced".to_string()
+    };
+    let rows: [(&str, String); 4] = [
+        (
+            "Frame",
+            format!("{} / {}", info.frame, info.frame_count.saturating_sub(1)),
+        ),
+        ("Time", format!("{:.3} s", info.time_secs)),
+        ("f0", f0),
+        (
+            "Aperiodicity",
+            format!("{:.3} (mean)", info.mean_aperiodicity),
+        ),
+    ];
+
+    let mut lines: Vec<Line<'static>> = Vec::with_capacity(rows.len() + 2 + info.peaks.len());
+    for (key, val) in rows {
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {key:>12}"), key_style),
+            Span::styled(" \u{2502} ", dim),
+            Span::styled(val, val_style),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(
+            " {:>2} {:>5} {:>10} {:>10}",
+            "#", "bin", "freq Hz", "level dB"
+        ),
+        key_style,
+    )));
+    if info.peaks.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  no envelope peaks (silent frame)",
+            dim,
+        )));
+    }
+    for (i, peak) in info.peaks.iter().enumerate() {
+        lines.push(Line::from(Span::styled(
+            format!(
+                " {:>2} {:>5} {:>10.1} {:>10.1}",
+                i + 1,
+                peak.bin,
+                peak.freq_hz,
+                peak.level_db
+            ),
+            val_style,
+        )));
+    }
+    lines
+}
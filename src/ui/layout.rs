@@ -1,23 +1,58 @@
-use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::layout::{Constraint, Direction, Flex, Layout, Rect};
 use ratatui::Frame;
 
 use crate::app::{AppMode, AppState, PanelFocus};
-use crate::ui::{eq_panel, file_picker, help, save_dialog, slider, spectrum, status_bar, transport};
+use crate::dsp::modifier::{FormantWarp, RangeCenter};
+use crate::ui::{
+    chain_order, eq_panel, f0_correct, file_picker, help, inspector, palette, punch_in,
+    save_dialog, seek_prompt, slider, spectrum, stats, status_bar, tour, transport,
+};
+
+/// Smallest terminal the layout renders in; below it only a notice is shown.
+pub const MIN_WIDTH: u16 = 40;
+pub const MIN_HEIGHT: u16 = 16;
+
+/// Smallest popup drawn: a border all round and a 1×1 interior.
+pub const MIN_POPUP_WIDTH: u16 = 3;
+pub const MIN_POPUP_HEIGHT: u16 = 3;
+
+/// Whether a bordered overlay at `rect` keeps at least a 1×1 interior.
+/// Overlays skip rendering entirely when it doesn't.
+pub fn has_interior(rect: Rect) -> bool {
+    rect.width >= MIN_POPUP_WIDTH && rect.height >= MIN_POPUP_HEIGHT
+}
 
-pub fn render(frame: &mut Frame, app: &mut AppState) {
-    let area = frame.area();
+/// A popup `width` wide and `height` rows tall centred in `area`, shrunk
+/// to fit it; `None` if what is left has no interior (see [`has_interior`]).
+pub fn centered_popup(width: Constraint, height: u16, area: Rect) -> Option<Rect> {
+    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+    let horizontal = Layout::horizontal([width]).flex(Flex::Center);
+    let [v] = vertical.areas(area);
+    let [h] = horizontal.areas(v);
+    // Layout may hand back a rect reaching past a tiny area; keep it inside.
+    let popup = h.intersection(area);
+    has_interior(popup).then_some(popup)
+}
+
+/// Screen rects of the main panels, shared by rendering and anything that
+/// needs to point at a panel (the first-run tour, mouse hit-testing).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanelRects {
+    pub world: Rect,
+    pub effects: Rect,
+    pub master: Rect,
+    pub eq: Rect,
+    pub spectrum: Rect,
+    pub transport: Rect,
+    pub status: Rect,
+}
 
+/// Split `area` into the main panels, or `None` if it is smaller than
+/// [`MIN_WIDTH`]×[`MIN_HEIGHT`].
+pub fn panel_rects(area: Rect) -> Option<PanelRects> {
     // H-5: Minimum terminal size guard to prevent zero-height render areas.
-    if area.height < 16 || area.width < 40 {
-        use ratatui::style::{Color, Style};
-        use ratatui::text::Span;
-        use ratatui::widgets::Paragraph;
-        let msg = Paragraph::new(Span::styled(
-            "Terminal too small (min 40×16)",
-            Style::default().fg(Color::Red),
-        ));
-        frame.render_widget(msg, area);
-        return;
+    if area.height < MIN_HEIGHT || area.width < MIN_WIDTH {
+        return None;
     }
 
     // Main vertical layout:
@@ -31,10 +66,6 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
         ])
         .split(area);
 
-    let top = vertical[0];
-    let transport_area = vertical[1];
-    let status_area = vertical[2];
-
     // Top area: split vertically into slider panels (40%), EQ panel (10), and spectrum (Min 4)
     let top_split = Layout::default()
         .direction(Direction::Vertical)
@@ -43,11 +74,7 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
             Constraint::Length(10),     // EQ panel
             Constraint::Min(4),         // spectrum
         ])
-        .split(top);
-
-    let sliders_area = top_split[0];
-    let eq_area = top_split[1];
-    let spectrum_area = top_split[2];
+        .split(vertical[0]);
 
     // Slider panels: three columns (WORLD 40% | Effects 40% | Master 20%)
     let slider_cols = Layout::default()
@@ -57,7 +84,35 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
             Constraint::Percentage(40),
             Constraint::Percentage(20),
         ])
-        .split(sliders_area);
+        .split(top_split[0]);
+
+    Some(PanelRects {
+        world: slider_cols[0],
+        effects: slider_cols[1],
+        master: slider_cols[2],
+        eq: top_split[1],
+        spectrum: top_split[2],
+        transport: vertical[1],
+        status: vertical[2],
+    })
+}
+
+pub fn render(frame: &mut Frame, app: &mut AppState) {
+    let area = frame.area();
+
+    let Some(rects) = panel_rects(area) else {
+        use ratatui::style::{Color, Style};
+        use ratatui::text::Span;
+        use ratatui::widgets::Paragraph;
+        let msg = Paragraph::new(Span::styled(
+            format!("Terminal too small (min {MIN_WIDTH}×{MIN_HEIGHT})"),
+            Style::default().fg(Color::Red),
+        ));
+        frame.render_widget(msg, area);
+        return;
+    };
+    // Everything above the transport bar (the frame inspector's anchor).
+    let top = rects.world.union(rects.spectrum);
 
     // Render slider panels
     let world_selected = if app.focus == PanelFocus::WorldSliders {
@@ -65,19 +120,38 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
     } else {
         None
     };
-    let world_title = if app.world_bypass {
+    let world_title = if app.analysis_lost {
+        "WORLD Vocoder [analysis lost]"
+    } else if app.world_bypass {
         "WORLD Vocoder [OFF]"
+    } else if app.world_whisper {
+        "WORLD Vocoder [Whisper]"
     } else {
         "WORLD Vocoder"
     };
+    let mut world_title = match app.world_region {
+        Some((start, end)) if !app.world_bypass && !app.analysis_lost => {
+            format!("{world_title} [{start:.2}–{end:.2}s]")
+        }
+        _ => world_title.to_string(),
+    };
+    if !app.world_automation.is_empty() {
+        world_title.push_str(" [automated]");
+    }
+    if app.world_formant_warp != FormantWarp::Linear {
+        world_title.push_str(&format!(" [formant: {}]", app.world_formant_warp.name()));
+    }
+    if app.world_range_center != RangeCenter::Mean {
+        world_title.push_str(&format!(" [range: {}]", app.world_range_center.name()));
+    }
     slider::render(
         frame,
-        slider_cols[0],
-        world_title,
+        rects.world,
+        &world_title,
         &app.world_sliders,
         world_selected,
         app.focus == PanelFocus::WorldSliders,
-        app.world_bypass,
+        app.world_bypass || app.analysis_lost,
     );
 
     let effects_selected = if app.focus == PanelFocus::EffectsSliders {
@@ -87,7 +161,7 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
     };
     slider::render(
         frame,
-        slider_cols[1],
+        rects.effects,
         "Effects",
         &app.effects_sliders,
         effects_selected,
@@ -102,7 +176,7 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
     };
     slider::render(
         frame,
-        slider_cols[2],
+        rects.master,
         "Master",
         &app.master_sliders,
         master_selected,
@@ -111,22 +185,38 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
     );
 
     // EQ panel
-    eq_panel::render(
-        frame,
-        eq_area,
-        &app.eq_gains,
-        app.eq_selected_band,
-        app.focus == PanelFocus::EqBands,
-    );
+    if app.eq_parametric_mode {
+        eq_panel::render_parametric(
+            frame,
+            rects.eq,
+            &app.eq_parametric,
+            app.eq_parametric_cell,
+            app.focus == PanelFocus::EqBands,
+        );
+    } else {
+        eq_panel::render(
+            frame,
+            rects.eq,
+            app.eq_layout,
+            &app.eq_gains,
+            app.eq_selected_band,
+            app.focus == PanelFocus::EqBands,
+        );
+    }
 
     // Spectrum visualizer (GPU pixel or Unicode fallback)
-    spectrum::render(frame, spectrum_area, app);
+    spectrum::render(frame, rects.spectrum, app);
 
     // Transport bar
-    transport::render(frame, transport_area, app);
+    transport::render(frame, rects.transport, app);
 
     // Status bar
-    status_bar::render(frame, status_area, app);
+    status_bar::render(frame, rects.status, app);
+
+    // Frame inspector: non-modal, so transport keys keep moving the playhead.
+    if app.inspector_open {
+        inspector::render(frame, top, app);
+    }
 
     // Modal overlays (on top of everything)
     if app.mode == AppMode::FilePicker {
@@ -135,7 +225,27 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
     if app.mode == AppMode::Saving {
         save_dialog::render(frame, app);
     }
+    if app.mode == AppMode::Seeking {
+        seek_prompt::render(frame, app);
+    }
     if app.mode == AppMode::Help {
         help::render(frame);
     }
+    if app.mode == AppMode::Stats {
+        stats::render(frame, app);
+    }
+    if app.mode == AppMode::ChainOrder {
+        chain_order::render(frame, app);
+    }
+    if app.mode == AppMode::F0Correct {
+        f0_correct::render(frame, top, app);
+    }
+    if app.mode == AppMode::PunchIn {
+        punch_in::render(frame, top, app);
+    }
+    if app.mode == AppMode::Tour {
+        tour::render(frame, &rects, app);
+    }
+
+    palette::apply(frame.buffer_mut(), app.config.palette);
 }
@@ -1,9 +1,18 @@
+pub mod chain_order;
 pub mod eq_panel;
+pub mod f0_correct;
 pub mod file_picker;
 pub mod help;
+pub mod inspector;
 pub mod layout;
+pub mod palette;
+pub mod punch_in;
 pub mod save_dialog;
+pub mod seek_prompt;
 pub mod slider;
 pub mod spectrum;
+pub mod stats;
 pub mod status_bar;
+pub mod text;
+pub mod tour;
 pub mod transport;
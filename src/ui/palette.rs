@@ -0,0 +1,67 @@
+use ratatui::buffer::Buffer;
+use ratatui::style::Color;
+
+use crate::config::Palette;
+
+/// The 16 ANSI colours with their usual xterm RGB values.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Map an RGB or indexed colour to the nearest of the 16 ANSI colours;
+/// named colours pass through unchanged.
+pub fn to_ansi16(color: Color) -> Color {
+    let (r, g, b) = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(i) if i < 16 => return ANSI16[i as usize].0,
+        Color::Indexed(i) => indexed_rgb(i),
+        other => return other,
+    };
+    ANSI16
+        .iter()
+        .min_by_key(|(_, (ar, ag, ab))| {
+            let dr = r as i32 - *ar as i32;
+            let dg = g as i32 - *ag as i32;
+            let db = b as i32 - *ab as i32;
+            dr * dr + dg * dg + db * db
+        })
+        .map(|&(c, _)| c)
+        .unwrap_or(color)
+}
+
+/// RGB value of an xterm-256 colour index ≥ 16 (6×6×6 cube, then greys).
+fn indexed_rgb(i: u8) -> (u8, u8, u8) {
+    if i >= 232 {
+        let v = 8 + (i - 232) * 10;
+        return (v, v, v);
+    }
+    let i = i - 16;
+    let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+    (level(i / 36), level((i / 6) % 6), level(i % 6))
+}
+
+/// Restrict a rendered frame to `palette`, after all widgets have drawn.
+pub fn apply(buf: &mut Buffer, palette: Palette) {
+    if palette == Palette::TrueColor {
+        return;
+    }
+    for cell in buf.content.iter_mut() {
+        cell.fg = to_ansi16(cell.fg);
+        cell.bg = to_ansi16(cell.bg);
+    }
+}
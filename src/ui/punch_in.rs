@@ -0,0 +1,66 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::AppState;
+use crate::ui::layout::has_interior;
+
+const WIDTH: u16 = 50;
+/// Borders, region, length mode and key hint rows.
+const HEIGHT: u16 = 5;
+
+/// Render the punch-in panel in the bottom-left corner of `area`, while
+/// the region is being chosen.
+pub fn render(frame: &mut Frame, area: Rect, app: &AppState) {
+    let Some(region) = app.punch_region else {
+        return;
+    };
+    let width = WIDTH.min(area.width);
+    let height = HEIGHT.min(area.height);
+    let rect = Rect::new(area.x, area.bottom() - height, width, height);
+    if !has_interior(rect) {
+        return;
+    }
+
+    frame.render_widget(Clear, rect);
+    let block = Block::default()
+        .title(" Punch In (B) ")
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+    let inner = block.inner(rect);
+    frame.render_widget(block, rect);
+
+    let key_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let val_style = Style::default().fg(Color::White);
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let (start, end) = region.bounds();
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("  Region", key_style),
+            Span::styled(" \u{2502} ", dim),
+            Span::styled(
+                format!("{start:.3} \u{2013} {end:.3} s ({:.2} s)", end - start),
+                val_style,
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  Length", key_style),
+            Span::styled(" \u{2502} ", dim),
+            Span::styled(region.mode.label(), val_style),
+        ]),
+        Line::from(Span::styled(
+            " \u{2190}/\u{2192} region end \u{00b7} Tab length \u{00b7} Enter clip \u{00b7} Esc",
+            dim,
+        )),
+    ];
+    frame.render_widget(Paragraph::new(lines), inner);
+}
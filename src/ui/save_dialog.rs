@@ -1,4 +1,4 @@
-use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::layout::Constraint;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
@@ -6,9 +6,12 @@ use ratatui::Frame;
 
 use crate::app::AppState;
 use crate::ui::file_picker::render_input_line;
+use crate::ui::layout::centered_popup;
 
 pub fn render(frame: &mut Frame, app: &AppState) {
-    let area = centered_rect(60, 5, frame.area());
+    let Some(area) = centered_popup(Constraint::Percentage(60), 5, frame.area()) else {
+        return;
+    };
 
     frame.render_widget(Clear, area);
 
@@ -44,11 +47,3 @@ pub fn render(frame: &mut Frame, app: &AppState) {
     let paragraph = Paragraph::new(lines);
     frame.render_widget(paragraph, inner);
 }
-
-fn centered_rect(percent_x: u16, height: u16, area: Rect) -> Rect {
-    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
-    let horizontal = Layout::horizontal([Constraint::Percentage(percent_x)]).flex(Flex::Center);
-    let [v] = vertical.areas(area);
-    let [h] = horizontal.areas(v);
-    h
-}
@@ -0,0 +1,57 @@
+use ratatui::layout::Constraint;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::AppState;
+use crate::ui::file_picker::render_input_line;
+use crate::ui::layout::centered_popup;
+
+pub fn render(frame: &mut Frame, app: &AppState) {
+    let Some(area) = centered_popup(Constraint::Percentage(40), 5, frame.area()) else {
+        return;
+    };
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Go to ")
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let (before, after) = render_input_line(app, inner.width as usize);
+
+    // The hint line doubles as the error line, so the prompt never resizes.
+    let hint = match app.seek_error {
+        Some(ref e) => Line::from(Span::styled(
+            format!(" {e}"),
+            Style::default().fg(Color::Red),
+        )),
+        None => Line::from(Span::styled(
+            " mm:ss, hh:mm:ss, ss.s or 37% (Esc to cancel)",
+            Style::default().fg(Color::DarkGray),
+        )),
+    };
+
+    let lines = vec![
+        hint,
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" > ", Style::default().fg(Color::Cyan)),
+            Span::styled(before, Style::default().fg(Color::White)),
+            Span::styled("█", Style::default().fg(Color::Cyan)),
+            Span::styled(after, Style::default().fg(Color::White)),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(lines);
+    frame.render_widget(paragraph, inner);
+}
@@ -52,11 +52,7 @@ fn gradient_color(t: f64, is_selected: bool, focused: bool, dimmed: bool) -> Col
     };
 
     let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
-    Color::Rgb(
-        lerp(from.0, to.0),
-        lerp(from.1, to.1),
-        lerp(from.2, to.2),
-    )
+    Color::Rgb(lerp(from.0, to.0), lerp(from.1, to.1), lerp(from.2, to.2))
 }
 
 /// Render a panel of sliders.
@@ -112,7 +108,11 @@ pub fn render(
 
         // Bar line: "  [████████░░░░░░░░] 3.5 st"
         let bar_width = (inner.width as usize).saturating_sub(6); // padding + value space
-        let value_str = if slider.unit.is_empty() {
+        let value_str = if let Some(name) = slider.choice_label() {
+            name.to_string()
+        } else if let Some(stop) = slider.stop_label() {
+            stop.to_string()
+        } else if slider.unit.is_empty() {
             format!("{:.2}", slider.value)
         } else {
             format!("{:.1} {}", slider.value, slider.unit)
@@ -170,7 +170,14 @@ pub fn render(
             }
 
             spans.push(Span::raw("] "));
-            spans.push(Span::styled(value_str, Style::default().fg(if dimmed { Color::DarkGray } else { Color::Yellow })));
+            spans.push(Span::styled(
+                value_str,
+                Style::default().fg(if dimmed {
+                    Color::DarkGray
+                } else {
+                    Color::Yellow
+                }),
+            ));
 
             let bar_line = Line::from(spans);
             lines.push(bar_line);
@@ -178,7 +185,14 @@ pub fn render(
             // Narrow fallback: just show value
             let val_line = Line::from(vec![
                 Span::raw("  "),
-                Span::styled(value_str, Style::default().fg(if dimmed { Color::DarkGray } else { Color::Yellow })),
+                Span::styled(
+                    value_str,
+                    Style::default().fg(if dimmed {
+                        Color::DarkGray
+                    } else {
+                        Color::Yellow
+                    }),
+                ),
             ]);
             lines.push(val_line);
         }
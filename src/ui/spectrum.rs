@@ -5,13 +5,97 @@ use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
 use crate::app::AppState;
+use crate::dsp::spectrum::{
+    level_to_height, FFT_SIZE, INSPECT_FFT_SIZE, SPECTRUM_CEILING_DB, SPECTRUM_FLOOR_DB,
+};
+use crate::dsp::{effects, modifier, world};
 
 const BLOCKS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
 
+/// Marker for the unmodified envelope in the spectral slider preview.
+const BEFORE_MARK: char = '·';
+/// Marker for the envelope with the pending slider values applied.
+const AFTER_MARK: char = '•';
+
+/// Background of empty cells inside the highlighted EQ band.
+const BAND_HIGHLIGHT_BG: Color = Color::Rgb(20, 50, 60);
+/// Bar colour inside the highlighted EQ band.
+const BAND_HIGHLIGHT_FG: Color = Color::Rgb(0, 210, 230);
+
+/// Frequency axis: the (fractional) analyzer bin shown in column `col` of
+/// `num_cols`. Quadratic in the column position — an approximation of a log
+/// axis — starting at DC and ending at bin `bin_count - 1`.
+pub fn column_to_bin(col: usize, num_cols: usize, bin_count: usize) -> f64 {
+    let t = if num_cols > 1 {
+        col as f64 / (num_cols - 1) as f64
+    } else {
+        0.0
+    };
+    (bin_count as f64 - 1.0) * t * t
+}
+
+/// Inverse of [`column_to_bin`]: the column nearest `freq_hz`, clamped to the panel.
+pub fn freq_to_column(freq_hz: f64, sample_rate: u32, fft_size: usize, num_cols: usize) -> usize {
+    let bin_count = fft_size / 2;
+    if num_cols == 0 || bin_count < 2 || sample_rate == 0 {
+        return 0;
+    }
+    let bin = (freq_hz * fft_size as f64 / sample_rate as f64).clamp(0.0, (bin_count - 1) as f64);
+    let t = (bin / (bin_count as f64 - 1.0)).sqrt();
+    (t * (num_cols as f64 - 1.0)).round() as usize
+}
+
+/// Columns (inclusive) covering the frequency span of band `band` of an
+/// EQ `layout` whose peaking bands have Q `q`, or `None` for an unknown
+/// band or an empty panel.
+pub fn eq_band_columns(
+    layout: effects::EqLayout,
+    q: f32,
+    band: usize,
+    sample_rate: u32,
+    fft_size: usize,
+    num_cols: usize,
+) -> Option<(usize, usize)> {
+    let (lo, hi) = effects::eq_band_span_hz(layout, q, band)?;
+    if num_cols == 0 {
+        return None;
+    }
+    let nyquist = sample_rate as f64 / 2.0;
+    Some((
+        freq_to_column(f64::from(lo), sample_rate, fft_size, num_cols),
+        freq_to_column(f64::from(hi).min(nyquist), sample_rate, fft_size, num_cols),
+    ))
+}
+
 /// Render the spectrum analyzer using Unicode colored blocks.
 pub fn render(frame: &mut Frame, area: Rect, app: &AppState) {
+    // Inspect mode: peak readouts go on a text row under the panel.
+    let (area, readout_area) = if app.inspect_active() && area.height >= 5 {
+        let panel = Rect {
+            height: area.height - 1,
+            ..area
+        };
+        let row = Rect {
+            y: area.y + area.height - 1,
+            height: 1,
+            ..area
+        };
+        (panel, Some(row))
+    } else {
+        (area, None)
+    };
+
+    let inspect_title;
+    let title = if spectral_preview(app).is_some() {
+        " Spectrum · envelope preview (· before  • after) "
+    } else if app.inspect_active() {
+        inspect_title = format!(" Spectrum · inspect ({INSPECT_FFT_SIZE}-pt FFT, ←/→ step) ");
+        &inspect_title
+    } else {
+        " Spectrum "
+    };
     let block = Block::default()
-        .title(" Spectrum ")
+        .title(title)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::DarkGray));
 
@@ -19,27 +103,56 @@ pub fn render(frame: &mut Frame, area: Rect, app: &AppState) {
     frame.render_widget(block, area);
 
     render_unicode_fallback(frame, inner, app);
+
+    if let Some(row) = readout_area {
+        frame.render_widget(Paragraph::new(peak_readout_line(app)), row);
+    }
+}
+
+/// Text row listing the inspect-mode peaks, loudest first.
+fn peak_readout_line(app: &AppState) -> Line<'static> {
+    let label_style = Style::default().fg(Color::DarkGray);
+    if app.spectrum_peaks.is_empty() {
+        return Line::from(Span::styled(" Peaks: none", label_style));
+    }
+    let mut spans = vec![Span::styled(" Peaks:", label_style)];
+    for (i, peak) in app.spectrum_peaks.iter().enumerate() {
+        spans.push(Span::styled(format!("  {}. ", i + 1), label_style));
+        spans.push(Span::styled(
+            format!("{:.1} Hz {:+.1} dBFS", peak.freq_hz, peak.level_db),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    Line::from(spans)
 }
 
 /// Fallback Unicode/Braille renderer for terminals without graphics protocol support.
 fn render_unicode_fallback(frame: &mut Frame, area: Rect, app: &AppState) {
+    let preview = spectral_preview(app);
     // DEBUG: Log why fallback is happening (only for error cases)
-    if app.spectrum_bins.is_empty() {
-        let placeholder = Paragraph::new("  No audio playing")
-            .style(Style::default().fg(Color::DarkGray));
+    if app.spectrum_bins.is_empty() && preview.is_none() {
+        let placeholder =
+            Paragraph::new("  No audio playing").style(Style::default().fg(Color::DarkGray));
         frame.render_widget(placeholder, area);
         return;
     }
     if area.width < 2 || area.height < 1 {
-        let placeholder = Paragraph::new("  No audio playing")
-            .style(Style::default().fg(Color::DarkGray));
+        let placeholder =
+            Paragraph::new("  No audio playing").style(Style::default().fg(Color::DarkGray));
         frame.render_widget(placeholder, area);
         return;
     }
 
     let num_bars = area.width as usize;
     let inner_h = area.height as usize;
-    let bin_count = app.spectrum_bins.len();
+    // With no live spectrum (paused) the preview still uses the analyzer's bin layout.
+    // Inspect mode computes a larger FFT, so derive its size from the bins.
+    let bin_count = if app.spectrum_bins.is_empty() {
+        FFT_SIZE / 2
+    } else {
+        app.spectrum_bins.len()
+    };
+    let analyzer_fft_size = bin_count * 2;
 
     // Reserve bottom row for frequency labels if space allows (inner_h >= 2).
     // The spectrum scale is quadratic (t²), which approximates log scale perceptually
@@ -51,34 +164,120 @@ fn render_unicode_fallback(frame: &mut Frame, area: Rect, app: &AppState) {
         inner_h as f32
     };
 
+    // Level range mapped onto the bar height: fixed, or the rolling auto-range.
+    let (floor_db, ceiling_db) = if app.spectrum_auto_range {
+        app.spectrum_range.range()
+    } else {
+        (SPECTRUM_FLOOR_DB, SPECTRUM_CEILING_DB)
+    };
+
+    let sample_rate = app
+        .file_info
+        .as_ref()
+        .map(|f| f.sample_rate)
+        .unwrap_or(44100);
+
     // Precompute bar heights via quadratic-frequency mapping
     let mut heights = Vec::with_capacity(num_bars);
     for i in 0..num_bars {
-        let t = if num_bars > 1 {
-            i as f32 / (num_bars - 1) as f32
-        } else {
-            0.0
-        };
         // L-7: Map log-frequency starting from bin 0 (DC) not bin 1.
-        // Use (bin_count - 1) * t² to include the full range [0, bin_count-1].
-        let bin = ((bin_count as f32 - 1.0) * t.powf(2.0)).round() as usize;
-        let db = app.spectrum_bins[bin].clamp(-80.0, 0.0);
-        let h = ((db + 80.0) / 80.0 * bar_height).clamp(0.0, bar_height);
-        heights.push(h);
+        let bin = column_to_bin(i, num_bars, bin_count).round() as usize;
+        let db = app
+            .spectrum_bins
+            .get(bin)
+            .copied()
+            .unwrap_or(SPECTRUM_FLOOR_DB);
+        heights.push(level_to_height(db, floor_db, ceiling_db, bar_height));
     }
 
     // Build lines top-to-bottom (bars only; label row added later)
-    let bar_rows = if has_label_row {
-        inner_h - 1
+    let bar_rows = if has_label_row { inner_h - 1 } else { inner_h };
+    // Envelope preview curves: one row index per column, same frequency mapping
+    // as the bars, both normalized to the "before" envelope's peak.
+    let curves = preview.map(|(before, after, fft_size)| {
+        let reference = before.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        (
+            envelope_rows(
+                &before,
+                reference,
+                fft_size,
+                num_bars,
+                analyzer_fft_size,
+                bar_rows,
+            ),
+            envelope_rows(
+                &after,
+                reference,
+                fft_size,
+                num_bars,
+                analyzer_fft_size,
+                bar_rows,
+            ),
+        )
+    });
+
+    // Auto-range: show the active range as small dB labels at the left edge
+    // (ceiling on the top bar row, floor on the bottom one).
+    let range_labels = if app.spectrum_auto_range && bar_rows >= 2 {
+        let top = format!("{:.0}", ceiling_db);
+        let bottom = format!("{:.0}", floor_db);
+        (top.len().max(bottom.len()) + 4 <= num_bars).then_some((top, bottom))
     } else {
-        inner_h
+        None
     };
+
+    // Columns of the EQ band being adjusted, drawn in a highlight colour.
+    let highlight = app.eq_highlight_band().and_then(|band| {
+        let eq = app.eq_params();
+        eq_band_columns(
+            eq.layout,
+            eq.peak_q(),
+            band,
+            sample_rate,
+            analyzer_fft_size,
+            num_bars,
+        )
+    });
+    let highlighted = |col: usize| highlight.is_some_and(|(lo, hi)| (lo..=hi).contains(&col));
+
     let mut lines = Vec::with_capacity(inner_h);
     for r in 0..bar_rows {
         let level = bar_rows - r; // 1 = bottom bar row, bar_rows = top bar row
         let row_ratio = level as f32 / inner_h as f32; // Use full inner_h for gradient consistency
         let mut spans = Vec::with_capacity(num_bars);
-        for &h in &heights {
+        for (col, &h) in heights.iter().enumerate() {
+            if let Some((ref top, ref bottom)) = range_labels {
+                let label = if r == 0 {
+                    Some(top)
+                } else if r == bar_rows - 1 {
+                    Some(bottom)
+                } else {
+                    None
+                };
+                if let Some(ch) = label.and_then(|l| l.chars().nth(col)) {
+                    spans.push(Span::styled(
+                        ch.to_string(),
+                        Style::default().fg(Color::Rgb(120, 120, 120)),
+                    ));
+                    continue;
+                }
+            }
+            if let Some((ref before, ref after)) = curves {
+                if after[col] == r {
+                    spans.push(Span::styled(
+                        AFTER_MARK.to_string(),
+                        Style::default().fg(Color::Cyan),
+                    ));
+                    continue;
+                }
+                if before[col] == r {
+                    spans.push(Span::styled(
+                        BEFORE_MARK.to_string(),
+                        Style::default().fg(Color::White),
+                    ));
+                    continue;
+                }
+            }
             let full = h as usize;
             let frac = h - full as f32;
             let ch = if level <= full {
@@ -93,32 +292,25 @@ fn render_unicode_fallback(frame: &mut Frame, area: Rect, app: &AppState) {
             // 0.0 (bottom) → #3D0066 deep violet
             // 0.5 (mid) → #CC00FF electric purple
             // 1.0 (top) → #FF0099 neon pink
-            let color = punk_gradient_color(row_ratio);
-            spans.push(Span::styled(
-                ch.to_string(),
-                Style::default().fg(color),
-            ));
+            let style = if !highlighted(col) {
+                Style::default().fg(punk_gradient_color(row_ratio))
+            } else if ch == ' ' {
+                Style::default().bg(BAND_HIGHLIGHT_BG)
+            } else {
+                Style::default().fg(BAND_HIGHLIGHT_FG).bg(BAND_HIGHLIGHT_BG)
+            };
+            spans.push(Span::styled(ch.to_string(), style));
         }
         lines.push(Line::from(spans));
     }
 
     // Add frequency label row if space was reserved
     if has_label_row {
-        let sample_rate = app
-            .file_info
-            .as_ref()
-            .map(|f| f.sample_rate)
-            .unwrap_or(44100) as f32;
-        let fft_size = 2048.0;
-
         // Select frequency labels based on terminal width to avoid crowding.
         // Adaptive label sets ensure good spacing across different screen widths.
         let freq_labels: &[(f32, &str)] = match num_bars {
             0..=50 => &[], // Too narrow, skip labels
-            51..=80 => &[
-                (1000.0, "1k"),
-                (10000.0, "10k"),
-            ],
+            51..=80 => &[(1000.0, "1k"), (10000.0, "10k")],
             81..=120 => &[
                 (100.0, "100"),
                 (1000.0, "1k"),
@@ -154,10 +346,7 @@ fn render_unicode_fallback(frame: &mut Frame, area: Rect, app: &AppState) {
         let label_color = Color::Rgb(120, 120, 120); // Muted gray
 
         for &(freq_hz, label_text) in freq_labels {
-            // Inverse quadratic map: bin → col
-            let bin = (freq_hz * fft_size / sample_rate).clamp(0.0, (bin_count - 1) as f32);
-            let t = (bin / (bin_count as f32 - 1.0)).sqrt();
-            let col = (t * (num_bars as f32 - 1.0)).round() as usize;
+            let col = freq_to_column(f64::from(freq_hz), sample_rate, analyzer_fft_size, num_bars);
 
             // Skip if label overflows or overlaps with previous label
             if col + label_text.len() <= num_bars && col >= last_col {
@@ -183,6 +372,56 @@ fn render_unicode_fallback(frame: &mut Frame, area: Rect, app: &AppState) {
     frame.render_widget(paragraph, area);
 }
 
+/// Before/after envelopes (power → dB) for the spectral slider preview, or
+/// `None` when no spectral slider is selected or no frame has arrived yet.
+fn spectral_preview(app: &AppState) -> Option<(Vec<f64>, Vec<f64>, usize)> {
+    if !app.spectral_slider_selected() {
+        return None;
+    }
+    let snapshot = app.frame_snapshot.as_ref()?;
+    let after = modifier::preview_envelope_at_rate(
+        &snapshot.envelope,
+        snapshot.fft_size,
+        snapshot.sample_rate as i32,
+        &app.world_slider_values(),
+    );
+    let to_db = |env: &[f64]| -> Vec<f64> {
+        env.iter()
+            .map(|&p| 10.0 * world::frame_energy_floor(p).log10())
+            .collect()
+    };
+    Some((to_db(&snapshot.envelope), to_db(&after), snapshot.fft_size))
+}
+
+/// Map an envelope in dB onto the bar grid: for each column, the row index
+/// (0 = top) at which the curve is drawn. Levels are relative to `reference_db`
+/// with the same 80 dB range as the bars; values below it sit on the bottom row.
+fn envelope_rows(
+    envelope_db: &[f64],
+    reference_db: f64,
+    fft_size: usize,
+    num_cols: usize,
+    analyzer_fft_size: usize,
+    bar_rows: usize,
+) -> Vec<usize> {
+    let bin_count = analyzer_fft_size / 2;
+    (0..num_cols)
+        .map(|col| {
+            if envelope_db.is_empty() || bar_rows == 0 {
+                return usize::MAX;
+            }
+            // Analyzer bin → envelope bin (WORLD fft_size), same Hz.
+            let analyzer_bin = column_to_bin(col, num_cols, bin_count);
+            let env_bin = ((analyzer_bin * fft_size as f64 / analyzer_fft_size as f64).round()
+                as usize)
+                .min(envelope_db.len() - 1);
+            let rel = (envelope_db[env_bin] - reference_db).clamp(-80.0, 0.0);
+            let level = ((rel + 80.0) / 80.0 * bar_rows as f64).ceil().max(1.0) as usize;
+            bar_rows - level.min(bar_rows)
+        })
+        .collect()
+}
+
 /// Interpolate true color through the punk gradient:
 /// 0.0 (bottom) → #3D0066 deep violet
 /// 0.5 (mid) → #CC00FF electric purple
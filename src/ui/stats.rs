@@ -0,0 +1,76 @@
+use ratatui::layout::Constraint;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::AppState;
+use crate::ui::layout::centered_popup;
+
+/// Render the session statistics overlay.
+pub fn render(frame: &mut Frame, app: &AppState) {
+    let stats = &app.session_stats;
+    // Show at most the last few exports so the popup stays compact.
+    const MAX_EXPORTS: usize = 5;
+    let shown_exports = stats.exports.len().min(MAX_EXPORTS);
+    let height = (10 + shown_exports) as u16;
+    let Some(area) = centered_popup(Constraint::Percentage(70), height, frame.area()) else {
+        return;
+    };
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Session ")
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let key_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let val_style = Style::default().fg(Color::White);
+
+    let rows: [(&str, String); 5] = [
+        ("Files processed", stats.files_processed.to_string()),
+        ("Audio analyzed", format!("{:.1} s", stats.analyzed_secs)),
+        ("Resyntheses", stats.resyntheses.to_string()),
+        (
+            "Synthesis time",
+            format!(
+                "{:.1} s (mean {:.0} ms)",
+                stats.synthesis_time.as_secs_f64(),
+                stats.mean_synthesis_time().as_secs_f64() * 1000.0
+            ),
+        ),
+        ("Exports", stats.exports.len().to_string()),
+    ];
+
+    let mut lines: Vec<Line> = Vec::with_capacity(rows.len() + shown_exports + 3);
+    for (key, val) in rows {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {key:>16}"), key_style),
+            Span::styled("  \u{2502}  ", Style::default().fg(Color::DarkGray)),
+            Span::styled(val, val_style),
+        ]));
+    }
+    for record in stats.exports.iter().rev().take(shown_exports) {
+        lines.push(Line::from(Span::styled(
+            format!("    \u{2192} {}", record.output),
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Press any key to close",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
@@ -1,10 +1,11 @@
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
-use crate::app::AppState;
+use crate::app::{AppState, ANALYSIS_LOST_MSG};
+use crate::ui::text::truncate_middle;
 
 /// Animated spinner characters for progress indication.
 const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
@@ -14,12 +15,14 @@ fn spinner_frame() -> &'static str {
     let idx = (std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
-        .subsec_millis() / 100) as usize % 10;
+        .subsec_millis()
+        / 100) as usize
+        % 10;
     SPINNER[idx]
 }
 
 pub fn render(frame: &mut Frame, area: Rect, app: &AppState) {
-    let line = if let Some(ref info) = app.file_info {
+    let mut line = if let Some(ref info) = app.file_info {
         // #13: Clamp to avoid truncation for very long audio (>71 min wraps u32).
         let total_secs = info.duration_secs.max(0.0);
         let mins = (total_secs / 60.0).min(u32::MAX as f64) as u32;
@@ -32,7 +35,11 @@ pub fn render(frame: &mut Frame, area: Rect, app: &AppState) {
         };
         let mut spans = vec![
             Span::styled(" File: ", Style::default().fg(Color::DarkGray)),
-            Span::styled(&info.name, Style::default().fg(Color::White)),
+            // Keep the name to a third of the bar so the format fields stay visible.
+            Span::styled(
+                truncate_middle(&info.name, (area.width as usize / 3).max(8)),
+                Style::default().fg(Color::White),
+            ),
             Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
             Span::styled(
                 format!("{} Hz", info.sample_rate),
@@ -46,6 +53,30 @@ pub fn render(frame: &mut Frame, area: Rect, app: &AppState) {
                 Style::default().fg(Color::White),
             ),
         ];
+        if app.b_edited {
+            spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled("B edited", Style::default().fg(Color::Yellow)));
+        }
+        if let Some(ref classified) = app.content_class {
+            spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(
+                classified.describe(),
+                Style::default().fg(Color::White),
+            ));
+        }
+        if let Some(ref measured) = app.ab_loudness {
+            // Highlight a level mismatch: the louder version tends to win A/B.
+            let color = if measured.is_mismatched() {
+                Color::Yellow
+            } else {
+                Color::DarkGray
+            };
+            spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(
+                measured.describe(),
+                Style::default().fg(color),
+            ));
+        }
         if let Some(ref status) = app.processing_status {
             spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
             spans.push(Span::styled(
@@ -53,6 +84,17 @@ pub fn render(frame: &mut Frame, area: Rect, app: &AppState) {
                 Style::default().fg(Color::Yellow),
             ));
         }
+        if let Some(ref label) = app.export_in_flight {
+            let progress = match app.export_progress {
+                Some(ref line) => line.clone(),
+                None => format!("exporting {label}"),
+            };
+            spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(
+                format!("{} {progress}", spinner_frame()),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
         if let Some(ref msg) = app.status_message {
             spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
             spans.push(Span::styled(msg, Style::default().fg(Color::Red)));
@@ -77,6 +119,40 @@ pub fn render(frame: &mut Frame, area: Rect, app: &AppState) {
         ))
     };
 
+    // Stays until `R` re-analyzes; status messages time out.
+    if app.analysis_lost {
+        line.spans.insert(
+            0,
+            Span::styled(
+                format!(" {ANALYSIS_LOST_MSG} "),
+                Style::default().fg(Color::Black).bg(Color::Yellow),
+            ),
+        );
+    }
+
+    if app.config.safe_mode {
+        line.spans.insert(
+            0,
+            Span::styled(
+                " SAFE MODE ",
+                Style::default().fg(Color::Black).bg(Color::Yellow),
+            ),
+        );
+    }
+
+    // Most prominent: inserted last so it leads the line.
+    if app.playback.is_output_limited() {
+        line.spans.insert(
+            0,
+            Span::styled(
+                " OUTPUT LIMITED — press g to restore ",
+                Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Red)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        );
+    }
     let paragraph = Paragraph::new(line);
     frame.render_widget(paragraph, area);
 }
@@ -0,0 +1,76 @@
+//! Grapheme- and display-width-aware text truncation shared by the UI widgets.
+//!
+//! Byte-index slicing (`&s[..n]`) panics on multi-byte UTF-8 and can split
+//! combining sequences; everything here walks grapheme clusters and measures
+//! terminal columns with `unicode-width` instead.
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+const ELLIPSIS: &str = "…";
+
+/// Terminal display width of `s` in columns.
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// Longest prefix of `s` (whole graphemes) that fits in `max_width` columns.
+pub fn take_head(s: &str, max_width: usize) -> &str {
+    let mut used = 0;
+    let mut end = 0;
+    for (idx, g) in s.grapheme_indices(true) {
+        let w = UnicodeWidthStr::width(g);
+        if used + w > max_width {
+            break;
+        }
+        used += w;
+        end = idx + g.len();
+    }
+    &s[..end]
+}
+
+/// Longest suffix of `s` (whole graphemes) that fits in `max_width` columns.
+pub fn take_tail(s: &str, max_width: usize) -> &str {
+    let mut used = 0;
+    let mut start = s.len();
+    for (idx, g) in s.grapheme_indices(true).rev() {
+        let w = UnicodeWidthStr::width(g);
+        if used + w > max_width {
+            break;
+        }
+        used += w;
+        start = idx;
+    }
+    &s[start..]
+}
+
+/// Truncate a path for display, replacing the middle with `…` so that the
+/// file name at the end stays visible (e.g. `…/recordings/take_03_final.wav`).
+///
+/// The result never exceeds `max_width` columns. When the path fits it is
+/// returned unchanged.
+pub fn truncate_middle(path: &str, max_width: usize) -> String {
+    if display_width(path) <= max_width {
+        return path.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+    let budget = max_width - display_width(ELLIPSIS);
+
+    // Last component, including a trailing '/' for directories.
+    let trimmed = path.strip_suffix('/').unwrap_or(path);
+    let name_start = trimmed.rfind('/').map(|i| i + 1).unwrap_or(0);
+    let name_width = display_width(&path[name_start..]);
+
+    // Give the head up to a third of the budget, but never at the expense of
+    // the file name; the tail takes everything else.
+    let head_budget = if name_width >= budget {
+        0
+    } else {
+        (budget / 3).min(budget - name_width)
+    };
+    let head = take_head(path, head_budget);
+    let tail = take_tail(path, budget - display_width(head));
+    format!("{head}{ELLIPSIS}{tail}")
+}
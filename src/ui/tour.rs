@@ -0,0 +1,121 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::app::{AppState, TourTarget, TOUR_STEPS};
+use crate::ui::layout::{has_interior, PanelRects};
+
+/// Foreground for everything outside the highlighted panel (same grey as
+/// the dimmed slider gradient's midpoint).
+const DIM_FG: Color = Color::Rgb(70, 70, 70);
+const CALLOUT_WIDTH: u16 = 64;
+/// Borders, up to four wrapped lines of text, and the key hint.
+const CALLOUT_HEIGHT: u16 = 7;
+
+/// The rect a walkthrough step points at.
+pub fn target_rect(rects: &PanelRects, target: TourTarget) -> Rect {
+    match target {
+        TourTarget::WorldSliders => rects.world,
+        TourTarget::EffectsSliders => rects.effects,
+        TourTarget::Master => rects.master,
+        TourTarget::EqBands => rects.eq,
+        TourTarget::Spectrum => rects.spectrum,
+        TourTarget::Transport => rects.transport,
+    }
+}
+
+/// Grey out every cell outside `keep`, dropping backgrounds and bold, so
+/// only the highlighted panel stays in colour.
+pub fn dim_outside(buf: &mut Buffer, keep: Rect) {
+    let area = buf.area;
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            if keep.contains((x, y).into()) {
+                continue;
+            }
+            if let Some(cell) = buf.cell_mut((x, y)) {
+                cell.set_fg(DIM_FG);
+                cell.set_bg(Color::Reset);
+                cell.modifier.remove(Modifier::BOLD);
+            }
+        }
+    }
+}
+
+/// Draw the current walkthrough step over the rendered UI: dim the rest,
+/// frame the target panel, and explain it in a callout on the other half
+/// of the screen.
+pub fn render(frame: &mut Frame, rects: &PanelRects, app: &AppState) {
+    let (Some(index), Some(step)) = (app.tour_step, app.current_tour_step()) else {
+        return;
+    };
+    let target = target_rect(rects, step.target);
+    dim_outside(frame.buffer_mut(), target);
+
+    let accent = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(accent),
+        target,
+    );
+
+    let area = frame.area();
+    let width = CALLOUT_WIDTH.min(area.width);
+    let height = CALLOUT_HEIGHT.min(area.height);
+    let x = area.x + (area.width - width) / 2;
+    // Opposite half from the target so the callout never covers it.
+    let target_mid = target.y + target.height / 2;
+    let y = if target_mid < area.y + area.height / 2 {
+        area.bottom().saturating_sub(height + 1).max(area.y)
+    } else {
+        (area.y + 1).min(area.bottom() - height)
+    };
+    let callout = Rect::new(x, y, width, height);
+    if !has_interior(callout) {
+        return;
+    }
+
+    frame.render_widget(Clear, callout);
+    let block = Block::default()
+        .title(format!(
+            " {} ({}/{}) ",
+            step.title,
+            index + 1,
+            TOUR_STEPS.len()
+        ))
+        .borders(Borders::ALL)
+        .border_style(accent);
+    let inner = block.inner(callout);
+    frame.render_widget(block, callout);
+
+    let [text_area, hint_area] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(inner);
+    frame.render_widget(
+        Paragraph::new(step.text)
+            .style(Style::default().fg(Color::White))
+            .wrap(Wrap { trim: true }),
+        text_area,
+    );
+    let next = if index + 1 < TOUR_STEPS.len() {
+        "any key: next"
+    } else {
+        "any key: finish"
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(format!(" {next}"), Style::default().fg(Color::Cyan)),
+            Span::styled(
+                "  \u{00b7}  Esc: skip tour  \u{00b7}  ? twice: replay",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ])),
+        hint_area,
+    );
+}
@@ -11,10 +11,19 @@ pub fn render(frame: &mut Frame, area: Rect, app: &AppState) {
     let focused = app.focus == PanelFocus::Transport;
     let border_color = if focused { Color::Cyan } else { Color::White };
 
-    let block = Block::default()
+    let mut block = Block::default()
         .title(" Transport ")
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color));
+    // Sliders moved while on A: processing runs, but nothing heard changes.
+    if app.ab_hint && app.ab_original {
+        block = block.title_bottom(Span::styled(
+            " Listening to A — press 'a' to hear changes ",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -24,7 +33,11 @@ pub fn render(frame: &mut Frame, area: Rect, app: &AppState) {
     }
 
     let playing = app.playback.playing.load(Ordering::Relaxed);
-    let play_icon = if playing { "▶ Playing" } else { "⏸ Paused " };
+    let play_icon = if playing {
+        "▶ Playing"
+    } else {
+        "⏸ Paused "
+    };
 
     let loop_str = if app.loop_enabled { "On" } else { "Off" };
 
@@ -53,8 +66,19 @@ pub fn render(frame: &mut Frame, area: Rect, app: &AppState) {
     let time_str = format!(" {cur_min}:{cur_sec:02}/{dur_min}:{dur_sec:02} ");
     let loop_str = format!("  [Loop: {loop_str}]  ");
     let ab_display = format!(" [{ab_str}]");
-    let bar_budget = (inner.width as usize)
-        .saturating_sub(play_icon.len() + loop_str.len() + time_str.len() + ab_display.len());
+    // Pitch of the original under the playhead, once the curve is in.
+    let pitch_str = match (&app.pitch_curve, app.pitch_at(app.source_playhead_secs())) {
+        (None, _) => String::new(),
+        (Some(_), Some(hz)) => format!(" {hz:>4.0} Hz"),
+        (Some(_), None) => "    — Hz".to_string(),
+    };
+    let bar_budget = (inner.width as usize).saturating_sub(
+        play_icon.len()
+            + loop_str.len()
+            + time_str.len()
+            + ab_display.len()
+            + pitch_str.chars().count(),
+    );
 
     let fraction = if duration > 0.0 {
         (current_time / duration).clamp(0.0, 1.0)
@@ -87,6 +111,9 @@ pub fn render(frame: &mut Frame, area: Rect, app: &AppState) {
         ));
     }
     spans.push(Span::styled(time_str, Style::default().fg(Color::White)));
+    if !pitch_str.is_empty() {
+        spans.push(Span::styled(pitch_str, Style::default().fg(Color::Cyan)));
+    }
     spans.push(Span::styled(
         ab_display,
         Style::default()
@@ -0,0 +1,78 @@
+/// Where a typed "go to" timestamp points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SeekTarget {
+    /// Absolute time in seconds.
+    Secs(f64),
+    /// Percentage of the file's duration (not yet clamped to 0–100).
+    Percent(f64),
+}
+
+impl SeekTarget {
+    /// Resolve to seconds within `[0, duration_secs]`.
+    pub fn to_secs(self, duration_secs: f64) -> f64 {
+        let duration = duration_secs.max(0.0);
+        let secs = match self {
+            Self::Secs(s) => s,
+            Self::Percent(p) => duration * p / 100.0,
+        };
+        secs.clamp(0.0, duration)
+    }
+}
+
+/// Parse a seek timestamp: `ss`/`ss.s`, `mm:ss`, `hh:mm:ss` (seconds may be
+/// fractional, minutes and seconds after the first field must be below 60),
+/// or a percentage like `37%`. Surrounding whitespace is ignored.
+pub fn parse_timestamp(input: &str) -> Result<SeekTarget, String> {
+    let s = input.trim();
+    if s.is_empty() {
+        return Err("Enter a time like 14:32, 1:02:03, 95.5 or 37%".to_string());
+    }
+    if let Some(pct) = s.strip_suffix('%') {
+        return parse_non_negative(pct.trim())
+            .map(SeekTarget::Percent)
+            .ok_or_else(|| format!("Invalid percentage: {s}"));
+    }
+
+    let fields: Vec<&str> = s.split(':').collect();
+    let invalid = || format!("Invalid time: {s}");
+    let secs = match fields.as_slice() {
+        [secs] => parse_non_negative(secs).ok_or_else(invalid)?,
+        [mins, secs] => {
+            let mins = parse_whole(mins).ok_or_else(invalid)?;
+            mins * 60.0 + parse_sub_minute(secs).ok_or_else(invalid)?
+        }
+        [hours, mins, secs] => {
+            let hours = parse_whole(hours).ok_or_else(invalid)?;
+            let mins = parse_whole(mins)
+                .filter(|&m| m < 60.0)
+                .ok_or_else(invalid)?;
+            hours * 3600.0 + mins * 60.0 + parse_sub_minute(secs).ok_or_else(invalid)?
+        }
+        _ => return Err(invalid()),
+    };
+    Ok(SeekTarget::Secs(secs))
+}
+
+/// Finite, non-negative decimal number (no sign, no exponent, no inf/nan).
+fn parse_non_negative(s: &str) -> Option<f64> {
+    let plain = !s.is_empty()
+        && s.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && s.chars().any(|c| c.is_ascii_digit());
+    if !plain {
+        return None;
+    }
+    s.parse::<f64>().ok()
+}
+
+/// Whole number of hours or minutes.
+fn parse_whole(s: &str) -> Option<f64> {
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    s.parse::<u32>().ok().map(f64::from)
+}
+
+/// Seconds field after a colon: below 60, may be fractional.
+fn parse_sub_minute(s: &str) -> Option<f64> {
+    parse_non_negative(s).filter(|&v| v < 60.0)
+}
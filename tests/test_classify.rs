@@ -0,0 +1,124 @@
+use voiceforge::app::AppState;
+use voiceforge::audio::decoder::AudioData;
+use voiceforge::dsp::classify::{classify, preset, ContentClass, ContentFeatures};
+use voiceforge::dsp::{generate, world};
+
+const SR: u32 = 16000;
+
+fn classify_audio(audio: &AudioData) -> (ContentClass, f64, ContentFeatures) {
+    let params = world::analyze(audio).expect("analysis failed");
+    let features = ContentFeatures::from_params(&params, audio.sample_rate);
+    let result = classify(&features);
+    (result.class, result.confidence, features)
+}
+
+#[test]
+fn test_syllable_rhythm_classifies_as_speech() {
+    let (class, confidence, f) = classify_audio(&generate::syllables(140.0, 4.0, 0.5, SR, 2.0));
+    assert_eq!(class, ContentClass::Speech, "{f:?}");
+    assert!(confidence > 0.8, "{confidence} {f:?}");
+    assert!(f.rhythmicity > 0.8, "{f:?}");
+    assert!(f.voiced_ratio > 0.4, "{f:?}");
+}
+
+#[test]
+fn test_steady_chord_classifies_as_singing() {
+    for freqs in [[220.0, 277.18, 329.63], [261.63, 329.63, 392.0]] {
+        let (class, confidence, f) = classify_audio(&generate::chord(&freqs, 0.5, SR, 2.0));
+        assert_eq!(class, ContentClass::Singing, "{freqs:?}: {f:?}");
+        assert!(confidence > 0.7, "{confidence} {f:?}");
+        assert!(
+            f.rhythmicity < 0.2,
+            "beating partials are not rhythm: {f:?}"
+        );
+    }
+}
+
+#[test]
+fn test_white_noise_classifies_as_other() {
+    let (class, confidence, f) = classify_audio(&generate::white_noise(0.3, SR, 2.0, 7));
+    assert_eq!(class, ContentClass::Other, "{f:?}");
+    assert!(confidence > 0.9, "{confidence} {f:?}");
+    assert!(f.flatness > 0.5 && f.centroid_hz > 3000.0, "{f:?}");
+}
+
+#[test]
+fn test_silence_gives_finite_features() {
+    let params = world::analyze(&generate::silence(SR, 0.5)).unwrap();
+    let f = ContentFeatures::from_params(&params, SR);
+    for value in [
+        f.voiced_ratio,
+        f.f0_spread_st,
+        f.centroid_hz,
+        f.flatness,
+        f.rhythmicity,
+    ] {
+        assert!(value.is_finite(), "{f:?}");
+    }
+    assert_eq!(f.voiced_ratio, 0.0);
+    assert!(f.rhythmicity < 0.05, "{f:?}");
+    assert_eq!(classify(&f).class, ContentClass::Other);
+}
+
+/// App with a classification in place and `--auto-preset` on.
+fn classified_app(audio: &AudioData) -> AppState {
+    let mut app = AppState::new();
+    app.config.auto_preset = true;
+    let params = world::analyze(audio).unwrap();
+    app.content_class = Some(classify(&ContentFeatures::from_params(
+        &params,
+        audio.sample_rate,
+    )));
+    app
+}
+
+#[test]
+fn test_preset_applied_only_over_neutral_controls() {
+    let speech = generate::syllables(140.0, 4.0, 0.5, SR, 2.0);
+    let mut app = classified_app(&speech);
+    assert!(app.sliders_neutral());
+    assert_eq!(app.apply_content_preset(), Some(ContentClass::Speech));
+    let applied = preset(ContentClass::Speech).unwrap();
+    let fx = app.effects_params();
+    assert_eq!(fx.low_cut_hz, applied.low_cut_hz as f32);
+    assert_eq!(fx.compressor_thresh_db, applied.compressor_thresh_db as f32);
+    // Now the controls are no longer neutral: a second pass leaves them.
+    assert!(!app.sliders_neutral());
+    assert_eq!(app.apply_content_preset(), None);
+
+    // Any touched control blocks it: a WORLD slider, an EQ band, an effect.
+    let mut app = classified_app(&speech);
+    app.world_sliders[0].value = 2.0;
+    assert_eq!(app.apply_content_preset(), None);
+    assert_eq!(app.effects_sliders[0].value, app.effects_sliders[0].default);
+
+    let mut app = classified_app(&speech);
+    app.eq_gains[3] = -1.5;
+    assert_eq!(app.apply_content_preset(), None);
+
+    let mut app = classified_app(&speech);
+    app.effects_sliders[3].value = 0.3;
+    assert_eq!(app.apply_content_preset(), None);
+    assert_eq!(app.effects_sliders[2].value, 0.0);
+
+    // The master gain is only a listening level.
+    let mut app = classified_app(&speech);
+    app.master_sliders[0].value = -6.0;
+    assert!(app.apply_content_preset().is_some());
+}
+
+#[test]
+fn test_preset_needs_flag_and_a_class_with_a_preset() {
+    let mut app = classified_app(&generate::syllables(140.0, 4.0, 0.5, SR, 2.0));
+    app.config.auto_preset = false;
+    assert_eq!(app.apply_content_preset(), None);
+    assert!(app.sliders_neutral());
+
+    let mut app = classified_app(&generate::white_noise(0.3, SR, 1.0, 3));
+    assert_eq!(app.apply_content_preset(), None, "Other has no preset");
+    assert!(app.sliders_neutral());
+
+    let mut app = AppState::new();
+    app.config.auto_preset = true;
+    assert_eq!(app.apply_content_preset(), None, "nothing classified yet");
+}
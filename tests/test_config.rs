@@ -0,0 +1,269 @@
+use std::time::{Duration, Instant};
+
+use tempfile::TempDir;
+use voiceforge::audio::decoder::AudioFormat;
+use voiceforge::config::{Config, Palette};
+use voiceforge::dsp::effects::{EffectStage, Precision};
+use voiceforge::dsp::processing::{ProcessingCommand, ProcessingHandle, ProcessingResult};
+
+#[test]
+fn test_default_config_from_plain_args() {
+    let config = Config::from_args(["song.wav"]);
+    assert!(!config.safe_mode);
+    assert!(config.analyze_on_load);
+    assert_eq!(config.palette, Palette::TrueColor);
+    assert_eq!(config.effects_precision, Precision::F32);
+    assert_eq!(config.file.as_deref(), Some("song.wav"));
+    assert_eq!(
+        config,
+        Config {
+            file: Some("song.wav".into()),
+            ..Config::default()
+        }
+    );
+}
+
+#[test]
+fn test_safe_mode_flags() {
+    let config = Config::from_args(["--safe-mode", "song.wav"]);
+    let default = Config::default();
+    assert!(config.safe_mode);
+    assert!(!config.analyze_on_load);
+    assert_eq!(config.palette, Palette::Ansi16);
+    assert!(!config.mouse_capture);
+    assert!(!config.graphics_protocols);
+    assert!(config.spectrum_fft_size < default.spectrum_fft_size);
+    assert!(config.inspect_fft_size < default.inspect_fft_size);
+    assert!(config.resynth_debounce > default.resynth_debounce);
+    assert!(config.effects_debounce > default.effects_debounce);
+    assert_eq!(config.file.as_deref(), Some("song.wav"));
+}
+
+#[test]
+fn test_flags_combine_in_any_order() {
+    let config = Config::from_args(["a.wav", "--f64-effects", "--safe-mode", "b.wav"]);
+    assert!(config.safe_mode);
+    assert_eq!(config.effects_precision, Precision::F64);
+    assert_eq!(config.file.as_deref(), Some("a.wav"));
+    assert_eq!(Config::from_args(["--bogus"]), Config::default());
+}
+
+#[test]
+fn test_load_command_follows_analysis_gate() {
+    let normal = Config::default().load_command("a.wav".into());
+    assert!(matches!(normal, ProcessingCommand::Load(ref p) if p == "a.wav"));
+    let safe = Config::safe().load_command("a.wav".into());
+    assert!(matches!(safe, ProcessingCommand::Decode(ref p) if p == "a.wav"));
+}
+
+#[test]
+fn test_safe_mode_load_never_analyzes() {
+    let dir = TempDir::new().expect("failed to create temp dir");
+    let path = dir.path().join("tone.wav");
+    let samples: Vec<f32> = (0..8000)
+        .map(|i| 0.3 * (2.0 * std::f32::consts::PI * 220.0 * i as f32 / 16000.0).sin())
+        .collect();
+    voiceforge::audio::export::export_wav(&samples, 16000, 1, &path).unwrap();
+
+    let config = Config::from_args(["--safe-mode"]);
+    let cmd = config.load_command(path.to_string_lossy().into_owned());
+    assert!(!matches!(cmd, ProcessingCommand::Analyze(_)));
+
+    let handle = ProcessingHandle::spawn();
+    handle.send(cmd);
+    // A resynthesis request after a playback-only load must be a no-op.
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        Default::default(),
+        AudioFormat::mono(16000),
+        None,
+    ));
+    handle.send(ProcessingCommand::ScanDirectory("/nonexistent-dir/".into()));
+
+    let mut ready = false;
+    let mut listed = false;
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !(ready && listed) && Instant::now() < deadline {
+        while let Some(result) = handle.try_recv() {
+            match result {
+                ProcessingResult::AudioReady(audio, _) => {
+                    assert_eq!(audio.samples.len(), 8000);
+                    ready = true;
+                }
+                ProcessingResult::AnalysisDone(_) => panic!("safe mode load ran analysis"),
+                ProcessingResult::SynthesisDone(_) => panic!("resynthesized without analysis"),
+                ProcessingResult::DirectoryListing(..) => listed = true,
+                _ => {}
+            }
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert!(ready && listed);
+}
+
+#[test]
+fn test_ansi16_palette_maps_rgb_to_nearest_basic_colour() {
+    use ratatui::style::Color;
+    use voiceforge::ui::palette::to_ansi16;
+    assert_eq!(to_ansi16(Color::Rgb(255, 0, 153)), Color::Magenta);
+    assert_eq!(to_ansi16(Color::Rgb(120, 120, 120)), Color::DarkGray);
+    assert_eq!(to_ansi16(Color::Rgb(0, 220, 255)), Color::LightCyan);
+    assert_eq!(to_ansi16(Color::Indexed(1)), Color::Red);
+    assert_eq!(to_ansi16(Color::Yellow), Color::Yellow);
+    assert_eq!(to_ansi16(Color::Reset), Color::Reset);
+}
+
+#[test]
+fn test_output_ceiling_flag() {
+    assert_eq!(Config::default().output_ceiling_db, -3.0);
+    assert_eq!(
+        Config::from_args(["--output-ceiling=-6"]).output_ceiling_db,
+        -6.0
+    );
+    // Above 0 dBFS is capped; garbage is ignored.
+    assert_eq!(
+        Config::from_args(["--output-ceiling=4"]).output_ceiling_db,
+        0.0
+    );
+    assert_eq!(
+        Config::from_args(["--output-ceiling=loud"]).output_ceiling_db,
+        -3.0
+    );
+}
+
+#[test]
+fn test_extended_analysis_flag() {
+    assert!(!Config::default().extended_analysis);
+    assert!(Config::from_args(["--extended-analysis"]).extended_analysis);
+}
+
+#[test]
+fn test_cache_analysis_flag() {
+    assert!(!Config::default().cache_analysis);
+    assert!(Config::from_args(["--cache-analysis"]).cache_analysis);
+}
+
+#[test]
+fn test_preview_analysis_flags() {
+    assert_eq!(
+        Config::default().preview_analysis_secs,
+        Some(voiceforge::config::PREVIEW_ANALYSIS_SECS)
+    );
+    assert_eq!(Config::safe().preview_analysis_secs, None);
+    assert_eq!(
+        Config::from_args(["--preview-analysis=20"]).preview_analysis_secs,
+        Some(20.0)
+    );
+    assert_eq!(
+        Config::from_args(["--no-preview-analysis"]).preview_analysis_secs,
+        None
+    );
+    for invalid in [
+        "--preview-analysis=-1",
+        "--preview-analysis=inf",
+        "--preview-analysis=soon",
+    ] {
+        assert_eq!(Config::from_args([invalid]), Config::default(), "{invalid}");
+    }
+}
+
+#[test]
+fn test_switch_to_b_flag() {
+    assert!(!Config::default().switch_to_b_on_change);
+    assert!(Config::from_args(["--switch-to-b"]).switch_to_b_on_change);
+    assert!(Config::from_args(["--safe-mode", "--switch-to-b"]).switch_to_b_on_change);
+}
+
+#[test]
+fn test_no_dither_flag() {
+    assert!(Config::default().dither);
+    assert!(!Config::from_args(["--no-dither"]).dither);
+}
+
+#[test]
+fn test_fx_order_flag() {
+    assert_eq!(Config::default().effects_order, EffectStage::DEFAULT_ORDER);
+    let order = Config::from_args(["--fx-order=limiter,eq"]).effects_order;
+    assert_eq!(
+        &order[..3],
+        &[EffectStage::Limiter, EffectStage::Eq, EffectStage::HighPass]
+    );
+    assert_eq!(order.len(), EffectStage::DEFAULT_ORDER.len());
+    assert_eq!(Config::from_args(["--fx-order=eq,wah"]), Config::default());
+}
+
+#[test]
+fn test_chipmunk_pitch_fx_flag() {
+    assert!(!Config::default().resample_pitch_fx);
+    assert!(Config::from_args(["--chipmunk-pitch-fx"]).resample_pitch_fx);
+}
+
+#[test]
+fn test_auto_preset_flag() {
+    assert!(!Config::default().auto_preset);
+    assert!(Config::from_args(["--auto-preset"]).auto_preset);
+}
+
+#[test]
+fn test_punch_fade_flag() {
+    assert_eq!(Config::default().punch_fade, Duration::from_millis(10));
+    assert_eq!(
+        Config::from_args(["--punch-fade=25"]).punch_fade,
+        Duration::from_millis(25)
+    );
+    assert_eq!(
+        Config::from_args(["--punch-fade=0"]).punch_fade,
+        Duration::ZERO
+    );
+    assert_eq!(
+        Config::from_args(["--punch-fade=soft"]).punch_fade,
+        Duration::from_millis(10)
+    );
+}
+
+#[test]
+fn test_analysis_flags() {
+    let defaults = Config::default().analysis;
+    assert_eq!(defaults, world_sys::AnalyzeOptions::default());
+
+    let config = Config::from_args(["--f0-floor=50", "--f0-ceil=1200", "--frame-period=10"]);
+    assert_eq!(config.analysis.f0_floor, 50.0);
+    assert_eq!(config.analysis.f0_ceil, 1200.0);
+    assert_eq!(config.analysis.frame_period, 10.0);
+    assert_eq!(config.analysis.d4c_threshold, defaults.d4c_threshold);
+
+    // An unparsable value is skipped alone; an invalid combination resets all.
+    assert_eq!(Config::from_args(["--f0-floor=low"]).analysis, defaults);
+    assert_eq!(Config::from_args(["--f0-floor=900"]).analysis, defaults);
+    assert_eq!(
+        Config::from_args(["--frame-period=0", "--f0-ceil=1200"]).analysis,
+        defaults
+    );
+
+    assert_eq!(
+        Config::from_args(["--d4c-threshold=0.5"])
+            .analysis
+            .d4c_threshold,
+        0.5
+    );
+    assert_eq!(
+        Config::from_args(["--d4c-threshold=1.5"]).analysis,
+        defaults
+    );
+    assert_eq!(
+        Config::from_args(["--cheaptrick-q1=-0.05"])
+            .analysis
+            .cheaptrick_q1,
+        Some(-0.05)
+    );
+    assert_eq!(Config::from_args(["--cheaptrick-q1=3"]).analysis, defaults);
+    assert!(defaults.refine_f0);
+    assert!(!Config::from_args(["--no-refine-f0"]).analysis.refine_f0);
+}
+
+#[test]
+fn test_no_normalize_flag() {
+    assert!(Config::default().normalize_synthesis);
+    assert!(Config::safe().normalize_synthesis);
+    assert!(!Config::from_args(["--no-normalize"]).normalize_synthesis);
+}
@@ -1,35 +1,14 @@
-use std::f32::consts::PI;
-use std::path::{Path, PathBuf};
-
-/// Generate a mono test WAV file at `path` using hound.
-fn generate_test_wav(path: &Path, sample_rate: u32, duration_secs: f32, freq: f32) {
-    let spec = hound::WavSpec {
-        channels: 1,
-        sample_rate,
-        bits_per_sample: 16,
-        sample_format: hound::SampleFormat::Int,
-    };
-    let mut writer = hound::WavWriter::create(path, spec).expect("failed to create WAV");
-    let num_samples = (sample_rate as f32 * duration_secs) as usize;
-    for i in 0..num_samples {
-        let t = i as f32 / sample_rate as f32;
-        let sample = (2.0 * PI * freq * t).sin() * 0.5;
-        let amplitude = (sample * i16::MAX as f32) as i16;
-        writer.write_sample(amplitude).expect("failed to write sample");
-    }
-    writer.finalize().expect("failed to finalize WAV");
-}
+mod test_support;
 
-fn test_wav_path(name: &str) -> PathBuf {
-    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/test_samples");
-    std::fs::create_dir_all(&dir).expect("failed to create test_samples dir");
-    dir.join(name)
-}
+use std::path::Path;
+
+use tempfile::TempDir;
+use test_support::{interleave, sine, write_wav};
 
 #[test]
 fn test_decoder_wav_basic() {
-    let path = test_wav_path("test_basic_440hz.wav");
-    generate_test_wav(&path, 44100, 0.5, 440.0);
+    let dir = TempDir::new().expect("failed to create temp dir");
+    let path = write_wav(dir.path(), "basic_440hz.wav", &sine(440.0, 0.5, 44100, 0.5));
 
     let audio = voiceforge::audio::decoder::decode_file(&path).expect("failed to decode WAV");
 
@@ -52,27 +31,9 @@ fn test_decoder_wav_basic() {
 
 #[test]
 fn test_decoder_stereo_wav() {
-    let path = test_wav_path("test_stereo.wav");
-
-    let spec = hound::WavSpec {
-        channels: 2,
-        sample_rate: 22050,
-        bits_per_sample: 16,
-        sample_format: hound::SampleFormat::Int,
-    };
-    let mut writer = hound::WavWriter::create(&path, spec).expect("failed to create WAV");
-    for i in 0..22050 {
-        let t = i as f32 / 22050.0;
-        let left = (2.0 * PI * 440.0 * t).sin() * 0.5;
-        let right = (2.0 * PI * 880.0 * t).sin() * 0.3;
-        writer
-            .write_sample((left * i16::MAX as f32) as i16)
-            .expect("write");
-        writer
-            .write_sample((right * i16::MAX as f32) as i16)
-            .expect("write");
-    }
-    writer.finalize().expect("finalize");
+    let dir = TempDir::new().expect("failed to create temp dir");
+    let stereo = interleave(&[sine(440.0, 0.5, 22050, 1.0), sine(880.0, 0.3, 22050, 1.0)]);
+    let path = write_wav(dir.path(), "stereo.wav", &stereo);
 
     let audio = voiceforge::audio::decoder::decode_file(&path).expect("failed to decode");
 
@@ -91,8 +52,12 @@ fn test_decoder_invalid_path() {
 
 #[test]
 fn test_decoder_frame_count() {
-    let path = test_wav_path("test_framecount_440hz.wav");
-    generate_test_wav(&path, 44100, 0.5, 440.0);
+    let dir = TempDir::new().expect("failed to create temp dir");
+    let path = write_wav(
+        dir.path(),
+        "framecount_440hz.wav",
+        &sine(440.0, 0.5, 44100, 0.5),
+    );
 
     let audio = voiceforge::audio::decoder::decode_file(&path).expect("failed to decode WAV");
 
@@ -100,3 +65,91 @@ fn test_decoder_frame_count() {
     assert_eq!(audio.frame_count(), audio.samples.len());
     assert_eq!(audio.frame_count(), (44100.0 * 0.5) as usize);
 }
+
+#[test]
+fn test_concat_appends_buffers_of_one_format() {
+    let first = sine(440.0, 0.5, 16000, 0.5);
+    let second = sine(220.0, 0.5, 16000, 0.25);
+    let joined = first.concat(&second).unwrap();
+    assert_eq!(joined.format(), first.format());
+    assert_eq!(joined.frame_count(), 8000 + 4000);
+    assert_eq!(joined.samples[..8000], first.samples[..]);
+    assert_eq!(joined.samples[8000..], second.samples[..]);
+
+    let err = first.concat(&sine(440.0, 0.5, 44100, 0.1)).unwrap_err();
+    assert_eq!(err, "concat: expected 16000 Hz mono, got 44100 Hz mono");
+    assert!(first
+        .concat(&interleave(&[second.clone(), second]))
+        .is_err());
+}
+
+#[test]
+fn test_decoder_roundtrips_generated_fixtures() {
+    let dir = TempDir::new().expect("failed to create temp dir");
+    for (name, audio) in [
+        (
+            "chord.wav",
+            test_support::chord(&[261.6, 329.6, 392.0], 0.8, 16000, 0.5),
+        ),
+        ("noise.wav", test_support::white_noise(0.5, 16000, 0.5, 7)),
+        (
+            "sweep.wav",
+            test_support::sweep(100.0, 4000.0, 0.7, 16000, 0.5),
+        ),
+    ] {
+        let path = write_wav(dir.path(), name, &audio);
+        let decoded = voiceforge::audio::decoder::decode_file(&path).expect("failed to decode");
+        assert_eq!(decoded.samples.len(), audio.samples.len(), "{name}");
+        let max_err = decoded
+            .samples
+            .iter()
+            .zip(&audio.samples)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0_f32, f32::max);
+        // 16-bit quantization.
+        assert!(
+            max_err < 2.0 / i16::MAX as f32,
+            "{name}: max error {max_err}"
+        );
+    }
+}
+
+#[test]
+fn test_audio_format_survives_decode() {
+    use voiceforge::audio::decoder::AudioFormat;
+
+    let dir = TempDir::new().expect("failed to create temp dir");
+    let stereo = interleave(&[sine(440.0, 0.5, 22050, 0.1), sine(660.0, 0.5, 22050, 0.1)]);
+    let path = write_wav(dir.path(), "stereo.wav", &stereo);
+    let decoded = voiceforge::audio::decoder::decode_file(&path).expect("failed to decode");
+    assert_eq!(
+        decoded.format(),
+        AudioFormat {
+            sample_rate: 22050,
+            channels: 2
+        }
+    );
+    assert_eq!(decoded.format(), stereo.format());
+}
+
+#[test]
+fn test_audio_format_check_names_stage_and_both_formats() {
+    use voiceforge::audio::decoder::AudioFormat;
+
+    let expected = AudioFormat::mono(48000);
+    assert_eq!(expected.check(AudioFormat::mono(48000), "effects"), Ok(()));
+    assert_eq!(
+        expected.check(AudioFormat::mono(44100), "effects"),
+        Err("effects: expected 48000 Hz mono, got 44100 Hz mono".to_string())
+    );
+    let stereo = AudioFormat {
+        sample_rate: 48000,
+        channels: 2,
+    };
+    assert_eq!(stereo.to_string(), "48000 Hz stereo");
+    let surround = AudioFormat {
+        sample_rate: 48000,
+        channels: 6,
+    };
+    assert_eq!(surround.to_string(), "48000 Hz 6 ch");
+}
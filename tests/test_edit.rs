@@ -0,0 +1,263 @@
+mod test_support;
+
+use voiceforge::audio::decoder::AudioData;
+use voiceforge::audio::edit::{resample, splice, EditError, LengthMode, Splice};
+
+const RATE: u32 = 1000;
+
+fn constant(value: f32, frames: usize) -> AudioData {
+    AudioData {
+        samples: vec![value; frames],
+        sample_rate: RATE,
+        channels: 1,
+    }
+}
+
+/// Mono ramp whose sample `i` is `offset + i`, so every sample says where
+/// it came from.
+fn ramp(offset: f32, frames: usize) -> AudioData {
+    AudioData {
+        samples: (0..frames).map(|i| offset + i as f32).collect(),
+        sample_rate: RATE,
+        channels: 1,
+    }
+}
+
+fn region(start: usize, end: usize, fade: usize, mode: LengthMode) -> Splice {
+    Splice {
+        start,
+        end,
+        fade,
+        mode,
+    }
+}
+
+#[test]
+fn test_fit_keeps_length_and_everything_outside_region() {
+    let base = ramp(0.0, 100);
+    let clip = ramp(1000.0, 30);
+    let out = splice(&base, &clip, &region(20, 50, 0, LengthMode::Fit)).unwrap();
+    assert_eq!(out.samples.len(), base.samples.len());
+    assert_eq!(out.samples[..20], base.samples[..20]);
+    assert_eq!(out.samples[20..50], clip.samples[..]);
+    assert_eq!(out.samples[50..], base.samples[50..]);
+}
+
+#[test]
+fn test_fit_truncates_longer_and_pads_shorter_clips() {
+    let base = ramp(0.0, 100);
+    let long = ramp(1000.0, 45);
+    let out = splice(&base, &long, &region(20, 50, 0, LengthMode::Fit)).unwrap();
+    assert_eq!(out.samples.len(), 100);
+    assert_eq!(out.samples[20..50], long.samples[..30]);
+    assert_eq!(out.samples[50], 50.0);
+
+    let short = ramp(1000.0, 10);
+    let out = splice(&base, &short, &region(20, 50, 0, LengthMode::Fit)).unwrap();
+    assert_eq!(out.samples.len(), 100);
+    assert_eq!(out.samples[20..30], short.samples[..]);
+    assert!(out.samples[30..50].iter().all(|&s| s == 0.0));
+    assert_eq!(out.samples[50..], base.samples[50..]);
+}
+
+#[test]
+fn test_shift_moves_remainder_by_length_difference() {
+    let base = ramp(0.0, 100);
+    for clip_frames in [5, 30, 70] {
+        let clip = ramp(1000.0, clip_frames);
+        let out = splice(&base, &clip, &region(20, 50, 0, LengthMode::Shift)).unwrap();
+        assert_eq!(
+            out.samples.len(),
+            100 - 30 + clip_frames,
+            "{clip_frames} frames"
+        );
+        assert_eq!(out.samples[..20], base.samples[..20]);
+        assert_eq!(out.samples[20..20 + clip_frames], clip.samples[..]);
+        assert_eq!(out.samples[20 + clip_frames..], base.samples[50..]);
+        assert_eq!(
+            out.duration_secs(),
+            (100 - 30 + clip_frames) as f64 / f64::from(RATE)
+        );
+    }
+}
+
+#[test]
+fn test_crossfades_are_continuous_at_both_boundaries() {
+    // Base at 0, clip at 1: the output must ramp, never jump. (A short clip
+    // in Fit mode is padded with silence inside the region, so it only
+    // appears in Shift mode here.)
+    let cases = [
+        (LengthMode::Fit, 30),
+        (LengthMode::Fit, 60),
+        (LengthMode::Shift, 12),
+        (LengthMode::Shift, 30),
+        (LengthMode::Shift, 60),
+    ];
+    for (mode, clip_frames) in cases {
+        let out = splice(
+            &constant(0.0, 100),
+            &constant(1.0, clip_frames),
+            &region(20, 50, 8, mode),
+        )
+        .unwrap();
+        let inserted = match mode {
+            LengthMode::Fit => 30,
+            LengthMode::Shift => clip_frames,
+        };
+        // The 12-frame clip only has room for two 6-frame fades.
+        let fade = 8.min(inserted / 2);
+        let max_step = 1.0 / fade as f32 + 1e-6;
+        for (i, pair) in out.samples.windows(2).enumerate() {
+            let step = (pair[1] - pair[0]).abs();
+            assert!(
+                step <= max_step,
+                "{mode:?} {clip_frames}: step {step} at {i}"
+            );
+        }
+        // Untouched outside the region, fully the clip between the fades.
+        assert_eq!(out.samples[19], 0.0);
+        assert!(out.samples[20] > 0.0 && out.samples[20] < 0.5);
+        if inserted > 2 * fade {
+            assert_eq!(out.samples[20 + fade], 1.0, "{mode:?} {clip_frames}");
+        }
+        let after = 20 + inserted;
+        assert!(out.samples[after - 1] > 0.0 && out.samples[after - 1] < 0.5);
+        assert_eq!(out.samples[after], 0.0);
+    }
+}
+
+#[test]
+fn test_fade_out_joins_the_base_just_before_region_end() {
+    // On ramps, a continuous join means the last faded sample is close to
+    // the base sample right before `end`, and the next one is base[end].
+    let base = ramp(0.0, 100);
+    let clip = ramp(0.0, 40);
+    let out = splice(&base, &clip, &region(20, 50, 4, LengthMode::Shift)).unwrap();
+    let last_inserted = 20 + 40 - 1;
+    assert!(
+        (out.samples[last_inserted] - 49.0).abs() < 5.0,
+        "{}",
+        out.samples[last_inserted]
+    );
+    assert_eq!(out.samples[last_inserted + 1], 50.0);
+}
+
+#[test]
+fn test_fade_shortened_to_fit_region_and_clip() {
+    // A fade longer than half the region is cut so the two fades don't
+    // overlap; a zero fade is a hard cut.
+    let out = splice(
+        &constant(0.0, 100),
+        &constant(1.0, 10),
+        &region(40, 50, 1000, LengthMode::Fit),
+    )
+    .unwrap();
+    assert_eq!(out.samples.len(), 100);
+    assert!(out.samples[40..50].iter().all(|&s| s > 0.0 && s < 1.0));
+    assert!(out.samples[40..45].windows(2).all(|w| w[1] > w[0]));
+    assert!(out.samples[45..50].windows(2).all(|w| w[1] < w[0]));
+
+    let out = splice(
+        &constant(0.0, 100),
+        &constant(1.0, 10),
+        &region(40, 50, 0, LengthMode::Fit),
+    )
+    .unwrap();
+    assert!(out.samples[40..50].iter().all(|&s| s == 1.0));
+
+    let out = splice(
+        &constant(0.0, 100),
+        &constant(1.0, 1),
+        &region(40, 50, 8, LengthMode::Shift),
+    )
+    .unwrap();
+    assert_eq!(out.samples.len(), 91);
+    assert_eq!(out.samples[40], 1.0);
+}
+
+#[test]
+fn test_stereo_channels_stay_interleaved() {
+    let base = test_support::interleave(&[constant(0.25, 50), constant(-0.25, 50)]);
+    let clip = test_support::interleave(&[constant(0.75, 20), constant(-0.75, 20)]);
+    let out = splice(&base, &clip, &region(10, 20, 0, LengthMode::Shift)).unwrap();
+    assert_eq!(out.channels, 2);
+    assert_eq!(out.frame_count(), 50 - 10 + 20);
+    let left: Vec<f32> = out.samples.iter().step_by(2).copied().collect();
+    let right: Vec<f32> = out.samples.iter().skip(1).step_by(2).copied().collect();
+    assert!(left[10..30].iter().all(|&s| s == 0.75));
+    assert!(right[10..30].iter().all(|&s| s == -0.75));
+    assert!(left[30..].iter().all(|&s| s == 0.25));
+    assert!(right[30..].iter().all(|&s| s == -0.25));
+}
+
+#[test]
+fn test_splice_rejects_other_layouts_and_bad_regions() {
+    let base = constant(0.0, 100);
+    let stereo = test_support::interleave(&[constant(1.0, 10), constant(1.0, 10)]);
+    let err = splice(&base, &stereo, &region(10, 20, 0, LengthMode::Fit)).unwrap_err();
+    assert_eq!(
+        err,
+        EditError::ChannelMismatch {
+            expected: 1,
+            got: 2
+        }
+    );
+    assert_eq!(err.to_string(), "clip has 2 channel(s), the buffer 1");
+
+    let other_rate = AudioData {
+        sample_rate: 2 * RATE,
+        ..constant(1.0, 10)
+    };
+    let err = splice(&base, &other_rate, &region(10, 20, 0, LengthMode::Fit)).unwrap_err();
+    assert_eq!(
+        err,
+        EditError::RateMismatch {
+            expected: 1000,
+            got: 2000
+        }
+    );
+
+    let clip = constant(1.0, 10);
+    for (start, end) in [(20, 20), (30, 20), (90, 101)] {
+        let err = splice(&base, &clip, &region(start, end, 0, LengthMode::Fit)).unwrap_err();
+        assert_eq!(
+            err,
+            EditError::InvalidRegion {
+                start,
+                end,
+                frames: 100
+            }
+        );
+    }
+    // The whole buffer is a valid region.
+    assert!(splice(&base, &clip, &region(0, 100, 4, LengthMode::Shift)).is_ok());
+}
+
+#[test]
+fn test_resample_to_session_rate() {
+    let clip = test_support::sine(50.0, 0.5, 2000, 0.5);
+    let out = resample(&clip, RATE);
+    assert_eq!(out.sample_rate, RATE);
+    assert_eq!(out.frame_count(), 500);
+    assert!((out.duration_secs() - clip.duration_secs()).abs() < 1e-9);
+
+    // Linear data stays linear; same rate is a plain copy.
+    let up = resample(&ramp(0.0, 11), 2 * RATE);
+    assert_eq!(up.frame_count(), 22);
+    for (i, &s) in up.samples.iter().take(20).enumerate() {
+        assert!((s - i as f32 / 2.0).abs() < 1e-5, "{i}: {s}");
+    }
+    let same = resample(&clip, 2000);
+    assert_eq!(same.samples, clip.samples);
+
+    let stereo = test_support::interleave(&[constant(0.5, 100), constant(-0.5, 100)]);
+    let down = resample(
+        &AudioData {
+            sample_rate: 2000,
+            ..stereo
+        },
+        RATE,
+    );
+    assert_eq!((down.channels, down.frame_count()), (2, 50));
+    assert!(down.samples.chunks(2).all(|f| f == [0.5, -0.5]));
+}
@@ -1,4 +1,7 @@
-use voiceforge::dsp::effects::{apply_effects, apply_gain, EffectsParams};
+use voiceforge::dsp::effects::{
+    apply_chorus, apply_delay, apply_effects, apply_gain, apply_limiter, apply_tremolo,
+    EffectsParams, EqParams, Precision, ReverbPreset,
+};
 
 fn sine_wave(freq: f32, sample_rate: u32, num_samples: usize) -> Vec<f32> {
     (0..num_samples)
@@ -15,7 +18,7 @@ fn test_effects_neutral_passthrough() {
     let input = sine_wave(440.0, 44100, 4096);
     let params = EffectsParams::default();
     assert!(params.is_neutral());
-    let output = apply_effects(&input, 44100, &params);
+    let output = apply_effects(&input, 44100, 1, &params);
     assert_eq!(output, input);
 }
 
@@ -55,7 +58,7 @@ fn test_effects_lowcut_attenuates_bass() {
         low_cut_hz: 500.0,
         ..Default::default()
     };
-    let output = apply_effects(&input, sr, &params);
+    let output = apply_effects(&input, sr, 1, &params);
     // Skip the first 1000 samples for filter settling
     let rms_in = rms(&input[1000..]);
     let rms_out = rms(&output[1000..]);
@@ -74,7 +77,7 @@ fn test_effects_highcut_attenuates_treble() {
         high_cut_hz: 2000.0,
         ..Default::default()
     };
-    let output = apply_effects(&input, sr, &params);
+    let output = apply_effects(&input, sr, 1, &params);
     let rms_in = rms(&input[1000..]);
     let rms_out = rms(&output[1000..]);
     assert!(
@@ -100,8 +103,8 @@ fn test_effects_compressor_reduces_dynamics() {
         compressor_thresh_db: -20.0,
         ..Default::default()
     };
-    let loud_out = apply_effects(&loud, sr, &params);
-    let quiet_out = apply_effects(&quiet, sr, &params);
+    let loud_out = apply_effects(&loud, sr, 1, &params);
+    let quiet_out = apply_effects(&quiet, sr, 1, &params);
 
     // Skip the first 2000 samples for filter/envelope settling
     let input_ratio = rms(&loud[2000..]) / rms(&quiet[2000..]);
@@ -113,6 +116,63 @@ fn test_effects_compressor_reduces_dynamics() {
     );
 }
 
+#[test]
+fn test_compressor_ratio_sets_how_much_the_dynamics_shrink() {
+    let sr = 44100;
+    let loud: Vec<f32> = sine_wave(440.0, sr, 44100)
+        .iter()
+        .map(|s| s * 0.8)
+        .collect();
+    let quiet: Vec<f32> = sine_wave(440.0, sr, 44100)
+        .iter()
+        .map(|s| s * 0.1)
+        .collect();
+    let output_ratio = |ratio: f32| {
+        let params = EffectsParams {
+            compressor_thresh_db: -20.0,
+            comp_ratio: ratio,
+            ..Default::default()
+        };
+        rms(&apply_effects(&loud, sr, 1, &params)[2000..])
+            / rms(&apply_effects(&quiet, sr, 1, &params)[2000..])
+    };
+    let gentle = output_ratio(2.0);
+    let hard = output_ratio(8.0);
+    assert!(
+        hard < gentle,
+        "loud/quiet {hard:.2} at 8:1, {gentle:.2} at 2:1"
+    );
+}
+
+#[test]
+fn test_compressor_slow_attack_lets_a_short_burst_through() {
+    let sr = 44100;
+    // 10 ms burst after 100 ms of silence.
+    let burst_len = sr as usize / 100;
+    let mut input = vec![0.0f32; sr as usize / 10];
+    input.extend(sine_wave(1000.0, sr, burst_len).iter().map(|s| s * 0.9));
+    input.extend(vec![0.0f32; sr as usize / 10]);
+    let burst = sr as usize / 10..sr as usize / 10 + burst_len;
+
+    let burst_change_db = |attack_ms: f32| {
+        let params = EffectsParams {
+            compressor_thresh_db: -20.0,
+            comp_attack_ms: attack_ms,
+            comp_makeup_db: Some(0.0),
+            ..Default::default()
+        };
+        let output = apply_effects(&input, sr, 1, &params);
+        20.0 * (rms(&output[burst.clone()]) / rms(&input[burst.clone()])).log10()
+    };
+    let slow = burst_change_db(50.0);
+    let fast = burst_change_db(1.0);
+    assert!(slow > -1.5, "50 ms attack took the burst down {slow:.2} dB");
+    assert!(
+        fast < slow - 6.0,
+        "1 ms attack only {fast:.2} dB vs {slow:.2} dB"
+    );
+}
+
 #[test]
 fn test_effects_pitch_shift_up() {
     let sr = 44100;
@@ -121,8 +181,16 @@ fn test_effects_pitch_shift_up() {
         pitch_shift_semitones: 12.0,
         ..Default::default()
     };
-    let output = apply_effects(&input, sr, &params);
-    // +12 semitones = 2x frequency → buffer should be ~half length
+    let output = apply_effects(&input, sr, 1, &params);
+    // The phase vocoder keeps the duration exactly.
+    assert_eq!(output.len(), input.len());
+
+    // Resampling (the chipmunk effect) halves it.
+    let chipmunk = EffectsParams {
+        pitch_shift_resample: true,
+        ..params
+    };
+    let output = apply_effects(&input, sr, 1, &chipmunk);
     let ratio = output.len() as f32 / input.len() as f32;
     assert!(
         (ratio - 0.5).abs() < 0.01,
@@ -138,8 +206,16 @@ fn test_effects_pitch_shift_down() {
         pitch_shift_semitones: -12.0,
         ..Default::default()
     };
-    let output = apply_effects(&input, sr, &params);
-    // -12 semitones = 0.5x frequency → buffer should be ~double length
+    let output = apply_effects(&input, sr, 1, &params);
+    // The phase vocoder keeps the duration exactly.
+    assert_eq!(output.len(), input.len());
+
+    // Resampling (the chipmunk effect) doubles it.
+    let chipmunk = EffectsParams {
+        pitch_shift_resample: true,
+        ..params
+    };
+    let output = apply_effects(&input, sr, 1, &chipmunk);
     let ratio = output.len() as f32 / input.len() as f32;
     assert!(
         (ratio - 2.0).abs() < 0.01,
@@ -147,6 +223,40 @@ fn test_effects_pitch_shift_down() {
     );
 }
 
+#[test]
+fn test_effects_pitch_shift_moves_the_spectral_peak_at_the_same_length() {
+    use voiceforge::dsp::spectrum::compute_spectrum_dbfs;
+
+    let sr = 44100;
+    let input = sine_wave(440.0, sr, 44100);
+    const FFT_SIZE: usize = 8192;
+    let bin_hz = sr as f32 / FFT_SIZE as f32;
+    for (semitones, expected_hz) in [(12.0, 880.0), (-12.0, 220.0), (7.0, 659.26)] {
+        let params = EffectsParams {
+            pitch_shift_semitones: semitones,
+            ..Default::default()
+        };
+        let output = apply_effects(&input, sr, 1, &params);
+        assert_eq!(output.len(), input.len());
+        let mid = output.len() / 2 - FFT_SIZE / 2;
+        let spectrum = compute_spectrum_dbfs(&output[mid..mid + FFT_SIZE], FFT_SIZE);
+        let peak = (1..spectrum.len())
+            .max_by(|&a, &b| spectrum[a].total_cmp(&spectrum[b]))
+            .unwrap();
+        let peak_hz = peak as f32 * bin_hz;
+        assert!(
+            (peak_hz - expected_hz).abs() <= 2.0 * bin_hz,
+            "{semitones:+} st: peak at {peak_hz:.1} Hz, expected ~{expected_hz} Hz"
+        );
+        // A steady tone stays steady: no dropouts across the buffer.
+        let level = rms(&output[mid..mid + FFT_SIZE]);
+        assert!(
+            (level / rms(&input) - 1.0).abs() < 0.2,
+            "{semitones:+} st: level {level}"
+        );
+    }
+}
+
 #[test]
 fn test_effects_reverb_differs_from_dry() {
     let sr = 44100;
@@ -155,7 +265,7 @@ fn test_effects_reverb_differs_from_dry() {
         reverb_mix: 0.5,
         ..Default::default()
     };
-    let output = apply_effects(&input, sr, &params);
+    let output = apply_effects(&input, sr, 1, &params);
     assert_eq!(output.len(), input.len());
     // Reverb should produce a different signal
     let diff: f32 = input
@@ -164,7 +274,212 @@ fn test_effects_reverb_differs_from_dry() {
         .map(|(a, b)| (a - b).abs())
         .sum::<f32>()
         / input.len() as f32;
-    assert!(diff > 0.01, "reverb output should differ from dry: avg diff = {diff}");
+    assert!(
+        diff > 0.01,
+        "reverb output should differ from dry: avg diff = {diff}"
+    );
+}
+
+/// Wet-only impulse response of a reverb preset, as RMS per 50 ms window.
+fn reverb_window_rms(preset: ReverbPreset, sr: u32, secs: f32) -> Vec<f32> {
+    let mut impulse = vec![0.0_f32; (sr as f32 * secs) as usize];
+    impulse[0] = 1.0;
+    let params = EffectsParams {
+        reverb_mix: 1.0,
+        reverb_preset: preset,
+        ..Default::default()
+    };
+    let ir = apply_effects(&impulse, sr, 1, &params);
+    ir.chunks(sr as usize / 20).map(rms).collect()
+}
+
+/// Fraction of samples in each 50 ms window above a small threshold — a
+/// simple echo-density measure.
+fn reverb_window_density(preset: ReverbPreset, sr: u32, secs: f32) -> Vec<f32> {
+    let mut impulse = vec![0.0_f32; (sr as f32 * secs) as usize];
+    impulse[0] = 1.0;
+    let params = EffectsParams {
+        reverb_mix: 1.0,
+        reverb_preset: preset,
+        ..Default::default()
+    };
+    let ir = apply_effects(&impulse, sr, 1, &params);
+    ir.chunks(sr as usize / 20)
+        .map(|w| w.iter().filter(|s| s.abs() > 1e-4).count() as f32 / w.len() as f32)
+        .collect()
+}
+
+#[test]
+fn test_reverb_presets_impulse_responses_differ() {
+    let sr = 44100;
+    let rms_by_preset: Vec<Vec<f32>> = ReverbPreset::ALL
+        .iter()
+        .map(|&p| reverb_window_rms(p, sr, 1.0))
+        .collect();
+
+    // Pairwise, the window-RMS envelopes must differ noticeably.
+    for a in 0..rms_by_preset.len() {
+        for b in a + 1..rms_by_preset.len() {
+            let diff: f32 = rms_by_preset[a]
+                .iter()
+                .zip(&rms_by_preset[b])
+                .map(|(x, y)| (x - y).abs())
+                .sum();
+            assert!(
+                diff > 1e-3,
+                "{:?} and {:?} have near-identical envelopes (diff {diff})",
+                ReverbPreset::ALL[a],
+                ReverbPreset::ALL[b]
+            );
+        }
+    }
+
+    // Decay: energy remaining after 500 ms relative to the first 100 ms.
+    let tail_ratio = |env: &[f32]| {
+        let head: f32 = env[..2].iter().map(|x| x * x).sum();
+        let tail: f32 = env[10..].iter().map(|x| x * x).sum();
+        tail / head
+    };
+    let room = tail_ratio(&rms_by_preset[0]);
+    let hall = tail_ratio(&rms_by_preset[1]);
+    let plate = tail_ratio(&rms_by_preset[2]);
+    assert!(
+        hall > plate,
+        "hall should decay slower than plate: {hall} vs {plate}"
+    );
+    assert!(
+        plate > room,
+        "plate should decay slower than room: {plate} vs {room}"
+    );
+}
+
+#[test]
+fn test_reverb_presets_echo_density_growth() {
+    let sr = 44100;
+    // Room's short, dense early taps and combs fill the first window faster
+    // than the hall's sparse, late reflections.
+    let room = reverb_window_density(ReverbPreset::Room, sr, 0.5);
+    let hall = reverb_window_density(ReverbPreset::Hall, sr, 0.5);
+    assert!(
+        room[0] > hall[0],
+        "room should be denser in the first 50 ms: {} vs {}",
+        room[0],
+        hall[0]
+    );
+    // Density grows over time for every preset as the combs recirculate.
+    for preset in ReverbPreset::ALL {
+        let d = reverb_window_density(preset, sr, 0.5);
+        assert!(
+            d[3] >= d[0],
+            "{preset:?}: density fell from {} to {}",
+            d[0],
+            d[3]
+        );
+    }
+}
+
+/// Impulse response of the full-wet Plate reverb with the given room shape.
+fn reverb_ir(sr: u32, secs: f32, size: f32, damping: f32, predelay_ms: f32) -> Vec<f32> {
+    let mut impulse = vec![0.0_f32; (sr as f32 * secs) as usize];
+    impulse[0] = 1.0;
+    let params = EffectsParams {
+        reverb_mix: 1.0,
+        reverb_size: size,
+        reverb_damping: damping,
+        reverb_predelay_ms: predelay_ms,
+        ..Default::default()
+    };
+    apply_effects(&impulse, sr, 1, &params)
+}
+
+/// RT60 in seconds from the Schroeder backward-integrated energy decay,
+/// extrapolated from the -5 to -35 dB span.
+fn rt60(ir: &[f32], sr: u32) -> f32 {
+    let mut energy: Vec<f64> = ir.iter().map(|&s| (s as f64).powi(2)).collect();
+    for i in (0..energy.len() - 1).rev() {
+        energy[i] += energy[i + 1];
+    }
+    let total = energy[0];
+    let time_at = |db: f64| {
+        energy
+            .iter()
+            .position(|&e| 10.0 * (e / total).log10() <= db)
+            .unwrap() as f32
+            / sr as f32
+    };
+    2.0 * (time_at(-35.0) - time_at(-5.0))
+}
+
+#[test]
+fn test_reverb_size_lengthens_the_decay() {
+    let sr = 44100;
+    let small = rt60(&reverb_ir(sr, 6.0, 1.0, 0.0, 0.0), sr);
+    let large = rt60(&reverb_ir(sr, 6.0, 1.8, 0.0, 0.0), sr);
+    assert!(
+        large > small * 1.5,
+        "RT60 {small:.2} s at size 1, {large:.2} s at size 1.8"
+    );
+}
+
+#[test]
+fn test_reverb_damping_darkens_the_tail() {
+    use voiceforge::dsp::spectrum::compute_spectrum_dbfs;
+
+    let sr = 44100;
+    const FFT_SIZE: usize = 2048;
+    // High-band over low-band energy in the tail (0.3 s on), in dB.
+    let tilt_db = |damping: f32| {
+        let ir = reverb_ir(sr, 1.2, 1.0, damping, 0.0);
+        let (mut low, mut high) = (0.0f64, 0.0f64);
+        for window in ir[(sr as usize * 3 / 10)..].chunks_exact(FFT_SIZE) {
+            for (k, db) in compute_spectrum_dbfs(window, FFT_SIZE)
+                .into_iter()
+                .enumerate()
+            {
+                let freq = k as f32 * sr as f32 / FFT_SIZE as f32;
+                let power = 10f64.powf(db as f64 / 10.0);
+                if (100.0..1000.0).contains(&freq) {
+                    low += power;
+                } else if freq >= 4000.0 {
+                    high += power;
+                }
+            }
+        }
+        10.0 * (high / low).log10()
+    };
+    let bright = tilt_db(0.0);
+    let dark = tilt_db(0.6);
+    assert!(
+        dark < bright - 6.0,
+        "tail HF/LF {bright:.1} dB undamped, {dark:.1} dB damped"
+    );
+}
+
+#[test]
+fn test_reverb_predelay_holds_the_wet_signal_back() {
+    let sr = 44100;
+    let ir = reverb_ir(sr, 0.5, 1.0, 0.0, 0.0);
+    let delayed = reverb_ir(sr, 0.5, 1.0, 0.0, 50.0);
+    assert_eq!(delayed.len(), ir.len());
+    let shift = 2205;
+    assert!(delayed[..shift].iter().all(|&s| s == 0.0));
+    assert_eq!(&delayed[shift..], &ir[..ir.len() - shift]);
+}
+
+#[test]
+fn test_reverb_presets_noop_at_zero_mix() {
+    let sr = 44100;
+    let input = sine_wave(440.0, sr, 4410);
+    for preset in ReverbPreset::ALL {
+        let params = EffectsParams {
+            reverb_mix: 0.0,
+            reverb_preset: preset,
+            ..Default::default()
+        };
+        assert!(params.is_neutral(), "{preset:?} at mix 0 should be neutral");
+        let output = apply_effects(&input, sr, 1, &params);
+        assert_eq!(output, input, "{preset:?} at mix 0 changed the signal");
+    }
 }
 
 #[test]
@@ -173,7 +488,7 @@ fn test_effects_empty_input() {
         gain_db: 6.0,
         ..Default::default()
     };
-    let output = apply_effects(&[], 44100, &params);
+    let output = apply_effects(&[], 44100, 1, &params);
     assert!(output.is_empty());
 }
 
@@ -211,6 +526,350 @@ fn test_effects_params_neutral_with_eq() {
     assert!(!params_with_eq.is_neutral());
 }
 
+#[test]
+fn test_limiter_holds_a_boosted_sine_under_the_ceiling() {
+    let sr = 44100;
+    let input = sine_wave(1000.0, sr, 44100);
+    let mut params = EffectsParams {
+        limiter_ceiling_db: -1.0,
+        ..Default::default()
+    };
+    params.eq.gains[5] = 6.0;
+    assert!(!params.is_neutral());
+    let ceiling = 10f32.powf(-1.0 / 20.0);
+
+    let output = apply_effects(&input, sr, 1, &params);
+    assert_eq!(output.len(), input.len());
+    let peak = output.iter().fold(0.0_f32, |m, s| m.max(s.abs()));
+    assert!(
+        peak <= ceiling + 1e-6,
+        "peak {peak} over the {ceiling} ceiling"
+    );
+    // A steady tone is turned down to the ceiling, not pumped below it.
+    let level = rms(&output[2000..]);
+    let at_ceiling = ceiling / std::f32::consts::SQRT_2;
+    assert!(
+        level > 0.9 * at_ceiling,
+        "rms {level}, a sine at the ceiling has {at_ceiling}"
+    );
+
+    // Even an opening peak, with no lookahead before it.
+    let mut impulse = vec![0.0_f32; 1000];
+    impulse[0] = 2.0;
+    impulse[500] = -3.0;
+    apply_limiter(&mut impulse, sr, -1.0, 50.0);
+    assert!(
+        impulse.iter().all(|s| s.abs() <= ceiling + 1e-6),
+        "{:?}",
+        (impulse[0], impulse[500])
+    );
+}
+
+#[test]
+fn test_limiter_passes_a_signal_below_the_ceiling_unchanged() {
+    let sr = 44100;
+    let input: Vec<f32> = sine_wave(440.0, sr, 44100)
+        .iter()
+        .map(|s| 0.5 * s)
+        .collect();
+    let params = EffectsParams {
+        limiter_ceiling_db: -3.0,
+        ..Default::default()
+    };
+    let output = apply_effects(&input, sr, 1, &params);
+    assert_eq!(output.len(), input.len());
+    for (i, (&out, &inp)) in output.iter().zip(&input).enumerate() {
+        assert!((out - inp).abs() < 1e-4, "sample {i}: {out} vs {inp}");
+    }
+    // 0 dB is off.
+    assert!(EffectsParams::default().is_neutral());
+}
+
+#[test]
+fn test_gate_silences_the_noise_floor_between_bursts() {
+    let sr = 44100;
+    let second = sr as usize;
+    let tone = sine_wave(440.0, sr, second);
+    let noise_amplitude = 0.5 * 10f32.powf(-70.0 / 20.0);
+    // xorshift noise, like the generators'.
+    let mut state: u32 = 0x9E37_79B9;
+    let mut noise = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        noise_amplitude * (2.0 * (state as f32 / u32::MAX as f32) - 1.0)
+    };
+    // Burst, gap, burst, gap: a second each.
+    let input: Vec<f32> = (0..4 * second)
+        .map(|i| {
+            let floor = noise();
+            if (i / second).is_multiple_of(2) {
+                0.5 * tone[i % second] + floor
+            } else {
+                floor
+            }
+        })
+        .collect();
+    let params = EffectsParams {
+        gate_thresh_db: -40.0,
+        ..Default::default()
+    };
+    assert!(!params.is_neutral());
+    let output = apply_effects(&input, sr, 1, &params);
+    assert_eq!(output.len(), input.len());
+
+    let db = |out: &[f32], inp: &[f32]| 20.0 * (rms(out) / rms(inp)).log10();
+    for burst in [0, 2] {
+        // Past the attack.
+        let steady = burst * second + second / 10..(burst + 1) * second;
+        let change = db(&output[steady.clone()], &input[steady]);
+        assert!(
+            change.abs() < 1.0,
+            "burst {burst} changed by {change:.2} dB"
+        );
+    }
+    for gap in [1, 3] {
+        // Past the hold and release.
+        let closed = gap * second + second / 2..(gap + 1) * second;
+        let change = db(&output[closed.clone()], &input[closed]);
+        assert!(change < -40.0, "gap {gap} only down {change:.1} dB");
+    }
+}
+
+#[test]
+fn test_deesser_ducks_the_sibilance_band_only() {
+    use voiceforge::dsp::spectrum::compute_spectrum_dbfs;
+
+    let sr = 44100;
+    let low = sine_wave(300.0, sr, sr as usize);
+    let high = sine_wave(7000.0, sr, sr as usize);
+    let input: Vec<f32> = low
+        .iter()
+        .zip(&high)
+        .map(|(&l, &h)| 0.4 * l + 0.2 * h)
+        .collect();
+    // 6 dB under the 7 kHz tone's peak.
+    let params = EffectsParams {
+        deess_thresh_db: 20.0 * 0.2_f32.log10() - 6.0,
+        ..Default::default()
+    };
+    assert!(!params.is_neutral());
+    let output = apply_effects(&input, sr, 1, &params);
+    assert_eq!(output.len(), input.len());
+
+    const FFT_SIZE: usize = 8192;
+    let mid = input.len() / 2 - FFT_SIZE / 2;
+    let before = compute_spectrum_dbfs(&input[mid..mid + FFT_SIZE], FFT_SIZE);
+    let after = compute_spectrum_dbfs(&output[mid..mid + FFT_SIZE], FFT_SIZE);
+    let change_db = |hz: f32| {
+        let bin = (hz * FFT_SIZE as f32 / sr as f32).round() as usize;
+        let peak = |spectrum: &[f32]| {
+            spectrum[bin - 2..=bin + 2]
+                .iter()
+                .copied()
+                .fold(f32::MIN, f32::max)
+        };
+        peak(&after) - peak(&before)
+    };
+    let sibilance = change_db(7000.0);
+    let voice = change_db(300.0);
+    assert!(sibilance < -4.0, "7 kHz only changed by {sibilance:.2} dB");
+    assert!(voice.abs() < 0.5, "300 Hz changed by {voice:.2} dB");
+
+    // Under the threshold it changes nothing.
+    let quiet = EffectsParams {
+        deess_thresh_db: -1.0,
+        ..Default::default()
+    };
+    assert_eq!(apply_effects(&input, sr, 1, &quiet), input);
+}
+
+#[test]
+fn test_chorus_keeps_the_length_and_combs_white_noise() {
+    use voiceforge::dsp::generate;
+    use voiceforge::dsp::spectrum::compute_spectrum_dbfs;
+
+    let sr = 44100;
+    let noise = generate::white_noise(0.5, sr, 1.0, 3).samples;
+
+    // Mix 0 is a bit-identical passthrough, in the chain and on its own.
+    let off = EffectsParams {
+        chorus_mix: 0.0,
+        chorus_rate_hz: 1.5,
+        ..Default::default()
+    };
+    assert!(off.is_neutral());
+    assert_eq!(apply_effects(&noise, sr, 1, &off), noise);
+    let mut direct = noise.clone();
+    apply_chorus(&mut direct, sr, 0.0, 1.5);
+    assert_eq!(direct, noise);
+
+    let params = EffectsParams {
+        chorus_mix: 0.5,
+        chorus_rate_hz: 0.1,
+        ..Default::default()
+    };
+    assert!(!params.is_neutral());
+    let output = apply_effects(&noise, sr, 1, &params);
+    assert_eq!(output.len(), noise.len());
+
+    // Over one short window the delays barely move, so the response is a
+    // comb: output over input swings by several dB from bin to bin.
+    const FFT_SIZE: usize = 4096;
+    let at = sr as usize / 2;
+    let before = compute_spectrum_dbfs(&noise[at..at + FFT_SIZE], FFT_SIZE);
+    let after = compute_spectrum_dbfs(&output[at..at + FFT_SIZE], FFT_SIZE);
+    let response: Vec<f32> = (20..FFT_SIZE / 4).map(|k| after[k] - before[k]).collect();
+    let mean = response.iter().sum::<f32>() / response.len() as f32;
+    let ripple =
+        (response.iter().map(|r| (r - mean).powi(2)).sum::<f32>() / response.len() as f32).sqrt();
+    assert!(ripple > 2.0, "comb ripple only {ripple:.2} dB");
+}
+
+#[test]
+fn test_tremolo_swings_the_level_at_its_rate() {
+    let sr = 44100;
+    let tone = sine_wave(1000.0, sr, 2 * sr as usize);
+    let params = EffectsParams {
+        tremolo_depth: 1.0,
+        tremolo_rate_hz: 4.0,
+        ..EffectsParams::default()
+    };
+    assert!(!params.is_neutral());
+    let output = apply_effects(&tone, sr, 1, &params);
+    assert_eq!(output.len(), tone.len());
+
+    let window = sr as usize / 20;
+    let levels: Vec<f32> = output
+        .chunks_exact(window)
+        .zip(tone.chunks_exact(window))
+        .map(|(out, dry)| rms(out) / rms(dry))
+        .collect();
+    let quietest = levels.iter().copied().fold(f32::MAX, f32::min);
+    let loudest = levels.iter().copied().fold(0.0f32, f32::max);
+    assert!(
+        quietest < 0.15,
+        "quietest window at {quietest:.2} of the dry level"
+    );
+    assert!(
+        loudest > 0.8,
+        "loudest window at {loudest:.2} of the dry level"
+    );
+    // One trough per cycle: 8 in two seconds at 4 Hz.
+    let troughs = levels
+        .windows(3)
+        .filter(|w| w[1] < w[0] && w[1] < w[2] && w[1] < 0.5)
+        .count();
+    assert_eq!(troughs, 8, "window levels {levels:?}");
+
+    // Depth 0 passes the signal through.
+    let mut dry = tone.clone();
+    apply_tremolo(&mut dry, sr, 0.0, 4.0);
+    assert_eq!(dry, tone);
+}
+
+#[test]
+fn test_delay_echoes_an_impulse_halving_each_repeat() {
+    let sr = 44100;
+    let mut impulse = vec![0.0f32; sr as usize];
+    impulse[0] = 1.0;
+    let params = EffectsParams {
+        delay_time_ms: 100.0,
+        delay_feedback: 0.5,
+        delay_mix: 0.5,
+        ..EffectsParams::default()
+    };
+    assert!(!params.is_neutral());
+    assert_eq!(params.tail_frames(sr), 3 * 4410);
+
+    let output = apply_effects(&impulse, sr, 1, &params);
+    assert_eq!(
+        output.len(),
+        impulse.len() + 3 * 4410,
+        "tail should fit three repeats"
+    );
+    assert!((output[0] - 0.5).abs() < 1e-6, "dry share {}", output[0]);
+    for (repeat, expected) in [0.5f32, 0.25, 0.125].into_iter().enumerate() {
+        let at = (repeat + 1) * 4410;
+        assert!(
+            (output[at] - expected).abs() < 1e-3,
+            "echo {} at {at}: {}",
+            repeat + 1,
+            output[at]
+        );
+    }
+    let stray = output
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| i % 4410 != 0)
+        .map(|(_, s)| s.abs())
+        .fold(0.0f32, f32::max);
+    assert!(stray < 1e-6, "energy between echoes: {stray}");
+
+    // The in-place stage keeps the buffer's length; only the chain extends it.
+    let mut direct = impulse.clone();
+    apply_delay(&mut direct, sr, 100.0, 0.5, 0.5);
+    assert_eq!(&direct[..], &output[..impulse.len()]);
+}
+
+#[test]
+fn test_lufs_tracks_gain_and_reads_dual_mono_like_mono() {
+    use voiceforge::dsp::effects::{measure_lufs_approx, measure_rms};
+    use voiceforge::dsp::loudness::SILENCE_DB;
+
+    let sr = 44100;
+    let tone = sine_wave(1000.0, sr, sr as usize * 2);
+    assert!((measure_rms(&tone) - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-3);
+
+    let lufs = measure_lufs_approx(&tone, sr, 1);
+    let half: Vec<f32> = tone.iter().map(|s| s * 0.5).collect();
+    assert!((lufs - measure_lufs_approx(&half, sr, 1) - 6.02).abs() < 0.05);
+    let dual: Vec<f32> = tone.iter().flat_map(|&s| [s, s]).collect();
+    assert!((measure_lufs_approx(&dual, sr, 2) - lufs).abs() < 0.01);
+
+    // Quiet passages under the relative gate don't drag the reading down
+    // (ungated, half the file at -46 dB would cost 3 dB; only the blocks
+    // straddling the edge count).
+    let mut gappy = tone.clone();
+    gappy.extend(half.iter().map(|s| s * 0.01));
+    assert!((measure_lufs_approx(&gappy, sr, 1) - lufs).abs() < 0.5);
+    assert_eq!(measure_lufs_approx(&vec![0.0; 4410], sr, 1), SILENCE_DB);
+}
+
+#[test]
+fn test_lufs_normalization_brings_a_quiet_sine_to_the_target() {
+    use voiceforge::dsp::effects::{apply_effects_normalized, measure_lufs_approx};
+
+    let sr = 48000;
+    // -20 dBFS RMS at 997 Hz, where K-weighting is about flat.
+    let quiet: Vec<f32> = sine_wave(997.0, sr, 3 * sr as usize)
+        .iter()
+        .map(|s| s * 0.1 * std::f32::consts::SQRT_2)
+        .collect();
+    let params = EffectsParams {
+        target_lufs: Some(-16.0),
+        ..EffectsParams::default()
+    };
+    assert!(!params.is_neutral());
+
+    let (louder, applied) = apply_effects_normalized(&quiet, sr, 1, &params);
+    let applied = applied.unwrap();
+    let hotter = 20.0 * (rms(&louder) / rms(&quiet)).log10();
+    assert!((hotter - 4.0).abs() < 0.5, "came out {hotter:.2} dB hotter");
+    assert!((applied.gain_db - hotter).abs() < 0.01);
+    assert!(
+        (applied.measured_lufs + 20.0).abs() < 0.5,
+        "measured {:.2} LUFS",
+        applied.measured_lufs
+    );
+    assert!((measure_lufs_approx(&louder, sr, 1) + 16.0).abs() < 0.01);
+
+    // Already at the target: (next to) no change.
+    let (again, second) = apply_effects_normalized(&louder, sr, 1, &params);
+    assert!(second.unwrap().gain_db.abs() < 0.5);
+    assert!((20.0 * (rms(&again) / rms(&louder)).log10()).abs() < 0.5);
+}
+
 #[test]
 fn test_eq_boost_at_1khz() {
     use voiceforge::dsp::effects::{apply_effects, EffectsParams};
@@ -224,7 +883,7 @@ fn test_eq_boost_at_1khz() {
     let mut params = EffectsParams::default();
     params.eq.gains[5] = 6.0;
 
-    let output = apply_effects(&input, sr, &params);
+    let output = apply_effects(&input, sr, 1, &params);
     let output_rms = rms(&output[2000..]);
 
     let ratio = output_rms / input_rms;
@@ -235,6 +894,46 @@ fn test_eq_boost_at_1khz() {
     );
 }
 
+#[test]
+fn test_parametric_band_notches_its_frequency_only() {
+    use voiceforge::dsp::effects::{ParametricBand, ParametricKind};
+
+    let sr = 44100;
+    let params = EffectsParams {
+        parametric: vec![ParametricBand {
+            freq_hz: 3700.0,
+            q: 8.0,
+            gain_db: -12.0,
+            kind: ParametricKind::Peak,
+        }],
+        ..EffectsParams::default()
+    };
+    assert!(!params.is_neutral());
+
+    let change_db = |freq: f32| {
+        let input = sine_wave(freq, sr, 44100);
+        let output = apply_effects(&input, sr, 1, &params);
+        20.0 * (rms(&output[4000..]) / rms(&input[4000..])).log10()
+    };
+    let notch = change_db(3700.0);
+    assert!(
+        (notch + 12.0).abs() < 0.5,
+        "3.7 kHz changed by {notch:.2} dB"
+    );
+    let off = change_db(2000.0);
+    assert!(off.abs() < 1.0, "2 kHz changed by {off:.2} dB");
+
+    // A 0 dB band is off.
+    let flat = EffectsParams {
+        parametric: vec![ParametricBand {
+            gain_db: 0.0,
+            ..params.parametric[0]
+        }],
+        ..EffectsParams::default()
+    };
+    assert!(flat.is_neutral());
+}
+
 #[test]
 fn test_eq_boost_isolation_far_band() {
     use voiceforge::dsp::effects::{apply_effects, EffectsParams};
@@ -248,7 +947,7 @@ fn test_eq_boost_isolation_far_band() {
     let mut params = EffectsParams::default();
     params.eq.gains[1] = 6.0;
 
-    let output = apply_effects(&input, sr, &params);
+    let output = apply_effects(&input, sr, 1, &params);
     let output_rms = rms(&output[2000..]);
 
     let ratio = output_rms / input_rms;
@@ -268,7 +967,7 @@ fn test_eq_extreme_low_sample_rate() {
     let mut params = EffectsParams::default();
     params.eq.gains[11] = 6.0; // 16 kHz band boost
 
-    let output = apply_effects(&input, sr, &params);
+    let output = apply_effects(&input, sr, 1, &params);
     assert_eq!(output.len(), input.len());
 }
 
@@ -281,7 +980,7 @@ fn test_eq_extreme_high_sample_rate() {
     let mut params = EffectsParams::default();
     params.eq.gains[10] = 6.0; // 10 kHz band boost
 
-    let output = apply_effects(&input, sr, &params);
+    let output = apply_effects(&input, sr, 1, &params);
     assert_eq!(output.len(), input.len());
     // Verify no NaN/Inf in output
     for &s in &output {
@@ -304,7 +1003,7 @@ fn test_eq_all_bands_at_max_boost() {
         params.eq.gains[i] = 6.0;
     }
 
-    let output = apply_effects(&input, sr, &params);
+    let output = apply_effects(&input, sr, 1, &params);
     assert_eq!(output.len(), input.len());
 
     // Check for NaN/Inf (most important check)
@@ -315,7 +1014,10 @@ fn test_eq_all_bands_at_max_boost() {
     // Verify at least some amplification
     let input_rms = rms(&input[2000..]);
     let output_rms = rms(&output[2000..]);
-    assert!(output_rms > input_rms * 1.5, "Massive boost should amplify signal");
+    assert!(
+        output_rms > input_rms * 1.5,
+        "Massive boost should amplify signal"
+    );
 }
 
 #[test]
@@ -330,7 +1032,7 @@ fn test_eq_all_bands_at_max_cut() {
         params.eq.gains[i] = -6.0;
     }
 
-    let output = apply_effects(&input, sr, &params);
+    let output = apply_effects(&input, sr, 1, &params);
     assert_eq!(output.len(), input.len());
 
     // Check for NaN/Inf (most important check)
@@ -341,7 +1043,10 @@ fn test_eq_all_bands_at_max_cut() {
     // Verify at least some attenuation
     let input_rms = rms(&input[2000..]);
     let output_rms = rms(&output[2000..]);
-    assert!(output_rms < input_rms * 0.9, "Massive cuts should attenuate signal");
+    assert!(
+        output_rms < input_rms * 0.9,
+        "Massive cuts should attenuate signal"
+    );
 }
 
 #[test]
@@ -353,7 +1058,7 @@ fn test_eq_with_very_short_buffer() {
     let mut params = EffectsParams::default();
     params.eq.gains[5] = 6.0; // 1 kHz boost
 
-    let output = apply_effects(&input, sr, &params);
+    let output = apply_effects(&input, sr, 1, &params);
     assert_eq!(output.len(), 10);
 
     for &s in &output {
@@ -370,6 +1075,456 @@ fn test_eq_neutral_skip_optimization() {
     let params = EffectsParams::default(); // All EQ gains at 0
     assert!(params.eq.is_neutral());
 
-    let output = apply_effects(&input, sr, &params);
+    let output = apply_effects(&input, sr, 1, &params);
     assert_eq!(output, input); // Should be exact same (passthrough)
 }
+
+/// Pathological chain for precision tests: every EQ band at max boost plus
+/// both cut filters.
+fn pathological_chain(precision: Precision) -> EffectsParams {
+    EffectsParams {
+        low_cut_hz: 80.0,
+        high_cut_hz: 16000.0,
+        eq: EqParams {
+            gains: vec![6.0; 12],
+            ..EqParams::default()
+        },
+        precision,
+        ..Default::default()
+    }
+}
+
+/// Mean spectrum level (dB) between 3 and 10 kHz — well away from a 1 kHz
+/// fundamental — over the last few FFT windows, after amplifying by `scale`
+/// so the floor lands inside `compute_spectrum`'s -80 dB window.
+fn broadband_floor_db(samples: &[f32], sr: u32, scale: f32) -> f32 {
+    use voiceforge::dsp::spectrum::{compute_spectrum, FFT_SIZE};
+    let lo = (3000.0 * FFT_SIZE as f32 / sr as f32) as usize;
+    let hi = (10000.0 * FFT_SIZE as f32 / sr as f32) as usize;
+    let windows = 8;
+    let mut total = 0.0;
+    for w in 0..windows {
+        let end = samples.len() - w * FFT_SIZE;
+        let frame: Vec<f32> = samples[end - FFT_SIZE..end]
+            .iter()
+            .map(|s| s * scale)
+            .collect();
+        let spectrum = compute_spectrum(&frame, FFT_SIZE);
+        total += spectrum[lo..hi].iter().sum::<f32>() / (hi - lo) as f32;
+    }
+    total / windows as f32
+}
+
+#[test]
+fn test_f64_chain_lowers_noise_floor() {
+    let sr = 44100;
+    // -60 dBFS sine, generated in f64 so the input itself is clean.
+    let amp = 10.0_f64.powf(-60.0 / 20.0);
+    let input: Vec<f32> = (0..sr as usize * 2)
+        .map(|i| (amp * (2.0 * std::f64::consts::PI * 1000.0 * i as f64 / sr as f64).sin()) as f32)
+        .collect();
+
+    let out32 = apply_effects(&input, sr, 1, &pathological_chain(Precision::F32));
+    let out64 = apply_effects(&input, sr, 1, &pathological_chain(Precision::F64));
+    assert_eq!(out32.len(), out64.len());
+
+    let floor32 = broadband_floor_db(&out32, sr, 1e6);
+    let floor64 = broadband_floor_db(&out64, sr, 1e6);
+    assert!(
+        floor64 < floor32 - 3.0,
+        "f64 floor {floor64:.1} dB should be well below f32 floor {floor32:.1} dB"
+    );
+}
+
+#[test]
+fn test_f64_chain_matches_f32_signal() {
+    let sr = 44100;
+    let input = sine_wave(440.0, sr, 22050);
+    let mut params = pathological_chain(Precision::F32);
+    params.compressor_thresh_db = -12.0;
+    params.reverb_mix = 0.3;
+    let out32 = apply_effects(&input, sr, 1, &params);
+    params.precision = Precision::F64;
+    let out64 = apply_effects(&input, sr, 1, &params);
+    assert_eq!(out32.len(), out64.len());
+    let max_diff = out32
+        .iter()
+        .zip(&out64)
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0_f32, f32::max);
+    assert!(
+        max_diff < 1e-3,
+        "precision modes diverge: max diff {max_diff}"
+    );
+}
+
+#[test]
+fn test_precision_does_not_affect_neutrality() {
+    let params = EffectsParams {
+        precision: Precision::F64,
+        ..Default::default()
+    };
+    assert!(params.is_neutral());
+    let input = sine_wave(440.0, 44100, 1024);
+    assert_eq!(apply_effects(&input, 44100, 1, &params), input);
+}
+
+#[test]
+fn test_stereo_channels_keep_their_own_filter_state() {
+    let sr = 44100;
+    let left = sine_wave(1000.0, sr, 8192);
+    let stereo: Vec<f32> = left.iter().flat_map(|&l| [l, 0.0]).collect();
+    let low_cut = EffectsParams {
+        low_cut_hz: 300.0,
+        ..Default::default()
+    };
+    let everything = EffectsParams {
+        low_cut_hz: 300.0,
+        high_cut_hz: 8000.0,
+        compressor_thresh_db: -12.0,
+        pitch_shift_semitones: 2.0,
+        reverb_mix: 0.3,
+        eq_tilt_db: 3.0,
+        ..Default::default()
+    };
+
+    for params in [low_cut, everything] {
+        let output = apply_effects(&stereo, sr, 2, &params);
+        assert_eq!(output.len() % 2, 0);
+        let (out_left, out_right): (Vec<f32>, Vec<f32>) = output
+            .chunks_exact(2)
+            .map(|frame| (frame[0], frame[1]))
+            .unzip();
+        assert!(
+            out_right.iter().all(|&s| s == 0.0),
+            "the silent right channel picked up the left"
+        );
+        // The left channel comes out exactly as it would on its own.
+        assert_eq!(out_left, apply_effects(&left, sr, 1, &params));
+    }
+}
+
+/// Steady-state gain in dB that the effects chain applies to a pure tone.
+fn tone_gain_db(freq: f64, params: &EffectsParams) -> f32 {
+    let sr = 44100;
+    let input: Vec<f32> = (0..sr as usize)
+        .map(|i| (0.25 * (2.0 * std::f64::consts::PI * freq * i as f64 / sr as f64).sin()) as f32)
+        .collect();
+    let output = apply_effects(&input, sr, 1, params);
+    // Skip the first half to let the filters settle.
+    let half = sr as usize / 2;
+    20.0 * (rms(&output[half..]) / rms(&input[half..])).log10()
+}
+
+/// Nominal tilt line at `freq`: -tilt at 31 Hz rising to +tilt at 16 kHz.
+fn tilt_line_db(freq: f32, tilt_db: f32) -> f32 {
+    let pos = (freq.ln() - 31.0_f32.ln()) / (16000.0_f32.ln() - 31.0_f32.ln());
+    tilt_db * (2.0 * pos - 1.0)
+}
+
+#[test]
+fn test_eq_tilt_offsets_span() {
+    use voiceforge::dsp::effects::eq_tilt_offsets;
+    let offsets = eq_tilt_offsets(-3.0);
+    assert!((offsets[0] - 3.0).abs() < 1e-5);
+    assert!((offsets[11] + 3.0).abs() < 1e-5);
+    assert!(
+        offsets.windows(2).all(|w| w[1] < w[0]),
+        "not monotonic: {offsets:?}"
+    );
+}
+
+#[test]
+fn test_eq_tilt_slope_between_63hz_and_8khz() {
+    for tilt in [3.0_f32, -3.0, 6.0] {
+        let params = EffectsParams {
+            eq_tilt_db: tilt,
+            ..Default::default()
+        };
+        assert!(!params.is_neutral());
+        let measured = tone_gain_db(8000.0, &params) - tone_gain_db(63.0, &params);
+        let expected = tilt_line_db(8000.0, tilt) - tilt_line_db(63.0, tilt);
+        assert!(
+            (measured - expected).abs() < 1.0,
+            "tilt {tilt}: measured {measured:.2} dB, expected {expected:.2} dB"
+        );
+    }
+}
+
+#[test]
+fn test_eq_tilt_composes_with_manual_bands() {
+    let mut eq = EqParams::default();
+    eq.gains[5] = 3.0; // 1 kHz band
+    let manual = EffectsParams {
+        eq: eq.clone(),
+        ..Default::default()
+    };
+    let tilt = EffectsParams {
+        eq_tilt_db: 3.0,
+        ..Default::default()
+    };
+    let both = EffectsParams {
+        eq,
+        eq_tilt_db: 3.0,
+        ..Default::default()
+    };
+    for freq in [250.0, 1000.0, 4000.0] {
+        let sum = tone_gain_db(freq, &manual) + tone_gain_db(freq, &tilt);
+        let combined = tone_gain_db(freq, &both);
+        assert!(
+            (combined - sum).abs() < 1.0,
+            "{freq} Hz: combined {combined:.2} dB vs manual+tilt {sum:.2} dB"
+        );
+    }
+}
+
+#[test]
+fn test_chain_order_decides_whether_the_eq_boost_reaches_the_compressor() {
+    use voiceforge::dsp::effects::{EffectStage, ParametricBand, ParametricKind};
+    let sr = 44100;
+    // -20 dBFS peak: under the threshold as it comes, 12 dB over it once boosted.
+    let input: Vec<f32> = sine_wave(1000.0, sr, sr as usize)
+        .iter()
+        .map(|s| s * 0.1)
+        .collect();
+    let with_order = |first: EffectStage, second: EffectStage| EffectsParams {
+        compressor_thresh_db: -14.0,
+        comp_makeup_db: Some(0.0),
+        parametric: vec![ParametricBand {
+            freq_hz: 1000.0,
+            q: 1.0,
+            gain_db: 12.0,
+            kind: ParametricKind::Peak,
+        }],
+        order: vec![first, second],
+        ..EffectsParams::default()
+    };
+    let half = sr as usize / 2;
+    let eq_first = apply_effects(
+        &input,
+        sr,
+        1,
+        &with_order(EffectStage::Eq, EffectStage::Compressor),
+    );
+    let comp_first = apply_effects(
+        &input,
+        sr,
+        1,
+        &with_order(EffectStage::Compressor, EffectStage::Eq),
+    );
+    assert_eq!(eq_first.len(), comp_first.len());
+    // Compressed only when the boost comes first.
+    let diff_db = 20.0 * (rms(&comp_first[half..]) / rms(&eq_first[half..])).log10();
+    assert!(
+        diff_db > 3.0,
+        "EQ-first output only {diff_db:.1} dB below compressor-first"
+    );
+    let boost_db = 20.0 * (rms(&comp_first[half..]) / rms(&input[half..])).log10();
+    assert!(
+        (boost_db - 12.0).abs() < 0.5,
+        "compressor-first boost {boost_db:.1} dB"
+    );
+}
+
+#[test]
+fn test_length_changing_stages_work_anywhere_in_the_order() {
+    let sr = 44100;
+    let input = sine_wave(440.0, sr, 8192);
+    let mut params = EffectsParams {
+        delay_mix: 0.5,
+        pitch_shift_semitones: 12.0,
+        pitch_shift_resample: true,
+        reverb_mix: 0.3,
+        ..EffectsParams::default()
+    };
+    let default_len = apply_effects(&input, sr, 2, &params).len();
+    params.order.reverse();
+    let reversed = apply_effects(&input, sr, 2, &params);
+    // Resampling an octave up now halves the delay's tail as well.
+    assert!(reversed.len() < default_len);
+    assert_eq!(reversed.len() % 2, 0);
+    assert!(reversed.iter().all(|s| s.is_finite()));
+}
+
+#[test]
+fn test_parse_order_fills_in_stages_left_out() {
+    use voiceforge::dsp::effects::EffectStage;
+    let order = EffectStage::parse_order("eq, comp").unwrap();
+    assert_eq!(order.len(), EffectStage::DEFAULT_ORDER.len());
+    assert_eq!(
+        &order[..3],
+        &[
+            EffectStage::Eq,
+            EffectStage::Compressor,
+            EffectStage::HighPass
+        ]
+    );
+    assert_eq!(order.last(), Some(&EffectStage::Limiter));
+    assert_eq!(
+        EffectStage::parse_order("").unwrap(),
+        EffectStage::DEFAULT_ORDER
+    );
+    assert!(EffectStage::parse_order("eq,eq").is_err());
+    assert!(EffectStage::parse_order("flanger").is_err());
+}
+
+#[test]
+fn test_fx_mix_zero_returns_the_input_bit_exactly() {
+    let sr = 44100;
+    let input = sine_wave(440.0, sr, 8192);
+    let mut params = EffectsParams {
+        compressor_thresh_db: -20.0,
+        reverb_mix: 0.5,
+        eq_tilt_db: 6.0,
+        fx_mix: 0.0,
+        ..EffectsParams::default()
+    };
+    assert!(!params.is_neutral());
+    assert_eq!(apply_effects(&input, sr, 1, &params), input);
+    for precision in [Precision::F32, Precision::F64] {
+        params.precision = precision;
+        params.delay_mix = 0.5;
+        params.pitch_shift_semitones = 12.0;
+        params.pitch_shift_resample = true;
+        // The chain is skipped: no delay tail, no resampling.
+        assert_eq!(apply_effects(&input, sr, 1, &params), input);
+        assert_eq!(params.tail_frames(sr), 0);
+    }
+    assert!(EffectsParams {
+        fx_mix: 1.0,
+        ..EffectsParams::default()
+    }
+    .is_neutral());
+}
+
+#[test]
+fn test_fx_mix_half_blends_an_eq_boost_halfway() {
+    use voiceforge::dsp::effects::{ParametricBand, ParametricKind};
+    let boost = |fx_mix: f32| {
+        tone_gain_db(
+            1000.0,
+            &EffectsParams {
+                parametric: vec![ParametricBand {
+                    freq_hz: 1000.0,
+                    q: 1.0,
+                    gain_db: 6.0,
+                    kind: ParametricKind::Peak,
+                }],
+                fx_mix,
+                ..EffectsParams::default()
+            },
+        )
+    };
+    let full = boost(1.0);
+    let half = boost(0.5);
+    assert!((full - 6.0).abs() < 0.2, "full boost {full:.2} dB");
+    // In phase at the peak's centre, so the levels add linearly: 20·log10(1.5).
+    let expected = 20.0 * ((1.0 + 10f32.powf(full / 20.0)) / 2.0).log10();
+    assert!(
+        (half - expected).abs() < 0.1,
+        "half mix {half:.2} dB, expected {expected:.2}"
+    );
+    assert!(half > 0.4 * full && half < 0.65 * full);
+}
+
+#[test]
+fn test_31_band_eq_boosts_one_third_octave() {
+    use voiceforge::dsp::effects::EqLayout;
+    let mut eq = EqParams::flat(EqLayout::Bands31);
+    assert_eq!(eq.gains.len(), 31);
+    eq.gains[18] = 6.0; // 1.25 kHz band
+    let params = EffectsParams {
+        eq,
+        ..Default::default()
+    };
+    let at_band = tone_gain_db(1250.0, &params);
+    let octave_up = tone_gain_db(2500.0, &params);
+    assert!(
+        (at_band - 6.0).abs() < 0.3,
+        "1.25 kHz raised {at_band:.2} dB"
+    );
+    assert!(octave_up.abs() < 1.5, "2.5 kHz raised {octave_up:.2} dB");
+}
+
+#[test]
+fn test_31_band_eq_tilt_spans_the_layout() {
+    use voiceforge::dsp::effects::EqLayout;
+    let params = EffectsParams {
+        eq: EqParams::flat(EqLayout::Bands31),
+        eq_tilt_db: 3.0,
+        ..Default::default()
+    };
+    let (low, high) = (tone_gain_db(100.0, &params), tone_gain_db(8000.0, &params));
+    assert!(
+        low < -0.5 && high > 0.5,
+        "tilt: {low:.2} dB at 100 Hz, {high:.2} dB at 8 kHz"
+    );
+}
+
+#[test]
+fn test_eq_q_sets_how_far_a_band_reaches() {
+    let octave_up_db = |q: f32| {
+        let mut eq = EqParams {
+            q: Some(q),
+            ..EqParams::default()
+        };
+        eq.gains[5] = 6.0; // 1 kHz band
+        tone_gain_db(
+            2000.0,
+            &EffectsParams {
+                eq,
+                ..Default::default()
+            },
+        )
+    };
+    let narrow = octave_up_db(4.0);
+    let wide = octave_up_db(0.7);
+    assert!(narrow.abs() < 0.7, "Q 4: 2 kHz raised {narrow:.2} dB");
+    assert!(wide > narrow + 1.5, "Q 0.7: 2 kHz raised {wide:.2} dB");
+}
+
+#[test]
+fn test_biquad_chain_in_blocks_matches_one_pass() {
+    use voiceforge::dsp::effects::{Biquad, BiquadChain, BiquadType};
+    let sr = 44100;
+    let input: Vec<f32> = sine_wave(440.0, sr, 10_000)
+        .iter()
+        .zip(sine_wave(5300.0, sr, 10_000))
+        .map(|(a, b)| 0.5 * a + 0.3 * b)
+        .collect();
+    let chain = || -> BiquadChain {
+        [
+            Biquad::new(BiquadType::Highpass, 80.0, sr),
+            Biquad::new(
+                BiquadType::Peaking {
+                    gain_db: 6.0,
+                    q: 2.0,
+                },
+                1000.0,
+                sr,
+            ),
+            Biquad::new(BiquadType::HighShelf { gain_db: -4.0 }, 6000.0, sr),
+            Biquad::new(BiquadType::Lowpass, 12000.0, sr),
+        ]
+        .into_iter()
+        .collect()
+    };
+
+    let mut whole = input.clone();
+    chain().process(&mut whole);
+    assert_ne!(whole, input);
+
+    // 10 000 isn't a multiple of 64: the last block is short.
+    let mut blocks = input.clone();
+    let mut streaming = chain();
+    for block in blocks.chunks_mut(64) {
+        streaming.process(block);
+    }
+    assert_eq!(blocks, whole);
+
+    // After a reset the chain starts over as if new.
+    streaming.reset();
+    let mut again = input.clone();
+    streaming.process(&mut again);
+    assert_eq!(again, whole);
+}
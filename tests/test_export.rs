@@ -1,7 +1,10 @@
 use std::path::Path;
 
 use tempfile::TempDir;
-use voiceforge::audio::export::{default_export_path, export_wav};
+use voiceforge::audio::export::{
+    default_export_path, export_wav, export_wav_streaming, export_wav_with_progress,
+};
+use voiceforge::dsp::effects::{apply_gain, gained};
 
 fn sine_wave(freq: f32, sample_rate: u32, num_samples: usize) -> Vec<f32> {
     (0..num_samples)
@@ -105,3 +108,85 @@ fn test_default_export_path_collision() {
         "should append _2 when default exists"
     );
 }
+
+#[test]
+fn test_export_wav_reports_increasing_progress() {
+    let dir = TempDir::new().expect("failed to create temp dir");
+    let path = dir.path().join("progress.wav");
+    let samples = sine_wave(440.0, 44100, 44100 * 5);
+    let mut reported = Vec::new();
+    export_wav_with_progress(&samples, 44100, 1, &path, |pct| reported.push(pct))
+        .expect("export should succeed");
+    assert!(reported.len() > 1, "got {reported:?}");
+    assert!(reported.windows(2).all(|w| w[0] < w[1]), "got {reported:?}");
+    assert_eq!(reported.last(), Some(&100));
+}
+
+/// Sample-by-sample writer, as the in-memory export worked before streaming.
+fn write_reference(samples: &[f32], sample_rate: u32, channels: u16, path: &Path) {
+    let spec = hound::WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(path, spec).unwrap();
+    for &s in samples {
+        writer
+            .write_sample((s.clamp(-1.0, 1.0) * 32767.0) as i16)
+            .unwrap();
+    }
+    writer.finalize().unwrap();
+}
+
+#[test]
+fn test_streaming_export_matches_in_memory_bytes() {
+    let dir = TempDir::new().expect("failed to create temp dir");
+    // Not a multiple of the chunk size, with samples that clip after gain.
+    let samples: Vec<f32> = sine_wave(330.0, 44100, 150_002)
+        .into_iter()
+        .map(|s| s * 0.9)
+        .collect();
+    for (channels, gain_db) in [(1, 0.0), (1, 3.5), (2, -6.0)] {
+        let streamed = dir.path().join("streamed.wav");
+        let in_memory = dir.path().join("in_memory.wav");
+        let reference = dir.path().join("reference.wav");
+        export_wav_streaming(
+            gained(&samples, gain_db),
+            44100,
+            channels,
+            &streamed,
+            |_| {},
+        )
+        .expect("streaming export should succeed");
+        let mut baked = samples.clone();
+        apply_gain(&mut baked, gain_db);
+        export_wav(&baked, 44100, channels, &in_memory).expect("export should succeed");
+        write_reference(&baked, 44100, channels, &reference);
+
+        let streamed = std::fs::read(&streamed).unwrap();
+        assert_eq!(
+            streamed,
+            std::fs::read(&in_memory).unwrap(),
+            "{channels} ch, {gain_db} dB"
+        );
+        assert_eq!(
+            streamed,
+            std::fs::read(&reference).unwrap(),
+            "{channels} ch, {gain_db} dB"
+        );
+    }
+}
+
+#[test]
+fn test_streaming_export_empty_source() {
+    let dir = TempDir::new().expect("failed to create temp dir");
+    let path = dir.path().join("empty.wav");
+    let mut reported = Vec::new();
+    export_wav_streaming(std::iter::empty(), 44100, 1, &path, |pct| {
+        reported.push(pct)
+    })
+    .expect("export of empty should succeed");
+    assert!(reported.is_empty());
+    assert_eq!(hound::WavReader::open(&path).unwrap().len(), 0);
+}
@@ -0,0 +1,91 @@
+//! Peak-allocation checks for streaming export. Its own test binary: the
+//! counting allocator is process-wide, so nothing else may run alongside.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tempfile::TempDir;
+use voiceforge::audio::decoder::AudioData;
+use voiceforge::audio::export::export_wav_streaming;
+use voiceforge::dsp::effects::gained;
+use voiceforge::dsp::processing::{
+    ExportJob, ProcessingCommand, ProcessingHandle, ProcessingResult,
+};
+
+/// Records the largest single allocation since the last reset.
+struct PeakAlloc;
+
+static LARGEST: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LARGEST.fetch_max(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        LARGEST.fetch_max(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAlloc = PeakAlloc;
+
+/// Two minutes at 48 kHz: 23 MB as f32, 11.5 MB as i16.
+const LEN: usize = 48_000 * 120;
+
+#[test]
+fn test_export_makes_no_full_length_copy() {
+    let dir = TempDir::new().expect("failed to create temp dir");
+    let samples: Vec<f32> = (0..LEN).map(|i| ((i % 100) as f32 / 100.0) - 0.5).collect();
+    // Anything a quarter of the i16 output or more counts as a full copy.
+    let limit = LEN * std::mem::size_of::<i16>() / 4;
+
+    LARGEST.store(0, Ordering::Relaxed);
+    let path = dir.path().join("direct.wav");
+    export_wav_streaming(gained(&samples, -3.0), 48_000, 1, &path, |_| {})
+        .expect("export should succeed");
+    let direct = LARGEST.load(Ordering::Relaxed);
+    assert!(direct < limit, "largest allocation {direct} bytes");
+
+    // Same through the worker, with the live gain baked in on the way.
+    let audio = Arc::new(AudioData {
+        samples,
+        sample_rate: 48_000,
+        channels: 1,
+    });
+    let handle = ProcessingHandle::spawn();
+    let path = dir.path().join("worker.wav").to_string_lossy().into_owned();
+    LARGEST.store(0, Ordering::Relaxed);
+    handle.send(ProcessingCommand::Export(ExportJob {
+        audio,
+        gain_db: -3.0,
+        path: path.clone(),
+        label: "B".to_string(),
+    }));
+    let deadline = Instant::now() + Duration::from_secs(60);
+    let mut outcome = None;
+    while outcome.is_none() && Instant::now() < deadline {
+        match handle.try_recv() {
+            Some(ProcessingResult::ExportDone(p, result)) => outcome = Some((p, result)),
+            Some(_) => {}
+            None => std::thread::sleep(Duration::from_millis(10)),
+        }
+    }
+    let (done_path, result) = outcome.expect("export timed out");
+    assert_eq!(done_path, path);
+    assert!(result.is_ok(), "{result:?}");
+    let worker = LARGEST.load(Ordering::Relaxed);
+    assert!(worker < limit, "largest allocation {worker} bytes");
+    assert_eq!(
+        std::fs::read(&path).unwrap(),
+        std::fs::read(dir.path().join("direct.wav")).unwrap()
+    );
+}
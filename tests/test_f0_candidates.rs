@@ -0,0 +1,135 @@
+mod test_support;
+
+use voiceforge::dsp::f0_candidates::{
+    apply_candidate, attach, frame_range, merge_candidates, F0_CANDIDATE_COUNT,
+};
+use voiceforge::dsp::world;
+use world_sys::F0Candidate;
+
+fn candidate(f0: f64, score: f64) -> F0Candidate {
+    F0Candidate { f0, score }
+}
+
+#[test]
+fn test_merge_ranks_by_votes_and_fills_octaves() {
+    // Frame 0: three tracks agree within a semitone, one halves the pitch.
+    // Frame 1: a 1-1 tie between 300 and 150 goes to the earlier track.
+    // Frame 2: mostly unvoiced.
+    let tracks = vec![
+        vec![440.0, 300.0, 0.0],
+        vec![441.5, 150.0, 0.0],
+        vec![220.0, 0.0, 0.0],
+        vec![438.0, 0.0, 200.0],
+    ];
+    let lattice = merge_candidates(&tracks, F0_CANDIDATE_COUNT);
+    assert_eq!(lattice.len(), 3);
+
+    // The earliest track's value stands for the cluster.
+    assert_eq!(
+        lattice[0],
+        vec![
+            candidate(440.0, 0.75),
+            candidate(220.0, 0.25),
+            candidate(880.0, 0.0)
+        ]
+    );
+    assert_eq!(
+        lattice[1],
+        vec![
+            candidate(0.0, 0.5),
+            candidate(300.0, 0.25),
+            candidate(150.0, 0.25)
+        ]
+    );
+    assert_eq!(
+        lattice[2],
+        vec![
+            candidate(0.0, 0.75),
+            candidate(200.0, 0.25),
+            candidate(100.0, 0.0)
+        ]
+    );
+
+    assert!(merge_candidates(&[], 3).is_empty());
+    assert!(merge_candidates(&tracks, 1)
+        .iter()
+        .all(|row| row.len() == 1));
+}
+
+#[test]
+fn test_lattice_ranks_true_pitch_first_on_clean_tone() {
+    let tone = test_support::sine(440.0, 0.5, 16000, 0.5);
+    let mut params = world::analyze(&tone).expect("analysis failed");
+    attach(&tone, &mut params).expect("candidate tracks failed");
+    let lattice = params.f0_candidates.as_ref().expect("no lattice");
+    assert_eq!(lattice.len(), params.f0.len());
+
+    // Skip the onset/offset frames where the analysis windows leave the tone.
+    let middle = &lattice[20..lattice.len() - 20];
+    for (i, row) in middle.iter().enumerate() {
+        assert!(!row.is_empty() && row.len() <= F0_CANDIDATE_COUNT);
+        let best = row[0];
+        assert!(
+            (best.f0 - 440.0).abs() < 440.0 * 0.02,
+            "frame {}: {row:?}",
+            i + 20
+        );
+        assert!(best.score > 0.5, "frame {}: {row:?}", i + 20);
+        // The alternatives are other pitches (e.g. octave errors).
+        assert!(
+            row[1..].iter().all(|c| (c.f0 - 440.0).abs() > 20.0),
+            "{row:?}"
+        );
+    }
+}
+
+#[test]
+fn test_apply_candidate_changes_exactly_the_selected_range() {
+    let tone = test_support::sine(440.0, 0.5, 16000, 0.5);
+    let mut params = world::analyze(&tone).expect("analysis failed");
+    attach(&tone, &mut params).expect("candidate tracks failed");
+    let before = params.f0.clone();
+
+    let tpos = &params.temporal_positions;
+    let frames = frame_range(tpos, tpos[45], tpos[30]);
+    assert_eq!(frames, 30..46, "either order, both ends included");
+    let written = apply_candidate(&mut params, frames.clone(), 1).unwrap();
+    assert_eq!(written, frames.len());
+
+    let lattice = params.f0_candidates.as_ref().unwrap();
+    for (frame, (&new, &old)) in params.f0.iter().zip(&before).enumerate() {
+        if frames.contains(&frame) {
+            assert_eq!(new, lattice[frame][1].f0, "frame {frame}");
+            assert_ne!(new, old, "frame {frame}");
+        } else {
+            assert_eq!(new, old, "frame {frame} outside the range changed");
+        }
+    }
+
+    // Candidate #1 puts the analysis' own value back.
+    apply_candidate(&mut params, frames, 0).unwrap();
+    assert_eq!(params.f0, before);
+}
+
+#[test]
+fn test_apply_candidate_needs_lattice_and_clamps_range() {
+    let tone = test_support::sine(440.0, 0.5, 16000, 0.2);
+    let mut params = world::analyze(&tone).expect("analysis failed");
+    assert!(
+        params.f0_candidates.is_none(),
+        "plain analysis keeps no lattice"
+    );
+    assert!(apply_candidate(&mut params, 0..5, 1).is_err());
+
+    attach(&tone, &mut params).unwrap();
+    let count = params.f0.len();
+    assert_eq!(
+        apply_candidate(&mut params, count - 2..count + 10, 0).unwrap(),
+        2
+    );
+    assert_eq!(
+        apply_candidate(&mut params, count + 1..count + 3, 0).unwrap(),
+        0
+    );
+    assert_eq!(frame_range(&[], 0.0, 1.0), 0..0);
+}
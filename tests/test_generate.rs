@@ -0,0 +1,117 @@
+use voiceforge::dsp::generate::{chord, interleave, silence, sine, sweep, syllables, white_noise};
+use voiceforge::dsp::spectrum::{compute_spectrum_dbfs, extract_window, find_peaks};
+
+const SR: u32 = 16000;
+const FFT: usize = 4096;
+
+/// Loudest spectral peaks (Hz) of the window starting at frame `start`.
+fn peaks_hz(audio: &voiceforge::audio::decoder::AudioData, start: usize, count: usize) -> Vec<f32> {
+    let window = extract_window(audio, start * audio.channels as usize, FFT);
+    find_peaks(
+        &compute_spectrum_dbfs(&window, FFT),
+        FFT,
+        audio.sample_rate,
+        count,
+    )
+    .iter()
+    .map(|p| p.freq_hz)
+    .collect()
+}
+
+#[test]
+fn test_sine_length_level_and_pitch() {
+    let tone = sine(440.0, 0.5, SR, 1.5);
+    assert_eq!(tone.samples.len(), 24000);
+    assert_eq!(tone.channels, 1);
+    let peak = tone.samples.iter().fold(0.0_f32, |m, s| m.max(s.abs()));
+    assert!((peak - 0.5).abs() < 1e-3, "peak {peak}");
+    let [f] = peaks_hz(&tone, 0, 1)[..] else {
+        panic!("no peak")
+    };
+    assert!((f - 440.0).abs() < 2.0, "peak at {f} Hz");
+}
+
+#[test]
+fn test_chord_contains_each_voice_and_stays_in_range() {
+    let freqs = [261.63, 329.63, 392.0];
+    let audio = chord(&freqs, 0.9, SR, 1.0);
+    assert!(audio.samples.iter().all(|s| s.abs() <= 0.9 + 1e-6));
+    let mut found = peaks_hz(&audio, 0, 3);
+    found.sort_by(f32::total_cmp);
+    for (got, want) in found.iter().zip(freqs) {
+        assert!((got - want as f32).abs() < 2.0, "{got} vs {want}");
+    }
+}
+
+#[test]
+fn test_sweep_rises_from_start_to_end() {
+    let audio = sweep(200.0, 3200.0, 0.7, SR, 4.0);
+    assert_eq!(audio.samples.len(), 64000);
+    let early = peaks_hz(&audio, 0, 1)[0];
+    let late = peaks_hz(&audio, audio.samples.len() - FFT, 1)[0];
+    assert!(early < 300.0, "start {early} Hz");
+    assert!(late > 2500.0, "end {late} Hz");
+    // Exponential: halfway in time is the geometric mean (800 Hz).
+    let mid = peaks_hz(&audio, audio.samples.len() / 2 - FFT / 2, 1)[0];
+    assert!((mid - 800.0).abs() < 80.0, "middle {mid} Hz");
+}
+
+#[test]
+fn test_white_noise_is_seeded_bounded_and_zero_mean() {
+    let a = white_noise(0.5, SR, 1.0, 42);
+    assert_eq!(a.samples, white_noise(0.5, SR, 1.0, 42).samples);
+    assert_ne!(a.samples, white_noise(0.5, SR, 1.0, 43).samples);
+    assert!(a.samples.iter().all(|s| (-0.5..0.5).contains(s)));
+    let mean = a.samples.iter().sum::<f32>() / a.samples.len() as f32;
+    assert!(mean.abs() < 0.01, "mean {mean}");
+    // Uniform on [-a, a) has RMS a/√3.
+    let rms = (a.samples.iter().map(|s| s * s).sum::<f32>() / a.samples.len() as f32).sqrt();
+    assert!((rms - 0.5 / 3.0_f32.sqrt()).abs() < 0.01, "rms {rms}");
+    // Seed 0 still produces noise, not a stuck generator.
+    assert!(white_noise(0.5, SR, 0.01, 0)
+        .samples
+        .iter()
+        .any(|&s| s != 0.0));
+}
+
+#[test]
+fn test_silence_and_interleave() {
+    assert!(silence(SR, 0.25).samples.iter().all(|&s| s == 0.0));
+    assert_eq!(silence(SR, 0.25).samples.len(), 4000);
+
+    let left = sine(440.0, 0.5, SR, 0.1);
+    let right = silence(SR, 0.2);
+    let stereo = interleave(&[left.clone(), right]);
+    assert_eq!(stereo.channels, 2);
+    assert_eq!(stereo.frame_count(), left.samples.len());
+    assert_eq!(stereo.samples[2], left.samples[1]);
+    assert_eq!(stereo.samples[3], 0.0);
+}
+
+#[test]
+fn test_syllables_are_gated_at_the_syllable_rate() {
+    let audio = syllables(150.0, 4.0, 0.5, SR, 1.0);
+    assert_eq!(audio.samples.len(), 16000);
+    assert!(audio.samples.iter().all(|s| s.abs() <= 0.5 + 1e-6));
+    // 250 ms syllables: sounding for the first 150 ms, silent for the rest.
+    let rms = |range: std::ops::Range<usize>| {
+        let part = &audio.samples[range];
+        (part.iter().map(|s| s * s).sum::<f32>() / part.len() as f32).sqrt()
+    };
+    for syllable in 0..4 {
+        let start = syllable * 4000;
+        assert!(
+            rms(start + 1000..start + 1400) > 0.1,
+            "syllable {syllable} silent"
+        );
+        assert_eq!(
+            rms(start + 2500..start + 4000),
+            0.0,
+            "gap {syllable} not silent"
+        );
+    }
+    let [f] = peaks_hz(&audio, 1000, 1)[..] else {
+        panic!("no peak")
+    };
+    assert!((150.0..=180.0).contains(&f), "fundamental at {f} Hz");
+}
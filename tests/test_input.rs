@@ -0,0 +1,994 @@
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use voiceforge::app::{
+    Action, AppMode, AppState, FileInfo, PanelFocus, PickerPurpose, ANALYSIS_LOST_MSG,
+    F0_CORRECTION_STEP_SECS, MAX_RESYNTH_DEFER, PITCH_NUDGE_SECS, VOICING_THRESHOLD_STEP,
+};
+use voiceforge::audio::decoder::AudioData;
+use voiceforge::audio::edit::{LengthMode, Splice};
+use voiceforge::dsp::modifier::{self, FormantWarp, RangeCenter, WorldSliderValues};
+use voiceforge::input::handler::handle_key_event;
+
+fn press(app: &mut AppState, code: KeyCode) {
+    handle_key_event(KeyEvent::new(code, KeyModifiers::NONE), app);
+}
+
+fn type_text(app: &mut AppState, text: &str) {
+    for c in text.chars() {
+        press(app, KeyCode::Char(c));
+    }
+}
+
+/// App with a 10-minute stereo file at 1 kHz loaded and Transport focused.
+fn transport_app() -> AppState {
+    let mut app = AppState::new();
+    let sample_rate = 1000;
+    let channels = 2;
+    let duration_secs = 600.0;
+    app.file_info = Some(FileInfo {
+        name: "long.wav".into(),
+        path: "long.wav".into(),
+        sample_rate,
+        channels,
+        original_channels: channels,
+        duration_secs,
+        total_samples: (duration_secs as usize) * sample_rate as usize * channels as usize,
+    });
+    app.focus = PanelFocus::Transport;
+    app
+}
+
+fn position(app: &AppState) -> usize {
+    app.playback.position.load(Ordering::Acquire)
+}
+
+#[test]
+fn test_goto_prompt_seeks_to_typed_time() {
+    let mut app = transport_app();
+    press(&mut app, KeyCode::Char('g'));
+    assert_eq!(app.mode, AppMode::Seeking);
+
+    type_text(&mut app, "4:32.5");
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.mode, AppMode::Normal);
+    // 272.5 s × 1000 Hz × 2 channels.
+    assert_eq!(position(&app), 545_000);
+    assert!(app.file_picker_input.is_empty());
+
+    press(&mut app, KeyCode::Char('g'));
+    type_text(&mut app, "50%");
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(position(&app), 600_000);
+}
+
+#[test]
+fn test_goto_past_end_clamps_to_last_frame() {
+    let mut app = transport_app();
+    press(&mut app, KeyCode::Char('g'));
+    type_text(&mut app, "2:00:00");
+    press(&mut app, KeyCode::Enter);
+    let total = app.file_info.as_ref().unwrap().total_samples;
+    assert_eq!(position(&app), total - 2);
+}
+
+#[test]
+fn test_goto_invalid_input_stays_in_prompt() {
+    let mut app = transport_app();
+    app.playback.position.store(1234, Ordering::Release);
+    press(&mut app, KeyCode::Char('g'));
+    type_text(&mut app, "1:75");
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.mode, AppMode::Seeking);
+    assert!(app.seek_error.is_some());
+    assert_eq!(position(&app), 1234);
+
+    // Editing clears the error; a corrected entry then seeks.
+    press(&mut app, KeyCode::Backspace);
+    assert!(app.seek_error.is_none());
+    press(&mut app, KeyCode::Backspace);
+    type_text(&mut app, "15");
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.mode, AppMode::Normal);
+    assert_eq!(position(&app), 150_000);
+}
+
+#[test]
+fn test_goto_escape_cancels_without_seeking() {
+    let mut app = transport_app();
+    press(&mut app, KeyCode::Char('g'));
+    type_text(&mut app, "3:00");
+    press(&mut app, KeyCode::Esc);
+    assert_eq!(app.mode, AppMode::Normal);
+    assert_eq!(position(&app), 0);
+}
+
+#[test]
+fn test_goto_needs_transport_focus() {
+    let mut app = transport_app();
+    app.focus = PanelFocus::WorldSliders;
+    press(&mut app, KeyCode::Char('g'));
+    assert_eq!(app.mode, AppMode::Normal);
+}
+
+#[test]
+fn test_inspector_refreshes_after_playhead_moves_one_frame() {
+    use voiceforge::dsp::inspect::FrameInfo;
+
+    let mut app = transport_app();
+    assert!(
+        !app.inspector_wants_frame(1.0),
+        "closed inspector fetches nothing"
+    );
+
+    press(&mut app, KeyCode::Char('f'));
+    assert!(app.inspector_open);
+    assert!(app.inspector_wants_frame(1.0));
+
+    // Request in flight: no duplicates.
+    app.frame_info_requested = true;
+    app.frame_info_playhead = Some(1.0);
+    assert!(!app.inspector_wants_frame(1.2));
+
+    app.frame_info_requested = false;
+    app.frame_info = Some(FrameInfo {
+        frame: 200,
+        frame_count: 1000,
+        time_secs: 1.0,
+        frame_period_ms: 5.0,
+        f0_hz: 0.0,
+        peaks: Vec::new(),
+        mean_aperiodicity: 0.0,
+    });
+    assert!(!app.inspector_wants_frame(1.004), "within one frame");
+    assert!(app.inspector_wants_frame(1.006));
+    assert!(
+        app.inspector_wants_frame(0.994),
+        "moving backwards counts too"
+    );
+
+    // Esc closes the inspector instead of quitting.
+    press(&mut app, KeyCode::Esc);
+    assert!(!app.inspector_open);
+    assert!(!app.should_quit);
+    assert!(app.frame_info.is_none());
+}
+
+#[test]
+fn test_ctrl_z_suspends_in_any_mode() {
+    use voiceforge::app::Action;
+
+    let ctrl_z = KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL);
+    let mut app = transport_app();
+    assert!(matches!(
+        handle_key_event(ctrl_z, &mut app),
+        Some(Action::Suspend)
+    ));
+
+    // Even while typing into a prompt; plain 'z' is still text there.
+    press(&mut app, KeyCode::Char('g'));
+    assert_eq!(app.mode, AppMode::Seeking);
+    assert!(matches!(
+        handle_key_event(ctrl_z, &mut app),
+        Some(Action::Suspend)
+    ));
+    assert_eq!(app.mode, AppMode::Seeking);
+    press(&mut app, KeyCode::Char('z'));
+    assert_eq!(app.file_picker_input, "z");
+}
+
+#[test]
+fn test_f0_correction_needs_flag_and_analysis() {
+    let mut app = transport_app();
+    press(&mut app, KeyCode::Char('c'));
+    assert_eq!(app.mode, AppMode::Normal);
+    assert!(app
+        .status_message
+        .as_deref()
+        .unwrap()
+        .contains("--extended-analysis"));
+
+    app.config.extended_analysis = true;
+    press(&mut app, KeyCode::Char('c'));
+    assert_eq!(app.mode, AppMode::Normal);
+    assert!(app
+        .status_message
+        .as_deref()
+        .unwrap()
+        .contains("waiting for analysis"));
+}
+
+#[test]
+fn test_f0_correction_extends_range_and_cycles_candidates() {
+    let mut app = transport_app();
+    app.config.extended_analysis = true;
+    app.original_audio = Some(Arc::new(AudioData {
+        samples: vec![0.0; 600 * 1000],
+        sample_rate: 1000,
+        channels: 1,
+    }));
+    // 2 s into the stereo file.
+    app.playback.position.store(4000, Ordering::Release);
+
+    press(&mut app, KeyCode::Char('c'));
+    assert_eq!(app.mode, AppMode::F0Correct);
+    assert!(app.inspector_open);
+    for _ in 0..4 {
+        press(&mut app, KeyCode::Right);
+    }
+    press(&mut app, KeyCode::Left);
+    let c = app.f0_correction.unwrap();
+    assert_eq!(c.anchor_secs, 2.0);
+    assert!((c.end_secs - (2.0 + 3.0 * F0_CORRECTION_STEP_SECS)).abs() < 1e-9);
+
+    // The first step offers the runner-up; cycling wraps back to the best.
+    let ranks: Vec<usize> = [KeyCode::Tab, KeyCode::Char('c'), KeyCode::Tab]
+        .into_iter()
+        .map(
+            |code| match handle_key_event(KeyEvent::new(code, KeyModifiers::NONE), &mut app) {
+                Some(Action::ApplyF0Candidate(choice)) => {
+                    assert_eq!(choice.start_secs, 2.0);
+                    assert_eq!(choice.end_secs, c.end_secs);
+                    choice.rank
+                }
+                _ => panic!("expected a candidate to apply"),
+            },
+        )
+        .collect();
+    assert_eq!(ranks, [1, 2, 0]);
+
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.mode, AppMode::Normal);
+    assert!(app.f0_correction.is_none());
+}
+
+/// [`transport_app`] with both sides loaded and playing A (the original).
+fn listening_to_a() -> AppState {
+    let mut app = transport_app();
+    let audio = Arc::new(AudioData {
+        samples: vec![0.0; 2000],
+        sample_rate: 1000,
+        channels: 1,
+    });
+    app.original_audio = Some(Arc::clone(&audio));
+    app.audio_data = Some(Arc::clone(&audio));
+    app.playback.audio_lock = Some(Arc::new(RwLock::new(audio)));
+    press(&mut app, KeyCode::Char('a'));
+    assert!(app.ab_original);
+    app.focus = PanelFocus::WorldSliders;
+    app
+}
+
+#[test]
+fn test_slider_change_on_a_shows_hint_until_toggled_to_b() {
+    let mut app = listening_to_a();
+    let action = handle_key_event(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE), &mut app);
+    assert!(matches!(action, Some(Action::Resynthesize)));
+    assert!(!app.ab_hint);
+
+    // The main loop reports the change; staying on A raises the hint.
+    assert!(!app.note_processing_change());
+    assert!(app.ab_original);
+    assert!(app.ab_hint);
+    assert!(!app.note_processing_change());
+    assert!(app.ab_hint, "hint persists across further changes");
+
+    press(&mut app, KeyCode::Char('a'));
+    assert!(!app.ab_original);
+    assert!(!app.ab_hint);
+    // On B changes are audible: no hint.
+    assert!(!app.note_processing_change());
+    assert!(!app.ab_hint);
+}
+
+#[test]
+fn test_switch_to_b_option_switches_on_slider_change() {
+    let mut app = listening_to_a();
+    app.config.switch_to_b_on_change = true;
+    assert!(app.note_processing_change(), "caller must swap the buffer");
+    assert!(!app.ab_original);
+    assert!(!app.ab_hint);
+
+    // Without a stream to swap, fall back to the hint.
+    let mut app = listening_to_a();
+    app.config.switch_to_b_on_change = true;
+    app.playback.audio_lock = None;
+    assert!(!app.note_processing_change());
+    assert!(app.ab_original);
+    assert!(app.ab_hint);
+}
+
+#[test]
+fn test_resynthesis_deferred_only_on_a_while_one_is_in_flight() {
+    let mut app = listening_to_a();
+    let now = Instant::now();
+    assert!(app.defer_resynthesis(Some(now)));
+    assert!(!app.defer_resynthesis(None));
+    // A pass that never answered does not block forever.
+    let stale = now.checked_sub(MAX_RESYNTH_DEFER + Duration::from_secs(1));
+    if let Some(stale) = stale {
+        assert!(!app.defer_resynthesis(Some(stale)));
+    }
+
+    press(&mut app, KeyCode::Char('a'));
+    assert!(!app.defer_resynthesis(Some(now)));
+}
+
+#[test]
+fn test_effects_published_only_on_change() {
+    let mut app = AppState::new();
+    let initial = app.publish_effects();
+    assert_eq!(app.publish_effects(), initial);
+
+    app.focus = PanelFocus::EffectsSliders;
+    app.selected_slider = 0;
+    press(&mut app, KeyCode::Right);
+    let moved = app.publish_effects();
+    assert_eq!(moved, initial + 1);
+    assert_eq!(*app.effects.load().1, app.effects_params());
+    assert_eq!(app.publish_effects(), moved);
+}
+
+#[test]
+fn test_punch_in_needs_b() {
+    let mut app = listening_to_a();
+    press(&mut app, KeyCode::Char('e'));
+    assert_eq!(app.mode, AppMode::Normal);
+    assert!(app.punch_region.is_none());
+    assert!(app.status_message.as_deref().unwrap().contains("press 'a'"));
+
+    let mut empty = AppState::new();
+    press(&mut empty, KeyCode::Char('e'));
+    assert_eq!(empty.mode, AppMode::Normal);
+}
+
+#[test]
+fn test_punch_in_region_then_clip_from_picker() {
+    let mut app = listening_to_a();
+    press(&mut app, KeyCode::Char('a'));
+    app.playback.position.store(500, Ordering::Release);
+    press(&mut app, KeyCode::Char('e'));
+    assert_eq!(app.mode, AppMode::PunchIn);
+
+    // An empty region can't take a clip yet.
+    let action = handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), &mut app);
+    assert!(action.is_none());
+    assert_eq!(app.mode, AppMode::PunchIn);
+
+    for _ in 0..4 {
+        press(&mut app, KeyCode::Right);
+    }
+    press(&mut app, KeyCode::Tab);
+    let fade = app.config.punch_fade.as_millis() as usize;
+    assert_eq!(
+        app.punch_splice(),
+        Some(Splice {
+            start: 500,
+            end: 700,
+            fade,
+            mode: LengthMode::Shift,
+        })
+    );
+
+    let action = handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), &mut app);
+    assert!(matches!(action, Some(Action::ScanDirectory)));
+    assert_eq!(app.mode, AppMode::FilePicker);
+    assert_eq!(app.picker_purpose, PickerPurpose::PunchIn);
+
+    type_text(&mut app, "fix.wav");
+    let action = handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), &mut app);
+    assert!(
+        matches!(action, Some(Action::PunchIn(ref p)) if p == "fix.wav"),
+        "{action:?}"
+    );
+    // The region stays until the main loop has built the job; the next
+    // picker opens files again.
+    assert!(app.punch_splice().is_some());
+    assert_eq!(app.picker_purpose, PickerPurpose::Open);
+}
+
+#[test]
+fn test_punch_in_cancelled_from_region_or_picker() {
+    let mut app = listening_to_a();
+    press(&mut app, KeyCode::Char('a'));
+    press(&mut app, KeyCode::Char('e'));
+    press(&mut app, KeyCode::Left);
+    // The region can't go before the start of B.
+    assert_eq!(app.punch_region.unwrap().end_secs, 0.0);
+    press(&mut app, KeyCode::Esc);
+    assert_eq!(app.mode, AppMode::Normal);
+    assert!(app.punch_region.is_none());
+
+    press(&mut app, KeyCode::Char('e'));
+    press(&mut app, KeyCode::Right);
+    press(&mut app, KeyCode::Enter);
+    press(&mut app, KeyCode::Esc);
+    assert_eq!(app.mode, AppMode::Normal);
+    assert!(app.punch_region.is_none());
+    assert_eq!(app.picker_purpose, PickerPurpose::Open);
+}
+
+#[test]
+fn test_lost_analysis_blocks_resynthesis_until_reanalyzed() {
+    let mut app = transport_app();
+    app.original_audio = Some(Arc::new(AudioData {
+        samples: vec![0.0; 1000],
+        sample_rate: 1000,
+        channels: 1,
+    }));
+    let format = app.analyzed_format();
+    assert_eq!(app.resynthesis_format(), format);
+    let r = KeyEvent::new(KeyCode::Char('R'), KeyModifiers::SHIFT);
+    assert!(handle_key_event(r, &mut app).is_none());
+    assert!(app.status_message.as_deref().unwrap().contains("intact"));
+
+    app.processing_status = Some("Internal error: boom".to_string());
+    app.f0_edited = true;
+    app.invalidate_analysis();
+    assert!(app.analysis_lost);
+    assert!(!app.f0_edited);
+    // The panic's message moves out of the spinner into a status.
+    assert!(app.processing_status.is_none());
+    assert_eq!(app.status_message.as_deref(), Some("Internal error: boom"));
+
+    // Slider changes get told why nothing happens.
+    assert_eq!(app.resynthesis_format(), None);
+    assert_eq!(app.status_message.as_deref(), Some(ANALYSIS_LOST_MSG));
+    assert!(matches!(
+        handle_key_event(r, &mut app),
+        Some(Action::Reanalyze)
+    ));
+
+    app.prepare_for_load();
+    assert!(!app.analysis_lost);
+
+    // Nothing analyzed yet: nothing to lose.
+    let mut empty = AppState::new();
+    empty.invalidate_analysis();
+    assert!(!empty.analysis_lost);
+}
+
+#[test]
+fn test_voicing_threshold_keys_step_and_clamp() {
+    let mut app = transport_app();
+    let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+    assert!(handle_key_event(key('T'), &mut app).is_none());
+    assert!(app
+        .status_message
+        .as_deref()
+        .unwrap()
+        .contains("Load a file"));
+
+    app.original_audio = Some(Arc::new(AudioData {
+        samples: vec![0.0; 1000],
+        sample_rate: 1000,
+        channels: 1,
+    }));
+    app.config.analysis.d4c_threshold = 0.85;
+    app.f0_edited = true;
+    let Some(Action::SetVoicingThreshold(up)) = handle_key_event(key('T'), &mut app) else {
+        panic!("T should re-analyze");
+    };
+    assert!((up - (0.85 + VOICING_THRESHOLD_STEP)).abs() < 1e-12, "{up}");
+    assert!(!app.f0_edited);
+    let Some(Action::SetVoicingThreshold(down)) = handle_key_event(key('t'), &mut app) else {
+        panic!("t should re-analyze");
+    };
+    assert!((down - 0.8).abs() < 1e-12, "{down}");
+
+    // Off-grid values snap to the grid; the ends stop.
+    app.config.analysis.d4c_threshold = 0.97;
+    assert!(
+        matches!(handle_key_event(key('T'), &mut app), Some(Action::SetVoicingThreshold(t)) if t == 1.0)
+    );
+    app.config.analysis.d4c_threshold = 1.0;
+    assert!(handle_key_event(key('T'), &mut app).is_none());
+    assert!(app
+        .status_message
+        .as_deref()
+        .unwrap()
+        .contains("already at 1.00"));
+    app.config.analysis.d4c_threshold = 0.0;
+    assert!(handle_key_event(key('t'), &mut app).is_none());
+
+    app.config.analyze_on_load = false;
+    assert!(handle_key_event(key('T'), &mut app).is_none());
+    assert!(app
+        .status_message
+        .as_deref()
+        .unwrap()
+        .contains("Analysis is off"));
+}
+
+#[test]
+fn test_pitch_readout_takes_the_nearest_curve_point() {
+    let mut app = transport_app();
+    assert_eq!(app.pitch_at(1.0), None);
+    app.pitch_curve = Some(vec![
+        (0.0, 0.0),
+        (0.005, 200.0),
+        (0.010, 210.0),
+        (0.015, 0.0),
+    ]);
+    assert_eq!(app.pitch_at(-1.0), None);
+    assert_eq!(app.pitch_at(0.006), Some(200.0));
+    assert_eq!(app.pitch_at(0.009), Some(210.0));
+    assert_eq!(app.pitch_at(0.014), None);
+    assert_eq!(app.pitch_at(60.0), None);
+    app.pitch_curve = Some(vec![(0.0, 150.0)]);
+    assert_eq!(app.pitch_at(60.0), Some(150.0));
+
+    app.prepare_for_load();
+    assert!(app.pitch_curve.is_none());
+}
+
+#[test]
+fn test_whisper_key_toggles_and_resynthesizes() {
+    let mut app = transport_app();
+    assert!(!app.world_slider_values().whisper);
+    press(&mut app, KeyCode::Char('W'));
+    assert!(app.world_whisper);
+    assert!(app.world_slider_values().whisper);
+    assert!(!app.world_slider_values().is_neutral());
+    let toggle = handle_key_event(
+        KeyEvent::new(KeyCode::Char('W'), KeyModifiers::NONE),
+        &mut app,
+    );
+    assert!(matches!(toggle, Some(Action::Resynthesize)));
+    assert!(!app.world_whisper);
+    assert!(app.world_slider_values().is_neutral());
+}
+
+#[test]
+fn test_m_marks_and_clears_the_world_region() {
+    let mut app = transport_app();
+    let key = KeyEvent::new(KeyCode::Char('m'), KeyModifiers::NONE);
+    let seek_to = |app: &mut AppState, secs: f64| {
+        let position = (secs * 1000.0) as usize * 2;
+        app.playback.position.store(position, Ordering::Relaxed);
+    };
+
+    seek_to(&mut app, 12.0);
+    assert!(handle_key_event(key, &mut app).is_none());
+    assert_eq!(app.world_region_start, Some(12.0));
+    // The end may come before the start.
+    seek_to(&mut app, 4.5);
+    assert!(matches!(
+        handle_key_event(key, &mut app),
+        Some(Action::Resynthesize)
+    ));
+    assert_eq!(app.world_region, Some((4.5, 12.0)));
+    assert_eq!(app.world_slider_values().region, Some((4.5, 12.0)));
+
+    assert!(matches!(
+        handle_key_event(key, &mut app),
+        Some(Action::Resynthesize)
+    ));
+    assert_eq!(app.world_region, None);
+    assert_eq!(app.world_region_start, None);
+}
+
+#[test]
+fn test_enter_on_pitch_range_cycles_its_center() {
+    let mut app = transport_app();
+    let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+    // Only with the Pitch Range slider selected.
+    assert!(handle_key_event(enter, &mut app).is_none());
+    assert_eq!(app.world_range_center, RangeCenter::Mean);
+
+    app.focus = PanelFocus::WorldSliders;
+    app.selected_slider = app
+        .world_sliders
+        .iter()
+        .position(|s| s.label == "Pitch Range")
+        .unwrap();
+    // At 1× the centre changes nothing, so nothing to resynthesize.
+    assert!(handle_key_event(enter, &mut app).is_none());
+    assert_eq!(
+        app.world_slider_values().pitch_range_center,
+        RangeCenter::Median
+    );
+
+    press(&mut app, KeyCode::Right);
+    assert!(matches!(
+        handle_key_event(enter, &mut app),
+        Some(Action::Resynthesize)
+    ));
+    assert_eq!(
+        app.world_slider_values().pitch_range_center,
+        RangeCenter::PerSegment
+    );
+    assert!(matches!(
+        handle_key_event(enter, &mut app),
+        Some(Action::Resynthesize)
+    ));
+    assert_eq!(app.world_range_center, RangeCenter::Mean);
+}
+
+#[test]
+fn test_enter_on_formant_shift_switches_its_warp() {
+    let mut app = transport_app();
+    let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+    app.focus = PanelFocus::WorldSliders;
+    app.selected_slider = app
+        .world_sliders
+        .iter()
+        .position(|s| s.label == "Formant Shift")
+        .unwrap();
+    // Unshifted, the warp changes nothing, so nothing to resynthesize.
+    assert!(handle_key_event(enter, &mut app).is_none());
+    assert_eq!(app.world_slider_values().formant_warp, FormantWarp::Log);
+
+    app.world_sliders[app.selected_slider].value = 3.0;
+    assert!(matches!(
+        handle_key_event(enter, &mut app),
+        Some(Action::Resynthesize)
+    ));
+    assert_eq!(app.world_formant_warp, FormantWarp::Linear);
+    assert_eq!(app.world_range_center, RangeCenter::Mean);
+}
+
+#[test]
+fn test_enter_on_humanize_rerolls_its_seed() {
+    let mut app = transport_app();
+    let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+    app.focus = PanelFocus::WorldSliders;
+    app.selected_slider = app
+        .world_sliders
+        .iter()
+        .position(|s| s.label == "Humanize")
+        .unwrap();
+    assert!(app.world_slider_values().is_neutral());
+    // At 0 ct the seed changes nothing, so nothing to resynthesize.
+    assert!(handle_key_event(enter, &mut app).is_none());
+    assert_eq!(app.world_slider_values().humanize_seed, 1);
+
+    press(&mut app, KeyCode::Right);
+    let values = app.world_slider_values();
+    assert_eq!(values.humanize_cents, 5.0);
+    assert!(!values.is_neutral());
+    assert!(matches!(
+        handle_key_event(enter, &mut app),
+        Some(Action::Resynthesize)
+    ));
+    assert_eq!(app.world_humanize_seed, 2);
+}
+
+#[test]
+fn test_parametric_eq_mode_selects_and_adjusts_band_parameters() {
+    let mut app = transport_app();
+    app.focus = PanelFocus::EqBands;
+    assert!(
+        handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), &mut app).is_none()
+    );
+    assert!(app.eq_parametric_mode);
+
+    // Band 1's frequency is selected; moving it while at 0 dB changes nothing audible.
+    let freq = app.eq_parametric[0].freq_hz;
+    assert!(handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE), &mut app).is_none());
+    assert!((app.eq_parametric[0].freq_hz / freq - 2f32.powf(1.0 / 12.0)).abs() < 1e-4);
+    assert!(app.sliders_neutral());
+
+    // Right twice reaches the gain; Down cuts it and the effects follow.
+    press(&mut app, KeyCode::Right);
+    press(&mut app, KeyCode::Right);
+    assert_eq!(app.parametric_selection(), (0, 2));
+    let cut = handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE), &mut app);
+    assert!(matches!(cut, Some(Action::ReapplyEffects)));
+    assert_eq!(app.eq_parametric[0].gain_db, -0.5);
+    assert_eq!(app.effects_params().parametric[0].gain_db, -0.5);
+    assert!(!app.effects_params().is_neutral());
+    assert!(
+        app.eq_gains.iter().all(|&g| g == 0.0),
+        "graphic bands untouched"
+    );
+
+    // Right past the last parameter moves on to the next band; d resets.
+    press(&mut app, KeyCode::Right);
+    press(&mut app, KeyCode::Right);
+    assert_eq!(app.parametric_selection(), (1, 0));
+    press(&mut app, KeyCode::Left);
+    press(&mut app, KeyCode::Left);
+    let reset = handle_key_event(
+        KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE),
+        &mut app,
+    );
+    assert!(matches!(reset, Some(Action::ReapplyEffects)));
+    assert!(app.effects_params().is_neutral());
+
+    press(&mut app, KeyCode::Enter);
+    assert!(!app.eq_parametric_mode);
+}
+
+#[test]
+fn test_l_toggles_loudness_matching_of_b() {
+    let mut app = transport_app();
+    assert!(!app.effects_params().loudness_match);
+    let l = KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE);
+    assert!(matches!(
+        handle_key_event(l, &mut app),
+        Some(Action::ReapplyEffects)
+    ));
+    assert!(app.effects_params().loudness_match);
+    // A listening aid, not an edit: the controls still count as untouched.
+    assert!(app.effects_params().is_neutral());
+    assert!(matches!(
+        handle_key_event(l, &mut app),
+        Some(Action::ReapplyEffects)
+    ));
+    assert!(!app.effects_params().loudness_match);
+}
+
+#[test]
+fn test_comma_and_period_nudge_the_pitch_around_the_playhead() {
+    let mut app = transport_app();
+    let (down, up) = (
+        KeyEvent::new(KeyCode::Char(','), KeyModifiers::NONE),
+        KeyEvent::new(KeyCode::Char('.'), KeyModifiers::NONE),
+    );
+    app.playback.position.store(6000 * 2, Ordering::Relaxed);
+
+    assert!(matches!(
+        handle_key_event(up, &mut app),
+        Some(Action::Resynthesize)
+    ));
+    assert!(matches!(
+        handle_key_event(up, &mut app),
+        Some(Action::Resynthesize)
+    ));
+    let offsets = app.world_slider_values().f0_offsets;
+    assert_eq!(modifier::f0_offset_at(&offsets, 6.0), 2.0);
+    assert_eq!(
+        modifier::f0_offset_at(&offsets, 6.0 + PITCH_NUDGE_SECS),
+        2.0
+    );
+    assert_eq!(modifier::f0_offset_at(&offsets, 5.0), 0.0);
+    assert_eq!(modifier::f0_offset_at(&offsets, 7.0), 0.0);
+    assert!(!app.world_slider_values().is_neutral());
+
+    assert!(matches!(
+        handle_key_event(down, &mut app),
+        Some(Action::Resynthesize)
+    ));
+    assert!(matches!(
+        handle_key_event(down, &mut app),
+        Some(Action::Resynthesize)
+    ));
+    assert!(app.world_slider_values().is_neutral());
+}
+
+#[test]
+fn test_k_ramps_the_selected_slider_between_two_playhead_positions() {
+    let mut app = transport_app();
+    let key = KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE);
+    app.focus = PanelFocus::WorldSliders;
+    app.selected_slider = 2; // speed can't be automated
+    assert!(handle_key_event(key, &mut app).is_none());
+    app.selected_slider = 0;
+
+    app.playback.position.store(2000 * 2, Ordering::Relaxed);
+    assert!(
+        handle_key_event(key, &mut app).is_none(),
+        "the start alone changes nothing"
+    );
+    app.world_sliders[0].value = 4.0;
+    app.playback.position.store(6000 * 2, Ordering::Relaxed);
+    assert!(matches!(
+        handle_key_event(key, &mut app),
+        Some(Action::Resynthesize)
+    ));
+    let values = app.world_slider_values();
+    assert_eq!(values.automation.pitch_shift, vec![(2.0, 0.0), (6.0, 4.0)]);
+    assert_eq!(
+        modifier::automated_value(values.pitch_shift, &values.automation.pitch_shift, 4.0),
+        2.0
+    );
+
+    assert!(matches!(
+        handle_key_event(key, &mut app),
+        Some(Action::Resynthesize)
+    ));
+    assert!(app.world_slider_values().automation.is_empty());
+}
+
+#[test]
+fn test_loudness_target_slider_is_off_at_its_bottom_stop() {
+    let mut app = transport_app();
+    app.focus = PanelFocus::EffectsSliders;
+    app.selected_slider = app
+        .effects_sliders
+        .iter()
+        .position(|s| s.label == "Loudness Target")
+        .unwrap();
+    assert_eq!(
+        app.effects_sliders[app.selected_slider].stop_label(),
+        Some("Off")
+    );
+    assert_eq!(app.effects_params().target_lufs, None);
+
+    let key = KeyEvent::new(KeyCode::Right, KeyModifiers::NONE);
+    assert!(matches!(
+        handle_key_event(key, &mut app),
+        Some(Action::ReapplyEffects)
+    ));
+    assert_eq!(app.effects_sliders[app.selected_slider].stop_label(), None);
+    assert_eq!(app.effects_params().target_lufs, Some(-24.0));
+}
+
+#[test]
+fn test_voice_preset_selector_sets_the_preset_sliders() {
+    let mut app = transport_app();
+    app.focus = PanelFocus::WorldSliders;
+    let label = |app: &AppState, name: &str| {
+        app.world_sliders
+            .iter()
+            .position(|s| s.label == name)
+            .unwrap()
+    };
+    let (preset, amount) = (label(&app, "Voice Preset"), label(&app, "Preset Amount"));
+
+    app.selected_slider = preset;
+    let key = KeyEvent::new(KeyCode::Right, KeyModifiers::NONE);
+    assert!(matches!(
+        handle_key_event(key, &mut app),
+        Some(Action::Resynthesize)
+    ));
+    assert_eq!(app.world_sliders[preset].choice_label(), Some("Feminize"));
+    let feminine = WorldSliderValues::preset_feminize(1.0);
+    assert_eq!(app.world_slider_values().pitch_shift, feminine.pitch_shift);
+    assert_eq!(
+        app.world_slider_values().formant_shift,
+        feminine.formant_shift
+    );
+
+    // Less of it, then a hand-tuned pitch that the preset doesn't undo
+    // until the preset or its amount moves again.
+    app.selected_slider = amount;
+    press(&mut app, KeyCode::Left);
+    let softer = WorldSliderValues::preset_feminize(0.9);
+    assert!((app.world_slider_values().pitch_shift - softer.pitch_shift).abs() < 1e-9);
+    app.selected_slider = label(&app, "Pitch Shift");
+    press(&mut app, KeyCode::Right);
+    assert!((app.world_slider_values().pitch_shift - (softer.pitch_shift + 0.5)).abs() < 1e-9);
+    assert!((app.world_slider_values().breathiness - softer.breathiness).abs() < 1e-9);
+}
+
+#[test]
+fn test_shift_arrows_reorder_the_effects_chain() {
+    use voiceforge::dsp::effects::EffectStage;
+    let mut app = transport_app();
+    let key = |code, modifiers| KeyEvent::new(code, modifiers);
+    assert!(handle_key_event(key(KeyCode::Char('O'), KeyModifiers::SHIFT), &mut app).is_none());
+    assert_eq!(app.mode, AppMode::ChainOrder);
+
+    // Plain arrows only move the selection.
+    for _ in 0..3 {
+        assert!(handle_key_event(key(KeyCode::Down, KeyModifiers::NONE), &mut app).is_none());
+    }
+    assert_eq!(
+        app.effects_order[app.chain_order_selected],
+        EffectStage::Compressor
+    );
+    let up = key(KeyCode::Up, KeyModifiers::SHIFT);
+    assert!(matches!(
+        handle_key_event(up, &mut app),
+        Some(Action::ReapplyEffects)
+    ));
+    assert_eq!(
+        app.effects_params().order[2..4],
+        [EffectStage::Compressor, EffectStage::Gate]
+    );
+    assert_eq!(app.chain_order_selected, 2);
+
+    // Nothing above the top stage to swap with.
+    app.chain_order_selected = 0;
+    assert!(handle_key_event(up, &mut app).is_none());
+    assert!(matches!(
+        handle_key_event(key(KeyCode::Char('d'), KeyModifiers::NONE), &mut app),
+        Some(Action::ReapplyEffects)
+    ));
+    assert_eq!(app.effects_order, EffectStage::DEFAULT_ORDER);
+    handle_key_event(key(KeyCode::Esc, KeyModifiers::NONE), &mut app);
+    assert_eq!(app.mode, AppMode::Normal);
+}
+
+#[test]
+fn test_closing_the_chain_overlay_reports_the_fx_order_flag() {
+    use voiceforge::dsp::effects::EffectStage;
+    let mut app = transport_app();
+    let key = |code, modifiers| KeyEvent::new(code, modifiers);
+    handle_key_event(key(KeyCode::Char('O'), KeyModifiers::SHIFT), &mut app);
+    handle_key_event(key(KeyCode::Esc, KeyModifiers::NONE), &mut app);
+    assert!(app.status_message.is_none(), "default order needs no flag");
+
+    handle_key_event(key(KeyCode::Char('O'), KeyModifiers::SHIFT), &mut app);
+    handle_key_event(key(KeyCode::Down, KeyModifiers::SHIFT), &mut app);
+    handle_key_event(key(KeyCode::Enter, KeyModifiers::NONE), &mut app);
+    assert_eq!(app.mode, AppMode::Normal);
+    let status = app.status_message.clone().expect("order reported on close");
+    let flag = status
+        .split_once("--fx-order=")
+        .expect("flag in the status")
+        .1;
+    assert_eq!(EffectStage::parse_order(flag).unwrap(), app.effects_order);
+    assert_ne!(app.effects_order, EffectStage::DEFAULT_ORDER);
+}
+
+#[test]
+fn test_fx_mix_slider_blends_in_the_dry_signal() {
+    let mut app = transport_app();
+    app.focus = PanelFocus::EffectsSliders;
+    app.selected_slider = app
+        .effects_sliders
+        .iter()
+        .position(|s| s.label == "FX Mix")
+        .unwrap();
+    assert_eq!(app.effects_params().fx_mix, 1.0);
+    assert!(app.effects_params().is_neutral());
+
+    let key = KeyEvent::new(KeyCode::Left, KeyModifiers::NONE);
+    assert!(matches!(
+        handle_key_event(key, &mut app),
+        Some(Action::ReapplyEffects)
+    ));
+    assert_eq!(app.effects_params().fx_mix, 0.95);
+    assert!(!app.effects_params().is_neutral());
+}
+
+#[test]
+fn test_b_switches_the_graphic_eq_to_31_bands() {
+    use voiceforge::dsp::effects::EqLayout;
+    let mut app = transport_app();
+    let b = KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE);
+    // Flat: nothing to reapply.
+    assert!(handle_key_event(b, &mut app).is_none());
+    assert_eq!(app.eq_layout, EqLayout::Bands31);
+    assert_eq!(app.effects_params().eq.gains.len(), 31);
+
+    app.focus = PanelFocus::EqBands;
+    let right = KeyEvent::new(KeyCode::Right, KeyModifiers::NONE);
+    for _ in 0..40 {
+        handle_key_event(right, &mut app);
+    }
+    assert_eq!(app.eq_selected_band, 30);
+    let up = KeyEvent::new(KeyCode::Up, KeyModifiers::NONE);
+    assert!(matches!(
+        handle_key_event(up, &mut app),
+        Some(Action::ReapplyEffects)
+    ));
+
+    // Back to 12 bands: the boost can't carry over and the selection clamps.
+    assert!(matches!(
+        handle_key_event(b, &mut app),
+        Some(Action::ReapplyEffects)
+    ));
+    assert_eq!(app.eq_layout, EqLayout::Bands12);
+    assert_eq!(app.eq_gains, vec![0.0; 12]);
+    assert_eq!(app.eq_selected_band, 11);
+}
+
+#[test]
+fn test_eq_width_slider_is_auto_at_its_bottom_stop() {
+    let mut app = transport_app();
+    app.focus = PanelFocus::EffectsSliders;
+    app.selected_slider = app
+        .effects_sliders
+        .iter()
+        .position(|s| s.label == "EQ Width")
+        .unwrap();
+    assert_eq!(
+        app.effects_sliders[app.selected_slider].stop_label(),
+        Some("Auto")
+    );
+    assert_eq!(app.effects_params().eq.q, None);
+
+    let key = KeyEvent::new(KeyCode::Right, KeyModifiers::NONE);
+    assert!(matches!(
+        handle_key_event(key, &mut app),
+        Some(Action::ReapplyEffects)
+    ));
+    let q = app.effects_params().eq.q.unwrap();
+    assert!((q - 0.7).abs() < 1e-6, "{q}");
+}
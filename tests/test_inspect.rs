@@ -0,0 +1,108 @@
+use voiceforge::dsp::inspect::{envelope_peaks, nearest_frame, FrameInfo, INSPECT_PEAK_COUNT};
+
+/// Positions every 5 ms, as WORLD's default frame period produces.
+fn positions(count: usize) -> Vec<f64> {
+    (0..count).map(|i| i as f64 * 0.005).collect()
+}
+
+#[test]
+fn test_nearest_frame_rounds_to_closest_position() {
+    let tpos = positions(100);
+    assert_eq!(nearest_frame(&tpos, 0.0), Some(0));
+    assert_eq!(nearest_frame(&tpos, 0.0049), Some(1));
+    assert_eq!(nearest_frame(&tpos, 0.0051), Some(1));
+    assert_eq!(nearest_frame(&tpos, 0.0124), Some(2));
+    assert_eq!(nearest_frame(&tpos, 0.0126), Some(3));
+    // Exactly halfway goes to the earlier frame.
+    assert_eq!(nearest_frame(&[0.0, 1.0], 0.5), Some(0));
+}
+
+#[test]
+fn test_nearest_frame_clamps_and_handles_degenerate_input() {
+    let tpos = positions(10);
+    assert_eq!(nearest_frame(&tpos, -3.0), Some(0));
+    assert_eq!(nearest_frame(&tpos, 99.0), Some(9));
+    assert_eq!(nearest_frame(&tpos, f64::NAN), Some(0));
+    assert_eq!(nearest_frame(&[], 1.0), None);
+    // Non-uniform positions are searched, not computed from the period.
+    assert_eq!(nearest_frame(&[0.0, 0.1, 0.5, 2.0], 0.35), Some(2));
+}
+
+#[test]
+fn test_envelope_peaks_loudest_first_at_raw_bins() {
+    let fft_size = 64;
+    let sample_rate = 6400; // 100 Hz per bin
+    let mut envelope = vec![1e-4; fft_size / 2 + 1];
+    for (bin, power) in [
+        (3, 1e-2),
+        (8, 1.0),
+        (12, 1e-1),
+        (20, 1e-3),
+        (25, 1e-3),
+        (29, 1e-2),
+    ] {
+        envelope[bin] = power;
+    }
+    let peaks = envelope_peaks(&envelope, fft_size, sample_rate, INSPECT_PEAK_COUNT);
+    assert_eq!(peaks.len(), INSPECT_PEAK_COUNT);
+    let bins: Vec<usize> = peaks.iter().map(|p| p.bin).collect();
+    // Equal levels keep bin order.
+    assert_eq!(bins, [8, 12, 3, 29, 20]);
+    assert_eq!(peaks[0].freq_hz, 800.0);
+    assert!((peaks[0].level_db - 0.0).abs() < 1e-9);
+    assert!((peaks[1].level_db + 10.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_envelope_peaks_empty_for_flat_silent_or_short_rows() {
+    assert!(envelope_peaks(&[0.0; 513], 1024, 44100, 5).is_empty());
+    assert!(envelope_peaks(&[1e-3; 513], 1024, 44100, 5).is_empty());
+    assert!(envelope_peaks(&[1.0, 2.0], 2, 44100, 5).is_empty());
+    assert!(envelope_peaks(&[1.0, 2.0, 1.0], 0, 44100, 5).is_empty());
+}
+
+#[test]
+fn test_frame_info_reads_nearest_frame() {
+    let fft_size = 64;
+    let width = fft_size / 2 + 1;
+    let frames = 10;
+    let mut spectrogram = vec![vec![1e-4; width]; frames];
+    spectrogram[4][10] = 0.5;
+    let params = world_sys::WorldParams {
+        f0: (0..frames)
+            .map(|f| if f == 4 { 180.5 } else { 0.0 })
+            .collect(),
+        temporal_positions: positions(frames),
+        spectrogram,
+        aperiodicity: (0..frames).map(|f| vec![f as f64 / 10.0; width]).collect(),
+        fft_size,
+        frame_period: 5.0,
+        sample_rate: 6400,
+        f0_candidates: None,
+        voicing: Vec::new(),
+    };
+
+    let info = FrameInfo::from_params(&params, 6400, 0.021).expect("frames exist");
+    assert_eq!(info.frame, 4);
+    assert_eq!(info.frame_count, frames);
+    assert_eq!(info.time_secs, 0.02);
+    assert_eq!(info.frame_period_ms, 5.0);
+    assert_eq!(info.f0_hz, 180.5);
+    assert!((info.mean_aperiodicity - 0.4).abs() < 1e-12);
+    assert_eq!(info.peaks.len(), 1);
+    assert_eq!(info.peaks[0].bin, 10);
+    assert_eq!(info.peaks[0].freq_hz, 1000.0);
+
+    let empty = world_sys::WorldParams {
+        f0: Vec::new(),
+        temporal_positions: Vec::new(),
+        spectrogram: Vec::new(),
+        aperiodicity: Vec::new(),
+        fft_size,
+        frame_period: 5.0,
+        sample_rate: 6400,
+        f0_candidates: None,
+        voicing: Vec::new(),
+    };
+    assert!(FrameInfo::from_params(&empty, 6400, 0.0).is_none());
+}
@@ -0,0 +1,73 @@
+use voiceforge::dsp::loudness::{rms_dbfs, AbLoudness, SILENCE_DB};
+
+fn sine(amplitude: f32, len: usize) -> Vec<f32> {
+    // Whole periods of a 100-sample cycle, so the RMS is exact.
+    (0..len)
+        .map(|i| amplitude * (2.0 * std::f32::consts::PI * i as f32 / 100.0).sin())
+        .collect()
+}
+
+#[test]
+fn test_rms_of_full_scale_sine_is_minus_3_db() {
+    let db = rms_dbfs(&sine(1.0, 10_000));
+    assert!((db - (-3.0103)).abs() < 0.01, "got {db}");
+}
+
+#[test]
+fn test_rms_tracks_amplitude() {
+    let full = rms_dbfs(&sine(1.0, 10_000));
+    let half = rms_dbfs(&sine(0.5, 10_000));
+    assert!((full - half - 6.0206).abs() < 0.01, "got {full} vs {half}");
+    // DC at 0.25 reads exactly 20·log10(0.25).
+    let dc = rms_dbfs(&[0.25; 64]);
+    assert!((dc - (-12.0412)).abs() < 0.01, "got {dc}");
+}
+
+#[test]
+fn test_rms_of_silence_and_empty() {
+    assert_eq!(rms_dbfs(&[]), SILENCE_DB);
+    assert_eq!(rms_dbfs(&[0.0; 128]), SILENCE_DB);
+    assert_eq!(rms_dbfs(&[1e-12; 128]), SILENCE_DB);
+}
+
+#[test]
+fn test_delta_formatting() {
+    let louder = AbLoudness {
+        original_db: Some(-20.0),
+        processed_db: -17.7,
+    };
+    assert_eq!(louder.describe(), "B is +2.3 dB louder than A");
+    assert!(louder.is_mismatched());
+
+    let quieter = AbLoudness {
+        original_db: Some(-20.0),
+        processed_db: -21.5,
+    };
+    assert_eq!(quieter.describe(), "B is 1.5 dB quieter than A");
+    assert!(quieter.is_mismatched());
+
+    let close = AbLoudness {
+        original_db: Some(-20.0),
+        processed_db: -19.4,
+    };
+    assert_eq!(close.describe(), "B is +0.6 dB louder than A");
+    assert!(!close.is_mismatched());
+
+    let same = AbLoudness {
+        original_db: Some(-20.0),
+        processed_db: -20.02,
+    };
+    assert_eq!(same.describe(), "B matches A in level");
+    assert!(!same.is_mismatched());
+}
+
+#[test]
+fn test_delta_without_analyzed_original() {
+    let measured = AbLoudness {
+        original_db: None,
+        processed_db: -18.24,
+    };
+    assert_eq!(measured.delta_db(), None);
+    assert!(!measured.is_mismatched());
+    assert_eq!(measured.describe(), "B -18.2 dBFS RMS (A not analyzed)");
+}
@@ -1,6 +1,10 @@
 use std::f64::consts::PI;
+use std::sync::{Arc, Mutex};
 
-use voiceforge::dsp::modifier::{self, WorldSliderValues};
+use voiceforge::dsp::modifier::{
+    self, Automation, DirtyStages, F0Pipeline, F0Stage, FormantWarp, GrowlRate, RangeCenter,
+    RetuneScale, SpectralPipeline, SpectralStage, VoicePreset, WorldSliderValues,
+};
 
 /// Generate a harmonic-rich test signal and analyze it with WORLD.
 fn make_test_params() -> (world_sys::WorldParams, u32) {
@@ -102,3 +106,2017 @@ fn test_modifier_speed_2x() {
     assert_eq!(modified.aperiodicity.len(), modified.f0.len());
     assert_eq!(modified.temporal_positions.len(), modified.f0.len());
 }
+
+/// Params whose f0 alternates voiced and unvoiced runs of the given lengths,
+/// starting voiced.
+fn alternating_params(runs: &[usize]) -> world_sys::WorldParams {
+    let width = 33;
+    let f0: Vec<f64> = runs
+        .iter()
+        .enumerate()
+        .flat_map(|(k, &len)| std::iter::repeat_n(if k % 2 == 0 { 150.0 } else { 0.0 }, len))
+        .collect();
+    let frames = f0.len();
+    world_sys::WorldParams {
+        f0,
+        temporal_positions: (0..frames).map(|i| i as f64 * 0.005).collect(),
+        spectrogram: vec![vec![1.0; width]; frames],
+        aperiodicity: vec![vec![0.1; width]; frames],
+        fft_size: 64,
+        frame_period: 5.0,
+        sample_rate: 16000,
+        f0_candidates: None,
+        voicing: Vec::new(),
+    }
+}
+
+/// Lengths of the voiced and unvoiced runs of `f0`, in order.
+fn run_lengths(f0: &[f64]) -> Vec<usize> {
+    let mut runs: Vec<usize> = Vec::new();
+    for (i, &f) in f0.iter().enumerate() {
+        if i > 0 && (f > 0.0) == (f0[i - 1] > 0.0) {
+            *runs.last_mut().unwrap() += 1;
+        } else {
+            runs.push(1);
+        }
+    }
+    runs
+}
+
+#[test]
+fn test_voiced_and_unvoiced_runs_stretch_by_their_own_speed() {
+    for runs in [vec![20, 20, 20, 20, 20, 20], vec![30, 7, 13, 41, 9, 3, 25]] {
+        let params = alternating_params(&runs);
+        for (speed, speed_unvoiced) in [(0.5, 2.0), (2.0, 0.5), (0.8, 1.0)] {
+            let values = WorldSliderValues {
+                speed,
+                speed_unvoiced,
+                ..Default::default()
+            };
+            assert!(values.is_stretched());
+            let modified = modifier::apply(&params, &values);
+
+            let stretched = run_lengths(&modified.f0);
+            assert_eq!(
+                stretched.len(),
+                runs.len(),
+                "{runs:?} at {speed}/{speed_unvoiced}"
+            );
+            for (k, (&before, &after)) in runs.iter().zip(&stretched).enumerate() {
+                let factor = if k % 2 == 0 { speed } else { speed_unvoiced };
+                let expected = before as f64 / factor;
+                assert!(
+                    (after as f64 - expected).abs() <= 1.0,
+                    "run {k} of {runs:?} at {speed}/{speed_unvoiced}: {after} frames, expected ~{expected}"
+                );
+            }
+
+            // A uniform stretch by the frame-weighted harmonic mean of the
+            // two speeds has the same total length.
+            let voiced: usize = runs.iter().step_by(2).sum();
+            let unvoiced: usize = runs.iter().skip(1).step_by(2).sum();
+            let total = voiced as f64 / speed + unvoiced as f64 / speed_unvoiced;
+            assert!(
+                (modified.f0.len() as f64 - total).abs() <= 1.0,
+                "{} vs {total}",
+                modified.f0.len()
+            );
+            assert_eq!(modified.spectrogram.len(), modified.f0.len());
+            assert_eq!(modified.aperiodicity.len(), modified.f0.len());
+            assert_eq!(modified.temporal_positions.len(), modified.f0.len());
+            for (i, &t) in modified.temporal_positions.iter().enumerate() {
+                assert!((t - i as f64 * 0.005).abs() < 1e-12);
+            }
+        }
+    }
+
+    // An unset unvoiced speed follows the voiced one: a uniform stretch.
+    let params = alternating_params(&[20, 20, 20]);
+    let uniform = WorldSliderValues {
+        speed: 2.0,
+        ..Default::default()
+    };
+    assert_eq!(uniform.unvoiced_speed(), 2.0);
+    let same = WorldSliderValues {
+        speed_unvoiced: 2.0,
+        ..uniform.clone()
+    };
+    assert_eq!(
+        modifier::apply(&params, &uniform).f0,
+        modifier::apply(&params, &same).f0
+    );
+}
+
+/// Synthetic params with a distinct, non-flat envelope per frame.
+fn synthetic_params() -> world_sys::WorldParams {
+    let fft_size = 1024;
+    let width = fft_size / 2 + 1;
+    let frames = 8;
+    world_sys::WorldParams {
+        f0: vec![200.0; frames],
+        temporal_positions: (0..frames).map(|i| i as f64 * 0.005).collect(),
+        spectrogram: (0..frames)
+            .map(|f| {
+                (0..width)
+                    .map(|i| 1.0 + ((i + 7 * f) as f64 * 0.05).sin().abs() * 10.0)
+                    .collect()
+            })
+            .collect(),
+        aperiodicity: vec![vec![0.1; width]; frames],
+        fft_size,
+        frame_period: 5.0,
+        sample_rate: 16000,
+        f0_candidates: None,
+        voicing: Vec::new(),
+    }
+}
+
+#[test]
+fn test_formant_shift_row_matches_full_matrix() {
+    let params = synthetic_params();
+    let values = WorldSliderValues {
+        formant_shift: 3.5,
+        ..Default::default()
+    };
+    let modified = modifier::apply(&params, &values);
+    for (k, row) in params.spectrogram.iter().enumerate() {
+        let mut single = row.clone();
+        modifier::formant_shift_row(&mut single, params.fft_size, values.formant_shift);
+        assert_eq!(single, modified.spectrogram[k], "frame {k}");
+    }
+}
+
+#[test]
+fn test_row_stages_on_long_params_match_row_functions() {
+    // Enough frames for the row-parallel paths.
+    let short = synthetic_params();
+    let frames = 1200;
+    let params = world_sys::WorldParams {
+        f0: vec![200.0; frames],
+        temporal_positions: (0..frames).map(|i| i as f64 * 0.005).collect(),
+        spectrogram: (0..frames)
+            .map(|f| {
+                let gain = 1.0 + f as f64 / 100.0;
+                short.spectrogram[f % 8].iter().map(|v| v * gain).collect()
+            })
+            .collect(),
+        aperiodicity: vec![vec![0.1; 513]; frames],
+        ..short
+    };
+    let values = WorldSliderValues {
+        formant_shift: 2.5,
+        ..Default::default()
+    };
+    let modified = modifier::apply(&params, &values);
+    for k in [0, 299, 300, 777, frames - 1] {
+        let mut single = params.spectrogram[k].clone();
+        modifier::formant_shift_row(&mut single, params.fft_size, values.formant_shift);
+        assert_eq!(single, modified.spectrogram[k], "frame {k}");
+    }
+
+    // Half speed doubles the frames; the ends land on the source's ends.
+    let half_speed = WorldSliderValues {
+        speed: 0.5,
+        ..Default::default()
+    };
+    let slowed = modifier::apply(&params, &half_speed);
+    assert_eq!(slowed.spectrogram.len(), 2 * frames);
+    assert_eq!(slowed.spectrogram[0], params.spectrogram[0]);
+    assert_eq!(
+        slowed.spectrogram[2 * frames - 1],
+        params.spectrogram[frames - 1]
+    );
+}
+
+#[test]
+fn test_spectral_tilt_row_matches_full_matrix() {
+    let params = synthetic_params();
+    let values = WorldSliderValues {
+        spectral_tilt: -4.0,
+        ..Default::default()
+    };
+    let modified = modifier::apply(&params, &values);
+    let k = 3;
+    let mut single = params.spectrogram[k].clone();
+    modifier::spectral_tilt_row_at(
+        &mut single,
+        params.fft_size,
+        params.sample_rate,
+        values.spectral_tilt,
+        values.tilt_pivot_hz,
+    );
+    assert_eq!(single, modified.spectrogram[k]);
+}
+
+#[test]
+fn test_preview_envelope_matches_full_matrix() {
+    let params = synthetic_params();
+    let values = WorldSliderValues {
+        formant_shift: -2.0,
+        spectral_tilt: 3.0,
+        ..Default::default()
+    };
+    let modified = modifier::apply(&params, &values);
+    let k = 5;
+    let preview = modifier::preview_envelope_at_rate(
+        &params.spectrogram[k],
+        params.fft_size,
+        params.sample_rate,
+        &values,
+    );
+    assert_eq!(preview, modified.spectrogram[k]);
+    // Neutral values leave the row untouched.
+    let neutral =
+        modifier::preview_envelope(&params.spectrogram[k], params.fft_size, &Default::default());
+    assert_eq!(neutral, params.spectrogram[k]);
+}
+
+#[test]
+fn test_window_matches_full_apply() {
+    let mut params = synthetic_params();
+    params.f0 = vec![150.0, 0.0, 180.0, 200.0, 220.0, 0.0, 260.0, 300.0];
+    let values = WorldSliderValues {
+        pitch_shift: 2.0,
+        pitch_range: 1.5,
+        breathiness: 0.2,
+        formant_shift: 1.0,
+        ..Default::default()
+    };
+    let full = modifier::apply(&params, &values);
+    let window = modifier::apply_window(&params, &values, 2..6);
+    // The pitch range still works around the whole contour's mean.
+    assert_eq!(window.f0, full.f0[2..6]);
+    assert_eq!(window.temporal_positions, params.temporal_positions[2..6]);
+    assert_eq!(window.spectrogram, full.spectrogram[2..6]);
+    assert_eq!(window.aperiodicity, full.aperiodicity[2..6]);
+    assert_eq!(window.fft_size, params.fft_size);
+}
+
+#[test]
+fn test_low_confidence_frames_resist_pitch_changes() {
+    let mut params = synthetic_params();
+    params.voicing = vec![1.0, 1.0, 0.5, 0.25, 0.0, 0.0, 1.0, 1.0];
+    let octave_up = WorldSliderValues {
+        pitch_shift: 12.0,
+        ..Default::default()
+    };
+    let shifted = modifier::apply(&params, &octave_up);
+    let expected = [
+        400.0,
+        400.0,
+        400.0,
+        200.0 * 2f64.sqrt(),
+        200.0,
+        200.0,
+        400.0,
+        400.0,
+    ];
+    for (i, (&got, want)) in shifted.f0.iter().zip(expected).enumerate() {
+        assert!((got - want).abs() < 1e-9, "frame {i}: {got} vs {want}");
+    }
+    assert_eq!(shifted.voicing, params.voicing);
+
+    // The range works around the confident frames' mean: the doubtful
+    // frame at 500 Hz neither pulls the mean up nor gets stretched.
+    params.f0 = vec![200.0, 300.0, 250.0, 250.0, 500.0, 0.0, 200.0, 300.0];
+    let wider = WorldSliderValues {
+        pitch_range: 2.0,
+        ..Default::default()
+    };
+    let ranged = modifier::apply(&params, &wider);
+    assert_eq!(ranged.f0[..4], [150.0, 350.0, 250.0, 250.0]);
+    assert_eq!(ranged.f0[4..6], [500.0, 0.0]);
+
+    // Without a voicing track every voiced frame is trusted.
+    params.voicing.clear();
+    let shifted = modifier::apply(&params, &octave_up);
+    assert!(shifted
+        .f0
+        .iter()
+        .zip(&params.f0)
+        .all(|(&s, &f)| s == 2.0 * f));
+    assert!(shifted.voicing.is_empty());
+}
+
+#[test]
+fn test_pipeline_stage_order_is_stable() {
+    let expected_f0 = [
+        "pitch_smooth",
+        "pitch_shift",
+        "pitch_range",
+        "pitch_flatten",
+        "retune",
+        "humanize",
+        "pitch_offsets",
+        "vibrato",
+        "growl",
+        "robot",
+    ];
+    let expected_spectral = [
+        "spectral_smooth",
+        "breathiness",
+        "formant_shift",
+        "spectral_tilt",
+        "presence",
+        "nasality",
+        "growl",
+        "robot",
+    ];
+    // Same order whether the sliders are neutral or not.
+    let moved = WorldSliderValues {
+        pitch_shift: 3.0,
+        pitch_range: 1.5,
+        breathiness: 0.2,
+        formant_shift: -1.0,
+        spectral_tilt: 2.0,
+        flatten_amount: 0.5,
+        vibrato_depth_cents: 50.0,
+        robot_f0: Some(120.0),
+        f0_smoothing_frames: 5.0,
+        retune_strength: 1.0,
+        ..Default::default()
+    };
+    for values in [WorldSliderValues::default(), moved] {
+        assert_eq!(F0Pipeline::from_values(&values).stage_names(), expected_f0);
+        assert_eq!(
+            SpectralPipeline::from_values(&values).stage_names(),
+            expected_spectral
+        );
+    }
+}
+
+/// Test stage that records the contour it receives and leaves it alone.
+struct Probe(Arc<Mutex<Vec<f64>>>);
+
+impl F0Stage for Probe {
+    fn name(&self) -> &'static str {
+        "probe"
+    }
+
+    fn apply(&self, f0: &mut [f64], _tpos: &[f64]) {
+        *self.0.lock().unwrap() = f0.to_vec();
+    }
+}
+
+#[test]
+fn test_inserted_f0_stage_sees_earlier_stages() {
+    let mut params = synthetic_params();
+    params.f0 = vec![100.0, 200.0, 0.0, 300.0, 200.0, 0.0, 100.0, 300.0];
+    let values = WorldSliderValues {
+        pitch_shift: 12.0,
+        pitch_range: 0.5,
+        ..Default::default()
+    };
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let mut f0 = F0Pipeline::from_values(&values);
+    f0.insert(2, Box::new(Probe(Arc::clone(&seen))));
+    assert_eq!(
+        f0.stage_names(),
+        [
+            "pitch_smooth",
+            "pitch_shift",
+            "probe",
+            "pitch_range",
+            "pitch_flatten",
+            "retune",
+            "humanize",
+            "pitch_offsets",
+            "vibrato",
+            "growl",
+            "robot",
+        ]
+    );
+
+    let modified = modifier::apply_pipelines(
+        &params,
+        &f0,
+        values.speed,
+        &SpectralPipeline::from_values(&values),
+    );
+
+    // The probe ran after the octave shift but before the range compression.
+    let shifted: Vec<f64> = params.f0.iter().map(|f| f * 2.0).collect();
+    let seen = seen.lock().unwrap().clone();
+    assert_eq!(seen.len(), shifted.len());
+    for (a, b) in seen.iter().zip(&shifted) {
+        assert!((a - b).abs() < 1e-9, "probe saw {a}, expected {b}");
+    }
+    // A read-only probe doesn't change the result.
+    assert_eq!(modified.f0, modifier::apply(&params, &values).f0);
+}
+
+/// Test stage that flattens the envelope to unit power.
+struct Flatten;
+
+impl SpectralStage for Flatten {
+    fn name(&self) -> &'static str {
+        "flatten"
+    }
+
+    fn apply(&self, spectrogram: &mut [Vec<f64>], _aperiodicity: &mut [Vec<f64>], _fft: usize) {
+        for row in spectrogram.iter_mut() {
+            row.iter_mut().for_each(|v| *v = 1.0);
+        }
+    }
+}
+
+#[test]
+fn test_spectral_stage_position_matters() {
+    let params = synthetic_params();
+    let values = WorldSliderValues {
+        spectral_tilt: 6.0,
+        ..Default::default()
+    };
+    let f0 = F0Pipeline::from_values(&values);
+
+    // Last: nothing runs after it, so the envelope stays flat.
+    let mut last = SpectralPipeline::from_values(&values);
+    last.insert(last.len(), Box::new(Flatten));
+    let out = modifier::apply_pipelines(&params, &f0, 1.0, &last);
+    assert!(out.spectrogram.iter().flatten().all(|&v| v == 1.0));
+
+    // First: the tilt stage sees the flat envelope and slopes it.
+    let mut first = SpectralPipeline::from_values(&values);
+    first.insert(0, Box::new(Flatten));
+    assert_eq!(first.stage_names()[0], "flatten");
+    let out = modifier::apply_pipelines(&params, &f0, 1.0, &first);
+    let mut expected = vec![1.0; params.fft_size / 2 + 1];
+    modifier::spectral_tilt_row_at(
+        &mut expected,
+        params.fft_size,
+        params.sample_rate,
+        values.spectral_tilt,
+        values.tilt_pivot_hz,
+    );
+    assert!(out.spectrogram.iter().all(|row| *row == expected));
+    assert_eq!(out.aperiodicity, params.aperiodicity);
+}
+
+const SILENT_FRAMES: std::ops::Range<usize> = 30..70;
+
+/// 100 frames at 5 ms with digital silence (all-zero envelope, f0 = 0) in
+/// [`SILENT_FRAMES`] between two voiced stretches at 200 Hz.
+fn params_with_silence() -> world_sys::WorldParams {
+    let fft_size = 1024;
+    let width = fft_size / 2 + 1;
+    let frames = 100;
+    let silent = |f: usize| SILENT_FRAMES.contains(&f);
+    // Falling envelope with a formant bump around bin 40 (~625 Hz at 16 kHz).
+    let envelope: Vec<f64> = (0..width)
+        .map(|k| {
+            let bump = (-((k as f64 - 40.0) / 15.0).powi(2)).exp();
+            1e-3 * (1.0 + 4.0 * bump) / (1.0 + k as f64 / 50.0)
+        })
+        .collect();
+    world_sys::WorldParams {
+        f0: (0..frames)
+            .map(|f| if silent(f) { 0.0 } else { 200.0 })
+            .collect(),
+        temporal_positions: (0..frames).map(|f| f as f64 * 0.005).collect(),
+        spectrogram: (0..frames)
+            .map(|f| {
+                if silent(f) {
+                    vec![0.0; width]
+                } else {
+                    envelope.clone()
+                }
+            })
+            .collect(),
+        aperiodicity: (0..frames)
+            .map(|f| vec![if silent(f) { 0.999 } else { 0.1 }; width])
+            .collect(),
+        fft_size,
+        frame_period: 5.0,
+        sample_rate: 16000,
+        f0_candidates: None,
+        voicing: Vec::new(),
+    }
+}
+
+fn assert_finite(params: &world_sys::WorldParams, label: &str) {
+    for (name, rows) in [
+        ("spectrogram", &params.spectrogram),
+        ("aperiodicity", &params.aperiodicity),
+    ] {
+        for (f, row) in rows.iter().enumerate() {
+            assert!(
+                row.iter().all(|v| v.is_finite()),
+                "{label}: non-finite {name} in frame {f}"
+            );
+        }
+    }
+    assert!(
+        params.f0.iter().all(|v| v.is_finite()),
+        "{label}: non-finite f0"
+    );
+}
+
+#[test]
+fn test_frame_energy_floor_and_silence_mask() {
+    use voiceforge::dsp::world::{self, FRAME_ENERGY_EPS};
+
+    assert_eq!(world::frame_energy_floor(0.0), FRAME_ENERGY_EPS);
+    assert_eq!(world::frame_energy_floor(-1.0), FRAME_ENERGY_EPS);
+    assert_eq!(world::frame_energy_floor(f64::NAN), FRAME_ENERGY_EPS);
+    assert_eq!(world::frame_energy_floor(0.5), 0.5);
+
+    let params = params_with_silence();
+    let mask = world::silent_frames(&params.spectrogram);
+    assert_eq!(mask.len(), params.spectrogram.len());
+    for (f, &silent) in mask.iter().enumerate() {
+        assert_eq!(silent, SILENT_FRAMES.contains(&f), "frame {f}");
+    }
+}
+
+#[test]
+fn test_every_stage_leaves_silent_frames_silent() {
+    let params = params_with_silence();
+    let one = |f: fn(&mut WorldSliderValues)| {
+        let mut values = WorldSliderValues::default();
+        f(&mut values);
+        values
+    };
+    let cases = [
+        ("pitch_smooth", one(|v| v.f0_smoothing_frames = 9.0)),
+        ("pitch_shift", one(|v| v.pitch_shift = 5.0)),
+        ("pitch_range", one(|v| v.pitch_range = 1.8)),
+        ("breathiness", one(|v| v.breathiness = 0.8)),
+        ("formant_shift", one(|v| v.formant_shift = -4.0)),
+        ("spectral_tilt", one(|v| v.spectral_tilt = 6.0)),
+        ("presence", one(|v| v.presence_db = 12.0)),
+        ("nasality", one(|v| v.nasality_db = 6.0)),
+        ("spectral_smooth", one(|v| v.spectral_smoothing = 1.0)),
+        ("pitch_flatten", one(|v| v.flatten_amount = 1.0)),
+        ("retune", one(|v| v.retune_strength = 1.0)),
+        ("humanize", one(|v| v.humanize_cents = 50.0)),
+        (
+            "pitch_offsets",
+            one(|v| v.f0_offsets = vec![(0.0, -3.0), (0.5, 7.0)]),
+        ),
+        ("vibrato", one(|v| v.vibrato_depth_cents = 100.0)),
+        ("growl", one(|v| v.growl_depth = 1.0)),
+        ("denoise", one(|v| v.denoise_db = 30.0)),
+        ("robot", one(|v| v.robot_f0 = Some(150.0))),
+    ];
+    for (label, values) in cases {
+        let modified = modifier::apply(&params, &values);
+        assert_finite(&modified, label);
+        for f in SILENT_FRAMES {
+            assert!(
+                modified.spectrogram[f].iter().all(|&v| v == 0.0),
+                "{label}: silent frame {f} gained energy"
+            );
+            assert_eq!(
+                modified.aperiodicity[f], params.aperiodicity[f],
+                "{label}: frame {f}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_synthesis_with_silent_frames_is_finite_and_silent() {
+    let params = params_with_silence();
+    let sample_rate = 16000;
+    let values = WorldSliderValues {
+        f0_smoothing_frames: 5.0,
+        pitch_shift: 3.0,
+        automation: Automation {
+            pitch_shift: vec![(0.1, 0.0), (0.3, 2.0)],
+            breathiness: vec![(0.0, 0.2), (0.4, 0.6)],
+            spectral_tilt: Vec::new(),
+        },
+        pitch_range: 1.5,
+        pitch_range_center: RangeCenter::PerSegment,
+        speed: 1.25,
+        speed_unvoiced: 0.8,
+        breathiness: 0.5,
+        breathiness_crossover_hz: 1000.0,
+        formant_shift: 2.0,
+        formant_keep_energy: true,
+        formant_warp: FormantWarp::Log,
+        spectral_tilt: -3.0,
+        tilt_pivot_hz: 500.0,
+        presence_db: 4.0,
+        presence_center_hz: 3000.0,
+        presence_width_oct: 1.0,
+        nasality_db: -3.0,
+        denoise_db: 12.0,
+        spectral_smoothing: 0.5,
+        flatten_amount: 0.3,
+        flatten_target_hz: 0.0,
+        retune_strength: 0.5,
+        retune_speed_ms: 20.0,
+        retune_scale: RetuneScale::Minor,
+        retune_root: 9,
+        humanize_cents: 20.0,
+        humanize_seed: 7,
+        f0_offsets: vec![(0.1, 0.0), (0.2, 2.0)],
+        vibrato_depth_cents: 80.0,
+        vibrato_rate_hz: 6.0,
+        growl_depth: 0.5,
+        growl_rate: GrowlRate::Fixed(60.0),
+        robot_f0: None,
+        whisper: false,
+        region: Some((0.1, 0.4)),
+        bypass: false,
+        f0_edited: false,
+    };
+    let modified = modifier::apply(&params, &values);
+    assert_finite(&modified, "all sliders");
+
+    for p in [&params, &modified] {
+        let out =
+            voiceforge::dsp::world::synthesize(p, sample_rate, None).expect("synthesis failed");
+        assert!(!out.samples.is_empty());
+        assert!(
+            out.samples.iter().all(|s| s.is_finite()),
+            "synthesis produced non-finite samples"
+        );
+        // Middle of the silence, away from the edges where the voiced
+        // frames' responses (one fft_size long) and resampling overlap it.
+        let frames = p.f0.len() as f64;
+        let centre = p.f0.len() as f64 / 2.0 / frames * out.samples.len() as f64;
+        let span = 0.02 * sample_rate as f64;
+        let start = (centre - span) as usize;
+        let end = (centre + span) as usize;
+        let peak = out.samples[start..end]
+            .iter()
+            .fold(0.0_f32, |m, s| m.max(s.abs()));
+        assert!(peak < 1e-6, "silent region peaks at {peak}");
+        // The voiced stretches still make sound.
+        let voiced_peak = out.samples[..start / 2]
+            .iter()
+            .fold(0.0_f32, |m, s| m.max(s.abs()));
+        assert!(
+            voiced_peak > 1e-3,
+            "voiced region peaks at only {voiced_peak}"
+        );
+    }
+}
+
+#[test]
+fn test_dirty_stages_between_slider_values() {
+    let neutral = WorldSliderValues::default();
+    assert!(!DirtyStages::between(&neutral, &neutral).any());
+    let pitched = WorldSliderValues {
+        pitch_shift: 3.0,
+        ..Default::default()
+    };
+    let dirty = DirtyStages::between(&neutral, &pitched);
+    assert_eq!(
+        dirty,
+        DirtyStages {
+            f0: true,
+            ..Default::default()
+        }
+    );
+    let slower = WorldSliderValues {
+        speed: 0.8,
+        ..pitched.clone()
+    };
+    assert_eq!(
+        DirtyStages::between(&pitched, &slower),
+        DirtyStages {
+            speed: true,
+            ..Default::default()
+        }
+    );
+    let tilted = WorldSliderValues {
+        spectral_tilt: -3.0,
+        ..Default::default()
+    };
+    assert_eq!(
+        DirtyStages::between(&neutral, &tilted),
+        DirtyStages {
+            spectral: true,
+            ..Default::default()
+        }
+    );
+    let bypassed = WorldSliderValues {
+        bypass: true,
+        ..Default::default()
+    };
+    assert_eq!(DirtyStages::between(&neutral, &bypassed), DirtyStages::ALL);
+}
+
+#[test]
+fn test_changed_frames_spans_first_to_last_difference() {
+    let f0 = [0.0, 220.0, 221.0, 222.0, 0.0];
+    assert_eq!(modifier::changed_frames(&f0, &f0), None);
+    let mut corrected = f0;
+    corrected[1] = 110.0;
+    corrected[3] = 111.0;
+    assert_eq!(modifier::changed_frames(&f0, &corrected), Some(1..4));
+    assert_eq!(modifier::changed_frames(&f0, &f0[..3]), Some(0..5));
+}
+
+#[test]
+fn test_vibrato_oscillates_around_constant_f0() {
+    let mut params = synthetic_params();
+    let frames = 200; // 1 s at 5 ms
+    params.f0 = vec![440.0; frames];
+    params.f0[100..110].fill(0.0);
+    params.temporal_positions = (0..frames).map(|i| i as f64 * 0.005).collect();
+    params.spectrogram = vec![params.spectrogram[0].clone(); frames];
+    params.aperiodicity = vec![params.aperiodicity[0].clone(); frames];
+    let values = WorldSliderValues {
+        vibrato_depth_cents: 100.0,
+        vibrato_rate_hz: 5.0,
+        ..Default::default()
+    };
+    assert!(!values.is_neutral());
+    let modified = modifier::apply(&params, &values);
+
+    // A semitone either way: 440 · 2^(±1/12).
+    let voiced: Vec<f64> = modified.f0.iter().copied().filter(|&f| f > 0.0).collect();
+    let (low, high) = voiced
+        .iter()
+        .fold((f64::MAX, 0.0f64), |(lo, hi), &f| (lo.min(f), hi.max(f)));
+    assert!(
+        (low - 415.3).abs() < 1.0 && (high - 466.2).abs() < 1.0,
+        "{low}..{high}"
+    );
+    assert!(modified.f0[100..110].iter().all(|&f| f == 0.0));
+
+    // Five cycles a second: f0 rises through 440 Hz every 200 ms (the
+    // crossing at t = 0 has no frame before it), and the phase carries on
+    // through the unvoiced gap.
+    let rising = (1..frames)
+        .filter(|&i| modified.f0[i - 1] > 0.0 && modified.f0[i] > 0.0)
+        .filter(|&i| modified.f0[i - 1] < 440.0 && modified.f0[i] >= 440.0)
+        .count();
+    assert!((4..=5).contains(&rising), "{rising} cycles");
+    let t = 0.55;
+    let expected = 440.0 * 2.0_f64.powf((2.0 * std::f64::consts::PI * 5.0 * t).sin() / 12.0);
+    assert!(
+        (modified.f0[110] - expected).abs() < 1e-9,
+        "{} vs {expected}",
+        modified.f0[110]
+    );
+
+    // Zero depth or zero rate is neutral.
+    for values in [
+        WorldSliderValues::default(),
+        WorldSliderValues {
+            vibrato_depth_cents: 100.0,
+            vibrato_rate_hz: 0.0,
+            ..Default::default()
+        },
+    ] {
+        assert!(values.is_neutral());
+        assert_eq!(modifier::apply(&params, &values).f0, params.f0);
+    }
+}
+
+#[test]
+fn test_flatten_pulls_voiced_frames_to_the_target() {
+    let mut params = synthetic_params();
+    params.f0 = vec![100.0, 180.0, 0.0, 250.0, 300.0, 0.0, 150.0, 220.0];
+    let flatten = |amount, target| {
+        let values = WorldSliderValues {
+            flatten_amount: amount,
+            flatten_target_hz: target,
+            ..Default::default()
+        };
+        modifier::apply(&params, &values).f0
+    };
+
+    let monotone = flatten(1.0, 200.0);
+    for (i, (&out, &original)) in monotone.iter().zip(&params.f0).enumerate() {
+        let expected = if original > 0.0 { 200.0 } else { 0.0 };
+        assert_eq!(out, expected, "frame {i}");
+    }
+    // Halfway to the target.
+    assert_eq!(flatten(0.5, 200.0)[0], 150.0);
+    // Target 0: the median of the six voiced frames (the upper middle one).
+    assert!(flatten(1.0, 0.0).iter().all(|&f| f == 0.0 || f == 220.0));
+
+    let neutral = WorldSliderValues {
+        flatten_target_hz: 200.0,
+        ..Default::default()
+    };
+    assert!(neutral.is_neutral());
+    assert!(!WorldSliderValues {
+        flatten_amount: 0.2,
+        ..neutral
+    }
+    .is_neutral());
+}
+
+#[test]
+fn test_robotize_voices_every_frame_at_a_constant_pitch() {
+    let (mut params, sample_rate) = make_test_params();
+    // An unvoiced stretch in the middle that the robot has to voice.
+    params.f0[80..120].fill(0.0);
+    let values = WorldSliderValues {
+        robot_f0: Some(150.0),
+        ..Default::default()
+    };
+    assert!(!values.is_neutral());
+    let modified = modifier::apply(&params, &values);
+    assert!(modified.f0.iter().all(|&f| f == 150.0));
+    assert!(modified
+        .aperiodicity
+        .iter()
+        .flatten()
+        .all(|&a| a <= modifier::ROBOT_APERIODICITY));
+
+    let mut robotized = params.clone();
+    modifier::apply_robotize(&mut robotized, 150.0);
+    assert_eq!(robotized.f0, modified.f0);
+
+    let out =
+        voiceforge::dsp::world::synthesize(&modified, sample_rate, None).expect("synthesis failed");
+    assert!(out.samples.iter().all(|s| s.is_finite()));
+    let mono: Vec<f64> = out.samples.iter().map(|&s| f64::from(s)).collect();
+    let (_, f0) =
+        world_sys::analyze_f0_only(&mono, sample_rate as i32, &Default::default()).unwrap();
+    let voiced: Vec<f64> = f0.iter().copied().filter(|&f| f > 0.0).collect();
+    assert!(
+        voiced.len() > f0.len() * 3 / 4,
+        "{} of {} frames voiced",
+        voiced.len(),
+        f0.len()
+    );
+    let median = {
+        let mut sorted = voiced.clone();
+        sorted.sort_by(f64::total_cmp);
+        sorted[sorted.len() / 2]
+    };
+    assert!((median - 150.0).abs() < 5.0, "detected {median} Hz");
+}
+
+/// Normalized autocorrelation of `x` at `lag`.
+fn autocorrelation(x: &[f64], lag: usize) -> f64 {
+    let energy: f64 = x.iter().map(|v| v * v).sum();
+    x.iter().zip(&x[lag..]).map(|(a, b)| a * b).sum::<f64>() / energy
+}
+
+/// Power-weighted mean frequency of `x`, from one FFT over all of it.
+fn spectral_centroid(x: &[f64], sample_rate: u32) -> f64 {
+    let mut buffer: Vec<rustfft::num_complex::Complex<f64>> = x
+        .iter()
+        .map(|&v| rustfft::num_complex::Complex::new(v, 0.0))
+        .collect();
+    rustfft::FftPlanner::new()
+        .plan_fft_forward(x.len())
+        .process(&mut buffer);
+    let bin_hz = f64::from(sample_rate) / x.len() as f64;
+    let (weighted, total) = buffer[..x.len() / 2]
+        .iter()
+        .enumerate()
+        .fold((0.0, 0.0), |(w, t), (k, c)| {
+            (w + k as f64 * bin_hz * c.norm_sqr(), t + c.norm_sqr())
+        });
+    weighted / total
+}
+
+#[test]
+fn test_whisper_removes_the_pitch_but_keeps_the_envelope() {
+    let (params, sample_rate) = make_test_params();
+    let values = WorldSliderValues {
+        whisper: true,
+        pitch_shift: 7.0,
+        robot_f0: Some(200.0),
+        ..Default::default()
+    };
+    assert!(!values.is_neutral());
+    let whispered = modifier::apply(&params, &values);
+    assert!(whispered.f0.iter().all(|&f| f == 0.0));
+    assert!(whispered.aperiodicity.iter().flatten().all(|&a| a == 1.0));
+    let window = modifier::apply_window(&params, &values, 40..60);
+    assert_eq!(window.f0, whispered.f0[40..60]);
+    assert_eq!(window.aperiodicity, whispered.aperiodicity[40..60]);
+
+    let render = |p: &world_sys::WorldParams| -> Vec<f64> {
+        let out =
+            voiceforge::dsp::world::synthesize(p, sample_rate, None).expect("synthesis failed");
+        // The middle half, clear of the onset and release.
+        let n = out.samples.len();
+        out.samples[n / 4..3 * n / 4]
+            .iter()
+            .map(|&s| f64::from(s))
+            .collect()
+    };
+    let voiced = render(&params);
+    let noise = render(&whispered);
+    assert!(noise.iter().all(|s| s.is_finite()));
+    assert!(
+        noise.iter().map(|s| s * s).sum::<f64>() > 0.0,
+        "whisper is silent"
+    );
+
+    let period = (f64::from(sample_rate) / 440.0).round() as usize;
+    let (voiced_r, noise_r) = (
+        autocorrelation(&voiced, period),
+        autocorrelation(&noise, period),
+    );
+    assert!(voiced_r > 0.5, "voiced autocorrelation {voiced_r}");
+    assert!(noise_r.abs() < 0.2, "whisper autocorrelation {noise_r}");
+
+    let (voiced_c, noise_c) = (
+        spectral_centroid(&voiced, sample_rate),
+        spectral_centroid(&noise, sample_rate),
+    );
+    assert!(
+        noise_c / voiced_c < 2.0 && voiced_c / noise_c < 2.0,
+        "centroid {voiced_c} vs {noise_c} Hz"
+    );
+}
+
+#[test]
+fn test_smoothing_removes_a_single_octave_glitch() {
+    let mut params = synthetic_params();
+    params.f0 = vec![200.0; 20];
+    params.f0[8] = 400.0;
+    params.f0[12..14].fill(0.0);
+    let glitched = params.f0.clone();
+
+    let mut smoothed = params.clone();
+    modifier::smooth_f0(&mut smoothed, 3);
+    let mut expected = vec![200.0; 20];
+    expected[12..14].fill(0.0);
+    assert_eq!(smoothed.f0, expected);
+
+    // Through the slider, before the pitch shift sees the glitch.
+    let values = WorldSliderValues {
+        f0_smoothing_frames: 5.0,
+        pitch_shift: 12.0,
+        ..Default::default()
+    };
+    assert!(!values.is_neutral());
+    let shifted = modifier::apply(&params, &values).f0;
+    assert!(
+        shifted.iter().zip(&expected).all(|(&s, &e)| s == 2.0 * e),
+        "{shifted:?}"
+    );
+
+    // Never across an unvoiced gap: a run's ends stay, so a glitch on the
+    // first frame after the gap survives smoothing ...
+    params.f0[14] = 400.0;
+    let mut edge = params.clone();
+    modifier::smooth_f0(&mut edge, 9);
+    assert_eq!(edge.f0[14], 400.0);
+    assert_eq!(edge.f0[8], 200.0);
+    // ... and the outlier rejection unvoices it instead.
+    modifier::reject_f0_outliers(&mut edge, 6.0);
+    assert_eq!(edge.f0[14], 0.0);
+    assert!(edge
+        .f0
+        .iter()
+        .enumerate()
+        .all(|(i, &f)| i == 14 || f == expected[i]));
+
+    // Windows of 0 or 1 are off.
+    for window in [0, 1] {
+        let mut untouched = params.clone();
+        untouched.f0 = glitched.clone();
+        modifier::smooth_f0(&mut untouched, window);
+        assert_eq!(untouched.f0, glitched);
+    }
+    assert!(WorldSliderValues {
+        f0_smoothing_frames: 1.0,
+        ..Default::default()
+    }
+    .is_neutral());
+}
+
+#[test]
+fn test_retune_snaps_a_glide_to_the_nearest_note() {
+    let frames = 101;
+    let mut params = synthetic_params();
+    params.f0 = (0..frames)
+        .map(|i| 430.0 + 20.0 * i as f64 / (frames - 1) as f64)
+        .collect();
+    params.temporal_positions = (0..frames).map(|i| i as f64 * 0.005).collect();
+    let retune = |params: &world_sys::WorldParams, strength, speed_ms, scale, root| {
+        let values = WorldSliderValues {
+            retune_strength: strength,
+            retune_speed_ms: speed_ms,
+            retune_scale: scale,
+            retune_root: root,
+            ..Default::default()
+        };
+        modifier::apply(params, &values).f0
+    };
+    let cents = |f: f64, reference: f64| 1200.0 * (f / reference).log2();
+
+    // 430–450 Hz stays within 40 cents of A4 (440 Hz).
+    let snapped = retune(&params, 1.0, 0.0, RetuneScale::Chromatic, 0);
+    for &f in &snapped[10..frames - 10] {
+        assert!(cents(f, 440.0).abs() < 1.0, "{f} Hz");
+    }
+
+    // Half strength moves halfway.
+    let half = retune(&params, 0.5, 0.0, RetuneScale::Chromatic, 0);
+    let expected = cents(430.0, 440.0) / 2.0;
+    assert!(
+        (cents(half[0], 440.0) - expected).abs() < 1e-6,
+        "{} Hz",
+        half[0]
+    );
+
+    // 460 Hz is nearest A#4 (466.16 Hz). A major has no A#, so it falls to
+    // A4; G minor has it (as Bb).
+    params.f0 = vec![460.0; 4];
+    let first = |scale, root| retune(&params, 1.0, 0.0, scale, root)[0];
+    assert!(cents(first(RetuneScale::Chromatic, 0), 466.16).abs() < 1.0);
+    assert!(cents(first(RetuneScale::Major, 9), 440.0).abs() < 1.0);
+    assert!(cents(first(RetuneScale::Minor, 7), 466.16).abs() < 1.0);
+
+    // A slow correction starts on the note at the first frame of a run,
+    // then glides after a jump from 450 Hz (A4) to 500 Hz (B4).
+    params.f0 = [vec![450.0; 20], vec![500.0; 40]].concat();
+    params.temporal_positions = (0..60).map(|i| i as f64 * 0.005).collect();
+    let slow = retune(&params, 1.0, 100.0, RetuneScale::Chromatic, 0);
+    assert!(cents(slow[0], 440.0).abs() < 1.0, "{} Hz", slow[0]);
+    assert!(cents(slow[20], 493.88).abs() > 5.0, "{} Hz", slow[20]);
+    assert!(cents(slow[59], 493.88).abs() < 5.0, "{} Hz", slow[59]);
+
+    let neutral = WorldSliderValues {
+        retune_speed_ms: 0.0,
+        retune_scale: RetuneScale::Minor,
+        ..Default::default()
+    };
+    assert!(neutral.is_neutral());
+}
+
+#[test]
+fn test_region_confines_the_pitch_shift() {
+    let mut params = synthetic_params();
+    let frames = 201; // 1 s at 5 ms
+    params.f0 = vec![200.0; frames];
+    params.temporal_positions = (0..frames).map(|i| i as f64 * 0.005).collect();
+    params.spectrogram = vec![params.spectrogram[0].clone(); frames];
+    params.aperiodicity = vec![params.aperiodicity[0].clone(); frames];
+    let values = WorldSliderValues {
+        pitch_shift: 12.0,
+        formant_shift: 3.0,
+        region: Some((0.3, 0.6)),
+        ..Default::default()
+    };
+    let modified = modifier::apply(&params, &values);
+    let everywhere = modifier::apply(
+        &params,
+        &WorldSliderValues {
+            region: None,
+            ..values.clone()
+        },
+    );
+
+    let ramp = modifier::REGION_RAMP_SECS;
+    for (i, &t) in params.temporal_positions.iter().enumerate() {
+        if t <= 0.3 || t >= 0.6 {
+            assert_eq!(modified.f0[i], 200.0, "frame {i} at {t}s");
+            assert_eq!(
+                modified.spectrogram[i], params.spectrogram[i],
+                "frame {i} at {t}s"
+            );
+        } else if t >= 0.3 + ramp && t <= 0.6 - ramp {
+            assert!((modified.f0[i] - 400.0).abs() < 1e-9, "frame {i} at {t}s");
+            assert_eq!(
+                modified.spectrogram[i], everywhere.spectrogram[i],
+                "frame {i} at {t}s"
+            );
+        }
+    }
+    // Smooth edges: the f0 climbs and falls in steps of one ramp frame.
+    let max_step = 200.0 * 0.005 / ramp + 1e-9;
+    for pair in modified.f0.windows(2) {
+        assert!((pair[1] - pair[0]).abs() <= max_step, "{pair:?}");
+    }
+    let start_ramp: Vec<f64> = (60..65).map(|i| modified.f0[i]).collect();
+    assert!(start_ramp.windows(2).all(|w| w[1] > w[0]), "{start_ramp:?}");
+
+    // A window previews the same frames, and speed stretches the result.
+    let window = modifier::apply_window(&params, &values, 55..75);
+    assert_eq!(window.f0, modified.f0[55..75]);
+    assert_eq!(window.spectrogram, modified.spectrogram[55..75]);
+    let slower = modifier::apply(
+        &params,
+        &WorldSliderValues {
+            speed: 0.5,
+            ..values.clone()
+        },
+    );
+    assert_eq!(slower.f0.len(), 2 * frames);
+    assert_eq!(slower.f0[0], 200.0);
+    assert!((slower.f0[2 * 90] - 400.0).abs() < 1e-9);
+
+    assert!(WorldSliderValues {
+        region: Some((0.3, 0.6)),
+        ..Default::default()
+    }
+    .is_neutral());
+    let moved = DirtyStages::between(
+        &values,
+        &WorldSliderValues {
+            region: Some((0.2, 0.6)),
+            ..values.clone()
+        },
+    );
+    assert!(moved.f0 && moved.spectral && !moved.speed);
+}
+
+#[test]
+fn test_voice_presets_scale_towards_neutral_and_do_not_stack() {
+    let presets = [
+        WorldSliderValues::preset_feminize(1.0),
+        WorldSliderValues::preset_masculinize(1.0),
+        WorldSliderValues::preset_child(1.0),
+        WorldSliderValues::preset_elderly(1.0),
+    ];
+    for (values, preset) in presets.iter().zip(VoicePreset::ALL) {
+        assert!(!values.is_neutral(), "{preset:?}");
+        assert_eq!(*values, WorldSliderValues::preset(preset, 1.0));
+        assert_eq!(
+            WorldSliderValues::preset(preset, 0.0),
+            WorldSliderValues::default(),
+            "{preset:?}"
+        );
+        let half = WorldSliderValues::preset(preset, 0.5);
+        assert_eq!(half.pitch_shift, values.pitch_shift / 2.0);
+        assert_eq!(half.spectral_tilt, values.spectral_tilt / 2.0);
+        // Only the four preset sliders move.
+        let speed = WorldSliderValues {
+            speed: 1.5,
+            ..Default::default()
+        }
+        .with_preset(preset, 1.0);
+        assert_eq!(
+            speed,
+            WorldSliderValues {
+                speed: 1.5,
+                ..values.clone()
+            }
+        );
+    }
+
+    // Presets set their sliders rather than adding to them, so masculinize
+    // after feminize is plain masculinize, not neutral; and the two aren't
+    // mirror images (feminize's breath isn't taken back).
+    let feminine = WorldSliderValues::preset_feminize(0.8);
+    let both = feminine.clone().with_preset(VoicePreset::Masculinize, 0.8);
+    assert_eq!(both, WorldSliderValues::preset_masculinize(0.8));
+    assert!(!both.is_neutral());
+    assert_ne!(feminine.breathiness, -both.breathiness);
+}
+
+#[test]
+fn test_envelope_smoothing_removes_a_spike_but_keeps_formants() {
+    let fft_size = 1024;
+    let width = fft_size / 2 + 1;
+    let formants = [(40.0, 12.0), (110.0, 8.0), (200.0, 15.0)];
+    let db: Vec<f64> = (0..width)
+        .map(|k| {
+            let k = k as f64;
+            let bumps: f64 = formants
+                .iter()
+                .map(|&(centre, width)| 20.0 * (-((k - centre) / width).powi(2)).exp())
+                .sum();
+            bumps - 0.05 * k
+        })
+        .collect();
+    let mut row: Vec<f64> = db.iter().map(|d| 10f64.powf(d / 10.0)).collect();
+    let spike = 300;
+    row[spike] *= 100.0; // +20 dB in one bin
+    let to_db = |v: f64| 10.0 * v.log10();
+
+    let mut smoothed = row.clone();
+    modifier::smooth_envelope_row(&mut smoothed, fft_size, 0.5);
+    let prominence =
+        |r: &[f64]| to_db(r[spike]) - (to_db(r[spike - 3]) + to_db(r[spike + 3])) / 2.0;
+    assert!(prominence(&row) > 19.0);
+    assert!(
+        prominence(&smoothed) < 5.0,
+        "spike still {:.1} dB",
+        prominence(&smoothed)
+    );
+    for &(centre, _) in &formants {
+        let k = centre as usize;
+        let change = to_db(smoothed[k]) - to_db(row[k]);
+        assert!(
+            change.abs() < 1.0,
+            "formant at bin {k} moved {change:.2} dB"
+        );
+    }
+
+    // The stage does the same to every row, and 0 is off.
+    let mut params = synthetic_params();
+    params.spectrogram = vec![row.clone(); params.f0.len()];
+    let values = WorldSliderValues {
+        spectral_smoothing: 0.5,
+        ..Default::default()
+    };
+    assert!(!values.is_neutral());
+    let out = modifier::apply(&params, &values);
+    assert!(out.spectrogram.iter().all(|r| *r == smoothed));
+    let mut untouched = row.clone();
+    modifier::smooth_envelope_row(&mut untouched, fft_size, 0.0);
+    assert_eq!(untouched, row);
+}
+
+#[test]
+fn test_breathiness_only_raises_bins_above_the_crossover() {
+    // 16 kHz with fft 1024: 15.625 Hz per bin, aperiodicity 0.1 everywhere.
+    let params = synthetic_params();
+    let bin_hz = f64::from(params.sample_rate) / params.fft_size as f64;
+    let values = WorldSliderValues {
+        breathiness: 0.5,
+        breathiness_crossover_hz: 4000.0,
+        ..Default::default()
+    };
+    let out = modifier::apply(&params, &values);
+    for (row, original) in out.aperiodicity.iter().zip(&params.aperiodicity) {
+        for (k, (&ap, &before)) in row.iter().zip(original).enumerate() {
+            let freq = k as f64 * bin_hz;
+            if freq <= 500.0 {
+                assert_eq!(ap, before, "bin {k} ({freq} Hz) changed");
+            } else if freq > 6000.0 {
+                assert!(
+                    (ap - (before + 0.5)).abs() < 1e-12,
+                    "bin {k} ({freq} Hz): {ap}"
+                );
+            } else {
+                assert!(ap >= before && ap <= before + 0.5 + 1e-12);
+            }
+        }
+    }
+    assert!((modifier::breathiness_weight(4000.0, 4000.0) - 0.5).abs() < 1e-12);
+
+    // No crossover, or no sample rate to place it at: every bin.
+    for params in [
+        params.clone(),
+        world_sys::WorldParams {
+            sample_rate: 0,
+            ..params.clone()
+        },
+    ] {
+        let values = WorldSliderValues {
+            breathiness_crossover_hz: if params.sample_rate == 0 { 4000.0 } else { 0.0 },
+            ..values.clone()
+        };
+        let out = modifier::apply(&params, &values);
+        assert!(out
+            .aperiodicity
+            .iter()
+            .flatten()
+            .all(|&ap| (ap - 0.6).abs() < 1e-12));
+    }
+}
+
+/// Energy in dB of the bins within a sixth of an octave of `center_hz`.
+fn band_energy_db(row: &[f64], bin_hz: f64, center_hz: f64) -> f64 {
+    let (low, high) = (
+        center_hz * 2f64.powf(-1.0 / 12.0),
+        center_hz * 2f64.powf(1.0 / 12.0),
+    );
+    let energy: f64 = row
+        .iter()
+        .enumerate()
+        .filter(|&(k, _)| (low..=high).contains(&(k as f64 * bin_hz)))
+        .map(|(_, &p)| p)
+        .sum();
+    10.0 * energy.log10()
+}
+
+#[test]
+fn test_presence_boosts_its_band_and_leaves_two_octaves_away_alone() {
+    // At 44.1 kHz, so two octaves above the centre is still below Nyquist.
+    let params = world_sys::WorldParams {
+        sample_rate: 44100,
+        ..synthetic_params()
+    };
+    let bin_hz = f64::from(params.sample_rate) / params.fft_size as f64;
+    let center = 3000.0;
+    for gain in [9.0, -6.0] {
+        let mut boosted = params.clone();
+        modifier::apply_presence(&mut boosted, gain, center, 1.6);
+        for (row, original) in boosted.spectrogram.iter().zip(&params.spectrogram) {
+            let change =
+                |hz| band_energy_db(row, bin_hz, hz) - band_energy_db(original, bin_hz, hz);
+            assert!(
+                (change(center) - gain).abs() < 0.5,
+                "{gain} dB: centre moved {:.2}",
+                change(center)
+            );
+            for far in [center / 4.0, center * 4.0] {
+                assert!(
+                    change(far).abs() < 0.5,
+                    "{gain} dB: {far} Hz moved {:.2}",
+                    change(far)
+                );
+            }
+        }
+
+        // The slider runs the same curve.
+        let values = WorldSliderValues {
+            presence_db: gain,
+            presence_center_hz: center,
+            presence_width_oct: 1.6,
+            ..Default::default()
+        };
+        assert!(!values.is_neutral());
+        assert_eq!(
+            modifier::apply(&params, &values).spectrogram,
+            boosted.spectrogram
+        );
+        let preview = modifier::preview_envelope_at_rate(
+            &params.spectrogram[3],
+            params.fft_size,
+            params.sample_rate,
+            &values,
+        );
+        assert_eq!(preview, boosted.spectrogram[3]);
+    }
+}
+
+#[test]
+fn test_nasality_raises_the_nasal_peak_and_lowers_the_dip() {
+    let params = world_sys::WorldParams {
+        sample_rate: 44100,
+        ..synthetic_params()
+    };
+    let bin_hz = f64::from(params.sample_rate) / params.fft_size as f64;
+    for amount in [modifier::NASALITY_MAX_DB, -4.0] {
+        let mut nasal = params.clone();
+        modifier::apply_nasality(&mut nasal, amount);
+        for (row, original) in nasal.spectrogram.iter().zip(&params.spectrogram) {
+            let change =
+                |hz| band_energy_db(row, bin_hz, hz) - band_energy_db(original, bin_hz, hz);
+            assert!(
+                (change(350.0) - amount).abs() < 0.5,
+                "{amount} dB: 350 Hz moved {:.2}",
+                change(350.0)
+            );
+            assert!(
+                (change(1000.0) + amount).abs() < 0.5,
+                "{amount} dB: 1 kHz moved {:.2}",
+                change(1000.0)
+            );
+        }
+
+        let values = WorldSliderValues {
+            nasality_db: amount,
+            ..Default::default()
+        };
+        assert!(!values.is_neutral());
+        assert_eq!(
+            modifier::apply(&params, &values).spectrogram,
+            nasal.spectrogram
+        );
+    }
+}
+
+#[test]
+fn test_log_formant_warp_moves_peaks_by_whole_octaves() {
+    let (sample_rate, fft_size) = (44100.0, 2048);
+    let bin_hz = sample_rate / fft_size as f64;
+    let bins = fft_size / 2 + 1;
+    // Narrow bells in log frequency on a -60 dB floor.
+    let envelope = |peaks: &[f64]| -> Vec<f64> {
+        (0..bins)
+            .map(|k| {
+                let hz = (k as f64 * bin_hz).max(1.0);
+                1e-6 + peaks
+                    .iter()
+                    .map(|&p| (-0.5 * ((hz / p).log2() / 0.08).powi(2)).exp())
+                    .sum::<f64>()
+            })
+            .collect()
+    };
+    let peak_hz = |row: &[f64], low: f64, high: f64| {
+        let (k, _) = row
+            .iter()
+            .enumerate()
+            .filter(|&(k, _)| (low..high).contains(&(k as f64 * bin_hz)))
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap();
+        k as f64 * bin_hz
+    };
+
+    let mut up = envelope(&[1000.0, 4000.0]);
+    let dc = up[0];
+    modifier::formant_log_warp_row(&mut up, fft_size, 12.0);
+    assert_eq!(up[0], dc, "DC is kept");
+    for (from, to) in [(1000.0, 2000.0), (4000.0, 8000.0)] {
+        let found = peak_hz(&up, to / 1.4, to * 1.4);
+        assert!(
+            (found - to).abs() <= bin_hz,
+            "{from} Hz landed at {found:.0} Hz, not {to}"
+        );
+    }
+
+    // Down an octave, each bin averages the two it squeezes together.
+    let mut down = envelope(&[4000.0]);
+    modifier::formant_log_warp_row(&mut down, fft_size, -12.0);
+    let found = peak_hz(&down, 1400.0, 2800.0);
+    assert!(
+        (found - 2000.0).abs() <= bin_hz,
+        "4 kHz landed at {found:.0} Hz"
+    );
+
+    // The slider's log mode runs the same warp.
+    let mut shifted = envelope(&[1000.0]);
+    let mut expected = shifted.clone();
+    modifier::formant_shift_row_with(&mut shifted, fft_size, 12.0, FormantWarp::Log);
+    modifier::formant_log_warp_row(&mut expected, fft_size, 12.0);
+    let gain = shifted[100] / expected[100];
+    assert!(shifted
+        .iter()
+        .zip(&expected)
+        .all(|(s, e)| (s / e - gain).abs() < 1e-9));
+}
+
+#[test]
+fn test_formant_shift_keeps_the_loudness() {
+    let (params, sample_rate) = make_test_params();
+    let rms = |p: &world_sys::WorldParams| {
+        let out = world_sys::synthesize(p, sample_rate as i32).unwrap();
+        (out.iter().map(|x| x * x).sum::<f64>() / out.len() as f64).sqrt()
+    };
+    let reference = rms(&params);
+    let shifted = |keep_energy| {
+        let values = WorldSliderValues {
+            formant_shift: 5.0,
+            formant_keep_energy: keep_energy,
+            ..Default::default()
+        };
+        rms(&modifier::apply(&params, &values)) / reference
+    };
+    let kept = shifted(true);
+    let drifted = shifted(false);
+    assert!((kept - 1.0).abs() < 0.05, "normalized RMS ratio {kept:.3}");
+    assert!(
+        (drifted - 1.0).abs() > 0.05,
+        "plain warp RMS ratio {drifted:.3}"
+    );
+}
+
+/// [`synthetic_params`]' first frame repeated: `frames` frames at 200 Hz.
+fn constant_pitch_params(frames: usize) -> world_sys::WorldParams {
+    let short = synthetic_params();
+    world_sys::WorldParams {
+        f0: vec![200.0; frames],
+        temporal_positions: (0..frames).map(|f| f as f64 * 0.005).collect(),
+        spectrogram: vec![short.spectrogram[0].clone(); frames],
+        aperiodicity: vec![short.aperiodicity[0].clone(); frames],
+        ..short
+    }
+}
+
+#[test]
+fn test_f0_offsets_ramp_a_constant_pitch_up_an_octave_and_back() {
+    let params = constant_pitch_params(201);
+    let values = WorldSliderValues {
+        f0_offsets: vec![(0.0, 0.0), (0.5, 12.0), (1.0, 0.0)],
+        ..Default::default()
+    };
+    assert!(!values.is_neutral());
+    let out = modifier::apply(&params, &values);
+    let peak = out.f0.iter().copied().fold(0.0, f64::max);
+    assert!(
+        (out.f0[100] - 400.0).abs() < 1e-9 && peak == out.f0[100],
+        "{}",
+        out.f0[100]
+    );
+    assert!((out.f0[0] - 200.0).abs() < 1e-9 && (out.f0[200] - 200.0).abs() < 1e-9);
+    // Linear in semitones either side of the peak.
+    for (f, &hz) in out.f0.iter().enumerate() {
+        let t = f as f64 * 0.005;
+        let expected = if t <= 0.5 { 24.0 * t } else { 24.0 * (1.0 - t) };
+        assert!(
+            (12.0 * (hz / 200.0).log2() - expected).abs() < 1e-9,
+            "frame {f}: {hz}"
+        );
+    }
+    let mut direct = params.clone();
+    modifier::apply_f0_offsets(&mut direct, &values.f0_offsets);
+    assert_eq!(direct.f0, out.f0);
+
+    // Unvoiced frames stay unvoiced; the curve holds its ends.
+    let mut gappy = params.clone();
+    gappy.f0[100] = 0.0;
+    assert_eq!(modifier::apply(&gappy, &values).f0[100], 0.0);
+    assert_eq!(modifier::f0_offset_at(&[(1.0, 3.0), (2.0, 5.0)], 0.0), 3.0);
+    assert_eq!(modifier::f0_offset_at(&[(1.0, 3.0), (2.0, 5.0)], 9.0), 5.0);
+    assert_eq!(modifier::f0_offset_at(&[], 1.0), 0.0);
+}
+
+#[test]
+fn test_automated_pitch_shift_glides_a_constant_pitch_up_an_octave() {
+    let params = constant_pitch_params(201);
+    let values = WorldSliderValues {
+        // The lane replaces the slider.
+        pitch_shift: 5.0,
+        automation: Automation {
+            pitch_shift: vec![(0.0, 0.0), (1.0, 12.0)],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(!values.is_neutral());
+    let out = modifier::apply(&params, &values);
+    assert!((out.f0[0] - 200.0).abs() < 1e-9, "{}", out.f0[0]);
+    assert!((out.f0[200] - 400.0).abs() < 1e-9, "{}", out.f0[200]);
+    assert!(
+        out.f0.windows(2).all(|w| w[1] > w[0]),
+        "not rising: {:?}",
+        out.f0
+    );
+
+    let flat = WorldSliderValues {
+        automation: Automation {
+            pitch_shift: vec![(0.0, 0.0), (1.0, 0.0)],
+            ..Default::default()
+        },
+        ..values.clone()
+    };
+    assert!(flat.is_neutral(), "a lane at 0 overrides the slider");
+    let dirty = DirtyStages::between(&flat, &values);
+    assert!(dirty.f0 && !dirty.speed && !dirty.spectral, "{dirty:?}");
+}
+
+#[test]
+fn test_automated_breathiness_and_tilt_follow_source_time_at_any_speed() {
+    let params = constant_pitch_params(201);
+    let values = WorldSliderValues {
+        automation: Automation {
+            breathiness: vec![(0.0, 0.0), (1.0, 1.0)],
+            spectral_tilt: vec![(0.0, 0.0), (1.0, -6.0)],
+            ..Default::default()
+        },
+        breathiness_crossover_hz: 0.0,
+        ..Default::default()
+    };
+    let dirty = DirtyStages::between(&WorldSliderValues::default(), &values);
+    assert!(!dirty.f0 && !dirty.speed && dirty.spectral, "{dirty:?}");
+
+    let mean = |row: &[f64]| row.iter().sum::<f64>() / row.len() as f64;
+    for speed in [1.0, 2.0] {
+        let out = modifier::apply(
+            &params,
+            &WorldSliderValues {
+                speed,
+                ..values.clone()
+            },
+        );
+        let last = out.f0.len() - 1;
+        // The first frame is untouched, the last fully breathy and tilted.
+        assert_eq!(out.aperiodicity[0], params.aperiodicity[0], "speed {speed}");
+        assert_eq!(out.spectrogram[0], params.spectrogram[0], "speed {speed}");
+        assert!(
+            out.aperiodicity[last].iter().all(|&a| a == 1.0),
+            "speed {speed}"
+        );
+        let ap: Vec<f64> = out.aperiodicity.iter().map(|row| mean(row)).collect();
+        assert!(ap.windows(2).all(|w| w[1] >= w[0]), "speed {speed}: {ap:?}");
+        let top = params.spectrogram[0].len() - 1;
+        let highs: Vec<f64> = out.spectrogram.iter().map(|row| row[top]).collect();
+        assert!(
+            highs.windows(2).all(|w| w[1] <= w[0]) && highs[last] < highs[0],
+            "speed {speed}"
+        );
+    }
+}
+
+#[test]
+fn test_crossfade_glides_from_one_preset_to_the_other() {
+    let params = constant_pitch_params(201);
+    let normal = WorldSliderValues::default();
+    let monster = WorldSliderValues {
+        pitch_shift: -12.0,
+        formant_shift: -4.0,
+        breathiness: 0.4,
+        ..Default::default()
+    };
+    let (a, b) = (
+        modifier::apply(&params, &normal),
+        modifier::apply(&params, &monster),
+    );
+    let out = modifier::apply_crossfade(&params, &normal, &monster, (0.25, 0.75));
+    for (i, &t) in params.temporal_positions.iter().enumerate() {
+        let expected = if t <= 0.25 {
+            &a
+        } else if t >= 0.75 {
+            &b
+        } else {
+            continue;
+        };
+        assert_eq!(out.f0[i], expected.f0[i], "frame {i}");
+        assert_eq!(out.spectrogram[i], expected.spectrogram[i], "frame {i}");
+        assert_eq!(out.aperiodicity[i], expected.aperiodicity[i], "frame {i}");
+    }
+    // Halfway through, the pitch shift is the average of the two: -6 st.
+    let halfway = 200.0 * 2.0_f64.powf(-6.0 / 12.0);
+    assert!((out.f0[100] - halfway).abs() < 1e-9, "{}", out.f0[100]);
+    assert!(out.f0[50..=150].windows(2).all(|w| w[1] < w[0]));
+
+    assert_eq!(modifier::crossfade_weight(0.5, (0.25, 0.75)), 0.5);
+    assert_eq!(modifier::crossfade_weight(0.2, (0.3, 0.3)), 0.0);
+    assert_eq!(modifier::crossfade_weight(0.3, (0.3, 0.3)), 1.0);
+}
+
+#[test]
+fn test_apply_into_a_reused_scratch_matches_apply() {
+    let (params, _) = make_test_params();
+    let combos = [
+        WorldSliderValues::default(),
+        WorldSliderValues {
+            pitch_shift: 3.0,
+            ..Default::default()
+        },
+        WorldSliderValues {
+            breathiness: 0.3,
+            spectral_tilt: -2.0,
+            nasality_db: 3.0,
+            ..Default::default()
+        },
+        WorldSliderValues {
+            speed: 1.5,
+            formant_shift: 2.0,
+            ..Default::default()
+        },
+        WorldSliderValues {
+            pitch_shift: -4.0,
+            region: Some((0.2, 0.6)),
+            ..Default::default()
+        },
+        WorldSliderValues {
+            denoise_db: 20.0,
+            whisper: true,
+            ..Default::default()
+        },
+        WorldSliderValues {
+            pitch_shift: 7.0,
+            ..Default::default()
+        },
+    ];
+    // Each render lands on whatever the previous one left in the scratch.
+    let mut scratch = world_sys::WorldParams::default();
+    for values in combos.iter().chain(combos.iter().rev()) {
+        let expected = modifier::apply(&params, values);
+        modifier::apply_into(&params, values, &mut scratch);
+        let bits = |rows: &[Vec<f64>]| {
+            rows.iter()
+                .flatten()
+                .map(|v| v.to_bits())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(scratch.f0, expected.f0, "{values:?}");
+        assert_eq!(
+            scratch.temporal_positions, expected.temporal_positions,
+            "{values:?}"
+        );
+        assert_eq!(
+            bits(&scratch.spectrogram),
+            bits(&expected.spectrogram),
+            "{values:?}"
+        );
+        assert_eq!(
+            bits(&scratch.aperiodicity),
+            bits(&expected.aperiodicity),
+            "{values:?}"
+        );
+        assert_eq!(
+            scratch.spectrogram.len(),
+            expected.spectrogram.len(),
+            "{values:?}"
+        );
+        assert_eq!(scratch.voicing, expected.voicing, "{values:?}");
+        assert_eq!(
+            (scratch.fft_size, scratch.frame_period, scratch.sample_rate),
+            (
+                expected.fft_size,
+                expected.frame_period,
+                expected.sample_rate
+            )
+        );
+        assert!(scratch.f0_candidates.is_none());
+    }
+}
+
+#[test]
+fn test_nudges_accumulate_on_the_offset_curve() {
+    let mut offsets = Vec::new();
+    modifier::nudge_f0_offsets(&mut offsets, 1.0, 2.0, 1.0);
+    modifier::nudge_f0_offsets(&mut offsets, 1.5, 3.0, 2.0);
+    let at = |t| modifier::f0_offset_at(&offsets, t);
+    assert_eq!(
+        (at(0.5), at(1.2), at(1.7), at(2.5), at(4.0)),
+        (0.0, 1.0, 3.0, 2.0, 0.0)
+    );
+    assert!(offsets.windows(2).all(|w| w[0].0 < w[1].0));
+    // Half way up the ramp into the first nudge.
+    assert!((at(1.0 - modifier::REGION_RAMP_SECS / 2.0) - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn test_median_range_center_keeps_a_bimodal_contour_on_both_sides() {
+    // A long low stretch around 100 Hz, a gap, then a shorter one around
+    // 300 Hz: the mean (~180 Hz) sits between them, the median in the low one.
+    let low: Vec<f64> = (0..120).map(|f| 100.0 + (f % 5) as f64).collect();
+    let high: Vec<f64> = (0..60).map(|f| 300.0 + (f % 5) as f64).collect();
+    let mut params = synthetic_params();
+    params.f0 = low.iter().chain(&[0.0; 10]).chain(&high).copied().collect();
+    let frames = params.f0.len();
+    params.temporal_positions = (0..frames).map(|f| f as f64 * 0.005).collect();
+    params.spectrogram = vec![params.spectrogram[0].clone(); frames];
+    params.aperiodicity = vec![params.aperiodicity[0].clone(); frames];
+    let expanded = |center| {
+        let values = WorldSliderValues {
+            pitch_range: 2.5,
+            pitch_range_center: center,
+            ..Default::default()
+        };
+        modifier::apply(&params, &values).f0
+    };
+
+    // Around the mean, the low stretch is pushed subsonic, partly to 0 and
+    // so unvoiced.
+    let mean = expanded(RangeCenter::Mean);
+    assert!(mean[..120].iter().all(|&f| f < 20.0), "{:?}", &mean[..5]);
+    assert!(mean[..120].contains(&0.0));
+
+    // Around the median (in the low stretch), the low stretch keeps its
+    // pitch and voicing and the high one moves further up.
+    let median = expanded(RangeCenter::Median);
+    let center = 103.0;
+    for (f, (&out, &before)) in median.iter().zip(&params.f0).enumerate() {
+        if before == 0.0 {
+            assert_eq!(out, 0.0);
+        } else {
+            assert!(out > 0.0, "frame {f} lost its voicing");
+            assert_eq!(
+                out > center,
+                before > center,
+                "frame {f}: {before} -> {out}"
+            );
+            assert!(
+                (out - center - 2.5 * (before - center)).abs() < 1e-9,
+                "frame {f}"
+            );
+        }
+    }
+
+    // Per segment, each stretch expands around its own median.
+    let per_segment = expanded(RangeCenter::PerSegment);
+    for (span, center) in [(0..120, 102.0), (130..frames, 302.0)] {
+        for (&out, &before) in per_segment[span.clone()].iter().zip(&params.f0[span]) {
+            assert!(
+                (out - center - 2.5 * (before - center)).abs() < 1e-9,
+                "{before} -> {out}"
+            );
+        }
+    }
+    assert_eq!(RangeCenter::PerSegment.next(), RangeCenter::Mean);
+}
+
+#[test]
+fn test_slowing_down_never_interpolates_across_a_voicing_boundary() {
+    let short = synthetic_params();
+    let frames = 5;
+    let params = world_sys::WorldParams {
+        f0: vec![200.0, 200.0, 0.0, 0.0, 200.0],
+        temporal_positions: (0..frames).map(|f| 0.1 + f as f64 * 0.005).collect(),
+        spectrogram: short.spectrogram[..frames].to_vec(),
+        aperiodicity: (0..frames)
+            .map(|f| vec![f as f64 * 0.2; short.aperiodicity[0].len()])
+            .collect(),
+        ..short
+    };
+    let values = WorldSliderValues {
+        speed: 0.5,
+        ..Default::default()
+    };
+    let stretched = modifier::apply(&params, &values);
+    assert_eq!(stretched.f0.len(), 10);
+    assert!(
+        stretched.f0.iter().all(|&f| f == 0.0 || f == 200.0),
+        "{:?}",
+        stretched.f0
+    );
+    assert!(stretched.f0.contains(&0.0));
+    // The envelope and aperiodicity still interpolate.
+    assert!(stretched
+        .aperiodicity
+        .iter()
+        .any(|row| (row[0] / 0.2).fract().abs() > 1e-9));
+    // Same origin, one frame period apart.
+    assert_eq!(stretched.temporal_positions[0], 0.1);
+    assert!(stretched
+        .temporal_positions
+        .windows(2)
+        .all(|t| (t[1] - t[0] - 0.005).abs() < 1e-12));
+
+    // Within a voiced run f0 still glides.
+    let glide = world_sys::WorldParams {
+        f0: vec![100.0, 200.0, 300.0, 400.0, 500.0],
+        ..params
+    };
+    let stretched = modifier::apply(&glide, &values);
+    assert!(stretched.f0.windows(2).all(|w| w[1] > w[0]));
+    assert!(stretched.f0.iter().any(|&f| f % 100.0 != 0.0));
+}
+
+#[test]
+fn test_spectral_tilt_pivots_on_its_pivot_frequency() {
+    // 16 kHz with fft 1024: 1 kHz is bin 64, 2 kHz bin 128, 500 Hz bin 32.
+    let params = synthetic_params();
+    let values = WorldSliderValues {
+        spectral_tilt: 6.0,
+        ..Default::default()
+    };
+    assert_eq!(values.tilt_pivot_hz, 1000.0);
+    let out = modifier::apply(&params, &values);
+    let change_db = |k: usize, row: usize| {
+        10.0 * (out.spectrogram[row][k] / params.spectrogram[row][k]).log10()
+    };
+    for row in 0..params.f0.len() {
+        assert!(change_db(64, row).abs() < 1e-9);
+        assert!((change_db(128, row) - 6.0).abs() < 1e-9);
+        assert!((change_db(32, row) + 6.0).abs() < 1e-9);
+    }
+
+    // Without a sample rate it pivots on bin 1, as before.
+    let unknown = world_sys::WorldParams {
+        sample_rate: 0,
+        ..params.clone()
+    };
+    let out = modifier::apply(&unknown, &values);
+    let mut expected = params.spectrogram[0].clone();
+    modifier::spectral_tilt_row(&mut expected, params.fft_size, 6.0);
+    assert_eq!(out.spectrogram[0], expected);
+    assert_eq!(out.spectrogram[0][1], params.spectrogram[0][1]);
+}
+
+#[test]
+fn test_growl_is_deterministic_and_roughens_without_moving_the_mean() {
+    let params = constant_pitch_params(400);
+    let growled = |depth, rate| {
+        let values = WorldSliderValues {
+            growl_depth: depth,
+            growl_rate: rate,
+            ..Default::default()
+        };
+        modifier::apply(&params, &values)
+    };
+    let stats = |f0: &[f64]| {
+        let mean = f0.iter().sum::<f64>() / f0.len() as f64;
+        (
+            mean,
+            f0.iter().map(|f| (f - mean).powi(2)).sum::<f64>() / f0.len() as f64,
+        )
+    };
+
+    for rate in [GrowlRate::Subharmonic, GrowlRate::Fixed(60.0)] {
+        let (once, again) = (growled(0.7, rate), growled(0.7, rate));
+        assert_eq!((once.f0, once.aperiodicity), (again.f0, again.aperiodicity));
+        let mut previous = 0.0;
+        for depth in [0.25, 0.5, 1.0] {
+            let (mean, variance) = stats(&growled(depth, rate).f0);
+            assert!(
+                (mean / 200.0 - 1.0).abs() < 0.01,
+                "{rate:?} depth {depth}: mean {mean}"
+            );
+            assert!(
+                variance > previous,
+                "{rate:?} depth {depth}: variance {variance} <= {previous}"
+            );
+            previous = variance;
+        }
+    }
+
+    // A little aperiodicity in 1–3 kHz (bins 64..=192 at 16 kHz), nothing else.
+    let out = growled(1.0, GrowlRate::Subharmonic);
+    let mut direct = params.clone();
+    modifier::apply_growl(&mut direct, 1.0, GrowlRate::Subharmonic);
+    assert_eq!(
+        (&direct.f0, &direct.aperiodicity),
+        (&out.f0, &out.aperiodicity)
+    );
+    for (k, (&after, &before)) in out.aperiodicity[0]
+        .iter()
+        .zip(&params.aperiodicity[0])
+        .enumerate()
+    {
+        let raised = if (64..=192).contains(&k) {
+            modifier::GROWL_APERIODICITY
+        } else {
+            0.0
+        };
+        assert!((after - before - raised).abs() < 1e-12, "bin {k}");
+    }
+}
+
+#[test]
+fn test_humanize_drifts_slowly_by_the_slider_and_repeats_per_seed() {
+    // 10 s at 200 Hz with an unvoiced gap every second.
+    let mut params = constant_pitch_params(2000);
+    for (i, f) in params.f0.iter_mut().enumerate() {
+        if i % 200 >= 180 {
+            *f = 0.0;
+        }
+    }
+    let humanized = |cents, seed| {
+        let values = WorldSliderValues {
+            humanize_cents: cents,
+            humanize_seed: seed,
+            ..Default::default()
+        };
+        assert_eq!(values.is_neutral(), cents == 0.0);
+        modifier::apply(&params, &values).f0
+    };
+    let deviation_cents = |f0: &[f64]| -> Vec<f64> {
+        f0.iter()
+            .zip(&params.f0)
+            .filter(|&(_, &before)| before > 0.0)
+            .map(|(&after, &before)| 1200.0 * (after / before).log2())
+            .collect()
+    };
+    let std_dev = |cents: &[f64]| {
+        let mean = cents.iter().sum::<f64>() / cents.len() as f64;
+        (cents.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / cents.len() as f64).sqrt()
+    };
+
+    assert_eq!(humanized(30.0, 5), humanized(30.0, 5));
+    assert_ne!(humanized(30.0, 5), humanized(30.0, 6));
+    assert_eq!(humanized(0.0, 5), params.f0);
+
+    let base = std_dev(&deviation_cents(&humanized(10.0, 5)));
+    assert!(base > 2.0, "10 ct drifts by only {base} ct");
+    for cents in [20.0, 40.0, 50.0] {
+        let f0 = humanized(cents, 5);
+        for (i, (&after, &before)) in f0.iter().zip(&params.f0).enumerate() {
+            if before == 0.0 {
+                assert_eq!(after, 0.0, "unvoiced frame {i}");
+            }
+        }
+        let deviation = deviation_cents(&f0);
+        let ratio = std_dev(&deviation) / base;
+        assert!(
+            (ratio - cents / 10.0).abs() < 1e-6,
+            "{cents} ct: std-dev ratio {ratio}"
+        );
+        assert!(deviation.iter().all(|d| d.abs() <= cents + 1e-9));
+        // A drift, not a jitter: well under a cent per 5 ms frame at 50 ct.
+        let steepest = (1..f0.len())
+            .filter(|&i| params.f0[i - 1] > 0.0 && params.f0[i] > 0.0)
+            .map(|i| 1200.0 * (f0[i] / f0[i - 1]).log2().abs())
+            .fold(0.0, f64::max);
+        assert!(
+            steepest < 0.05 * cents,
+            "{cents} ct: {steepest} ct between frames"
+        );
+    }
+}
+
+#[test]
+fn test_denoise_lowers_the_hiss_between_harmonics() {
+    use voiceforge::dsp::generate;
+    use voiceforge::dsp::spectrum::compute_spectrum;
+
+    let sample_rate = 16000;
+    let tone = generate::sine(440.0, 0.5, sample_rate, 1.0);
+    let hiss = generate::white_noise(0.5 * 10f32.powf(-30.0 / 20.0), sample_rate, 1.0, 7);
+    let audio: Vec<f64> = tone
+        .samples
+        .iter()
+        .zip(&hiss.samples)
+        .map(|(&a, &b)| f64::from(a + b))
+        .collect();
+    let params = world_sys::analyze(&audio, sample_rate as i32);
+
+    // Mean level of the bins at least 100 Hz from any harmonic of 440 Hz.
+    const FFT_SIZE: usize = 2048;
+    let noise_floor_db = |values: &WorldSliderValues| {
+        let out =
+            world_sys::synthesize(&modifier::apply(&params, values), sample_rate as i32).unwrap();
+        let samples: Vec<f32> = out.iter().map(|&s| s as f32).collect();
+        let mid = samples.len() / 2 - FFT_SIZE / 2;
+        let spectrum = compute_spectrum(&samples[mid..mid + FFT_SIZE], FFT_SIZE);
+        let bin_hz = f64::from(sample_rate) / FFT_SIZE as f64;
+        let between: Vec<f32> = spectrum
+            .iter()
+            .enumerate()
+            .filter(|&(k, _)| {
+                let hz = k as f64 * bin_hz;
+                let nearest = (hz / 440.0).round() * 440.0;
+                hz > 200.0 && (hz - nearest).abs() > 100.0
+            })
+            .map(|(_, &db)| db)
+            .collect();
+        between.iter().sum::<f32>() / between.len() as f32
+    };
+    let plain = noise_floor_db(&WorldSliderValues::default());
+    let denoised = noise_floor_db(&WorldSliderValues {
+        denoise_db: 30.0,
+        ..Default::default()
+    });
+    assert!(
+        plain - denoised > 3.0,
+        "noise floor {plain:.1} -> {denoised:.1} dB"
+    );
+}
@@ -0,0 +1,245 @@
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::Terminal;
+use voiceforge::app::{AppMode, AppState, F0Correction, PunchRegion, FILE_PICKER_ROWS};
+use voiceforge::audio::edit::LengthMode;
+use voiceforge::dsp::effects::EqLayout;
+use voiceforge::ui::layout::{self, centered_popup, panel_rects, MIN_HEIGHT, MIN_WIDTH};
+use voiceforge::ui::{
+    chain_order, eq_panel, f0_correct, file_picker, help, inspector, punch_in, save_dialog,
+    seek_prompt, stats, tour,
+};
+
+/// Pathological sizes: tiny, one row high and wide, just under the guard.
+const SIZES: [(u16, u16); 6] = [
+    (5, 3),
+    (200, 2),
+    (39, 15),
+    (1, 1),
+    (2, 40),
+    (MIN_WIDTH, MIN_HEIGHT),
+];
+
+fn picker_app(matches: usize) -> AppState {
+    let mut app = AppState::new();
+    app.mode = AppMode::FilePicker;
+    app.file_picker_input = "/music/".to_string();
+    app.input_cursor = app.file_picker_input.len();
+    app.file_picker_matches = (0..matches)
+        .map(|i| format!("/music/a-rather-long-directory-name/take_{i:02}.wav"))
+        .collect();
+    app
+}
+
+/// One app per overlay mode (the frame inspector is non-modal).
+fn overlay_apps() -> Vec<(&'static str, AppState)> {
+    let mut picker = picker_app(12);
+    picker.file_picker_selected = Some(11);
+    picker.file_picker_scroll = 7;
+    let mut saving = AppState::new();
+    saving.mode = AppMode::Saving;
+    saving.file_picker_input = "/tmp/out.wav".to_string();
+    let mut seeking = AppState::new();
+    seeking.mode = AppMode::Seeking;
+    let mut help = AppState::new();
+    help.mode = AppMode::Help;
+    let mut stats = AppState::new();
+    stats.mode = AppMode::Stats;
+    let mut chain = AppState::new();
+    chain.mode = AppMode::ChainOrder;
+    chain.chain_order_selected = 11;
+    let mut f0 = AppState::new();
+    f0.mode = AppMode::F0Correct;
+    f0.f0_correction = Some(F0Correction {
+        anchor_secs: 1.0,
+        end_secs: 2.0,
+        rank: Some(0),
+    });
+    let mut punch = AppState::new();
+    punch.mode = AppMode::PunchIn;
+    punch.punch_region = Some(PunchRegion {
+        anchor_secs: 1.0,
+        end_secs: 0.5,
+        mode: LengthMode::Shift,
+    });
+    let mut tour = AppState::new();
+    tour.start_tour();
+    let mut inspector = AppState::new();
+    inspector.inspector_open = true;
+    vec![
+        ("file picker", picker),
+        ("save dialog", saving),
+        ("seek prompt", seeking),
+        ("help", help),
+        ("stats", stats),
+        ("chain order", chain),
+        ("f0 correction", f0),
+        ("punch-in", punch),
+        ("tour", tour),
+        ("inspector", inspector),
+    ]
+}
+
+fn draw(width: u16, height: u16, render: impl FnOnce(&mut ratatui::Frame)) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(render).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+fn is_blank(buf: &Buffer) -> bool {
+    buf.content.iter().all(|cell| cell.symbol() == " ")
+}
+
+#[test]
+fn test_every_overlay_renders_at_pathological_sizes() {
+    for (name, mut app) in overlay_apps() {
+        for (width, height) in SIZES {
+            let buf = draw(width, height, |frame| layout::render(frame, &mut app));
+            if panel_rects(buf.area).is_none() && width >= 20 {
+                let text: String = buf.content.iter().map(|c| c.symbol()).collect();
+                assert!(
+                    text.contains("Terminal too small"),
+                    "{name} at {width}×{height}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_overlays_drawn_directly_skip_areas_without_interior() {
+    for (name, app) in overlay_apps() {
+        for (width, height) in SIZES {
+            let area = Rect::new(0, 0, width, height);
+            let buf = draw(width, height, |frame| match name {
+                "file picker" => file_picker::render(frame, &app),
+                "save dialog" => save_dialog::render(frame, &app),
+                "seek prompt" => seek_prompt::render(frame, &app),
+                "help" => help::render(frame),
+                "stats" => stats::render(frame, &app),
+                "chain order" => chain_order::render(frame, &app),
+                "f0 correction" => f0_correct::render(frame, area, &app),
+                "punch-in" => punch_in::render(frame, area, &app),
+                "inspector" => inspector::render(frame, area, &app),
+                // The tour needs panel rects, so it only draws above the guard.
+                _ => {
+                    if let Some(rects) = panel_rects(area) {
+                        tour::render(frame, &rects, &app);
+                    }
+                }
+            });
+            if width < 3 || height < 3 {
+                assert!(is_blank(&buf), "{name} drew into {width}×{height}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_centered_popup_stays_inside_area() {
+    let area = Rect::new(10, 5, 30, 8);
+    let popup = centered_popup(Constraint::Length(200), 40, area).unwrap();
+    assert_eq!(popup, area);
+    let popup = centered_popup(Constraint::Percentage(60), 5, area).unwrap();
+    assert!(area.contains(popup.as_position()) && popup.bottom() <= area.bottom());
+    assert_eq!((popup.width, popup.height), (18, 5));
+
+    assert!(centered_popup(Constraint::Length(10), 5, Rect::new(0, 0, 2, 40)).is_none());
+    assert!(centered_popup(Constraint::Length(10), 5, Rect::new(0, 0, 200, 2)).is_none());
+    assert!(centered_popup(Constraint::Length(2), 5, area).is_none());
+    assert_eq!(
+        centered_popup(Constraint::Length(3), 3, Rect::new(0, 0, 3, 3)),
+        Some(Rect::new(0, 0, 3, 3))
+    );
+}
+
+#[test]
+fn test_picker_visible_rows_follow_terminal_height() {
+    assert_eq!(
+        file_picker::visible_rows(Rect::new(0, 0, 80, 24)),
+        FILE_PICKER_ROWS
+    );
+    assert_eq!(file_picker::visible_rows(Rect::new(0, 0, 80, 8)), 3);
+    assert_eq!(file_picker::visible_rows(Rect::new(0, 0, 80, 5)), 0);
+    assert_eq!(file_picker::visible_rows(Rect::new(0, 0, 2, 24)), 0);
+}
+
+#[test]
+fn test_resize_clamps_picker_window_to_visible_rows() {
+    let mut app = picker_app(12);
+    app.file_picker_selected = Some(11);
+    app.file_picker_scroll = 7;
+
+    // Shrinking to three rows keeps the selection as the last visible row.
+    app.clamp_file_picker(3);
+    assert_eq!(app.file_picker_rows, 3);
+    assert_eq!(
+        (app.file_picker_scroll, app.file_picker_selected),
+        (9, Some(11))
+    );
+
+    // Growing back never leaves the window past the last full page.
+    app.clamp_file_picker(FILE_PICKER_ROWS);
+    assert_eq!(
+        (app.file_picker_scroll, app.file_picker_selected),
+        (7, Some(11))
+    );
+
+    // Selection above the window pulls it up; stale indices are clamped.
+    app.file_picker_selected = Some(2);
+    app.clamp_file_picker(FILE_PICKER_ROWS);
+    assert_eq!(app.file_picker_scroll, 2);
+    app.file_picker_matches.truncate(4);
+    app.file_picker_selected = Some(9);
+    app.clamp_file_picker(FILE_PICKER_ROWS);
+    assert_eq!(
+        (app.file_picker_scroll, app.file_picker_selected),
+        (0, Some(3))
+    );
+    app.file_picker_matches.clear();
+    app.clamp_file_picker(0);
+    assert_eq!(
+        (app.file_picker_scroll, app.file_picker_selected),
+        (0, None)
+    );
+    assert_eq!(app.file_picker_rows, 1);
+}
+
+#[test]
+fn test_shrunken_picker_shows_selected_match() {
+    let mut app = picker_app(12);
+    app.file_picker_selected = Some(11);
+    app.file_picker_scroll = 7;
+    let (width, height) = (80, 8);
+    app.clamp_file_picker(file_picker::visible_rows(Rect::new(0, 0, width, height)));
+    let buf = draw(width, height, |frame| file_picker::render(frame, &app));
+    let rows: Vec<String> = (0..height)
+        .map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect())
+        .collect();
+    let shown: Vec<&String> = rows.iter().filter(|r| r.contains("take_")).collect();
+    assert_eq!(shown.len(), 3, "{rows:#?}");
+    assert!(
+        shown[2].contains("▶") && shown[2].contains("take_11"),
+        "{rows:#?}"
+    );
+    assert!(rows.iter().any(|r| r.contains("↑9")), "{rows:#?}");
+}
+
+#[test]
+fn test_31_band_eq_panel_groups_bands_when_narrow() {
+    let mut gains = vec![0.0; 31];
+    gains[17] = 6.0; // 1 kHz
+    let text = |width| {
+        let buf = draw(width, 12, |frame| {
+            eq_panel::render(frame, frame.area(), EqLayout::Bands31, &gains, 0, true)
+        });
+        buf.content.iter().map(|c| c.symbol()).collect::<String>()
+    };
+    // Wide enough for a column per band: every label fits.
+    let wide = text(128);
+    assert!(wide.contains("1.6k") && wide.contains("20k"));
+    // Two bands to a column: only every other label, but the boost still shows.
+    let narrow = text(24);
+    assert!(!narrow.contains("25") && narrow.contains('\u{2588}'));
+}
@@ -0,0 +1,34 @@
+use voiceforge::dsp::parallel::{for_each_row_on, map_rows_on, parse_threads};
+
+#[test]
+fn test_parse_threads() {
+    assert_eq!(parse_threads(None), None);
+    assert_eq!(parse_threads(Some("4")), Some(4));
+    assert_eq!(parse_threads(Some(" 12 ")), Some(12));
+    for invalid in ["0", "-2", "many", ""] {
+        assert_eq!(parse_threads(Some(invalid)), None, "{invalid:?}");
+    }
+}
+
+#[test]
+fn test_rows_match_sequential_for_any_thread_count() {
+    let row = |i: usize| -> Vec<f64> {
+        (0..33)
+            .map(|j| ((i * 33 + j) as f64 * 0.37).sin())
+            .collect()
+    };
+    for len in [0, 1, 255, 256, 1001] {
+        let sequential: Vec<Vec<f64>> = (0..len).map(row).collect();
+        for threads in [1, 2, 3, 16] {
+            assert_eq!(
+                map_rows_on(threads, len, row),
+                sequential,
+                "{len} rows, {threads} threads"
+            );
+
+            let mut rows = vec![Vec::new(); len];
+            for_each_row_on(threads, &mut rows, |i, r| *r = row(i));
+            assert_eq!(rows, sequential, "{len} rows, {threads} threads");
+        }
+    }
+}
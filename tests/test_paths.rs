@@ -0,0 +1,231 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use tempfile::TempDir;
+use voiceforge::paths::{create_dir, dir_in, expand_tilde_with, home_dir_in, DirKind, Platform};
+
+/// Environment lookup backed by a fixed list of variables.
+fn env_of(vars: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<OsString> {
+    move |name| {
+        vars.iter()
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| OsString::from(v))
+    }
+}
+
+fn dir(
+    kind: DirKind,
+    platform: Platform,
+    vars: &'static [(&'static str, &'static str)],
+) -> Option<PathBuf> {
+    dir_in(kind, platform, &env_of(vars))
+}
+
+#[test]
+fn test_unix_xdg_and_fallbacks() {
+    let home_only = &[("HOME", "/home/ana")];
+    let base = Path::new("/home/ana");
+    assert_eq!(
+        dir(DirKind::Config, Platform::Unix, home_only),
+        Some(base.join(".config/voiceforge"))
+    );
+    assert_eq!(
+        dir(DirKind::Data, Platform::Unix, home_only),
+        Some(base.join(".local/share/voiceforge"))
+    );
+    assert_eq!(
+        dir(DirKind::Cache, Platform::Unix, home_only),
+        Some(base.join(".cache/voiceforge"))
+    );
+    assert_eq!(
+        dir(DirKind::State, Platform::Unix, home_only),
+        Some(base.join(".local/state/voiceforge"))
+    );
+
+    let xdg = &[
+        ("HOME", "/home/ana"),
+        ("XDG_CONFIG_HOME", "/cfg"),
+        ("XDG_DATA_HOME", "/data"),
+        ("XDG_CACHE_HOME", "/cache"),
+        ("XDG_STATE_HOME", "relative/state"),
+    ];
+    assert_eq!(
+        dir(DirKind::Config, Platform::Unix, xdg),
+        Some(PathBuf::from("/cfg/voiceforge"))
+    );
+    assert_eq!(
+        dir(DirKind::Data, Platform::Unix, xdg),
+        Some(PathBuf::from("/data/voiceforge"))
+    );
+    assert_eq!(
+        dir(DirKind::Cache, Platform::Unix, xdg),
+        Some(PathBuf::from("/cache/voiceforge"))
+    );
+    // Relative XDG values are ignored.
+    assert_eq!(
+        dir(DirKind::State, Platform::Unix, xdg),
+        Some(base.join(".local/state/voiceforge"))
+    );
+
+    assert_eq!(dir(DirKind::Data, Platform::Unix, &[]), None);
+    assert_eq!(dir(DirKind::Data, Platform::Unix, &[("HOME", "")]), None);
+}
+
+#[test]
+fn test_macos_library_locations() {
+    let env = &[("HOME", "/Users/ana"), ("XDG_CONFIG_HOME", "/ignored")];
+    let library = Path::new("/Users/ana/Library");
+    let support = library.join("Application Support").join("voiceforge");
+    assert_eq!(
+        dir(DirKind::Config, Platform::MacOs, env),
+        Some(support.clone())
+    );
+    assert_eq!(dir(DirKind::Data, Platform::MacOs, env), Some(support));
+    assert_eq!(
+        dir(DirKind::Cache, Platform::MacOs, env),
+        Some(library.join("Caches").join("voiceforge"))
+    );
+    assert_eq!(
+        dir(DirKind::State, Platform::MacOs, env),
+        Some(library.join("Logs").join("voiceforge"))
+    );
+    assert_eq!(dir(DirKind::Config, Platform::MacOs, &[]), None);
+}
+
+#[test]
+fn test_windows_appdata_locations() {
+    let env = &[
+        ("USERPROFILE", r"C:\Users\ana"),
+        ("APPDATA", r"C:\Users\ana\AppData\Roaming"),
+        ("LOCALAPPDATA", r"C:\Users\ana\AppData\Local"),
+    ];
+    let roaming = PathBuf::from(r"C:\Users\ana\AppData\Roaming").join("voiceforge");
+    let local = PathBuf::from(r"C:\Users\ana\AppData\Local").join("voiceforge");
+    assert_eq!(
+        dir(DirKind::Config, Platform::Windows, env),
+        Some(roaming.clone())
+    );
+    assert_eq!(dir(DirKind::Data, Platform::Windows, env), Some(roaming));
+    assert_eq!(
+        dir(DirKind::State, Platform::Windows, env),
+        Some(local.clone())
+    );
+    assert_eq!(
+        dir(DirKind::Cache, Platform::Windows, env),
+        Some(local.join("cache"))
+    );
+
+    // Without APPDATA, fall back under the profile directory.
+    let profile_only = &[("USERPROFILE", r"C:\Users\ana")];
+    assert_eq!(
+        dir(DirKind::Data, Platform::Windows, profile_only),
+        Some(
+            PathBuf::from(r"C:\Users\ana")
+                .join("AppData")
+                .join("Roaming")
+                .join("voiceforge")
+        )
+    );
+}
+
+#[test]
+fn test_windows_home_resolution_order() {
+    let home = |vars| home_dir_in(Platform::Windows, &env_of(vars));
+    assert_eq!(
+        home(&[("USERPROFILE", r"C:\Users\ana"), ("HOME", "/c/msys")]),
+        Some(PathBuf::from(r"C:\Users\ana"))
+    );
+    assert_eq!(
+        home(&[
+            ("HOMEDRIVE", "D:"),
+            ("HOMEPATH", r"\Users\ana"),
+            ("HOME", "/c/msys")
+        ]),
+        Some(PathBuf::from(r"D:\Users\ana"))
+    );
+    assert_eq!(home(&[("HOME", "/c/msys")]), Some(PathBuf::from("/c/msys")));
+    assert_eq!(home(&[("HOMEDRIVE", "D:")]), None);
+    // Unix ignores USERPROFILE.
+    assert_eq!(
+        home_dir_in(Platform::Unix, &env_of(&[("USERPROFILE", r"C:\x")])),
+        None
+    );
+}
+
+#[test]
+fn test_tilde_expansion() {
+    let unix_home = Path::new("/home/ana");
+    let expand = |input, platform, home| expand_tilde_with(input, platform, home);
+    assert_eq!(
+        expand("~/music/", Platform::Unix, Some(unix_home)),
+        Some("/home/ana/music/".into())
+    );
+    assert_eq!(
+        expand("~", Platform::Unix, Some(unix_home)),
+        Some("/home/ana".into())
+    );
+    assert_eq!(
+        expand("~/", Platform::Unix, Some(Path::new("/home/ana/"))),
+        Some("/home/ana/".into())
+    );
+    assert_eq!(
+        expand("~/x", Platform::Unix, Some(Path::new("/"))),
+        Some("/x".into())
+    );
+    // Not a home reference: unchanged.
+    assert_eq!(
+        expand("./~/x", Platform::Unix, Some(unix_home)),
+        Some("./~/x".into())
+    );
+    assert_eq!(
+        expand("~bob/x", Platform::Unix, Some(unix_home)),
+        Some("~bob/x".into())
+    );
+    // Backslash only separates on Windows.
+    assert_eq!(
+        expand(r"~\x", Platform::Unix, Some(unix_home)),
+        Some(r"~\x".into())
+    );
+
+    let win_home = Path::new(r"C:\Users\ana");
+    assert_eq!(
+        expand(r"~\Music\", Platform::Windows, Some(win_home)),
+        Some(r"C:\Users\ana\Music\".into())
+    );
+    assert_eq!(
+        expand("~/Music/", Platform::Windows, Some(win_home)),
+        Some(r"C:\Users\ana/Music/".into())
+    );
+
+    // Unknown home: a tilde path can't be expanded, other input is untouched.
+    assert_eq!(expand("~/x", Platform::Unix, None), None);
+    assert_eq!(expand("x/y", Platform::Unix, None), Some("x/y".into()));
+}
+
+#[test]
+fn test_create_dir_makes_missing_parents() {
+    let tmp = TempDir::new().expect("failed to create temp dir");
+    let target = tmp.path().join("a/b/voiceforge");
+    let created = create_dir(DirKind::Data, Some(target.clone())).unwrap();
+    assert_eq!(created, target);
+    assert!(target.is_dir());
+    // Idempotent.
+    assert!(create_dir(DirKind::Data, Some(target)).is_ok());
+}
+
+#[test]
+fn test_create_dir_error_names_kind_and_path() {
+    let tmp = TempDir::new().expect("failed to create temp dir");
+    let file = tmp.path().join("occupied");
+    std::fs::write(&file, b"not a directory").unwrap();
+    let target = file.join("voiceforge");
+
+    let err = create_dir(DirKind::Cache, Some(target.clone())).unwrap_err();
+    let msg = err.to_string();
+    assert!(msg.starts_with("cannot create cache directory"), "{msg}");
+    assert!(msg.contains(&target.display().to_string()), "{msg}");
+
+    let err = create_dir(DirKind::State, None).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    assert!(err.to_string().starts_with("no state directory"), "{err}");
+}
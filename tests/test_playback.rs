@@ -87,3 +87,256 @@ fn test_toggle_playing_returns_new_state() {
     assert!(!new2); // now paused
     assert!(!state.playing.load(Ordering::Acquire));
 }
+
+#[test]
+fn test_set_live_gain_db_clamps_to_slider_range() {
+    use voiceforge::audio::playback::{db_to_linear, LIVE_GAIN_MAX_DB, LIVE_GAIN_MIN_DB};
+    let state = PlaybackState::new();
+    assert!(state.set_live_gain_db(40.0));
+    assert!((state.live_gain() - db_to_linear(LIVE_GAIN_MAX_DB)).abs() < 1e-6);
+    assert!(state.set_live_gain_db(-100.0));
+    assert!((state.live_gain() - db_to_linear(LIVE_GAIN_MIN_DB)).abs() < 1e-6);
+}
+
+#[test]
+fn test_set_live_gain_db_rejects_non_finite() {
+    let state = PlaybackState::new();
+    assert!(state.set_live_gain_db(6.0));
+    let before = state.live_gain();
+    for bad in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+        assert!(!state.set_live_gain_db(bad));
+        assert_eq!(state.live_gain(), before, "gain changed after {bad}");
+    }
+    assert!(state.live_gain().is_finite());
+}
+
+#[test]
+fn test_live_gain_db_linear_round_trip() {
+    use voiceforge::audio::playback::{db_to_linear, linear_to_db};
+    let state = PlaybackState::new();
+    assert!(
+        (state.live_gain() - 1.0).abs() < 1e-6,
+        "default gain should be unity"
+    );
+    for db in [-12.0_f32, -6.0, -0.5, 0.0, 3.0, 12.0] {
+        assert!((linear_to_db(db_to_linear(db)) - db).abs() < 1e-4);
+        state.set_live_gain_db(db);
+        assert!(
+            (state.live_gain_db() - db).abs() < 1e-4,
+            "{db} dB round trip"
+        );
+    }
+    assert!((db_to_linear(6.0) - 1.995).abs() < 1e-3);
+}
+
+/// Callback harness: a playing state over `samples` (mono) at 1 kHz, device mono.
+fn callback_harness(
+    samples: Vec<f32>,
+) -> (PlaybackState, voiceforge::audio::playback::CallbackContext) {
+    use std::sync::{Arc, RwLock};
+    use voiceforge::audio::decoder::AudioData;
+    use voiceforge::audio::playback::CallbackContext;
+
+    let state = PlaybackState::new();
+    state.playing.store(true, Ordering::Release);
+    state.loop_enabled.store(true, Ordering::Release);
+    let audio = Arc::new(RwLock::new(Arc::new(AudioData {
+        samples,
+        sample_rate: 1000,
+        channels: 1,
+    })));
+    let config = cpal::StreamConfig {
+        channels: 1,
+        sample_rate: 1000,
+        buffer_size: cpal::BufferSize::Default,
+    };
+    let ctx = CallbackContext::new(&state, &config, &audio);
+    (state, ctx)
+}
+
+/// Run the callback for `frames` frames (10 ms chunks at 1 kHz).
+fn run_callback(ctx: &mut voiceforge::audio::playback::CallbackContext, frames: usize) -> Vec<f32> {
+    let mut out = Vec::with_capacity(frames);
+    for _ in 0..frames / 10 {
+        let mut buf = [0.0_f32; 10];
+        voiceforge::audio::playback::write_audio_data(&mut buf, ctx);
+        out.extend_from_slice(&buf);
+    }
+    out
+}
+
+/// Full-scale 50 Hz sine at 1 kHz: peaks far above the ceiling, zero crossings below it.
+fn full_scale_sine() -> Vec<f32> {
+    (0..1000)
+        .map(|i| (2.0 * std::f32::consts::PI * 50.0 * i as f32 / 1000.0).sin())
+        .collect()
+}
+
+#[test]
+fn test_output_ceiling_clamps_full_scale() {
+    use voiceforge::audio::playback::{db_to_linear, DEFAULT_OUTPUT_CEILING_DB};
+    let (state, mut ctx) = callback_harness(vec![1.0; 1000]);
+    let out = run_callback(&mut ctx, 50);
+    let ceiling = db_to_linear(DEFAULT_OUTPUT_CEILING_DB);
+    assert!(out.iter().all(|s| s.abs() <= ceiling + 1e-6));
+    assert!(
+        (out[49] - ceiling).abs() < 1e-6,
+        "not clamped at the ceiling: {}",
+        out[49]
+    );
+    assert!(!state.is_output_limited(), "50 ms must not duck yet");
+
+    assert!(state.set_output_ceiling_db(-6.0));
+    let out = run_callback(&mut ctx, 10);
+    assert!((out[9] - db_to_linear(-6.0)).abs() < 1e-6);
+    assert!(!state.set_output_ceiling_db(f32::NAN));
+}
+
+#[test]
+fn test_sustained_clamp_ducks_after_100ms() {
+    use voiceforge::audio::playback::{db_to_linear, DEFAULT_OUTPUT_CEILING_DB, DUCK_DB};
+    let (state, mut ctx) = callback_harness(full_scale_sine());
+    run_callback(&mut ctx, 90);
+    assert!(!state.is_output_limited(), "ducked before 100 ms");
+    let out = run_callback(&mut ctx, 20);
+    assert!(
+        state.is_output_limited(),
+        "no duck after 110 ms of clamping"
+    );
+
+    // Ducked output sits ~20 dB down and stays there until acknowledged.
+    let out_after = run_callback(&mut ctx, 500);
+    let duck_peak = db_to_linear(DUCK_DB) * 1.0;
+    assert!(out_after.iter().all(|s| s.abs() <= duck_peak + 1e-6));
+    assert!(state.is_output_limited());
+    assert!(out.iter().any(|s| s.abs() > duck_peak), "ducked too early");
+
+    // Restore: back to the clamped level, and a persisting screech ducks again.
+    assert!(state.restore_output());
+    assert!(!state.restore_output());
+    let restored = run_callback(&mut ctx, 50);
+    let ceiling = db_to_linear(DEFAULT_OUTPUT_CEILING_DB);
+    assert!(restored.iter().any(|s| (s.abs() - ceiling).abs() < 1e-6));
+    assert!(!state.is_output_limited());
+    run_callback(&mut ctx, 100);
+    assert!(state.is_output_limited());
+}
+
+#[test]
+fn test_brief_peaks_and_quiet_signal_never_duck() {
+    // A single loud burst of 30 ms inside quiet material.
+    let mut samples = vec![0.2_f32; 1000];
+    for s in &mut samples[100..130] {
+        *s = 1.0;
+    }
+    let (state, mut ctx) = callback_harness(samples);
+    run_callback(&mut ctx, 2000);
+    assert!(!state.is_output_limited());
+}
+
+#[test]
+fn test_output_conversion_exact_codes() {
+    use voiceforge::audio::playback::{OutputSample, U16_MIDPOINT};
+    let cases: [(f32, i16, u16); 8] = [
+        (0.0, 0, 32768),
+        (1.0, 32767, 65535),
+        (-1.0, -32768, 0),
+        (0.5, 16384, 49152),
+        (-0.5, -16384, 16384),
+        (1.0 / 32768.0, 1, 32769),
+        (1.4 / 32768.0, 1, 32769),
+        (-2.6 / 32768.0, -3, 32765),
+    ];
+    for (value, i, u) in cases {
+        assert_eq!(i16::from_f32(value), i, "i16 code for {value}");
+        assert_eq!(u16::from_f32(value), u, "u16 code for {value}");
+        assert_eq!(f32::from_f32(value), value);
+    }
+    assert_eq!(u16::from_f32(0.0), U16_MIDPOINT);
+    // Out of range and NaN never wrap.
+    assert_eq!(i16::from_f32(3.0), i16::MAX);
+    assert_eq!(u16::from_f32(-3.0), 0);
+    assert_eq!(i16::from_f32(f32::NAN), 0);
+    assert_eq!(u16::from_f32(f32::NAN), U16_MIDPOINT);
+}
+
+/// Run the callback into a device buffer of `T` for `frames` frames.
+fn run_callback_as<T: voiceforge::audio::playback::OutputSample>(
+    ctx: &mut voiceforge::audio::playback::CallbackContext,
+    frames: usize,
+) -> Vec<T> {
+    let mut out = vec![T::from_f32(0.0); frames];
+    for chunk in out.chunks_mut(10) {
+        voiceforge::audio::playback::write_audio_data(chunk, ctx);
+    }
+    out
+}
+
+/// Mean and variance of `codes` around `centre`.
+fn code_stats(codes: impl Iterator<Item = f64>, centre: f64) -> (f64, f64) {
+    let codes: Vec<f64> = codes.collect();
+    let n = codes.len() as f64;
+    let mean = codes.iter().sum::<f64>() / n;
+    let var = codes.iter().map(|c| (c - centre).powi(2)).sum::<f64>() / n;
+    (mean, var)
+}
+
+#[test]
+fn test_integer_output_without_dither_rounds_exactly() {
+    // 1000.3 LSB: plain rounding always gives code 1000.
+    let value = 1000.3 / 32768.0;
+    let (state, mut ctx) = callback_harness(vec![value; 1000]);
+    state.dither.store(false, Ordering::Relaxed);
+    assert!(run_callback_as::<i16>(&mut ctx, 2000)
+        .iter()
+        .all(|&c| c == 1000));
+    assert!(run_callback_as::<u16>(&mut ctx, 2000)
+        .iter()
+        .all(|&c| c == 33768));
+}
+
+#[test]
+fn test_tpdf_dither_statistics_on_constant_input() {
+    use voiceforge::audio::playback::tpdf_dither;
+
+    let mut rng = 1;
+    let (mean, var) = code_stats((0..100_000).map(|_| f64::from(tpdf_dither(&mut rng))), 0.0);
+    assert!(mean.abs() < 0.01, "dither mean {mean}");
+    // Triangular over (-1, 1): variance 1/6 LSB².
+    assert!((var - 1.0 / 6.0).abs() < 0.01, "dither variance {var}");
+
+    let x = 1000.3;
+    let (_state, mut ctx) = callback_harness(vec![(x / 32768.0) as f32; 1000]);
+    let codes = run_callback_as::<i16>(&mut ctx, 50_000);
+    // Within one LSB of the input either way, never more.
+    assert!(
+        codes.iter().all(|&c| (999..=1002).contains(&c)),
+        "{:?}",
+        &codes[..20]
+    );
+    // TPDF makes the quantizer's mean output equal the input, and its
+    // total error power 1/6 + 1/12 = 1/4 LSB².
+    let (mean, var) = code_stats(codes.iter().map(|&c| f64::from(c)), x);
+    assert!((mean - x).abs() < 0.02, "mean code {mean}");
+    assert!((var - 0.25).abs() < 0.03, "error variance {var}");
+
+    // U16 dithers around the midpoint-shifted code.
+    let (_state, mut ctx) = callback_harness(vec![0.0; 1000]);
+    let codes = run_callback_as::<u16>(&mut ctx, 50_000);
+    assert!(codes.iter().all(|&c| (32767..=32769).contains(&c)));
+    let (mean, _) = code_stats(codes.iter().map(|&c| f64::from(c)), 32768.0);
+    assert!((mean - 32768.0).abs() < 0.02, "mean code {mean}");
+}
+
+#[test]
+fn test_float_output_is_never_dithered() {
+    let value = 1000.3 / 32768.0;
+    let (state, mut ctx) = callback_harness(vec![value; 1000]);
+    assert!(
+        state.dither.load(Ordering::Relaxed),
+        "dither is on by default"
+    );
+    assert!(run_callback_as::<f32>(&mut ctx, 1000)
+        .iter()
+        .all(|&s| s == value));
+}
@@ -0,0 +1,1809 @@
+mod test_support;
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tempfile::TempDir;
+use voiceforge::audio::decoder::{AudioData, AudioFormat};
+use voiceforge::audio::edit::{LengthMode, Splice};
+use voiceforge::dsp::classify::ContentClass;
+use voiceforge::dsp::effects::{EffectsParams, EqParams};
+use voiceforge::dsp::f0_candidates::F0Choice;
+use voiceforge::dsp::loudness::rms_dbfs;
+use voiceforge::dsp::modifier::WorldSliderValues;
+use voiceforge::dsp::processing::{
+    apply_fx_chain, changed_region, params_sidecar, preview_analysis_options, AnalysisOptions,
+    ExportJob, ProcessingCommand, ProcessingHandle, ProcessingResult, ProgressTracker, PunchJob,
+};
+
+/// Poll `cond` until it holds or `timeout` elapses.
+fn wait_for(timeout: Duration, mut cond: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if cond() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    cond()
+}
+
+fn short_tone() -> AudioData {
+    let sr = 16000;
+    AudioData {
+        samples: (0..sr / 4)
+            .map(|i| 0.4 * (2.0 * std::f32::consts::PI * 220.0 * i as f32 / sr as f32).sin())
+            .collect(),
+        sample_rate: sr,
+        channels: 1,
+    }
+}
+
+/// Format of [`short_tone`], as the UI would expect it back.
+fn tone_format() -> AudioFormat {
+    short_tone().format()
+}
+
+#[test]
+fn test_worker_alive_after_spawn() {
+    let handle = ProcessingHandle::spawn();
+    assert!(handle.is_alive());
+}
+
+#[test]
+fn test_worker_not_alive_after_shutdown() {
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Shutdown);
+    assert!(wait_for(Duration::from_secs(5), || !handle.is_alive()));
+}
+
+#[test]
+fn test_watchdog_detects_crash_and_respawn_recovers() {
+    let mut handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::SimulateCrash);
+    assert!(
+        wait_for(Duration::from_secs(5), || !handle.is_alive()),
+        "escaped panic should end the worker thread"
+    );
+
+    handle.respawn();
+    assert!(handle.is_alive());
+
+    // The fresh worker rebuilds its caches from a re-sent Analyze.
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    let mut analyzed = false;
+    assert!(wait_for(Duration::from_secs(30), || {
+        while let Some(result) = handle.try_recv() {
+            if matches!(result, ProcessingResult::AnalysisDone(_)) {
+                analyzed = true;
+            }
+        }
+        analyzed
+    }));
+    assert!(handle.is_alive());
+}
+
+#[test]
+fn test_worker_responsive_after_noop_resynth() {
+    // Resynthesize before any analysis is a no-op, not a crash; the worker
+    // must still answer afterwards.
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        Default::default(),
+        AudioFormat::mono(16000),
+        None,
+    ));
+    handle.send(ProcessingCommand::ScanDirectory(
+        "/nonexistent-dir/".to_string(),
+    ));
+    let mut listed = false;
+    assert!(wait_for(Duration::from_secs(5), || {
+        while let Some(result) = handle.try_recv() {
+            if matches!(result, ProcessingResult::DirectoryListing(..)) {
+                listed = true;
+            }
+        }
+        listed
+    }));
+    assert!(handle.is_alive());
+}
+
+#[test]
+fn test_caught_panic_invalidates_analysis_until_reanalyzed() {
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+
+    // The panic is caught: an error status, then the invalidation, and the
+    // worker keeps running.
+    handle.send(ProcessingCommand::SimulatePanic);
+    let mut status = None;
+    recv_matching(&handle, |r| match r {
+        ProcessingResult::Status(msg) => {
+            status = Some(msg);
+            None
+        }
+        ProcessingResult::AnalysisInvalidated => Some(()),
+        _ => None,
+    });
+    let status = status.expect("error status before the invalidation");
+    assert!(status.contains("simulated processing panic"), "{status}");
+    assert!(handle.is_alive());
+
+    // A resynthesis now says why it can't run instead of doing nothing.
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        0,
+        tone_format(),
+        None,
+    ));
+    let msg = recv_status_without_synthesis(&handle, "no analyzed audio");
+    assert!(msg.contains("16000 Hz mono"), "{msg}");
+
+    // Re-analyzing the same audio brings resynthesis back.
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        0,
+        tone_format(),
+        None,
+    ));
+    let processed = recv_matching(&handle, |r| match r {
+        ProcessingResult::SynthesisDone(audio) => Some(audio),
+        _ => None,
+    });
+    assert_eq!(processed.format(), tone_format());
+}
+
+#[test]
+fn test_panic_in_resynthesis_drain_invalidates_analysis() {
+    // A panic while draining queued slider changes takes the same path.
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        0,
+        tone_format(),
+        None,
+    ));
+    handle.send(ProcessingCommand::SimulatePanic);
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisInvalidated).then_some(())
+    });
+    assert!(handle.is_alive());
+}
+
+/// Hash of 16-bit PCM samples, for comparing a written file with a buffer.
+fn pcm_hash(samples: impl Iterator<Item = i16>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for s in samples {
+        s.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Hash of a buffer as `export_wav` quantizes it.
+fn buffer_hash(audio: &AudioData) -> u64 {
+    pcm_hash(
+        audio
+            .samples
+            .iter()
+            .map(|s| (s.clamp(-1.0, 1.0) * 32767.0) as i16),
+    )
+}
+
+/// Block until the worker sends a result matching `pick`.
+fn recv_matching<T>(
+    handle: &ProcessingHandle,
+    mut pick: impl FnMut(ProcessingResult) -> Option<T>,
+) -> T {
+    let mut found = None;
+    assert!(wait_for(Duration::from_secs(30), || {
+        while found.is_none() {
+            match handle.try_recv() {
+                Some(result) => found = pick(result),
+                None => break,
+            }
+        }
+        found.is_some()
+    }));
+    found.unwrap()
+}
+
+#[test]
+fn test_export_writes_snapshot_current_at_dispatch() {
+    let dir = TempDir::new().expect("failed to create temp dir");
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+
+    // Revision 1: the buffer current when the export is dispatched.
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        0,
+        tone_format(),
+        None,
+    ));
+    let rev1 = recv_matching(&handle, |r| match r {
+        ProcessingResult::SynthesisDone(audio) => Some(Arc::new(audio)),
+        _ => None,
+    });
+
+    // Interleave: a resynthesis queued on either side of the export.
+    let darker = EffectsParams {
+        high_cut_hz: 500.0,
+        ..Default::default()
+    };
+    let path = dir.path().join("rev1.wav").to_string_lossy().into_owned();
+    let darker = handle.effects().publish(darker);
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        darker,
+        tone_format(),
+        None,
+    ));
+    handle.send(ProcessingCommand::Export(ExportJob {
+        audio: Arc::clone(&rev1),
+        gain_db: 0.0,
+        path: path.clone(),
+        label: "B rev 1".to_string(),
+    }));
+    handle.send(ProcessingCommand::ReapplyEffects(darker, tone_format()));
+
+    let mut exported = None;
+    let mut latest = None;
+    assert!(wait_for(Duration::from_secs(30), || {
+        while let Some(result) = handle.try_recv() {
+            match result {
+                ProcessingResult::ExportDone(p, outcome) => exported = Some((p, outcome)),
+                ProcessingResult::SynthesisDone(audio) => latest = Some(audio),
+                _ => {}
+            }
+        }
+        exported.is_some() && latest.is_some()
+    }));
+
+    let (written, outcome) = exported.unwrap();
+    assert_eq!(written, path);
+    assert!(outcome.is_ok(), "export failed: {outcome:?}");
+    let mut reader = hound::WavReader::open(&path).expect("should read back");
+    let file_hash = pcm_hash(reader.samples::<i16>().map(|s| s.unwrap()));
+    assert_eq!(
+        file_hash,
+        buffer_hash(&rev1),
+        "file must match the dispatched snapshot"
+    );
+    assert_ne!(
+        file_hash,
+        buffer_hash(&latest.unwrap()),
+        "later generation leaked into the export"
+    );
+}
+
+#[test]
+fn test_export_failure_reported() {
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Export(ExportJob {
+        audio: Arc::new(short_tone()),
+        gain_db: 0.0,
+        path: "/nonexistent-dir/out.wav".to_string(),
+        label: "A".to_string(),
+    }));
+    let outcome = recv_matching(&handle, |r| match r {
+        ProcessingResult::ExportDone(_, outcome) => Some(outcome),
+        _ => None,
+    });
+    assert!(outcome.is_err());
+    assert!(handle.is_alive());
+}
+
+#[test]
+fn test_changed_region_block_diff() {
+    let old = short_tone(); // 4000 samples @ 16 kHz
+    assert_eq!(changed_region(&old, &old.clone()), None);
+
+    let mut edited = old.clone();
+    for s in &mut edited.samples[2100..2200] {
+        *s = -*s;
+    }
+    let (start, end) = changed_region(&old, &edited).expect("edit not detected");
+    // Block-aligned (1024 samples) span covering the edit.
+    assert!(start <= 2100.0 / 16000.0 && end >= 2200.0 / 16000.0);
+    assert!(
+        end - start <= 2048.0 / 16000.0 + 1e-9,
+        "span {start}..{end}"
+    );
+
+    let shorter = AudioData {
+        samples: old.samples[..3000].to_vec(),
+        ..old.clone()
+    };
+    assert_eq!(
+        changed_region(&old, &shorter),
+        Some((0.0, shorter.duration_secs()))
+    );
+}
+
+#[test]
+fn test_analyze_of_edited_version_reports_analysis() {
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+
+    // Same length, one block changed: the incremental path answers too.
+    let mut edited = short_tone();
+    for s in &mut edited.samples[1500..1600] {
+        *s *= 0.5;
+    }
+    handle.send(ProcessingCommand::Analyze(edited.clone()));
+    let mono = recv_matching(&handle, |r| match r {
+        ProcessingResult::AnalysisDone(mono) => Some(mono),
+        _ => None,
+    });
+    assert_eq!(mono.samples, edited.samples);
+    assert!(handle.is_alive());
+}
+
+#[test]
+fn test_loudness_follows_synthesis() {
+    let tone = short_tone();
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(tone.clone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+
+    // A low cut well above the 220 Hz tone makes B much quieter.
+    let fx = handle.effects().publish(EffectsParams {
+        low_cut_hz: 2000.0,
+        ..Default::default()
+    });
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        fx,
+        tone_format(),
+        None,
+    ));
+    let processed = recv_matching(&handle, |r| match r {
+        ProcessingResult::SynthesisDone(audio) => Some(audio),
+        _ => None,
+    });
+    let measured = recv_matching(&handle, |r| match r {
+        ProcessingResult::Loudness(m) => Some(m),
+        _ => None,
+    });
+    assert_eq!(measured.original_db, Some(rms_dbfs(&tone.samples)));
+    assert_eq!(measured.processed_db, rms_dbfs(&processed.samples));
+    let delta = measured.delta_db().unwrap();
+    assert!(delta < -6.0, "delta {delta}");
+    assert!(measured.is_mismatched());
+}
+
+#[test]
+fn test_progress_eta_extrapolates_linearly() {
+    let t0 = Instant::now();
+    // 60 s of audio.
+    let mut tracker = ProgressTracker::started_at("Decoding...", 60.0, t0);
+
+    // 25% after 2 s: 6 s to go, 7.5 s of audio per second.
+    let now = t0 + Duration::from_secs(2);
+    let status = tracker.update_at(25, now).unwrap();
+    assert_eq!(tracker.eta_secs_at(now), Some(6.0));
+    assert_eq!(tracker.realtime_factor_at(now), Some(7.5));
+    assert_eq!(status, "Decoding... 25% (7.5× realtime, ~6 s left)");
+
+    // 80% after 8 s: 2 s to go.
+    let now = t0 + Duration::from_secs(8);
+    tracker.update_at(80, now).unwrap();
+    assert_eq!(tracker.eta_secs_at(now), Some(2.0));
+
+    // Done: no ETA, throughput over the whole run.
+    let now = t0 + Duration::from_secs(10);
+    let status = tracker.update_at(100, now).unwrap();
+    assert_eq!(tracker.eta_secs_at(now), None);
+    assert_eq!(status, "Decoding... 100% (6.0× realtime)");
+}
+
+#[test]
+fn test_progress_formats_minutes_and_unknown_duration() {
+    let t0 = Instant::now();
+    let tracker = ProgressTracker::started_at("Analyzing...", 0.0, t0);
+    // Nothing measurable before the first percentage.
+    assert_eq!(
+        tracker.status_at(t0 + Duration::from_secs(3)),
+        "Analyzing... 0%"
+    );
+
+    let mut tracker = ProgressTracker::started_at("Analyzing...", 0.0, t0);
+    // 10% after 20 s: 180 s left, shown in whole minutes.
+    let status = tracker.update_at(10, t0 + Duration::from_secs(20)).unwrap();
+    assert_eq!(status, "Analyzing... 10% (~3 min left)");
+}
+
+#[test]
+fn test_progress_throttles_to_twice_a_second() {
+    let t0 = Instant::now();
+    let mut tracker = ProgressTracker::started_at("Decoding...", 10.0, t0);
+    let at = |ms| t0 + Duration::from_millis(ms);
+    assert!(tracker.update_at(1, at(100)).is_some());
+    assert!(tracker.update_at(2, at(300)).is_none());
+    assert!(tracker.update_at(3, at(599)).is_none());
+    assert!(tracker.update_at(4, at(600)).is_some());
+    assert!(tracker.update_at(5, at(700)).is_none());
+    // Completion is never swallowed.
+    assert!(tracker.update_at(100, at(710)).is_some());
+}
+
+#[test]
+fn test_progress_stall_saturates_eta() {
+    let t0 = Instant::now();
+    let mut tracker = ProgressTracker::started_at("Decoding...", 600.0, t0);
+    tracker.update_at(1, t0 + Duration::from_secs(1)).unwrap();
+    assert_eq!(tracker.eta_secs_at(t0 + Duration::from_secs(1)), Some(99.0));
+
+    // Stuck at 1%: the extrapolation grows with the wall clock...
+    let later = t0 + Duration::from_secs(30);
+    assert_eq!(tracker.eta_secs_at(later), Some(2970.0));
+    // ...until it saturates at the cap.
+    let much_later = t0 + Duration::from_secs(600);
+    assert_eq!(
+        tracker.eta_secs_at(much_later),
+        Some(ProgressTracker::MAX_ETA_SECS)
+    );
+    let status = tracker.update_at(1, much_later).unwrap();
+    assert!(status.ends_with("> 1 h left)"), "{status}");
+
+    // A late, lower percentage doesn't move progress backwards.
+    tracker.update_at(0, much_later + Duration::from_secs(1));
+    assert!(tracker
+        .status_at(much_later + Duration::from_secs(1))
+        .starts_with("Decoding... 1% "));
+}
+
+#[test]
+fn test_inspect_frame_returns_frame_info_over_channel() {
+    use voiceforge::dsp::inspect::FrameInfo;
+    use voiceforge::dsp::world;
+
+    let handle = ProcessingHandle::spawn();
+    // Before analysis there is nothing to inspect; the worker stays quiet.
+    handle.send(ProcessingCommand::InspectFrame(0.1));
+
+    let tone = short_tone();
+    handle.send(ProcessingCommand::Analyze(tone.clone()));
+    recv_matching(&handle, |r| match r {
+        ProcessingResult::AnalysisDone(_) => Some(()),
+        ProcessingResult::FrameInfo(_) => panic!("FrameInfo sent without analysis"),
+        _ => None,
+    });
+
+    handle.send(ProcessingCommand::InspectFrame(0.1));
+    let info = recv_matching(&handle, |r| match r {
+        ProcessingResult::FrameInfo(info) => Some(info),
+        _ => None,
+    });
+
+    let params = world::analyze(&tone).expect("analysis failed");
+    let expected = FrameInfo::from_params(&params, tone.sample_rate, 0.1).unwrap();
+    assert_eq!(info, expected);
+    assert_eq!(info.frame, 20);
+    assert!((info.f0_hz - 220.0).abs() < 5.0, "f0 {}", info.f0_hz);
+    assert!(!info.peaks.is_empty());
+}
+
+/// Wait for the first `Status` whose text contains `needle`, failing on any
+/// `SynthesisDone` on the way.
+fn recv_status_without_synthesis(handle: &ProcessingHandle, needle: &str) -> String {
+    recv_matching(handle, |r| match r {
+        ProcessingResult::Status(msg) if msg.contains(needle) => Some(msg),
+        ProcessingResult::SynthesisDone(audio) => {
+            panic!(
+                "processed audio sent while waiting for {needle:?}: {}",
+                audio.format()
+            )
+        }
+        _ => None,
+    })
+}
+
+#[test]
+fn test_failed_load_does_not_leave_stale_cache_for_effects() {
+    let dir = TempDir::new().expect("failed to create temp dir");
+    let first = test_support::write_wav(
+        dir.path(),
+        "first-44k.wav",
+        &test_support::sine(220.0, 0.4, 44100, 0.25),
+    );
+    // A 48 kHz file whose header claims a 0 Hz rate: decoding panics
+    // part-way, after the worker has started on the new load.
+    let broken = test_support::write_wav(
+        dir.path(),
+        "broken-48k.wav",
+        &test_support::sine(220.0, 0.4, 48000, 0.25),
+    );
+    let mut bytes = std::fs::read(&broken).unwrap();
+    bytes[24..28].fill(0);
+    std::fs::write(&broken, bytes).unwrap();
+
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Load(
+        first.to_string_lossy().into_owned(),
+    ));
+    let analyzed = recv_matching(&handle, |r| match r {
+        ProcessingResult::AnalysisDone(mono) => Some(mono),
+        _ => None,
+    });
+    assert_eq!(analyzed.format(), AudioFormat::mono(44100));
+
+    handle.send(ProcessingCommand::Load(
+        broken.to_string_lossy().into_owned(),
+    ));
+    recv_status_without_synthesis(&handle, "error");
+
+    // Tweak an effect for the file the user picked: the 44.1 kHz buffer
+    // from the first file must not be filtered as if it were 48 kHz.
+    let fx = handle.effects().publish(EffectsParams {
+        low_cut_hz: 300.0,
+        ..Default::default()
+    });
+    handle.send(ProcessingCommand::ReapplyEffects(
+        fx,
+        AudioFormat::mono(48000),
+    ));
+    let msg = recv_status_without_synthesis(&handle, "Format error");
+    assert!(msg.contains("expected 48000 Hz mono"), "{msg}");
+
+    // Same for a full resynthesis; the worker stays usable.
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        fx,
+        AudioFormat::mono(48000),
+        None,
+    ));
+    recv_status_without_synthesis(&handle, "Format error");
+    handle.send(ProcessingCommand::ScanDirectory(
+        "/nonexistent-dir/".to_string(),
+    ));
+    recv_matching(&handle, |r| match r {
+        ProcessingResult::DirectoryListing(..) => Some(()),
+        ProcessingResult::SynthesisDone(_) => panic!("stale resynthesis"),
+        _ => None,
+    });
+    assert!(handle.is_alive());
+}
+
+#[test]
+fn test_queued_commands_process_latest_published_effects() {
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+
+    // Three publishes before any command arrives: each queued command names
+    // an older revision, but all of them must run with the newest.
+    let effects = handle.effects();
+    let first = effects.publish(EffectsParams {
+        high_cut_hz: 500.0,
+        ..Default::default()
+    });
+    let second = effects.publish(EffectsParams {
+        low_cut_hz: 1000.0,
+        ..Default::default()
+    });
+    let latest = effects.publish(EffectsParams {
+        low_cut_hz: 300.0,
+        reverb_mix: 0.5,
+        ..Default::default()
+    });
+    assert_eq!((first, second, latest), (1, 2, 3));
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        first,
+        tone_format(),
+        None,
+    ));
+    handle.send(ProcessingCommand::ReapplyEffects(first, tone_format()));
+    handle.send(ProcessingCommand::ReapplyEffects(second, tone_format()));
+    let mut processed = vec![recv_matching(&handle, |r| match r {
+        ProcessingResult::SynthesisDone(audio) => Some(audio),
+        _ => None,
+    })];
+    // A drain runs other commands first, so only queue the marker once the
+    // first pass is out; any passes left over finish before it.
+    handle.send(ProcessingCommand::ScanDirectory(
+        "/nonexistent-dir/".to_string(),
+    ));
+    recv_matching(&handle, |r| match r {
+        ProcessingResult::SynthesisDone(audio) => {
+            processed.push(audio);
+            None
+        }
+        ProcessingResult::DirectoryListing(..) => Some(()),
+        _ => None,
+    });
+
+    // Reference: the same post-WORLD buffer with the latest parameters.
+    handle.send(ProcessingCommand::ReapplyEffects(latest, tone_format()));
+    let reference = recv_matching(&handle, |r| match r {
+        ProcessingResult::SynthesisDone(audio) => Some(audio),
+        _ => None,
+    });
+    for audio in &processed {
+        assert_eq!(audio.samples, reference.samples);
+    }
+    effects.publish(EffectsParams {
+        high_cut_hz: 500.0,
+        ..Default::default()
+    });
+    handle.send(ProcessingCommand::ReapplyEffects(latest, tone_format()));
+    let older = recv_matching(&handle, |r| match r {
+        ProcessingResult::SynthesisDone(audio) => Some(audio),
+        _ => None,
+    });
+    assert_ne!(older.samples, reference.samples);
+}
+
+#[test]
+fn test_effects_for_other_format_rejected() {
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+
+    let fx = handle.effects().publish(EffectsParams {
+        high_cut_hz: 500.0,
+        ..Default::default()
+    });
+    handle.send(ProcessingCommand::ReapplyEffects(
+        fx,
+        AudioFormat::mono(44100),
+    ));
+    let msg = recv_status_without_synthesis(&handle, "Format error");
+    assert!(
+        msg.contains("expected 44100 Hz mono, got 16000 Hz mono"),
+        "{msg}"
+    );
+
+    // The matching format still goes through.
+    handle.send(ProcessingCommand::ReapplyEffects(fx, tone_format()));
+    let processed = recv_matching(&handle, |r| match r {
+        ProcessingResult::SynthesisDone(audio) => Some(audio),
+        _ => None,
+    });
+    assert_eq!(processed.format(), tone_format());
+}
+
+#[test]
+fn test_loudness_match_trims_an_eq_boost_back_to_the_original() {
+    let tone = short_tone();
+    let boosted = EffectsParams {
+        eq: EqParams {
+            gains: vec![6.0; 12],
+            ..EqParams::default()
+        },
+        ..Default::default()
+    };
+    let rms = |audio: &AudioData| voiceforge::dsp::effects::measure_rms(&audio.samples);
+
+    // Off (or without a reference), the boost comes through.
+    let loud = apply_fx_chain(&tone, &boosted, tone_format(), Some(&tone)).unwrap();
+    assert_eq!(loud.trim_db, None);
+    assert!(rms(&loud.audio) > 1.5 * rms(&tone));
+
+    let matched_fx = EffectsParams {
+        loudness_match: true,
+        ..boosted.clone()
+    };
+    assert!(apply_fx_chain(&tone, &matched_fx, tone_format(), None)
+        .unwrap()
+        .trim_db
+        .is_none());
+    let matched = apply_fx_chain(&tone, &matched_fx, tone_format(), Some(&tone)).unwrap();
+    let trim = matched.trim_db.unwrap();
+    assert!(trim < -3.0, "trim {trim:.2} dB");
+    let ratio = rms(&matched.audio) / rms(&tone);
+    assert!((ratio - 1.0).abs() < 0.05, "matched RMS ratio {ratio:.3}");
+    assert_eq!(matched.audio.frame_count(), loud.audio.frame_count());
+
+    // Neutral effects are still matched: WORLD edits change the level too.
+    let quiet = AudioData {
+        samples: tone.samples.iter().map(|s| s * 0.5).collect(),
+        ..tone.clone()
+    };
+    let only_match = EffectsParams {
+        loudness_match: true,
+        ..Default::default()
+    };
+    let lifted = apply_fx_chain(&quiet, &only_match, tone_format(), Some(&tone)).unwrap();
+    assert!((lifted.trim_db.unwrap() - 6.02).abs() < 0.1);
+    assert!((rms(&lifted.audio) / rms(&tone) - 1.0).abs() < 0.05);
+}
+
+#[test]
+fn test_apply_fx_chain_checks_format() {
+    let tone = short_tone();
+    let fx = EffectsParams {
+        low_cut_hz: 300.0,
+        ..Default::default()
+    };
+    let processed = apply_fx_chain(&tone, &fx, tone_format(), None)
+        .unwrap()
+        .audio;
+    assert_eq!(processed.format(), tone_format());
+    assert_ne!(processed.samples, tone.samples);
+
+    let err = apply_fx_chain(&tone, &fx, AudioFormat::mono(48000), None).unwrap_err();
+    assert_eq!(err, "effects: expected 48000 Hz mono, got 16000 Hz mono");
+    // Checked even when the chain would be a no-op.
+    assert!(apply_fx_chain(
+        &tone,
+        &EffectsParams::default(),
+        AudioFormat::mono(48000),
+        None
+    )
+    .is_err());
+
+    // Stereo is filtered per channel: each comes out as the mono chain's.
+    let stereo = test_support::interleave(&[tone.clone(), tone]);
+    let processed_stereo = apply_fx_chain(&stereo, &fx, stereo.format(), None)
+        .unwrap()
+        .audio;
+    assert_eq!(processed_stereo.format(), stereo.format());
+    for channel in processed_stereo.samples.chunks_exact(2) {
+        assert_eq!(channel[0], channel[1]);
+    }
+    let left: Vec<f32> = processed_stereo
+        .samples
+        .iter()
+        .step_by(2)
+        .copied()
+        .collect();
+    assert_eq!(left, processed.samples);
+}
+
+#[test]
+fn test_f0_candidate_applied_with_extended_analysis() {
+    let options = AnalysisOptions {
+        f0_candidates: true,
+        ..Default::default()
+    };
+    let handle = ProcessingHandle::spawn_with(options, Default::default());
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+
+    let choice = F0Choice {
+        start_secs: 0.2,
+        end_secs: 0.05,
+        rank: 1,
+    };
+    handle.send(ProcessingCommand::ApplyF0Candidate(choice));
+    let (applied, written) = recv_matching(&handle, |r| match r {
+        ProcessingResult::F0Corrected(choice, written) => Some((choice, written)),
+        _ => None,
+    });
+    assert_eq!(applied, choice);
+    // 5 ms frames from 50 ms to 200 ms, both ends included.
+    assert_eq!(written, 31);
+    assert!(handle.is_alive());
+}
+
+#[test]
+fn test_f0_candidate_without_extended_analysis_reports_status() {
+    let handle = ProcessingHandle::spawn();
+    let choice = F0Choice {
+        start_secs: 0.0,
+        end_secs: 0.1,
+        rank: 1,
+    };
+    handle.send(ProcessingCommand::ApplyF0Candidate(choice));
+    let msg = recv_status_without_synthesis(&handle, "F0 correction");
+    assert!(msg.contains("no analyzed audio"), "{msg}");
+
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+    handle.send(ProcessingCommand::ApplyF0Candidate(choice));
+    let msg = recv_status_without_synthesis(&handle, "F0 correction");
+    assert!(msg.contains("--extended-analysis"), "{msg}");
+}
+
+#[test]
+fn test_classification_follows_analysis() {
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(test_support::white_noise(
+        0.3, 16000, 0.5, 5,
+    )));
+    let mut analyzed = false;
+    let classified = recv_matching(&handle, |r| match r {
+        ProcessingResult::AnalysisDone(_) => {
+            analyzed = true;
+            None
+        }
+        ProcessingResult::Classified(c) => {
+            assert!(analyzed, "classification arrived before AnalysisDone");
+            Some(c)
+        }
+        _ => None,
+    });
+    assert_eq!(classified.class, ContentClass::Other);
+}
+
+#[test]
+fn test_punch_in_resamples_and_splices_clip() {
+    let dir = TempDir::new().expect("failed to create temp dir");
+    // 0.2 s at twice the session rate: 3200 frames once resampled.
+    let clip = test_support::sine(440.0, 0.5, 32000, 0.2);
+    let clip_path = test_support::write_wav(dir.path(), "fix.wav", &clip);
+    let base = Arc::new(short_tone());
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::PunchIn(PunchJob {
+        base: Arc::clone(&base),
+        path: clip_path.to_string_lossy().into_owned(),
+        splice: Splice {
+            start: 1600,
+            end: 3200,
+            fade: 160,
+            mode: LengthMode::Shift,
+        },
+    }));
+    let spliced = recv_matching(&handle, |r| match r {
+        ProcessingResult::PunchedIn(audio) => Some(audio),
+        _ => None,
+    });
+    assert_eq!(spliced.format(), base.format());
+    assert_eq!(spliced.frame_count(), base.frame_count() - 1600 + 3200);
+    assert_eq!(spliced.samples[..1600], base.samples[..1600]);
+    assert_eq!(spliced.samples[1600 + 3200..], base.samples[3200..]);
+
+    // A stereo clip can't go into mono B; the worker says so and carries on.
+    let stereo = test_support::interleave(&[clip.clone(), clip]);
+    let stereo_path = test_support::write_wav(dir.path(), "stereo.wav", &stereo);
+    handle.send(ProcessingCommand::PunchIn(PunchJob {
+        base,
+        path: stereo_path.to_string_lossy().into_owned(),
+        splice: Splice {
+            start: 0,
+            end: 1600,
+            fade: 0,
+            mode: LengthMode::Fit,
+        },
+    }));
+    let msg = recv_matching(&handle, |r| match r {
+        ProcessingResult::Status(msg) => Some(msg),
+        ProcessingResult::PunchedIn(_) => panic!("stereo clip spliced into mono"),
+        _ => None,
+    });
+    assert_eq!(msg, "Punch-in failed: clip has 2 channel(s), the buffer 1");
+    assert!(handle.is_alive());
+}
+
+/// Five seconds of a 220 Hz tone: long enough for a resynthesis preview.
+fn long_tone() -> AudioData {
+    let sr = 16000;
+    AudioData {
+        samples: (0..sr * 5)
+            .map(|i| 0.4 * (2.0 * std::f32::consts::PI * 220.0 * i as f32 / sr as f32).sin())
+            .collect(),
+        sample_rate: sr,
+        channels: 1,
+    }
+}
+
+#[test]
+fn test_preview_around_playhead_precedes_full_synthesis() {
+    let tone = long_tone();
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(tone.clone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+
+    handle.set_playhead(3.0);
+    let values = WorldSliderValues {
+        pitch_shift: 4.0,
+        ..Default::default()
+    };
+    handle.send(ProcessingCommand::Resynthesize(
+        values,
+        0,
+        tone.format(),
+        None,
+    ));
+    let mut preview = None;
+    let full = recv_matching(&handle, |r| match r {
+        ProcessingResult::PreviewReady(clip) => {
+            preview = Some(clip);
+            None
+        }
+        ProcessingResult::SynthesisDone(audio) => Some(audio),
+        _ => None,
+    });
+    let clip = preview.expect("preview before SynthesisDone");
+
+    // About two seconds, starting a little before the playhead.
+    assert_eq!(clip.total_frames, full.frame_count());
+    assert_eq!(clip.audio.format(), full.format());
+    let start_secs = clip.start as f64 / 16000.0;
+    assert!((2.7..3.0).contains(&start_secs), "starts at {start_secs}");
+    assert!((clip.audio.duration_secs() - 2.0).abs() < 0.01);
+
+    // Inside its crossfades the preview sounds like the full render there:
+    // same level, same waveform up to the pulse phase (the window starts
+    // its pulse train afresh, so allow a lag of up to one period).
+    let inner = 800..clip.audio.frame_count() - 800;
+    let expected = &full.samples[clip.start + inner.start..clip.start + inner.end];
+    let got = &clip.audio.samples[inner.clone()];
+    let level = rms_dbfs(got) - rms_dbfs(expected);
+    assert!(level.abs() < 1.0, "preview level off by {level:.1} dB");
+    let correlation = (0..64)
+        .map(|lag| {
+            let a = &expected[..expected.len() - 64];
+            let b = &clip.audio.samples[inner.start + lag..inner.start + lag + a.len()];
+            let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+            let energy = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+            dot / (energy(a) * energy(b))
+        })
+        .fold(f32::MIN, f32::max);
+    assert!(correlation > 0.9, "correlation {correlation:.3}");
+
+    // It splices into a B of the right length only.
+    let silent = AudioData {
+        samples: vec![0.0; full.samples.len()],
+        ..full.clone()
+    };
+    let spliced = clip.splice_into(&silent).unwrap();
+    assert_eq!(spliced.frame_count(), full.frame_count());
+    assert!(spliced.samples[..clip.start].iter().all(|&s| s == 0.0));
+    assert_eq!(spliced.samples[clip.start + 800], clip.audio.samples[800]);
+    assert!(
+        clip.splice_into(&tone).is_none(),
+        "one frame shorter than WORLD's output"
+    );
+}
+
+#[test]
+fn test_no_preview_for_short_files_or_speed_changes() {
+    for (audio, speed) in [(short_tone(), 1.0), (long_tone(), 1.5)] {
+        let handle = ProcessingHandle::spawn();
+        handle.send(ProcessingCommand::Analyze(audio.clone()));
+        recv_matching(&handle, |r| {
+            matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+        });
+        let values = WorldSliderValues {
+            pitch_shift: 2.0,
+            speed,
+            ..Default::default()
+        };
+        handle.send(ProcessingCommand::Resynthesize(
+            values,
+            0,
+            audio.format(),
+            None,
+        ));
+        let mut previews = 0;
+        recv_matching(&handle, |r| match r {
+            ProcessingResult::PreviewReady(_) => {
+                previews += 1;
+                None
+            }
+            ProcessingResult::SynthesisDone(_) => Some(()),
+            _ => None,
+        });
+        assert_eq!(previews, 0, "speed {speed}");
+    }
+}
+
+#[test]
+fn test_new_analyze_cancels_the_one_in_flight() {
+    let sr = 16000;
+    let long = AudioData {
+        samples: (0..sr * 20)
+            .map(|i| 0.4 * (2.0 * std::f32::consts::PI * 150.0 * i as f32 / sr as f32).sin())
+            .collect(),
+        sample_rate: sr,
+        channels: 1,
+    };
+    let short = short_tone();
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(long.clone()));
+    handle.send(ProcessingCommand::Analyze(short.clone()));
+
+    // Superseded while queued: the long analysis never reports.
+    let analyzed = recv_matching(&handle, |r| match r {
+        ProcessingResult::AnalysisDone(mono) => Some(mono),
+        _ => None,
+    });
+    assert_eq!(analyzed.samples, short.samples);
+
+    // Superseded after its first stage: it stops there, without an error.
+    handle.send(ProcessingCommand::Analyze(long));
+    recv_matching(&handle, |r| match r {
+        ProcessingResult::Status(msg) if msg.starts_with("Analyzing... 25%") => Some(()),
+        _ => None,
+    });
+    let started = Instant::now();
+    handle.send(ProcessingCommand::Analyze(short.clone()));
+    let analyzed = recv_matching(&handle, |r| match r {
+        ProcessingResult::AnalysisDone(mono) => Some(mono),
+        ProcessingResult::Status(msg) => {
+            assert!(!msg.contains("error"), "{msg}");
+            None
+        }
+        _ => None,
+    });
+    assert_eq!(analyzed.samples, short.samples);
+    assert!(
+        started.elapsed() < Duration::from_secs(15),
+        "took {:?}",
+        started.elapsed()
+    );
+    assert!(handle.is_alive());
+
+    // The surviving analysis is usable.
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        0,
+        tone_format(),
+        None,
+    ));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::SynthesisDone(_)).then_some(())
+    });
+}
+
+/// Load `path` and return the statuses sent until its `AnalysisDone`.
+fn load_and_collect_statuses(handle: &ProcessingHandle, path: &str) -> Vec<String> {
+    handle.send(ProcessingCommand::Load(path.to_string()));
+    let mut statuses = Vec::new();
+    recv_matching(handle, |r| match r {
+        ProcessingResult::Status(status) => {
+            statuses.push(status);
+            None
+        }
+        ProcessingResult::AnalysisDone(_) => Some(()),
+        _ => None,
+    });
+    statuses
+}
+
+#[test]
+fn test_load_reuses_matching_sidecar_analysis() {
+    let dir = TempDir::new().expect("failed to create temp dir");
+    let wav = test_support::write_wav(dir.path(), "song.wav", &short_tone());
+    let wav = wav.to_string_lossy().into_owned();
+    let sidecar = dir.path().join("song.vfw");
+    assert_eq!(params_sidecar(&wav), sidecar);
+
+    let caching = AnalysisOptions {
+        cache_params: true,
+        ..Default::default()
+    };
+    let handle = ProcessingHandle::spawn_with(caching, Default::default());
+    let statuses = load_and_collect_statuses(&handle, &wav);
+    assert!(
+        statuses.iter().any(|s| s.starts_with("Analyzing")),
+        "{statuses:?}"
+    );
+    let saved = recv_matching(&handle, |r| match r {
+        ProcessingResult::Status(s) if s.starts_with("Analysis saved") => Some(s),
+        _ => None,
+    });
+    assert!(saved.contains("song.vfw"), "{saved}");
+    assert!(sidecar.exists());
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        0,
+        tone_format(),
+        None,
+    ));
+    let analyzed = recv_matching(&handle, |r| match r {
+        ProcessingResult::SynthesisDone(audio) => Some(audio),
+        _ => None,
+    });
+
+    // A fresh worker (caching off) reads the sidecar instead of analyzing,
+    // and resynthesizes exactly the same audio from it.
+    let handle = ProcessingHandle::spawn();
+    let statuses = load_and_collect_statuses(&handle, &wav);
+    assert!(
+        !statuses.iter().any(|s| s.starts_with("Analyzing")),
+        "{statuses:?}"
+    );
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        0,
+        tone_format(),
+        None,
+    ));
+    let cached = recv_matching(&handle, |r| match r {
+        ProcessingResult::SynthesisDone(audio) => Some(audio),
+        _ => None,
+    });
+    assert_eq!(cached.samples, analyzed.samples);
+
+    // Other analysis settings don't match the sidecar either.
+    let sharper = AnalysisOptions {
+        world: world_sys::AnalyzeOptions {
+            cheaptrick_q1: Some(-0.05),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let other = ProcessingHandle::spawn_with(sharper, Default::default());
+    let statuses = load_and_collect_statuses(&other, &wav);
+    assert!(
+        statuses.iter().any(|s| s.starts_with("Analyzing")),
+        "{statuses:?}"
+    );
+
+    // Different audio under the same name: the stale sidecar is ignored.
+    let louder = AudioData {
+        samples: short_tone().samples.iter().map(|s| s * 1.5).collect(),
+        ..short_tone()
+    };
+    test_support::write_wav(dir.path(), "song.wav", &louder);
+    let statuses = load_and_collect_statuses(&handle, &wav);
+    assert!(
+        statuses.iter().any(|s| s.starts_with("Analyzing")),
+        "{statuses:?}"
+    );
+}
+
+#[test]
+fn test_save_and_load_params_commands() {
+    let dir = TempDir::new().expect("failed to create temp dir");
+    let path = dir.path().join("tone.vfw").to_string_lossy().into_owned();
+    let handle = ProcessingHandle::spawn();
+    let status = |handle: &ProcessingHandle| {
+        recv_matching(handle, |r| match r {
+            ProcessingResult::Status(s) => Some(s),
+            _ => None,
+        })
+    };
+
+    handle.send(ProcessingCommand::SaveParams(path.clone()));
+    assert_eq!(status(&handle), "No analysis to save");
+    handle.send(ProcessingCommand::LoadParams(path.clone()));
+    assert_eq!(status(&handle), "Load audio before its analysis");
+
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+    handle.send(ProcessingCommand::SaveParams(path.clone()));
+    assert!(status(&handle).starts_with("Analysis saved"));
+    handle.send(ProcessingCommand::LoadParams(path.clone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+
+    // Params saved from other audio are refused; the analysis stays.
+    let other = AudioData {
+        samples: short_tone().samples.iter().rev().copied().collect(),
+        ..short_tone()
+    };
+    handle.send(ProcessingCommand::Analyze(other));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+    handle.send(ProcessingCommand::LoadParams(path.clone()));
+    let msg = status(&handle);
+    assert!(msg.contains("other audio"), "{msg}");
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        0,
+        tone_format(),
+        None,
+    ));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::SynthesisDone(_)).then_some(())
+    });
+
+    std::fs::write(&path, b"VFWP garbage").unwrap();
+    handle.send(ProcessingCommand::LoadParams(path));
+    let msg = status(&handle);
+    assert!(msg.starts_with("Analysis load error: params file"), "{msg}");
+}
+
+#[test]
+fn test_new_analysis_options_drop_the_analysis_until_reanalyzed() {
+    let mut handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+
+    handle.set_analysis(world_sys::AnalyzeOptions {
+        d4c_threshold: 0.0,
+        ..Default::default()
+    });
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        0,
+        tone_format(),
+        None,
+    ));
+    let msg = recv_matching(&handle, |r| match r {
+        ProcessingResult::Status(s) if s.starts_with("Format error") => Some(s),
+        _ => None,
+    });
+    assert!(msg.contains("no analyzed audio"), "{msg}");
+
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        0,
+        tone_format(),
+        None,
+    ));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::SynthesisDone(_)).then_some(())
+    });
+}
+
+#[test]
+fn test_preview_analysis_is_faster_and_synthesizes() {
+    let tone = test_support::sine(180.0, 0.4, 16000, 3.0);
+    let full = world_sys::AnalyzeOptions::default();
+    let preview = preview_analysis_options(&full);
+    let time = |options: &world_sys::AnalyzeOptions| {
+        let start = Instant::now();
+        let params = voiceforge::dsp::world::analyze_with_progress(
+            &tone,
+            options,
+            &Default::default(),
+            |_| {},
+        )
+        .expect("analysis failed");
+        (start.elapsed(), params)
+    };
+    let (full_time, full_params) = time(&full);
+    let (preview_time, preview_params) = time(&preview);
+    assert!(
+        preview_time.as_secs_f64() * 1.5 < full_time.as_secs_f64(),
+        "preview {preview_time:?} vs full {full_time:?}",
+    );
+    assert!(preview_params.f0.len() < full_params.f0.len());
+
+    let out =
+        world_sys::synthesize(&preview_params, 16000).expect("preview params don't synthesize");
+    assert!(
+        (out.len() as f64 - 3.0 * 16000.0).abs() < 16000.0 * 0.05,
+        "{} samples",
+        out.len()
+    );
+    assert!(out.iter().all(|s| s.is_finite()));
+    assert!(out.iter().any(|s| s.abs() > 0.1));
+}
+
+#[test]
+fn test_preview_is_replaced_by_the_full_analysis() {
+    let options = AnalysisOptions {
+        preview_from_secs: Some(0.0),
+        ..Default::default()
+    };
+    let handle = ProcessingHandle::spawn_with(options, Default::default());
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    let mut statuses = Vec::new();
+    recv_matching(&handle, |r| match r {
+        ProcessingResult::Status(s) => {
+            statuses.push(s);
+            None
+        }
+        ProcessingResult::AnalysisDone(_) => Some(()),
+        _ => None,
+    });
+    assert!(
+        statuses.iter().any(|s| s.starts_with("Previewing")),
+        "{statuses:?}"
+    );
+
+    // The preview answers resynthesis while the full analysis runs, and the
+    // full one is announced once it replaces the preview.
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        0,
+        tone_format(),
+        None,
+    ));
+    let (mut synthesized, mut refined, mut ready) = (false, false, false);
+    recv_matching(&handle, |r| {
+        match r {
+            ProcessingResult::SynthesisDone(audio) => {
+                synthesized = audio.samples.iter().any(|s| s.abs() > 0.1)
+            }
+            ProcessingResult::AnalysisRefined => refined = true,
+            ProcessingResult::Status(s) => ready |= s == "Full analysis ready",
+            _ => {}
+        }
+        (synthesized && refined).then_some(())
+    });
+    assert!(ready);
+    handle.send(ProcessingCommand::Resynthesize(
+        Default::default(),
+        0,
+        tone_format(),
+        None,
+    ));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::SynthesisDone(_)).then_some(())
+    });
+    assert!(handle.is_alive());
+}
+
+#[test]
+fn test_region_resynthesis_splices_into_the_last_render() {
+    let tone = test_support::sine(220.0, 0.4, 16000, 1.0);
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(tone.clone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+    let synthesis = |pitch_shift, range| {
+        let values = WorldSliderValues {
+            pitch_shift,
+            ..Default::default()
+        };
+        handle.send(ProcessingCommand::Resynthesize(
+            values,
+            0,
+            tone.format(),
+            range,
+        ));
+        recv_matching(&handle, |r| match r {
+            ProcessingResult::SynthesisDone(audio) => Some(audio),
+            _ => None,
+        })
+    };
+    let full = synthesis(6.0, None);
+    let before = synthesis(3.0, None);
+    let region = synthesis(6.0, Some(0.2..0.6));
+    assert_eq!(region.samples.len(), full.samples.len());
+
+    // The previous render outside 0.2..0.6 s; inside, the level of the
+    // full render with the same values and no jump at the seams beyond
+    // what that render has there.
+    let (start, end) = (3200, 9601);
+    assert_eq!(region.samples[..start], before.samples[..start]);
+    assert_eq!(region.samples[end..], before.samples[end..]);
+    assert_ne!(region.samples[start..end], before.samples[start..end]);
+    let inside = |audio: &AudioData| rms_dbfs(&audio.samples[start..end]);
+    assert!(
+        (inside(&region) - inside(&full)).abs() < 1.0,
+        "{} vs {}",
+        inside(&region),
+        inside(&full)
+    );
+    let max_step = |audio: &AudioData, seam: usize| {
+        audio.samples[seam - 400..seam + 400]
+            .windows(2)
+            .map(|w| (w[1] - w[0]).abs())
+            .fold(0.0f32, f32::max)
+    };
+    for seam in [start, end] {
+        let (got, expected) = (max_step(&region, seam), max_step(&full, seam));
+        assert!(
+            got < expected * 1.5,
+            "seam {seam}: step {got} vs {expected}"
+        );
+    }
+}
+
+#[test]
+fn test_pitch_curve_arrives_before_the_analysis() {
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    let mut curve = None;
+    recv_matching(&handle, |r| match r {
+        ProcessingResult::PitchCurve(points) => {
+            curve = Some(points);
+            None
+        }
+        ProcessingResult::AnalysisDone(_) => Some(()),
+        _ => None,
+    });
+    let curve = curve.expect("PitchCurve before AnalysisDone");
+    assert!(curve.windows(2).all(|w| w[1].0 > w[0].0));
+    let voiced: Vec<f64> = curve
+        .iter()
+        .map(|&(_, hz)| hz)
+        .filter(|&hz| hz > 0.0)
+        .collect();
+    assert!(
+        voiced.len() > curve.len() / 2,
+        "{} of {} voiced",
+        voiced.len(),
+        curve.len()
+    );
+    // The edge frames see the tone start and stop.
+    let inner = &voiced[2..voiced.len() - 2];
+    assert!(
+        inner.iter().all(|hz| (hz - 220.0).abs() < 5.0),
+        "{voiced:?}"
+    );
+}
+
+/// Statuses up to the next `SynthesisDone`, and its audio.
+fn synthesis_statuses(handle: &ProcessingHandle) -> (Vec<String>, AudioData) {
+    let mut statuses = Vec::new();
+    let audio = recv_matching(handle, |r| match r {
+        ProcessingResult::Status(msg) => {
+            statuses.push(msg);
+            None
+        }
+        ProcessingResult::SynthesisDone(audio) => Some(audio),
+        _ => None,
+    });
+    (statuses, audio)
+}
+
+#[test]
+fn test_effects_only_change_reuses_the_world_render() {
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+    let values = WorldSliderValues {
+        pitch_shift: 4.0,
+        ..Default::default()
+    };
+    handle.send(ProcessingCommand::Resynthesize(
+        values.clone(),
+        0,
+        tone_format(),
+        None,
+    ));
+    let (statuses, dry) = synthesis_statuses(&handle);
+    assert!(
+        statuses.iter().any(|s| s.starts_with("Synthesizing voice")),
+        "{statuses:?}"
+    );
+
+    // New effects, same sliders: WORLD never runs again.
+    let revision = handle.effects().publish(EffectsParams {
+        reverb_mix: 0.5,
+        ..Default::default()
+    });
+    handle.send(ProcessingCommand::Resynthesize(
+        values.clone(),
+        revision,
+        tone_format(),
+        None,
+    ));
+    let (statuses, wet) = synthesis_statuses(&handle);
+    assert!(
+        !statuses.iter().any(|s| s.starts_with("Synthesizing")),
+        "{statuses:?}"
+    );
+    assert_ne!(wet.samples, dry.samples);
+    handle.send(ProcessingCommand::ReapplyEffects(revision, tone_format()));
+    let (_, reapplied) = synthesis_statuses(&handle);
+    assert_eq!(reapplied.samples, wet.samples);
+
+    // Any WORLD stage changing renders again.
+    let values = WorldSliderValues {
+        breathiness: 0.5,
+        ..values
+    };
+    handle.send(ProcessingCommand::Resynthesize(
+        values,
+        revision,
+        tone_format(),
+        None,
+    ));
+    let (statuses, _) = synthesis_statuses(&handle);
+    assert!(
+        statuses.iter().any(|s| s.starts_with("Synthesizing voice")),
+        "{statuses:?}"
+    );
+}
+
+#[test]
+fn test_f0_correction_resynthesizes_only_the_changed_frames() {
+    let options = AnalysisOptions {
+        f0_candidates: true,
+        ..Default::default()
+    };
+    let handle = ProcessingHandle::spawn_with(options, Default::default());
+    let tone = test_support::sine(220.0, 0.4, 16000, 1.0);
+    handle.send(ProcessingCommand::Analyze(tone.clone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+    let values = WorldSliderValues {
+        pitch_shift: 2.0,
+        ..Default::default()
+    };
+    handle.send(ProcessingCommand::Resynthesize(
+        values.clone(),
+        0,
+        tone.format(),
+        None,
+    ));
+    let (_, before) = synthesis_statuses(&handle);
+
+    let choice = F0Choice {
+        start_secs: 0.4,
+        end_secs: 0.5,
+        rank: 1,
+    };
+    handle.send(ProcessingCommand::ApplyF0Candidate(choice));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::F0Corrected(..)).then_some(())
+    });
+    handle.send(ProcessingCommand::Resynthesize(
+        values,
+        0,
+        tone.format(),
+        None,
+    ));
+    let (statuses, after) = synthesis_statuses(&handle);
+    assert!(
+        statuses
+            .iter()
+            .any(|s| s.starts_with("Synthesizing changed frames")),
+        "{statuses:?}"
+    );
+    assert!(
+        !statuses.iter().any(|s| s.starts_with("Synthesizing voice")),
+        "{statuses:?}"
+    );
+
+    // Frames 80..=100 plus a 40 ms margin either side (0.36..0.54 s) are
+    // rendered again; the rest is the previous render.
+    assert_eq!(after.samples.len(), before.samples.len());
+    let (start, end) = (5760, 8720);
+    assert_eq!(after.samples[..start], before.samples[..start]);
+    assert_eq!(after.samples[end..], before.samples[end..]);
+    assert_ne!(after.samples[start..end], before.samples[start..end]);
+}
+
+#[test]
+fn test_morph_synthesizes_a_blend_with_another_take() {
+    let dir = TempDir::new().unwrap();
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Morph("/nonexistent.wav".into(), 0.5));
+    recv_status_without_synthesis(&handle, "Load audio before morphing");
+
+    let tone = test_support::sine(200.0, 0.4, 16000, 0.5);
+    handle.send(ProcessingCommand::Analyze(tone.clone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+    // The other take at another rate: it is resampled before analysis.
+    let other = test_support::sine(300.0, 0.4, 22050, 0.5);
+    let path = test_support::write_wav(dir.path(), "other.wav", &other);
+    let path = path.to_string_lossy().to_string();
+    handle.send(ProcessingCommand::Morph(path.clone(), 0.5));
+    let (statuses, blend) = synthesis_statuses(&handle);
+    assert!(
+        statuses.iter().any(|s| s == "Synthesizing morph..."),
+        "{statuses:?}"
+    );
+    assert_eq!(blend.format(), tone.format());
+    assert!(rms_dbfs(&blend.samples) > -20.0);
+
+    // ReapplyEffects runs on the blend.
+    handle.send(ProcessingCommand::ReapplyEffects(0, tone.format()));
+    let (_, again) = synthesis_statuses(&handle);
+    assert_eq!(again.samples, blend.samples);
+
+    handle.send(ProcessingCommand::Morph(path, 2.0));
+    recv_status_without_synthesis(&handle, "Morph failed");
+}
+
+#[test]
+fn test_resynthesize_morph_crossfades_two_presets() {
+    let handle = ProcessingHandle::spawn();
+    let (a, b) = (
+        WorldSliderValues::default(),
+        WorldSliderValues::preset_masculinize(1.0),
+    );
+    handle.send(ProcessingCommand::ResynthesizeMorph(
+        a.clone(),
+        b.clone(),
+        (0.1, 0.3),
+    ));
+    recv_status_without_synthesis(&handle, "Load audio before crossfading presets");
+
+    let tone = test_support::sine(200.0, 0.4, 16000, 0.5);
+    handle.send(ProcessingCommand::Analyze(tone.clone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+    handle.send(ProcessingCommand::ResynthesizeMorph(a, b, (0.1, 0.3)));
+    let (statuses, glide) = synthesis_statuses(&handle);
+    assert!(
+        statuses
+            .iter()
+            .any(|s| s == "Synthesizing preset crossfade..."),
+        "{statuses:?}"
+    );
+    assert_eq!(glide.format(), tone.format());
+    assert!(rms_dbfs(&glide.samples) > -20.0);
+
+    // ReapplyEffects runs on the crossfade.
+    handle.send(ProcessingCommand::ReapplyEffects(0, tone.format()));
+    let (_, again) = synthesis_statuses(&handle);
+    assert_eq!(again.samples, glide.samples);
+}
+
+#[test]
+fn test_clipping_render_is_normalized() {
+    let tone = test_support::sine(220.0, 0.8, 16000, 0.5);
+    // Pivoting far below the tone, the tilt boosts everything it has.
+    let tilted = WorldSliderValues {
+        spectral_tilt: 6.0,
+        tilt_pivot_hz: 20.0,
+        ..Default::default()
+    };
+    let peak = |audio: &AudioData| {
+        audio
+            .samples
+            .iter()
+            .fold(0.0f32, |peak, s| peak.max(s.abs()))
+    };
+    let render = |normalize_synthesis| {
+        let options = AnalysisOptions {
+            normalize_synthesis,
+            ..Default::default()
+        };
+        let handle = ProcessingHandle::spawn_with(options, Default::default());
+        handle.send(ProcessingCommand::Analyze(tone.clone()));
+        recv_matching(&handle, |r| {
+            matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+        });
+        handle.send(ProcessingCommand::Resynthesize(
+            tilted.clone(),
+            0,
+            tone.format(),
+            None,
+        ));
+        let (_, audio) = synthesis_statuses(&handle);
+        // The attenuation is reported after the render.
+        handle.send(ProcessingCommand::ScanDirectory(
+            "/nonexistent-dir/".to_string(),
+        ));
+        let mut normalized = None;
+        recv_matching(&handle, |r| match r {
+            ProcessingResult::Status(msg) if msg.starts_with("Normalized") => {
+                normalized = Some(msg);
+                None
+            }
+            ProcessingResult::DirectoryListing(..) => Some(()),
+            _ => None,
+        });
+        (audio, normalized)
+    };
+
+    let (control, status) = render(false);
+    assert!(peak(&control) > 1.0, "{}", peak(&control));
+    assert_eq!(status, None);
+    let (normalized, status) = render(true);
+    assert!(peak(&normalized) <= 1.0, "{}", peak(&normalized));
+    let status = status.expect("a Normalized status");
+    assert!(status.ends_with("dB to avoid clipping"), "{status}");
+    // The same render, scaled as a whole.
+    let ratio = f64::from(peak(&normalized) / peak(&control));
+    assert!(
+        (ratio - 0.99 / f64::from(peak(&control))).abs() < 1e-3,
+        "{ratio}"
+    );
+}
+
+#[test]
+fn test_failed_synthesis_keeps_the_last_render_for_effects() {
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+    let shifted = WorldSliderValues {
+        pitch_shift: 2.0,
+        ..Default::default()
+    };
+    handle.send(ProcessingCommand::Resynthesize(
+        shifted,
+        0,
+        tone_format(),
+        None,
+    ));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::SynthesisDone(_)).then_some(())
+    });
+
+    // A non-finite f0 fails WORLD's parameter validation.
+    let broken = WorldSliderValues {
+        pitch_shift: f64::NAN,
+        ..Default::default()
+    };
+    handle.send(ProcessingCommand::Resynthesize(
+        broken,
+        0,
+        tone_format(),
+        None,
+    ));
+    recv_status_without_synthesis(&handle, "Synthesis error");
+
+    // The render before the failure still takes effects changes.
+    let fx = handle.effects().publish(EffectsParams {
+        high_cut_hz: 500.0,
+        ..Default::default()
+    });
+    handle.send(ProcessingCommand::ReapplyEffects(fx, tone_format()));
+    let processed = recv_matching(&handle, |r| match r {
+        ProcessingResult::SynthesisDone(audio) => Some(audio),
+        _ => None,
+    });
+    assert_eq!(processed.format(), tone_format());
+}
+
+#[test]
+fn test_only_resynthesis_reports_a_synthesis_time() {
+    let handle = ProcessingHandle::spawn();
+    handle.send(ProcessingCommand::Analyze(short_tone()));
+    recv_matching(&handle, |r| {
+        matches!(r, ProcessingResult::AnalysisDone(_)).then_some(())
+    });
+    let shifted = WorldSliderValues {
+        pitch_shift: 2.0,
+        ..Default::default()
+    };
+    handle.send(ProcessingCommand::Resynthesize(
+        shifted,
+        0,
+        tone_format(),
+        None,
+    ));
+    let elapsed = recv_matching(&handle, |r| match r {
+        ProcessingResult::SynthesisTimed(elapsed) => Some(elapsed),
+        _ => None,
+    });
+    assert!(elapsed > Duration::ZERO);
+
+    // An effects-only pass is not a resynthesis. The directory listing,
+    // sent once it is done, marks the end of its results.
+    let fx = handle.effects().publish(EffectsParams {
+        high_cut_hz: 500.0,
+        ..Default::default()
+    });
+    handle.send(ProcessingCommand::ReapplyEffects(fx, tone_format()));
+    let mut fence_sent = false;
+    recv_matching(&handle, |r| match r {
+        ProcessingResult::SynthesisTimed(_) => panic!("effects-only pass timed"),
+        ProcessingResult::SynthesisDone(_) => {
+            handle.send(ProcessingCommand::ScanDirectory(
+                "/nonexistent-dir/".to_string(),
+            ));
+            fence_sent = true;
+            None
+        }
+        ProcessingResult::DirectoryListing(..) => Some(()),
+        _ => None,
+    });
+    assert!(fence_sent);
+}
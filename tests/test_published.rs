@@ -0,0 +1,64 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use voiceforge::published::Published;
+
+#[test]
+fn test_publish_increments_revision() {
+    let cell = Published::new(10);
+    assert_eq!(cell.revision(), 0);
+    assert_eq!(*cell.load().1, 10);
+    assert_eq!(cell.publish(11), 1);
+    assert_eq!(cell.publish(12), 2);
+    assert_eq!(cell.revision(), 2);
+    let (revision, value) = cell.load();
+    assert_eq!((revision, *value), (2, 12));
+    let (revision, value) = cell.try_load().unwrap();
+    assert_eq!((revision, *value), (2, 12));
+}
+
+#[test]
+fn test_loaded_snapshot_outlives_publish() {
+    let cell = Published::new(vec![1, 2, 3]);
+    let (_, old) = cell.load();
+    cell.publish(vec![4]);
+    assert_eq!(*old, vec![1, 2, 3]);
+    assert_eq!(*cell.load().1, vec![4]);
+}
+
+#[test]
+fn test_concurrent_readers_never_see_torn_snapshots() {
+    // Every published value is [revision; 64]; a reader that saw a mix of
+    // two publishes, or a value from another revision, would catch it.
+    let cell = Arc::new(Published::new(vec![0u64; 64]));
+    let done = Arc::new(AtomicBool::new(false));
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let cell = Arc::clone(&cell);
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                let mut last = 0;
+                while !done.load(Ordering::Relaxed) {
+                    let loaded = if last % 2 == 0 {
+                        Some(cell.load())
+                    } else {
+                        cell.try_load()
+                    };
+                    if let Some((revision, value)) = loaded {
+                        assert!(value.iter().all(|&v| v == revision), "torn at {revision}");
+                        assert!(revision >= last, "revision went back");
+                        last = revision;
+                    }
+                }
+            })
+        })
+        .collect();
+    for revision in 1..=2000 {
+        assert_eq!(cell.publish(vec![revision; 64]), revision);
+    }
+    done.store(true, Ordering::Relaxed);
+    for reader in readers {
+        reader.join().unwrap();
+    }
+}
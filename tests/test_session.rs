@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use tempfile::TempDir;
+use voiceforge::dsp::effects::EffectsParams;
+use voiceforge::dsp::modifier::WorldSliderValues;
+use voiceforge::session::{
+    append_history_line, format_export_line, format_session_line, ExportRecord, SessionStats,
+};
+
+fn export_record(output: &str) -> ExportRecord {
+    ExportRecord {
+        source: "/music/take1.wav".to_string(),
+        output: output.to_string(),
+        world: WorldSliderValues {
+            pitch_shift: 3.5,
+            ..Default::default()
+        },
+        effects: EffectsParams {
+            reverb_mix: 0.25,
+            ..Default::default()
+        },
+    }
+}
+
+#[test]
+fn test_session_stats_event_sequence() {
+    let mut stats = SessionStats::new();
+
+    // Load → analysis → two resyntheses → export → second file.
+    stats.record_file_loaded();
+    stats.record_analysis(12.5);
+    stats.record_synthesis(Duration::from_millis(300));
+    stats.record_synthesis(Duration::from_millis(100));
+    stats.record_export(export_record("/music/take1_processed.wav"));
+    stats.record_file_loaded();
+    stats.record_analysis(7.5);
+
+    assert_eq!(stats.files_processed, 2);
+    assert!((stats.analyzed_secs - 20.0).abs() < 1e-9);
+    assert_eq!(stats.resyntheses, 2);
+    assert_eq!(stats.synthesis_time, Duration::from_millis(400));
+    assert_eq!(stats.mean_synthesis_time(), Duration::from_millis(200));
+    assert_eq!(stats.exports.len(), 1);
+}
+
+#[test]
+fn test_session_stats_ignores_invalid_durations() {
+    let mut stats = SessionStats::new();
+    stats.record_analysis(f64::NAN);
+    stats.record_analysis(-1.0);
+    assert_eq!(stats.analyzed_secs, 0.0);
+    assert_eq!(stats.mean_synthesis_time(), Duration::ZERO);
+}
+
+#[test]
+fn test_history_export_line_format() {
+    let line = format_export_line(1_700_000_000, &export_record("/out/a.wav"));
+    let fields: Vec<&str> = line.split('\t').collect();
+    assert_eq!(fields[0], "1700000000");
+    assert_eq!(fields[1], "export");
+    assert_eq!(fields[2], "src=/music/take1.wav");
+    assert_eq!(fields[3], "out=/out/a.wav");
+    assert!(fields[4].contains("pitch=+3.5st"), "got {}", fields[4]);
+    assert!(fields[5].contains("reverb=0.25"), "got {}", fields[5]);
+    assert!(!line.contains('\n'));
+}
+
+#[test]
+fn test_history_session_line_format() {
+    let mut stats = SessionStats::new();
+    stats.record_file_loaded();
+    stats.record_analysis(3.0);
+    let line = format_session_line(42, &stats);
+    assert_eq!(
+        line,
+        "42\tsession\tfiles=1\tanalyzed=3.0s\tresyntheses=0\tsynth_time=0.0s\texports=0"
+    );
+}
+
+#[test]
+fn test_history_append_creates_and_appends() {
+    let dir = TempDir::new().expect("failed to create temp dir");
+    let path = dir.path().join("history.log");
+
+    append_history_line(&path, "first").unwrap();
+    append_history_line(&path, "second").unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(contents, "first\nsecond\n");
+}
@@ -1,4 +1,6 @@
+use voiceforge::dsp::effects::{eq_band_span_hz, EqLayout, EQ_PEAK_Q};
 use voiceforge::dsp::spectrum::{compute_spectrum, extract_window, FFT_SIZE};
+use voiceforge::ui::spectrum::{column_to_bin, eq_band_columns, freq_to_column};
 
 #[test]
 fn test_spectrum_440hz_peak() {
@@ -54,7 +56,10 @@ fn test_extract_window_stereo_downmix() {
     let window = extract_window(&audio, 0, 4);
     assert_eq!(window.len(), 4);
     for &s in &window {
-        assert!((s - 0.5).abs() < 1e-6, "stereo downmix should be 0.5, got {s}");
+        assert!(
+            (s - 0.5).abs() < 1e-6,
+            "stereo downmix should be 0.5, got {s}"
+        );
     }
 }
 
@@ -85,3 +90,328 @@ fn test_extract_window_zero_channels() {
     let window = extract_window(&audio, 0, 4);
     assert_eq!(window, vec![0.0; 4]);
 }
+
+fn render_spectrum(app: &voiceforge::app::AppState) -> ratatui::buffer::Buffer {
+    use ratatui::backend::TestBackend;
+    use ratatui::layout::Rect;
+    use ratatui::Terminal;
+    let mut terminal = Terminal::new(TestBackend::new(60, 14)).unwrap();
+    terminal
+        .draw(|frame| voiceforge::ui::spectrum::render(frame, Rect::new(0, 0, 60, 14), app))
+        .unwrap();
+    terminal.backend().buffer().clone()
+}
+
+/// Count `symbol` inside the panel, excluding the border/title rows.
+fn count_symbol(buf: &ratatui::buffer::Buffer, symbol: &str) -> usize {
+    let area = buf.area;
+    (1..area.height - 1)
+        .flat_map(|y| (1..area.width - 1).map(move |x| (x, y)))
+        .filter(|&pos| buf[pos].symbol() == symbol)
+        .count()
+}
+
+fn preview_app(tilt_db_per_oct: f64) -> voiceforge::app::AppState {
+    use voiceforge::app::{AppState, PanelFocus};
+    use voiceforge::dsp::processing::FrameSnapshot;
+    let mut app = AppState::new();
+    app.focus = PanelFocus::WorldSliders;
+    app.selected_slider = 5; // Spectral Tilt
+    app.world_sliders[5].value = tilt_db_per_oct;
+    let fft_size = 2048;
+    app.frame_snapshot = Some(FrameSnapshot {
+        envelope: (0..=fft_size / 2)
+            .map(|i| 1.0 / (1.0 + i as f64 * 0.01))
+            .collect(),
+        fft_size,
+        sample_rate: 44100,
+        time_secs: 0.0,
+    });
+    app
+}
+
+#[test]
+fn test_spectral_preview_overlay_draws_both_curves() {
+    // Strong tilt separates the curves, so both markers are visible.
+    let buf = render_spectrum(&preview_app(-6.0));
+    assert!(count_symbol(&buf, "•") > 0, "after curve missing");
+    assert!(count_symbol(&buf, "·") > 0, "before curve missing");
+    let title: String = (0..60).map(|x| buf[(x, 0)].symbol().to_string()).collect();
+    assert!(title.contains("preview"), "title: {title}");
+}
+
+#[test]
+fn test_spectral_preview_neutral_curves_coincide() {
+    // With neutral sliders "after" is drawn on top of "before" everywhere.
+    let buf = render_spectrum(&preview_app(0.0));
+    assert!(count_symbol(&buf, "•") > 0);
+    assert_eq!(count_symbol(&buf, "·"), 0);
+}
+
+#[test]
+fn test_spectral_preview_hidden_when_other_slider_selected() {
+    let mut app = preview_app(-6.0);
+    app.selected_slider = 0; // Pitch Shift
+    let buf = render_spectrum(&app);
+    assert_eq!(count_symbol(&buf, "•"), 0);
+    assert_eq!(count_symbol(&buf, "·"), 0);
+}
+
+#[test]
+fn test_percentile_nearest_rank() {
+    use voiceforge::dsp::spectrum::percentile;
+    let values: Vec<f32> = (0..=100).map(|i| -(i as f32)).collect();
+    assert_eq!(percentile(&values, 0.0), -100.0);
+    assert_eq!(percentile(&values, 1.0), 0.0);
+    assert_eq!(percentile(&values, 0.05), -95.0);
+    assert_eq!(percentile(&[], 0.5), -80.0);
+}
+
+#[test]
+fn test_auto_range_converges_on_quiet_signal() {
+    use voiceforge::dsp::spectrum::SpectrumAutoRange;
+    // A quiet recording: bins spread between -70 and -50 dB.
+    let bins: Vec<f32> = (0..1024)
+        .map(|i| -70.0 + 20.0 * i as f32 / 1023.0)
+        .collect();
+    let mut range = SpectrumAutoRange::new();
+    let (target_floor, target_ceiling) = {
+        range.update(&bins);
+        range.target()
+    };
+    assert!((target_floor - (-69.0 - SpectrumAutoRange::MARGIN_DB)).abs() < 0.5);
+    assert!((target_ceiling - (-51.0 + SpectrumAutoRange::MARGIN_DB)).abs() < 0.5);
+
+    // Slow adaptation: one frame moves only a little toward the target.
+    let (floor, ceiling) = range.range();
+    assert!(ceiling > -5.0, "adapted too fast: ceiling {ceiling}");
+    assert!(floor < -75.0, "adapted too fast: floor {floor}");
+
+    for _ in 0..300 {
+        range.update(&bins);
+    }
+    let (floor, ceiling) = range.range();
+    assert!(
+        (floor - target_floor).abs() < 0.5,
+        "floor {floor} vs {target_floor}"
+    );
+    assert!(
+        (ceiling - target_ceiling).abs() < 0.5,
+        "ceiling {ceiling} vs {target_ceiling}"
+    );
+}
+
+#[test]
+fn test_auto_range_respects_limits_and_min_span() {
+    use voiceforge::dsp::spectrum::SpectrumAutoRange;
+    let mut range = SpectrumAutoRange::new();
+    // Hot, flat signal: every bin at 0 dB.
+    for _ in 0..300 {
+        range.update(&vec![0.0; 1024]);
+    }
+    let (floor, ceiling) = range.range();
+    assert!(ceiling <= 0.0 && ceiling > -0.5, "ceiling {ceiling}");
+    assert!((ceiling - floor - SpectrumAutoRange::MIN_SPAN_DB).abs() < 0.5);
+
+    // Silence: everything at the -80 dB floor.
+    range.reset();
+    for _ in 0..300 {
+        range.update(&vec![-80.0; 1024]);
+    }
+    let (floor, ceiling) = range.range();
+    assert!((-80.0..-79.5).contains(&floor), "floor {floor}");
+    assert!(ceiling - floor >= SpectrumAutoRange::MIN_SPAN_DB - 0.5);
+}
+
+#[test]
+fn test_level_to_height_extremes() {
+    use voiceforge::dsp::spectrum::level_to_height;
+    assert_eq!(level_to_height(-80.0, -80.0, 0.0, 10.0), 0.0);
+    assert_eq!(level_to_height(0.0, -80.0, 0.0, 10.0), 10.0);
+    assert_eq!(level_to_height(-40.0, -80.0, 0.0, 10.0), 5.0);
+    // Outside the range clamps to the ends.
+    assert_eq!(level_to_height(-100.0, -60.0, -30.0, 8.0), 0.0);
+    assert_eq!(level_to_height(5.0, -60.0, -30.0, 8.0), 8.0);
+    // Degenerate range and non-finite input draw nothing.
+    assert_eq!(level_to_height(-10.0, -20.0, -20.0, 8.0), 0.0);
+    assert_eq!(level_to_height(f32::NAN, -80.0, 0.0, 8.0), 0.0);
+}
+
+fn two_tone(f1: f64, a1: f64, f2: f64, a2: f64, sr: f64, len: usize) -> Vec<f32> {
+    (0..len)
+        .map(|i| {
+            let t = i as f64 / sr;
+            (a1 * (2.0 * std::f64::consts::PI * f1 * t).sin()
+                + a2 * (2.0 * std::f64::consts::PI * f2 * t).sin()) as f32
+        })
+        .collect()
+}
+
+#[test]
+fn test_find_peaks_two_tones_sub_bin_accuracy() {
+    use voiceforge::dsp::spectrum::{compute_spectrum_dbfs, find_peaks, INSPECT_FFT_SIZE};
+    assert_eq!(INSPECT_FFT_SIZE, 8192);
+    // Off-bin frequencies (bin width ≈ 5.38 Hz) so interpolation has work to do.
+    for (f1, f2) in [(440.0, 3001.7), (123.4, 987.6), (1502.3, 7777.7)] {
+        let samples = two_tone(f1, 0.5, f2, 0.25, 44100.0, INSPECT_FFT_SIZE);
+        let bins = compute_spectrum_dbfs(&samples, INSPECT_FFT_SIZE);
+        let peaks = find_peaks(&bins, INSPECT_FFT_SIZE, 44100, 3);
+        assert_eq!(peaks.len(), 3);
+        // Loudest first: the 0.5 tone, then the 0.25 tone.
+        assert!(
+            (peaks[0].freq_hz as f64 - f1).abs() < 1.0,
+            "{f1}: got {:?}",
+            peaks[0]
+        );
+        assert!(
+            (peaks[1].freq_hz as f64 - f2).abs() < 1.0,
+            "{f2}: got {:?}",
+            peaks[1]
+        );
+        assert!(
+            (peaks[0].level_db + 6.02).abs() < 1.0,
+            "level {:?}",
+            peaks[0]
+        );
+        assert!(
+            (peaks[1].level_db + 12.04).abs() < 1.0,
+            "level {:?}",
+            peaks[1]
+        );
+        // Anything else is window leakage, far below both tones.
+        assert!(peaks[2].level_db < peaks[1].level_db - 20.0);
+    }
+}
+
+#[test]
+fn test_find_peaks_silence_and_degenerate_input() {
+    use voiceforge::dsp::spectrum::{compute_spectrum_dbfs, find_peaks};
+    let bins = compute_spectrum_dbfs(&[0.0; 1024], 1024);
+    assert!(find_peaks(&bins, 1024, 44100, 3).is_empty());
+    assert!(find_peaks(&[-10.0, -5.0], 4, 44100, 3).is_empty());
+    assert!(find_peaks(&[], 0, 44100, 3).is_empty());
+}
+
+#[test]
+fn test_inspect_view_renders_peak_readouts() {
+    use voiceforge::dsp::spectrum::SpectralPeak;
+    let mut app = voiceforge::app::AppState::new();
+    app.spectrum_inspect = true;
+    app.spectrum_bins = vec![-40.0; 4096];
+    app.spectrum_peaks = vec![SpectralPeak {
+        freq_hz: 440.2,
+        level_db: -6.0,
+    }];
+    let buf = render_spectrum(&app);
+    let text: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(text.contains("440.2 Hz -6.0 dBFS"), "no readout in: {text}");
+    assert!(text.contains("inspect"));
+
+    // Playing: the live view, no readouts.
+    app.playback
+        .playing
+        .store(true, std::sync::atomic::Ordering::Release);
+    let buf = render_spectrum(&app);
+    let text: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(!text.contains("dBFS"));
+}
+
+#[test]
+fn test_freq_to_column_inverts_column_to_bin() {
+    let (sr, fft) = (44100, 2048);
+    for num_cols in [40, 80, 157, 300] {
+        for col in 0..num_cols {
+            let bin = column_to_bin(col, num_cols, fft / 2);
+            let hz = bin * sr as f64 / fft as f64;
+            assert_eq!(
+                freq_to_column(hz, sr, fft, num_cols),
+                col,
+                "width {num_cols}"
+            );
+        }
+        // DC and Nyquist pin to the panel edges; beyond clamps.
+        assert_eq!(freq_to_column(0.0, sr, fft, num_cols), 0);
+        assert_eq!(freq_to_column(30000.0, sr, fft, num_cols), num_cols - 1);
+    }
+}
+
+#[test]
+fn test_eq_band_span_follows_q() {
+    // 1 kHz peaking band, Q 1.41: about 706 Hz .. 1416 Hz (one octave, centred geometrically).
+    let (lo, hi) = eq_band_span_hz(EqLayout::Bands12, EQ_PEAK_Q, 5).unwrap();
+    assert!(
+        (lo - 706.0).abs() < 2.0 && (hi - 1416.0).abs() < 2.0,
+        "{lo}..{hi}"
+    );
+    assert!(((lo * hi).sqrt() - 1000.0).abs() < 1.0);
+    assert!((hi - lo - 1000.0 / EQ_PEAK_Q).abs() < 1.0);
+    // Shelves extend to the ends of the spectrum.
+    assert_eq!(
+        eq_band_span_hz(EqLayout::Bands12, EQ_PEAK_Q, 0).unwrap().0,
+        0.0
+    );
+    assert_eq!(
+        eq_band_span_hz(EqLayout::Bands12, EQ_PEAK_Q, 11).unwrap().1,
+        f32::INFINITY
+    );
+    assert_eq!(eq_band_span_hz(EqLayout::Bands12, EQ_PEAK_Q, 12), None);
+}
+
+#[test]
+fn test_eq_band_columns_at_several_widths() {
+    let (sr, fft) = (44100, 2048);
+    for num_cols in [60, 120, 200] {
+        let expect = |hz: f64| freq_to_column(hz, sr, fft, num_cols);
+        // 1 kHz band: its -3 dB edges mapped through the axis.
+        let (lo, hi) = eq_band_columns(EqLayout::Bands12, EQ_PEAK_Q, 5, sr, fft, num_cols).unwrap();
+        assert_eq!(
+            (lo, hi),
+            (expect(706.2), expect(1415.9)),
+            "width {num_cols}"
+        );
+        assert!(lo < expect(1000.0) && expect(1000.0) < hi);
+        // High shelf runs to the right edge, low shelf from the left.
+        assert_eq!(
+            eq_band_columns(EqLayout::Bands12, EQ_PEAK_Q, 11, sr, fft, num_cols)
+                .unwrap()
+                .1,
+            num_cols - 1
+        );
+        assert_eq!(
+            eq_band_columns(EqLayout::Bands12, EQ_PEAK_Q, 0, sr, fft, num_cols)
+                .unwrap()
+                .0,
+            0
+        );
+        // Higher bands sit further right.
+        let starts: Vec<usize> = (1..11)
+            .map(|b| {
+                eq_band_columns(EqLayout::Bands12, EQ_PEAK_Q, b, sr, fft, num_cols)
+                    .unwrap()
+                    .0
+            })
+            .collect();
+        assert!(starts.windows(2).all(|w| w[0] <= w[1]), "{starts:?}");
+    }
+    // Wider panels give the same band more columns.
+    let width = |cols| {
+        let (lo, hi) = eq_band_columns(EqLayout::Bands12, EQ_PEAK_Q, 8, sr, fft, cols).unwrap();
+        hi - lo
+    };
+    assert!(width(200) > width(60));
+    assert_eq!(
+        eq_band_columns(EqLayout::Bands12, EQ_PEAK_Q, 5, sr, fft, 0),
+        None
+    );
+}
+
+#[test]
+fn test_eq_band_span_narrows_with_q() {
+    let (lo, hi) = eq_band_span_hz(EqLayout::Bands12, 4.0, 5).unwrap();
+    assert!((hi - lo - 250.0).abs() < 1.0, "{lo}..{hi}");
+    // Shelves don't have a Q.
+    assert_eq!(
+        eq_band_span_hz(EqLayout::Bands12, 4.0, 0),
+        eq_band_span_hz(EqLayout::Bands12, EQ_PEAK_Q, 0)
+    );
+}
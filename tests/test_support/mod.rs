@@ -0,0 +1,28 @@
+// Shared fixtures for integration tests: signals come from
+// `voiceforge::dsp::generate`, and files are written to a temp dir only
+// where a test genuinely needs one (decoder tests).
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+use voiceforge::audio::decoder::AudioData;
+pub use voiceforge::dsp::generate::*;
+
+/// Write `audio` as 16-bit PCM WAV to `dir/name` and return the path.
+pub fn write_wav(dir: &Path, name: &str, audio: &AudioData) -> PathBuf {
+    let path = dir.join(name);
+    let spec = hound::WavSpec {
+        channels: audio.channels,
+        sample_rate: audio.sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(&path, spec).expect("failed to create WAV");
+    for &s in &audio.samples {
+        writer
+            .write_sample((s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+            .expect("failed to write sample");
+    }
+    writer.finalize().expect("failed to finalize WAV");
+    path
+}
@@ -0,0 +1,96 @@
+use voiceforge::app::AppState;
+use voiceforge::terminal::{self, FOCUSED_FRAME_INTERVAL, UNFOCUSED_FRAME_INTERVAL};
+
+#[test]
+fn test_frame_interval_follows_focus() {
+    assert_eq!(terminal::frame_interval(true), FOCUSED_FRAME_INTERVAL);
+    assert_eq!(terminal::frame_interval(false), UNFOCUSED_FRAME_INTERVAL);
+    // ~30 fps focused, ~2 fps in a background pane.
+    assert!(FOCUSED_FRAME_INTERVAL.as_millis() <= 40);
+    assert!(UNFOCUSED_FRAME_INTERVAL.as_millis() >= 400);
+
+    let mut app = AppState::new();
+    assert!(app.terminal_focused, "assume focus until told otherwise");
+    assert_eq!(app.frame_interval(), FOCUSED_FRAME_INTERVAL);
+    app.terminal_focused = false;
+    assert_eq!(app.frame_interval(), UNFOCUSED_FRAME_INTERVAL);
+}
+
+/// Set in the re-executed test binary to run the child half of the suspend test.
+#[cfg(target_os = "linux")]
+const SUSPEND_CHILD_ENV: &str = "VOICEFORGE_SUSPEND_CHILD";
+
+/// Process state letter from `/proc/<pid>/stat` ('T' = stopped).
+#[cfg(target_os = "linux")]
+fn process_state(pid: u32) -> Option<char> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // The command name is parenthesized and may contain spaces.
+    stat[stat.rfind(')')? + 1..].trim_start().chars().next()
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_suspend_restores_before_stopping_and_reenters_on_sigcont() {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+    use std::time::{Duration, Instant};
+
+    if std::env::var_os(SUSPEND_CHILD_ENV).is_some() {
+        // Child: the same sequence the app runs on Ctrl+Z, with markers
+        // standing in for the terminal mode switches.
+        terminal::suspend_with(
+            || {
+                println!("MARK left-tui");
+                std::io::stdout().flush()
+            },
+            || {
+                println!("MARK reentered-tui");
+                std::io::stdout().flush()
+            },
+        )
+        .expect("suspend failed");
+        return;
+    }
+
+    let mut child = Command::new(std::env::current_exe().unwrap())
+        .args([
+            "--exact",
+            "test_suspend_restores_before_stopping_and_reenters_on_sigcont",
+            "--nocapture",
+        ])
+        .env(SUSPEND_CHILD_ENV, "1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("failed to re-run the test binary");
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while process_state(child.id()) != Some('T') {
+        if Instant::now() > deadline {
+            let _ = child.kill();
+            panic!(
+                "child never stopped (state {:?})",
+                process_state(child.id())
+            );
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    let status = Command::new("kill")
+        .args(["-CONT", &child.id().to_string()])
+        .status()
+        .expect("failed to run kill");
+    assert!(status.success());
+
+    let output = child.wait_with_output().expect("child did not finish");
+    assert!(output.status.success(), "child failed: {:?}", output.status);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // libtest's "test ... " prefix shares a line with the first marker.
+    let left = stdout
+        .find("MARK left-tui")
+        .expect("terminal not restored before stopping");
+    let reentered = stdout
+        .find("MARK reentered-tui")
+        .expect("terminal not re-entered");
+    assert!(left < reentered, "wrong order:\n{stdout}");
+}
@@ -0,0 +1,78 @@
+use voiceforge::app::AppState;
+use voiceforge::ui::file_picker::render_input_line;
+use voiceforge::ui::text::{display_width, take_head, take_tail, truncate_middle};
+
+#[test]
+fn test_truncate_middle_fits_unchanged() {
+    assert_eq!(truncate_middle("song.wav", 20), "song.wav");
+    assert_eq!(truncate_middle("", 0), "");
+}
+
+#[test]
+fn test_truncate_middle_preserves_filename_tail() {
+    let path = "/home/user/projects/audio/recordings/take_03_final.wav";
+    let out = truncate_middle(path, 30);
+    assert!(
+        display_width(&out) <= 30,
+        "width {} > 30: {out}",
+        display_width(&out)
+    );
+    assert!(out.ends_with("/recordings/take_03_final.wav") || out.ends_with("take_03_final.wav"));
+    assert!(out.contains('…'));
+}
+
+#[test]
+fn test_truncate_middle_cjk_no_panic_and_width() {
+    // Each CJK character is 3 bytes and 2 columns wide.
+    let path = "音楽/録音/とても長いディレクトリ名/最終テイク.wav";
+    for max in 0..display_width(path) + 2 {
+        let out = truncate_middle(path, max);
+        assert!(
+            display_width(&out) <= max,
+            "max {max}: width {} for {out}",
+            display_width(&out)
+        );
+    }
+    let out = truncate_middle(path, 20);
+    assert!(out.ends_with("最終テイク.wav"), "tail lost: {out}");
+}
+
+#[test]
+fn test_truncate_middle_emoji_and_combining() {
+    // "é" as e + U+0301 combining acute, plus a multi-codepoint emoji.
+    let path = "/data/cafe\u{301}/👩‍🎤/re\u{301}sume\u{301}_take.wav";
+    for max in 0..display_width(path) + 2 {
+        let out = truncate_middle(path, max);
+        assert!(display_width(&out) <= max);
+        // A combining mark must never start the output after the ellipsis.
+        assert!(!out.contains("…\u{301}"), "split grapheme: {out:?}");
+    }
+}
+
+#[test]
+fn test_truncate_middle_name_longer_than_budget() {
+    let path = "/a/very_long_file_name_that_does_not_fit.wav";
+    let out = truncate_middle(path, 12);
+    assert_eq!(display_width(&out), 12);
+    assert!(out.starts_with('…'));
+    assert!(out.ends_with("fit.wav"));
+}
+
+#[test]
+fn test_take_head_tail_whole_graphemes() {
+    assert_eq!(take_head("日本語", 3), "日");
+    assert_eq!(take_tail("日本語", 3), "語");
+    assert_eq!(take_head("e\u{301}x", 1), "e\u{301}");
+    assert_eq!(take_tail("abc", 0), "");
+}
+
+#[test]
+fn test_render_input_line_multibyte_cursor() {
+    let mut app = AppState::new();
+    app.file_picker_input = "録音/テイク.wav".to_string();
+    app.input_cursor = app.file_picker_input.len();
+    for width in 0..30 {
+        let (before, after) = render_input_line(&app, width);
+        assert!(display_width(&before) + display_width(&after) <= width.saturating_sub(4));
+    }
+}
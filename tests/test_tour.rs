@@ -0,0 +1,140 @@
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use tempfile::TempDir;
+use voiceforge::app::{Action, AppMode, AppState, TourTarget, TOUR_STEPS};
+use voiceforge::input::handler::handle_key_event;
+use voiceforge::session;
+use voiceforge::ui::layout::panel_rects;
+use voiceforge::ui::tour;
+
+fn press(app: &mut AppState, code: KeyCode) -> Option<Action> {
+    handle_key_event(KeyEvent::new(code, KeyModifiers::NONE), app)
+}
+
+#[test]
+fn test_tour_visits_each_panel_once_in_order() {
+    assert!((4..=6).contains(&TOUR_STEPS.len()));
+    let targets: Vec<TourTarget> = TOUR_STEPS.iter().map(|s| s.target).collect();
+    for (i, t) in targets.iter().enumerate() {
+        assert!(!targets[i + 1..].contains(t), "{t:?} appears twice");
+    }
+
+    let mut app = AppState::new();
+    app.start_tour();
+    assert_eq!(app.mode, AppMode::Tour);
+    // Any key advances; keys don't reach the panels underneath.
+    let keys = [
+        KeyCode::Char('q'),
+        KeyCode::Char(' '),
+        KeyCode::Tab,
+        KeyCode::Up,
+        KeyCode::Enter,
+    ];
+    for (i, key) in keys.into_iter().enumerate() {
+        assert_eq!(
+            app.current_tour_step().unwrap().target,
+            TOUR_STEPS[i].target
+        );
+        assert!(
+            press(&mut app, key).is_none(),
+            "step {i} ended the tour early"
+        );
+    }
+    assert!(!app.should_quit);
+    assert!(!app
+        .playback
+        .playing
+        .load(std::sync::atomic::Ordering::Relaxed));
+    assert_eq!(
+        app.current_tour_step().unwrap().target,
+        TourTarget::Transport
+    );
+
+    assert!(matches!(
+        press(&mut app, KeyCode::Char('x')),
+        Some(Action::TourFinished)
+    ));
+    assert_eq!(app.mode, AppMode::Normal);
+    assert!(app.current_tour_step().is_none());
+}
+
+#[test]
+fn test_tour_escape_skips_remaining_steps() {
+    let mut app = AppState::new();
+    app.start_tour();
+    press(&mut app, KeyCode::Right);
+    assert_eq!(app.tour_step, Some(1));
+    assert!(matches!(
+        press(&mut app, KeyCode::Esc),
+        Some(Action::TourFinished)
+    ));
+    assert_eq!(app.mode, AppMode::Normal);
+    assert_eq!(app.tour_step, None);
+    // Esc was consumed by the tour, not treated as quit.
+    assert!(!app.should_quit);
+}
+
+#[test]
+fn test_question_mark_twice_replays_tour() {
+    let mut app = AppState::new();
+    press(&mut app, KeyCode::Char('?'));
+    assert_eq!(app.mode, AppMode::Help);
+    press(&mut app, KeyCode::Char('?'));
+    assert_eq!(app.mode, AppMode::Tour);
+    assert_eq!(app.tour_step, Some(0));
+
+    // Other keys still just close help.
+    app.end_tour();
+    press(&mut app, KeyCode::Char('?'));
+    press(&mut app, KeyCode::Char('x'));
+    assert_eq!(app.mode, AppMode::Normal);
+}
+
+#[test]
+fn test_tour_marker_persists() {
+    let dir = TempDir::new().expect("failed to create temp dir");
+    assert!(session::is_first_run(dir.path()));
+
+    session::write_tour_marker(dir.path()).expect("failed to write marker");
+    assert!(dir.path().join(session::TOUR_MARKER_FILE).exists());
+    assert!(!session::is_first_run(dir.path()));
+
+    // Users with history from before the tour existed are not first-run.
+    let returning = TempDir::new().expect("failed to create temp dir");
+    session::append_history_line(&returning.path().join(session::HISTORY_FILE), "x")
+        .expect("failed to write history");
+    assert!(!session::is_first_run(returning.path()));
+}
+
+#[test]
+fn test_tour_dims_everything_but_target_panel() {
+    let area = Rect::new(0, 0, 100, 40);
+    let rects = panel_rects(area).expect("large enough");
+    assert!(panel_rects(Rect::new(0, 0, 39, 40)).is_none());
+
+    for step in &TOUR_STEPS {
+        let target = tour::target_rect(&rects, step.target);
+        assert!(target.area() > 0 && area.contains(target.as_position()));
+
+        let mut buf = Buffer::empty(area);
+        for cell in buf.content.iter_mut() {
+            cell.set_fg(Color::Cyan);
+        }
+        tour::dim_outside(&mut buf, target);
+        let inside = buf.cell((target.x, target.y)).unwrap().fg;
+        assert_eq!(inside, Color::Cyan, "{:?} target was dimmed", step.target);
+        let outside = if target.y > 0 {
+            (0, 0)
+        } else {
+            (0, area.bottom() - 1)
+        };
+        assert_ne!(
+            buf.cell(outside).unwrap().fg,
+            Color::Cyan,
+            "{:?}",
+            step.target
+        );
+    }
+}
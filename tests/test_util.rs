@@ -0,0 +1,61 @@
+use voiceforge::util::{parse_timestamp, SeekTarget};
+
+fn secs(input: &str) -> f64 {
+    match parse_timestamp(input) {
+        Ok(SeekTarget::Secs(s)) => s,
+        other => panic!("{input:?} parsed as {other:?}"),
+    }
+}
+
+#[test]
+fn test_parse_plain_seconds() {
+    assert_eq!(secs("0"), 0.0);
+    assert_eq!(secs("95"), 95.0);
+    assert_eq!(secs("12.5"), 12.5);
+    assert_eq!(secs(".5"), 0.5);
+    assert_eq!(secs("  42 "), 42.0);
+}
+
+#[test]
+fn test_parse_minutes_seconds() {
+    assert_eq!(secs("14:32"), 14.0 * 60.0 + 32.0);
+    assert_eq!(secs("0:05"), 5.0);
+    assert_eq!(secs("1:02.25"), 62.25);
+    // Leading field is unbounded: 90 minutes is fine.
+    assert_eq!(secs("90:00"), 5400.0);
+}
+
+#[test]
+fn test_parse_hours_minutes_seconds() {
+    assert_eq!(secs("1:02:03"), 3723.0);
+    assert_eq!(secs("0:00:59.5"), 59.5);
+    assert_eq!(secs("10:59:59"), 10.0 * 3600.0 + 59.0 * 60.0 + 59.0);
+}
+
+#[test]
+fn test_parse_percentage() {
+    assert_eq!(parse_timestamp("37%"), Ok(SeekTarget::Percent(37.0)));
+    assert_eq!(parse_timestamp("12.5 %"), Ok(SeekTarget::Percent(12.5)));
+    assert_eq!(parse_timestamp("0%"), Ok(SeekTarget::Percent(0.0)));
+}
+
+#[test]
+fn test_targets_clamp_to_duration() {
+    let duration = 120.0;
+    assert_eq!(SeekTarget::Secs(30.0).to_secs(duration), 30.0);
+    assert_eq!(SeekTarget::Secs(500.0).to_secs(duration), 120.0);
+    assert_eq!(SeekTarget::Percent(25.0).to_secs(duration), 30.0);
+    assert_eq!(SeekTarget::Percent(100.0).to_secs(duration), 120.0);
+    assert_eq!(SeekTarget::Percent(250.0).to_secs(duration), 120.0);
+    assert_eq!(SeekTarget::Secs(10.0).to_secs(0.0), 0.0);
+}
+
+#[test]
+fn test_parse_rejects_garbage() {
+    for bad in [
+        "", "   ", "abc", "1:2:3:4", "1:60", "1:60:00", "0:00:60", "-5", "+5", "1e3", "inf", "nan",
+        "1:", ":30", "1.5:30", "%", "abc%", "-10%", "1..2", ".", "12:3a",
+    ] {
+        assert!(parse_timestamp(bad).is_err(), "{bad:?} should be rejected");
+    }
+}
@@ -68,27 +68,7 @@ fn test_world_ffi_roundtrip() {
     );
 
     // Use peak cross-correlation to handle phase shifts
-    // Search for best alignment within one period
-    let period_samples = (sample_rate as f64 / freq) as usize;
-    let mut best_corr = f64::NEG_INFINITY;
-    let check_len = min_len - period_samples;
-    let input_energy: f64 = input[..check_len].iter().map(|x| x * x).sum();
-
-    for lag in 0..period_samples {
-        let cross: f64 = input[..check_len]
-            .iter()
-            .zip(output_slice[lag..lag + check_len].iter())
-            .map(|(a, b)| a * b)
-            .sum();
-        let out_energy: f64 = output_slice[lag..lag + check_len]
-            .iter()
-            .map(|x| x * x)
-            .sum();
-        let corr = cross / (input_energy.sqrt() * out_energy.sqrt() + 1e-10);
-        if corr > best_corr {
-            best_corr = corr;
-        }
-    }
+    let best_corr = peak_correlation(input, output_slice, (sample_rate as f64 / freq) as usize);
 
     assert!(
         best_corr > 0.7,
@@ -106,6 +86,28 @@ fn test_world_ffi_roundtrip() {
     );
 }
 
+/// Peak normalized cross-correlation of `output` against `input` over lags
+/// up to one period, so a phase shift doesn't count as a mismatch.
+fn peak_correlation(input: &[f64], output: &[f64], period_samples: usize) -> f64 {
+    let min_len = input.len().min(output.len());
+    let check_len = min_len - period_samples;
+    let input_energy: f64 = input[..check_len].iter().map(|x| x * x).sum();
+    let mut best_corr = f64::NEG_INFINITY;
+    for lag in 0..period_samples {
+        let cross: f64 = input[..check_len]
+            .iter()
+            .zip(output[lag..lag + check_len].iter())
+            .map(|(a, b)| a * b)
+            .sum();
+        let out_energy: f64 = output[lag..lag + check_len].iter().map(|x| x * x).sum();
+        let corr = cross / (input_energy.sqrt() * out_energy.sqrt() + 1e-10);
+        if corr > best_corr {
+            best_corr = corr;
+        }
+    }
+    best_corr
+}
+
 #[test]
 fn test_world_ffi_clone_params() {
     let audio: Vec<f64> = (0..4410)
@@ -117,6 +119,830 @@ fn test_world_ffi_clone_params() {
     assert_eq!(params.fft_size, cloned.fft_size);
 }
 
+/// One second of a harmonic tone at `freq` Hz, 16 kHz.
+fn harmonic_tone(freq: f64) -> Vec<f64> {
+    (0..16000)
+        .map(|i| {
+            let t = i as f64 / 16000.0;
+            (1..=4)
+                .map(|h| (2.0 * PI * freq * h as f64 * t).sin() / h as f64)
+                .sum::<f64>()
+                * 0.3
+        })
+        .collect()
+}
+
+fn mean_voiced_f0(params: &world_sys::WorldParams) -> f64 {
+    let voiced: Vec<f64> = params.f0.iter().copied().filter(|&f| f > 0.0).collect();
+    assert!(!voiced.is_empty(), "no voiced frames");
+    voiced.iter().sum::<f64>() / voiced.len() as f64
+}
+
+#[test]
+fn test_slice_selects_frames_and_rebases_positions() {
+    let params = world_sys::analyze(&harmonic_tone(200.0), 16000);
+    let slice = params.slice(0.2, 0.6).unwrap();
+    assert_eq!(slice.f0.len(), 81);
+    assert_eq!(slice.f0[..], params.f0[40..121]);
+    assert_eq!(slice.voicing[..], params.voicing[40..121]);
+    assert_eq!(slice.temporal_positions[0], 0.0);
+    assert!((slice.temporal_positions[80] - 0.4).abs() < 1e-9);
+    let out = world_sys::synthesize(&slice, 16000).unwrap();
+    assert!(
+        (out.len() as f64 - 0.4 * 16000.0).abs() <= 2.0,
+        "{} samples",
+        out.len()
+    );
+
+    // A range inside one frame hop still takes the frame on its edge.
+    assert_eq!(params.slice(0.5, 0.502).unwrap().f0.len(), 1);
+    for (start, end) in [(0.6, 0.2), (f64::NAN, 0.5), (5.0, 6.0), (0.501, 0.504)] {
+        assert!(
+            matches!(
+                params.slice(start, end),
+                Err(world_sys::WorldError::InvalidParams(_))
+            ),
+            "{start}..{end}",
+        );
+    }
+}
+
+#[test]
+fn test_concat_joins_two_takes_into_one_analysis() {
+    let first = world_sys::analyze(&harmonic_tone(200.0), 16000);
+    let second = world_sys::analyze(&harmonic_tone(300.0)[..8000], 16000);
+    let joined = first.concat(&second).unwrap();
+    let frames = first.f0.len();
+    assert_eq!(joined.f0.len(), frames + second.f0.len());
+    assert_eq!(joined.f0[..frames], first.f0[..]);
+    assert_eq!(joined.spectrogram[frames..], second.spectrogram[..]);
+    assert_eq!(joined.voicing.len(), joined.f0.len());
+    assert!(joined
+        .temporal_positions
+        .windows(2)
+        .all(|t| (t[1] - t[0] - 0.005).abs() < 1e-9));
+
+    // As long as both takes, give or take a frame each (DIO's last frame
+    // sits on the end of a take).
+    let out = world_sys::synthesize(&joined, 16000).unwrap();
+    let expected = 16000 + 8000;
+    assert!(
+        out.len().abs_diff(expected) <= 2 * 80,
+        "{} vs {expected}",
+        out.len()
+    );
+}
+
+#[test]
+fn test_concat_rejects_other_analysis_settings() {
+    let audio = harmonic_tone(200.0);
+    let default = world_sys::analyze(&audio, 16000);
+    let low_floor = world_sys::AnalyzeOptions {
+        f0_floor: 40.0,
+        ..Default::default()
+    };
+    let coarse = world_sys::AnalyzeOptions {
+        frame_period: 10.0,
+        ..Default::default()
+    };
+    for (options, rate) in [
+        (low_floor, 16000),
+        (coarse, 16000),
+        (Default::default(), 22050),
+    ] {
+        let other = world_sys::analyze_with_options(&audio, rate, &options).unwrap();
+        let err = default.concat(&other).unwrap_err();
+        assert!(
+            matches!(err, world_sys::WorldError::InvalidParams(ref msg) if msg.starts_with("can't join")),
+            "{err}"
+        );
+    }
+    let mut empty = default.clone();
+    empty.f0.clear();
+    assert!(default.concat(&empty).is_err());
+}
+
+/// Largest relative difference between two analyses' values, spectrogram
+/// bins floored as the morph floors them.
+fn max_relative_difference(a: &world_sys::WorldParams, b: &world_sys::WorldParams) -> f64 {
+    let floor = world_sys::SANITIZED_SPECTRUM_BIN;
+    let rows = |p: &world_sys::WorldParams| -> Vec<f64> {
+        let spectrogram = p.spectrogram.iter().flatten().map(|&v| v.max(floor));
+        p.f0.iter()
+            .copied()
+            .chain(spectrogram)
+            .chain(p.aperiodicity.iter().flatten().copied())
+            .collect()
+    };
+    rows(a)
+        .iter()
+        .zip(rows(b))
+        .map(|(&x, y)| (x - y).abs() / x.abs().max(y.abs()).max(1e-9))
+        .fold(0.0, f64::max)
+}
+
+#[test]
+fn test_morph_ends_reproduce_each_take_and_the_middle_blends() {
+    let low = world_sys::analyze(&harmonic_tone(200.0), 16000);
+    let high = world_sys::analyze(&harmonic_tone(300.0), 16000);
+    let at = |alpha| world_sys::morph(&low, &high, alpha).unwrap();
+    assert!(max_relative_difference(&at(0.0), &low) < 1e-9);
+    assert!(max_relative_difference(&at(1.0), &high) < 1e-9);
+
+    let middle = at(0.5);
+    let mean = mean_voiced_f0(&middle);
+    assert!(
+        mean > mean_voiced_f0(&low) && mean < mean_voiced_f0(&high),
+        "{mean}"
+    );
+    // Halfway in log Hz is the geometric mean.
+    assert!((mean - (200.0f64 * 300.0).sqrt()).abs() < 5.0, "{mean}");
+    let out = world_sys::synthesize(&middle, 16000).unwrap();
+    assert!(out.iter().all(|s| s.is_finite()));
+}
+
+#[test]
+fn test_morph_stretches_the_shorter_take() {
+    let long = world_sys::analyze(&harmonic_tone(200.0), 16000);
+    let short = world_sys::analyze(&harmonic_tone(300.0)[..8000], 16000);
+    let morphed = world_sys::morph(&short, &long, 0.0).unwrap();
+    assert_eq!(morphed.f0.len(), long.f0.len());
+    assert!(morphed
+        .temporal_positions
+        .windows(2)
+        .all(|t| (t[1] - t[0] - 0.005).abs() < 1e-9));
+    assert!((mean_voiced_f0(&morphed) - mean_voiced_f0(&short)).abs() < 1.0);
+    assert_eq!(
+        world_sys::morph(&long, &short, 1.0).unwrap().f0.len(),
+        long.f0.len()
+    );
+
+    let coarse = world_sys::AnalyzeOptions {
+        frame_period: 10.0,
+        ..Default::default()
+    };
+    let other = world_sys::analyze_with_options(&harmonic_tone(200.0), 16000, &coarse).unwrap();
+    let err = world_sys::morph(&long, &other, 0.5).unwrap_err();
+    assert!(
+        matches!(err, world_sys::WorldError::InvalidParams(ref msg) if msg.starts_with("can't morph")),
+        "{err}"
+    );
+    for alpha in [-0.1, 1.5, f64::NAN] {
+        assert!(world_sys::morph(&long, &short, alpha).is_err(), "{alpha}");
+    }
+}
+
+#[test]
+fn test_unrefined_f0_analysis_stays_close() {
+    let audio = harmonic_tone(440.0);
+    let unrefined = world_sys::AnalyzeOptions {
+        refine_f0: false,
+        ..Default::default()
+    };
+    let refined = world_sys::analyze(&audio, 16000);
+    let raw = world_sys::analyze_with_options(&audio, 16000, &unrefined).unwrap();
+    for params in [&refined, &raw] {
+        params.validate().unwrap();
+        assert!(
+            (mean_voiced_f0(params) - 440.0).abs() < 5.0,
+            "{}",
+            mean_voiced_f0(params)
+        );
+    }
+    assert_eq!(raw.f0.len(), refined.f0.len());
+    assert_ne!(raw.f0, refined.f0);
+    let (_, f0) = world_sys::analyze_f0_only(&audio, 16000, &unrefined).unwrap();
+    assert_eq!(f0, raw.f0);
+}
+
+#[test]
+fn test_f0_only_analysis_matches_the_full_contour() {
+    let audio = harmonic_tone(220.0);
+    let full = world_sys::analyze(&audio, 16000);
+    let (positions, f0) = world_sys::analyze_f0_only(&audio, 16000, &Default::default()).unwrap();
+    assert_eq!(positions, full.temporal_positions);
+    assert_eq!(f0.len(), full.f0.len());
+    for (i, (&fast, &slow)) in f0.iter().zip(&full.f0).enumerate() {
+        assert_eq!(fast > 0.0, slow > 0.0, "frame {i}: {fast} vs {slow}");
+        if slow > 0.0 {
+            let cents = 1200.0 * (fast / slow).log2();
+            assert!(cents.abs() < 5.0, "frame {i}: {cents:.1} cents");
+        }
+    }
+
+    let bad = world_sys::AnalyzeOptions {
+        dio_speed: 0,
+        ..Default::default()
+    };
+    assert!(world_sys::analyze_f0_only(&audio, 16000, &bad).is_err());
+}
+
+#[test]
+fn test_analyze_options_low_floor_tracks_low_voice() {
+    let options = world_sys::AnalyzeOptions {
+        f0_floor: 50.0,
+        ..Default::default()
+    };
+    let params = world_sys::analyze_with_options(&harmonic_tone(100.0), 16000, &options).unwrap();
+    let mean = mean_voiced_f0(&params);
+    assert!((mean - 100.0).abs() < 5.0, "mean f0 {mean:.1} Hz");
+    // The lower floor widens CheapTrick's window.
+    assert!(params.fft_size >= world_sys::analyze(&harmonic_tone(100.0), 16000).fft_size);
+}
+
+#[test]
+fn test_analyze_options_frame_period_reaches_params_and_synthesis() {
+    let audio = harmonic_tone(200.0);
+    let default = world_sys::analyze(&audio, 16000);
+    let options = world_sys::AnalyzeOptions {
+        frame_period: 10.0,
+        ..Default::default()
+    };
+    let coarse = world_sys::analyze_with_options(&audio, 16000, &options).unwrap();
+    assert_eq!(coarse.frame_period, 10.0);
+    assert_eq!(coarse.f0.len(), default.f0.len().div_ceil(2));
+    assert!((coarse.temporal_positions[1] - 0.010).abs() < 1e-12);
+    let out = world_sys::synthesize(&coarse, 16000).unwrap();
+    assert!(
+        out.len().abs_diff(audio.len()) <= 160,
+        "{} vs {}",
+        out.len(),
+        audio.len()
+    );
+}
+
+#[test]
+fn test_analyze_options_validation() {
+    let defaults = world_sys::AnalyzeOptions::default();
+    assert!(defaults.validate().is_ok());
+    assert!(defaults.f0_floor < defaults.f0_ceil);
+    let invalid = [
+        world_sys::AnalyzeOptions {
+            f0_floor: 0.0,
+            ..defaults
+        },
+        world_sys::AnalyzeOptions {
+            f0_floor: 500.0,
+            f0_ceil: 400.0,
+            ..defaults
+        },
+        world_sys::AnalyzeOptions {
+            f0_ceil: f64::NAN,
+            ..defaults
+        },
+        world_sys::AnalyzeOptions {
+            frame_period: 0.0,
+            ..defaults
+        },
+        world_sys::AnalyzeOptions {
+            frame_period: f64::INFINITY,
+            ..defaults
+        },
+        world_sys::AnalyzeOptions {
+            d4c_threshold: 1.5,
+            ..defaults
+        },
+        world_sys::AnalyzeOptions {
+            cheaptrick_q1: Some(-1.5),
+            ..defaults
+        },
+        world_sys::AnalyzeOptions {
+            cheaptrick_q1: Some(f64::NAN),
+            ..defaults
+        },
+        world_sys::AnalyzeOptions {
+            dio_speed: 0,
+            ..defaults
+        },
+        world_sys::AnalyzeOptions {
+            dio_speed: 13,
+            ..defaults
+        },
+    ];
+    for options in invalid {
+        let err = world_sys::analyze_with_options(&[0.0; 1600], 16000, &options).unwrap_err();
+        assert!(
+            matches!(err, world_sys::WorldError::InvalidParams(_)),
+            "{options:?}"
+        );
+    }
+}
+
+#[test]
+fn test_cheaptrick_q1_changes_the_envelope() {
+    let audio = harmonic_tone(200.0);
+    let envelope_shape = |cheaptrick_q1: Option<f64>| {
+        let options = world_sys::AnalyzeOptions {
+            cheaptrick_q1,
+            ..Default::default()
+        };
+        let params = world_sys::analyze_with_options(&audio, 16000, &options).unwrap();
+        // Per voiced frame, the share of energy in the top half of the
+        // spectrum, averaged.
+        let shares: Vec<f64> = params
+            .spectrogram
+            .iter()
+            .zip(&params.f0)
+            .filter(|(_, &f0)| f0 > 0.0)
+            .map(|(row, _)| {
+                let high: f64 = row[row.len() / 2..].iter().sum();
+                high / row.iter().sum::<f64>()
+            })
+            .collect();
+        assert!(!shares.is_empty());
+        shares.iter().sum::<f64>() / shares.len() as f64
+    };
+    let default = envelope_shape(None);
+    assert_eq!(
+        envelope_shape(Some(-0.15)),
+        default,
+        "None is WORLD's -0.15"
+    );
+    let weaker = envelope_shape(Some(-0.05));
+    assert!(
+        (weaker - default).abs() > 0.01 * default,
+        "high-band share: q1 -0.15 {default:.6}, -0.05 {weaker:.6}",
+    );
+}
+
+fn rms(samples: &[f64]) -> f64 {
+    (samples.iter().map(|s| s * s).sum::<f64>() / samples.len() as f64).sqrt()
+}
+
+#[test]
+fn test_coded_spectrogram_round_trip_synthesizes_close_to_raw() {
+    let params = world_sys::analyze(&harmonic_tone(200.0), 16000);
+    let coded = params.encode(16000, 60).unwrap();
+    assert_eq!(coded.coded_spectrogram.len(), params.f0.len());
+    assert!(coded.coded_spectrogram.iter().all(|row| row.len() == 60));
+    assert!(60 < params.fft_size / 2 + 1);
+    assert_eq!(coded.f0, params.f0);
+
+    let decoded = coded.decode().unwrap();
+    assert_eq!(decoded.fft_size, params.fft_size);
+    assert_eq!(decoded.frame_period, params.frame_period);
+    assert!(decoded
+        .spectrogram
+        .iter()
+        .flatten()
+        .all(|p| p.is_finite() && *p > 0.0));
+
+    // Compare the envelope alone: coded aperiodicity changes the noise
+    // excitation sample by sample (see the aperiodicity test).
+    let envelope_only = world_sys::WorldParams {
+        aperiodicity: params.aperiodicity.clone(),
+        ..decoded
+    };
+    let raw = world_sys::synthesize(&params, 16000).unwrap();
+    let round_trip = world_sys::synthesize(&envelope_only, 16000).unwrap();
+    assert_eq!(raw.len(), round_trip.len());
+    let error: Vec<f64> = raw.iter().zip(&round_trip).map(|(a, b)| a - b).collect();
+    let relative = rms(&error) / rms(&raw);
+    assert!(relative < 0.05, "relative RMS error {relative:.3}");
+}
+
+#[test]
+fn test_coded_aperiodicity_round_trip() {
+    // Noisy harmonics at 44.1 kHz, so aperiodicity varies across the bands.
+    let sr = 44100;
+    let audio: Vec<f64> = (0..sr as usize / 2)
+        .map(|i| {
+            let t = i as f64 / f64::from(sr);
+            let noise = ((i as f64 * 12.9898).sin() * 43758.5453).fract() - 0.5;
+            0.3 * (2.0 * PI * 220.0 * t).sin() + 0.1 * (2.0 * PI * 440.0 * t).sin() + 0.05 * noise
+        })
+        .collect();
+    let params = world_sys::analyze(&audio, sr);
+    assert_eq!(world_sys::aperiodicity_bands(sr), 5);
+    let coded = world_sys::code_aperiodicity(&params.aperiodicity, sr, params.fft_size).unwrap();
+    assert!(coded.iter().all(|row| row.len() == 5));
+    let decoded = world_sys::decode_aperiodicity(&coded, sr, params.fft_size).unwrap();
+    assert_eq!(decoded.len(), params.aperiodicity.len());
+
+    // Within a few dB on average below 15 kHz, where the bands are.
+    let top_bin = (15000.0 * params.fft_size as f64 / f64::from(sr)) as usize;
+    let mut total_db = 0.0;
+    let mut count = 0;
+    for (raw, dec) in params.aperiodicity.iter().zip(&decoded) {
+        assert!(dec.iter().all(|a| a.is_finite() && *a > 0.0 && *a <= 1.0));
+        for (a, b) in raw[1..top_bin].iter().zip(&dec[1..top_bin]) {
+            total_db += (20.0 * a.max(1e-12).log10() - 20.0 * b.log10()).abs();
+            count += 1;
+        }
+    }
+    let mean_db = total_db / count as f64;
+    assert!(mean_db < 6.0, "mean aperiodicity error {mean_db:.2} dB");
+
+    // Synthesis from fully coded params still resembles the input.
+    let round_trip = params.encode(sr, 60).unwrap().decode().unwrap();
+    let output = world_sys::synthesize(&round_trip, sr).unwrap();
+    let corr = peak_correlation(&audio, &output, (f64::from(sr) / 220.0) as usize);
+    assert!(corr > 0.7, "peak cross-correlation {corr:.3}");
+}
+
+#[test]
+fn test_aperiodicity_coding_needs_bands() {
+    assert_eq!(world_sys::aperiodicity_bands(16000), 1);
+    assert_eq!(world_sys::aperiodicity_bands(6000), 0);
+    assert_eq!(world_sys::aperiodicity_bands(0), 0);
+    let params = world_sys::analyze(&[0.0; 3000], 6000);
+    assert!(params.encode(6000, 40).is_err());
+    assert!(world_sys::code_aperiodicity(&params.aperiodicity, 6000, params.fft_size).is_err());
+    let narrow = vec![vec![0.5; 3]; 2];
+    assert!(world_sys::code_aperiodicity(&narrow, 16000, 1024).is_err());
+    assert!(world_sys::decode_aperiodicity(&narrow, 16000, 1024).is_err());
+}
+
+#[test]
+fn test_coding_silence_stays_finite() {
+    let params = world_sys::analyze(&[0.0; 8000], 16000);
+    let decoded = params.encode(16000, 40).unwrap().decode().unwrap();
+    assert!(decoded.spectrogram.iter().flatten().all(|p| p.is_finite()));
+    assert!(world_sys::synthesize(&decoded, 16000)
+        .unwrap()
+        .iter()
+        .all(|s| s.is_finite()));
+}
+
+#[test]
+fn test_coding_rejects_bad_dimensions_and_edited_rows() {
+    let params = world_sys::analyze(&harmonic_tone(200.0), 16000);
+    assert!(params.encode(16000, 0).is_err());
+    assert!(params.encode(16000, params.fft_size / 2 + 1).is_err());
+    assert!(params.encode(0, 40).is_err());
+
+    let mut coded = params.encode(16000, 40).unwrap();
+    coded.coded_spectrogram[3].pop();
+    assert!(coded.decode().is_err());
+    coded.coded_spectrogram.pop();
+    assert!(coded.decode().is_err());
+    let mut coded = params.encode(16000, 40).unwrap();
+    coded.coded_aperiodicity.pop();
+    assert!(coded.decode().is_err());
+}
+
+#[test]
+fn test_d4c_threshold_changes_aperiodicity_of_noise() {
+    // White noise over a quiet tone, so DIO still finds voiced frames for
+    // D4C to judge.
+    let noise = voiceforge::dsp::generate::white_noise(0.3, 16000, 1.0, 7);
+    let noise: Vec<f64> = noise
+        .samples
+        .iter()
+        .zip(harmonic_tone(200.0))
+        .map(|(&n, t)| f64::from(n) + 0.5 * t)
+        .collect();
+    let mean_aperiodicity = |d4c_threshold: f64| {
+        let options = world_sys::AnalyzeOptions {
+            d4c_threshold,
+            ..Default::default()
+        };
+        let params = world_sys::analyze_with_options(&noise, 16000, &options).unwrap();
+        let (sum, bins) = params
+            .aperiodicity
+            .iter()
+            .flatten()
+            .fold((0.0, 0usize), |(sum, bins), &v| (sum + v, bins + 1));
+        sum / bins as f64
+    };
+    // 0 keeps D4C's per-band estimate on every frame DIO calls voiced; the
+    // default treats noisy frames as fully aperiodic.
+    let kept = mean_aperiodicity(0.0);
+    let default = mean_aperiodicity(0.85);
+    assert!(
+        default > kept + 0.01,
+        "threshold 0: {kept:.4}, 0.85: {default:.4}"
+    );
+}
+
+#[test]
+fn test_voicing_confidence_separates_sine_from_noise() {
+    let mono = |audio: voiceforge::audio::decoder::AudioData| -> Vec<f64> {
+        audio.samples.iter().map(|&s| f64::from(s)).collect()
+    };
+    let sine = world_sys::analyze(
+        &mono(voiceforge::dsp::generate::sine(200.0, 0.5, 16000, 1.0)),
+        16000,
+    );
+    let noise = world_sys::analyze(
+        &mono(voiceforge::dsp::generate::white_noise(0.5, 16000, 1.0, 3)),
+        16000,
+    );
+    for params in [&sine, &noise] {
+        assert_eq!(params.voicing.len(), params.f0.len());
+        assert!(params.voicing.iter().all(|c| (0.0..=1.0).contains(c)));
+    }
+    assert!(sine.voiced_ratio() > 0.9, "sine: {}", sine.voiced_ratio());
+    assert!(
+        noise.voiced_ratio() < 0.1,
+        "noise: {}",
+        noise.voiced_ratio()
+    );
+
+    // Without a voicing track, every f0 > 0 frame counts as voiced.
+    let unknown = world_sys::WorldParams {
+        voicing: Vec::new(),
+        ..sine.clone()
+    };
+    let voiced = sine.f0.iter().filter(|&&f| f > 0.0).count();
+    assert_eq!(unknown.voiced_ratio(), voiced as f64 / sine.f0.len() as f64);
+    let short = world_sys::WorldParams {
+        voicing: vec![1.0; 3],
+        ..sine
+    };
+    assert!(short.validate().is_err());
+}
+
+#[test]
+fn test_analysis_progress_rises_through_every_stage() {
+    let audio: Vec<f64> = (0..10).flat_map(|_| harmonic_tone(150.0)).collect();
+    let mut reported = Vec::new();
+    world_sys::analyze_with_progress(
+        &audio,
+        16000,
+        &Default::default(),
+        &std::sync::atomic::AtomicBool::new(false),
+        |pct| reported.push(pct),
+    )
+    .unwrap();
+    assert!(reported.windows(2).all(|w| w[0] < w[1]), "{reported:?}");
+    assert_eq!(reported.last(), Some(&100));
+    for milestone in [25, 50, 75] {
+        assert!(reported.contains(&milestone), "{reported:?}");
+    }
+    // CheapTrick and D4C report inside their shares, not just at the end.
+    assert!(
+        reported.iter().filter(|&&p| p > 50 && p < 75).count() >= 10,
+        "{reported:?}"
+    );
+    assert!(
+        reported.iter().filter(|&&p| p > 75 && p < 100).count() >= 10,
+        "{reported:?}"
+    );
+}
+
+#[test]
+fn test_threaded_analysis_matches_sequential() {
+    let audio: Vec<f64> = (0..3).flat_map(|_| harmonic_tone(150.0)).collect();
+    let with_threads = |threads| {
+        let options = world_sys::AnalyzeOptions {
+            threads,
+            ..Default::default()
+        };
+        world_sys::analyze_with_options(&audio, 16000, &options).unwrap()
+    };
+    let sequential = with_threads(1);
+    for threads in [3, 8, 0] {
+        let parallel = with_threads(threads);
+        assert_eq!(parallel.f0, sequential.f0, "{threads} threads");
+        assert_eq!(
+            parallel.spectrogram, sequential.spectrogram,
+            "{threads} threads"
+        );
+        assert_eq!(
+            parallel.aperiodicity, sequential.aperiodicity,
+            "{threads} threads"
+        );
+    }
+}
+
+#[test]
+fn test_analysis_cancelled_between_stages() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let audio: Vec<f64> = (0..10).flat_map(|_| harmonic_tone(150.0)).collect();
+    let cancel = AtomicBool::new(false);
+    let mut stages = Vec::new();
+    let result =
+        world_sys::analyze_with_progress(&audio, 16000, &Default::default(), &cancel, |pct| {
+            stages.push(pct);
+            cancel.store(true, Ordering::Relaxed);
+        });
+    assert!(matches!(result, Err(world_sys::WorldError::Cancelled)));
+    assert_eq!(result.unwrap_err().to_string(), "analysis cancelled");
+    // Stopped right after DIO: StoneMask, CheapTrick and D4C never ran.
+    assert_eq!(stages, [25]);
+
+    // Set up front, nothing runs at all.
+    let result =
+        world_sys::analyze_with_progress(&audio, 16000, &Default::default(), &cancel, |_| {
+            panic!("no stage may run")
+        });
+    assert!(matches!(result, Err(world_sys::WorldError::Cancelled)));
+}
+
+#[test]
+fn test_synthesize_into_reuses_the_buffer() {
+    let params = world_sys::analyze(&harmonic_tone(200.0), 16000);
+    let expected = world_sys::synthesize(&params, 16000).unwrap();
+
+    let mut out = Vec::new();
+    assert_eq!(
+        world_sys::synthesize_into(&params, 16000, &mut out).unwrap(),
+        expected.len()
+    );
+    assert_eq!(out, expected);
+    let (capacity, ptr) = (out.capacity(), out.as_ptr());
+    // Stale samples from the first call must not leak into the second.
+    out.iter_mut().for_each(|s| *s = 1.0);
+    assert_eq!(
+        world_sys::synthesize_into(&params, 16000, &mut out).unwrap(),
+        expected.len()
+    );
+    assert_eq!(out, expected);
+    assert_eq!((out.capacity(), out.as_ptr()), (capacity, ptr));
+
+    // Shorter params reuse it too; a failed call leaves it alone.
+    let mut short = params.clone();
+    for rows in [&mut short.spectrogram, &mut short.aperiodicity] {
+        rows.truncate(100);
+    }
+    short.f0.truncate(100);
+    short.temporal_positions.truncate(100);
+    short.voicing.truncate(100);
+    let len = world_sys::synthesize_into(&short, 16000, &mut out).unwrap();
+    assert_eq!(out, world_sys::synthesize(&short, 16000).unwrap());
+    assert_eq!((out.len(), out.capacity()), (len, capacity));
+    short.f0.pop();
+    assert!(world_sys::synthesize_into(&short, 16000, &mut out).is_err());
+    assert_eq!(out.len(), len);
+}
+
+// --- Realtime synthesis ---
+
+#[test]
+fn test_realtime_synthesis_matches_offline_synthesis() {
+    let params = world_sys::analyze(&harmonic_tone(200.0), 16000);
+    let offline = world_sys::synthesize(&params, 16000).unwrap();
+    let streamed = world_sys::synthesize_realtime(&params, 16000).unwrap();
+    assert_eq!(streamed.len(), offline.len());
+    assert!(streamed.iter().all(|s| s.is_finite()));
+    let ratio = rms(&streamed) / rms(&offline);
+    assert!((0.8..1.25).contains(&ratio), "level ratio {ratio:.3}");
+    let corr = peak_correlation(&offline, &streamed, 80);
+    assert!(corr > 0.9, "correlation {corr:.3}");
+}
+
+#[test]
+fn test_realtime_synthesizer_blocks_and_backpressure() {
+    let params = world_sys::analyze(&harmonic_tone(200.0), 16000);
+    let frames = |range: std::ops::Range<usize>| {
+        (
+            params.f0[range.clone()].to_vec(),
+            params.spectrogram[range.clone()].to_vec(),
+            params.aperiodicity[range].to_vec(),
+        )
+    };
+    let mut synth =
+        world_sys::RealtimeSynthesizer::new(16000, params.frame_period, params.fft_size, 128, 4)
+            .unwrap();
+    assert_eq!(synth.buffer_size(), 128);
+    // Nothing queued, nothing to synthesize.
+    assert!(synth.synthesize_block().is_none());
+
+    // Four slots: the fifth chunk waits until blocks free one.
+    for chunk in 0..4 {
+        let (f0, sp, ap) = frames(chunk * 20..(chunk + 1) * 20);
+        assert!(
+            synth.add_parameters(&f0, &sp, &ap).unwrap(),
+            "chunk {chunk}"
+        );
+    }
+    let (f0, sp, ap) = frames(80..100);
+    assert!(!synth.add_parameters(&f0, &sp, &ap).unwrap());
+
+    let mut samples = 0;
+    while let Some(block) = synth.synthesize_block() {
+        assert_eq!(block.len(), 128);
+        assert!(block.iter().all(|s| s.is_finite()));
+        samples += block.len();
+    }
+    assert!(samples > 0);
+    assert!(synth.add_parameters(&f0, &sp, &ap).unwrap());
+    assert!(!synth.is_locked());
+
+    synth.refresh();
+    assert!(synth.synthesize_block().is_none());
+}
+
+#[test]
+fn test_realtime_synthesizer_rejects_bad_input() {
+    assert!(world_sys::RealtimeSynthesizer::new(0, 5.0, 1024, 128, 4).is_err());
+    assert!(world_sys::RealtimeSynthesizer::new(16000, 0.0, 1024, 128, 4).is_err());
+    assert!(world_sys::RealtimeSynthesizer::new(16000, 5.0, 1024, 0, 4).is_err());
+    assert!(world_sys::RealtimeSynthesizer::new(16000, 5.0, 1024, 128, 0).is_err());
+
+    let mut synth = world_sys::RealtimeSynthesizer::new(16000, 5.0, 1024, 128, 4).unwrap();
+    let rows = vec![vec![1e-6; 513]];
+    assert!(synth.add_parameters(&[], &[], &[]).is_err());
+    assert!(synth.add_parameters(&[100.0, 100.0], &rows, &rows).is_err());
+    assert!(synth
+        .add_parameters(&[100.0], &[vec![1e-6; 10]], &rows)
+        .is_err());
+}
+
+// --- Params files ---
+
+#[test]
+fn test_params_file_round_trip_synthesizes_identically() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("tone.vfw");
+    let params = world_sys::analyze(&harmonic_tone(200.0), 16000);
+    params.save_with_source(&path, 0xfeed).unwrap();
+    assert_eq!(world_sys::params_source_hash(&path).unwrap(), 0xfeed);
+
+    let loaded = world_sys::WorldParams::load(&path).unwrap();
+    assert_eq!(loaded.f0, params.f0);
+    assert_eq!(loaded.temporal_positions, params.temporal_positions);
+    assert_eq!(loaded.spectrogram, params.spectrogram);
+    assert_eq!(loaded.aperiodicity, params.aperiodicity);
+    assert!(!loaded.voicing.is_empty());
+    assert_eq!(loaded.voicing, params.voicing);
+    assert_eq!(
+        (loaded.fft_size, loaded.frame_period),
+        (params.fft_size, params.frame_period)
+    );
+    assert_eq!(loaded.sample_rate, 16000);
+    assert_eq!(
+        world_sys::synthesize(&loaded, 16000).unwrap(),
+        world_sys::synthesize(&params, 16000).unwrap(),
+    );
+
+    params.save(&path).unwrap();
+    assert_eq!(world_sys::params_source_hash(&path).unwrap(), 0);
+
+    // Params without a voicing track stay without one.
+    let unknown = world_sys::WorldParams {
+        voicing: Vec::new(),
+        ..params
+    };
+    unknown.save(&path).unwrap();
+    assert!(world_sys::WorldParams::load(&path)
+        .unwrap()
+        .voicing
+        .is_empty());
+}
+
+#[test]
+fn test_corrupted_params_file_is_a_clean_error() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("tone.vfw");
+    world_sys::analyze(&harmonic_tone(200.0), 16000)
+        .save(&path)
+        .unwrap();
+    let good = std::fs::read(&path).unwrap();
+
+    let mut bad_magic = good.clone();
+    bad_magic[0] = b'X';
+    let mut bad_version = good.clone();
+    bad_version[4] = 99;
+    let mut huge_count = good.clone();
+    huge_count[32..40].copy_from_slice(&u64::MAX.to_le_bytes());
+    let mut huge_fft = good.clone();
+    huge_fft[16..24].copy_from_slice(&(1u64 << 40).to_le_bytes());
+    let mut short_voicing = good.clone();
+    short_voicing[40..48].copy_from_slice(&1u64.to_le_bytes());
+    let mut no_rate = good.clone();
+    no_rate[48..56].copy_from_slice(&0u64.to_le_bytes());
+    let cases = [
+        ("truncated", good[..good.len() - 8].to_vec()),
+        ("header only", good[..20].to_vec()),
+        ("empty", Vec::new()),
+        ("bad magic", bad_magic),
+        ("bad version", bad_version),
+        ("huge frame count", huge_count),
+        ("huge fft_size", huge_fft),
+        ("short voicing", short_voicing),
+        ("no sample rate", no_rate),
+    ];
+    for (what, bytes) in cases {
+        std::fs::write(&path, bytes).unwrap();
+        let err = world_sys::WorldParams::load(&path).unwrap_err();
+        assert!(
+            matches!(err, world_sys::WorldError::File(_)),
+            "{what}: {err}"
+        );
+        assert!(world_sys::params_source_hash(&path).is_err(), "{what}");
+    }
+
+    let missing = dir.path().join("missing.vfw");
+    assert!(matches!(
+        world_sys::WorldParams::load(&missing),
+        Err(world_sys::WorldError::File(_))
+    ));
+}
+
+#[test]
+fn test_save_rejects_invalid_params() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut params = world_sys::analyze(&harmonic_tone(200.0), 16000);
+    params.spectrogram.pop();
+    assert!(matches!(
+        params.save(&dir.path().join("bad.vfw")),
+        Err(world_sys::WorldError::InvalidParams(_)),
+    ));
+}
+
 // --- Input validation ---
 
 #[test]
@@ -157,6 +983,9 @@ fn test_world_ffi_synthesize_empty_f0() {
         aperiodicity: vec![],
         fft_size: 1024,
         frame_period: 5.0,
+        sample_rate: 44100,
+        f0_candidates: None,
+        voicing: Vec::new(),
     };
     assert!(world_sys::synthesize(&params, 44100).is_err());
 }
@@ -170,6 +999,9 @@ fn test_world_ffi_synthesize_mismatched_spectrogram() {
         aperiodicity: vec![vec![0.0; 513]; 10],
         fft_size: 1024,
         frame_period: 5.0,
+        sample_rate: 44100,
+        f0_candidates: None,
+        voicing: Vec::new(),
     };
     assert!(world_sys::synthesize(&params, 44100).is_err());
 }
@@ -183,6 +1015,9 @@ fn test_world_ffi_synthesize_mismatched_aperiodicity() {
         aperiodicity: vec![vec![0.0; 513]; 3], // 3 rows, should be 10
         fft_size: 1024,
         frame_period: 5.0,
+        sample_rate: 44100,
+        f0_candidates: None,
+        voicing: Vec::new(),
     };
     assert!(world_sys::synthesize(&params, 44100).is_err());
 }
@@ -196,6 +1031,9 @@ fn test_world_ffi_synthesize_wrong_spectrogram_width() {
         aperiodicity: vec![vec![0.0; 513]; 10],
         fft_size: 1024,
         frame_period: 5.0,
+        sample_rate: 44100,
+        f0_candidates: None,
+        voicing: Vec::new(),
     };
     assert!(world_sys::synthesize(&params, 44100).is_err());
 }
@@ -209,6 +1047,335 @@ fn test_world_ffi_synthesize_mismatched_temporal_positions() {
         aperiodicity: vec![vec![0.0; 513]; 10],
         fft_size: 1024,
         frame_period: 5.0,
+        sample_rate: 44100,
+        f0_candidates: None,
+        voicing: Vec::new(),
     };
     assert!(world_sys::synthesize(&params, 44100).is_err());
 }
+
+#[test]
+fn test_world_ffi_synthesize_too_long_output() {
+    // Two frames a day apart: far past the limit, reported unclamped.
+    let params = world_sys::WorldParams {
+        f0: vec![0.0; 2],
+        temporal_positions: vec![0.0, 86_400.0],
+        spectrogram: vec![vec![1.0; 513]; 2],
+        aperiodicity: vec![vec![1.0; 513]; 2],
+        fft_size: 1024,
+        frame_period: 86_400_000.0,
+        sample_rate: 16000,
+        f0_candidates: None,
+        voicing: Vec::new(),
+    };
+    match world_sys::synthesize(&params, 16000) {
+        Err(world_sys::WorldError::AllocationTooLarge { requested, max }) => {
+            assert_eq!(requested, 86_400 * 16000 + 1);
+            assert!(max < requested);
+        }
+        other => panic!("expected AllocationTooLarge, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_non_finite_params_are_rejected_until_sanitized() {
+    let params = world_sys::analyze(&harmonic_tone(200.0), 16000);
+    let mut broken = Vec::new();
+    let mut nan_f0 = params.clone();
+    nan_f0.f0[2] = f64::NAN;
+    broken.push(nan_f0);
+    let mut nan_row = params.clone();
+    nan_row.spectrogram[3]
+        .iter_mut()
+        .for_each(|v| *v = f64::NAN);
+    broken.push(nan_row);
+    let mut inf_ap = params.clone();
+    inf_ap.aperiodicity[5][0] = f64::INFINITY;
+    broken.push(inf_ap);
+    let mut inf_position = params.clone();
+    inf_position.temporal_positions[1] = f64::NEG_INFINITY;
+    broken.push(inf_position);
+    for p in &broken {
+        assert!(p.validate().is_err());
+        assert!(world_sys::synthesize(p, 16000).is_err());
+        assert!(p.encode(16000, 40).is_err());
+    }
+
+    // Sanitizing repairs the analysis values (not positions) and counts them.
+    let width = params.fft_size / 2 + 1;
+    let mut all = params.clone();
+    all.f0[2] = f64::NAN;
+    all.spectrogram[3].iter_mut().for_each(|v| *v = f64::NAN);
+    all.aperiodicity[5][0] = f64::INFINITY;
+    all.voicing[7] = f32::NAN;
+    assert_eq!(all.sanitize(), 1 + width + 1 + 1);
+    assert_eq!(all.sanitize(), 0);
+    assert_eq!(all.f0[2], 0.0);
+    assert!(all.spectrogram[3]
+        .iter()
+        .all(|&v| v == world_sys::SANITIZED_SPECTRUM_BIN));
+    assert_eq!(all.aperiodicity[5][0], 1.0);
+    assert_eq!(all.voicing[7], 0.0);
+    let y = world_sys::synthesize(&all, 16000).unwrap();
+    assert!(y.iter().all(|s| s.is_finite()));
+}
+
+#[test]
+fn test_sanitize_analysis_tolerates_a_few_non_finite_values() {
+    use voiceforge::dsp::world;
+
+    let params = world_sys::analyze(&harmonic_tone(200.0), 16000);
+    let mut few = params.clone();
+    few.spectrogram[10][20] = f64::NAN;
+    few.aperiodicity[11][0] = f64::NAN;
+    assert_eq!(world::sanitize_analysis(&mut few).unwrap(), 2);
+    few.validate().unwrap();
+    let mut clean = params.clone();
+    assert_eq!(world::sanitize_analysis(&mut clean).unwrap(), 0);
+
+    // Past MAX_NON_FINITE_SHARE the analysis is rejected.
+    let mut many = params;
+    let rows = (many.f0.len() as f64 * world::MAX_NON_FINITE_SHARE).ceil() as usize + 1;
+    for frame in 0..rows {
+        many.spectrogram[frame].fill(f64::NAN);
+        many.aperiodicity[frame].fill(f64::NAN);
+    }
+    assert!(matches!(
+        world::sanitize_analysis(&mut many),
+        Err(world_sys::WorldError::InvalidParams(_)),
+    ));
+}
+
+/// Harmonic "voice" at `f0` with a few partials, `secs` long at 16 kHz.
+fn voiced_segment(f0: f64, brightness: f64, start: usize, len: usize, sr: f64) -> Vec<f32> {
+    (start..start + len)
+        .map(|i| {
+            let t = i as f64 / sr;
+            let f = f0 * (1.0 + 0.01 * (2.0 * PI * 5.0 * t).sin());
+            let phase = 2.0 * PI * f * t;
+            let s = phase.sin()
+                + brightness * (2.0 * phase).sin()
+                + 0.5 * brightness * (3.0 * phase).sin();
+            (0.3 * s) as f32
+        })
+        .collect()
+}
+
+#[test]
+fn test_reanalyze_region_splices_middle_third() {
+    use voiceforge::audio::decoder::AudioData;
+    use voiceforge::dsp::world;
+
+    let sr = 16000;
+    let third = sr as usize; // 1 s per third
+    let mut old = voiced_segment(180.0, 0.5, 0, 3 * third, sr as f64);
+    let original = world::analyze(&AudioData {
+        samples: old.clone(),
+        sample_rate: sr,
+        channels: 1,
+    })
+    .unwrap();
+
+    // Re-render the middle third: different pitch and timbre.
+    old.splice(
+        third..2 * third,
+        voiced_segment(140.0, 0.9, third, third, sr as f64),
+    );
+    let edited = AudioData {
+        samples: old,
+        sample_rate: sr,
+        channels: 1,
+    };
+    let mut incremental = original.clone();
+    world::reanalyze_region(&edited, &mut incremental, 1.0, 2.0, &Default::default()).unwrap();
+    let fresh = world::analyze(&edited).unwrap();
+
+    assert_eq!(incremental.f0.len(), original.f0.len());
+    let frame_secs = original.frame_period / 1000.0;
+    for frame in 0..incremental.f0.len() {
+        let t = frame as f64 * frame_secs;
+        if !(0.85..=2.15).contains(&t) {
+            // Outer thirds: bit-identical to the original analysis.
+            assert_eq!(
+                incremental.f0[frame].to_bits(),
+                original.f0[frame].to_bits()
+            );
+            assert_eq!(incremental.spectrogram[frame], original.spectrogram[frame]);
+            assert_eq!(
+                incremental.aperiodicity[frame],
+                original.aperiodicity[frame]
+            );
+        }
+        assert_eq!(
+            incremental.temporal_positions[frame].to_bits(),
+            fresh.temporal_positions[frame].to_bits()
+        );
+        if (1.05..=1.95).contains(&t) {
+            // Middle: matches a fresh full analysis of the edited audio.
+            assert!(
+                (incremental.f0[frame] - fresh.f0[frame]).abs() < 1.0,
+                "f0 at {t:.3}s: {} vs {}",
+                incremental.f0[frame],
+                fresh.f0[frame]
+            );
+            // CheapTrick adds a tiny random floor, so only compare bins
+            // within 60 dB of the frame's peak.
+            let peak = fresh.spectrogram[frame].iter().copied().fold(0.0, f64::max);
+            for (a, b) in incremental.spectrogram[frame]
+                .iter()
+                .zip(&fresh.spectrogram[frame])
+            {
+                if *b < peak * 1e-6 {
+                    continue;
+                }
+                let diff_db = 10.0 * (a.max(1e-20) / b.max(1e-20)).log10();
+                assert!(
+                    diff_db.abs() < 0.5,
+                    "spectrum at {t:.3}s off by {diff_db:.2} dB"
+                );
+            }
+        }
+    }
+    // The middle really was recomputed.
+    let mid = (1.5 / frame_secs) as usize;
+    assert_ne!(incremental.spectrogram[mid], original.spectrogram[mid]);
+}
+
+#[test]
+fn test_long_audio_is_analyzed_in_chunks_matching_a_single_pass() {
+    use std::sync::atomic::AtomicBool;
+    use voiceforge::audio::decoder::AudioData;
+    use voiceforge::dsp::world;
+
+    // Five minutes at 8 kHz, gliding in pitch so every chunk differs.
+    let sr = 8000;
+    let secs = 300;
+    let samples: Vec<f32> = (0..secs)
+        .flat_map(|s| voiced_segment(150.0 + (s % 7) as f64 * 10.0, 0.5, s * sr, sr, sr as f64))
+        .collect();
+    let audio = AudioData {
+        samples,
+        sample_rate: sr as u32,
+        channels: 1,
+    };
+    assert!(audio.duration_secs() > world::CHUNKED_ANALYSIS_SECS);
+    let mut progress = Vec::new();
+    let chunked = world::analyze_with_progress(
+        &audio,
+        &Default::default(),
+        &AtomicBool::new(false),
+        |pct| progress.push(pct),
+    )
+    .unwrap();
+    assert!(progress.windows(2).all(|w| w[0] < w[1]), "{progress:?}");
+    assert_eq!(progress.last(), Some(&100));
+
+    let mono: Vec<f64> = audio.samples.iter().map(|&s| f64::from(s)).collect();
+    let single = world_sys::analyze(&mono, sr as i32);
+    assert_eq!(chunked.f0.len(), single.f0.len());
+    assert_eq!(chunked.fft_size, single.fft_size);
+    assert_eq!(chunked.voicing.len(), single.f0.len());
+    assert!(chunked.f0_candidates.is_none());
+    assert_eq!(chunked.temporal_positions, single.temporal_positions);
+    let frame_secs = single.frame_period / 1000.0;
+
+    // Around every chunk boundary (and one chunk's middle as a control).
+    let boundaries = (1..secs / 30).map(|k| k as f64 * world::ANALYSIS_CHUNK_SECS);
+    for at in boundaries.chain([45.0]) {
+        let centre = (at / frame_secs).round() as usize;
+        for frame in centre - 20..centre + 20 {
+            let t = frame as f64 * frame_secs;
+            assert!(
+                (chunked.f0[frame] - single.f0[frame]).abs() < 1.0,
+                "f0 at {t:.3}s: {} vs {}",
+                chunked.f0[frame],
+                single.f0[frame],
+            );
+            // Bins within 60 dB of the frame's peak, as for region splices.
+            let peak = single.spectrogram[frame]
+                .iter()
+                .copied()
+                .fold(0.0, f64::max);
+            for (a, b) in chunked.spectrogram[frame]
+                .iter()
+                .zip(&single.spectrogram[frame])
+            {
+                if *b < peak * 1e-6 {
+                    continue;
+                }
+                let diff_db = 10.0 * (a.max(1e-20) / b.max(1e-20)).log10();
+                assert!(
+                    diff_db.abs() < 0.5,
+                    "spectrum at {t:.3}s off by {diff_db:.2} dB"
+                );
+            }
+            for (a, b) in chunked.aperiodicity[frame]
+                .iter()
+                .zip(&single.aperiodicity[frame])
+            {
+                assert!((a - b).abs() < 0.05, "aperiodicity at {t:.3}s: {a} vs {b}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_chunked_analysis_rejects_bad_chunks() {
+    use std::sync::atomic::AtomicBool;
+    use voiceforge::audio::decoder::AudioData;
+    use voiceforge::dsp::world;
+
+    let audio = AudioData {
+        samples: voiced_segment(180.0, 0.5, 0, 16000, 16000.0),
+        sample_rate: 16000,
+        channels: 1,
+    };
+    let cancel = AtomicBool::new(false);
+    for (chunk, overlap) in [(0.0, 0.1), (f64::NAN, 0.1), (0.5, -0.1)] {
+        assert!(world::analyze_chunked(
+            &audio,
+            &Default::default(),
+            chunk,
+            overlap,
+            &cancel,
+            |_| {}
+        )
+        .is_err());
+    }
+    // Short chunks still cover every frame once, on the single-pass grid.
+    let chunked =
+        world::analyze_chunked(&audio, &Default::default(), 0.25, 0.3, &cancel, |_| {}).unwrap();
+    let single = world::analyze(&audio).unwrap();
+    assert_eq!(chunked.f0.len(), single.f0.len());
+    assert_eq!(chunked.temporal_positions, single.temporal_positions);
+    chunked.validate().unwrap();
+
+    cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+    assert!(matches!(
+        world::analyze_chunked(&audio, &Default::default(), 0.25, 0.3, &cancel, |_| {}),
+        Err(world_sys::WorldError::Cancelled),
+    ));
+}
+
+#[test]
+fn test_reanalyze_region_rejects_length_change() {
+    use voiceforge::audio::decoder::AudioData;
+    use voiceforge::dsp::world;
+
+    let audio = AudioData {
+        samples: voiced_segment(180.0, 0.5, 0, 16000, 16000.0),
+        sample_rate: 16000,
+        channels: 1,
+    };
+    let mut params = world::analyze(&audio).unwrap();
+    let before = params.clone();
+    let longer = AudioData {
+        samples: voiced_segment(180.0, 0.5, 0, 24000, 16000.0),
+        ..audio.clone()
+    };
+    assert!(world::reanalyze_region(&longer, &mut params, 0.2, 0.4, &Default::default()).is_err());
+    assert!(world::reanalyze_region(&audio, &mut params, 0.4, 0.2, &Default::default()).is_err());
+    // A rejected splice leaves the params untouched.
+    assert_eq!(params.f0, before.f0);
+    assert_eq!(params.spectrogram, before.spectrogram);
+}